@@ -141,3 +141,7 @@ pub mod trading {
 pub mod utils {
     pub use RustQuant_utils::*;
 }
+
+/// WebAssembly bindings.
+#[cfg(feature = "wasm")]
+pub mod wasm;
@@ -0,0 +1,268 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! WebAssembly bindings, enabled with the `wasm` feature.
+//!
+//! The functions here are exported via `wasm-bindgen` and never panic:
+//! invalid inputs are validated up front and reported to JavaScript
+//! as thrown `Error`s.
+//!
+//! ```js
+//! import { black_scholes_call, simulate_gbm } from "rustquant";
+//!
+//! const call = black_scholes_call(100.0, 100.0, 1.0, 0.05, 0.2);
+//! const path = simulate_gbm(100.0, 0.05, 0.2, 1.0, 252, 42n);
+//! ```
+
+use wasm_bindgen::prelude::*;
+use RustQuant_error::RustQuantError;
+use RustQuant_instruments::{
+    implied_volatility, BlackScholes73, GeneralisedBlackScholesMerton, TypeFlag,
+};
+use RustQuant_stochastics::{GeometricBrownianMotion, StochasticProcess};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// EXPORTED FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Black-Scholes (1973) price of a European call.
+///
+/// # Arguments:
+/// * `s` - Underlying price.
+/// * `k` - Strike price.
+/// * `t` - Time to expiry (in years).
+/// * `r` - Risk-free rate.
+/// * `v` - Volatility.
+#[wasm_bindgen]
+pub fn black_scholes_call(s: f64, k: f64, t: f64, r: f64, v: f64) -> Result<f64, JsError> {
+    black_scholes(s, k, t, r, v, TypeFlag::Call).map_err(to_js_error)
+}
+
+/// Black-Scholes (1973) price of a European put.
+///
+/// # Arguments:
+/// * `s` - Underlying price.
+/// * `k` - Strike price.
+/// * `t` - Time to expiry (in years).
+/// * `r` - Risk-free rate.
+/// * `v` - Volatility.
+#[wasm_bindgen]
+pub fn black_scholes_put(s: f64, k: f64, t: f64, r: f64, v: f64) -> Result<f64, JsError> {
+    black_scholes(s, k, t, r, v, TypeFlag::Put).map_err(to_js_error)
+}
+
+/// Black-Scholes implied volatility of a European option.
+///
+/// # Arguments:
+/// * `price` - Observed option price.
+/// * `s` - Underlying price.
+/// * `k` - Strike price.
+/// * `t` - Time to expiry (in years).
+/// * `r` - Risk-free rate.
+/// * `is_call` - `true` for a call, `false` for a put.
+#[wasm_bindgen]
+pub fn black_scholes_implied_volatility(
+    price: f64,
+    s: f64,
+    k: f64,
+    t: f64,
+    r: f64,
+    is_call: bool,
+) -> Result<f64, JsError> {
+    let flag = if is_call {
+        TypeFlag::Call
+    } else {
+        TypeFlag::Put
+    };
+
+    black_scholes_iv(price, s, k, t, r, flag).map_err(to_js_error)
+}
+
+/// Simulate a single Geometric Brownian Motion path with a fixed seed.
+///
+/// Returns the `n_steps + 1` path values on an even grid over `[0, t_n]`.
+///
+/// # Arguments:
+/// * `x_0` - Initial value.
+/// * `mu` - Drift.
+/// * `sigma` - Volatility.
+/// * `t_n` - Terminal time.
+/// * `n_steps` - Number of time steps.
+/// * `seed` - Seed for the random number generator.
+#[wasm_bindgen]
+pub fn simulate_gbm(
+    x_0: f64,
+    mu: f64,
+    sigma: f64,
+    t_n: f64,
+    n_steps: usize,
+    seed: u64,
+) -> Result<Vec<f64>, JsError> {
+    gbm_path(x_0, mu, sigma, t_n, n_steps, seed).map_err(to_js_error)
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+fn to_js_error(error: RustQuantError) -> JsError {
+    JsError::new(&error.to_string())
+}
+
+fn check_positive(name: &str, value: f64) -> Result<(), RustQuantError> {
+    if value.is_finite() && value > 0.0 {
+        Ok(())
+    } else {
+        Err(RustQuantError::InvalidArgument(format!(
+            "`{name}` must be positive and finite, got {value}."
+        )))
+    }
+}
+
+fn check_finite(name: &str, value: f64) -> Result<(), RustQuantError> {
+    if value.is_finite() {
+        Ok(())
+    } else {
+        Err(RustQuantError::InvalidArgument(format!(
+            "`{name}` must be finite, got {value}."
+        )))
+    }
+}
+
+fn black_scholes(
+    s: f64,
+    k: f64,
+    t: f64,
+    r: f64,
+    v: f64,
+    flag: TypeFlag,
+) -> Result<f64, RustQuantError> {
+    check_positive("s", s)?;
+    check_positive("k", k)?;
+    check_positive("t", t)?;
+    check_positive("v", v)?;
+    check_finite("r", r)?;
+
+    Ok(BlackScholes73::new(s, r, v).price(k, t, flag))
+}
+
+fn black_scholes_iv(
+    price: f64,
+    s: f64,
+    k: f64,
+    t: f64,
+    r: f64,
+    flag: TypeFlag,
+) -> Result<f64, RustQuantError> {
+    check_positive("price", price)?;
+    check_positive("s", s)?;
+    check_positive("k", k)?;
+    check_positive("t", t)?;
+    check_finite("r", r)?;
+
+    let iv = implied_volatility(price, s, k, t, r, flag);
+
+    if iv.is_finite() && iv > 0.0 {
+        Ok(iv)
+    } else {
+        Err(RustQuantError::ComputationError(format!(
+            "No implied volatility found for price {price}."
+        )))
+    }
+}
+
+fn gbm_path(
+    x_0: f64,
+    mu: f64,
+    sigma: f64,
+    t_n: f64,
+    n_steps: usize,
+    seed: u64,
+) -> Result<Vec<f64>, RustQuantError> {
+    check_positive("x_0", x_0)?;
+    check_positive("t_n", t_n)?;
+    check_finite("mu", mu)?;
+    check_finite("sigma", sigma)?;
+
+    if sigma < 0.0 {
+        return Err(RustQuantError::InvalidArgument(format!(
+            "`sigma` must be non-negative, got {sigma}."
+        )));
+    }
+    if n_steps == 0 {
+        return Err(RustQuantError::InvalidArgument(
+            "`n_steps` must be at least 1.".to_string(),
+        ));
+    }
+
+    let gbm = GeometricBrownianMotion::new(mu, sigma);
+    let output = gbm.seedable_euler_maruyama(x_0, 0.0, t_n, n_steps, 1, false, seed);
+
+    output
+        .paths
+        .into_iter()
+        .next()
+        .ok_or_else(|| RustQuantError::ComputationError("No path was simulated.".to_string()))
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_wasm {
+    use super::*;
+    use RustQuant_utils::{assert_approx_equal, RUSTQUANT_EPSILON};
+
+    #[test]
+    fn test_black_scholes_call() {
+        let call = black_scholes_call(100.0, 100.0, 1.0, 0.05, 0.2).unwrap();
+
+        assert_approx_equal!(call, 10.450_583_572_185_565, 1e-8);
+    }
+
+    #[test]
+    fn test_black_scholes_put_call_parity() {
+        let call = black_scholes_call(100.0, 110.0, 0.5, 0.03, 0.25).unwrap();
+        let put = black_scholes_put(100.0, 110.0, 0.5, 0.03, 0.25).unwrap();
+
+        assert_approx_equal!(
+            call - put,
+            100.0 - 110.0 * (-0.03_f64 * 0.5).exp(),
+            RUSTQUANT_EPSILON
+        );
+    }
+
+    #[test]
+    fn test_black_scholes_implied_volatility() {
+        let call = black_scholes_call(100.0, 100.0, 1.0, 0.05, 0.2).unwrap();
+        let iv = black_scholes_implied_volatility(call, 100.0, 100.0, 1.0, 0.05, true).unwrap();
+
+        assert_approx_equal!(iv, 0.2, 1e-8);
+    }
+
+    #[test]
+    fn test_seeded_gbm_simulation() {
+        let first = simulate_gbm(100.0, 0.05, 0.2, 1.0, 252, 42).unwrap();
+        let second = simulate_gbm(100.0, 0.05, 0.2, 1.0, 252, 42).unwrap();
+
+        assert_eq!(first.len(), 253);
+        assert_eq!(first, second);
+        assert!(first.iter().all(|x| x.is_finite()));
+    }
+
+    #[test]
+    fn test_invalid_inputs_return_errors() {
+        assert!(black_scholes(-1.0, 100.0, 1.0, 0.05, 0.2, TypeFlag::Call).is_err());
+        assert!(black_scholes(100.0, 100.0, 1.0, f64::NAN, 0.2, TypeFlag::Call).is_err());
+        assert!(black_scholes_iv(0.0, 100.0, 100.0, 1.0, 0.05, TypeFlag::Call).is_err());
+        assert!(gbm_path(100.0, 0.05, 0.2, 1.0, 0, 42).is_err());
+        assert!(gbm_path(100.0, 0.05, -0.2, 1.0, 10, 42).is_err());
+    }
+}
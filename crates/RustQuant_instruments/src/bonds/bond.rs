@@ -0,0 +1,219 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023-2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Fixed-coupon bonds, priced off a discount curve.
+//!
+//! Each period of the bond's schedule pays the coupon rate times the
+//! period's day count factor, and the face value is repaid at maturity.
+//! The dirty price is the present value of the cash flows after
+//! settlement; the clean price is the dirty price less accrued interest.
+
+use crate::term_structures::YieldTermStructure;
+use time::Date;
+use RustQuant_time::Schedule;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS & ENUMS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Bond paying a fixed coupon on each date of its schedule.
+#[derive(Clone, Debug)]
+pub struct FixedCouponBond {
+    /// The face value of the bond.
+    pub face_value: f64,
+
+    /// The annual coupon rate.
+    pub coupon_rate: f64,
+
+    /// The coupon periods, with the day count of the coupons.
+    pub schedule: Schedule,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl FixedCouponBond {
+    /// Create a new fixed-coupon bond.
+    pub fn new(face_value: f64, coupon_rate: f64, schedule: Schedule) -> Self {
+        Self {
+            face_value,
+            coupon_rate,
+            schedule,
+        }
+    }
+
+    /// The bond's cash flows, as payment dates and amounts.
+    /// The last one includes the repayment of the face value.
+    pub fn cash_flows(&self) -> Vec<(Date, f64)> {
+        let mut cash_flows: Vec<(Date, f64)> = self
+            .schedule
+            .dates
+            .iter()
+            .zip(&self.schedule.day_count_factors)
+            .map(|(&date, factor)| (date, self.face_value * self.coupon_rate * factor))
+            .collect();
+
+        if let Some((_, amount)) = cash_flows.last_mut() {
+            *amount += self.face_value;
+        }
+
+        cash_flows
+    }
+
+    /// Interest accrued in the current coupon period up to `settlement`.
+    /// It is zero on coupon dates and outside the schedule.
+    pub fn accrued_interest(&self, settlement: Date) -> f64 {
+        self.schedule
+            .periods()
+            .into_iter()
+            .find(|&(start, end)| start <= settlement && settlement < end)
+            .map_or(0.0, |(start, _)| {
+                let factor = self
+                    .schedule
+                    .day_counting_convention
+                    .day_count_factor(start, settlement);
+
+                self.face_value * self.coupon_rate * factor
+            })
+    }
+
+    /// Dirty price on `settlement`: the cash flows paid after settlement,
+    /// discounted on `curve` back to the settlement date.
+    pub fn dirty_price<C>(&self, curve: &C, settlement: Date) -> f64
+    where
+        C: YieldTermStructure,
+    {
+        let present_value: f64 = self
+            .cash_flows()
+            .into_iter()
+            .filter(|&(date, _)| date > settlement)
+            .map(|(date, amount)| amount * curve.discount_factor(date))
+            .sum();
+
+        present_value / curve.discount_factor(settlement)
+    }
+
+    /// Clean price on `settlement`: the dirty price less accrued interest.
+    pub fn clean_price<C>(&self, curve: &C, settlement: Date) -> f64
+    where
+        C: YieldTermStructure,
+    {
+        self.dirty_price(curve, settlement) - self.accrued_interest(settlement)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_bond {
+    use super::*;
+    use crate::InterpolatedDiscountCurve;
+    use time::macros::date;
+    use RustQuant_math::interpolation::LinearInterpolator;
+    use RustQuant_time::{DayCountConvention, Frequency, WeekendCalendar};
+    use RustQuant_utils::{assert_approx_equal, RUSTQUANT_EPSILON};
+
+    const ISSUE_DATE: Date = date!(2024 - 01 - 15);
+    const CURVE_DAY_COUNT: DayCountConvention = DayCountConvention::Actual_365_Fixed;
+
+    /// Two-year, 5% semiannual bond with 30/360 coupons.
+    fn bond() -> FixedCouponBond {
+        let schedule = Schedule::builder(
+            ISSUE_DATE,
+            date!(2026 - 01 - 15),
+            Frequency::SemiAnnually,
+            WeekendCalendar,
+        )
+        .with_day_counting_convention(DayCountConvention::Thirty_360_ISDA)
+        .build()
+        .unwrap();
+
+        FixedCouponBond::new(100.0, 0.05, schedule)
+    }
+
+    /// Flat curve with continuously-compounded zero rate `r`.
+    fn flat_curve(r: f64) -> InterpolatedDiscountCurve<LinearInterpolator<f64, f64>> {
+        let dates = [date!(2025 - 01 - 15), date!(2027 - 01 - 15)];
+        let dfs = dates.map(|d| f64::exp(-r * CURVE_DAY_COUNT.day_count_factor(ISSUE_DATE, d)));
+
+        InterpolatedDiscountCurve::log_linear(ISSUE_DATE, &dates, &dfs, CURVE_DAY_COUNT).unwrap()
+    }
+
+    #[test]
+    fn test_cash_flows() {
+        let cash_flows = bond().cash_flows();
+
+        assert_eq!(cash_flows.len(), 4);
+        assert_eq!(cash_flows[3].0, date!(2026 - 01 - 15));
+
+        for (_, amount) in &cash_flows[..3] {
+            assert_approx_equal!(*amount, 2.5, RUSTQUANT_EPSILON);
+        }
+        assert_approx_equal!(cash_flows[3].1, 102.5, RUSTQUANT_EPSILON);
+    }
+
+    #[test]
+    fn test_accrued_interest() {
+        let bond = bond();
+
+        // 90 days of 30/360 into the first period.
+        assert_approx_equal!(
+            bond.accrued_interest(date!(2024 - 04 - 15)),
+            1.25,
+            RUSTQUANT_EPSILON
+        );
+        assert_approx_equal!(
+            bond.accrued_interest(date!(2024 - 07 - 15)),
+            0.0,
+            RUSTQUANT_EPSILON
+        );
+        assert_approx_equal!(
+            bond.accrued_interest(date!(2026 - 02 - 15)),
+            0.0,
+            RUSTQUANT_EPSILON
+        );
+    }
+
+    #[test]
+    fn test_prices() {
+        let (bond, curve) = (bond(), flat_curve(0.04));
+        let settlement = date!(2024 - 04 - 15);
+
+        let df = |date| f64::exp(-0.04 * CURVE_DAY_COUNT.day_count_factor(settlement, date));
+        let dirty = 2.5 * df(date!(2024 - 07 - 15))
+            + 2.5 * df(date!(2025 - 01 - 15))
+            + 2.5 * df(date!(2025 - 07 - 15))
+            + 102.5 * df(date!(2026 - 01 - 15));
+
+        assert_approx_equal!(bond.dirty_price(&curve, settlement), dirty, 1e-10);
+        assert_approx_equal!(bond.clean_price(&curve, settlement), dirty - 1.25, 1e-10);
+    }
+
+    #[test]
+    fn test_prices_over_coupon_date() {
+        // With zero rates, the dirty price drops by the coupon on the coupon
+        // date, and the accrued interest resets.
+        let (bond, curve) = (bond(), flat_curve(0.0));
+
+        let before = date!(2024 - 07 - 14);
+        let on = date!(2024 - 07 - 15);
+
+        assert_approx_equal!(bond.dirty_price(&curve, before), 110.0, RUSTQUANT_EPSILON);
+        assert_approx_equal!(bond.dirty_price(&curve, on), 107.5, RUSTQUANT_EPSILON);
+        assert_approx_equal!(
+            bond.clean_price(&curve, before),
+            110.0 - 100.0 * 0.05 * 179.0 / 360.0,
+            RUSTQUANT_EPSILON
+        );
+        assert_approx_equal!(bond.clean_price(&curve, on), 107.5, RUSTQUANT_EPSILON);
+    }
+}
@@ -0,0 +1,270 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023-2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Floating-rate notes.
+//!
+//! Each period of the note's schedule pays the index rate for the period
+//! plus a spread, times the period's day count factor, and the face value
+//! is repaid at maturity. Index rates are projected as simple forward
+//! rates on a forward curve, and the cash flows are discounted on a
+//! (possibly different) discount curve. The current period's rate has
+//! usually been fixed already, and can be given instead.
+
+use crate::term_structures::YieldTermStructure;
+use time::Date;
+use RustQuant_time::Schedule;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS & ENUMS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Floating-rate note, paying an index rate plus a spread.
+#[derive(Clone, Debug)]
+pub struct FloatingRateNote {
+    /// The face value of the note.
+    pub face_value: f64,
+
+    /// The spread over the index rate.
+    pub spread: f64,
+
+    /// The coupon periods, with the day count of the coupons.
+    pub schedule: Schedule,
+
+    /// The index rate fixed for the current period, if known.
+    pub current_fixing: Option<f64>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl FloatingRateNote {
+    /// Create a new floating-rate note, with no current fixing.
+    pub fn new(face_value: f64, spread: f64, schedule: Schedule) -> Self {
+        Self {
+            face_value,
+            spread,
+            schedule,
+            current_fixing: None,
+        }
+    }
+
+    /// Set the index rate fixed for the current period.
+    pub fn with_current_fixing(mut self, fixing: f64) -> Self {
+        self.current_fixing = Some(fixing);
+        self
+    }
+
+    /// Coupon rate, including the spread, of the period from `start` to
+    /// `end`, as of `settlement`.
+    ///
+    /// The current fixing is used for the period containing `settlement`;
+    /// otherwise the index rate is projected on `forward_curve`.
+    fn coupon_rate<F>(&self, forward_curve: &F, settlement: Date, start: Date, end: Date) -> f64
+    where
+        F: YieldTermStructure,
+    {
+        let index = match self.current_fixing {
+            Some(fixing) if start <= settlement && settlement < end => fixing,
+            _ => forward_curve.forward_rate(start, end),
+        };
+
+        index + self.spread
+    }
+
+    /// The note's cash flows after `settlement`, as payment dates and
+    /// amounts. The last one includes the repayment of the face value.
+    pub fn cash_flows<F>(&self, forward_curve: &F, settlement: Date) -> Vec<(Date, f64)>
+    where
+        F: YieldTermStructure,
+    {
+        let last = self.schedule.dates.last().copied();
+
+        self.schedule
+            .periods()
+            .into_iter()
+            .zip(&self.schedule.day_count_factors)
+            .filter(|&((_, end), _)| end > settlement)
+            .map(|((start, end), factor)| {
+                let rate = self.coupon_rate(forward_curve, settlement, start, end);
+                let coupon = self.face_value * rate * factor;
+
+                match Some(end) == last {
+                    true => (end, coupon + self.face_value),
+                    false => (end, coupon),
+                }
+            })
+            .collect()
+    }
+
+    /// Interest accrued in the current coupon period up to `settlement`.
+    /// It is zero on coupon dates and outside the schedule.
+    pub fn accrued_interest<F>(&self, forward_curve: &F, settlement: Date) -> f64
+    where
+        F: YieldTermStructure,
+    {
+        self.schedule
+            .periods()
+            .into_iter()
+            .find(|&(start, end)| start <= settlement && settlement < end)
+            .map_or(0.0, |(start, end)| {
+                let rate = self.coupon_rate(forward_curve, settlement, start, end);
+                let factor = self
+                    .schedule
+                    .day_counting_convention
+                    .day_count_factor(start, settlement);
+
+                self.face_value * rate * factor
+            })
+    }
+
+    /// Dirty price on `settlement`: the cash flows paid after settlement,
+    /// projected on `forward_curve` and discounted on `discount_curve`
+    /// back to the settlement date.
+    pub fn dirty_price<F, D>(&self, forward_curve: &F, discount_curve: &D, settlement: Date) -> f64
+    where
+        F: YieldTermStructure,
+        D: YieldTermStructure,
+    {
+        let present_value: f64 = self
+            .cash_flows(forward_curve, settlement)
+            .into_iter()
+            .map(|(date, amount)| amount * discount_curve.discount_factor(date))
+            .sum();
+
+        present_value / discount_curve.discount_factor(settlement)
+    }
+
+    /// Clean price on `settlement`: the dirty price less accrued interest.
+    pub fn clean_price<F, D>(&self, forward_curve: &F, discount_curve: &D, settlement: Date) -> f64
+    where
+        F: YieldTermStructure,
+        D: YieldTermStructure,
+    {
+        self.dirty_price(forward_curve, discount_curve, settlement)
+            - self.accrued_interest(forward_curve, settlement)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_floating_rate_note {
+    use super::*;
+    use crate::InterpolatedDiscountCurve;
+    use time::macros::date;
+    use RustQuant_math::interpolation::LinearInterpolator;
+    use RustQuant_time::{DayCountConvention, Frequency, WeekendCalendar};
+    use RustQuant_utils::{assert_approx_equal, RUSTQUANT_EPSILON};
+
+    const ISSUE_DATE: Date = date!(2024 - 01 - 15);
+    const DAY_COUNT: DayCountConvention = DayCountConvention::Actual_365_Fixed;
+
+    /// Two-year quarterly note, accruing on the curves' day count.
+    fn note(spread: f64) -> FloatingRateNote {
+        let schedule = Schedule::builder(
+            ISSUE_DATE,
+            date!(2026 - 01 - 15),
+            Frequency::Quarterly,
+            WeekendCalendar,
+        )
+        .with_day_counting_convention(DAY_COUNT)
+        .build()
+        .unwrap();
+
+        FloatingRateNote::new(100.0, spread, schedule)
+    }
+
+    /// Flat curve with continuously-compounded zero rate `r`.
+    fn flat_curve(r: f64) -> InterpolatedDiscountCurve<LinearInterpolator<f64, f64>> {
+        let dates = [date!(2025 - 01 - 15), date!(2027 - 01 - 15)];
+        let dfs = dates.map(|d| f64::exp(-r * DAY_COUNT.day_count_factor(ISSUE_DATE, d)));
+
+        InterpolatedDiscountCurve::log_linear(ISSUE_DATE, &dates, &dfs, DAY_COUNT).unwrap()
+    }
+
+    #[test]
+    fn test_par_on_single_curve() {
+        // Projecting and discounting on the same curve, a note with no
+        // spread is worth par on its reset dates.
+        let (note, curve) = (note(0.0), flat_curve(0.04));
+
+        for settlement in [ISSUE_DATE, date!(2024 - 07 - 15)] {
+            assert_approx_equal!(note.dirty_price(&curve, &curve, settlement), 100.0, 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_spread_and_separate_discount_curve() {
+        let note = note(0.01);
+        let (forward_curve, discount_curve) = (flat_curve(0.05), flat_curve(0.03));
+
+        let expected: f64 = note
+            .schedule
+            .periods()
+            .into_iter()
+            .zip(&note.schedule.day_count_factors)
+            .map(|((start, end), factor)| {
+                let rate = forward_curve.forward_rate(start, end) + 0.01;
+                let principal = if end == date!(2026 - 01 - 15) {
+                    100.0
+                } else {
+                    0.0
+                };
+
+                (100.0 * rate * factor + principal) * discount_curve.discount_factor(end)
+            })
+            .sum();
+
+        assert_approx_equal!(
+            note.dirty_price(&forward_curve, &discount_curve, ISSUE_DATE),
+            expected,
+            1e-10
+        );
+
+        // Projecting above the discount rate prices the note above par.
+        assert!(note.clean_price(&forward_curve, &discount_curve, ISSUE_DATE) > 100.0);
+    }
+
+    #[test]
+    fn test_current_fixing() {
+        let curve = flat_curve(0.04);
+        let settlement = date!(2024 - 03 - 15);
+        let note = note(0.005).with_current_fixing(0.06);
+
+        let cash_flows = note.cash_flows(&curve, settlement);
+        let factor = note.schedule.day_count_factors[0];
+
+        assert_eq!(cash_flows.len(), 8);
+        assert_approx_equal!(cash_flows[0].1, 100.0 * 0.065 * factor, RUSTQUANT_EPSILON);
+
+        // Later coupons are still projected.
+        let (start, end) = note.schedule.periods()[1];
+        assert_approx_equal!(
+            cash_flows[1].1,
+            100.0 * (curve.forward_rate(start, end) + 0.005) * note.schedule.day_count_factors[1],
+            RUSTQUANT_EPSILON
+        );
+
+        let accrued = 100.0 * 0.065 * DAY_COUNT.day_count_factor(ISSUE_DATE, settlement);
+
+        assert_approx_equal!(
+            note.accrued_interest(&curve, settlement),
+            accrued,
+            RUSTQUANT_EPSILON
+        );
+        assert_approx_equal!(
+            note.clean_price(&curve, &curve, settlement),
+            note.dirty_price(&curve, &curve, settlement) - accrued,
+            RUSTQUANT_EPSILON
+        );
+    }
+}
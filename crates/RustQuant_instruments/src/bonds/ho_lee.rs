@@ -0,0 +1,125 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Ho-Lee model for zero-coupon bond prices.
+//!
+//! The risk-neutral short rate follows the process:
+//!
+//! dr(t) = θ(t)dt + σdW(t)
+//!
+//! - `θ(t)`: is the time-dependent drift.
+//! - `σ`: is the diffusion coefficient.
+//!
+//! All times are year fractions.
+
+use RustQuant_math::integrate;
+
+/// Struct containing the Ho-Lee model parameters.
+pub struct HoLee {
+    /// Instantaneous volatility of the short rate ($\sigma$).
+    pub sigma: f64,
+
+    /// Time-dependent drift of the short rate ($\theta(t)$).
+    pub theta: Box<dyn Fn(f64) -> f64 + Send + Sync>,
+}
+
+impl HoLee {
+    /// Create a new Ho-Lee model.
+    pub fn new<F>(sigma: f64, theta: F) -> Self
+    where
+        F: Fn(f64) -> f64 + 'static + Send + Sync,
+    {
+        Self {
+            sigma,
+            theta: Box::new(theta),
+        }
+    }
+
+    /// Price at time `t` of a zero-coupon bond paying one unit at `maturity`,
+    /// given the short rate `r_t` at time `t`:
+    ///
+    /// $$
+    /// P(t, T) = \exp\left( -r_t (T - t) - \int_t^T \theta(u) (T - u) du
+    ///     + \frac{\sigma^2 (T - t)^3}{6} \right)
+    /// $$
+    ///
+    /// # Panics
+    /// Panics if `maturity < t`.
+    pub fn zero_coupon_bond_price(&self, r_t: f64, t: f64, maturity: f64) -> f64 {
+        assert!(maturity >= t, "The bond must mature after time t.");
+
+        let tau = maturity - t;
+
+        if tau == 0.0 {
+            return 1.0;
+        }
+
+        let drift = integrate(|u| (self.theta)(u) * (maturity - u), t, maturity);
+
+        (-r_t * tau - drift + self.sigma * self.sigma * tau.powi(3) / 6.0).exp()
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_ho_lee {
+    use super::*;
+    use RustQuant_stochastics::{StochasticProcess, StochasticProcessConfig};
+    use RustQuant_utils::{assert_approx_equal, RUSTQUANT_EPSILON};
+
+    #[test]
+    fn test_ho_lee_constant_drift() {
+        let (r, theta, sigma, tau) = (0.03, 0.002, 0.01, 4.0_f64);
+
+        let model = HoLee::new(sigma, move |_| theta);
+
+        assert_approx_equal!(
+            model.zero_coupon_bond_price(r, 1.0, 1.0 + tau),
+            (-r * tau - 0.5 * theta * tau * tau + sigma * sigma * tau.powi(3) / 6.0).exp(),
+            1e-10
+        );
+        assert_approx_equal!(
+            model.zero_coupon_bond_price(r, 2.0, 2.0),
+            1.0,
+            RUSTQUANT_EPSILON
+        );
+    }
+
+    #[test]
+    fn test_ho_lee_matches_simulated_short_rate() {
+        let (r_0, sigma, maturity) = (0.02, 0.01, 3.0_f64);
+        let theta = |t: f64| 0.004 * (-0.5 * t).exp();
+
+        let model = HoLee::new(sigma, theta);
+        let analytic = model.zero_coupon_bond_price(r_0, 0.0, maturity);
+
+        let process = RustQuant_stochastics::HoLee::new(sigma, theta);
+        let n_steps = 300;
+        let n_paths = 5000;
+        let config = StochasticProcessConfig::new(r_0, 0.0, maturity, n_steps, n_paths, true);
+        let output = process.euler_maruyama(&config);
+
+        // Monte Carlo zero-coupon bond: E[exp(-integral of r)].
+        let dt = maturity / n_steps as f64;
+        let simulated = output
+            .paths
+            .iter()
+            .map(|path| {
+                let integral: f64 = path.windows(2).map(|w| 0.5 * (w[0] + w[1]) * dt).sum();
+                (-integral).exp()
+            })
+            .sum::<f64>()
+            / n_paths as f64;
+
+        assert_approx_equal!(simulated, analytic, 2e-3);
+    }
+}
@@ -6,3 +6,17 @@
 //      - LICENSE-APACHE.md
 //      - LICENSE-MIT.md
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Bonds and short-rate bond pricing models.
+
+/// Ho-Lee model for zero-coupon bond prices.
+pub mod ho_lee;
+pub use ho_lee::*;
+
+/// Fixed-coupon bonds priced off a discount curve.
+pub mod bond;
+pub use bond::*;
+
+/// Floating-rate notes, projected on a forward curve.
+pub mod floating_rate_note;
+pub use floating_rate_note::*;
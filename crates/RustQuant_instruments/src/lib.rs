@@ -56,6 +56,10 @@ pub use instrument::*;
 pub mod bonds;
 // pub use bonds::*;
 
+/// Interest rate term structures and curve bootstrapping.
+pub mod term_structures;
+pub use term_structures::*;
+
 /// Option pricers and sensitivity functions.
 pub mod options;
 pub use options::*;
@@ -68,6 +72,10 @@ pub use fx::*;
 pub mod equities;
 pub use equities::*;
 
+/// Implied volatility smiles and surfaces.
+pub mod volatility;
+pub use volatility::*;
+
 /// Ticker symbol.
 pub mod ticker;
 pub use ticker::*;
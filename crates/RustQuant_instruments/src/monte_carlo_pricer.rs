@@ -75,6 +75,9 @@ impl_monte_carlo_pricer!(crate::PowerContract, path_independent);
 impl_monte_carlo_pricer!(crate::PowerOption, path_independent);
 impl_monte_carlo_pricer!(crate::SupershareOption, path_independent);
 impl_monte_carlo_pricer!(crate::BarrierOption, path_dependent);
+impl_monte_carlo_pricer!(crate::DoubleBarrierOption, path_dependent);
+impl_monte_carlo_pricer!(crate::LookbackOption, path_dependent);
+impl_monte_carlo_pricer!(crate::LadderOption, path_dependent);
 impl_monte_carlo_pricer!(crate::CappedPowerOption, path_independent);
 impl_monte_carlo_pricer!(crate::PoweredOption, path_independent);
 impl_monte_carlo_pricer!(crate::LogMoneynessContract, path_independent);
@@ -10,8 +10,11 @@
 use derive_builder::Builder;
 
 use super::option_flags::*;
-use super::{AveragingMethod, OptionContract};
+use super::{
+    monte_carlo_with_control_variate, AveragingMethod, ControlVariateEstimate, OptionContract,
+};
 use crate::Payoff;
+use RustQuant_stochastics::GeometricBrownianMotion;
 
 /// Asian option.
 #[derive(Debug, Clone, Builder)]
@@ -71,3 +74,563 @@ impl Payoff for AsianOption {
         }
     }
 }
+
+impl AsianOption {
+    /// Turnbull-Wakeman (1991) approximation for a fixed strike,
+    /// arithmetic average Asian option.
+    ///
+    /// The first two moments of the (continuously sampled) arithmetic
+    /// average are matched to a lognormal distribution, which is then
+    /// priced with the generalised Black-Scholes-Merton formula.
+    /// The averaging period is assumed to run from today until expiry.
+    ///
+    /// # Arguments:
+    /// * `s` - Underlying price.
+    /// * `r` - Risk-free rate.
+    /// * `q` - Dividend yield.
+    /// * `v` - Volatility.
+    ///
+    /// # Panics
+    /// Panics if the option is not a fixed strike option.
+    pub fn price_turnbull_wakeman(&self, s: f64, r: f64, q: f64, v: f64) -> f64 {
+        let k = match (self.contract.strike_flag, self.strike) {
+            (Some(StrikeFlag::Fixed), Some(k)) => k,
+            _ => panic!("Turnbull-Wakeman requires a fixed strike."),
+        };
+
+        let t = self.contract.time_to_expiry();
+
+        turnbull_wakeman::price(s, k, t, r, r - q, v, self.contract.type_flag)
+    }
+
+    /// Curran (1994) approximation for a fixed strike, discretely sampled
+    /// arithmetic average Asian option.
+    ///
+    /// The price is computed exactly on the paths where the geometric
+    /// average exceeds a critical level (where the arithmetic average
+    /// then exceeds the strike), and approximately on the rest. It is
+    /// usually more accurate than moment matching, particularly at high
+    /// volatility.
+    ///
+    /// The average is taken over `n_fixings` equally spaced dates from
+    /// today until expiry (inclusive), like the average of a simulated path.
+    ///
+    /// # Arguments:
+    /// * `s` - Underlying price.
+    /// * `r` - Risk-free rate.
+    /// * `q` - Dividend yield.
+    /// * `v` - Volatility.
+    /// * `n_fixings` - Number of averaging dates.
+    ///
+    /// # Panics
+    /// Panics if the option is not a fixed strike option, or if there are
+    /// fewer than two fixings.
+    pub fn price_curran(&self, s: f64, r: f64, q: f64, v: f64, n_fixings: usize) -> f64 {
+        let k = match (self.contract.strike_flag, self.strike) {
+            (Some(StrikeFlag::Fixed), Some(k)) => k,
+            _ => panic!("Curran's approximation requires a fixed strike."),
+        };
+
+        assert!(n_fixings >= 2, "At least two fixings are needed.");
+
+        let t = self.contract.time_to_expiry();
+
+        curran::price(s, k, t, r, r - q, v, n_fixings, self.contract.type_flag)
+    }
+
+    /// Exact price of a fixed strike, geometric average Asian option.
+    ///
+    /// The geometric average of a lognormal underlying is itself
+    /// lognormal, so the option is priced by Black-Scholes with an
+    /// adjusted cost of carry and volatility. With `Some(n_fixings)` the
+    /// average is taken over that many equally spaced dates from today
+    /// until expiry (inclusive), like the average of a simulated path;
+    /// with `None` it is sampled continuously (Kemna and Vorst, 1990).
+    ///
+    /// # Arguments:
+    /// * `s` - Underlying price.
+    /// * `r` - Risk-free rate.
+    /// * `q` - Dividend yield.
+    /// * `v` - Volatility.
+    /// * `n_fixings` - Number of averaging dates, or `None` for continuous sampling.
+    ///
+    /// # Panics
+    /// Panics if the option is not a fixed strike option, or if there are
+    /// fewer than two fixings.
+    pub fn price_geometric(&self, s: f64, r: f64, q: f64, v: f64, n_fixings: Option<usize>) -> f64 {
+        let k = match (self.contract.strike_flag, self.strike) {
+            (Some(StrikeFlag::Fixed), Some(k)) => k,
+            _ => panic!("The geometric closed form requires a fixed strike."),
+        };
+
+        if let Some(n) = n_fixings {
+            assert!(n >= 2, "At least two fixings are needed.");
+        }
+
+        let t = self.contract.time_to_expiry();
+
+        geometric::price(s, k, t, r, r - q, v, n_fixings, self.contract.type_flag)
+    }
+
+    /// Monte Carlo price of a fixed strike, arithmetic average Asian
+    /// option under geometric Brownian motion, with the geometric average
+    /// option as a control variate.
+    ///
+    /// The two averages are almost perfectly correlated, and the control's
+    /// price is known exactly from [`AsianOption::price_geometric`], so the
+    /// adjusted estimate has a far smaller standard error than plain Monte
+    /// Carlo. The average is taken over the `n_steps + 1` points of each
+    /// path, including today.
+    ///
+    /// # Arguments:
+    /// * `s` - Underlying price.
+    /// * `r` - Risk-free rate.
+    /// * `q` - Dividend yield.
+    /// * `v` - Volatility.
+    /// * `n_paths` - Number of simulated paths.
+    /// * `n_steps` - Number of time steps per path.
+    ///
+    /// # Panics
+    /// Panics if the option is not a fixed strike, arithmetic average
+    /// option, or if the option has expired.
+    pub fn price_monte_carlo_geometric_control(
+        &self,
+        s: f64,
+        r: f64,
+        q: f64,
+        v: f64,
+        n_paths: usize,
+        n_steps: usize,
+    ) -> ControlVariateEstimate {
+        assert!(
+            matches!(self.averaging_method, AveragingMethod::ArithmeticDiscrete),
+            "The geometric control variate is for arithmetic averages."
+        );
+
+        let mut control = self.clone();
+        control.averaging_method = AveragingMethod::GeometricDiscrete;
+
+        let control_price = control.price_geometric(s, r, q, v, Some(n_steps + 1));
+        let t = self.contract.time_to_expiry();
+
+        monte_carlo_with_control_variate(
+            &GeometricBrownianMotion::new(r - q, v),
+            |path| self.payoff(path.to_vec()),
+            |path| control.payoff(path.to_vec()),
+            control_price,
+            s,
+            r,
+            t,
+            n_paths,
+            n_steps,
+        )
+    }
+}
+
+mod geometric {
+    use super::TypeFlag;
+    use crate::options::option_models::bsm;
+
+    /// Adjusted cost of carry and volatility of the geometric average,
+    /// with `n` equally spaced fixings (including the first and last
+    /// dates) or continuous sampling.
+    pub(crate) fn moments(b: f64, v: f64, n: Option<usize>) -> (f64, f64) {
+        let v2 = v * v;
+
+        // Variance of the log geometric average, per unit time.
+        let v2_g = match n {
+            Some(n) => {
+                let n = n as f64;
+                v2 * (2.0 * n - 1.0) / (6.0 * n)
+            }
+            None => v2 / 3.0,
+        };
+
+        // The log average has mean ln(s) + (b - v^2 / 2) t / 2.
+        let b_g = 0.5 * (b - 0.5 * v2) + 0.5 * v2_g;
+
+        (b_g, v2_g.sqrt())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn price(
+        s: f64,
+        k: f64,
+        t: f64,
+        r: f64,
+        b: f64,
+        v: f64,
+        n: Option<usize>,
+        flag: TypeFlag,
+    ) -> f64 {
+        let (b_g, v_g) = moments(b, v, n);
+
+        match flag {
+            TypeFlag::Call => bsm::call_price(s, k, t, r, b_g, v_g),
+            TypeFlag::Put => bsm::put_price(s, k, t, r, b_g, v_g),
+        }
+    }
+}
+
+mod turnbull_wakeman {
+    use super::TypeFlag;
+    use crate::options::option_models::bsm;
+
+    /// Adjusted cost of carry and volatility of the matched lognormal.
+    pub(crate) fn moments(t: f64, b: f64, v: f64) -> (f64, f64) {
+        let v2 = v * v;
+
+        let (m1, m2) = if b.abs() < f64::EPSILON {
+            (
+                1.0,
+                2.0 * ((v2 * t).exp() - 1.0 - v2 * t) / (v2 * v2 * t * t),
+            )
+        } else {
+            (
+                ((b * t).exp() - 1.0) / (b * t),
+                2.0 * ((2.0 * b + v2) * t).exp() / ((b + v2) * (2.0 * b + v2) * t * t)
+                    + 2.0 / (b * t * t) * (1.0 / (2.0 * b + v2) - (b * t).exp() / (b + v2)),
+            )
+        };
+
+        let b_a = m1.ln() / t;
+        let v_a = (m2.ln() / t - 2.0 * b_a).sqrt();
+
+        (b_a, v_a)
+    }
+
+    pub(crate) fn price(s: f64, k: f64, t: f64, r: f64, b: f64, v: f64, flag: TypeFlag) -> f64 {
+        let (b_a, v_a) = moments(t, b, v);
+
+        match flag {
+            TypeFlag::Call => bsm::call_price(s, k, t, r, b_a, v_a),
+            TypeFlag::Put => bsm::put_price(s, k, t, r, b_a, v_a),
+        }
+    }
+}
+
+mod curran {
+    use super::TypeFlag;
+    use RustQuant_math::{Distribution, N};
+
+    /// Curran (1994) price with fixings at `t_i = i t / (n - 1)`, for
+    /// `i = 0, ..., n - 1`, and cost of carry `b`.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn price(
+        s: f64,
+        k: f64,
+        t: f64,
+        r: f64,
+        b: f64,
+        v: f64,
+        n: usize,
+        flag: TypeFlag,
+    ) -> f64 {
+        let n_f = n as f64;
+        let dt = t / (n_f - 1.0);
+        let v2 = v * v;
+
+        // Mean and variance of the log geometric average.
+        let mu = s.ln() + (b - 0.5 * v2) * 0.5 * t;
+        let var_g = v2 * dt * (n_f - 1.0) * (2.0 * n_f - 1.0) / (6.0 * n_f);
+        let sd_g = var_g.sqrt();
+
+        // Mean and variance of each log fixing, and its covariance with
+        // the log geometric average.
+        let fixings: Vec<(f64, f64, f64)> = (0..n)
+            .map(|i| {
+                let i = i as f64;
+                let t_i = i * dt;
+                let covariance = v2 * dt * (i - i * (i + 1.0) / (2.0 * n_f));
+
+                (s.ln() + (b - 0.5 * v2) * t_i, v2 * t_i, covariance)
+            })
+            .collect();
+
+        let discount = (-r * t).exp();
+        let expected_average = fixings
+            .iter()
+            .map(|(mu_i, var_i, _)| (mu_i + 0.5 * var_i).exp())
+            .sum::<f64>()
+            / n_f;
+
+        // Critical geometric average: the arithmetic average, given the
+        // geometric average at this level, is approximately the strike.
+        let k_hat = 2.0 * k
+            - fixings
+                .iter()
+                .map(|(mu_i, var_i, cov_i)| {
+                    (mu_i + cov_i * (k.ln() - mu) / var_g + 0.5 * (var_i - cov_i * cov_i / var_g))
+                        .exp()
+                })
+                .sum::<f64>()
+                / n_f;
+
+        // The call is always exercised, so it is a forward on the average.
+        let call = if k_hat <= 0.0 {
+            discount * (expected_average - k)
+        } else {
+            let d = (mu - k_hat.ln()) / sd_g;
+
+            discount
+                * (fixings
+                    .iter()
+                    .map(|(mu_i, var_i, cov_i)| {
+                        (mu_i + 0.5 * var_i).exp() * N.cdf(d + cov_i / sd_g)
+                    })
+                    .sum::<f64>()
+                    / n_f
+                    - k * N.cdf(d))
+        };
+
+        match flag {
+            TypeFlag::Call => call,
+            TypeFlag::Put => call - discount * (expected_average - k),
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_asian {
+    use super::*;
+    use crate::{ExerciseFlag, MonteCarloPricer, OptionContractBuilder};
+    use std::time::Instant;
+    use RustQuant_stochastics::{
+        BrownianMotion, GeometricBrownianMotion, StochasticProcess, StochasticProcessConfig,
+    };
+    use RustQuant_time::{today, year_fraction};
+    use RustQuant_utils::assert_approx_equal;
+
+    #[test]
+    fn test_turnbull_wakeman_zero_volatility_limit() {
+        // As volatility vanishes the average is deterministic.
+        let (b_a, v_a) = turnbull_wakeman::moments(1.0, 0.05, 1e-4);
+
+        assert_approx_equal!(b_a, ((0.05_f64).exp() - 1.0).ln() - 0.05_f64.ln(), 1e-6);
+        assert!(v_a < 1e-3);
+    }
+
+    #[test]
+    fn test_turnbull_wakeman_monte_carlo() {
+        let (s, k, r) = (100.0, 100.0, 0.05);
+        let expiry = today() + time::Duration::days(365);
+        let t = year_fraction(today(), expiry);
+
+        for flag in [TypeFlag::Call, TypeFlag::Put] {
+            let contract = OptionContractBuilder::default()
+                .type_flag(flag)
+                .exercise_flag(ExerciseFlag::European { expiry })
+                .strike_flag(Some(StrikeFlag::Fixed))
+                .build()
+                .unwrap();
+
+            let option = AsianOption::new(contract, AveragingMethod::ArithmeticDiscrete, Some(k));
+
+            for v in [0.1, 0.25, 0.4] {
+                let start = Instant::now();
+                let analytic = option.price_turnbull_wakeman(s, r, 0.0, v);
+                let analytic_time = start.elapsed();
+
+                let process = GeometricBrownianMotion::new(r, v);
+                let config = StochasticProcessConfig::new(s, 0.0, t, 250, 50_000, true);
+
+                let start = Instant::now();
+                let mc = option.price_monte_carlo(&process, &config, r);
+                let mc_time = start.elapsed();
+
+                // Moment matching is slightly biased upwards at high volatility.
+                assert_approx_equal!(analytic, mc, 0.03 * mc + 0.05);
+                assert!(analytic_time < mc_time);
+            }
+        }
+    }
+
+    #[test]
+    fn test_curran_monte_carlo() {
+        let (s, r, q) = (100.0, 0.05, 0.02);
+        let expiry = today() + time::Duration::days(365);
+        let t = year_fraction(today(), expiry);
+
+        // Twelve monthly steps, so thirteen fixings including today.
+        let n_steps = 12;
+        let average = |path: &[f64]| path.iter().sum::<f64>() / path.len() as f64;
+
+        for flag in [TypeFlag::Call, TypeFlag::Put] {
+            let contract = OptionContractBuilder::default()
+                .type_flag(flag)
+                .exercise_flag(ExerciseFlag::European { expiry })
+                .strike_flag(Some(StrikeFlag::Fixed))
+                .build()
+                .unwrap();
+
+            for (k, v) in [(90.0, 0.2), (100.0, 0.3), (110.0, 0.5)] {
+                let option = AsianOption::new(
+                    contract.clone(),
+                    AveragingMethod::ArithmeticDiscrete,
+                    Some(k),
+                );
+                let curran = option.price_curran(s, r, q, v, n_steps + 1);
+
+                // The average itself is the control, so the estimate is not
+                // thrown off by noise in the simulated forward.
+                let expected_average = (0..=n_steps)
+                    .map(|i| s * ((r - q) * t * i as f64 / n_steps as f64).exp())
+                    .sum::<f64>()
+                    / (n_steps + 1) as f64;
+
+                // Exact GBM fixings, from Brownian paths: an Euler scheme with
+                // monthly steps would not be lognormal.
+                let config =
+                    StochasticProcessConfig::new(0.0, 0.0, t, n_steps, 50_000, true).with_seed(29);
+                let brownian = BrownianMotion::new().euler_maruyama(&config);
+                let discount = (-r * t).exp();
+
+                let (controls, payoffs): (Vec<f64>, Vec<f64>) = brownian
+                    .paths
+                    .iter()
+                    .map(|w| {
+                        let path: Vec<f64> = w
+                            .iter()
+                            .zip(&brownian.times)
+                            .map(|(w, time)| s * ((r - q - 0.5 * v * v) * time + v * w).exp())
+                            .collect();
+
+                        (discount * average(&path), discount * option.payoff(path))
+                    })
+                    .unzip();
+
+                let estimate = ControlVariateEstimate::from_samples(
+                    &payoffs,
+                    &controls,
+                    discount * expected_average,
+                );
+
+                // Allow for a small bias in the approximation.
+                let error = (curran - estimate.adjusted.price).abs();
+                assert!(error < 3.0 * estimate.adjusted.standard_error + 0.01);
+            }
+        }
+    }
+
+    #[test]
+    fn test_curran_deep_in_the_money() {
+        // A tiny strike: the call is a forward on the average.
+        let (s, r, q, v, t, n) = (100.0, 0.05, 0.01, 0.3, 1.0, 5);
+        let b = r - q;
+
+        let expected_average = (0..n)
+            .map(|i| s * (b * t * i as f64 / (n - 1) as f64).exp())
+            .sum::<f64>()
+            / n as f64;
+        let forward = (-r * t).exp() * (expected_average - 1.0);
+
+        assert_approx_equal!(
+            curran::price(s, 1.0, t, r, b, v, n, TypeFlag::Call),
+            forward,
+            1e-10
+        );
+        assert_approx_equal!(
+            curran::price(s, 1.0, t, r, b, v, n, TypeFlag::Put),
+            0.0,
+            1e-10
+        );
+    }
+
+    #[test]
+    fn test_geometric_continuous_limit() {
+        // Kemna-Vorst: the continuous geometric average has volatility
+        // v / sqrt(3) and cost of carry (b - v^2 / 6) / 2.
+        let (b_g, v_g) = geometric::moments(0.05, 0.3, None);
+
+        assert_approx_equal!(b_g, 0.5 * (0.05 - 0.09 / 6.0), 1e-12);
+        assert_approx_equal!(v_g, 0.3 / 3_f64.sqrt(), 1e-12);
+
+        // Many fixings converge to continuous sampling.
+        let discrete = geometric::price(
+            100.0,
+            100.0,
+            1.0,
+            0.05,
+            0.05,
+            0.3,
+            Some(100_000),
+            TypeFlag::Call,
+        );
+        let continuous = geometric::price(100.0, 100.0, 1.0, 0.05, 0.05, 0.3, None, TypeFlag::Call);
+
+        assert_approx_equal!(discrete, continuous, 1e-4);
+    }
+
+    #[test]
+    fn test_geometric_monte_carlo() {
+        let (s, r, q, v) = (100.0, 0.05, 0.02, 0.3);
+        let expiry = today() + time::Duration::days(365);
+        let t = year_fraction(today(), expiry);
+        let n_steps = 12;
+
+        for flag in [TypeFlag::Call, TypeFlag::Put] {
+            let contract = OptionContractBuilder::default()
+                .type_flag(flag)
+                .exercise_flag(ExerciseFlag::European { expiry })
+                .strike_flag(Some(StrikeFlag::Fixed))
+                .build()
+                .unwrap();
+
+            let option =
+                AsianOption::new(contract, AveragingMethod::GeometricDiscrete, Some(100.0));
+            let exact = option.price_geometric(s, r, q, v, Some(n_steps + 1));
+
+            // Exact GBM fixings, from Brownian paths.
+            let config =
+                StochasticProcessConfig::new(0.0, 0.0, t, n_steps, 50_000, true).with_seed(31);
+            let brownian = BrownianMotion::new().euler_maruyama(&config);
+            let discount = (-r * t).exp();
+
+            let payoffs: Vec<f64> = brownian
+                .paths
+                .iter()
+                .map(|w| {
+                    let path = w
+                        .iter()
+                        .zip(&brownian.times)
+                        .map(|(w, time)| s * ((r - q - 0.5 * v * v) * time + v * w).exp())
+                        .collect();
+
+                    discount * option.payoff(path)
+                })
+                .collect();
+
+            let estimate = crate::MonteCarloEstimate::from_samples(&payoffs);
+
+            assert!((estimate.price - exact).abs() < 3.0 * estimate.standard_error);
+        }
+    }
+
+    #[test]
+    fn test_geometric_control_variate() {
+        let (s, r, q, v) = (100.0, 0.05, 0.0, 0.25);
+        let expiry = today() + time::Duration::days(365);
+        let n_steps = 52;
+
+        let contract = OptionContractBuilder::default()
+            .type_flag(TypeFlag::Call)
+            .exercise_flag(ExerciseFlag::European { expiry })
+            .strike_flag(Some(StrikeFlag::Fixed))
+            .build()
+            .unwrap();
+
+        let option = AsianOption::new(contract, AveragingMethod::ArithmeticDiscrete, Some(100.0));
+        let estimate = option.price_monte_carlo_geometric_control(s, r, q, v, 10_000, n_steps);
+        let curran = option.price_curran(s, r, q, v, n_steps + 1);
+
+        assert!(estimate.adjusted.standard_error < 0.1 * estimate.raw.standard_error);
+        assert!(
+            (estimate.adjusted.price - curran).abs()
+                < 3.0 * estimate.adjusted.standard_error + 0.01
+        );
+    }
+}
@@ -0,0 +1,337 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Autocallable structured notes.
+//!
+//! On each observation date, the underlying's performance (relative to its
+//! initial value) is compared with:
+//!
+//! - the coupon barrier: a Phoenix note pays its coupon if the performance
+//!   is at or above it (with memory, any coupons missed before as well);
+//! - the autocall barrier: the note is redeemed early at par if the
+//!   performance is at or above it. A Snowball note pays all its coupons
+//!   to date only then.
+//!
+//! If the note is never called, it repays par at maturity, unless the
+//! underlying fell to the knock-in barrier at some fixing and ends below
+//! the put strike, when the holder bears the loss of a put on the
+//! performance.
+
+use super::MonteCarloEstimate;
+use RustQuant_stochastics::{StochasticProcess, StochasticProcessConfig};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS & ENUMS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// How an autocallable note pays its coupons.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AutocallableCoupon {
+    /// All coupons to date are paid when the note is called, and none
+    /// otherwise.
+    Snowball,
+
+    /// Coupons are paid on each observation date at or above the coupon
+    /// barrier.
+    Phoenix {
+        /// Whether coupons missed on earlier dates are paid as well.
+        memory: bool,
+    },
+}
+
+/// Autocallable structured note on a single underlying.
+///
+/// Barriers and the put strike are fractions of the underlying's initial
+/// value, and the coupon is a fraction of the notional per observation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AutocallableNote {
+    /// Notional of the note.
+    pub notional: f64,
+
+    /// Observation dates (in years), increasing; the last is maturity.
+    pub observation_times: Vec<f64>,
+
+    /// Coupon per observation, as a fraction of the notional.
+    pub coupon: f64,
+
+    /// Snowball or Phoenix coupons.
+    pub coupon_style: AutocallableCoupon,
+
+    /// Coupon barrier, as a fraction of the initial value.
+    pub coupon_barrier: f64,
+
+    /// Autocall barrier, as a fraction of the initial value.
+    pub autocall_barrier: f64,
+
+    /// Knock-in barrier of the put, monitored at every fixing, as a
+    /// fraction of the initial value.
+    pub knock_in_barrier: f64,
+
+    /// Strike of the knock-in put, as a fraction of the initial value.
+    pub put_strike: f64,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl AutocallableNote {
+    /// Create a new autocallable note, with a put struck at the initial value.
+    ///
+    /// # Panics
+    /// Panics if there are no observation dates, or they are not positive
+    /// and increasing.
+    pub fn new(
+        notional: f64,
+        observation_times: Vec<f64>,
+        coupon: f64,
+        coupon_style: AutocallableCoupon,
+        coupon_barrier: f64,
+        autocall_barrier: f64,
+        knock_in_barrier: f64,
+    ) -> Self {
+        assert!(
+            !observation_times.is_empty()
+                && observation_times[0] > 0.0
+                && observation_times.windows(2).all(|pair| pair[0] < pair[1]),
+            "The observation dates must be positive and increasing."
+        );
+
+        Self {
+            notional,
+            observation_times,
+            coupon,
+            coupon_style,
+            coupon_barrier,
+            autocall_barrier,
+            knock_in_barrier,
+            put_strike: 1.0,
+        }
+    }
+
+    /// Set the strike of the knock-in put.
+    pub fn with_put_strike(mut self, put_strike: f64) -> Self {
+        self.put_strike = put_strike;
+        self
+    }
+
+    /// Maturity of the note (in years).
+    pub fn maturity(&self) -> f64 {
+        self.observation_times[self.observation_times.len() - 1]
+    }
+
+    /// Cash flows of the note along one path, as (time, amount) pairs.
+    ///
+    /// Each observation date is taken at the fixing whose time is nearest.
+    ///
+    /// # Arguments:
+    /// * `path` - Fixings of the underlying, starting with its initial value.
+    /// * `times` - Times (in years) of the fixings.
+    pub fn cash_flows(&self, path: &[f64], times: &[f64]) -> Vec<(f64, f64)> {
+        let performance = |i: usize| path[i] / path[0];
+        let nearest = |time: f64| {
+            (0..times.len())
+                .min_by(|&i, &j| (times[i] - time).abs().total_cmp(&(times[j] - time).abs()))
+                .unwrap()
+        };
+
+        let coupon = self.notional * self.coupon;
+        let mut cash_flows = Vec::new();
+        let mut unpaid = 0.0;
+        let mut last = 0;
+
+        for &time in &self.observation_times {
+            let index = nearest(time);
+            let level = performance(index);
+            last = index;
+
+            unpaid += coupon;
+
+            if let AutocallableCoupon::Phoenix { memory } = self.coupon_style {
+                if level >= self.coupon_barrier {
+                    cash_flows.push((time, unpaid));
+                    unpaid = 0.0;
+                } else if !memory {
+                    unpaid = 0.0;
+                }
+            }
+
+            if level >= self.autocall_barrier {
+                let redemption = match self.coupon_style {
+                    AutocallableCoupon::Snowball => self.notional + unpaid,
+                    AutocallableCoupon::Phoenix { .. } => self.notional,
+                };
+                cash_flows.push((time, redemption));
+
+                return cash_flows;
+            }
+        }
+
+        // Not called: par, less the put if it knocked in.
+        let knocked_in = (0..=last).any(|i| performance(i) <= self.knock_in_barrier);
+        let put = match knocked_in {
+            true => (self.put_strike - performance(last)).max(0.0) / self.put_strike,
+            false => 0.0,
+        };
+
+        cash_flows.push((self.maturity(), self.notional * (1.0 - put)));
+        cash_flows
+    }
+
+    /// Monte Carlo price of the note, simulating the underlying with any
+    /// process. The simulation should run to the note's maturity, with
+    /// time steps that land on (or near) the observation dates.
+    ///
+    /// # Arguments:
+    /// * `process` - The process driving the underlying (with risk-neutral drift).
+    /// * `config` - The simulation configuration.
+    /// * `rate` - Continuously compounded discount rate.
+    ///
+    /// # Panics
+    /// Panics if `config.m_paths < 2`.
+    pub fn price_monte_carlo<P>(
+        &self,
+        process: &P,
+        config: &StochasticProcessConfig,
+        rate: f64,
+    ) -> MonteCarloEstimate
+    where
+        P: StochasticProcess,
+    {
+        let trajectories = process.euler_maruyama(config);
+
+        let discounted: Vec<f64> = trajectories
+            .paths
+            .iter()
+            .map(|path| {
+                self.cash_flows(path, &trajectories.times)
+                    .iter()
+                    .map(|&(time, amount)| amount * (-rate * time).exp())
+                    .sum()
+            })
+            .collect();
+
+        MonteCarloEstimate::from_samples(&discounted)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_autocallable {
+    use super::*;
+    use crate::{BlackScholes73, GeneralisedBlackScholesMerton, TypeFlag};
+    use RustQuant_stochastics::GeometricBrownianMotion;
+    use RustQuant_utils::assert_approx_equal;
+
+    const TIMES: [f64; 5] = [0.0, 0.25, 0.5, 0.75, 1.0];
+
+    fn note(coupon_style: AutocallableCoupon) -> AutocallableNote {
+        AutocallableNote::new(
+            100.0,
+            vec![0.25, 0.5, 0.75, 1.0],
+            0.02,
+            coupon_style,
+            0.8,
+            1.0,
+            0.6,
+        )
+    }
+
+    #[test]
+    fn test_cash_flows() {
+        // Below the coupon barrier once, then called at 0.75.
+        let path = [100.0, 85.0, 70.0, 105.0, 90.0];
+
+        let phoenix = note(AutocallableCoupon::Phoenix { memory: false });
+        assert_eq!(
+            phoenix.cash_flows(&path, &TIMES),
+            vec![(0.25, 2.0), (0.75, 2.0), (0.75, 100.0)]
+        );
+
+        let memory = note(AutocallableCoupon::Phoenix { memory: true });
+        assert_eq!(
+            memory.cash_flows(&path, &TIMES),
+            vec![(0.25, 2.0), (0.75, 4.0), (0.75, 100.0)]
+        );
+
+        let snowball = note(AutocallableCoupon::Snowball);
+        assert_eq!(snowball.cash_flows(&path, &TIMES), vec![(0.75, 106.0)]);
+
+        // Never called: knocked in at 0.5, so the holder takes the loss.
+        let path = [100.0, 85.0, 55.0, 75.0, 90.0];
+
+        assert_eq!(snowball.cash_flows(&path, &TIMES), vec![(1.0, 100.0 * 0.9)]);
+        assert_eq!(
+            snowball.with_put_strike(0.8).cash_flows(&path, &TIMES),
+            vec![(1.0, 100.0)]
+        );
+    }
+
+    #[test]
+    fn test_bond_and_put_limits() {
+        let (s, r, v) = (100.0, 0.04, 0.3);
+        let process = GeometricBrownianMotion::new(r, v);
+        let config = StochasticProcessConfig::new(s, 0.0, 1.0, 4, 20_000, true).with_seed(29);
+
+        // Coupons always paid, never called and never knocked in: a coupon bond.
+        let bond = AutocallableNote::new(
+            100.0,
+            vec![0.25, 0.5, 0.75, 1.0],
+            0.02,
+            AutocallableCoupon::Phoenix { memory: false },
+            0.0,
+            f64::INFINITY,
+            0.0,
+        )
+        .price_monte_carlo(&process, &config, r);
+
+        let exact = 100.0 * f64::exp(-r)
+            + TIMES[1..]
+                .iter()
+                .map(|t| 2.0 * f64::exp(-r * t))
+                .sum::<f64>();
+
+        assert_approx_equal!(bond.price, exact, 1e-10);
+
+        // No coupons, never called and always knocked in: par less a put.
+        let short_put = AutocallableNote::new(
+            100.0,
+            vec![1.0],
+            0.0,
+            AutocallableCoupon::Snowball,
+            0.0,
+            f64::INFINITY,
+            f64::INFINITY,
+        )
+        .price_monte_carlo(&process, &config, r);
+
+        let exact =
+            100.0 * f64::exp(-r) - BlackScholes73::new(s, r, v).price(s, 1.0, TypeFlag::Put);
+
+        assert!((short_put.price - exact).abs() < 3.0 * short_put.standard_error);
+    }
+
+    #[test]
+    fn test_memory_and_snowball_ordering() {
+        let process = GeometricBrownianMotion::new(0.03, 0.25);
+        let config = StochasticProcessConfig::new(100.0, 0.0, 1.0, 52, 10_000, true).with_seed(31);
+
+        let price = |style| note(style).price_monte_carlo(&process, &config, 0.03).price;
+
+        // Memory can only add coupons, path by path.
+        assert!(
+            price(AutocallableCoupon::Phoenix { memory: true })
+                > price(AutocallableCoupon::Phoenix { memory: false })
+        );
+        assert!(price(AutocallableCoupon::Snowball) < 100.0);
+    }
+}
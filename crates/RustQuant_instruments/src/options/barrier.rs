@@ -8,8 +8,15 @@
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
 use crate::Payoff;
+use RustQuant_math::{gaussian::N, Distribution};
+use RustQuant_stochastics::{StochasticProcess, StochasticProcessConfig};
 
-use super::{BarrierType, OptionContract, TypeFlag};
+use super::{BarrierType, DoubleBarrierType, MonteCarloEstimate, OptionContract, TypeFlag};
+use super::{GeneralisedBlackScholesMerton, Merton73};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
 /// Barrier option.
 #[derive(Debug, Clone)]
@@ -30,6 +37,33 @@ pub struct BarrierOption {
     pub rebate: Option<f64>,
 }
 
+/// Double barrier option.
+///
+/// A knock-out option is knocked out if the underlying touches either
+/// the lower or the upper barrier before expiry. A knock-in option only
+/// pays if it does.
+#[derive(Debug, Clone)]
+pub struct DoubleBarrierOption {
+    /// The option contract.
+    pub contract: OptionContract,
+
+    /// Knock-out or knock-in.
+    pub barrier_type: DoubleBarrierType,
+
+    /// Lower barrier level.
+    pub lower_barrier: f64,
+
+    /// Upper barrier level.
+    pub upper_barrier: f64,
+
+    /// Strike price of the option.
+    pub strike: f64,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
 impl Payoff for BarrierOption {
     type Underlying = Vec<f64>;
 
@@ -91,3 +125,444 @@ impl Payoff for BarrierOption {
         }
     }
 }
+
+impl BarrierOption {
+    /// Monte Carlo price of the option under continuous monitoring, with
+    /// the Brownian bridge correction between time steps.
+    ///
+    /// Checking the barrier only at the simulated time points misses
+    /// crossings between them, which overprices knock-out options. Instead,
+    /// given the simulated values $S_i$ and $S_{i + 1}$ on the same side of
+    /// the barrier $B$, the log price is treated as a Brownian bridge, which
+    /// stays on that side with probability
+    ///
+    /// $$
+    /// 1 - \exp\left(-\frac{2 \ln(S_i / B) \ln(S_{i + 1} / B)}{\sigma_i^2 \Delta t}\right)
+    /// $$
+    ///
+    /// where $\sigma_i$ is the local volatility `process.diffusion(S_i, t_i) / S_i`.
+    /// Each path's payoff is weighted by its survival probability (or its
+    /// complement, for knock-in options), which is exact for geometric
+    /// Brownian motion and also lowers the variance of the estimate.
+    ///
+    /// # Arguments:
+    /// * `process` - The process driving the underlying (with risk-neutral drift).
+    /// * `config` - The simulation configuration.
+    /// * `rate` - Continuously compounded discount rate.
+    ///
+    /// # Panics
+    /// Panics if `config.m_paths < 2`.
+    pub fn price_monte_carlo_bridge<P>(
+        &self,
+        process: &P,
+        config: &StochasticProcessConfig,
+        rate: f64,
+    ) -> MonteCarloEstimate
+    where
+        P: StochasticProcess,
+    {
+        let trajectories = process.euler_maruyama(config);
+        let discount_factor = (-rate * (config.t_n - config.t_0)).exp();
+
+        let b = self.barrier;
+        let up = matches!(
+            self.barrier_type,
+            BarrierType::UpAndOut | BarrierType::UpAndIn
+        );
+        let knock_in = matches!(
+            self.barrier_type,
+            BarrierType::UpAndIn | BarrierType::DownAndIn
+        );
+
+        let discounted: Vec<f64> = trajectories
+            .paths
+            .iter()
+            .map(|path| {
+                let safe = |x: f64| if up { x < b } else { x > b };
+
+                let survival = match path.iter().all(|&x| safe(x)) {
+                    false => 0.0,
+                    true => path
+                        .windows(2)
+                        .zip(trajectories.times.windows(2))
+                        .map(|(s, t)| {
+                            let sigma = process.diffusion(s[0], t[0]) / s[0];
+                            let exponent = -2.0 * (s[0] / b).ln() * (s[1] / b).ln()
+                                / (sigma * sigma * (t[1] - t[0]));
+
+                            1.0 - exponent.exp()
+                        })
+                        .product(),
+                };
+
+                let terminal = path[path.len() - 1];
+                let payoff = match self.contract.type_flag {
+                    TypeFlag::Call => (terminal - self.strike).max(0.0),
+                    TypeFlag::Put => (self.strike - terminal).max(0.0),
+                };
+
+                match knock_in {
+                    true => discount_factor * payoff * (1.0 - survival),
+                    false => discount_factor * payoff * survival,
+                }
+            })
+            .collect();
+
+        MonteCarloEstimate::from_samples(&discounted)
+    }
+}
+
+impl DoubleBarrierOption {
+    /// Create a new double knock-out option.
+    pub fn new(
+        contract: OptionContract,
+        lower_barrier: f64,
+        upper_barrier: f64,
+        strike: f64,
+    ) -> Self {
+        assert!(lower_barrier < upper_barrier);
+
+        Self {
+            contract,
+            barrier_type: DoubleBarrierType::KnockOut,
+            lower_barrier,
+            upper_barrier,
+            strike,
+        }
+    }
+
+    /// Set whether the option is knocked out or knocked in.
+    pub fn with_barrier_type(mut self, barrier_type: DoubleBarrierType) -> Self {
+        self.barrier_type = barrier_type;
+        self
+    }
+
+    /// Price the option under continuous monitoring, via the
+    /// Ikeda-Kunitomo (1992) series with flat barriers.
+    ///
+    /// Knock-in options are priced by in-out parity, as the vanilla
+    /// option less the knock-out option.
+    ///
+    /// # Arguments:
+    /// * `s` - Underlying price.
+    /// * `t` - Time to expiry (in years).
+    /// * `r` - Risk-free rate.
+    /// * `q` - Dividend yield.
+    /// * `v` - Volatility.
+    pub fn price_closed_form(&self, s: f64, t: f64, r: f64, q: f64, v: f64) -> f64 {
+        match self.barrier_type {
+            DoubleBarrierType::KnockOut => self.knock_out_price(s, t, r, q, v),
+            DoubleBarrierType::KnockIn => {
+                Merton73::new(s, r, q, v).price(self.strike, t, self.contract.type_flag)
+                    - self.knock_out_price(s, t, r, q, v)
+            }
+        }
+    }
+
+    /// Ikeda-Kunitomo price of the knock-out option with the same barriers.
+    fn knock_out_price(&self, s: f64, t: f64, r: f64, q: f64, v: f64) -> f64 {
+        let (l, u, k) = (self.lower_barrier, self.upper_barrier, self.strike);
+
+        // Already knocked out.
+        if s <= l || s >= u {
+            return 0.0;
+        }
+
+        match self.contract.type_flag {
+            TypeFlag::Call => {
+                let (asset, cash) = double_barrier::legs(s, k.max(l), u, l, u, t, r, r - q, v);
+                asset - k * cash
+            }
+            TypeFlag::Put => {
+                let (asset, cash) = double_barrier::legs(s, l, k.min(u), l, u, t, r, r - q, v);
+                k * cash - asset
+            }
+        }
+    }
+}
+
+impl Payoff for DoubleBarrierOption {
+    type Underlying = Vec<f64>;
+
+    fn payoff(&self, underlying: Self::Underlying) -> f64 {
+        let touched = underlying
+            .iter()
+            .any(|&x| x <= self.lower_barrier || x >= self.upper_barrier);
+
+        let alive = match self.barrier_type {
+            DoubleBarrierType::KnockOut => !touched,
+            DoubleBarrierType::KnockIn => touched,
+        };
+
+        if !alive {
+            return 0.0;
+        }
+
+        let terminal = underlying.last().copied().unwrap_or(0.0);
+
+        match self.contract.type_flag {
+            TypeFlag::Call => (terminal - self.strike).max(0.0),
+            TypeFlag::Put => (self.strike - terminal).max(0.0),
+        }
+    }
+}
+
+mod double_barrier {
+    use super::{Distribution, N};
+
+    /// Number of terms either side of zero in the truncated series.
+    const SERIES_TERMS: i32 = 5;
+
+    /// Returns the discounted `(asset, cash)` legs of a double knock-out claim
+    /// paying on $S_T \in (a, c)$, where $L \leq a$ and $c \leq U$:
+    ///
+    /// - asset: $e^{-rT} E[S_T 1\{a < S_T < c, \text{not knocked out}\}]$
+    /// - cash: $e^{-rT} P(a < S_T < c, \text{not knocked out})$
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn legs(
+        s: f64,
+        a: f64,
+        c: f64,
+        l: f64,
+        u: f64,
+        t: f64,
+        r: f64,
+        b: f64,
+        v: f64,
+    ) -> (f64, f64) {
+        if a >= c {
+            return (0.0, 0.0);
+        }
+
+        let v_sqrt_t = v * t.sqrt();
+        let drift = (b + 0.5 * v * v) * t;
+        let mu = 2.0 * b / (v * v) + 1.0;
+
+        let mut asset = 0.0;
+        let mut cash = 0.0;
+
+        for n in -SERIES_TERMS..=SERIES_TERMS {
+            let n = f64::from(n);
+
+            // Direct and reflected images of the starting point.
+            let direct = (s * u.powf(2.0 * n) / l.powf(2.0 * n)).ln();
+            let reflected = (l.powf(2.0 * n + 2.0) / (s * u.powf(2.0 * n))).ln();
+
+            let d = |x: f64| (direct - x.ln() + drift) / v_sqrt_t;
+            let e = |x: f64| (reflected - x.ln() + drift) / v_sqrt_t;
+
+            let ratio_1 = (u / l).powf(n);
+            let ratio_2 = l.powf(n + 1.0) / (u.powf(n) * s);
+
+            asset += ratio_1.powf(mu) * (N.cdf(d(a)) - N.cdf(d(c)))
+                - ratio_2.powf(mu) * (N.cdf(e(a)) - N.cdf(e(c)));
+
+            cash += ratio_1.powf(mu - 2.0) * (N.cdf(d(a) - v_sqrt_t) - N.cdf(d(c) - v_sqrt_t))
+                - ratio_2.powf(mu - 2.0) * (N.cdf(e(a) - v_sqrt_t) - N.cdf(e(c) - v_sqrt_t));
+        }
+
+        (s * ((b - r) * t).exp() * asset, (-r * t).exp() * cash)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_double_barrier {
+    use super::*;
+    use crate::OptionContractBuilder;
+    use crate::{BlackScholes73, ExerciseFlag, GeneralisedBlackScholesMerton, MonteCarloPricer};
+    use time::macros::date;
+    use RustQuant_stochastics::{GeometricBrownianMotion, StochasticProcessConfig};
+    use RustQuant_utils::assert_approx_equal;
+
+    fn contract(type_flag: TypeFlag) -> OptionContract {
+        OptionContractBuilder::default()
+            .type_flag(type_flag)
+            .exercise_flag(ExerciseFlag::European {
+                expiry: date!(2025 - 01 - 01),
+            })
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_double_barrier_wide_barriers_approach_vanilla() {
+        let bs = BlackScholes73::new(100.0, 0.05, 0.2);
+
+        for flag in [TypeFlag::Call, TypeFlag::Put] {
+            let vanilla = bs.price(100.0, 1.0, flag);
+
+            let narrow = DoubleBarrierOption::new(contract(flag), 70.0, 140.0, 100.0)
+                .price_closed_form(100.0, 1.0, 0.05, 0.0, 0.2);
+            let wide = DoubleBarrierOption::new(contract(flag), 20.0, 500.0, 100.0)
+                .price_closed_form(100.0, 1.0, 0.05, 0.0, 0.2);
+
+            assert!(narrow < wide);
+            assert_approx_equal!(wide, vanilla, 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_double_barrier_knocked_out() {
+        let option = DoubleBarrierOption::new(contract(TypeFlag::Call), 80.0, 120.0, 100.0);
+
+        assert_eq!(option.price_closed_form(125.0, 1.0, 0.05, 0.0, 0.2), 0.0);
+        assert_eq!(option.price_closed_form(80.0, 1.0, 0.05, 0.0, 0.2), 0.0);
+    }
+
+    #[test]
+    fn test_double_barrier_monte_carlo_agrees_with_series() {
+        let (s, t, r, v) = (100.0, 0.5, 0.05, 0.2);
+        let n_steps = 500;
+
+        // Broadie-Glasserman-Kou shift, so the continuous-monitoring series
+        // matches the discretely monitored simulation.
+        let shift = (0.5826 * v * (t / n_steps as f64).sqrt()).exp();
+
+        for flag in [TypeFlag::Call, TypeFlag::Put] {
+            let option = DoubleBarrierOption::new(contract(flag), 80.0, 130.0, 100.0);
+            let shifted =
+                DoubleBarrierOption::new(contract(flag), 80.0 / shift, 130.0 * shift, 100.0);
+
+            let process = GeometricBrownianMotion::new(r, v);
+            let config = StochasticProcessConfig::new(s, 0.0, t, n_steps, 50_000, true);
+
+            let mc = option.price_monte_carlo(&process, &config, r);
+            let series = shifted.price_closed_form(s, t, r, 0.0, v);
+
+            assert_approx_equal!(mc, series, 0.1);
+        }
+    }
+    #[test]
+    fn test_double_knock_in() {
+        let (s, t, r, q, v) = (100.0, 0.5, 0.05, 0.02, 0.25);
+        let bs = Merton73::new(s, r, q, v);
+
+        for flag in [TypeFlag::Call, TypeFlag::Put] {
+            let out = DoubleBarrierOption::new(contract(flag), 85.0, 120.0, 100.0);
+            let knock_in = out.clone().with_barrier_type(DoubleBarrierType::KnockIn);
+
+            // In-out parity.
+            assert_approx_equal!(
+                knock_in.price_closed_form(s, t, r, q, v) + out.price_closed_form(s, t, r, q, v),
+                bs.price(100.0, t, flag),
+                1e-10
+            );
+
+            // Already knocked in.
+            assert_approx_equal!(
+                knock_in.price_closed_form(125.0, t, r, q, v),
+                Merton73::new(125.0, r, q, v).price(100.0, t, flag),
+                1e-10
+            );
+        }
+    }
+
+    #[test]
+    fn test_double_knock_in_monte_carlo_agrees_with_series() {
+        let (s, t, r, v) = (100.0, 0.5, 0.05, 0.2);
+        let n_steps = 500;
+        let shift = (0.5826 * v * (t / n_steps as f64).sqrt()).exp();
+
+        for flag in [TypeFlag::Call, TypeFlag::Put] {
+            let option = DoubleBarrierOption::new(contract(flag), 85.0, 120.0, 100.0)
+                .with_barrier_type(DoubleBarrierType::KnockIn);
+            let shifted =
+                DoubleBarrierOption::new(contract(flag), 85.0 / shift, 120.0 * shift, 100.0)
+                    .with_barrier_type(DoubleBarrierType::KnockIn);
+
+            let process = GeometricBrownianMotion::new(r, v);
+            let config =
+                StochasticProcessConfig::new(s, 0.0, t, n_steps, 50_000, true).with_seed(5);
+
+            let mc = option.price_monte_carlo(&process, &config, r);
+            let series = shifted.price_closed_form(s, t, r, 0.0, v);
+
+            assert_approx_equal!(mc, series, 0.1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests_barrier {
+    use super::*;
+    use crate::{
+        BlackScholes73, ExerciseFlag, GeneralisedBlackScholesMerton, MonteCarloPricer,
+        OptionContractBuilder,
+    };
+    use time::macros::date;
+    use RustQuant_stochastics::GeometricBrownianMotion;
+
+    const S: f64 = 100.0;
+    const K: f64 = 100.0;
+    const B: f64 = 90.0;
+    const R: f64 = 0.05;
+    const V: f64 = 0.2;
+    const T: f64 = 1.0;
+
+    fn option(barrier_type: BarrierType) -> BarrierOption {
+        let contract = OptionContractBuilder::default()
+            .type_flag(TypeFlag::Call)
+            .exercise_flag(ExerciseFlag::European {
+                expiry: date!(2025 - 01 - 01),
+            })
+            .build()
+            .unwrap();
+
+        BarrierOption {
+            contract,
+            barrier_type,
+            barrier: B,
+            strike: K,
+            rebate: None,
+        }
+    }
+
+    /// Continuously monitored down-and-out call with B <= K (Merton, 1973):
+    /// C(S) - (B / S)^(2 nu / v^2) C(B^2 / S), with nu = r - v^2 / 2.
+    fn down_and_out_call() -> f64 {
+        let nu = R - 0.5 * V * V;
+
+        BlackScholes73::new(S, R, V).price(K, T, TypeFlag::Call)
+            - (B / S).powf(2.0 * nu / (V * V))
+                * BlackScholes73::new(B * B / S, R, V).price(K, T, TypeFlag::Call)
+    }
+
+    #[test]
+    fn test_bridge_matches_continuous_monitoring() {
+        // Only 50 monitoring dates: the naive estimate is biased upwards.
+        let gbm = GeometricBrownianMotion::new(R, V);
+        let config = StochasticProcessConfig::new(S, 0.0, T, 50, 20_000, true).with_seed(13);
+
+        let exact = down_and_out_call();
+        let bridge = option(BarrierType::DownAndOut).price_monte_carlo_bridge(&gbm, &config, R);
+        let naive = option(BarrierType::DownAndOut).price_monte_carlo(&gbm, &config, R);
+
+        assert!((bridge.price - exact).abs() < 3.0 * bridge.standard_error);
+        assert!(naive - exact > 5.0 * bridge.standard_error);
+    }
+
+    #[test]
+    fn test_bridge_in_out_parity() {
+        // The same paths price both, so they add up to the vanilla estimate.
+        let gbm = GeometricBrownianMotion::new(R, V);
+        let config = StochasticProcessConfig::new(S, 0.0, T, 20, 10_000, true).with_seed(3);
+
+        let out = option(BarrierType::DownAndOut).price_monte_carlo_bridge(&gbm, &config, R);
+        let knock_in = option(BarrierType::DownAndIn).price_monte_carlo_bridge(&gbm, &config, R);
+
+        let vanilla = crate::EuropeanVanillaOption::new(K, RustQuant_time::today(), TypeFlag::Call)
+            .price_monte_carlo(&gbm, &config, R);
+
+        assert!((out.price + knock_in.price - vanilla).abs() < 1e-10);
+
+        // Up-and-out with the barrier far away is the vanilla option.
+        let mut far = option(BarrierType::UpAndOut);
+        far.barrier = 1e6;
+
+        assert!((far.price_monte_carlo_bridge(&gbm, &config, R).price - vanilla).abs() < 1e-10);
+    }
+}
@@ -8,8 +8,11 @@
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
 use crate::Payoff;
+use RustQuant_math::{gaussian::N, Distribution};
 
-use super::{BinaryType, OptionContract, TypeFlag};
+use super::{
+    BinaryType, GeneralisedBlackScholesMerton, Greeks, Merton73, OptionContract, TypeFlag,
+};
 
 /// Binary option.
 #[derive(Debug, Clone)]
@@ -20,10 +23,158 @@ pub struct BinaryOption {
     /// Strike price of the option.
     pub strike: f64,
 
+    /// Amount paid by a cash-or-nothing option.
+    pub cash: f64,
+
     /// Type of binary option.
     pub binary_type: BinaryType,
 }
 
+impl BinaryOption {
+    /// Create a new binary option.
+    pub fn new(contract: OptionContract, strike: f64, cash: f64, binary_type: BinaryType) -> Self {
+        Self {
+            contract,
+            strike,
+            cash,
+            binary_type,
+        }
+    }
+
+    /// Price the option under the Black-Scholes-Merton model
+    /// (Reiner and Rubinstein, 1991).
+    ///
+    /// # Arguments:
+    /// * `s` - Underlying price.
+    /// * `t` - Time to expiry (in years).
+    /// * `r` - Risk-free rate.
+    /// * `q` - Dividend yield.
+    /// * `v` - Volatility.
+    pub fn price_closed_form(&self, s: f64, t: f64, r: f64, q: f64, v: f64) -> f64 {
+        let (d1, d2) = self.d1_d2(s, t, r, q, v);
+        let phi = self.phi();
+
+        match self.binary_type {
+            BinaryType::CashOrNothing => self.cash * (-r * t).exp() * N.cdf(phi * d2),
+            BinaryType::AssetOrNothing => s * (-q * t).exp() * N.cdf(phi * d1),
+        }
+    }
+
+    /// Greeks of the option under the Black-Scholes-Merton model.
+    ///
+    /// Theta is the sensitivity to the passage of time, $-\partial V / \partial t$,
+    /// and rho is the sensitivity to the risk-free rate with the dividend
+    /// yield held fixed.
+    /// Note that delta and gamma of a digital blow up near the strike as
+    /// expiry approaches; see `overhedge` for more realistic risk numbers.
+    pub fn greeks_closed_form(&self, s: f64, t: f64, r: f64, q: f64, v: f64) -> Greeks {
+        let (d1, d2) = self.d1_d2(s, t, r, q, v);
+        let phi = self.phi();
+        let sqrt_t = t.sqrt();
+        let price = self.price_closed_form(s, t, r, q, v);
+
+        // Rates of change of d1 and d2 with respect to the time to expiry.
+        let d1_t = (r - q + 0.5 * v * v) / (v * sqrt_t) - d1 / (2.0 * t);
+        let d2_t = d1_t - v / (2.0 * sqrt_t);
+
+        match self.binary_type {
+            BinaryType::CashOrNothing => {
+                // Discounted density of the payoff boundary.
+                let density = self.cash * (-r * t).exp() * phi * N.pdf(d2);
+
+                Greeks {
+                    delta: density / (s * v * sqrt_t),
+                    gamma: -density * d1 / (s * s * v * v * t),
+                    vega: -density * d1 / v,
+                    theta: r * price - density * d2_t,
+                    rho: -t * price + density * sqrt_t / v,
+                }
+            }
+            BinaryType::AssetOrNothing => {
+                let density = s * (-q * t).exp() * phi * N.pdf(d1);
+
+                Greeks {
+                    delta: price / s + density / (s * v * sqrt_t),
+                    gamma: -density * d2 / (s * s * v * v * t),
+                    vega: -density * d2 / v,
+                    theta: q * price - density * d1_t,
+                    rho: density * sqrt_t / v,
+                }
+            }
+        }
+    }
+
+    /// Price and Greeks of the vanilla spread that over-hedges the option.
+    ///
+    /// A digital paying one unit above the strike $K$ is super-replicated
+    /// by $1 / \epsilon$ calls struck at $K - \epsilon$ less $1 / \epsilon$
+    /// calls struck at $K$ (and puts struck at $K + \epsilon$ and $K$ for
+    /// a digital put). The spread has bounded delta and gamma, and its
+    /// price converges to the digital's as the width $\epsilon$ goes to zero.
+    /// An asset-or-nothing option is a vanilla option plus $K$ cash digitals.
+    ///
+    /// # Arguments:
+    /// * `s` - Underlying price.
+    /// * `t` - Time to expiry (in years).
+    /// * `r` - Risk-free rate.
+    /// * `q` - Dividend yield.
+    /// * `v` - Volatility.
+    /// * `spread` - Width of the spread ($\epsilon$).
+    ///
+    /// # Panics
+    /// Panics if `spread` is not positive.
+    #[allow(clippy::too_many_arguments)]
+    pub fn overhedge(&self, s: f64, t: f64, r: f64, q: f64, v: f64, spread: f64) -> (f64, Greeks) {
+        assert!(spread > 0.0, "The spread width must be positive.");
+
+        let model = Merton73::new(s, r, q, v);
+        let flag = self.contract.type_flag;
+        let k = self.strike;
+
+        let vanilla = |k: f64| {
+            (
+                model.price(k, t, flag),
+                Greeks {
+                    delta: model.delta(k, t, flag),
+                    gamma: model.gamma(k, t, flag),
+                    vega: model.vega(k, t, flag),
+                    theta: model.theta(k, t, flag),
+                    rho: model.rho(k, t, flag),
+                },
+            )
+        };
+
+        // Unit digital, from the spread of vanillas struck further in the money.
+        let (inner_price, inner_greeks) = vanilla(k - self.phi() * spread);
+        let (price, greeks) = vanilla(k);
+        let digital = (
+            (inner_price - price) / spread,
+            (inner_greeks + greeks * -1.0) * (1.0 / spread),
+        );
+
+        match self.binary_type {
+            BinaryType::CashOrNothing => (digital.0 * self.cash, digital.1 * self.cash),
+            BinaryType::AssetOrNothing => (
+                self.phi() * price + k * digital.0,
+                greeks * self.phi() + digital.1 * k,
+            ),
+        }
+    }
+
+    fn phi(&self) -> f64 {
+        match self.contract.type_flag {
+            TypeFlag::Call => 1.0,
+            TypeFlag::Put => -1.0,
+        }
+    }
+
+    fn d1_d2(&self, s: f64, t: f64, r: f64, q: f64, v: f64) -> (f64, f64) {
+        let d1 = ((s / self.strike).ln() + (r - q + 0.5 * v * v) * t) / (v * t.sqrt());
+
+        (d1, d1 - v * t.sqrt())
+    }
+}
+
 impl Payoff for BinaryOption {
     type Underlying = f64;
 
@@ -31,11 +182,11 @@ impl Payoff for BinaryOption {
         match self.binary_type {
             BinaryType::CashOrNothing => match self.contract.type_flag {
                 TypeFlag::Call => match underlying > self.strike {
-                    true => self.strike,
+                    true => self.cash,
                     false => 0.0,
                 },
                 TypeFlag::Put => match underlying < self.strike {
-                    true => self.strike,
+                    true => self.cash,
                     false => 0.0,
                 },
             },
@@ -52,3 +203,134 @@ impl Payoff for BinaryOption {
         }
     }
 }
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_binary {
+    use super::*;
+    use crate::{ExerciseFlag, OptionContractBuilder};
+    use time::macros::date;
+    use RustQuant_utils::assert_approx_equal;
+
+    fn binary(type_flag: TypeFlag, binary_type: BinaryType) -> BinaryOption {
+        let contract = OptionContractBuilder::default()
+            .type_flag(type_flag)
+            .exercise_flag(ExerciseFlag::European {
+                expiry: date!(2025 - 01 - 01),
+            })
+            .build()
+            .unwrap();
+
+        BinaryOption::new(contract, 65.0, 10.0, binary_type)
+    }
+
+    #[test]
+    fn test_cash_or_nothing_price() {
+        // Haug (2007), The Complete Guide to Option Pricing Formulas:
+        // S = 100, K = 80, T = 0.75, r = 0.06, b = 0, v = 0.35, cash = 10.
+        let mut put = binary(TypeFlag::Put, BinaryType::CashOrNothing);
+        put.strike = 80.0;
+
+        assert_approx_equal!(
+            put.price_closed_form(100.0, 0.75, 0.06, 0.06, 0.35),
+            2.6710,
+            1e-4
+        );
+    }
+
+    #[test]
+    fn test_asset_or_nothing_price() {
+        // Haug (2007): S = 70, K = 65, T = 0.5, r = 0.07, b = 0.02, v = 0.27.
+        let put = binary(TypeFlag::Put, BinaryType::AssetOrNothing);
+
+        assert_approx_equal!(
+            put.price_closed_form(70.0, 0.5, 0.07, 0.05, 0.27),
+            20.2069,
+            1e-4
+        );
+    }
+
+    #[test]
+    fn test_parity() {
+        // A call and put pay the cash (or the asset) between them.
+        let (s, t, r, q, v) = (70.0, 0.5, 0.07, 0.05, 0.27);
+
+        let cash = binary(TypeFlag::Call, BinaryType::CashOrNothing)
+            .price_closed_form(s, t, r, q, v)
+            + binary(TypeFlag::Put, BinaryType::CashOrNothing).price_closed_form(s, t, r, q, v);
+        let asset = binary(TypeFlag::Call, BinaryType::AssetOrNothing)
+            .price_closed_form(s, t, r, q, v)
+            + binary(TypeFlag::Put, BinaryType::AssetOrNothing).price_closed_form(s, t, r, q, v);
+
+        assert_approx_equal!(cash, 10.0 * (-r * t).exp(), 1e-12);
+        assert_approx_equal!(asset, s * (-q * t).exp(), 1e-12);
+    }
+
+    #[test]
+    fn test_greeks_against_finite_differences() {
+        let (s, t, r, q, v) = (70.0, 0.5, 0.07, 0.05, 0.27);
+        let h = 1e-4;
+
+        for type_flag in [TypeFlag::Call, TypeFlag::Put] {
+            for binary_type in [BinaryType::CashOrNothing, BinaryType::AssetOrNothing] {
+                let option = binary(type_flag, binary_type);
+                let price =
+                    |s: f64, t: f64, r: f64, v: f64| option.price_closed_form(s, t, r, q, v);
+                let greeks = option.greeks_closed_form(s, t, r, q, v);
+
+                let delta = (price(s + h, t, r, v) - price(s - h, t, r, v)) / (2.0 * h);
+                let gamma = (price(s + h, t, r, v) - 2.0 * price(s, t, r, v)
+                    + price(s - h, t, r, v))
+                    / (h * h);
+                let vega = (price(s, t, r, v + h) - price(s, t, r, v - h)) / (2.0 * h);
+                let theta = -(price(s, t + h, r, v) - price(s, t - h, r, v)) / (2.0 * h);
+                let rho = (price(s, t, r + h, v) - price(s, t, r - h, v)) / (2.0 * h);
+
+                assert_approx_equal!(greeks.delta, delta, 1e-6);
+                assert_approx_equal!(greeks.gamma, gamma, 1e-5);
+                assert_approx_equal!(greeks.vega, vega, 1e-5);
+                assert_approx_equal!(greeks.theta, theta, 1e-5);
+                assert_approx_equal!(greeks.rho, rho, 1e-5);
+            }
+        }
+    }
+
+    #[test]
+    fn test_overhedge() {
+        let (s, t, r, q, v) = (70.0, 0.5, 0.07, 0.05, 0.27);
+
+        for type_flag in [TypeFlag::Call, TypeFlag::Put] {
+            for binary_type in [BinaryType::CashOrNothing, BinaryType::AssetOrNothing] {
+                let option = binary(type_flag, binary_type);
+                let exact = option.price_closed_form(s, t, r, q, v);
+                let exact_greeks = option.greeks_closed_form(s, t, r, q, v);
+
+                // The spread always costs more, and converges as it narrows.
+                let (wide, _) = option.overhedge(s, t, r, q, v, 1.0);
+                let (narrow, greeks) = option.overhedge(s, t, r, q, v, 1e-3);
+
+                assert!(wide > narrow && narrow > exact);
+                assert_approx_equal!(narrow, exact, 1e-3);
+                assert_approx_equal!(greeks.delta, exact_greeks.delta, 1e-3);
+                assert_approx_equal!(greeks.vega, exact_greeks.vega, 1e-2);
+            }
+        }
+    }
+
+    #[test]
+    fn test_overhedge_bounds_delta_at_expiry() {
+        // An at-the-money digital an hour from expiry.
+        let option = binary(TypeFlag::Call, BinaryType::CashOrNothing);
+        let t = 1.0 / (365.0 * 24.0);
+
+        let exact = option.greeks_closed_form(65.0, t, 0.05, 0.0, 0.2);
+        let (_, hedged) = option.overhedge(65.0, t, 0.05, 0.0, 0.2, 0.5);
+
+        // The spread's delta is at most cash / width.
+        assert!(exact.delta > 10.0 / 0.5);
+        assert!(hedged.delta <= 10.0 / 0.5);
+    }
+}
@@ -25,6 +25,7 @@
 // IMPORTS
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
+use crate::options::option_models::bsm;
 use crate::options::TypeFlag;
 use crate::Instrument;
 use time::Date;
@@ -68,6 +69,11 @@ pub struct BlackScholesMerton {
 
     /// Call or put flag.
     pub option_type: TypeFlag,
+
+    /// Day count convention for the time to expiry (optional, defaults to
+    /// `DayCountConvention::default()`).
+    #[builder(default = "DayCountConvention::default()")]
+    pub day_count: DayCountConvention,
 }
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -120,9 +126,17 @@ impl BlackScholesMerton {
             evaluation_date,
             expiration_date,
             option_type,
+            day_count: DayCountConvention::default(),
         }
     }
 
+    /// Set the day count convention for the time to expiry.
+    #[must_use]
+    pub fn with_day_count(mut self, day_count: DayCountConvention) -> Self {
+        self.day_count = day_count;
+        self
+    }
+
     /// Generalised Black-Scholes European Option Price.
     #[must_use]
     pub fn price(&self) -> f64 {
@@ -151,10 +165,11 @@ impl BlackScholesMerton {
         )
     }
 
-    /// Compute the year fraction between two dates.
+    /// Compute the year fraction from the evaluation date to expiry,
+    /// under the option's day count.
     #[must_use]
     pub fn year_fraction(&self) -> f64 {
-        DayCountConvention::default().day_count_factor(
+        self.day_count.day_count_factor(
             self.evaluation_date.unwrap_or(today()),
             self.expiration_date,
         )
@@ -168,10 +183,7 @@ impl BlackScholesMerton {
         // Compute time to maturity.
         let T = self.year_fraction();
 
-        let d1 = (1.0 / (v * T.sqrt())) * ((S / K).ln() + (b + 0.5 * v.powi(2)) * T);
-        let d2 = d1 - v * T.sqrt();
-
-        (d1, d2)
+        (bsm::d1(S, K, T, b, v), bsm::d2(S, K, T, b, v))
     }
 
     // Unpack struct to get option parameters.
@@ -244,12 +256,11 @@ impl BlackScholesMerton {
     /// Also known as convexity.
     #[must_use]
     pub fn gamma(&self) -> f64 {
-        let n = Gaussian::default();
         let (S, _, v, r, b) = self.unpack();
         let T = self.year_fraction();
         let (d1, _) = self.d1_d2();
 
-        ((b - r) * T).exp() * n.pdf(d1) / (S * v * T.sqrt())
+        ((b - r) * T).exp() * bsm::density_over_std_dev(d1, T, v) / S
     }
 
     /// Gamma percent of generalised Black-Scholes European Option.
@@ -352,12 +363,12 @@ impl BlackScholesMerton {
 
         match self.option_type {
             TypeFlag::Call => {
-                -S * ((b - r) * T).exp() * n.pdf(d1) * v / (2.0 * T.sqrt())
+                -0.5 * S * ((b - r) * T).exp() * v * v * bsm::density_over_std_dev(d1, T, v)
                     - (b - r) * S * ((b - r) * T).exp() * n.cdf(d1)
                     - r * K * (-r * T).exp() * n.cdf(d2)
             }
             TypeFlag::Put => {
-                -S * ((b - r) * T).exp() * n.pdf(d1) * v / (2.0 * T.sqrt())
+                -0.5 * S * ((b - r) * T).exp() * v * v * bsm::density_over_std_dev(d1, T, v)
                     + (b - r) * S * ((b - r) * T).exp() * n.cdf(-d1)
                     + r * K * (-r * T).exp() * n.cdf(-d2)
             }
@@ -478,4 +489,29 @@ mod tests_black_scholes_merton {
         );
         assert_approx_equal!(bsm.price(), 2.456571166461579, 1e-2);
     }
+
+    #[test]
+    fn expiry_today_is_intrinsic() {
+        for (k, flag, intrinsic) in [
+            (95.0, TypeFlag::Call, 5.0),
+            (105.0, TypeFlag::Call, 0.0),
+            (105.0, TypeFlag::Put, 5.0),
+        ] {
+            let bsm = BlackScholesMerton::new(-0.01, 100.0, k, 0.2, -0.01, None, today(), flag);
+
+            assert_approx_equal!(bsm.price(), intrinsic, 1e-12);
+            assert_eq!(bsm.gamma(), 0.0);
+            assert!(bsm.theta().is_finite());
+        }
+    }
+
+    #[test]
+    fn day_count_sets_time_to_expiry() {
+        let expiry = today() + Duration::days(365);
+        let bsm =
+            BlackScholesMerton::new(0.05, 100.0, 100.0, 0.2, 0.05, None, expiry, TypeFlag::Call)
+                .with_day_count(DayCountConvention::Actual_360);
+
+        assert_approx_equal!(bsm.year_fraction(), 365.0 / 360.0, 1e-12);
+    }
 }
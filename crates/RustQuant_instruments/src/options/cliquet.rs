@@ -0,0 +1,249 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use super::MonteCarloEstimate;
+use crate::Payoff;
+use RustQuant_stochastics::{StochasticProcess, StochasticProcessConfig};
+
+/// Cliquet (ratchet) option.
+///
+/// The underlying is fixed at each reset time, and the return over each
+/// period is clamped to the local floor and cap. The payoff at maturity is
+/// the notional times the sum of the clamped returns, itself clamped to
+/// the global floor and cap:
+///
+/// $$
+/// N \min\left(\max\left(\sum_i \min(\max(R_i, F_l), C_l), F_g\right), C_g\right),
+/// \quad R_i = \frac{S(t_i)}{S(t_{i - 1})} - 1
+/// $$
+///
+/// Unset floors and caps are infinite.
+#[derive(Debug, Clone)]
+pub struct CliquetOption {
+    /// Reset times (in years), increasing, with the last one at maturity.
+    /// The first period starts today.
+    pub reset_times: Vec<f64>,
+
+    /// Notional amount.
+    pub notional: f64,
+
+    /// Floor on each periodic return.
+    pub local_floor: f64,
+
+    /// Cap on each periodic return.
+    pub local_cap: f64,
+
+    /// Floor on the sum of the clamped returns.
+    pub global_floor: f64,
+
+    /// Cap on the sum of the clamped returns.
+    pub global_cap: f64,
+}
+
+impl Payoff for CliquetOption {
+    /// The underlying at today and at each reset time.
+    type Underlying = Vec<f64>;
+
+    fn payoff(&self, underlying: Self::Underlying) -> f64 {
+        let sum = underlying
+            .windows(2)
+            .map(|pair| (pair[1] / pair[0] - 1.0).clamp(self.local_floor, self.local_cap))
+            .sum::<f64>();
+
+        self.notional * sum.max(self.global_floor).min(self.global_cap)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl CliquetOption {
+    /// Create a new cliquet option, without floors or caps.
+    ///
+    /// # Panics
+    /// Panics if `reset_times` is empty, or not positive and increasing.
+    pub fn new(reset_times: Vec<f64>, notional: f64) -> Self {
+        assert!(
+            !reset_times.is_empty(),
+            "At least one reset time is needed."
+        );
+        assert!(
+            reset_times[0] > 0.0 && reset_times.windows(2).all(|pair| pair[0] < pair[1]),
+            "The reset times must be positive and increasing."
+        );
+
+        Self {
+            reset_times,
+            notional,
+            local_floor: f64::NEG_INFINITY,
+            local_cap: f64::INFINITY,
+            global_floor: f64::NEG_INFINITY,
+            global_cap: f64::INFINITY,
+        }
+    }
+
+    /// Set the floor and cap on each periodic return.
+    pub fn with_local_bounds(mut self, floor: f64, cap: f64) -> Self {
+        assert!(
+            floor <= cap,
+            "The local floor must not exceed the local cap."
+        );
+
+        self.local_floor = floor;
+        self.local_cap = cap;
+        self
+    }
+
+    /// Set the floor and cap on the sum of the clamped returns.
+    pub fn with_global_bounds(mut self, floor: f64, cap: f64) -> Self {
+        assert!(
+            floor <= cap,
+            "The global floor must not exceed the global cap."
+        );
+
+        self.global_floor = floor;
+        self.global_cap = cap;
+        self
+    }
+
+    /// Maturity of the option (the last reset time).
+    pub fn maturity(&self) -> f64 {
+        self.reset_times[self.reset_times.len() - 1]
+    }
+
+    /// Monte Carlo price of the option, for any model of the underlying.
+    ///
+    /// The simulation should run from today to maturity. Each reset time
+    /// is fixed at its nearest time step, so `config.n_steps` should be
+    /// large enough to separate the resets (ideally a multiple of the
+    /// number of periods).
+    ///
+    /// # Arguments:
+    /// * `process` - The process driving the underlying (with risk-neutral drift).
+    /// * `config` - The simulation configuration.
+    /// * `rate` - Continuously compounded discount rate.
+    ///
+    /// # Panics
+    /// Panics if `config.m_paths < 2`.
+    pub fn price_monte_carlo<P>(
+        &self,
+        process: &P,
+        config: &StochasticProcessConfig,
+        rate: f64,
+    ) -> MonteCarloEstimate
+    where
+        P: StochasticProcess,
+    {
+        let trajectories = process.euler_maruyama(config);
+
+        let nearest_step = |reset: f64| {
+            trajectories
+                .times
+                .iter()
+                .map(|time| (time - config.t_0 - reset).abs())
+                .enumerate()
+                .min_by(|a, b| a.1.total_cmp(&b.1))
+                .map_or(0, |(step, _)| step)
+        };
+
+        let fixing_steps: Vec<usize> = std::iter::once(0)
+            .chain(self.reset_times.iter().map(|&time| nearest_step(time)))
+            .collect();
+
+        let discount_factor = (-rate * self.maturity()).exp();
+
+        let discounted: Vec<f64> = trajectories
+            .paths
+            .iter()
+            .map(|path| {
+                let fixings = fixing_steps.iter().map(|&step| path[step]).collect();
+
+                discount_factor * self.payoff(fixings)
+            })
+            .collect();
+
+        MonteCarloEstimate::from_samples(&discounted)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_cliquet {
+    use super::*;
+    use crate::{BlackScholes73, GeneralisedBlackScholesMerton, TypeFlag};
+    use RustQuant_stochastics::GeometricBrownianMotion;
+    use RustQuant_utils::assert_approx_equal;
+
+    const R: f64 = 0.04;
+    const V: f64 = 0.2;
+
+    fn quarterly() -> Vec<f64> {
+        vec![0.25, 0.5, 0.75, 1.0]
+    }
+
+    #[test]
+    fn test_cliquet_payoff() {
+        let cliquet = CliquetOption::new(quarterly(), 100.0)
+            .with_local_bounds(-0.05, 0.1)
+            .with_global_bounds(0.0, 0.2);
+
+        // Returns 20%, -10%, 5%, 0% clamp to 10%, -5%, 5%, 0%.
+        let payoff = cliquet.payoff(vec![100.0, 120.0, 108.0, 113.4, 113.4]);
+        assert_approx_equal!(payoff, 10.0, 1e-12);
+
+        // Falling every period: the global floor applies.
+        assert_eq!(cliquet.payoff(vec![100.0, 90.0, 80.0, 70.0, 60.0]), 0.0);
+    }
+
+    #[test]
+    fn test_cliquet_locally_capped_matches_forward_start_spreads() {
+        // Without global bounds, each period is a forward start call spread
+        // on the return: E[min(max(R, 0), c)] = (C(1) - C(1 + c)) e^{r dt}
+        // for calls C on a unit underlying.
+        let (cap, dt) = (0.05, 0.25);
+        let unit = BlackScholes73::new(1.0, R, V);
+        let period = (unit.price(1.0, dt, TypeFlag::Call)
+            - unit.price(1.0 + cap, dt, TypeFlag::Call))
+            * (R * dt).exp();
+
+        let exact = 100.0 * 4.0 * period * (-R).exp();
+
+        let cliquet = CliquetOption::new(quarterly(), 100.0).with_local_bounds(0.0, cap);
+        let config = StochasticProcessConfig::new(100.0, 0.0, 1.0, 100, 20_000, true).with_seed(19);
+        let estimate = cliquet.price_monte_carlo(&GeometricBrownianMotion::new(R, V), &config, R);
+
+        assert!((estimate.price - exact).abs() < 3.0 * estimate.standard_error);
+    }
+
+    #[test]
+    fn test_cliquet_global_floor() {
+        let gbm = GeometricBrownianMotion::new(R, V);
+        let floor = 0.02;
+
+        let cliquet = CliquetOption::new(quarterly(), 100.0)
+            .with_local_bounds(-0.03, 0.03)
+            .with_global_bounds(floor, f64::INFINITY);
+        let config = StochasticProcessConfig::new(100.0, 0.0, 1.0, 100, 5_000, true).with_seed(23);
+        let estimate = cliquet.price_monte_carlo(&gbm, &config, R);
+
+        // The floor is guaranteed, and the sum of the returns is capped at 12%.
+        assert!(estimate.price >= 100.0 * floor * (-R).exp());
+        assert!(estimate.price <= 100.0 * 0.12 * (-R).exp());
+    }
+
+    #[test]
+    #[should_panic(expected = "positive and increasing")]
+    fn test_cliquet_unordered_resets_panic() {
+        CliquetOption::new(vec![0.5, 0.25], 1.0);
+    }
+}
@@ -0,0 +1,307 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Discrete dividend schedules, and European pricing with the escrowed
+//! dividend model.
+//!
+//! On each ex-date the stock drops by the cash amount, or by the given
+//! fraction of its price. The escrowed spot is the present value of the
+//! stock's forward after the dividends to expiry, i.e. the spot less what
+//! the holder of the stock (rather than the option) receives. It follows
+//! geometric Brownian motion, so European options are priced by
+//! Black-Scholes on the escrowed spot.
+
+use super::{BlackScholes73, EuropeanVanillaOption, GeneralisedBlackScholesMerton};
+use time::Date;
+use RustQuant_error::RustQuantError;
+use RustQuant_time::{today, DayCountConvention};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS & ENUMS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// A single discrete dividend.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Dividend {
+    /// A fixed cash amount.
+    Cash(f64),
+
+    /// A fraction of the stock price on the ex-date.
+    Yield(f64),
+}
+
+/// Discrete dividends, by ex-date.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DividendSchedule {
+    /// Ex-dates and dividends, in date order.
+    pub dividends: Vec<(Date, Dividend)>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl DividendSchedule {
+    /// Create an empty dividend schedule.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a cash dividend of `amount`, going ex on `ex_date`.
+    ///
+    /// # Panics
+    /// Panics if `amount` is negative.
+    pub fn with_cash(self, ex_date: Date, amount: f64) -> Self {
+        assert!(amount >= 0.0, "Cash dividends must be non-negative.");
+
+        self.with_dividend(ex_date, Dividend::Cash(amount))
+    }
+
+    /// Add a dividend of `fraction` of the stock price, going ex on `ex_date`.
+    ///
+    /// # Panics
+    /// Panics if `fraction` is not in $[0, 1)$.
+    pub fn with_yield(self, ex_date: Date, fraction: f64) -> Self {
+        assert!(
+            (0.0..1.0).contains(&fraction),
+            "Dividend yields must be in [0, 1)."
+        );
+
+        self.with_dividend(ex_date, Dividend::Yield(fraction))
+    }
+
+    /// Insert a dividend after any others on or before its ex-date.
+    fn with_dividend(mut self, ex_date: Date, dividend: Dividend) -> Self {
+        let index = self.dividends.partition_point(|&(date, _)| date <= ex_date);
+        self.dividends.insert(index, (ex_date, dividend));
+        self
+    }
+
+    /// Escrowed spot: the spot less the present value of the dividends that
+    /// go ex after `valuation_date`, up to and including `expiry`.
+    ///
+    /// A cash dividend is reduced by any proportional dividends after it,
+    /// since they are paid on a lower stock price.
+    ///
+    /// # Arguments:
+    /// * `s` - Underlying price on the valuation date.
+    /// * `r` - Risk-free rate.
+    /// * `valuation_date` - The valuation date.
+    /// * `expiry` - The option's expiry date.
+    /// * `day_count` - Day count convention for the times to the ex-dates.
+    pub fn escrowed_spot(
+        &self,
+        s: f64,
+        r: f64,
+        valuation_date: Date,
+        expiry: Date,
+        day_count: DayCountConvention,
+    ) -> f64 {
+        // Walk backwards from expiry, so each cash dividend is scaled by the
+        // proportional dividends paid after it.
+        self.dividends
+            .iter()
+            .rev()
+            .filter(|&&(date, _)| date > valuation_date && date <= expiry)
+            .fold(
+                (s, 1.0),
+                |(escrowed, retained), &(date, dividend)| match dividend {
+                    Dividend::Cash(amount) => {
+                        let t = day_count.day_count_factor(valuation_date, date);
+                        (escrowed - retained * amount * (-r * t).exp(), retained)
+                    }
+                    Dividend::Yield(fraction) => {
+                        (escrowed * (1.0 - fraction), retained * (1.0 - fraction))
+                    }
+                },
+            )
+            .0
+    }
+}
+
+impl EuropeanVanillaOption {
+    /// Price of the option under Black-Scholes with discrete dividends,
+    /// using the escrowed dividend model.
+    ///
+    /// Times to the ex-dates and to expiry are both measured in the
+    /// option's day count.
+    ///
+    /// # Arguments:
+    /// * `s` - Underlying price.
+    /// * `r` - Risk-free rate.
+    /// * `v` - Volatility of the escrowed spot.
+    /// * `dividends` - The dividend schedule.
+    ///
+    /// # Errors
+    /// - `RustQuantError::InvalidArgument` if the dividends to expiry are
+    ///   worth as much as the stock.
+    pub fn price_with_dividends(
+        &self,
+        s: f64,
+        r: f64,
+        v: f64,
+        dividends: &DividendSchedule,
+    ) -> Result<f64, RustQuantError> {
+        let valuation_date = today();
+        let escrowed = dividends.escrowed_spot(s, r, valuation_date, self.expiry, self.day_count);
+
+        if escrowed <= 0.0 {
+            return Err(RustQuantError::InvalidArgument(
+                "The dividends to expiry must be worth less than the stock.".to_string(),
+            ));
+        }
+
+        let t = self.time_to_expiry().max(0.0);
+
+        Ok(BlackScholes73::new(escrowed, r, v).price(self.strike, t, self.type_flag))
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_dividends {
+    use super::*;
+    use crate::{Merton73, TypeFlag};
+    use time::Duration;
+    use RustQuant_time::year_fraction;
+    use RustQuant_utils::{assert_approx_equal, RUSTQUANT_EPSILON};
+
+    const DAY_COUNT: DayCountConvention = DayCountConvention::Actual_365_25;
+
+    #[test]
+    fn test_no_dividends() {
+        let expiry = today() + Duration::days(365);
+        let t = year_fraction(today(), expiry);
+
+        for type_flag in [TypeFlag::Call, TypeFlag::Put] {
+            let option = EuropeanVanillaOption::new(105.0, expiry, type_flag);
+
+            assert_approx_equal!(
+                option
+                    .price_with_dividends(100.0, 0.05, 0.2, &DividendSchedule::new())
+                    .unwrap(),
+                BlackScholes73::new(100.0, 0.05, 0.2).price(105.0, t, type_flag),
+                RUSTQUANT_EPSILON
+            );
+        }
+    }
+
+    #[test]
+    fn test_cash_dividends() {
+        let expiry = today() + Duration::days(365);
+        let (first, second) = (today() + Duration::days(90), today() + Duration::days(270));
+
+        // Dividends before today or after expiry do not count.
+        let dividends = DividendSchedule::new()
+            .with_cash(second, 2.0)
+            .with_cash(today() - Duration::days(30), 5.0)
+            .with_cash(first, 1.5)
+            .with_cash(expiry + Duration::days(1), 5.0);
+
+        let escrowed = 100.0
+            - 1.5 * f64::exp(-0.05 * year_fraction(today(), first))
+            - 2.0 * f64::exp(-0.05 * year_fraction(today(), second));
+
+        assert_approx_equal!(
+            dividends.escrowed_spot(100.0, 0.05, today(), expiry, DAY_COUNT),
+            escrowed,
+            RUSTQUANT_EPSILON
+        );
+
+        let t = year_fraction(today(), expiry);
+        let option = EuropeanVanillaOption::new(100.0, expiry, TypeFlag::Put);
+
+        assert_approx_equal!(
+            option
+                .price_with_dividends(100.0, 0.05, 0.2, &dividends)
+                .unwrap(),
+            BlackScholes73::new(escrowed, 0.05, 0.2).price(100.0, t, TypeFlag::Put),
+            RUSTQUANT_EPSILON
+        );
+    }
+
+    #[test]
+    fn test_yield_matches_continuous_dividend_yield() {
+        // A proportional dividend of y has the same forward as a continuous
+        // yield of -ln(1 - y) / T.
+        let expiry = today() + Duration::days(365);
+        let t = year_fraction(today(), expiry);
+        let dividends = DividendSchedule::new().with_yield(today() + Duration::days(180), 0.03);
+        let q = -f64::ln(1.0 - 0.03) / t;
+
+        for type_flag in [TypeFlag::Call, TypeFlag::Put] {
+            let option = EuropeanVanillaOption::new(95.0, expiry, type_flag);
+
+            assert_approx_equal!(
+                option
+                    .price_with_dividends(100.0, 0.04, 0.25, &dividends)
+                    .unwrap(),
+                Merton73::new(100.0, 0.04, q, 0.25).price(95.0, t, type_flag),
+                1e-12
+            );
+        }
+    }
+
+    #[test]
+    fn test_cash_dividend_before_yield() {
+        // The stock pays 2 in cash, then 10% of what is left.
+        let expiry = today() + Duration::days(365);
+        let cash_date = today() + Duration::days(100);
+        let dividends = DividendSchedule::new()
+            .with_yield(today() + Duration::days(200), 0.1)
+            .with_cash(cash_date, 2.0);
+
+        let pv = 2.0 * f64::exp(-0.05 * year_fraction(today(), cash_date));
+
+        assert_approx_equal!(
+            dividends.escrowed_spot(100.0, 0.05, today(), expiry, DAY_COUNT),
+            (100.0 - pv) * 0.9,
+            RUSTQUANT_EPSILON
+        );
+    }
+
+    #[test]
+    fn test_dividends_use_option_day_count() {
+        let day_count = DayCountConvention::Actual_360;
+        let expiry = today() + Duration::days(365);
+        let ex_date = today() + Duration::days(180);
+        let dividends = DividendSchedule::new().with_cash(ex_date, 2.0);
+        let option =
+            EuropeanVanillaOption::new(100.0, expiry, TypeFlag::Call).with_day_count(day_count);
+
+        let escrowed = 100.0 - 2.0 * f64::exp(-0.05 * 180.0 / 360.0);
+
+        assert_approx_equal!(
+            dividends.escrowed_spot(100.0, 0.05, today(), expiry, day_count),
+            escrowed,
+            RUSTQUANT_EPSILON
+        );
+        assert_approx_equal!(
+            option
+                .price_with_dividends(100.0, 0.05, 0.2, &dividends)
+                .unwrap(),
+            BlackScholes73::new(escrowed, 0.05, 0.2).price(100.0, 365.0 / 360.0, TypeFlag::Call),
+            RUSTQUANT_EPSILON
+        );
+    }
+
+    #[test]
+    fn test_dividends_worth_more_than_stock() {
+        let expiry = today() + Duration::days(365);
+        let dividends = DividendSchedule::new().with_cash(today() + Duration::days(10), 150.0);
+        let option = EuropeanVanillaOption::new(100.0, expiry, TypeFlag::Call);
+
+        assert!(option
+            .price_with_dividends(100.0, 0.05, 0.2, &dividends)
+            .is_err());
+    }
+}
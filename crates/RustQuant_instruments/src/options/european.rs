@@ -0,0 +1,583 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Quasi-Monte Carlo pricing of European options, adjoint (AAD) Greeks,
+//! and Newton implied volatility solvers.
+
+use super::EuropeanVanillaOption;
+use crate::options::option_models::bsm;
+use crate::{Payoff, TypeFlag};
+use errorfunctions::RealErrorFunctions;
+use std::f64::consts::SQRT_2;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+use RustQuant_autodiff::{variable::Variable, Accumulate, Gradient, Graph};
+use RustQuant_error::RustQuantError;
+use RustQuant_math::{gaussian::N, Distribution, SobolSequence, SOBOL_MAX_DIMENSION};
+use RustQuant_stochastics::BrownianBridge;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// CONSTANTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Newton iterations stop once the volatility step is below this.
+const NEWTON_VOLATILITY_TOLERANCE: f64 = 1e-12;
+
+/// Maximum number of Newton iterations for implied volatility.
+const NEWTON_MAX_ITERATIONS: usize = 100;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Quasi-Monte Carlo pricer for European options under geometric Brownian motion.
+///
+/// Sobol points are mapped to standard normals through the inverse normal CDF,
+/// and the Brownian path is built with a Brownian bridge, so the first
+/// (best distributed) Sobol coordinate sets the terminal value.
+/// The result is deterministic.
+#[derive(Debug, Clone, Copy)]
+pub struct QuasiMonteCarloPricer {
+    /// Number of Sobol points (paths).
+    n_points: usize,
+
+    /// Number of time steps per path (the dimension of the Sobol points).
+    n_steps: usize,
+}
+
+/// Price and first-order Greeks of a European option, from one reverse
+/// (adjoint) pass over the autodiff tape of its price.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AdjointGreeks {
+    /// Option price.
+    pub price: f64,
+
+    /// Sensitivity to the underlying price.
+    pub delta: f64,
+
+    /// Sensitivity to the volatility.
+    pub vega: f64,
+
+    /// Sensitivity to the passage of time (per year).
+    pub theta: f64,
+
+    /// Sensitivity to the risk-free rate.
+    pub rho: f64,
+}
+
+/// Result of a Newton implied volatility solve.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NewtonImpliedVolatility {
+    /// The implied volatility.
+    pub volatility: f64,
+
+    /// Number of Newton iterations taken.
+    pub iterations: usize,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl QuasiMonteCarloPricer {
+    /// Create a new quasi-Monte Carlo pricer.
+    ///
+    /// Powers of two for `n_points` give the most uniform coverage.
+    ///
+    /// # Errors
+    /// - `RustQuantError::InvalidArgument` if `n_points` is zero, or `n_steps`
+    ///   is zero or greater than [`SOBOL_MAX_DIMENSION`].
+    pub fn new(n_points: usize, n_steps: usize) -> Result<Self, RustQuantError> {
+        if n_points == 0 {
+            return Err(RustQuantError::InvalidArgument(
+                "`n_points` must be at least 1.".to_string(),
+            ));
+        }
+        if n_steps == 0 || n_steps > SOBOL_MAX_DIMENSION {
+            return Err(RustQuantError::InvalidArgument(format!(
+                "`n_steps` must be between 1 and {SOBOL_MAX_DIMENSION}, got {n_steps}."
+            )));
+        }
+
+        Ok(Self { n_points, n_steps })
+    }
+
+    /// Price a European vanilla option.
+    ///
+    /// # Arguments:
+    /// * `option` - The option to price.
+    /// * `s` - Underlying price.
+    /// * `r` - Risk-free rate.
+    /// * `q` - Dividend yield.
+    /// * `v` - Volatility.
+    pub fn price(&self, option: &EuropeanVanillaOption, s: f64, r: f64, q: f64, v: f64) -> f64 {
+        let t = option.time_to_expiry();
+
+        if t <= 0.0 {
+            return option.payoff(s);
+        }
+
+        let times: Vec<f64> = (1..=self.n_steps)
+            .map(|i| t * i as f64 / self.n_steps as f64)
+            .collect();
+
+        let bridge = BrownianBridge::new(&times).expect("Times are positive and increasing.");
+        let mut sobol = SobolSequence::new(self.n_steps).expect("Validated in `new`.");
+
+        // The origin maps to -infinity under the inverse CDF.
+        sobol.skip_points(1);
+
+        let drift = (r - q - 0.5 * v * v) * t;
+
+        let payoffs: f64 = sobol
+            .take(self.n_points)
+            .map(|point| {
+                let normals: Vec<f64> = point.iter().map(|&u| N.inv_cdf(u)).collect();
+                let path = bridge.build(&normals);
+
+                option.payoff(s * (drift + v * path[self.n_steps - 1]).exp())
+            })
+            .sum();
+
+        (-r * t).exp() * payoffs / self.n_points as f64
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ADJOINT GREEKS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Scalars the Black-Scholes-Merton price can be computed in: plain `f64`
+/// values, or autodiff `Variable`s that record the computation on a tape.
+trait Scalar:
+    Copy
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+    + Sub<f64, Output = Self>
+    + Mul<f64, Output = Self>
+    + Div<f64, Output = Self>
+{
+    fn ln(self) -> Self;
+    fn exp(self) -> Self;
+    fn sqrt(self) -> Self;
+    fn erfc(self) -> Self;
+}
+
+impl Scalar for f64 {
+    fn ln(self) -> Self {
+        f64::ln(self)
+    }
+
+    fn exp(self) -> Self {
+        f64::exp(self)
+    }
+
+    fn sqrt(self) -> Self {
+        f64::sqrt(self)
+    }
+
+    fn erfc(self) -> Self {
+        RealErrorFunctions::erfc(self)
+    }
+}
+
+impl<'v> Scalar for Variable<'v> {
+    fn ln(self) -> Self {
+        Variable::ln(self)
+    }
+
+    fn exp(self) -> Self {
+        Variable::exp(self)
+    }
+
+    fn sqrt(self) -> Self {
+        Variable::sqrt(self)
+    }
+
+    fn erfc(self) -> Self {
+        Variable::erfc(self)
+    }
+}
+
+/// Price and first-order Greeks of a European option under the
+/// Black-Scholes-Merton model, by adjoint algorithmic differentiation.
+///
+/// The underlying price, volatility, rate and time to expiry are recorded
+/// as variables on one tape, so a single reverse pass over the price gives
+/// all four sensitivities, whatever their number. The price itself is the
+/// same generic code that runs on plain `f64` values.
+///
+/// # Arguments:
+/// * `option` - The option.
+/// * `s` - Underlying price.
+/// * `r` - Risk-free rate.
+/// * `q` - Dividend yield.
+/// * `v` - Volatility.
+///
+/// # Errors
+/// - `RustQuantError::InvalidArgument` if the option has expired.
+pub fn adjoint_greeks(
+    option: &EuropeanVanillaOption,
+    s: f64,
+    r: f64,
+    q: f64,
+    v: f64,
+) -> Result<AdjointGreeks, RustQuantError> {
+    let t = option.time_to_expiry();
+
+    if t <= 0.0 {
+        return Err(RustQuantError::InvalidArgument(
+            "Greeks are undefined for an expired option.".to_string(),
+        ));
+    }
+
+    let graph = Graph::new();
+    let inputs = graph.vars(&[s, v, r, t]);
+    let price = bsm_price(
+        inputs[0],
+        option.strike,
+        inputs[3],
+        inputs[2],
+        q,
+        inputs[1],
+        option.type_flag,
+    );
+
+    let gradient = price.accumulate().wrt(&inputs);
+
+    Ok(AdjointGreeks {
+        price: price.value(),
+        delta: gradient[0],
+        vega: gradient[1],
+        rho: gradient[2],
+        // The passage of time shortens the time to expiry.
+        theta: -gradient[3],
+    })
+}
+
+/// Black-Scholes-Merton price, generic over the scalar type so the same
+/// code prices with `f64` values or records its adjoint on a tape.
+fn bsm_price<T: Scalar>(s: T, k: f64, t: T, r: T, q: f64, sigma: T, flag: TypeFlag) -> T {
+    let cdf = |x: T| (-x / SQRT_2).erfc() * 0.5;
+
+    let std_dev = sigma * t.sqrt();
+    let d1 = ((s / k).ln() + (r - q) * t + std_dev * std_dev * 0.5) / std_dev;
+    let d2 = d1 - std_dev;
+
+    let spot = s * (-(t * q)).exp();
+    let strike = (-(r * t)).exp() * k;
+
+    match flag {
+        TypeFlag::Call => spot * cdf(d1) - strike * cdf(d2),
+        TypeFlag::Put => strike * cdf(-d2) - spot * cdf(-d1),
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLIED VOLATILITY
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Implied volatility of a European option by Newton's method, with vega
+/// read from the autodiff tape of the Black-Scholes-Merton price.
+///
+/// Pricing and vega share one code path, so there is no hand-coded vega.
+///
+/// # Arguments:
+/// * `option` - The option.
+/// * `price` - Market price of the option.
+/// * `s` - Underlying price.
+/// * `r` - Risk-free rate.
+/// * `q` - Dividend yield.
+///
+/// # Errors
+/// - `RustQuantError::InvalidArgument` if the option has expired, or `price`
+///   is outside the no-arbitrage bounds.
+/// - `RustQuantError::ComputationError` if the iteration does not converge.
+pub fn autodiff_implied_volatility(
+    option: &EuropeanVanillaOption,
+    price: f64,
+    s: f64,
+    r: f64,
+    q: f64,
+) -> Result<NewtonImpliedVolatility, RustQuantError> {
+    let (k, t, flag) = implied_volatility_inputs(option, price, s, r, q)?;
+
+    newton_implied_volatility(price, initial_volatility(s, k, t, r, q), |v| {
+        let graph = Graph::new();
+        let sigma = graph.var(v);
+        let value = bsm_price(graph.var(s), k, graph.var(t), graph.var(r), q, sigma, flag);
+
+        (value.value(), value.accumulate().wrt(&sigma))
+    })
+}
+
+/// Implied volatility of a European option by Newton's method, with the
+/// analytic Black-Scholes-Merton vega.
+///
+/// # Arguments:
+/// * `option` - The option.
+/// * `price` - Market price of the option.
+/// * `s` - Underlying price.
+/// * `r` - Risk-free rate.
+/// * `q` - Dividend yield.
+///
+/// # Errors
+/// - `RustQuantError::InvalidArgument` if the option has expired, or `price`
+///   is outside the no-arbitrage bounds.
+/// - `RustQuantError::ComputationError` if the iteration does not converge.
+pub fn analytic_implied_volatility(
+    option: &EuropeanVanillaOption,
+    price: f64,
+    s: f64,
+    r: f64,
+    q: f64,
+) -> Result<NewtonImpliedVolatility, RustQuantError> {
+    let (k, t, flag) = implied_volatility_inputs(option, price, s, r, q)?;
+    let b = r - q;
+
+    newton_implied_volatility(price, initial_volatility(s, k, t, r, q), |v| {
+        let value = match flag {
+            TypeFlag::Call => bsm::call_price(s, k, t, r, b, v),
+            TypeFlag::Put => bsm::put_price(s, k, t, r, b, v),
+        };
+
+        (value, bsm::call_vega(s, k, t, r, b, v))
+    })
+}
+
+/// Validate the inputs, returning the strike, time to expiry and type.
+fn implied_volatility_inputs(
+    option: &EuropeanVanillaOption,
+    price: f64,
+    s: f64,
+    r: f64,
+    q: f64,
+) -> Result<(f64, f64, TypeFlag), RustQuantError> {
+    let k = option.strike;
+    let t = option.time_to_expiry();
+
+    if t <= 0.0 {
+        return Err(RustQuantError::InvalidArgument(
+            "Implied volatility is undefined for an expired option.".to_string(),
+        ));
+    }
+
+    let spot = s * (-q * t).exp();
+    let strike = k * (-r * t).exp();
+
+    let (lower, upper) = match option.type_flag {
+        TypeFlag::Call => ((spot - strike).max(0.0), spot),
+        TypeFlag::Put => ((strike - spot).max(0.0), strike),
+    };
+
+    if price.is_nan() || price <= lower || price >= upper {
+        return Err(RustQuantError::InvalidArgument(format!(
+            "The price must be within the no-arbitrage bounds ({lower}, {upper}), got {price}."
+        )));
+    }
+
+    Ok((k, t, option.type_flag))
+}
+
+/// Manaster-Koehler starting point, from which Newton's method converges
+/// monotonically for Black-Scholes prices.
+fn initial_volatility(s: f64, k: f64, t: f64, r: f64, q: f64) -> f64 {
+    (2.0 * ((s / k).ln() + (r - q) * t).abs() / t)
+        .sqrt()
+        .max(0.1)
+}
+
+/// Newton iteration on `price_and_vega(v) = (price, vega)`.
+fn newton_implied_volatility<F>(
+    target: f64,
+    initial: f64,
+    price_and_vega: F,
+) -> Result<NewtonImpliedVolatility, RustQuantError>
+where
+    F: Fn(f64) -> (f64, f64),
+{
+    let mut volatility = initial;
+
+    for iterations in 1..=NEWTON_MAX_ITERATIONS {
+        let (price, vega) = price_and_vega(volatility);
+
+        if vega.is_nan() || vega <= 0.0 {
+            return Err(RustQuantError::ComputationError(format!(
+                "Vega vanished at volatility {volatility}."
+            )));
+        }
+
+        let step = (price - target) / vega;
+
+        // Halve the volatility rather than stepping to a negative value.
+        volatility = if step < volatility {
+            volatility - step
+        } else {
+            0.5 * volatility
+        };
+
+        if step.abs() < NEWTON_VOLATILITY_TOLERANCE {
+            return Ok(NewtonImpliedVolatility {
+                volatility,
+                iterations,
+            });
+        }
+    }
+
+    Err(RustQuantError::ComputationError(format!(
+        "Implied volatility did not converge in {NEWTON_MAX_ITERATIONS} iterations."
+    )))
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_european {
+    use super::*;
+    use crate::{GeneralisedBlackScholesMerton, Merton73};
+    use rand::Rng;
+    use rand_distr::StandardNormal;
+    use time::Duration;
+    use RustQuant_stochastics::seeded_rng;
+    use RustQuant_time::today;
+    use RustQuant_utils::{assert_approx_equal, RUSTQUANT_EPSILON};
+
+    #[test]
+    fn test_qmc_beats_pseudo_random_mc() {
+        let (s, k, r, q, v) = (100.0, 100.0, 0.05, 0.0, 0.2);
+        let n = 4096;
+
+        let option = EuropeanVanillaOption::new(k, today() + Duration::days(365), TypeFlag::Call);
+        let t = option.time_to_expiry();
+        let exact = Merton73::new(s, r, q, v).price(k, t, TypeFlag::Call);
+
+        let qmc = QuasiMonteCarloPricer::new(n, 1)
+            .unwrap()
+            .price(&option, s, r, q, v);
+
+        // Pseudo-random estimate with the same number of paths.
+        let mut rng = seeded_rng(42);
+        let discounted: Vec<f64> = (0..n)
+            .map(|_| {
+                let z: f64 = rng.sample(StandardNormal);
+                let s_t = s * ((r - q - 0.5 * v * v) * t + v * t.sqrt() * z).exp();
+                (-r * t).exp() * option.payoff(s_t)
+            })
+            .collect();
+        let mean = discounted.iter().sum::<f64>() / n as f64;
+        let variance = discounted.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1) as f64;
+        let mc_standard_error = (variance / n as f64).sqrt();
+
+        assert!((qmc - exact).abs() < 0.1 * mc_standard_error);
+    }
+
+    #[test]
+    fn test_qmc_terminal_value_set_by_first_dimension() {
+        let option =
+            EuropeanVanillaOption::new(110.0, today() + Duration::days(182), TypeFlag::Put);
+
+        let one_step = QuasiMonteCarloPricer::new(1024, 1).unwrap();
+        let many_steps = QuasiMonteCarloPricer::new(1024, 16).unwrap();
+
+        // The bridge builds the terminal value from the first coordinate only.
+        assert_approx_equal!(
+            one_step.price(&option, 100.0, 0.03, 0.01, 0.3),
+            many_steps.price(&option, 100.0, 0.03, 0.01, 0.3),
+            1e-12
+        );
+    }
+
+    #[test]
+    fn test_autodiff_implied_volatility_matches_analytic_vega() {
+        let (s, r, q) = (100.0, 0.05, 0.02);
+
+        for (k, v, flag) in [
+            (100.0, 0.2, TypeFlag::Call),
+            (80.0, 0.35, TypeFlag::Put),
+            (130.0, 0.15, TypeFlag::Call),
+            (120.0, 0.5, TypeFlag::Put),
+        ] {
+            let option = EuropeanVanillaOption::new(k, today() + Duration::days(365), flag);
+            let t = option.time_to_expiry();
+            let price = Merton73::new(s, r, q, v).price(k, t, flag);
+
+            let autodiff = autodiff_implied_volatility(&option, price, s, r, q).unwrap();
+            let analytic = analytic_implied_volatility(&option, price, s, r, q).unwrap();
+
+            assert_eq!(autodiff.iterations, analytic.iterations);
+            assert!(autodiff.iterations <= 8);
+            assert_approx_equal!(autodiff.volatility, analytic.volatility, RUSTQUANT_EPSILON);
+            assert_approx_equal!(autodiff.volatility, v, RUSTQUANT_EPSILON);
+        }
+    }
+
+    #[test]
+    fn test_adjoint_greeks_match_closed_form() {
+        let (s, r, q) = (100.0, 0.05, 0.02);
+
+        for (k, v, flag) in [
+            (100.0, 0.2, TypeFlag::Call),
+            (80.0, 0.35, TypeFlag::Put),
+            (130.0, 0.15, TypeFlag::Call),
+        ] {
+            let option = EuropeanVanillaOption::new(k, today() + Duration::days(365), flag);
+            let t = option.time_to_expiry();
+            let model = Merton73::new(s, r, q, v);
+
+            let greeks = adjoint_greeks(&option, s, r, q, v).unwrap();
+
+            assert_approx_equal!(greeks.price, model.price(k, t, flag), 1e-10);
+            assert_approx_equal!(greeks.delta, model.delta(k, t, flag), 1e-10);
+            assert_approx_equal!(greeks.vega, model.vega(k, t, flag), 1e-10);
+            assert_approx_equal!(greeks.theta, model.theta(k, t, flag), 1e-10);
+            assert_approx_equal!(greeks.rho, model.rho(k, t, flag), 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_generic_price_on_plain_values() {
+        let (s, k, t, r, q, v) = (100.0, 95.0, 0.75, 0.04, 0.01, 0.25);
+
+        for flag in [TypeFlag::Call, TypeFlag::Put] {
+            assert_approx_equal!(
+                bsm_price(s, k, t, r, q, v, flag),
+                Merton73::new(s, r, q, v).price(k, t, flag),
+                1e-12
+            );
+        }
+    }
+
+    #[test]
+    fn test_adjoint_greeks_expired_option() {
+        let option = EuropeanVanillaOption::new(100.0, today(), TypeFlag::Call);
+
+        assert!(adjoint_greeks(&option, 100.0, 0.05, 0.0, 0.2).is_err());
+    }
+
+    #[test]
+    fn test_implied_volatility_outside_bounds() {
+        let option =
+            EuropeanVanillaOption::new(100.0, today() + Duration::days(365), TypeFlag::Call);
+
+        assert!(autodiff_implied_volatility(&option, 0.0, 100.0, 0.05, 0.0).is_err());
+        assert!(autodiff_implied_volatility(&option, 100.0, 100.0, 0.05, 0.0).is_err());
+        assert!(analytic_implied_volatility(&option, -1.0, 100.0, 0.05, 0.0).is_err());
+    }
+
+    #[test]
+    fn test_qmc_invalid_arguments() {
+        assert!(QuasiMonteCarloPricer::new(0, 1).is_err());
+        assert!(QuasiMonteCarloPricer::new(1024, 0).is_err());
+        assert!(QuasiMonteCarloPricer::new(1024, SOBOL_MAX_DIMENSION + 1).is_err());
+    }
+}
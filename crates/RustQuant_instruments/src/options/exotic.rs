@@ -0,0 +1,515 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Closed-form pricers for exotic options.
+
+use super::{GeneralisedBlackScholesMerton, Merton73, TypeFlag};
+use RustQuant_math::{bivariate_normal_cdf, gauss_legendre, Distribution, N};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Margrabe (1978) price of the option to exchange asset 2 for asset 1,
+/// paying `max(S1(T) - S2(T), 0)` at maturity.
+///
+/// The price does not depend on the risk-free rate, since asset 2 acts
+/// as the numeraire. The volatility of the ratio `S1 / S2` is
+/// `sqrt(sigma1^2 + sigma2^2 - 2 rho sigma1 sigma2)`.
+///
+/// # Arguments:
+/// * `s1` - Price of the asset received.
+/// * `s2` - Price of the asset delivered.
+/// * `sigma1` - Volatility of asset 1.
+/// * `sigma2` - Volatility of asset 2.
+/// * `rho` - Correlation between the two assets.
+/// * `q1` - Dividend yield of asset 1.
+/// * `q2` - Dividend yield of asset 2.
+/// * `maturity` - Time to maturity in years.
+#[allow(clippy::too_many_arguments)]
+pub fn margrabe_price(
+    s1: f64,
+    s2: f64,
+    sigma1: f64,
+    sigma2: f64,
+    rho: f64,
+    q1: f64,
+    q2: f64,
+    maturity: f64,
+) -> f64 {
+    let forward1 = s1 * (-q1 * maturity).exp();
+    let forward2 = s2 * (-q2 * maturity).exp();
+
+    let sigma = (sigma1 * sigma1 + sigma2 * sigma2 - 2.0 * rho * sigma1 * sigma2)
+        .max(0.0)
+        .sqrt();
+    let std_dev = sigma * maturity.sqrt();
+
+    // No variance left: the option is worth its intrinsic value.
+    if std_dev <= 0.0 {
+        return (forward1 - forward2).max(0.0);
+    }
+
+    let d1 = ((forward1 / forward2).ln() + 0.5 * std_dev * std_dev) / std_dev;
+    let d2 = d1 - std_dev;
+
+    forward1 * N.cdf(d1) - forward2 * N.cdf(d2)
+}
+
+/// Deltas of the Margrabe (1978) exchange option with respect to `s1` and
+/// `s2`, with the same arguments as [`margrabe_price`].
+///
+/// The price is homogeneous of degree one in the two asset prices, so
+/// `price = s1 * delta1 + s2 * delta2` and the option is replicated by
+/// holding `delta1` of asset 1 and `delta2` (negative) of asset 2.
+#[allow(clippy::too_many_arguments)]
+pub fn margrabe_deltas(
+    s1: f64,
+    s2: f64,
+    sigma1: f64,
+    sigma2: f64,
+    rho: f64,
+    q1: f64,
+    q2: f64,
+    maturity: f64,
+) -> (f64, f64) {
+    let discount1 = (-q1 * maturity).exp();
+    let discount2 = (-q2 * maturity).exp();
+
+    let sigma = (sigma1 * sigma1 + sigma2 * sigma2 - 2.0 * rho * sigma1 * sigma2)
+        .max(0.0)
+        .sqrt();
+    let std_dev = sigma * maturity.sqrt();
+
+    // No variance left: hold both assets if in the money, neither otherwise.
+    if std_dev <= 0.0 {
+        return match s1 * discount1 > s2 * discount2 {
+            true => (discount1, -discount2),
+            false => (0.0, 0.0),
+        };
+    }
+
+    let d1 = ((s1 * discount1 / (s2 * discount2)).ln() + 0.5 * std_dev * std_dev) / std_dev;
+    let d2 = d1 - std_dev;
+
+    (discount1 * N.cdf(d1), -discount2 * N.cdf(d2))
+}
+
+/// Kirk (1995) approximate price of a spread option on two futures (or
+/// forward) prices, paying `max(F1(T) - F2(T) - K, 0)` for a call and
+/// `max(K - F1(T) + F2(T), 0)` for a put.
+///
+/// `F2(T) + K` is treated as lognormal, which is accurate when `K` is
+/// small relative to `F2`. With `K = 0` it reduces to Margrabe's formula.
+/// Options on spot prices can be priced with the forwards `S e^{(r - q) T}`.
+///
+/// # Arguments:
+/// * `flag` - Call or put on the spread.
+/// * `f1` - Futures price of the asset received.
+/// * `f2` - Futures price of the asset delivered.
+/// * `strike` - Strike of the spread.
+/// * `sigma1` - Volatility of asset 1.
+/// * `sigma2` - Volatility of asset 2.
+/// * `rho` - Correlation between the two assets.
+/// * `r` - Risk-free rate.
+/// * `maturity` - Time to maturity in years.
+///
+/// # Panics
+/// Panics if `f2 + strike` is not positive.
+#[allow(clippy::too_many_arguments)]
+pub fn kirk_spread_price(
+    flag: TypeFlag,
+    f1: f64,
+    f2: f64,
+    strike: f64,
+    sigma1: f64,
+    sigma2: f64,
+    rho: f64,
+    r: f64,
+    maturity: f64,
+) -> f64 {
+    let shifted = f2 + strike;
+
+    assert!(shifted > 0.0, "Kirk's approximation needs F2 + K > 0.");
+
+    let weight = f2 / shifted;
+    let sigma = (sigma1 * sigma1 - 2.0 * rho * sigma1 * sigma2 * weight
+        + sigma2 * sigma2 * weight * weight)
+        .max(0.0)
+        .sqrt();
+
+    let model = super::Black76::new(f1 / shifted, r, sigma);
+
+    shifted * model.price(1.0, maturity, flag)
+}
+
+/// Price of a spread option on two futures (or forward) prices, by one
+/// dimensional integration (Pearson, 1995).
+///
+/// Conditional on asset 2, asset 1 is lognormal, so the option is a Black
+/// (1976) option struck at `F2(T) + K`. The conditional prices are
+/// integrated against the normal density with Gauss-Legendre quadrature,
+/// which gives the exact price to about machine precision.
+///
+/// # Arguments:
+/// * `flag` - Call or put on the spread.
+/// * `f1` - Futures price of the asset received.
+/// * `f2` - Futures price of the asset delivered.
+/// * `strike` - Strike of the spread.
+/// * `sigma1` - Volatility of asset 1.
+/// * `sigma2` - Volatility of asset 2.
+/// * `rho` - Correlation between the two assets.
+/// * `r` - Risk-free rate.
+/// * `maturity` - Time to maturity in years.
+#[allow(clippy::too_many_arguments)]
+pub fn spread_option_price(
+    flag: TypeFlag,
+    f1: f64,
+    f2: f64,
+    strike: f64,
+    sigma1: f64,
+    sigma2: f64,
+    rho: f64,
+    r: f64,
+    maturity: f64,
+) -> f64 {
+    let t = maturity;
+    let discount = (-r * t).exp();
+
+    // Conditional volatility of asset 1, given asset 2.
+    let residual = sigma1 * (1.0 - rho * rho).max(0.0).sqrt() * t.sqrt();
+
+    // Undiscounted call on asset 1 struck at F2(T) + K, given the normal
+    // variate z driving asset 2.
+    let conditional_call = |z: f64| {
+        let f2_t = f2 * (sigma2 * t.sqrt() * z - 0.5 * sigma2 * sigma2 * t).exp();
+        let f1_t = f1 * (rho * sigma1 * t.sqrt() * z - 0.5 * rho * rho * sigma1 * sigma1 * t).exp();
+        let k = f2_t + strike;
+
+        if k <= 0.0 || residual <= 0.0 {
+            return (f1_t - k).max(0.0);
+        }
+
+        let d1 = ((f1_t / k).ln() + 0.5 * residual * residual) / residual;
+
+        f1_t * N.cdf(d1) - k * N.cdf(d1 - residual)
+    };
+
+    // The normal density is negligible beyond ten standard deviations.
+    let call = discount
+        * (-10..10)
+            .map(|a| {
+                let a = f64::from(a);
+                gauss_legendre(|z| conditional_call(z) * N.pdf(z), a, a + 1.0, 32)
+            })
+            .sum::<f64>();
+
+    match flag {
+        TypeFlag::Call => call,
+        TypeFlag::Put => call - discount * (f1 - f2 - strike),
+    }
+}
+
+/// Geske (1979) price of a compound option: an option, expiring at
+/// `outer_expiry`, to buy or sell a European option expiring at
+/// `inner_expiry` for `outer_strike`.
+///
+/// The outer option is exercised when the underlying is beyond the
+/// critical price at which the inner option is worth `outer_strike`, so
+/// the price involves the bivariate normal distribution with correlation
+/// `sqrt(outer_expiry / inner_expiry)`. See Haug (2007), section 4.6.
+///
+/// # Arguments:
+/// * `outer` - Call or put on the inner option.
+/// * `inner` - Type of the inner option.
+/// * `s` - Underlying price.
+/// * `outer_strike` - Price paid (or received) for the inner option.
+/// * `inner_strike` - Strike of the inner option.
+/// * `outer_expiry` - Time to expiry of the compound option in years.
+/// * `inner_expiry` - Time to expiry of the inner option in years.
+/// * `r` - Risk-free rate.
+/// * `q` - Dividend yield.
+/// * `v` - Volatility.
+///
+/// # Panics
+/// Panics if the inner option does not expire after the outer one, or if
+/// `outer_strike` is at least the largest value of an inner put.
+#[allow(clippy::too_many_arguments)]
+pub fn compound_option_price(
+    outer: TypeFlag,
+    inner: TypeFlag,
+    s: f64,
+    outer_strike: f64,
+    inner_strike: f64,
+    outer_expiry: f64,
+    inner_expiry: f64,
+    r: f64,
+    q: f64,
+    v: f64,
+) -> f64 {
+    assert!(
+        0.0 < outer_expiry && outer_expiry < inner_expiry,
+        "The inner option must expire after the compound option."
+    );
+
+    let (k1, k2, t1, t2) = (outer_strike, inner_strike, outer_expiry, inner_expiry);
+
+    let i = compound::critical_price(inner, k1, k2, t2 - t1, r, q, v);
+
+    let y1 = ((s / i).ln() + (r - q + 0.5 * v * v) * t1) / (v * t1.sqrt());
+    let y2 = y1 - v * t1.sqrt();
+    let z1 = ((s / k2).ln() + (r - q + 0.5 * v * v) * t2) / (v * t2.sqrt());
+    let z2 = z1 - v * t2.sqrt();
+    let rho = (t1 / t2).sqrt();
+
+    let asset = s * (-q * t2).exp();
+    let cash = k2 * (-r * t2).exp();
+    let premium = k1 * (-r * t1).exp();
+
+    let m = bivariate_normal_cdf;
+
+    match (outer, inner) {
+        (TypeFlag::Call, TypeFlag::Call) => {
+            asset * m(z1, y1, rho) - cash * m(z2, y2, rho) - premium * N.cdf(y2)
+        }
+        (TypeFlag::Put, TypeFlag::Call) => {
+            cash * m(z2, -y2, -rho) - asset * m(z1, -y1, -rho) + premium * N.cdf(-y2)
+        }
+        (TypeFlag::Call, TypeFlag::Put) => {
+            cash * m(-z2, -y2, rho) - asset * m(-z1, -y1, rho) - premium * N.cdf(-y2)
+        }
+        (TypeFlag::Put, TypeFlag::Put) => {
+            asset * m(-z1, y1, -rho) - cash * m(-z2, y2, -rho) + premium * N.cdf(y2)
+        }
+    }
+}
+
+mod compound {
+    use super::{GeneralisedBlackScholesMerton, Merton73, TypeFlag};
+
+    /// Underlying price at which the inner option, with `tau` years left,
+    /// is worth `k1`.
+    ///
+    /// The inner option value is convex and monotone in the underlying, so
+    /// Newton's method converges monotonically from a starting point on the
+    /// side of the root where the option is worth more than `k1`.
+    pub(crate) fn critical_price(
+        inner: TypeFlag,
+        k1: f64,
+        k2: f64,
+        tau: f64,
+        r: f64,
+        q: f64,
+        v: f64,
+    ) -> f64 {
+        let (growth, discount) = ((q * tau).exp(), (-r * tau).exp());
+
+        // Lower bounds on the option value, S e^{-q tau} - K e^{-r tau}
+        // for a call and K e^{-r tau} - S e^{-q tau} for a put, give the start.
+        let mut i = match inner {
+            TypeFlag::Call => (k1 + k2 * discount) * growth,
+            TypeFlag::Put => {
+                assert!(
+                    k1 < k2 * discount,
+                    "The outer strike must be below the largest value of the inner put."
+                );
+
+                ((k2 * discount - k1) * growth).max(f64::MIN_POSITIVE)
+            }
+        };
+
+        for _ in 0..100 {
+            let model = Merton73::new(i, r, q, v);
+            let error = model.price(k2, tau, inner) - k1;
+
+            if error.abs() <= 1e-12 * k1.max(1.0) {
+                break;
+            }
+
+            i -= error / model.delta(k2, tau, inner);
+        }
+
+        i
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_exotic {
+    use super::*;
+    use RustQuant_utils::{assert_approx_equal, RUSTQUANT_EPSILON};
+
+    #[test]
+    fn test_margrabe_riskless_asset_is_vanilla_call() {
+        // A riskless asset 2 paying q2 = r grows like cash,
+        // so exchanging it for asset 1 is a call struck at s2.
+        let (s, k, r, q, v, t) = (100.0, 95.0, 0.05, 0.02, 0.3, 1.5);
+
+        let margrabe = margrabe_price(s, k, v, 0.0, 0.4, q, r, t);
+        let vanilla = Merton73::new(s, r, q, v).price(k, t, TypeFlag::Call);
+
+        assert_approx_equal!(margrabe, vanilla, RUSTQUANT_EPSILON);
+    }
+
+    #[test]
+    fn test_margrabe_exchange_parity() {
+        // Swapping the assets gives the reverse exchange, and the difference
+        // is the forward exchange: max(a - b, 0) - max(b - a, 0) = a - b.
+        let (s1, s2, sigma1, sigma2, rho, q1, q2, t) =
+            (100.0, 90.0, 0.25, 0.35, 0.5, 0.01, 0.03, 2.0);
+
+        let forward = margrabe_price(s1, s2, sigma1, sigma2, rho, q1, q2, t);
+        let reverse = margrabe_price(s2, s1, sigma2, sigma1, rho, q2, q1, t);
+
+        assert_approx_equal!(
+            forward - reverse,
+            s1 * (-q1 * t).exp() - s2 * (-q2 * t).exp(),
+            RUSTQUANT_EPSILON
+        );
+    }
+
+    #[test]
+    fn test_margrabe_perfectly_correlated_assets() {
+        // Identical volatilities with rho = 1 leave no optionality.
+        let price = margrabe_price(100.0, 90.0, 0.2, 0.2, 1.0, 0.0, 0.0, 1.0);
+
+        assert_approx_equal!(price, 10.0, RUSTQUANT_EPSILON);
+    }
+
+    #[test]
+    fn test_compound_option_haug() {
+        // Haug (2007), section 4.6: put on call with S = 500, K1 = 50,
+        // K2 = 520, T1 = 0.25, T2 = 0.5, r = 0.08, b = 0.05, v = 0.35.
+        // Haug quotes 21.1965; the reference is by direct quadrature of the
+        // discounted payoff at T1.
+        let price = compound_option_price(
+            TypeFlag::Put,
+            TypeFlag::Call,
+            500.0,
+            50.0,
+            520.0,
+            0.25,
+            0.5,
+            0.08,
+            0.03,
+            0.35,
+        );
+
+        assert_approx_equal!(price, 21.196_350_394_352_38, 1e-10);
+    }
+
+    #[test]
+    fn test_compound_option_parity() {
+        // Call on option - put on option = inner option - K1 e^{-r T1}.
+        let (s, k1, k2, t1, t2, r, q, v) = (100.0, 4.0, 105.0, 0.5, 1.5, 0.05, 0.02, 0.3);
+        let model = Merton73::new(s, r, q, v);
+
+        for inner in [TypeFlag::Call, TypeFlag::Put] {
+            let call = compound_option_price(TypeFlag::Call, inner, s, k1, k2, t1, t2, r, q, v);
+            let put = compound_option_price(TypeFlag::Put, inner, s, k1, k2, t1, t2, r, q, v);
+
+            assert!(call > 0.0 && put > 0.0);
+            assert_approx_equal!(
+                call - put,
+                model.price(k2, t2, inner) - k1 * (-r * t1).exp(),
+                1e-10
+            );
+        }
+    }
+
+    #[test]
+    fn test_compound_option_free_outer_strike() {
+        // A call on an option that costs almost nothing is the option itself.
+        let (s, k2, t1, t2, r, q, v) = (100.0, 95.0, 0.25, 1.0, 0.04, 0.01, 0.25);
+        let model = Merton73::new(s, r, q, v);
+
+        for inner in [TypeFlag::Call, TypeFlag::Put] {
+            let price = compound_option_price(TypeFlag::Call, inner, s, 1e-8, k2, t1, t2, r, q, v);
+
+            assert_approx_equal!(price, model.price(k2, t2, inner), 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_margrabe_deltas() {
+        let (s1, s2, sigma1, sigma2, rho, q1, q2, t) =
+            (105.0, 95.0, 0.25, 0.3, 0.6, 0.02, 0.04, 0.8);
+        let (delta1, delta2) = margrabe_deltas(s1, s2, sigma1, sigma2, rho, q1, q2, t);
+
+        let h = 1e-4;
+        let price = |s1, s2| margrabe_price(s1, s2, sigma1, sigma2, rho, q1, q2, t);
+
+        assert_approx_equal!(
+            delta1,
+            (price(s1 + h, s2) - price(s1 - h, s2)) / (2.0 * h),
+            1e-7
+        );
+        assert_approx_equal!(
+            delta2,
+            (price(s1, s2 + h) - price(s1, s2 - h)) / (2.0 * h),
+            1e-7
+        );
+
+        // Euler's theorem for a price homogeneous of degree one.
+        assert_approx_equal!(s1 * delta1 + s2 * delta2, price(s1, s2), 1e-12);
+    }
+
+    #[test]
+    fn test_spread_option_zero_strike_is_margrabe() {
+        // Futures cost nothing to carry, so q = r in Margrabe's formula.
+        let (f1, f2, sigma1, sigma2, rho, r, t) = (110.0, 100.0, 0.3, 0.2, 0.4, 0.05, 1.5);
+        let margrabe = margrabe_price(f1, f2, sigma1, sigma2, rho, r, r, t);
+
+        let exact = spread_option_price(TypeFlag::Call, f1, f2, 0.0, sigma1, sigma2, rho, r, t);
+        let kirk = kirk_spread_price(TypeFlag::Call, f1, f2, 0.0, sigma1, sigma2, rho, r, t);
+
+        assert_approx_equal!(exact, margrabe, 1e-10);
+        assert_approx_equal!(kirk, margrabe, 1e-10);
+    }
+
+    #[test]
+    fn test_spread_option_single_asset_is_black() {
+        // Without volatility, asset 2 is a constant added to the strike.
+        let (f1, f2, k, sigma1, r, t) = (50.0, 20.0, 25.0, 0.35, 0.03, 0.75);
+        let black = crate::Black76::new(f1, r, sigma1);
+
+        for flag in [TypeFlag::Call, TypeFlag::Put] {
+            let exact = spread_option_price(flag, f1, f2, k, sigma1, 0.0, 0.3, r, t);
+
+            assert_approx_equal!(exact, black.price(f2 + k, t, flag), 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_kirk_close_to_exact() {
+        // A crack spread: small strike relative to the futures prices.
+        let (f1, f2, sigma1, sigma2, rho, r, t) = (122.0, 120.0, 0.2, 0.2, -0.5, 0.1, 0.1);
+
+        for k in [1.0, 3.0, 6.0] {
+            for flag in [TypeFlag::Call, TypeFlag::Put] {
+                let exact = spread_option_price(flag, f1, f2, k, sigma1, sigma2, rho, r, t);
+                let kirk = kirk_spread_price(flag, f1, f2, k, sigma1, sigma2, rho, r, t);
+
+                assert_approx_equal!(kirk, exact, 0.01);
+            }
+        }
+
+        // Put-call parity holds for both.
+        let (k, discount) = (3.0, (-r * t).exp());
+        for price in [spread_option_price, kirk_spread_price] {
+            let call = price(TypeFlag::Call, f1, f2, k, sigma1, sigma2, rho, r, t);
+            let put = price(TypeFlag::Put, f1, f2, k, sigma1, sigma2, rho, r, t);
+
+            assert_approx_equal!(call - put, discount * (f1 - f2 - k), 1e-10);
+        }
+    }
+}
@@ -8,7 +8,6 @@
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
 use super::option_flags::*;
-use std::cmp::Ordering;
 use time::Date;
 use RustQuant_time::{today, DayCountConvention};
 
@@ -41,6 +40,79 @@ pub struct FiniteDifferencePricer {
     pub type_flag: TypeFlag,
     /// Option Style
     pub exercise_flag: ExerciseFlag,
+
+    /// Day count convention for the time to expiry
+    pub day_count: DayCountConvention,
+}
+
+/// Time stepping scheme for the finite difference pricer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FiniteDifferenceScheme {
+    /// Explicit (forward Euler) scheme. Only stable for small time steps,
+    /// $\Delta t \leq \Delta x^2 / \sigma^2$.
+    Explicit,
+
+    /// Implicit (backward Euler) scheme. Unconditionally stable, first order in time.
+    Implicit,
+
+    /// Crank-Nicolson scheme. Unconditionally stable, second order in time.
+    CrankNicolson,
+}
+
+/// Condition imposed at the edges of the price grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BoundaryCondition {
+    /// The asymptotic option value, e.g. $S - K e^{-r \tau}$ for a deep
+    /// in-the-money call.
+    Dirichlet,
+
+    /// The value is linear in log price at the edges, which needs no
+    /// knowledge of the payoff.
+    Linearity,
+}
+
+/// Method of imposing early exercise in the implicit schemes.
+/// The explicit scheme always uses projection, which is exact for it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EarlyExerciseMethod {
+    /// Solve without the constraint, then take the maximum with the payoff.
+    /// Only first order accurate in time.
+    Projection,
+
+    /// Projected successive over-relaxation, which solves the linear
+    /// complementarity problem by Gauss-Seidel iteration.
+    Psor {
+        /// Relaxation parameter, in $(0, 2)$.
+        omega: f64,
+        /// Convergence tolerance on the largest update.
+        tolerance: f64,
+        /// Maximum number of iterations per time step.
+        max_iterations: usize,
+    },
+
+    /// Forsyth and Vetzal (2002) penalty method, which adds a large
+    /// penalty wherever the constraint is violated and iterates.
+    Penalty {
+        /// The penalty factor, e.g. $10^8$.
+        penalty: f64,
+        /// Convergence tolerance on the relative change.
+        tolerance: f64,
+        /// Maximum number of iterations per time step.
+        max_iterations: usize,
+    },
+}
+
+/// Settings for the finite difference pricer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FiniteDifferenceSettings {
+    /// Half-width of the log price grid, in standard deviations at maturity.
+    pub grid_width: f64,
+
+    /// Condition at the edges of the grid.
+    pub boundary_condition: BoundaryCondition,
+
+    /// Early exercise method for American options.
+    pub early_exercise: EarlyExerciseMethod,
 }
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -80,106 +152,159 @@ impl FiniteDifferencePricer {
             price_steps,
             type_flag,
             exercise_flag,
+            day_count: DayCountConvention::default(),
         }
     }
 
-    fn tridiagonal_matrix_multiply_vector(
-        &self,
-        sub_diagonal: f64,
-        diagonal: f64,
-        super_diagonal: f64,
-        v: Vec<f64>,
-    ) -> Vec<f64> {
-        let mut Av: Vec<f64> = Vec::new();
-
-        Av.push(diagonal * v[0] + super_diagonal * v[1]);
+    /// Set the day count convention for the time to expiry.
+    pub fn with_day_count(mut self, day_count: DayCountConvention) -> Self {
+        self.day_count = day_count;
+        self
+    }
 
-        for i in 1..(v.len() - 1) {
-            Av.push(sub_diagonal * v[i - 1] + diagonal * v[i] + super_diagonal * v[i + 1])
-        }
+    /// Explicit method
+    pub fn explicit(&self) -> f64 {
+        self.solve(
+            FiniteDifferenceScheme::Explicit,
+            &FiniteDifferenceSettings::default(),
+        )
+    }
 
-        Av.push(sub_diagonal * v[v.len() - 2] + diagonal * v[v.len() - 1]);
+    ///Implicit method
+    pub fn implicit(&self) -> f64 {
+        self.solve(
+            FiniteDifferenceScheme::Implicit,
+            &FiniteDifferenceSettings::default(),
+        )
+    }
 
-        Av
+    /// Crank-Nicolson method
+    pub fn crank_nicolson(&self) -> f64 {
+        self.solve(
+            FiniteDifferenceScheme::CrankNicolson,
+            &FiniteDifferenceSettings::default(),
+        )
     }
 
-    fn general_matrix_multiply_vector(&self, A: &[Vec<f64>], v: Vec<f64>) -> Vec<f64> {
-        let mut Av: Vec<f64> = Vec::new();
-        let mut value: f64;
+    /// Solve the Black-Scholes PDE with the given scheme and settings.
+    ///
+    /// The PDE is solved in log price $x = \ln S$ and time to maturity
+    /// $\tau$, for $u = e^{r \tau} V$, which removes the discounting term:
+    /// $u_\tau = \frac{1}{2} \sigma^2 u_{xx} + (r - \frac{1}{2} \sigma^2) u_x$.
+    /// Each step solves $(I - \theta L) u^{n+1} = (I + (1 - \theta) L) u^n$,
+    /// with $\theta = 0, 1, \frac{1}{2}$ for the explicit, implicit and
+    /// Crank-Nicolson schemes.
+    ///
+    /// American options are priced as a linear complementarity problem,
+    /// $u \geq e^{r \tau} g$ for the payoff $g$, with the method in `settings`.
+    pub fn solve(
+        &self,
+        scheme: FiniteDifferenceScheme,
+        settings: &FiniteDifferenceSettings,
+    ) -> f64 {
+        let (T, delta_t, delta_x, x_min) = self.grid(settings.grid_width);
+        let (x, y) = self.coefficients(delta_t, delta_x);
+        let theta = scheme.theta();
+
+        let american = matches!(self.exercise_flag, ExerciseFlag::American { .. });
+        let n = self.price_steps as usize;
+        let r = self.risk_free_rate;
+
+        // The spatial operator L, per time step, on each interior node.
+        let (lower, centre, upper) = (x - y, -2.0 * x, x + y);
+
+        let log_prices: Vec<f64> = (0..=n).map(|i| x_min + i as f64 * delta_x).collect();
+        let exercise = |tau: f64| -> Vec<f64> {
+            log_prices
+                .iter()
+                .map(|&x| (r * tau).exp() * self.payoff(x.exp()))
+                .collect()
+        };
+
+        // Values on the full grid, including the two boundary nodes.
+        let mut u: Vec<f64> = log_prices.iter().map(|&x| self.payoff(x.exp())).collect();
+
+        for t in 1..=self.time_steps {
+            let tau = t as f64 * delta_t;
+
+            // Explicit part, (I + (1 - theta) L) u^n, on the interior.
+            let mut rhs: Vec<f64> = (1..n)
+                .map(|i| {
+                    u[i] + (1.0 - theta) * (lower * u[i - 1] + centre * u[i] + upper * u[i + 1])
+                })
+                .collect();
+
+            // Tridiagonal system (I - theta L) u^{n+1} = rhs on the interior,
+            // as (sub, diagonal, super) rows.
+            let mut rows = vec![(-theta * lower, 1.0 - theta * centre, -theta * upper); n - 1];
+
+            match settings.boundary_condition {
+                BoundaryCondition::Dirichlet => {
+                    let (u_min, u_max) = self.dirichlet_boundary(tau, &log_prices, american);
+
+                    rhs[0] += theta * lower * u_min;
+                    rhs[n - 2] += theta * upper * u_max;
+                    u[0] = u_min;
+                    u[n] = u_max;
+                }
+                BoundaryCondition::Linearity => {
+                    // u_0 = 2 u_1 - u_2 and u_n = 2 u_{n-1} - u_{n-2}.
+                    let (sub, diagonal, sup) = rows[0];
+                    rows[0] = (0.0, diagonal + 2.0 * sub, sup - sub);
+
+                    let (sub, diagonal, sup) = rows[n - 2];
+                    rows[n - 2] = (sub - sup, diagonal + 2.0 * sup, 0.0);
+                }
+            }
+
+            let interior = match (american, scheme) {
+                (false, _) => thomas(&rows, &rhs),
+                // Projection is exact for the explicit scheme.
+                (true, FiniteDifferenceScheme::Explicit) => {
+                    let g = exercise(tau);
+
+                    thomas(&rows, &rhs)
+                        .iter()
+                        .zip(&g[1..n])
+                        .map(|(u, g)| u.max(*g))
+                        .collect()
+                }
+                (true, _) => {
+                    let g = exercise(tau);
+
+                    settings.early_exercise.solve(&rows, &rhs, &g[1..n])
+                }
+            };
+
+            u[1..n].copy_from_slice(&interior);
 
-        for row in A {
-            value = 0.0;
-            for (a, b) in row.iter().zip(&v) {
-                value += a * b;
+            if let BoundaryCondition::Linearity = settings.boundary_condition {
+                u[0] = 2.0 * u[1] - u[2];
+                u[n] = 2.0 * u[n - 1] - u[n - 2];
             }
-            Av.push(value);
         }
 
-        Av
+        f64::exp(-r * T) * self.return_price(u[1..n].to_vec())
     }
 
-    fn invert_tridiagonal_matrix(
-        &self,
-        sub_diagonal: f64,
-        diagonal: f64,
-        super_diagonal: f64,
-    ) -> Vec<Vec<f64>> {
-        let mut theta: Vec<f64> = Vec::new();
-        let system_size: usize = (self.price_steps - 1) as usize;
-
-        theta.push(1.0);
-        theta.push(diagonal);
-        theta.push(diagonal * diagonal - super_diagonal * sub_diagonal);
-
-        for i in 2..system_size {
-            theta.push(diagonal * theta[i] - super_diagonal * sub_diagonal * theta[i - 1])
-        }
+    /// Values of $u = e^{r \tau} V$ at the edges of the grid, from the
+    /// asymptotic behaviour of the option.
+    fn dirichlet_boundary(&self, tau: f64, log_prices: &[f64], american: bool) -> (f64, f64) {
+        let (k, r) = (self.strike_price, self.risk_free_rate);
+        let (s_min, s_max) = (log_prices[0].exp(), log_prices[log_prices.len() - 1].exp());
+        let growth = (r * tau).exp();
 
-        let mut phi: Vec<f64> = Vec::new();
-        phi.push(1.0);
-        phi.push(diagonal);
-
-        for i in 1..(system_size) {
-            phi.push(diagonal * phi[i] - super_diagonal * sub_diagonal * phi[i - 1])
-        }
+        match self.type_flag {
+            TypeFlag::Call => (0.0, (s_max * growth - k).max(0.0)),
+            TypeFlag::Put => {
+                let european = (k - s_min * growth).max(0.0);
 
-        let theta_n = theta.pop().unwrap();
-        phi.pop();
-        phi.reverse();
-
-        let mut value: f64;
-        let mut inverse_matrix: Vec<Vec<f64>> = Vec::new();
-        let mut matrix_row: Vec<f64> = Vec::new();
-
-        for i in 0..system_size {
-            for j in 0..system_size {
-                value = (-1.0_f64).powi((i + j) as i32);
-
-                match i.cmp(&j) {
-                    Ordering::Less => {
-                        for k in i..j {
-                            value *= match k {
-                                k if k == system_size - 1 => diagonal,
-                                _ => super_diagonal,
-                            }
-                        }
-                        value *= theta[i] * phi[j] / theta_n;
-                    }
-                    Ordering::Equal => value *= theta[i] * phi[i] / theta_n,
-                    Ordering::Greater => {
-                        for _k in (j + 1)..(i + 1) {
-                            value *= sub_diagonal
-                        }
-                        value *= theta[j] * phi[i] / theta_n
-                    }
+                match american {
+                    true => (european.max(growth * (k - s_min)), 0.0),
+                    false => (european, 0.0),
                 }
-                matrix_row.push(value);
             }
-            inverse_matrix.push(matrix_row.clone());
-            matrix_row.clear()
         }
-
-        inverse_matrix
     }
 
     fn payoff(&self, s: f64) -> f64 {
@@ -189,33 +314,8 @@ impl FiniteDifferencePricer {
         }
     }
 
-    fn american_time_stop_step(&self, v: Vec<f64>, tau: f64, x_min: f64, delta_x: f64) -> Vec<f64> {
-        (1..self.price_steps)
-            .map(|i: u32| {
-                v[(i - 1) as usize].max(
-                    f64::exp(self.risk_free_rate * tau)
-                        * self.payoff(f64::exp(x_min + (i as f64) * delta_x)),
-                )
-            })
-            .collect()
-    }
-
-    fn initial_condition(&self, x_min: f64, delta_x: f64) -> Vec<f64> {
-        (1..self.price_steps)
-            .map(|i: u32| self.payoff(f64::exp(x_min + (i as f64) * delta_x)))
-            .collect()
-    }
-
-    fn call_boundary(&self, tau: f64, x_max: f64) -> f64 {
-        f64::exp(x_max) - self.strike_price * f64::exp(-self.risk_free_rate * tau)
-    }
-
-    fn put_boundary(&self, tau: f64, x_min: f64) -> f64 {
-        self.strike_price * f64::exp(-self.risk_free_rate * tau) - f64::exp(x_min)
-    }
-
     fn year_fraction(&self) -> f64 {
-        DayCountConvention::default().day_count_factor(
+        self.day_count.day_count_factor(
             self.evaluation_date.unwrap_or(today()),
             self.expiration_date,
         )
@@ -232,11 +332,11 @@ impl FiniteDifferencePricer {
         }
     }
 
-    fn grid(&self) -> (f64, f64, f64, f64) {
+    fn grid(&self, width: f64) -> (f64, f64, f64, f64) {
         let T: f64 = self.year_fraction();
         let delta_t: f64 = T / (self.time_steps as f64);
-        let x_min: f64 = self.initial_price.ln() - 5.0 * self.volatility * T.sqrt();
-        let delta_x: f64 = (self.initial_price.ln() + 5.0 * self.volatility * T.sqrt() - x_min)
+        let x_min: f64 = self.initial_price.ln() - width * self.volatility * T.sqrt();
+        let delta_x: f64 = (self.initial_price.ln() + width * self.volatility * T.sqrt() - x_min)
             / self.price_steps as f64;
 
         (T, delta_t, delta_x, x_min)
@@ -248,126 +348,145 @@ impl FiniteDifferencePricer {
             delta_t * (self.risk_free_rate - 0.5 * self.volatility.powi(2)) / (2.0 * delta_x),
         )
     }
+}
 
-    /// Explicit method
-    pub fn explicit(&self) -> f64 {
-        let (T, delta_t, delta_x, x_min) = self.grid();
-        let (x, y) = self.coefficients(delta_t, delta_x);
-        let sub_diagonal: f64 = x - y;
-        let diagonal: f64 = 1.0 - 2.0 * x;
-        let super_diagonal: f64 = x + y;
-
-        let mut v: Vec<f64> = self.initial_condition(x_min, delta_x);
-
-        for t in 1..(self.time_steps + 1) {
-            v = self.tridiagonal_matrix_multiply_vector(sub_diagonal, diagonal, super_diagonal, v);
-
-            match self.type_flag {
-                TypeFlag::Call => {
-                    v[(self.price_steps - 2) as usize] += super_diagonal
-                        * self.call_boundary(
-                            (t as f64) * delta_t,
-                            self.initial_price.ln() + 5.0 * self.volatility * T.sqrt(),
-                        );
-                }
-                TypeFlag::Put => {
-                    v[0] += sub_diagonal * self.put_boundary((t as f64) * delta_t, x_min);
-                }
-            }
-
-            if let ExerciseFlag::American {
-                start: Date::MIN,
-                end: Date::MAX,
-            } = self.exercise_flag
-            {
-                v = self.american_time_stop_step(v, (t as f64) * delta_t, x_min, delta_x);
-            }
+impl FiniteDifferenceScheme {
+    /// Weight of the implicit part of each time step.
+    fn theta(&self) -> f64 {
+        match self {
+            FiniteDifferenceScheme::Explicit => 0.0,
+            FiniteDifferenceScheme::Implicit => 1.0,
+            FiniteDifferenceScheme::CrankNicolson => 0.5,
         }
-
-        f64::exp(-self.risk_free_rate * T) * self.return_price(v)
     }
+}
 
-    ///Implicit method
-    pub fn implicit(&self) -> f64 {
-        let (T, delta_t, delta_x, x_min) = self.grid();
-        let (x, y) = self.coefficients(delta_t, delta_x);
-
-        let inverse_matrix: Vec<Vec<f64>> =
-            self.invert_tridiagonal_matrix(-x + y, 1.0 + 2.0 * x, -x - y);
+impl Default for FiniteDifferenceSettings {
+    /// Five standard deviations either side of the spot, Dirichlet
+    /// boundaries, and projected SOR for early exercise.
+    fn default() -> Self {
+        Self {
+            grid_width: 5.0,
+            boundary_condition: BoundaryCondition::Dirichlet,
+            early_exercise: EarlyExerciseMethod::Psor {
+                omega: 1.2,
+                tolerance: 1e-10,
+                max_iterations: 1000,
+            },
+        }
+    }
+}
 
-        let mut v: Vec<f64> = self.initial_condition(x_min, delta_x);
+impl EarlyExerciseMethod {
+    /// Solve the tridiagonal system `rows * u = rhs` subject to `u >= g`.
+    fn solve(&self, rows: &[(f64, f64, f64)], rhs: &[f64], g: &[f64]) -> Vec<f64> {
+        match *self {
+            EarlyExerciseMethod::Projection => thomas(rows, rhs)
+                .iter()
+                .zip(g)
+                .map(|(u, g)| u.max(*g))
+                .collect(),
+
+            EarlyExerciseMethod::Psor {
+                omega,
+                tolerance,
+                max_iterations,
+            } => {
+                // Start from the projected unconstrained solution.
+                let mut u: Vec<f64> = thomas(rows, rhs)
+                    .iter()
+                    .zip(g)
+                    .map(|(u, g)| u.max(*g))
+                    .collect();
+                let m = u.len();
+
+                for _ in 0..max_iterations {
+                    let mut error: f64 = 0.0;
+
+                    for i in 0..m {
+                        let (sub, diagonal, sup) = rows[i];
+                        let left = if i > 0 { sub * u[i - 1] } else { 0.0 };
+                        let right = if i + 1 < m { sup * u[i + 1] } else { 0.0 };
+
+                        let gauss_seidel = (rhs[i] - left - right) / diagonal;
+                        let updated = (u[i] + omega * (gauss_seidel - u[i])).max(g[i]);
+
+                        error = error.max((updated - u[i]).abs());
+                        u[i] = updated;
+                    }
 
-        for t in 1..(self.time_steps + 1) {
-            match self.type_flag {
-                TypeFlag::Call => {
-                    v[(self.price_steps - 2) as usize] -= (-x - y)
-                        * self.call_boundary(
-                            (t as f64) * delta_t,
-                            self.initial_price.ln() + 5.0 * self.volatility * T.sqrt(),
-                        );
-                }
-                TypeFlag::Put => {
-                    v[0] -= (-x + y) * self.put_boundary((t as f64) * delta_t, x_min);
+                    if error < tolerance {
+                        break;
+                    }
                 }
-            }
-
-            v = self.general_matrix_multiply_vector(&inverse_matrix, v);
 
-            if let ExerciseFlag::American {
-                start: Date::MIN,
-                end: Date::MAX,
-            } = self.exercise_flag
-            {
-                v = self.american_time_stop_step(v, (t as f64) * delta_t, x_min, delta_x);
+                u
             }
-        }
-
-        f64::exp(-self.risk_free_rate * T) * self.return_price(v)
-    }
 
-    /// Crank-Nicolson method
-    pub fn crank_nicolson(&self) -> f64 {
-        let (T, delta_t, delta_x, x_min) = self.grid();
-        let (x, y) = self.coefficients(delta_t, delta_x);
-        let sub_diagonal: f64 = 0.5 * (x - y);
-        let diagonal: f64 = 1.0 - x;
-        let super_diagonal: f64 = 0.5 * (x + y);
-
-        let inverse_future_matrix =
-            self.invert_tridiagonal_matrix(-sub_diagonal, 1.0 + x, -super_diagonal);
-
-        let mut v: Vec<f64> = self.initial_condition(x_min, delta_x);
-
-        for t in 1..(self.time_steps + 1) {
-            v = self.tridiagonal_matrix_multiply_vector(sub_diagonal, diagonal, super_diagonal, v);
-
-            match self.type_flag {
-                TypeFlag::Call => {
-                    v[(self.price_steps - 2) as usize] += 2.0
-                        * super_diagonal
-                        * self.call_boundary(
-                            (t as f64) * delta_t,
-                            self.initial_price.ln() + 5.0 * self.volatility * T.sqrt(),
-                        );
-                }
-                TypeFlag::Put => {
-                    v[0] += 2.0 * sub_diagonal * self.put_boundary((t as f64) * delta_t, x_min);
+            EarlyExerciseMethod::Penalty {
+                penalty,
+                tolerance,
+                max_iterations,
+            } => {
+                let mut u = thomas(rows, rhs);
+
+                for _ in 0..max_iterations {
+                    // Penalise the nodes that violate the constraint.
+                    let (penalised_rows, penalised_rhs): (Vec<_>, Vec<_>) = rows
+                        .iter()
+                        .zip(rhs)
+                        .zip(u.iter().zip(g))
+                        .map(|((&(sub, diagonal, sup), &b), (&u, &g))| match u < g {
+                            true => ((sub, diagonal + penalty, sup), b + penalty * g),
+                            false => ((sub, diagonal, sup), b),
+                        })
+                        .unzip();
+
+                    let updated = thomas(&penalised_rows, &penalised_rhs);
+                    let error = updated
+                        .iter()
+                        .zip(&u)
+                        .map(|(a, b)| (a - b).abs() / a.abs().max(1.0))
+                        .fold(0.0, f64::max);
+
+                    u = updated;
+
+                    if error < tolerance {
+                        break;
+                    }
                 }
-            }
-
-            v = self.general_matrix_multiply_vector(&inverse_future_matrix, v);
 
-            if let ExerciseFlag::American {
-                start: Date::MIN,
-                end: Date::MAX,
-            } = self.exercise_flag
-            {
-                v = self.american_time_stop_step(v, (t as f64) * delta_t, x_min, delta_x);
+                u
             }
         }
+    }
+}
 
-        f64::exp(-self.risk_free_rate * T) * self.return_price(v)
+/// Solve a tridiagonal system, given as `(sub, diagonal, super)` rows,
+/// with the Thomas algorithm.
+fn thomas(rows: &[(f64, f64, f64)], rhs: &[f64]) -> Vec<f64> {
+    let m = rhs.len();
+    let mut c = vec![0.0; m];
+    let mut d = vec![0.0; m];
+
+    for i in 0..m {
+        let (sub, diagonal, sup) = rows[i];
+        let (c_previous, d_previous) = if i > 0 {
+            (c[i - 1], d[i - 1])
+        } else {
+            (0.0, 0.0)
+        };
+        let denominator = diagonal - sub * c_previous;
+
+        c[i] = sup / denominator;
+        d[i] = (rhs[i] - sub * d_previous) / denominator;
     }
+
+    for i in (0..m - 1).rev() {
+        d[i] -= c[i] * d[i + 1];
+    }
+
+    d
 }
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -394,6 +513,7 @@ mod tests_finite_difference_pricer_at_the_money {
         exercise_flag: ExerciseFlag::European {
             expiry: date!(2025 - 01 - 01),
         },
+        day_count: DayCountConvention::Actual_Actual_ISDA,
     };
 
     const EUROPEAN_PUT: FiniteDifferencePricer = FiniteDifferencePricer {
@@ -409,6 +529,7 @@ mod tests_finite_difference_pricer_at_the_money {
         exercise_flag: ExerciseFlag::European {
             expiry: date!(2025 - 01 - 01),
         },
+        day_count: DayCountConvention::Actual_Actual_ISDA,
     };
 
     const AMERICAN_CALL: FiniteDifferencePricer = FiniteDifferencePricer {
@@ -425,6 +546,7 @@ mod tests_finite_difference_pricer_at_the_money {
             start: date!(2024 - 01 - 01),
             end: date!(2025 - 01 - 01),
         },
+        day_count: DayCountConvention::Actual_Actual_ISDA,
     };
 
     const AMERICAN_PUT: FiniteDifferencePricer = FiniteDifferencePricer {
@@ -441,6 +563,7 @@ mod tests_finite_difference_pricer_at_the_money {
             start: date!(2024 - 01 - 01),
             end: date!(2025 - 01 - 01),
         },
+        day_count: DayCountConvention::Actual_Actual_ISDA,
     };
 
     const EXPECT_A_CALL: f64 = 0.680_478_009_892_241;
@@ -533,6 +656,7 @@ mod tests_finite_difference_pricer_in_the_money {
         exercise_flag: ExerciseFlag::European {
             expiry: date!(2025 - 01 - 01),
         },
+        day_count: DayCountConvention::Actual_Actual_ISDA,
     };
 
     const EUROPEAN_PUT: FiniteDifferencePricer = FiniteDifferencePricer {
@@ -548,6 +672,7 @@ mod tests_finite_difference_pricer_in_the_money {
         exercise_flag: ExerciseFlag::European {
             expiry: date!(2025 - 01 - 01),
         },
+        day_count: DayCountConvention::Actual_Actual_ISDA,
     };
 
     const AMERICAN_CALL: FiniteDifferencePricer = FiniteDifferencePricer {
@@ -564,6 +689,7 @@ mod tests_finite_difference_pricer_in_the_money {
             start: date!(2024 - 01 - 01),
             end: date!(2025 - 01 - 01),
         },
+        day_count: DayCountConvention::Actual_Actual_ISDA,
     };
 
     const AMERICAN_PUT: FiniteDifferencePricer = FiniteDifferencePricer {
@@ -580,6 +706,7 @@ mod tests_finite_difference_pricer_in_the_money {
             start: date!(2024 - 01 - 01),
             end: date!(2025 - 01 - 01),
         },
+        day_count: DayCountConvention::Actual_Actual_ISDA,
     };
 
     const EXPECT_A_CALL: f64 = 5.487_706_388_002_172;
@@ -672,6 +799,7 @@ mod tests_finite_difference_pricer_out_of_the_money {
         exercise_flag: ExerciseFlag::European {
             expiry: date!(2025 - 01 - 01),
         },
+        day_count: DayCountConvention::Actual_Actual_ISDA,
     };
 
     const EUROPEAN_PUT: FiniteDifferencePricer = FiniteDifferencePricer {
@@ -687,6 +815,7 @@ mod tests_finite_difference_pricer_out_of_the_money {
         exercise_flag: ExerciseFlag::European {
             expiry: date!(2025 - 01 - 01),
         },
+        day_count: DayCountConvention::Actual_Actual_ISDA,
     };
 
     const AMERICAN_CALL: FiniteDifferencePricer = FiniteDifferencePricer {
@@ -703,6 +832,7 @@ mod tests_finite_difference_pricer_out_of_the_money {
             start: date!(2024 - 01 - 01),
             end: date!(2025 - 01 - 01),
         },
+        day_count: DayCountConvention::Actual_Actual_ISDA,
     };
 
     const AMERICAN_PUT: FiniteDifferencePricer = FiniteDifferencePricer {
@@ -719,6 +849,7 @@ mod tests_finite_difference_pricer_out_of_the_money {
             start: date!(2024 - 01 - 01),
             end: date!(2025 - 01 - 01),
         },
+        day_count: DayCountConvention::Actual_Actual_ISDA,
     };
 
     const EXPECT_A_CALL: f64 = 0.000_059_393_327_777_911;
@@ -786,3 +917,123 @@ mod tests_finite_difference_pricer_out_of_the_money {
         assert_approx_equal!(EUROPEAN_PUT.crank_nicolson(), EXPECT_E_PUT, EPS);
     }
 }
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS: SETTINGS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_finite_difference_pricer_settings {
+    use super::*;
+    use time::macros::date;
+    use RustQuant_utils::assert_approx_equal;
+
+    // Longstaff and Schwartz (2001), Table 1: S = 36, K = 40, r = 0.06,
+    // v = 0.2, T = 1. The continuously exercisable price is 4.4867.
+    const AMERICAN_PUT: FiniteDifferencePricer = FiniteDifferencePricer {
+        initial_price: 36.0,
+        strike_price: 40.0,
+        risk_free_rate: 0.06,
+        volatility: 0.2,
+        evaluation_date: Some(date!(2023 - 01 - 01)),
+        expiration_date: date!(2024 - 01 - 01),
+        time_steps: 500,
+        price_steps: 500,
+        type_flag: TypeFlag::Put,
+        exercise_flag: ExerciseFlag::American {
+            start: date!(2023 - 01 - 01),
+            end: date!(2024 - 01 - 01),
+        },
+        day_count: DayCountConvention::Actual_Actual_ISDA,
+    };
+
+    const EXPECT_A_PUT: f64 = 4.4867;
+
+    fn settings(early_exercise: EarlyExerciseMethod) -> FiniteDifferenceSettings {
+        FiniteDifferenceSettings {
+            early_exercise,
+            ..FiniteDifferenceSettings::default()
+        }
+    }
+
+    #[test]
+    fn american_put_early_exercise_methods() {
+        let psor = AMERICAN_PUT.solve(
+            FiniteDifferenceScheme::CrankNicolson,
+            &FiniteDifferenceSettings::default(),
+        );
+        let penalty = AMERICAN_PUT.solve(
+            FiniteDifferenceScheme::CrankNicolson,
+            &settings(EarlyExerciseMethod::Penalty {
+                penalty: 1e8,
+                tolerance: 1e-10,
+                max_iterations: 100,
+            }),
+        );
+        let projection = AMERICAN_PUT.solve(
+            FiniteDifferenceScheme::CrankNicolson,
+            &settings(EarlyExerciseMethod::Projection),
+        );
+
+        assert_approx_equal!(psor, EXPECT_A_PUT, 2e-3);
+        assert_approx_equal!(penalty, psor, 1e-5);
+        assert_approx_equal!(projection, psor, 5e-3);
+    }
+
+    #[test]
+    fn american_put_implicit_psor() {
+        let price = AMERICAN_PUT.solve(
+            FiniteDifferenceScheme::Implicit,
+            &FiniteDifferenceSettings::default(),
+        );
+
+        assert_approx_equal!(price, EXPECT_A_PUT, 5e-3);
+    }
+
+    #[test]
+    fn linearity_boundary_condition() {
+        let european_put = FiniteDifferencePricer {
+            exercise_flag: ExerciseFlag::European {
+                expiry: date!(2024 - 01 - 01),
+            },
+            ..AMERICAN_PUT
+        };
+
+        for scheme in [
+            FiniteDifferenceScheme::Implicit,
+            FiniteDifferenceScheme::CrankNicolson,
+        ] {
+            let dirichlet = european_put.solve(scheme, &FiniteDifferenceSettings::default());
+            let linearity = european_put.solve(
+                scheme,
+                &FiniteDifferenceSettings {
+                    boundary_condition: BoundaryCondition::Linearity,
+                    ..FiniteDifferenceSettings::default()
+                },
+            );
+
+            assert_approx_equal!(linearity, dirichlet, 1e-6);
+        }
+    }
+
+    #[test]
+    fn american_call_without_dividends_is_european() {
+        let american_call = FiniteDifferencePricer {
+            strike_price: 36.0,
+            type_flag: TypeFlag::Call,
+            ..AMERICAN_PUT
+        };
+        let european_call = FiniteDifferencePricer {
+            exercise_flag: ExerciseFlag::European {
+                expiry: date!(2024 - 01 - 01),
+            },
+            ..american_call
+        };
+
+        assert_approx_equal!(
+            american_call.crank_nicolson(),
+            european_call.crank_nicolson(),
+            1e-8
+        );
+    }
+}
@@ -7,15 +7,309 @@
 //      - LICENSE-MIT.md
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
+use super::{
+    GeneralisedBlackScholesMerton, Merton73, MonteCarloEstimate, OptionContract, TypeFlag,
+};
+use time::Date;
+use RustQuant_math::{gaussian::N, Distribution};
+use RustQuant_stochastics::{StochasticProcess, StochasticProcessConfig};
+use RustQuant_time::today;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
 /// Forward start option.
+///
+/// The strike is set on the start date as a fraction `alpha` of the
+/// then-prevailing spot price:
+///     - alpha < 1: call (put) will start (1 - alpha)% in-the-money (out-of-the-money).
+///     - alpha = 1: the option starts at-the-money.
+///     - alpha > 1: call (put) will start (alpha - 1)% out-of-the-money (in-the-money).
 #[derive(Debug, Clone)]
 pub struct ForwardStartOption {
     /// The option contract.
     pub contract: OptionContract,
 
-    /// Strike price of the option.
-    pub strike: f64,
+    /// Proportion of the spot on the start date used as the strike.
+    pub alpha: f64,
 
-    /// Forward start date.
+    /// Forward start date (when the strike is set).
     pub start_date: Date,
+
+    /// The strike, once it has been fixed on the start date.
+    pub strike: Option<f64>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl ForwardStartOption {
+    /// Create a new forward start option.
+    pub fn new(contract: OptionContract, alpha: f64, start_date: Date) -> Self {
+        assert!(alpha > 0.0);
+
+        Self {
+            contract,
+            alpha,
+            start_date,
+            strike: None,
+        }
+    }
+
+    /// Set the strike fixed on the start date, once it has passed.
+    pub fn with_strike(mut self, strike: f64) -> Self {
+        self.strike = Some(strike);
+        self
+    }
+
+    /// The fixed strike, if the start date has passed.
+    ///
+    /// # Panics
+    /// Panics if the start date has passed and the strike is not set.
+    fn fixed_strike(&self) -> Option<f64> {
+        match self.start_date < today() {
+            true => Some(
+                self.strike
+                    .expect("The start date has passed, so the fixed strike must be set."),
+            ),
+            false => None,
+        }
+    }
+
+    /// Rubinstein (1990) forward start option price.
+    ///
+    /// The price scales with the (dividend-discounted) spot and otherwise
+    /// only depends on `alpha` and the volatility over the remaining life
+    /// of the option after the start date.
+    ///
+    /// # Arguments:
+    /// * `s` - Underlying price.
+    /// * `r` - Risk-free rate.
+    /// * `q` - Dividend yield.
+    /// * `v` - Volatility.
+    ///
+    /// Once the start date has passed, the option is a vanilla option on
+    /// the fixed strike.
+    ///
+    /// # Panics
+    /// Panics if the start date is not before expiry, or has passed
+    /// without the strike being set.
+    pub fn price(&self, s: f64, r: f64, q: f64, v: f64) -> f64 {
+        let t_start = self.contract.time_to(self.start_date);
+        let t_end = self.contract.time_to_expiry();
+
+        assert!(t_start < t_end, "The start date must be before expiry.");
+
+        if let Some(strike) = self.fixed_strike() {
+            return Merton73::new(s, r, q, v).price(strike, t_end, self.contract.type_flag);
+        }
+
+        rubinstein::price(
+            s,
+            self.alpha,
+            t_start,
+            t_end,
+            r,
+            r - q,
+            v,
+            self.contract.type_flag,
+        )
+    }
+
+    /// Monte Carlo forward start option price, for any model of the
+    /// underlying. The simulation should run from today to the option's
+    /// expiry.
+    ///
+    /// Each path sets its strike to `alpha` times the underlying at the
+    /// start date, and pays off at expiry. If the start date falls between
+    /// time steps, the strike is fixed at the nearest one instead, so
+    /// choose `config.n_steps` to put the start date on the time grid.
+    /// Once the start date has passed, every path uses the fixed strike.
+    ///
+    /// # Arguments:
+    /// * `process` - The process driving the underlying (with risk-neutral drift).
+    /// * `config` - The simulation configuration.
+    /// * `rate` - Continuously compounded discount rate.
+    ///
+    /// # Panics
+    /// Panics if the start date is not before expiry, has passed without
+    /// the strike being set, or `config.m_paths < 2`.
+    pub fn price_monte_carlo<P>(
+        &self,
+        process: &P,
+        config: &StochasticProcessConfig,
+        rate: f64,
+    ) -> MonteCarloEstimate
+    where
+        P: StochasticProcess,
+    {
+        let t_start = self.contract.time_to(self.start_date);
+        let t_end = self.contract.time_to_expiry();
+
+        assert!(t_start < t_end, "The start date must be before expiry.");
+
+        let fixed_strike = self.fixed_strike();
+        let trajectories = process.euler_maruyama(config);
+
+        let start_step = trajectories
+            .times
+            .iter()
+            .map(|time| (time - config.t_0 - t_start).abs())
+            .enumerate()
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map_or(0, |(step, _)| step);
+        let discount_factor = (-rate * (config.t_n - config.t_0)).exp();
+
+        let discounted: Vec<f64> = trajectories
+            .paths
+            .iter()
+            .map(|path| {
+                let strike = fixed_strike.unwrap_or(self.alpha * path[start_step]);
+                let terminal = path[path.len() - 1];
+                let payoff = match self.contract.type_flag {
+                    TypeFlag::Call => (terminal - strike).max(0.0),
+                    TypeFlag::Put => (strike - terminal).max(0.0),
+                };
+
+                discount_factor * payoff
+            })
+            .collect();
+
+        MonteCarloEstimate::from_samples(&discounted)
+    }
+}
+
+mod rubinstein {
+    use super::{Distribution, TypeFlag, N};
+
+    /// Rubinstein (1990) forward start price, with cost of carry `b`.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn price(
+        s: f64,
+        alpha: f64,
+        t_start: f64,
+        t_end: f64,
+        r: f64,
+        b: f64,
+        v: f64,
+        type_flag: TypeFlag,
+    ) -> f64 {
+        let tau = t_end - t_start;
+
+        let d1 = ((1.0 / alpha).ln() + (b + 0.5 * v * v) * tau) / (v * tau.sqrt());
+        let d2 = d1 - v * tau.sqrt();
+
+        let carry = s * ((b - r) * t_start).exp();
+
+        match type_flag {
+            TypeFlag::Call => {
+                carry * (((b - r) * tau).exp() * N.cdf(d1) - alpha * (-r * tau).exp() * N.cdf(d2))
+            }
+            TypeFlag::Put => {
+                carry * (alpha * (-r * tau).exp() * N.cdf(-d2) - ((b - r) * tau).exp() * N.cdf(-d1))
+            }
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_forward_start {
+    use super::*;
+    use crate::{BlackScholes73, ExerciseFlag, OptionContractBuilder};
+    use RustQuant_stochastics::GeometricBrownianMotion;
+    use RustQuant_time::year_fraction;
+    use RustQuant_utils::{assert_approx_equal, RUSTQUANT_EPSILON};
+
+    fn contract(type_flag: TypeFlag, expiry: Date) -> OptionContract {
+        OptionContractBuilder::default()
+            .type_flag(type_flag)
+            .exercise_flag(ExerciseFlag::European { expiry })
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_forward_start_haug() {
+        // Haug, The Complete Guide to Option Pricing Formulas, p. 37.
+        let call = rubinstein::price(60.0, 1.1, 0.25, 1.0, 0.08, 0.04, 0.3, TypeFlag::Call);
+
+        assert_approx_equal!(call, 4.4064, 1e-4);
+    }
+
+    #[test]
+    fn test_forward_start_today_is_vanilla() {
+        let expiry = today() + time::Duration::days(365);
+        let t = year_fraction(today(), expiry);
+        let model = Merton73::new(60.0, 0.08, 0.04, 0.3);
+
+        for flag in [TypeFlag::Call, TypeFlag::Put] {
+            for alpha in [0.9, 1.0, 1.1] {
+                let option = ForwardStartOption::new(contract(flag, expiry), alpha, today());
+
+                assert_approx_equal!(
+                    option.price(60.0, 0.08, 0.04, 0.3),
+                    model.price(alpha * 60.0, t, flag),
+                    RUSTQUANT_EPSILON
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_forward_start_monte_carlo() {
+        // The start date falls on a time step: 73 days is 20 of 100 steps.
+        let start = today() + time::Duration::days(73);
+        let expiry = today() + time::Duration::days(365);
+        let t = year_fraction(today(), expiry);
+        let gbm = GeometricBrownianMotion::new(0.05, 0.25);
+        let config = StochasticProcessConfig::new(50.0, 0.0, t, 100, 20_000, true).with_seed(7);
+
+        for flag in [TypeFlag::Call, TypeFlag::Put] {
+            let option = ForwardStartOption::new(contract(flag, expiry), 1.05, start);
+
+            let exact = option.price(50.0, 0.05, 0.0, 0.25);
+            let estimate = option.price_monte_carlo(&gbm, &config, 0.05);
+
+            assert!((estimate.price - exact).abs() < 3.0 * estimate.standard_error);
+        }
+    }
+
+    #[test]
+    fn test_forward_start_after_start_date() {
+        // Once started, the option is a vanilla on the fixed strike.
+        let start = today() - time::Duration::days(30);
+        let expiry = today() + time::Duration::days(365);
+        let t = year_fraction(today(), expiry);
+        let option =
+            ForwardStartOption::new(contract(TypeFlag::Call, expiry), 1.1, start).with_strike(55.0);
+
+        assert_approx_equal!(
+            option.price(60.0, 0.08, 0.04, 0.3),
+            Merton73::new(60.0, 0.08, 0.04, 0.3).price(55.0, t, TypeFlag::Call),
+            RUSTQUANT_EPSILON
+        );
+
+        let gbm = GeometricBrownianMotion::new(0.08, 0.3);
+        let config = StochasticProcessConfig::new(60.0, 0.0, t, 50, 5_000, true).with_seed(11);
+        let estimate = option.price_monte_carlo(&gbm, &config, 0.08);
+        let exact = BlackScholes73::new(60.0, 0.08, 0.3).price(55.0, t, TypeFlag::Call);
+
+        assert!((estimate.price - exact).abs() < 3.0 * estimate.standard_error);
+    }
+
+    #[test]
+    #[should_panic(expected = "fixed strike must be set")]
+    fn test_forward_start_after_start_date_needs_strike() {
+        let start = today() - time::Duration::days(30);
+        let expiry = today() + time::Duration::days(365);
+        let option = ForwardStartOption::new(contract(TypeFlag::Call, expiry), 1.1, start);
+
+        option.price(60.0, 0.08, 0.04, 0.3);
+    }
 }
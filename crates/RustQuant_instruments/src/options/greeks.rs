@@ -0,0 +1,729 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Option sensitivities (Greeks), for aggregating risk across positions.
+//!
+//! Closed-form models report their own Greeks through `OptionGreeks`,
+//! including the higher-order Greeks needed for smile risk.
+//! Any other pricer (lattice, finite difference, Monte Carlo, ...) can get
+//! them by bumping and revaluing with `BumpAndRevalue`.
+
+use super::BlackScholesMerton;
+use std::iter::Sum;
+use std::ops::{Add, Mul};
+
+/// Smallest bump size, so that inputs at zero (or zero relative bumps)
+/// still move, rather than dividing zero by zero.
+pub const MIN_BUMP_SIZE: f64 = 1e-6;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS & TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// First-order Greeks (plus gamma) of an option.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Greeks {
+    /// Sensitivity to the underlying price.
+    pub delta: f64,
+
+    /// Sensitivity of delta to the underlying price.
+    pub gamma: f64,
+
+    /// Sensitivity to the volatility.
+    pub vega: f64,
+
+    /// Sensitivity to the passage of time.
+    pub theta: f64,
+
+    /// Sensitivity to the risk-free rate.
+    pub rho: f64,
+}
+
+/// Second and third-order Greeks of an option, for smile risk.
+///
+/// Charm and color are sensitivities to the passage of time, like theta,
+/// i.e. to a *decrease* in the time to expiry.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct HigherOrderGreeks {
+    /// Sensitivity of delta to the volatility (equivalently, of vega to
+    /// the underlying price).
+    pub vanna: f64,
+
+    /// Sensitivity of vega to the volatility, also known as vomma.
+    pub volga: f64,
+
+    /// Sensitivity of delta to the passage of time.
+    pub charm: f64,
+
+    /// Sensitivity of gamma to the underlying price.
+    pub speed: f64,
+
+    /// Sensitivity of gamma to the volatility.
+    pub zomma: f64,
+
+    /// Sensitivity of gamma to the passage of time.
+    pub color: f64,
+}
+
+/// Market inputs that a pricer is revalued under when bumping.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MarketState {
+    /// Underlying price.
+    pub spot: f64,
+
+    /// Volatility.
+    pub volatility: f64,
+
+    /// Risk-free rate.
+    pub rate: f64,
+
+    /// Time to expiry (in years).
+    pub time_to_expiry: f64,
+}
+
+/// Size of a bump to one market input.
+///
+/// Bump sizes are floored at [`MIN_BUMP_SIZE`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Bump {
+    /// Bump by a fixed amount.
+    Absolute(f64),
+
+    /// Bump by a fraction of the input's current value.
+    Relative(f64),
+}
+
+/// Finite difference used to estimate a sensitivity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DifferenceScheme {
+    /// Bump both ways: second order accurate, but twice the revaluations.
+    #[default]
+    Central,
+
+    /// Bump one way only: first order accurate.
+    Forward,
+}
+
+/// Finite difference (bump-and-revalue) Greeks for any pricer.
+///
+/// The pricer is a function of the `MarketState`, so it can wrap lattice,
+/// PDE or Monte Carlo pricers alike. Monte Carlo pricers should use the
+/// same random numbers for every revaluation (e.g. a fixed seed), or the
+/// simulation noise swamps the differences.
+///
+/// Theta is the sensitivity to the passage of time, i.e. to a *decrease*
+/// in the time to expiry, so a forward difference shortens the option.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BumpAndRevalue {
+    /// Bump to the underlying price, for delta and gamma.
+    pub spot_bump: Bump,
+
+    /// Bump to the volatility, for vega.
+    pub volatility_bump: Bump,
+
+    /// Bump to the risk-free rate, for rho.
+    pub rate_bump: Bump,
+
+    /// Bump to the time to expiry, for theta.
+    pub time_bump: Bump,
+
+    /// Central or forward differences.
+    pub scheme: DifferenceScheme,
+}
+
+/// Options that can report their Greeks and be repriced under a market move.
+pub trait OptionGreeks {
+    /// Returns the option's Greeks.
+    fn greeks(&self) -> Greeks;
+
+    /// Returns the option's higher-order Greeks.
+    fn higher_order_greeks(&self) -> HigherOrderGreeks;
+
+    /// Returns a copy of the option with the underlying price shifted by
+    /// `spot_shift` and the volatility shifted by `vol_shift` (both absolute).
+    fn shifted(&self, spot_shift: f64, vol_shift: f64) -> Self
+    where
+        Self: Sized;
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl Add for Greeks {
+    type Output = Greeks;
+
+    fn add(self, other: Greeks) -> Greeks {
+        Greeks {
+            delta: self.delta + other.delta,
+            gamma: self.gamma + other.gamma,
+            vega: self.vega + other.vega,
+            theta: self.theta + other.theta,
+            rho: self.rho + other.rho,
+        }
+    }
+}
+
+impl Mul<f64> for Greeks {
+    type Output = Greeks;
+
+    fn mul(self, scalar: f64) -> Greeks {
+        Greeks {
+            delta: self.delta * scalar,
+            gamma: self.gamma * scalar,
+            vega: self.vega * scalar,
+            theta: self.theta * scalar,
+            rho: self.rho * scalar,
+        }
+    }
+}
+
+impl Sum for Greeks {
+    fn sum<I: Iterator<Item = Greeks>>(iter: I) -> Greeks {
+        iter.fold(Greeks::default(), Add::add)
+    }
+}
+
+impl Add for HigherOrderGreeks {
+    type Output = HigherOrderGreeks;
+
+    fn add(self, other: HigherOrderGreeks) -> HigherOrderGreeks {
+        HigherOrderGreeks {
+            vanna: self.vanna + other.vanna,
+            volga: self.volga + other.volga,
+            charm: self.charm + other.charm,
+            speed: self.speed + other.speed,
+            zomma: self.zomma + other.zomma,
+            color: self.color + other.color,
+        }
+    }
+}
+
+impl Mul<f64> for HigherOrderGreeks {
+    type Output = HigherOrderGreeks;
+
+    fn mul(self, scalar: f64) -> HigherOrderGreeks {
+        HigherOrderGreeks {
+            vanna: self.vanna * scalar,
+            volga: self.volga * scalar,
+            charm: self.charm * scalar,
+            speed: self.speed * scalar,
+            zomma: self.zomma * scalar,
+            color: self.color * scalar,
+        }
+    }
+}
+
+impl Sum for HigherOrderGreeks {
+    fn sum<I: Iterator<Item = HigherOrderGreeks>>(iter: I) -> HigherOrderGreeks {
+        iter.fold(HigherOrderGreeks::default(), Add::add)
+    }
+}
+
+impl Bump {
+    /// Size of the bump to `value`, at least [`MIN_BUMP_SIZE`].
+    pub fn size(&self, value: f64) -> f64 {
+        let size = match self {
+            Bump::Absolute(size) => size.abs(),
+            Bump::Relative(fraction) => (fraction * value).abs(),
+        };
+
+        size.max(MIN_BUMP_SIZE)
+    }
+}
+
+impl Default for BumpAndRevalue {
+    /// Central differences, with a 0.1% relative spot bump, one basis point
+    /// bumps to the volatility and rate, and a one day time bump.
+    fn default() -> Self {
+        Self {
+            spot_bump: Bump::Relative(1e-3),
+            volatility_bump: Bump::Absolute(1e-4),
+            rate_bump: Bump::Absolute(1e-4),
+            time_bump: Bump::Absolute(1.0 / 365.0),
+            scheme: DifferenceScheme::Central,
+        }
+    }
+}
+
+impl BumpAndRevalue {
+    /// Create a new bump-and-revalue engine.
+    pub fn new(
+        spot_bump: Bump,
+        volatility_bump: Bump,
+        rate_bump: Bump,
+        time_bump: Bump,
+        scheme: DifferenceScheme,
+    ) -> Self {
+        Self {
+            spot_bump,
+            volatility_bump,
+            rate_bump,
+            time_bump,
+            scheme,
+        }
+    }
+
+    /// Greeks of `pricer` at `state`, by revaluing it under bumped inputs.
+    ///
+    /// Gamma always uses a second difference: centred on the spot for the
+    /// central scheme, and on the spot plus one bump for the forward scheme.
+    /// The option is never revalued past expiry or at a non-positive
+    /// volatility: when the time to expiry (or the volatility) is smaller
+    /// than its bump, theta (or vega) falls back to a one-sided difference
+    /// on the other side.
+    pub fn greeks<F>(&self, pricer: F, state: &MarketState) -> Greeks
+    where
+        F: Fn(&MarketState) -> f64,
+    {
+        let base = pricer(state);
+
+        let h_s = self.spot_bump.size(state.spot);
+        let h_v = self.volatility_bump.size(state.volatility);
+        let h_r = self.rate_bump.size(state.rate);
+        let h_t = self.time_bump.size(state.time_to_expiry);
+
+        let price = |spot: f64, volatility: f64, rate: f64, time_to_expiry: f64| {
+            pricer(&MarketState {
+                spot,
+                volatility,
+                rate,
+                time_to_expiry,
+            })
+        };
+
+        let MarketState {
+            spot: s,
+            volatility: v,
+            rate: r,
+            time_to_expiry: t,
+        } = *state;
+
+        match self.scheme {
+            DifferenceScheme::Central => {
+                let up = price(s + h_s, v, r, t);
+                let down = price(s - h_s, v, r, t);
+
+                Greeks {
+                    delta: (up - down) / (2.0 * h_s),
+                    gamma: (up - 2.0 * base + down) / (h_s * h_s),
+                    vega: first_difference(|v| price(s, v, r, t), v, h_v, v > h_v),
+                    theta: -first_difference(|t| price(s, v, r, t), t, h_t, t >= h_t),
+                    rho: (price(s, v, r + h_r, t) - price(s, v, r - h_r, t)) / (2.0 * h_r),
+                }
+            }
+            DifferenceScheme::Forward => {
+                let up = price(s + h_s, v, r, t);
+                let up_2 = price(s + 2.0 * h_s, v, r, t);
+
+                Greeks {
+                    delta: (up - base) / h_s,
+                    gamma: (up_2 - 2.0 * up + base) / (h_s * h_s),
+                    vega: (price(s, v + h_v, r, t) - base) / h_v,
+                    theta: match t >= h_t {
+                        true => (price(s, v, r, t - h_t) - base) / h_t,
+                        false => (base - price(s, v, r, t + h_t)) / h_t,
+                    },
+                    rho: (price(s, v, r + h_r, t) - base) / h_r,
+                }
+            }
+        }
+    }
+
+    /// Higher-order Greeks of `pricer` at `state`, by revaluing it under
+    /// bumped inputs.
+    ///
+    /// These use central differences, whatever the scheme, since one-sided
+    /// differences of second and third derivatives are too inaccurate to
+    /// be useful. The exceptions are a time to expiry or volatility no
+    /// larger than its bump, which would revalue the option at or past
+    /// expiry, or at a non-positive volatility: those sensitivities fall
+    /// back to one-sided differences on the other side.
+    pub fn higher_order_greeks<F>(&self, pricer: F, state: &MarketState) -> HigherOrderGreeks
+    where
+        F: Fn(&MarketState) -> f64,
+    {
+        let MarketState {
+            spot: s,
+            volatility: v,
+            rate: r,
+            time_to_expiry: t,
+        } = *state;
+
+        let h_s = self.spot_bump.size(s);
+        let h_v = self.volatility_bump.size(v);
+        let h_t = self.time_bump.size(t);
+        let (centred_v, centred_t) = (v > h_v, t > h_t);
+
+        let price = |spot: f64, volatility: f64, time_to_expiry: f64| {
+            pricer(&MarketState {
+                spot,
+                volatility,
+                rate: r,
+                time_to_expiry,
+            })
+        };
+        let delta = |v: f64, t: f64| (price(s + h_s, v, t) - price(s - h_s, v, t)) / (2.0 * h_s);
+        let gamma = |v: f64, t: f64| {
+            (price(s + h_s, v, t) - 2.0 * price(s, v, t) + price(s - h_s, v, t)) / (h_s * h_s)
+        };
+
+        let speed = (price(s + 2.0 * h_s, v, t) - 2.0 * price(s + h_s, v, t)
+            + 2.0 * price(s - h_s, v, t)
+            - price(s - 2.0 * h_s, v, t))
+            / (2.0 * h_s * h_s * h_s);
+
+        HigherOrderGreeks {
+            vanna: first_difference(|v| delta(v, t), v, h_v, centred_v),
+            volga: second_difference(|v| price(s, v, t), v, h_v, centred_v),
+            charm: -first_difference(|t| delta(v, t), t, h_t, centred_t),
+            speed,
+            zomma: first_difference(|v| gamma(v, t), v, h_v, centred_v),
+            color: -first_difference(|t| gamma(v, t), t, h_t, centred_t),
+        }
+    }
+}
+
+/// First derivative of `f` at `x`, by a central difference if `centred`,
+/// and otherwise a forward difference (bumping `x` up only).
+fn first_difference<F>(f: F, x: f64, h: f64, centred: bool) -> f64
+where
+    F: Fn(f64) -> f64,
+{
+    match centred {
+        true => (f(x + h) - f(x - h)) / (2.0 * h),
+        false => (f(x + h) - f(x)) / h,
+    }
+}
+
+/// Second derivative of `f` at `x`, by a central difference if `centred`,
+/// and otherwise a forward difference (bumping `x` up only).
+fn second_difference<F>(f: F, x: f64, h: f64, centred: bool) -> f64
+where
+    F: Fn(f64) -> f64,
+{
+    match centred {
+        true => (f(x + h) - 2.0 * f(x) + f(x - h)) / (h * h),
+        false => (f(x + 2.0 * h) - 2.0 * f(x + h) + f(x)) / (h * h),
+    }
+}
+
+impl OptionGreeks for BlackScholesMerton {
+    fn greeks(&self) -> Greeks {
+        Greeks {
+            delta: self.delta(),
+            gamma: self.gamma(),
+            vega: self.vega(),
+            theta: self.theta(),
+            rho: self.rho(),
+        }
+    }
+
+    fn higher_order_greeks(&self) -> HigherOrderGreeks {
+        HigherOrderGreeks {
+            vanna: self.vanna(),
+            volga: self.vomma(),
+            // `charm` is the sensitivity to the time to expiry.
+            charm: -self.charm(),
+            speed: self.speed(),
+            zomma: self.zomma(),
+            color: self.colour(),
+        }
+    }
+
+    fn shifted(&self, spot_shift: f64, vol_shift: f64) -> Self {
+        BlackScholesMerton {
+            underlying_price: self.underlying_price + spot_shift,
+            volatility: self.volatility + vol_shift,
+            ..*self
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_greeks {
+    use super::*;
+    use crate::{GeneralisedBlackScholesMerton, Lattice, TypeFlag};
+    use time::Duration;
+    use RustQuant_time::today;
+    use RustQuant_utils::{assert_approx_equal, RUSTQUANT_EPSILON};
+
+    #[test]
+    fn test_greeks_aggregation() {
+        let option = BlackScholesMerton::new(
+            0.05,
+            100.0,
+            100.0,
+            0.2,
+            0.05,
+            None,
+            today() + Duration::days(365),
+            TypeFlag::Call,
+        );
+
+        let greeks = option.greeks();
+        let total: Greeks = [greeks, greeks * -1.0, greeks].into_iter().sum();
+
+        assert_eq!(total, greeks);
+        assert_approx_equal!(greeks.delta, option.delta(), RUSTQUANT_EPSILON);
+    }
+
+    #[test]
+    fn test_shifted() {
+        let option = BlackScholesMerton::new(
+            0.05,
+            100.0,
+            100.0,
+            0.2,
+            0.05,
+            None,
+            today() + Duration::days(365),
+            TypeFlag::Call,
+        );
+
+        let shifted = option.shifted(5.0, 0.01);
+
+        assert_approx_equal!(shifted.underlying_price, 105.0, RUSTQUANT_EPSILON);
+        assert_approx_equal!(shifted.volatility, 0.21, RUSTQUANT_EPSILON);
+        assert!(shifted.price() > option.price());
+    }
+
+    fn merton(state: &MarketState) -> f64 {
+        crate::Merton73::new(state.spot, state.rate, 0.02, state.volatility).price(
+            95.0,
+            state.time_to_expiry,
+            TypeFlag::Put,
+        )
+    }
+
+    const STATE: MarketState = MarketState {
+        spot: 100.0,
+        volatility: 0.25,
+        rate: 0.04,
+        time_to_expiry: 0.75,
+    };
+
+    fn closed_form() -> Greeks {
+        let model = crate::Merton73::new(100.0, 0.04, 0.02, 0.25);
+
+        Greeks {
+            delta: model.delta(95.0, 0.75, TypeFlag::Put),
+            gamma: model.gamma(95.0, 0.75, TypeFlag::Put),
+            vega: model.vega(95.0, 0.75, TypeFlag::Put),
+            theta: model.theta(95.0, 0.75, TypeFlag::Put),
+            rho: model.rho(95.0, 0.75, TypeFlag::Put),
+        }
+    }
+
+    #[test]
+    fn test_bump_and_revalue_central() {
+        let bumped = BumpAndRevalue::default().greeks(merton, &STATE);
+        let exact = closed_form();
+
+        assert_approx_equal!(bumped.delta, exact.delta, 1e-6);
+        assert_approx_equal!(bumped.gamma, exact.gamma, 1e-6);
+        assert_approx_equal!(bumped.vega, exact.vega, 1e-6);
+        assert_approx_equal!(bumped.rho, exact.rho, 1e-6);
+        assert_approx_equal!(bumped.theta, exact.theta, 1e-4);
+    }
+
+    #[test]
+    fn test_bump_and_revalue_forward() {
+        let engine = BumpAndRevalue::new(
+            Bump::Absolute(1e-4),
+            Bump::Relative(1e-5),
+            Bump::Absolute(1e-6),
+            Bump::Absolute(1e-6),
+            DifferenceScheme::Forward,
+        );
+        let bumped = engine.greeks(merton, &STATE);
+        let exact = closed_form();
+
+        assert_approx_equal!(bumped.delta, exact.delta, 1e-4);
+        assert_approx_equal!(bumped.gamma, exact.gamma, 1e-3);
+        assert_approx_equal!(bumped.vega, exact.vega, 1e-3);
+        assert_approx_equal!(bumped.rho, exact.rho, 1e-3);
+        assert_approx_equal!(bumped.theta, exact.theta, 1e-3);
+    }
+
+    #[test]
+    fn test_bump_sizes_are_floored() {
+        assert_eq!(Bump::Relative(1e-3).size(0.0), MIN_BUMP_SIZE);
+        assert_eq!(Bump::Absolute(0.0).size(0.2), MIN_BUMP_SIZE);
+        assert_eq!(Bump::Relative(1e-3).size(-50.0), 0.05);
+    }
+
+    #[test]
+    fn test_bump_and_revalue_edge_cases() {
+        // The pricer rejects inputs the engine must never revalue at.
+        let checked = |state: &MarketState| {
+            assert!(
+                state.volatility > 0.0,
+                "Revalued at a non-positive volatility."
+            );
+            assert!(state.time_to_expiry >= 0.0, "Revalued past expiry.");
+
+            merton(state)
+        };
+
+        let engine = BumpAndRevalue {
+            volatility_bump: Bump::Absolute(0.5),
+            rate_bump: Bump::Relative(1e-3),
+            ..BumpAndRevalue::default()
+        };
+
+        for scheme in [DifferenceScheme::Central, DifferenceScheme::Forward] {
+            let engine = BumpAndRevalue { scheme, ..engine };
+
+            let at_zero_rate = engine.greeks(checked, &MarketState { rate: 0.0, ..STATE });
+            let at_expiry = engine.greeks(
+                checked,
+                &MarketState {
+                    spot: 90.0,
+                    time_to_expiry: 0.0,
+                    ..STATE
+                },
+            );
+
+            for greeks in [at_zero_rate, at_expiry] {
+                for greek in [
+                    greeks.delta,
+                    greeks.gamma,
+                    greeks.vega,
+                    greeks.theta,
+                    greeks.rho,
+                ] {
+                    assert!(greek.is_finite());
+                }
+            }
+        }
+
+        let higher = engine.higher_order_greeks(
+            checked,
+            &MarketState {
+                time_to_expiry: 1e-4,
+                ..STATE
+            },
+        );
+
+        for greek in [
+            higher.vanna,
+            higher.volga,
+            higher.charm,
+            higher.speed,
+            higher.zomma,
+            higher.color,
+        ] {
+            assert!(greek.is_finite());
+        }
+    }
+
+    #[test]
+    fn test_higher_order_greeks_aggregation() {
+        let option = BlackScholesMerton::new(
+            0.05,
+            100.0,
+            110.0,
+            0.2,
+            0.03,
+            None,
+            today() + Duration::days(365),
+            TypeFlag::Put,
+        );
+
+        let greeks = option.higher_order_greeks();
+        let total: HigherOrderGreeks = [greeks, greeks * -1.0, greeks].into_iter().sum();
+
+        assert_eq!(total, greeks);
+        assert_approx_equal!(greeks.volga, option.vomma(), RUSTQUANT_EPSILON);
+    }
+
+    #[test]
+    fn test_higher_order_greeks_match_bump_and_revalue() {
+        for (k, flag) in [(95.0, TypeFlag::Put), (110.0, TypeFlag::Call)] {
+            let option = BlackScholesMerton::new(
+                0.02,
+                100.0,
+                k,
+                0.25,
+                0.04,
+                None,
+                today() + Duration::days(365),
+                flag,
+            );
+            let state = MarketState {
+                spot: 100.0,
+                volatility: 0.25,
+                rate: 0.04,
+                time_to_expiry: option.year_fraction(),
+            };
+            let pricer = |state: &MarketState| {
+                crate::Merton73::new(state.spot, state.rate, 0.02, state.volatility).price(
+                    k,
+                    state.time_to_expiry,
+                    flag,
+                )
+            };
+
+            let engine = BumpAndRevalue {
+                volatility_bump: Bump::Absolute(1e-3),
+                ..BumpAndRevalue::default()
+            };
+            let bumped = engine.higher_order_greeks(pricer, &state);
+            let exact = option.higher_order_greeks();
+
+            assert_approx_equal!(bumped.vanna, exact.vanna, 1e-4);
+            assert_approx_equal!(bumped.volga, exact.volga, 1e-3);
+            assert_approx_equal!(bumped.charm, exact.charm, 1e-4);
+            assert_approx_equal!(bumped.speed, exact.speed, 1e-5);
+            assert_approx_equal!(bumped.zomma, exact.zomma, 1e-4);
+            assert_approx_equal!(bumped.color, exact.color, 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_bump_and_revalue_lattice() {
+        // An American put has no closed form, but a tree can be bumped.
+        // Its delta and gamma agree with the ones read off the tree.
+        let tree = |state: &MarketState| {
+            crate::BinomialTree::new(
+                state.spot,
+                state.rate,
+                0.0,
+                state.volatility,
+                state.time_to_expiry,
+                500,
+            )
+        };
+        let option = crate::LatticeOption::new(40.0, TypeFlag::Put, true);
+        let state = MarketState {
+            spot: 36.0,
+            volatility: 0.2,
+            rate: 0.06,
+            time_to_expiry: 1.0,
+        };
+
+        // A spot bump of several nodes smooths out the tree's oscillation.
+        let engine = BumpAndRevalue {
+            spot_bump: Bump::Relative(0.02),
+            ..BumpAndRevalue::default()
+        };
+        let bumped = engine.greeks(|state| tree(state).value(&option).price, &state);
+        let nodes = tree(&state).value(&option);
+
+        assert_approx_equal!(bumped.delta, nodes.delta, 0.01);
+        assert_approx_equal!(bumped.gamma, nodes.gamma, 0.01);
+        assert!(bumped.vega > 0.0);
+        assert!(bumped.rho < 0.0);
+    }
+}
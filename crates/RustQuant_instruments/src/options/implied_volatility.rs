@@ -90,11 +90,37 @@ pub(crate) const MAXIMUM_RATIONAL_CUBIC_CONTROL_PARAMETER_VALUE: f64 =
 /// ```
 #[must_use]
 pub fn implied_volatility(price: f64, S: f64, K: f64, T: f64, r: f64, flag: TypeFlag) -> f64 {
-    let rate = (r * T).exp();
-
-    let undiscounted_option_price = price * rate;
+    black76_implied_volatility(price, S * (r * T).exp(), K, T, r, flag)
+}
 
-    let F = S * rate;
+/// Implied volatility of an option on a forward or futures price,
+/// under the Black (1976) model, with the same method as `implied_volatility`.
+///
+/// Options on a dividend paying stock can be inverted with the forward
+/// price $F = S e^{(r - q) T}$.
+/// If price is below intrinsic value, it returns -INF,
+/// if price is above intrinsic value, it returns INF.
+/// ```
+/// use RustQuant::instruments::options::implied_volatility::*;
+/// use RustQuant::instruments::options::{Black76, GeneralisedBlackScholesMerton, TypeFlag};
+/// use RustQuant::utils::assert_approx_equal;
+///
+/// let price = Black76::new(100.0, 0.03, 0.25).price(130.0, 0.5, TypeFlag::Put);
+///
+/// let iv = black76_implied_volatility(price, 100.0, 130.0, 0.5, 0.03, TypeFlag::Put);
+///
+/// assert_approx_equal!(iv, 0.25, 1e-12);
+/// ```
+#[must_use]
+pub fn black76_implied_volatility(
+    price: f64,
+    F: f64,
+    K: f64,
+    T: f64,
+    r: f64,
+    flag: TypeFlag,
+) -> f64 {
+    let undiscounted_option_price = price * (r * T).exp();
 
     let q = match flag {
         TypeFlag::Call => 1.0,
@@ -1054,7 +1080,7 @@ pub(crate) fn implied_volatility_from_a_transformed_rational_guess_with_limited_
 #[cfg(test)]
 mod test_lets_be_rational {
     use super::*;
-    use crate::BlackScholesMerton;
+    use crate::{Black76, BlackScholes73, BlackScholesMerton, GeneralisedBlackScholesMerton};
     use std::panic;
     use time::Duration;
     use RustQuant_time::today;
@@ -1197,6 +1223,47 @@ mod test_lets_be_rational {
         assert_approx_equal!(s, 0.04000000000000133, 1e-10);
     }
 
+    #[test]
+    fn test_black76_round_trip() {
+        // Deep in and out of the money, short and long dated.
+        for &(T, v) in &[(1.0 / 52.0, 0.05), (0.5, 0.2), (2.0, 0.8), (10.0, 1.5)] {
+            for &K in &[20.0, 60.0, 90.0, 100.0, 110.0, 160.0, 400.0] {
+                let model = Black76::new(100.0, 0.03, v);
+
+                // The time value of either option is the out-of-the-money
+                // price, by put-call parity. Below this, the prices carry
+                // no information about the volatility.
+                let time_value = f64::min(
+                    model.price(K, T, TypeFlag::Call),
+                    model.price(K, T, TypeFlag::Put),
+                );
+                if time_value < 1e-10 {
+                    continue;
+                }
+
+                for flag in [TypeFlag::Call, TypeFlag::Put] {
+                    let price = model.price(K, T, flag);
+
+                    let iv = black76_implied_volatility(price, 100.0, K, T, 0.03, flag);
+
+                    assert_approx_equal!(iv, v, 1e-8);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_black76_agrees_with_black_scholes() {
+        let (S, K, T, r) = (100.0, 130.0, 0.75, 0.04);
+        let price = BlackScholes73::new(S, r, 0.3).price(K, T, TypeFlag::Call);
+
+        assert_approx_equal!(
+            implied_volatility(price, S, K, T, r, TypeFlag::Call),
+            black76_implied_volatility(price, S * (r * T).exp(), K, T, r, TypeFlag::Call),
+            1e-15
+        );
+    }
+
     #[test]
     fn test_linear_interpolation() {
         let x = -4.920_739_400_840_902;
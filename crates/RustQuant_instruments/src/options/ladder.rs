@@ -0,0 +1,242 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Ladder options.
+//!
+//! A ladder call locks in the gain $L_j - K$ of the highest rung $L_j$ the
+//! underlying reaches before expiry, and pays
+//! $\max(S_T - K, L_j - K, 0)$. A ladder put does the same with rungs
+//! below the strike, locking in $K - L_j$ at the lowest rung reached.
+//!
+//! With $L_0 = K$, the call's payoff is
+//! $S_T - K + (L_J - S_T)^+$ where $J$ is the highest rung reached, which
+//! telescopes into a vanilla call plus, for each rung, an up-and-in put
+//! struck at the rung less one struck at the rung below, both knocked in
+//! at the rung. Puts struck at or below their barrier knock in at the
+//! reflection of a vanilla put,
+//! $P_{ui}(S) = (H / S)^{2 \nu / \sigma^2} P(H^2 / S)$ with
+//! $\nu = r - q - \sigma^2 / 2$, so the ladder has a closed form under
+//! continuous monitoring.
+
+use super::{GeneralisedBlackScholesMerton, Merton73, TypeFlag};
+use crate::Payoff;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Ladder option.
+#[derive(Debug, Clone)]
+pub struct LadderOption {
+    /// Strike price.
+    pub strike: f64,
+
+    /// Rungs, in the order the underlying reaches them: increasing above
+    /// the strike for a call, decreasing below it for a put.
+    pub rungs: Vec<f64>,
+
+    /// Call or put.
+    pub type_flag: TypeFlag,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl LadderOption {
+    /// Create a new ladder option. The rungs may be given in any order.
+    ///
+    /// # Panics
+    /// Panics if a call has a rung at or below the strike, or a put a
+    /// rung at or above it.
+    pub fn new(strike: f64, mut rungs: Vec<f64>, type_flag: TypeFlag) -> Self {
+        match type_flag {
+            TypeFlag::Call => {
+                assert!(
+                    rungs.iter().all(|&rung| rung > strike),
+                    "A ladder call's rungs must be above the strike."
+                );
+                rungs.sort_by(f64::total_cmp);
+            }
+            TypeFlag::Put => {
+                assert!(
+                    rungs.iter().all(|&rung| rung < strike),
+                    "A ladder put's rungs must be below the strike."
+                );
+                rungs.sort_by(|a, b| b.total_cmp(a));
+            }
+        }
+
+        Self {
+            strike,
+            rungs,
+            type_flag,
+        }
+    }
+
+    /// Price of the option under continuous monitoring of the rungs,
+    /// by decomposition into a vanilla option and knock-in options.
+    ///
+    /// # Arguments:
+    /// * `s` - Underlying price.
+    /// * `t` - Time to expiry (in years).
+    /// * `r` - Risk-free rate.
+    /// * `q` - Dividend yield.
+    /// * `v` - Volatility.
+    pub fn price_closed_form(&self, s: f64, t: f64, r: f64, q: f64, v: f64) -> f64 {
+        let flag = self.type_flag;
+        let opposite = match flag {
+            TypeFlag::Call => TypeFlag::Put,
+            TypeFlag::Put => TypeFlag::Call,
+        };
+
+        let nu = r - q - 0.5 * v * v;
+
+        // Option of the opposite type struck at `k`, knocked in at `h`.
+        let knock_in = |k: f64, h: f64| {
+            let reached = match flag {
+                TypeFlag::Call => s >= h,
+                TypeFlag::Put => s <= h,
+            };
+
+            match reached {
+                true => Merton73::new(s, r, q, v).price(k, t, opposite),
+                false => {
+                    (h / s).powf(2.0 * nu / (v * v))
+                        * Merton73::new(h * h / s, r, q, v).price(k, t, opposite)
+                }
+            }
+        };
+
+        let vanilla = Merton73::new(s, r, q, v).price(self.strike, t, flag);
+
+        let mut below = self.strike;
+
+        vanilla
+            + self
+                .rungs
+                .iter()
+                .map(|&rung| {
+                    let value = knock_in(rung, rung) - knock_in(below, rung);
+                    below = rung;
+                    value
+                })
+                .sum::<f64>()
+    }
+}
+
+impl Payoff for LadderOption {
+    type Underlying = Vec<f64>;
+
+    fn payoff(&self, underlying: Self::Underlying) -> f64 {
+        let terminal = underlying[underlying.len() - 1];
+
+        match self.type_flag {
+            TypeFlag::Call => {
+                let high = underlying.iter().copied().fold(f64::MIN, f64::max);
+                let locked_in = self
+                    .rungs
+                    .iter()
+                    .rev()
+                    .find(|&&rung| rung <= high)
+                    .map_or(0.0, |rung| rung - self.strike);
+
+                (terminal - self.strike).max(locked_in).max(0.0)
+            }
+            TypeFlag::Put => {
+                let low = underlying.iter().copied().fold(f64::MAX, f64::min);
+                let locked_in = self
+                    .rungs
+                    .iter()
+                    .rev()
+                    .find(|&&rung| rung >= low)
+                    .map_or(0.0, |rung| self.strike - rung);
+
+                (self.strike - terminal).max(locked_in).max(0.0)
+            }
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_ladder {
+    use super::*;
+    use crate::MonteCarloEngine;
+    use RustQuant_stochastics::GeometricBrownianMotion;
+    use RustQuant_utils::assert_approx_equal;
+
+    const S: f64 = 100.0;
+    const R: f64 = 0.05;
+    const Q: f64 = 0.02;
+    const V: f64 = 0.25;
+    const T: f64 = 1.0;
+
+    #[test]
+    fn test_payoff() {
+        let path = vec![100.0, 112.0, 121.0, 95.0, 105.0];
+
+        let call = LadderOption::new(100.0, vec![120.0, 110.0, 130.0], TypeFlag::Call);
+        let put = LadderOption::new(110.0, vec![100.0, 90.0], TypeFlag::Put);
+
+        assert_eq!(call.rungs, vec![110.0, 120.0, 130.0]);
+        assert_eq!(call.payoff(path.clone()), 20.0);
+        assert_eq!(put.payoff(path), 10.0);
+    }
+
+    #[test]
+    fn test_limits() {
+        // Without rungs, a ladder is a vanilla option.
+        for type_flag in [TypeFlag::Call, TypeFlag::Put] {
+            assert_approx_equal!(
+                LadderOption::new(100.0, vec![], type_flag).price_closed_form(S, T, R, Q, V),
+                Merton73::new(S, R, Q, V).price(100.0, T, type_flag),
+                1e-12
+            );
+        }
+
+        // A rung already reached locks in its gain.
+        let ladder = LadderOption::new(90.0, vec![95.0], TypeFlag::Call);
+        let exact =
+            Merton73::new(S, R, Q, V).price(95.0, T, TypeFlag::Call) + 5.0 * f64::exp(-R * T);
+
+        assert_approx_equal!(ladder.price_closed_form(S, T, R, Q, V), exact, 1e-10);
+    }
+
+    #[test]
+    fn test_monte_carlo_agrees_with_decomposition() {
+        let engine = MonteCarloEngine::new(
+            GeometricBrownianMotion::new(R - Q, V),
+            S,
+            R,
+            T,
+            20_000,
+            1000,
+        )
+        .with_seed(41);
+
+        let options = [
+            LadderOption::new(100.0, vec![110.0, 120.0, 130.0], TypeFlag::Call),
+            LadderOption::new(100.0, vec![90.0, 80.0], TypeFlag::Put),
+        ];
+
+        for option in options {
+            let exact = option.price_closed_form(S, T, R, Q, V);
+            let estimate = engine.price_path(&option);
+
+            // Discrete monitoring misses some rungs, so allow for a small bias.
+            assert!(estimate.price < exact);
+            assert!(exact - estimate.price < 3.0 * estimate.standard_error + 0.1);
+            assert!(exact > Merton73::new(S, R, Q, V).price(100.0, T, option.type_flag));
+        }
+    }
+}
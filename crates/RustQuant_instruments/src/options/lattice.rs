@@ -0,0 +1,838 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Recombining lattice (tree) pricers for the Black-Scholes model.
+//!
+//! The binomial and trinomial trees share the `Lattice` trait, which rolls
+//! an option back through the tree, applying early exercise and knock-out
+//! barriers at each node. Bermudan exercise dates are rounded to the
+//! nearest time step. Delta, gamma and theta are read off the nodes
+//! near the root, so they come at no extra cost.
+//!
+//! Discrete cash dividends are handled on the binomial tree with the
+//! escrowed dividend model: the tree is built for the stock price less the
+//! present value of the dividends to expiry, and the present value of the
+//! dividends still to be paid is added back at each node. The tree still
+//! recombines, and early exercise sees the cum-dividend price.
+//!
+//! `BinomialOption` reports the Greeks of an option on the binomial tree
+//! through `OptionGreeks`, like the closed-form European pricers, so
+//! American options can be risk-managed alongside them.
+
+use super::{BarrierType, TypeFlag};
+use super::{Bump, BumpAndRevalue, Greeks, HigherOrderGreeks, MarketState, OptionGreeks};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS & TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Cox-Ross-Rubinstein (1979) binomial tree.
+#[derive(Debug, Clone)]
+pub struct BinomialTree {
+    /// Underlying price at the root.
+    pub s: f64,
+
+    /// Risk-free rate.
+    pub r: f64,
+
+    /// Dividend yield.
+    pub q: f64,
+
+    /// Volatility.
+    pub v: f64,
+
+    /// Time to expiry (in years).
+    pub t: f64,
+
+    /// Number of time steps.
+    pub steps: usize,
+
+    /// Discrete cash dividends, as (time in years from the root, amount).
+    pub dividends: Vec<(f64, f64)>,
+}
+
+/// Trinomial tree in log price, with nodes spaced $\lambda \sigma \sqrt{\Delta t}$ apart.
+///
+/// The stretch $\lambda \geq 1$ keeps the branch probabilities positive.
+/// With `with_barrier`, it is chosen so that a layer of nodes lies exactly
+/// on the barrier (Ritchken, 1995). Barrier prices then converge smoothly
+/// to their continuously monitored values, without the sawtooth pattern
+/// of trees whose nodes straddle the barrier.
+#[derive(Debug, Clone, Copy)]
+pub struct TrinomialTree {
+    /// Underlying price at the root.
+    pub s: f64,
+
+    /// Risk-free rate.
+    pub r: f64,
+
+    /// Dividend yield.
+    pub q: f64,
+
+    /// Volatility.
+    pub v: f64,
+
+    /// Time to expiry (in years).
+    pub t: f64,
+
+    /// Number of time steps.
+    pub steps: usize,
+
+    /// Stretch of the node spacing ($\lambda$).
+    pub stretch: f64,
+}
+
+/// When an option on a lattice can be exercised.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LatticeExercise {
+    /// Only at expiry.
+    European,
+
+    /// At every node.
+    American,
+
+    /// At expiry, and at the given times (in years from the root).
+    /// Each time is rounded to the nearest step of the tree.
+    Bermudan(Vec<f64>),
+}
+
+/// An option that can be priced on a lattice.
+#[derive(Debug, Clone)]
+pub struct LatticeOption {
+    /// Strike price.
+    pub strike: f64,
+
+    /// Call or put.
+    pub type_flag: TypeFlag,
+
+    /// Exercise style.
+    pub exercise: LatticeExercise,
+
+    /// Barrier type and level, monitored at every node.
+    pub barrier: Option<(BarrierType, f64)>,
+}
+
+/// Price and Greeks of an option, from the nodes of a lattice.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct LatticeValuation {
+    /// Option price.
+    pub price: f64,
+
+    /// Sensitivity to the underlying price.
+    pub delta: f64,
+
+    /// Sensitivity of delta to the underlying price.
+    pub gamma: f64,
+
+    /// Sensitivity to the passage of time (per year).
+    pub theta: f64,
+}
+
+/// An option on a binomial tree, reporting its Greeks through `OptionGreeks`.
+///
+/// Delta, gamma and theta are read off the nodes of the tree. Vega, rho
+/// and the higher-order Greeks come from revaluing on trees rebuilt with
+/// bumped inputs. The bumps span several nodes, so the tree's oscillation
+/// in the underlying price does not swamp them.
+#[derive(Debug, Clone)]
+pub struct BinomialOption {
+    /// The tree, for the underlying and market inputs.
+    pub tree: BinomialTree,
+
+    /// The option.
+    pub option: LatticeOption,
+}
+
+/// A recombining tree for the underlying price.
+pub trait Lattice {
+    /// Number of time steps.
+    fn steps(&self) -> usize;
+
+    /// Length of each time step (in years).
+    fn time_step(&self) -> f64;
+
+    /// Number of nodes at `step`.
+    fn nodes(&self, step: usize) -> usize;
+
+    /// Underlying price at `node` of `step`, counting up from the lowest.
+    fn underlying(&self, step: usize, node: usize) -> f64;
+
+    /// Discounted expected values at `step`, given the values at `step + 1`.
+    fn rollback(&self, step: usize, next: &[f64]) -> Vec<f64>;
+
+    /// Price an option by backward induction, and read its Greeks off the
+    /// first layer of the tree with at least three nodes.
+    ///
+    /// Knock-in barriers are priced by in-out parity, so they are only
+    /// supported for European exercise.
+    ///
+    /// # Panics
+    /// Panics for an American or Bermudan knock-in option, or if the tree
+    /// has too few steps to reach three nodes.
+    fn value(&self, option: &LatticeOption) -> LatticeValuation {
+        let knock_out = match option.barrier {
+            Some((BarrierType::UpAndIn, b)) => Some((BarrierType::UpAndOut, b)),
+            Some((BarrierType::DownAndIn, b)) => Some((BarrierType::DownAndOut, b)),
+            _ => None,
+        };
+
+        if let Some(knock_out) = knock_out {
+            assert!(
+                option.exercise == LatticeExercise::European,
+                "Knock-in barriers are only supported for European exercise."
+            );
+
+            let vanilla = self.value(&LatticeOption {
+                barrier: None,
+                ..option.clone()
+            });
+            let out = self.value(&LatticeOption {
+                barrier: Some(knock_out),
+                ..option.clone()
+            });
+
+            return LatticeValuation {
+                price: vanilla.price - out.price,
+                delta: vanilla.delta - out.delta,
+                gamma: vanilla.gamma - out.gamma,
+                theta: vanilla.theta - out.theta,
+            };
+        }
+
+        let n = self.steps();
+        let greek_step = (1..=n)
+            .find(|&step| self.nodes(step) >= 3)
+            .expect("The tree needs enough steps to have three nodes.");
+
+        let dt = self.time_step();
+        let exercisable = |step: usize| match &option.exercise {
+            LatticeExercise::European => false,
+            LatticeExercise::American => true,
+            LatticeExercise::Bermudan(times) => {
+                times.iter().any(|&time| (time / dt).round() == step as f64)
+            }
+        };
+
+        let payoff = |s: f64| match option.type_flag {
+            TypeFlag::Call => (s - option.strike).max(0.0),
+            TypeFlag::Put => (option.strike - s).max(0.0),
+        };
+
+        let knocked_out = |s: f64| match option.barrier {
+            Some((BarrierType::UpAndOut, b)) => s >= b,
+            Some((BarrierType::DownAndOut, b)) => s <= b,
+            _ => false,
+        };
+
+        let apply = |step: usize, values: &mut [f64]| {
+            for (node, value) in values.iter_mut().enumerate() {
+                let s = self.underlying(step, node);
+
+                if knocked_out(s) {
+                    *value = 0.0;
+                } else if exercisable(step) {
+                    *value = value.max(payoff(s));
+                }
+            }
+        };
+
+        let mut values: Vec<f64> = (0..self.nodes(n))
+            .map(|node| self.underlying(n, node))
+            .map(|s| if knocked_out(s) { 0.0 } else { payoff(s) })
+            .collect();
+
+        let mut layer = Vec::new();
+
+        for step in (0..n).rev() {
+            values = self.rollback(step, &values);
+            apply(step, &mut values);
+
+            if step == greek_step {
+                layer.clone_from(&values);
+            }
+        }
+
+        // The three nodes around the middle of the Greeks layer.
+        let middle = self.nodes(greek_step) / 2;
+        let s: Vec<f64> = (middle - 1..=middle + 1)
+            .map(|node| self.underlying(greek_step, node))
+            .collect();
+        let v = &layer[middle - 1..=middle + 1];
+
+        let delta_down = (v[1] - v[0]) / (s[1] - s[0]);
+        let delta_up = (v[2] - v[1]) / (s[2] - s[1]);
+
+        LatticeValuation {
+            price: values[0],
+            delta: (v[2] - v[0]) / (s[2] - s[0]),
+            gamma: 2.0 * (delta_up - delta_down) / (s[2] - s[0]),
+            theta: (v[1] - values[0]) / (greek_step as f64 * dt),
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl LatticeOption {
+    /// Create a new European or American option without a barrier.
+    pub fn new(strike: f64, type_flag: TypeFlag, american: bool) -> Self {
+        let exercise = match american {
+            true => LatticeExercise::American,
+            false => LatticeExercise::European,
+        };
+
+        Self {
+            strike,
+            type_flag,
+            exercise,
+            barrier: None,
+        }
+    }
+
+    /// Create a new Bermudan option without a barrier, exercisable at
+    /// expiry and at `exercise_times` (in years from the root).
+    pub fn bermudan(strike: f64, type_flag: TypeFlag, exercise_times: Vec<f64>) -> Self {
+        Self {
+            strike,
+            type_flag,
+            exercise: LatticeExercise::Bermudan(exercise_times),
+            barrier: None,
+        }
+    }
+
+    /// Add a barrier, monitored at every node of the tree.
+    pub fn with_barrier(mut self, barrier_type: BarrierType, barrier: f64) -> Self {
+        self.barrier = Some((barrier_type, barrier));
+        self
+    }
+}
+
+impl BinomialTree {
+    /// Create a new binomial tree.
+    ///
+    /// # Panics
+    /// Panics if `steps` is less than two.
+    pub fn new(s: f64, r: f64, q: f64, v: f64, t: f64, steps: usize) -> Self {
+        assert!(steps >= 2, "The tree needs at least two steps.");
+
+        Self {
+            s,
+            r,
+            q,
+            v,
+            t,
+            steps,
+            dividends: Vec::new(),
+        }
+    }
+
+    /// Add discrete cash dividends, as (time in years from the root, amount)
+    /// pairs, using the escrowed dividend model.
+    ///
+    /// A dividend paid at a time step is already paid at that step's
+    /// nodes, so an American call that exercises to capture it does so one
+    /// step before.
+    ///
+    /// # Panics
+    /// Panics if a dividend is paid outside $(0, T]$, is negative, or if the
+    /// dividends are worth more than the stock.
+    pub fn with_dividends(mut self, dividends: Vec<(f64, f64)>) -> Self {
+        assert!(
+            dividends
+                .iter()
+                .all(|&(time, amount)| time > 0.0 && time <= self.t && amount >= 0.0),
+            "Dividends must be non-negative and paid after the root, by expiry."
+        );
+
+        self.dividends = dividends;
+
+        assert!(
+            self.dividends_value(0.0) < self.s,
+            "The dividends must be worth less than the stock."
+        );
+
+        self
+    }
+
+    /// Up move factor, $u = e^{\sigma \sqrt{\Delta t}}$.
+    fn up(&self) -> f64 {
+        (self.v * self.time_step().sqrt()).exp()
+    }
+
+    /// Present value at `time` of the dividends paid after it.
+    fn dividends_value(&self, time: f64) -> f64 {
+        self.dividends
+            .iter()
+            .filter(|&&(paid, _)| paid > time)
+            .map(|&(paid, amount)| amount * (-self.r * (paid - time)).exp())
+            .sum()
+    }
+}
+
+impl BinomialOption {
+    /// Create a new option on a binomial tree.
+    pub fn new(tree: BinomialTree, option: LatticeOption) -> Self {
+        Self { tree, option }
+    }
+
+    /// Price, delta, gamma and theta of the option, from the tree.
+    pub fn value(&self) -> LatticeValuation {
+        self.tree.value(&self.option)
+    }
+
+    /// Market inputs of the tree.
+    fn market_state(&self) -> MarketState {
+        MarketState {
+            spot: self.tree.s,
+            volatility: self.tree.v,
+            rate: self.tree.r,
+            time_to_expiry: self.tree.t,
+        }
+    }
+
+    /// Price of the option on the tree rebuilt under `state`.
+    fn revalue(&self, state: &MarketState) -> f64 {
+        let tree = BinomialTree {
+            s: state.spot,
+            v: state.volatility,
+            r: state.rate,
+            t: state.time_to_expiry,
+            ..self.tree.clone()
+        };
+
+        tree.value(&self.option).price
+    }
+
+    /// Bumps of a 2% relative spot move, one volatility point and ten
+    /// basis points of rate.
+    fn bumps() -> BumpAndRevalue {
+        BumpAndRevalue {
+            spot_bump: Bump::Relative(0.02),
+            volatility_bump: Bump::Absolute(0.01),
+            rate_bump: Bump::Absolute(1e-3),
+            ..BumpAndRevalue::default()
+        }
+    }
+}
+
+impl OptionGreeks for BinomialOption {
+    fn greeks(&self) -> Greeks {
+        let nodes = self.value();
+        let bumped = Self::bumps().greeks(|state| self.revalue(state), &self.market_state());
+
+        Greeks {
+            delta: nodes.delta,
+            gamma: nodes.gamma,
+            vega: bumped.vega,
+            theta: nodes.theta,
+            rho: bumped.rho,
+        }
+    }
+
+    fn higher_order_greeks(&self) -> HigherOrderGreeks {
+        Self::bumps().higher_order_greeks(|state| self.revalue(state), &self.market_state())
+    }
+
+    fn shifted(&self, spot_shift: f64, vol_shift: f64) -> Self {
+        let tree = BinomialTree {
+            s: self.tree.s + spot_shift,
+            v: self.tree.v + vol_shift,
+            ..self.tree.clone()
+        };
+
+        Self::new(tree, self.option.clone())
+    }
+}
+
+impl Lattice for BinomialTree {
+    fn steps(&self) -> usize {
+        self.steps
+    }
+
+    fn time_step(&self) -> f64 {
+        self.t / self.steps as f64
+    }
+
+    fn nodes(&self, step: usize) -> usize {
+        step + 1
+    }
+
+    fn underlying(&self, step: usize, node: usize) -> f64 {
+        let time = step as f64 * self.time_step();
+        let escrowed = self.dividends_value(0.0);
+
+        (self.s - escrowed) * self.up().powi(2 * node as i32 - step as i32)
+            + self.dividends_value(time)
+    }
+
+    fn rollback(&self, _step: usize, next: &[f64]) -> Vec<f64> {
+        let dt = self.time_step();
+        let (u, d) = (self.up(), 1.0 / self.up());
+        let p = (((self.r - self.q) * dt).exp() - d) / (u - d);
+        let discount = (-self.r * dt).exp();
+
+        next.windows(2)
+            .map(|pair| discount * (p * pair[1] + (1.0 - p) * pair[0]))
+            .collect()
+    }
+}
+
+impl TrinomialTree {
+    /// Create a new trinomial tree, with stretch $\lambda = \sqrt{3}$.
+    ///
+    /// # Panics
+    /// Panics if `steps` is zero.
+    pub fn new(s: f64, r: f64, q: f64, v: f64, t: f64, steps: usize) -> Self {
+        assert!(steps >= 1, "The tree needs at least one step.");
+
+        Self {
+            s,
+            r,
+            q,
+            v,
+            t,
+            steps,
+            stretch: 3_f64.sqrt(),
+        }
+    }
+
+    /// Set the stretch of the node spacing ($\lambda$).
+    ///
+    /// # Panics
+    /// Panics if `stretch` is less than one.
+    pub fn with_stretch(mut self, stretch: f64) -> Self {
+        assert!(stretch >= 1.0, "The stretch must be at least one.");
+
+        self.stretch = stretch;
+        self
+    }
+
+    /// Choose the smallest stretch $\lambda \geq 1$ that puts a layer of
+    /// nodes on `barrier`. If the barrier is within one minimal node spacing
+    /// of the spot, the stretch is left unchanged.
+    pub fn with_barrier(mut self, barrier: f64) -> Self {
+        let eta = (self.s / barrier).ln().abs() / (self.v * self.time_step().sqrt());
+        let layers = eta.floor();
+
+        if layers >= 1.0 {
+            self.stretch = eta / layers;
+        }
+
+        self
+    }
+
+    /// Log price spacing of the nodes, $\lambda \sigma \sqrt{\Delta t}$.
+    fn spacing(&self) -> f64 {
+        self.stretch * self.v * self.time_step().sqrt()
+    }
+}
+
+impl Lattice for TrinomialTree {
+    fn steps(&self) -> usize {
+        self.steps
+    }
+
+    fn time_step(&self) -> f64 {
+        self.t / self.steps as f64
+    }
+
+    fn nodes(&self, step: usize) -> usize {
+        2 * step + 1
+    }
+
+    fn underlying(&self, step: usize, node: usize) -> f64 {
+        self.s * ((node as f64 - step as f64) * self.spacing()).exp()
+    }
+
+    fn rollback(&self, _step: usize, next: &[f64]) -> Vec<f64> {
+        let dt = self.time_step();
+        let lambda = self.stretch;
+
+        // Match the mean and variance of the log price over a step.
+        let nu = self.r - self.q - 0.5 * self.v * self.v;
+        let drift = nu * dt.sqrt() / (2.0 * lambda * self.v);
+
+        let p_u = 0.5 / (lambda * lambda) + drift;
+        let p_d = 0.5 / (lambda * lambda) - drift;
+        let p_m = 1.0 - 1.0 / (lambda * lambda);
+        let discount = (-self.r * dt).exp();
+
+        next.windows(3)
+            .map(|triple| discount * (p_d * triple[0] + p_m * triple[1] + p_u * triple[2]))
+            .collect()
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_lattice {
+    use super::*;
+    use crate::{BlackScholes73, GeneralisedBlackScholesMerton};
+    use RustQuant_math::{gauss_legendre, Distribution, N};
+    use RustQuant_utils::assert_approx_equal;
+
+    #[test]
+    fn test_european_prices_and_greeks() {
+        let bs = BlackScholes73::new(100.0, 0.05, 0.2);
+        let binomial = BinomialTree::new(100.0, 0.05, 0.0, 0.2, 1.0, 1000);
+        let trinomial = TrinomialTree::new(100.0, 0.05, 0.0, 0.2, 1.0, 1000);
+
+        for type_flag in [TypeFlag::Call, TypeFlag::Put] {
+            let option = LatticeOption::new(95.0, type_flag, false);
+            let exact = bs.price(95.0, 1.0, type_flag);
+
+            let b = binomial.value(&option);
+            let t = trinomial.value(&option);
+
+            assert_approx_equal!(b.price, exact, 5e-3);
+            assert_approx_equal!(t.price, exact, 5e-3);
+
+            for valuation in [b, t] {
+                assert_approx_equal!(valuation.delta, bs.delta(95.0, 1.0, type_flag), 1e-3);
+                assert_approx_equal!(valuation.gamma, bs.gamma(95.0, 1.0, type_flag), 1e-3);
+                assert_approx_equal!(valuation.theta, bs.theta(95.0, 1.0, type_flag), 0.05);
+            }
+        }
+    }
+
+    #[test]
+    fn test_binomial_option_greeks() {
+        let t = 1.0;
+        let tree = BinomialTree::new(100.0, 0.05, 0.0, 0.2, t, 500);
+
+        // European options match the closed form through the same interface.
+        for type_flag in [TypeFlag::Call, TypeFlag::Put] {
+            let bs = BlackScholes73::new(100.0, 0.05, 0.2);
+            let greeks =
+                BinomialOption::new(tree.clone(), LatticeOption::new(95.0, type_flag, false))
+                    .greeks();
+
+            assert_approx_equal!(greeks.delta, bs.delta(95.0, t, type_flag), 2e-3);
+            assert_approx_equal!(greeks.gamma, bs.gamma(95.0, t, type_flag), 2e-3);
+            assert_approx_equal!(greeks.theta, bs.theta(95.0, t, type_flag), 0.05);
+            assert_approx_equal!(greeks.vega, bs.vega(95.0, t, type_flag), 0.5);
+            assert_approx_equal!(greeks.rho, bs.rho(95.0, t, type_flag), 0.5);
+        }
+
+        // Early exercise makes the put more sensitive to the spot, and less to rates.
+        let european = BinomialOption::new(
+            tree.clone(),
+            LatticeOption::new(100.0, TypeFlag::Put, false),
+        );
+        let american = BinomialOption::new(tree, LatticeOption::new(100.0, TypeFlag::Put, true));
+        let (e, a) = (european.greeks(), american.greeks());
+
+        assert!(a.delta < e.delta);
+        assert!(a.rho < 0.0 && a.rho > e.rho);
+        assert!(a.vega > 0.0 && a.vega < e.vega);
+
+        // Shifting the spot by delta moves the price to first order.
+        let shifted = american.shifted(1.0, 0.0).value().price;
+        let expected = american.value().price + a.delta + 0.5 * a.gamma;
+
+        assert_approx_equal!(shifted, expected, 0.01);
+        assert!(american.higher_order_greeks().speed.is_finite());
+    }
+
+    #[test]
+    fn test_american_put() {
+        // Longstaff and Schwartz (2001): S = 36, K = 40, r = 0.06, v = 0.2,
+        // T = 1. The continuously exercisable price is 4.4867.
+        let option = LatticeOption::new(40.0, TypeFlag::Put, true);
+
+        let binomial = BinomialTree::new(36.0, 0.06, 0.0, 0.2, 1.0, 1000).value(&option);
+        let trinomial = TrinomialTree::new(36.0, 0.06, 0.0, 0.2, 1.0, 1000).value(&option);
+
+        assert_approx_equal!(binomial.price, 4.4867, 2e-3);
+        assert_approx_equal!(trinomial.price, 4.4867, 2e-3);
+        assert!(trinomial.delta < 0.0 && trinomial.delta > -1.0);
+    }
+
+    #[test]
+    fn test_european_with_dividends() {
+        // In the escrowed dividend model, a European option is priced by
+        // Black-Scholes on the stock less the dividends' present value.
+        let dividends = vec![(0.25, 1.5), (0.75, 1.5)];
+        let escrowed: f64 = dividends
+            .iter()
+            .map(|&(time, amount)| amount * f64::exp(-0.05 * time))
+            .sum();
+        let bs = BlackScholes73::new(100.0 - escrowed, 0.05, 0.2);
+
+        let tree = BinomialTree::new(100.0, 0.05, 0.0, 0.2, 1.0, 1000).with_dividends(dividends);
+
+        for type_flag in [TypeFlag::Call, TypeFlag::Put] {
+            let valuation = tree.value(&LatticeOption::new(100.0, type_flag, false));
+
+            assert_approx_equal!(valuation.price, bs.price(100.0, 1.0, type_flag), 5e-3);
+            assert_approx_equal!(valuation.delta, bs.delta(100.0, 1.0, type_flag), 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_american_call_with_dividend() {
+        // Without dividends, an American call is never exercised early.
+        let plain = BinomialTree::new(100.0, 0.05, 0.0, 0.2, 1.0, 500);
+        assert_approx_equal!(
+            plain
+                .value(&LatticeOption::new(100.0, TypeFlag::Call, true))
+                .price,
+            plain
+                .value(&LatticeOption::new(100.0, TypeFlag::Call, false))
+                .price,
+            1e-10
+        );
+
+        // A large dividend just before expiry makes early exercise
+        // worthwhile. Black's approximation, the larger of the European
+        // calls expiring at expiry and just before the dividend, is a
+        // lower bound. Just before the dividend, the stock is the escrowed
+        // price plus the dividend, so that call has strike K - D.
+        let (time, amount) = (0.9, 8.0);
+        let tree = plain.with_dividends(vec![(time, amount)]);
+        let american = tree.value(&LatticeOption::new(100.0, TypeFlag::Call, true));
+        let european = tree.value(&LatticeOption::new(100.0, TypeFlag::Call, false));
+
+        let bs = BlackScholes73::new(100.0 - amount * f64::exp(-0.05 * time), 0.05, 0.2);
+        let black = bs.price(100.0, 1.0, TypeFlag::Call).max(bs.price(
+            100.0 - amount,
+            time,
+            TypeFlag::Call,
+        ));
+
+        assert!(american.price > european.price + 1.0);
+        assert!(american.price >= black - 5e-3);
+        assert!(american.price < black + 0.5);
+    }
+
+    #[test]
+    #[should_panic(expected = "Dividends must be non-negative")]
+    fn test_dividend_after_expiry_panics() {
+        let _ = BinomialTree::new(100.0, 0.05, 0.0, 0.2, 1.0, 100).with_dividends(vec![(1.5, 1.0)]);
+    }
+
+    #[test]
+    fn test_barrier_in_out_parity() {
+        let tree = TrinomialTree::new(100.0, 0.05, 0.02, 0.25, 1.0, 500).with_barrier(90.0);
+        let vanilla = LatticeOption::new(100.0, TypeFlag::Call, false);
+
+        let out = tree.value(&vanilla.clone().with_barrier(BarrierType::DownAndOut, 90.0));
+        let knock_in = tree.value(&vanilla.clone().with_barrier(BarrierType::DownAndIn, 90.0));
+
+        assert_approx_equal!(
+            out.price + knock_in.price,
+            tree.value(&vanilla).price,
+            1e-12
+        );
+    }
+
+    #[test]
+    fn test_down_and_out_call() {
+        // Continuously monitored down-and-out call with B <= K:
+        // C(S) - (B / S)^(2 nu / v^2) C(B^2 / S), with nu = r - v^2 / 2.
+        // With a layer of nodes on the barrier, a path cannot cross it
+        // without touching it, so the tree converges to continuous monitoring.
+        let (s, k, b, r, v, t, n) = (100.0, 100.0, 90.0, 0.05, 0.2, 1.0, 500);
+
+        let nu = r - 0.5 * v * v;
+        let exact = BlackScholes73::new(s, r, v).price(k, t, TypeFlag::Call)
+            - (b / s).powf(2.0 * nu / (v * v))
+                * BlackScholes73::new(b * b / s, r, v).price(k, t, TypeFlag::Call);
+
+        let option =
+            LatticeOption::new(k, TypeFlag::Call, false).with_barrier(BarrierType::DownAndOut, b);
+        let tree = TrinomialTree::new(s, r, 0.0, v, t, n).with_barrier(b);
+
+        assert_approx_equal!(tree.value(&option).price, exact, 0.01);
+    }
+
+    #[test]
+    fn test_barrier_on_layer() {
+        let tree = TrinomialTree::new(100.0, 0.05, 0.0, 0.2, 1.0, 100).with_barrier(90.0);
+        let layers = (100.0_f64 / 90.0).ln() / tree.spacing();
+
+        assert!(tree.stretch >= 1.0);
+        assert_approx_equal!(layers, layers.round(), 1e-9);
+    }
+
+    #[test]
+    #[should_panic(expected = "Knock-in barriers")]
+    fn test_american_knock_in_panics() {
+        let option = LatticeOption::new(100.0, TypeFlag::Put, true)
+            .with_barrier(BarrierType::UpAndIn, 110.0);
+
+        TrinomialTree::new(100.0, 0.05, 0.0, 0.2, 1.0, 10).value(&option);
+    }
+
+    #[test]
+    fn test_bermudan_limits() {
+        let tree = BinomialTree::new(36.0, 0.06, 0.0, 0.2, 1.0, 200);
+
+        let european = tree.value(&LatticeOption::new(40.0, TypeFlag::Put, false));
+        let american = tree.value(&LatticeOption::new(40.0, TypeFlag::Put, true));
+
+        // Exercisable only at expiry, or at every step.
+        let at_expiry = LatticeOption::bermudan(40.0, TypeFlag::Put, vec![1.0]);
+        let every_step = (0..=200).map(|step| step as f64 / 200.0).collect();
+        let every_step = LatticeOption::bermudan(40.0, TypeFlag::Put, every_step);
+
+        assert_eq!(tree.value(&at_expiry), european);
+        assert_eq!(tree.value(&every_step), american);
+
+        // More exercise dates are worth more.
+        let quarterly = LatticeOption::bermudan(40.0, TypeFlag::Put, vec![0.25, 0.5, 0.75]);
+        let monthly = (1..12).map(|month| month as f64 / 12.0).collect();
+        let monthly = LatticeOption::bermudan(40.0, TypeFlag::Put, monthly);
+
+        let quarterly = tree.value(&quarterly).price;
+        let monthly = tree.value(&monthly).price;
+
+        assert!(european.price < quarterly);
+        assert!(quarterly < monthly);
+        assert!(monthly < american.price);
+    }
+
+    #[test]
+    fn test_bermudan_put_one_exercise_date() {
+        // Exercisable at t1 and T: at t1 the holder receives the larger of
+        // the exercise value and the European put to T, so the price is
+        // e^{-r t1} E[max(K - S(t1), P(S(t1), T - t1))].
+        let (s, k, r, v, t1, t) = (36.0, 40.0, 0.06, 0.2, 0.5, 1.0);
+
+        let value_at_t1 = |z: f64| {
+            let s_t1 = s * ((r - 0.5 * v * v) * t1 + v * f64::sqrt(t1) * z).exp();
+            let european = BlackScholes73::new(s_t1, r, v).price(k, t - t1, TypeFlag::Put);
+
+            (k - s_t1).max(european) * N.pdf(z)
+        };
+
+        let expected = (-r * t1).exp()
+            * (-10..10)
+                .map(|a| gauss_legendre(value_at_t1, a as f64, a as f64 + 1.0, 32))
+                .sum::<f64>();
+
+        let option = LatticeOption::bermudan(k, TypeFlag::Put, vec![t1]);
+
+        for price in [
+            BinomialTree::new(s, r, 0.0, v, t, 1000)
+                .value(&option)
+                .price,
+            TrinomialTree::new(s, r, 0.0, v, t, 1000)
+                .value(&option)
+                .price,
+        ] {
+            assert_approx_equal!(price, expected, 2e-3);
+        }
+    }
+}
@@ -0,0 +1,282 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Longstaff-Schwartz (2001) least-squares Monte Carlo for American options.
+//!
+//! Working backwards through the simulated time points, the continuation
+//! value of each in-the-money path is estimated by regressing its realised
+//! discounted cash flows on a basis of functions of the current state.
+//! A path exercises when its payoff exceeds the estimated continuation value.
+//!
+//! Since the paths are simulated beforehand, any process that produces
+//! `Trajectories` can be used, e.g. Heston, Merton jump diffusion or CEV.
+
+use crate::{MonteCarloEstimate, Payoff};
+use nalgebra::{DMatrix, DVector};
+use RustQuant_stochastics::Trajectories;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS & ENUMS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Basis functions for the continuation value regression.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RegressionBasis {
+    /// Monomials $1, x, x^2, \ldots$.
+    Polynomial,
+
+    /// Weighted Laguerre polynomials $e^{-x/2} L_k(x)$, as used by
+    /// Longstaff and Schwartz.
+    #[default]
+    Laguerre,
+}
+
+/// Longstaff-Schwartz least-squares Monte Carlo pricer.
+#[derive(Debug, Clone, Copy)]
+pub struct LongstaffSchwartz {
+    /// The regression basis.
+    pub basis: RegressionBasis,
+
+    /// The highest degree of the basis functions.
+    pub degree: usize,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl RegressionBasis {
+    /// The basis functions up to `degree`, evaluated at `x`.
+    pub fn evaluate(&self, x: f64, degree: usize) -> Vec<f64> {
+        let mut values = Vec::with_capacity(degree + 1);
+
+        match self {
+            RegressionBasis::Polynomial => {
+                let mut power = 1.0;
+                for _ in 0..=degree {
+                    values.push(power);
+                    power *= x;
+                }
+            }
+            RegressionBasis::Laguerre => {
+                // (k + 1) L_{k+1} = (2k + 1 - x) L_k - k L_{k-1}.
+                let weight = (-0.5 * x).exp();
+                let (mut current, mut previous) = (1.0, 0.0);
+                for k in 0..=degree {
+                    values.push(weight * current);
+
+                    let k = k as f64;
+                    (current, previous) = (
+                        ((2.0 * k + 1.0 - x) * current - k * previous) / (k + 1.0),
+                        current,
+                    );
+                }
+            }
+        }
+
+        values
+    }
+}
+
+impl Default for LongstaffSchwartz {
+    /// Laguerre polynomials up to degree 3.
+    fn default() -> Self {
+        Self {
+            basis: RegressionBasis::Laguerre,
+            degree: 3,
+        }
+    }
+}
+
+impl LongstaffSchwartz {
+    /// Create a new Longstaff-Schwartz pricer.
+    pub fn new(basis: RegressionBasis, degree: usize) -> Self {
+        Self { basis, degree }
+    }
+
+    /// Price an American option on simulated paths of the underlying,
+    /// exercisable at every time point after the first.
+    ///
+    /// The state is normalised by the initial value before regressing, so
+    /// the basis is well conditioned whatever the level of the underlying.
+    /// The regression uses the same paths as the estimate, so the price is
+    /// biased slightly (the exercise policy is optimised in-sample).
+    ///
+    /// # Arguments:
+    /// * `trajectories` - Paths of the underlying (with risk-neutral drift).
+    /// * `payoff` - The exercise value, as a function of the underlying.
+    /// * `discount_rate` - Continuously compounded discount rate.
+    ///
+    /// # Panics
+    /// Panics if there are fewer than two paths or two time points.
+    pub fn price<F>(
+        &self,
+        trajectories: &Trajectories,
+        payoff: &F,
+        discount_rate: f64,
+    ) -> MonteCarloEstimate
+    where
+        F: Payoff<Underlying = f64>,
+    {
+        let Trajectories { times, paths } = trajectories;
+        let n_steps = times.len() - 1;
+
+        assert!(
+            paths.len() > 1,
+            "At least two paths are needed for a standard error."
+        );
+        assert!(n_steps > 0, "At least two time points are needed.");
+
+        let scale = paths[0][0].abs().max(f64::MIN_POSITIVE);
+
+        // Cash flow of each path, and the time point it is received at.
+        let mut cash_flows: Vec<(f64, usize)> = paths
+            .iter()
+            .map(|path| (payoff.payoff(path[n_steps]), n_steps))
+            .collect();
+
+        for t in (1..n_steps).rev() {
+            let in_the_money: Vec<usize> = (0..paths.len())
+                .filter(|&p| payoff.payoff(paths[p][t]) > 0.0)
+                .collect();
+
+            // Too few points to fit the regression, so hold.
+            if in_the_money.len() <= self.degree + 1 {
+                continue;
+            }
+
+            let regressors: Vec<f64> = in_the_money
+                .iter()
+                .flat_map(|&p| self.basis.evaluate(paths[p][t] / scale, self.degree))
+                .collect();
+            let X = DMatrix::from_row_slice(in_the_money.len(), self.degree + 1, &regressors);
+
+            let y = DVector::from_iterator(
+                in_the_money.len(),
+                in_the_money.iter().map(|&p| {
+                    let (cash_flow, time) = cash_flows[p];
+                    cash_flow * (-discount_rate * (times[time] - times[t])).exp()
+                }),
+            );
+
+            // Solve the (small) normal equations.
+            let beta = X
+                .tr_mul(&X)
+                .svd(true, true)
+                .solve(&X.tr_mul(&y), f64::EPSILON)
+                .expect("The SVD was computed with both U and V.");
+            let continuation = X * beta;
+
+            for (row, &p) in in_the_money.iter().enumerate() {
+                let exercise = payoff.payoff(paths[p][t]);
+
+                if exercise > continuation[row] {
+                    cash_flows[p] = (exercise, t);
+                }
+            }
+        }
+
+        let discounted: Vec<f64> = cash_flows
+            .iter()
+            .map(|&(cash_flow, time)| cash_flow * (-discount_rate * (times[time] - times[0])).exp())
+            .collect();
+
+        let estimate = MonteCarloEstimate::from_samples(&discounted);
+
+        // Exercising immediately is known exactly.
+        let intrinsic = payoff.payoff(paths[0][0]);
+        if intrinsic > estimate.price {
+            return MonteCarloEstimate {
+                price: intrinsic,
+                standard_error: 0.0,
+            };
+        }
+
+        estimate
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_longstaff_schwartz {
+    use super::*;
+    use crate::{BlackScholes73, EuropeanVanillaOption, GeneralisedBlackScholesMerton, TypeFlag};
+    use RustQuant_stochastics::{
+        GeometricBrownianMotion, StochasticProcess, StochasticProcessConfig,
+    };
+    use RustQuant_time::today;
+    use RustQuant_utils::assert_approx_equal;
+
+    #[test]
+    fn test_american_put() {
+        // Longstaff and Schwartz (2001), Table 1: S = 36, K = 40, r = 0.06,
+        // v = 0.2, T = 1, with 50 exercise dates. The finite difference
+        // price is 4.478 and the European price is 3.844.
+        let gbm = GeometricBrownianMotion::new(0.06, 0.2);
+        let config = StochasticProcessConfig::new(36.0, 0.0, 1.0, 50, 100_000, true).with_seed(1);
+        let paths = gbm.euler_maruyama(&config);
+
+        let put = EuropeanVanillaOption::new(40.0, today(), TypeFlag::Put);
+
+        for basis in [RegressionBasis::Laguerre, RegressionBasis::Polynomial] {
+            let estimate = LongstaffSchwartz::new(basis, 3).price(&paths, &put, 0.06);
+
+            assert_approx_equal!(estimate.price, 4.478, 0.03);
+            assert!(estimate.standard_error < 0.01);
+        }
+    }
+
+    #[test]
+    fn test_american_call_without_dividends() {
+        // Early exercise is never optimal, so the price is European.
+        let exact = BlackScholes73::new(100.0, 0.05, 0.2).price(100.0, 1.0, TypeFlag::Call);
+
+        let gbm = GeometricBrownianMotion::new(0.05, 0.2);
+        let config = StochasticProcessConfig::new(100.0, 0.0, 1.0, 50, 50_000, true).with_seed(2);
+        let paths = gbm.euler_maruyama(&config);
+
+        let call = EuropeanVanillaOption::new(100.0, today(), TypeFlag::Call);
+        let estimate = LongstaffSchwartz::default().price(&paths, &call, 0.05);
+
+        assert!((estimate.price - exact).abs() < 3.0 * estimate.standard_error);
+    }
+
+    #[test]
+    fn test_deep_in_the_money_exercises_immediately() {
+        let gbm = GeometricBrownianMotion::new(0.06, 0.2);
+        let config = StochasticProcessConfig::new(10.0, 0.0, 1.0, 10, 1_000, true).with_seed(3);
+        let paths = gbm.euler_maruyama(&config);
+
+        let put = EuropeanVanillaOption::new(40.0, today(), TypeFlag::Put);
+        let estimate = LongstaffSchwartz::default().price(&paths, &put, 0.06);
+
+        assert_eq!(estimate.price, 30.0);
+        assert_eq!(estimate.standard_error, 0.0);
+    }
+
+    #[test]
+    fn test_laguerre_basis() {
+        // L_0 = 1, L_1 = 1 - x, L_2 = (x^2 - 4x + 2) / 2.
+        let x: f64 = 0.7;
+        let values = RegressionBasis::Laguerre.evaluate(x, 2);
+        let weight = (-0.5 * x).exp();
+
+        assert_approx_equal!(values[0], weight, 1e-15);
+        assert_approx_equal!(values[1], weight * (1.0 - x), 1e-15);
+        assert_approx_equal!(values[2], weight * (x * x - 4.0 * x + 2.0) / 2.0, 1e-15);
+
+        assert_eq!(
+            RegressionBasis::Polynomial.evaluate(2.0, 3),
+            vec![1.0, 2.0, 4.0, 8.0]
+        );
+    }
+}
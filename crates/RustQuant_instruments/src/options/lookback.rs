@@ -12,6 +12,11 @@ use super::{OptionContract, TypeFlag};
 use crate::Payoff;
 
 /// Lookback option.
+///
+/// The extremum is taken over the fixings of the path (i.e. the option is
+/// discretely monitored), optionally restricted to a lookback window.
+/// Simulated paths are priced with `MonteCarloEngine::price_path` or
+/// `MonteCarloPricer`.
 #[derive(Debug, Clone)]
 pub struct LookbackOption {
     /// The option contract.
@@ -22,16 +27,68 @@ pub struct LookbackOption {
     /// If no strike is provided, the strike will be assumed to be
     /// floating (e.g. strike = S_max).
     pub strike: Option<f64>,
+
+    /// Lookback window, as fractions of the option's life: `(0.0, 0.5)`
+    /// looks back over the first half only. `None` looks back over the
+    /// whole life.
+    pub lookback_window: Option<(f64, f64)>,
+}
+
+impl LookbackOption {
+    /// Create a new lookback option, looking back over its whole life.
+    pub fn new(contract: OptionContract, strike: Option<f64>) -> Self {
+        Self {
+            contract,
+            strike,
+            lookback_window: None,
+        }
+    }
+
+    /// Look back only over the fixings between `start` and `end`, as
+    /// fractions of the option's life (partial lookback).
+    ///
+    /// # Panics
+    /// Panics unless $0 \le$ `start` $\le$ `end` $\le 1$.
+    pub fn with_lookback_window(mut self, start: f64, end: f64) -> Self {
+        assert!(
+            0.0 <= start && start <= end && end <= 1.0,
+            "The lookback window must satisfy 0 <= start <= end <= 1."
+        );
+
+        self.lookback_window = Some((start, end));
+        self
+    }
+
+    /// The fixings of `path` inside the lookback window.
+    ///
+    /// # Panics
+    /// Panics if no fixing falls inside the window.
+    fn window<'a>(&self, path: &'a [f64]) -> &'a [f64] {
+        let (start, end) = match self.lookback_window {
+            Some(window) => window,
+            None => return path,
+        };
+
+        // The fixings are equally spaced, from the start of the option's
+        // life (index 0) to expiry (index n).
+        let n = (path.len() - 1) as f64;
+        let first = (start * n - 1e-9).ceil() as usize;
+        let last = (end * n + 1e-9).floor() as usize;
+
+        assert!(first <= last, "No fixings fall inside the lookback window.");
+
+        &path[first..=last]
+    }
 }
 
 impl Payoff for LookbackOption {
     type Underlying = Vec<f64>;
 
     fn payoff(&self, underlying: Self::Underlying) -> f64 {
-        let s = underlying;
         let k = self.strike;
 
-        let terminal = s.last().unwrap();
+        let terminal = underlying.last().unwrap();
+        let s = self.window(&underlying);
 
         let s_max = s.iter().max_by(|x, y| x.total_cmp(y)).unwrap_or(terminal);
         let s_min = s.iter().min_by(|x, y| x.total_cmp(y)).unwrap_or(terminal);
@@ -52,3 +109,99 @@ impl Payoff for LookbackOption {
         }
     }
 }
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_lookback {
+    use super::*;
+    use crate::{
+        BlackScholes73, ExerciseFlag, GeneralisedBlackScholesMerton, MonteCarloEngine,
+        OptionContractBuilder,
+    };
+    use RustQuant_stochastics::GeometricBrownianMotion;
+    use RustQuant_time::today;
+
+    const S: f64 = 100.0;
+    const R: f64 = 0.05;
+    const V: f64 = 0.2;
+    const T: f64 = 1.0;
+
+    fn lookback(
+        type_flag: TypeFlag,
+        strike_flag: StrikeFlag,
+        strike: Option<f64>,
+    ) -> LookbackOption {
+        let contract = OptionContractBuilder::default()
+            .type_flag(type_flag)
+            .exercise_flag(ExerciseFlag::European { expiry: today() })
+            .strike_flag(Some(strike_flag))
+            .build()
+            .unwrap();
+
+        LookbackOption::new(contract, strike)
+    }
+
+    fn engine(n_steps: usize) -> MonteCarloEngine<GeometricBrownianMotion> {
+        MonteCarloEngine::new(GeometricBrownianMotion::new(R, V), S, R, T, 10_000, n_steps)
+            .with_seed(3)
+    }
+
+    #[test]
+    fn test_partial_window_payoff() {
+        let path = vec![100.0, 90.0, 105.0, 95.0, 120.0];
+        let call = lookback(TypeFlag::Call, StrikeFlag::Floating, None);
+        let put = lookback(TypeFlag::Put, StrikeFlag::Fixed, Some(100.0));
+
+        assert_eq!(call.payoff(path.clone()), 30.0);
+        assert_eq!(put.payoff(path.clone()), 10.0);
+
+        // The second half of the life holds the fixings at 105, 95 and 120.
+        assert_eq!(
+            call.with_lookback_window(0.5, 1.0).payoff(path.clone()),
+            25.0
+        );
+        assert_eq!(put.with_lookback_window(0.5, 1.0).payoff(path), 5.0);
+    }
+
+    #[test]
+    fn test_degenerate_windows_are_vanillas() {
+        let vanilla = BlackScholes73::new(S, R, V).price(S, T, TypeFlag::Call);
+
+        // Looking back only at inception or at expiry leaves an at-the-money call.
+        let floating =
+            lookback(TypeFlag::Call, StrikeFlag::Floating, None).with_lookback_window(0.0, 0.0);
+        let fixed =
+            lookback(TypeFlag::Call, StrikeFlag::Fixed, Some(S)).with_lookback_window(1.0, 1.0);
+
+        for option in [floating, fixed] {
+            let estimate = engine(20).price_path(&option);
+
+            assert!((estimate.price - vanilla).abs() < 3.0 * estimate.standard_error);
+        }
+    }
+
+    #[test]
+    fn test_floating_call_monitoring() {
+        // Goldman, Sosin and Gatto (1979), continuously monitored, at inception.
+        let continuous = 17.216_802_237_360_9;
+        let call = lookback(TypeFlag::Call, StrikeFlag::Floating, None);
+
+        let monthly = engine(12).price_path(&call);
+        let daily = engine(252).price_path(&call);
+
+        // Discrete monitoring misses part of the minimum, less so when frequent.
+        assert!(monthly.price < daily.price - 5.0 * daily.standard_error);
+        assert!(daily.price < continuous);
+        assert!(continuous - daily.price < 1.0);
+
+        // A longer window can only lower the minimum, path by path.
+        let first_half = engine(252).price_path(&call.clone().with_lookback_window(0.0, 0.5));
+        let second_half = engine(252).price_path(&call.with_lookback_window(0.5, 1.0));
+
+        assert!(first_half.price < daily.price);
+        assert!(second_half.price < daily.price);
+    }
+}
@@ -19,10 +19,18 @@ pub use option_models::*;
 // pub mod bachelier;
 // pub use bachelier::*;
 
+/// Autocallable structured notes.
+pub mod autocallable;
+pub use autocallable::*;
+
 /// Barrier option pricers.
 pub mod barrier;
 pub use barrier::*;
 
+/// Basket option Monte Carlo pricer.
+pub mod basket;
+pub use basket::*;
+
 /// Binary option pricers.
 pub mod binary;
 pub use binary::*;
@@ -34,8 +42,33 @@ pub use binary::*;
 pub mod black_scholes_merton;
 pub use black_scholes_merton::*;
 
-// /// Forward start options pricers.
-// pub mod forward_start;
+/// Cliquet (ratchet) options.
+pub mod cliquet;
+pub use cliquet::*;
+
+/// Discrete dividend schedules and escrowed dividend pricing.
+pub mod dividends;
+pub use dividends::*;
+
+/// European option quasi-Monte Carlo pricer and Newton implied volatility.
+pub mod european;
+pub use european::*;
+
+/// Exotic option closed-form pricers.
+pub mod exotic;
+pub use exotic::*;
+
+/// Fourier transform pricers: Carr-Madan FFT and the COS method.
+pub mod fourier;
+pub use fourier::*;
+
+/// Forward start options pricers.
+pub mod forward_start;
+pub use forward_start::*;
+
+/// Option Greeks and their aggregation.
+pub mod greeks;
+pub use greeks::*;
 
 // /// Heston model option pricer.
 // pub mod heston;
@@ -45,6 +78,18 @@ pub use black_scholes_merton::*;
 pub mod implied_volatility;
 pub use implied_volatility::*;
 
+/// Ladder options.
+pub mod ladder;
+pub use ladder::*;
+
+/// Binomial and trinomial lattice pricers.
+pub mod lattice;
+pub use lattice::*;
+
+/// Longstaff-Schwartz least-squares Monte Carlo for American options.
+pub mod longstaff_schwartz;
+pub use longstaff_schwartz::*;
+
 /// Lookback option pricers.
 pub mod lookback;
 pub use lookback::*;
@@ -53,14 +98,38 @@ pub use lookback::*;
 // pub mod merton_jump_diffusion;
 // pub use merton_jump_diffusion::*;
 
+/// Process-agnostic Monte Carlo pricing engine.
+pub mod monte_carlo;
+pub use monte_carlo::*;
+
+/// Mountain range (Himalaya and Atlas) options.
+pub mod mountain_range;
+pub use mountain_range::*;
+
 /// Base option traits.
 pub mod option_contract;
 pub use option_contract::*;
 
+/// Parisian barrier options.
+pub mod parisian;
+pub use parisian::*;
+
 /// Power options and contracts.
 pub mod power;
 pub use power::*;
 
+/// Quanto options.
+pub mod quanto;
+pub use quanto::*;
+
+/// Rainbow (best-of and worst-of) options.
+pub mod rainbow;
+pub use rainbow::*;
+
+/// Shout options.
+pub mod shout;
+pub use shout::*;
+
 /// Finite Difference Pricer
 pub mod finite_difference_pricer;
 
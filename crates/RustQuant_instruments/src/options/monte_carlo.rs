@@ -0,0 +1,763 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Process-agnostic Monte Carlo pricing engine.
+//!
+//! Any [`StochasticProcess`] can be paired with any terminal or
+//! path-dependent [`Payoff`], so GBM, Merton jump diffusion, CEV, etc.
+//! share one pricing code path.
+//!
+//! Control variates reduce the variance of an estimate with a second,
+//! correlated quantity whose price is known, e.g. the geometric Asian
+//! for the arithmetic Asian, or the underlying itself for vanilla payoffs.
+//!
+//! [`MonteCarloEngine`] bundles a process with the simulation settings
+//! and variance reduction toggles, so an instrument can be priced from a
+//! payoff alone.
+
+use crate::Payoff;
+use RustQuant_math::{Distribution, N};
+use RustQuant_stochastics::{
+    PathConstruction, SamplingMethod, StochasticProcess, StochasticProcessConfig,
+};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Monte Carlo price estimate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MonteCarloEstimate {
+    /// Mean of the discounted payoffs.
+    pub price: f64,
+
+    /// Standard error of the mean.
+    pub standard_error: f64,
+}
+
+/// Monte Carlo price estimate with and without a control variate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ControlVariateEstimate {
+    /// Plain Monte Carlo estimate.
+    pub raw: MonteCarloEstimate,
+
+    /// Estimate adjusted by the control variate.
+    pub adjusted: MonteCarloEstimate,
+
+    /// Fitted control coefficient, `Cov(payoff, control) / Var(control)`.
+    pub beta: f64,
+}
+
+/// Monte Carlo pricing engine: a stochastic process for the underlying,
+/// with the discounting and simulation settings to price payoffs under it.
+///
+/// Antithetic sampling, stratified or quasi-random draws and the
+/// underlying as a control variate are all off by default, and can be
+/// toggled with the `with_*` methods.
+#[derive(Debug, Clone)]
+pub struct MonteCarloEngine<P> {
+    /// The process driving the underlying (with risk-neutral drift).
+    pub process: P,
+
+    /// Initial value of the underlying.
+    pub x_0: f64,
+
+    /// Continuously compounded discount rate.
+    pub discount_rate: f64,
+
+    /// Time to maturity in years.
+    pub maturity: f64,
+
+    /// Number of simulated paths.
+    pub n_paths: usize,
+
+    /// Number of time steps per path.
+    pub n_steps: usize,
+
+    /// Simulate paths in antithetic pairs or not.
+    pub antithetic: bool,
+
+    /// Source of the Gaussian draws.
+    pub sampling: SamplingMethod,
+
+    /// How the Gaussian draws are turned into paths.
+    pub construction: PathConstruction,
+
+    /// Price of the discounted terminal underlying, when it is used as a
+    /// control variate.
+    pub underlying_control: Option<f64>,
+
+    /// Seed for reproducible prices.
+    pub seed: Option<u64>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl MonteCarloEstimate {
+    /// Two-sided confidence interval for the price at `level` (e.g. 0.95),
+    /// from the normal approximation of the sample mean.
+    ///
+    /// # Panics
+    /// Panics if `level` is not in $(0, 1)$.
+    pub fn confidence_interval(&self, level: f64) -> (f64, f64) {
+        assert!(
+            level > 0.0 && level < 1.0,
+            "The confidence level must be in (0, 1)."
+        );
+
+        let half_width = N.inv_cdf(0.5 + 0.5 * level) * self.standard_error;
+
+        (self.price - half_width, self.price + half_width)
+    }
+
+    /// Sample mean and its standard error.
+    ///
+    /// # Panics
+    /// Panics if there are fewer than two samples.
+    pub fn from_samples(samples: &[f64]) -> Self {
+        assert!(
+            samples.len() > 1,
+            "At least two paths are needed for a standard error."
+        );
+
+        let n = samples.len() as f64;
+        let price = samples.iter().sum::<f64>() / n;
+        let variance = samples.iter().map(|x| (x - price).powi(2)).sum::<f64>() / (n - 1.0);
+
+        Self {
+            price,
+            standard_error: (variance / n).sqrt(),
+        }
+    }
+}
+
+impl ControlVariateEstimate {
+    /// Adjust discounted payoff samples with control samples of known price:
+    /// `payoff - beta * (control - control_price)`, with the variance
+    /// minimising `beta` estimated from the same samples.
+    ///
+    /// # Panics
+    /// Panics if the sample lengths differ, or there are fewer than two samples.
+    pub fn from_samples(payoffs: &[f64], controls: &[f64], control_price: f64) -> Self {
+        assert_eq!(
+            payoffs.len(),
+            controls.len(),
+            "Each payoff sample needs a control sample."
+        );
+
+        let raw = MonteCarloEstimate::from_samples(payoffs);
+        let control_mean = controls.iter().sum::<f64>() / controls.len() as f64;
+
+        let (covariance, variance) =
+            payoffs
+                .iter()
+                .zip(controls)
+                .fold((0.0, 0.0), |(covariance, variance), (y, c)| {
+                    (
+                        covariance + (y - raw.price) * (c - control_mean),
+                        variance + (c - control_mean).powi(2),
+                    )
+                });
+
+        // A constant control carries no information.
+        let beta = if variance > 0.0 {
+            covariance / variance
+        } else {
+            0.0
+        };
+
+        let adjusted: Vec<f64> = payoffs
+            .iter()
+            .zip(controls)
+            .map(|(y, c)| y - beta * (c - control_price))
+            .collect();
+
+        Self {
+            raw,
+            adjusted: MonteCarloEstimate::from_samples(&adjusted),
+            beta,
+        }
+    }
+}
+
+impl<P: StochasticProcess> MonteCarloEngine<P> {
+    /// Create a new Monte Carlo engine, with no variance reduction.
+    ///
+    /// # Arguments:
+    /// * `process` - The process driving the underlying (with risk-neutral drift).
+    /// * `x_0` - Initial value of the underlying.
+    /// * `discount_rate` - Continuously compounded discount rate.
+    /// * `maturity` - Time to maturity in years.
+    /// * `n_paths` - Number of simulated paths.
+    /// * `n_steps` - Number of time steps per path.
+    pub fn new(
+        process: P,
+        x_0: f64,
+        discount_rate: f64,
+        maturity: f64,
+        n_paths: usize,
+        n_steps: usize,
+    ) -> Self {
+        Self {
+            process,
+            x_0,
+            discount_rate,
+            maturity,
+            n_paths,
+            n_steps,
+            antithetic: false,
+            sampling: SamplingMethod::PseudoRandom,
+            construction: PathConstruction::Incremental,
+            underlying_control: None,
+            seed: None,
+        }
+    }
+
+    /// Simulate paths in antithetic pairs. Each pair's average payoff is
+    /// one sample of the estimate, so the standard error stays honest.
+    pub fn with_antithetic(mut self, antithetic: bool) -> Self {
+        self.antithetic = antithetic;
+        self
+    }
+
+    /// Choose the source of the Gaussian draws (e.g. stratified or Sobol).
+    pub fn with_sampling(mut self, sampling: SamplingMethod) -> Self {
+        self.sampling = sampling;
+        self
+    }
+
+    /// Choose how the Gaussian draws are turned into paths.
+    pub fn with_path_construction(mut self, construction: PathConstruction) -> Self {
+        self.construction = construction;
+        self
+    }
+
+    /// Use the discounted terminal underlying as a control variate, with
+    /// known price `price` (the initial value, less the present value of
+    /// any dividends).
+    pub fn with_underlying_control(mut self, price: f64) -> Self {
+        self.underlying_control = Some(price);
+        self
+    }
+
+    /// Seed the simulation, so it gives the same price every time.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// The simulation configuration of the engine.
+    pub fn config(&self) -> StochasticProcessConfig {
+        let config = StochasticProcessConfig::new(
+            self.x_0,
+            0.0,
+            self.maturity,
+            self.n_steps,
+            self.n_paths,
+            true,
+        )
+        .with_antithetic(self.antithetic)
+        .with_sampling(self.sampling)
+        .with_path_construction(self.construction);
+
+        match self.seed {
+            Some(seed) => config.with_seed(seed),
+            None => config,
+        }
+    }
+
+    /// Price a terminal payoff.
+    ///
+    /// # Panics
+    /// Panics if the maturity is not positive, there are fewer than two
+    /// samples, or the number of paths is odd with antithetic sampling.
+    pub fn price<F>(&self, payoff: &F) -> MonteCarloEstimate
+    where
+        F: Payoff<Underlying = f64>,
+    {
+        self.estimate(|path| payoff.payoff(path[path.len() - 1]))
+    }
+
+    /// Price a path-dependent payoff, evaluated on each path including its
+    /// initial value.
+    ///
+    /// # Panics
+    /// Panics if the maturity is not positive, there are fewer than two
+    /// samples, or the number of paths is odd with antithetic sampling.
+    pub fn price_path<F>(&self, payoff: &F) -> MonteCarloEstimate
+    where
+        F: Payoff<Underlying = Vec<f64>>,
+    {
+        self.estimate(|path| payoff.payoff(path.to_vec()))
+    }
+
+    /// Average the discounted `payoff` of each path, adjusted by the control
+    /// variate if there is one.
+    fn estimate<F>(&self, payoff: F) -> MonteCarloEstimate
+    where
+        F: Fn(&[f64]) -> f64,
+    {
+        assert!(self.maturity > 0.0, "The maturity must be positive.");
+        assert!(
+            !self.antithetic || self.n_paths.is_multiple_of(2),
+            "Antithetic sampling needs an even number of paths."
+        );
+
+        let trajectories = self.process.euler_maruyama(&self.config());
+        let discount_factor = (-self.discount_rate * self.maturity).exp();
+
+        let (mut payoffs, mut controls): (Vec<f64>, Vec<f64>) = trajectories
+            .paths
+            .iter()
+            .map(|path| {
+                (
+                    discount_factor * payoff(path),
+                    discount_factor * path[path.len() - 1],
+                )
+            })
+            .unzip();
+
+        // The paths of an antithetic pair are not independent, but the pairs are.
+        if self.antithetic {
+            let pair_means = |samples: &[f64]| {
+                samples
+                    .chunks(2)
+                    .map(|pair| 0.5 * (pair[0] + pair[1]))
+                    .collect()
+            };
+
+            payoffs = pair_means(&payoffs);
+            controls = pair_means(&controls);
+        }
+
+        match self.underlying_control {
+            Some(control_price) => {
+                ControlVariateEstimate::from_samples(&payoffs, &controls, control_price).adjusted
+            }
+            None => MonteCarloEstimate::from_samples(&payoffs),
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Price a terminal payoff by Monte Carlo simulation of any stochastic process.
+///
+/// The process is simulated with its Euler-Maruyama scheme (so processes
+/// with their own scheme, such as jump diffusions, use it), and the
+/// discounted terminal payoffs are averaged.
+///
+/// # Arguments:
+/// * `process` - The process driving the underlying (with risk-neutral drift).
+/// * `payoff` - The payoff at maturity.
+/// * `x_0` - Initial value of the underlying.
+/// * `discount_rate` - Continuously compounded discount rate.
+/// * `maturity` - Time to maturity in years.
+/// * `n_paths` - Number of simulated paths.
+/// * `n_steps` - Number of time steps per path.
+///
+/// # Panics
+/// Panics if `maturity` is not positive, or `n_paths < 2`.
+pub fn monte_carlo<P, F>(
+    process: &P,
+    payoff: &F,
+    x_0: f64,
+    discount_rate: f64,
+    maturity: f64,
+    n_paths: usize,
+    n_steps: usize,
+) -> MonteCarloEstimate
+where
+    P: StochasticProcess,
+    F: Payoff<Underlying = f64>,
+{
+    assert!(maturity > 0.0, "The maturity must be positive.");
+
+    let config = StochasticProcessConfig::new(x_0, 0.0, maturity, n_steps, n_paths, true);
+
+    monte_carlo_with_config(process, payoff, discount_rate, &config)
+}
+
+/// Price a terminal payoff by Monte Carlo simulation, with full control
+/// over the simulation through `config` (e.g. antithetic, stratified,
+/// Latin hypercube or Sobol sampling, and seeding).
+///
+/// The maturity is `config.t_n - config.t_0`.
+/// With stratified or quasi-random sampling the paths are not independent,
+/// so the reported standard error overstates the error of the estimate.
+///
+/// # Arguments:
+/// * `process` - The process driving the underlying (with risk-neutral drift).
+/// * `payoff` - The payoff at maturity.
+/// * `discount_rate` - Continuously compounded discount rate.
+/// * `config` - The simulation configuration.
+///
+/// # Panics
+/// Panics if `config.m_paths < 2`.
+pub fn monte_carlo_with_config<P, F>(
+    process: &P,
+    payoff: &F,
+    discount_rate: f64,
+    config: &StochasticProcessConfig,
+) -> MonteCarloEstimate
+where
+    P: StochasticProcess,
+    F: Payoff<Underlying = f64>,
+{
+    assert!(
+        config.m_paths > 1,
+        "At least two paths are needed for a standard error."
+    );
+
+    let trajectories = process.euler_maruyama(config);
+
+    let discount_factor = (-discount_rate * (config.t_n - config.t_0)).exp();
+    let discounted: Vec<f64> = trajectories
+        .paths
+        .iter()
+        .map(|path| discount_factor * payoff.payoff(path[config.n_steps]))
+        .collect();
+
+    MonteCarloEstimate::from_samples(&discounted)
+}
+
+/// Price a path-dependent payoff by Monte Carlo simulation of any
+/// stochastic process.
+///
+/// The payoff is evaluated on each simulated path, including the
+/// initial value, and the discounted payoffs are averaged. Terminal
+/// payoffs can be combined with path-dependent ones through `Terminal`.
+///
+/// # Arguments:
+/// * `process` - The process driving the underlying (with risk-neutral drift).
+/// * `payoff` - The payoff at maturity, as a function of the path.
+/// * `discount_rate` - Continuously compounded discount rate.
+/// * `config` - The simulation configuration.
+///
+/// # Panics
+/// Panics if `config.m_paths < 2`.
+pub fn monte_carlo_path<P, F>(
+    process: &P,
+    payoff: &F,
+    discount_rate: f64,
+    config: &StochasticProcessConfig,
+) -> MonteCarloEstimate
+where
+    P: StochasticProcess,
+    F: Payoff<Underlying = Vec<f64>>,
+{
+    assert!(
+        config.m_paths > 1,
+        "At least two paths are needed for a standard error."
+    );
+
+    let trajectories = process.euler_maruyama(config);
+
+    let discount_factor = (-discount_rate * (config.t_n - config.t_0)).exp();
+    let discounted: Vec<f64> = trajectories
+        .paths
+        .into_iter()
+        .map(|path| discount_factor * payoff.payoff(path))
+        .collect();
+
+    MonteCarloEstimate::from_samples(&discounted)
+}
+
+/// Price a path-dependent payoff by Monte Carlo simulation, with a control variate.
+///
+/// Both `payoff` and `control` are evaluated on each simulated path
+/// (including the initial value) and discounted. `control_price` is the
+/// known price of the control, e.g. the closed-form geometric Asian price
+/// when pricing an arithmetic Asian, or `x_0` when the control is the
+/// terminal value of a non-dividend paying underlying.
+///
+/// # Arguments:
+/// * `process` - The process driving the underlying (with risk-neutral drift).
+/// * `payoff` - The payoff at maturity, as a function of the path.
+/// * `control` - The control's payoff at maturity, as a function of the path.
+/// * `control_price` - The known price of the control.
+/// * `x_0` - Initial value of the underlying.
+/// * `discount_rate` - Continuously compounded discount rate.
+/// * `maturity` - Time to maturity in years.
+/// * `n_paths` - Number of simulated paths.
+/// * `n_steps` - Number of time steps per path.
+///
+/// # Panics
+/// Panics if `maturity` is not positive, or `n_paths < 2`.
+#[allow(clippy::too_many_arguments)]
+pub fn monte_carlo_with_control_variate<P, F, C>(
+    process: &P,
+    payoff: F,
+    control: C,
+    control_price: f64,
+    x_0: f64,
+    discount_rate: f64,
+    maturity: f64,
+    n_paths: usize,
+    n_steps: usize,
+) -> ControlVariateEstimate
+where
+    P: StochasticProcess,
+    F: Fn(&[f64]) -> f64,
+    C: Fn(&[f64]) -> f64,
+{
+    assert!(maturity > 0.0, "The maturity must be positive.");
+
+    let config = StochasticProcessConfig::new(x_0, 0.0, maturity, n_steps, n_paths, true);
+    let trajectories = process.euler_maruyama(&config);
+
+    let discount_factor = (-discount_rate * maturity).exp();
+    let (payoffs, controls): (Vec<f64>, Vec<f64>) = trajectories
+        .paths
+        .iter()
+        .map(|path| {
+            (
+                discount_factor * payoff(path),
+                discount_factor * control(path),
+            )
+        })
+        .unzip();
+
+    ControlVariateEstimate::from_samples(&payoffs, &controls, control_price)
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_monte_carlo {
+    use super::*;
+    use crate::{BlackScholes73, EuropeanVanillaOption, GeneralisedBlackScholesMerton, TypeFlag};
+    use RustQuant_math::{Distribution, N};
+    use RustQuant_stochastics::{
+        GeometricBrownianMotion, MertonJumpDiffusion, PathConstruction, SamplingMethod,
+    };
+    use RustQuant_time::today;
+
+    const S: f64 = 100.0;
+    const K: f64 = 100.0;
+    const R: f64 = 0.05;
+    const V: f64 = 0.2;
+    const T: f64 = 1.0;
+
+    fn call() -> EuropeanVanillaOption {
+        EuropeanVanillaOption::new(K, today(), TypeFlag::Call)
+    }
+
+    #[test]
+    fn test_generic_engine_gbm_matches_black_scholes() {
+        let exact = BlackScholes73::new(S, R, V).price(K, T, TypeFlag::Call);
+
+        let gbm = GeometricBrownianMotion::new(R, V);
+        let config = StochasticProcessConfig::new(S, 0.0, T, 252, 20_000, true).with_seed(41);
+        let estimate = monte_carlo_with_config(&gbm, &call(), R, &config);
+
+        assert!((estimate.price - exact).abs() < 2.0 * estimate.standard_error);
+        assert!(estimate.standard_error < 0.2);
+    }
+
+    #[test]
+    fn test_generic_engine_swaps_in_merton_jump_diffusion() {
+        let exact = BlackScholes73::new(S, R, V).price(K, T, TypeFlag::Call);
+        let payoff = call();
+
+        // Without jumps, Merton's model is GBM.
+        let config = StochasticProcessConfig::new(S, 0.0, T, 252, 20_000, true).with_seed(43);
+        let no_jumps = MertonJumpDiffusion::new(R, V, 0.0, 0.0, 1.0);
+        let estimate = monte_carlo_with_config(&no_jumps, &payoff, R, &config);
+
+        assert!((estimate.price - exact).abs() < 2.0 * estimate.standard_error);
+
+        // Compensated jumps leave the forward unchanged but fatten the tails.
+        let jumps = MertonJumpDiffusion::new(R, V, 1.0, -0.1, 0.3);
+        let with_jumps = monte_carlo_with_config(&jumps, &payoff, R, &config);
+
+        assert!(with_jumps.price > exact + 3.0 * with_jumps.standard_error);
+    }
+
+    #[test]
+    fn test_underlying_control_for_vanilla_call() {
+        let exact = BlackScholes73::new(S, R, V).price(K, T, TypeFlag::Call);
+
+        let gbm = GeometricBrownianMotion::new(R, V);
+        let estimate = monte_carlo_with_control_variate(
+            &gbm,
+            |path| (path[252] - K).max(0.0),
+            |path| path[252],
+            S,
+            S,
+            R,
+            T,
+            20_000,
+            252,
+        );
+
+        assert!((estimate.adjusted.price - exact).abs() < 3.0 * estimate.adjusted.standard_error);
+        assert!(estimate.adjusted.standard_error < 0.5 * estimate.raw.standard_error);
+        assert!(estimate.beta > 0.0);
+    }
+
+    #[test]
+    fn test_geometric_asian_control_for_arithmetic_asian() {
+        let n_steps = 50;
+
+        // The discrete geometric average of GBM, over the path including S_0,
+        // is lognormal: ln G ~ N(ln S + (r - v^2 / 2) T / 2, v^2 T (2n + 1) / (6 (n + 1))).
+        let n = n_steps as f64;
+        let mean = S.ln() + (R - 0.5 * V * V) * T / 2.0;
+        let variance = V * V * T * (2.0 * n + 1.0) / (6.0 * (n + 1.0));
+        let d1 = (mean - K.ln() + variance) / variance.sqrt();
+        let d2 = d1 - variance.sqrt();
+        let geometric_price =
+            (-R * T).exp() * ((mean + 0.5 * variance).exp() * N.cdf(d1) - K * N.cdf(d2));
+
+        let arithmetic = |path: &[f64]| (path.iter().sum::<f64>() / path.len() as f64 - K).max(0.0);
+        let geometric = |path: &[f64]| {
+            let log_mean = path.iter().map(|s| s.ln()).sum::<f64>() / path.len() as f64;
+            (log_mean.exp() - K).max(0.0)
+        };
+
+        let gbm = GeometricBrownianMotion::new(R, V);
+        let estimate = monte_carlo_with_control_variate(
+            &gbm,
+            arithmetic,
+            geometric,
+            geometric_price,
+            S,
+            R,
+            T,
+            10_000,
+            n_steps,
+        );
+
+        // The two averages are almost perfectly correlated.
+        assert!(estimate.adjusted.standard_error < 0.1 * estimate.raw.standard_error);
+        assert!(
+            (estimate.adjusted.price - estimate.raw.price).abs()
+                < 3.0 * estimate.raw.standard_error
+        );
+
+        // The arithmetic average is never below the geometric one.
+        assert!(estimate.adjusted.price > geometric_price);
+    }
+
+    #[test]
+    fn test_composed_payoffs() {
+        use crate::{CallPayoff, CorridorPayoff, PayoffExt, Terminal};
+
+        let config = StochasticProcessConfig::new(S, 0.0, T, 50, 20_000, false).with_seed(42);
+        let gbm = GeometricBrownianMotion::new(R, V);
+
+        // A call capped at 20 is a 100/120 call spread.
+        let bs = BlackScholes73::new(S, R, V);
+        let exact = bs.price(K, T, TypeFlag::Call) - bs.price(K + 20.0, T, TypeFlag::Call);
+
+        let capped = CallPayoff::new(K).capped(20.0);
+        let terminal = monte_carlo_with_config(&gbm, &capped, R, &config);
+        let path = monte_carlo_path(&gbm, &Terminal::new(capped), R, &config);
+
+        assert!((terminal.price - exact).abs() < 3.0 * terminal.standard_error);
+        assert_eq!(terminal, path);
+
+        // Adding a corridor adds its price.
+        let corridor = CorridorPayoff::new(90.0, 110.0);
+        let structure = Terminal::new(capped).plus(corridor);
+
+        let corridor_price = monte_carlo_path(&gbm, &corridor, R, &config).price;
+        let structure_price = monte_carlo_path(&gbm, &structure, R, &config).price;
+
+        assert!(corridor_price > 0.0 && corridor_price < f64::exp(-R * T));
+        assert!((structure_price - path.price - corridor_price).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_engine_variance_reduction() {
+        use crate::CallPayoff;
+
+        let exact = BlackScholes73::new(S, R, V).price(K, T, TypeFlag::Call);
+        let engine = || {
+            MonteCarloEngine::new(GeometricBrownianMotion::new(R, V), S, R, T, 20_000, 50)
+                .with_seed(11)
+        };
+        let call = CallPayoff::new(K);
+
+        let plain = engine().price(&call);
+        let antithetic = engine().with_antithetic(true).price(&call);
+        let controlled = engine()
+            .with_antithetic(true)
+            .with_underlying_control(S)
+            .price(&call);
+
+        for estimate in [plain, antithetic, controlled] {
+            let (lower, upper) = estimate.confidence_interval(0.999);
+
+            assert!(lower < exact && exact < upper);
+        }
+
+        assert!(antithetic.standard_error < plain.standard_error);
+        assert!(controlled.standard_error < 0.5 * antithetic.standard_error);
+
+        // Seeded engines are reproducible.
+        assert_eq!(plain, engine().price(&call));
+    }
+
+    #[test]
+    fn test_confidence_interval() {
+        let estimate = MonteCarloEstimate {
+            price: 10.0,
+            standard_error: 0.5,
+        };
+        let (lower, upper) = estimate.confidence_interval(0.95);
+
+        assert!((upper - 10.0 - 0.5 * 1.959_963_984_540_054).abs() < 1e-8);
+        assert!((10.0 - lower - (upper - 10.0)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_control_variate_from_samples() {
+        // A control equal to the payoff removes all the variance.
+        let payoffs = [1.0, 2.0, 4.0, 7.0];
+        let estimate = ControlVariateEstimate::from_samples(&payoffs, &payoffs, 3.0);
+
+        assert!((estimate.beta - 1.0).abs() < 1e-12);
+        assert!((estimate.adjusted.price - 3.0).abs() < 1e-12);
+        assert!(estimate.adjusted.standard_error < 1e-12);
+        assert!((estimate.raw.price - 3.5).abs() < 1e-12);
+
+        // A constant control is ignored.
+        let estimate = ControlVariateEstimate::from_samples(&payoffs, &[1.0; 4], 2.0);
+        assert_eq!(estimate.beta, 0.0);
+        assert_eq!(estimate.adjusted, estimate.raw);
+    }
+
+    #[test]
+    fn test_monte_carlo_stratified_sampling() {
+        let exact = BlackScholes73::new(S, R, V).price(K, T, TypeFlag::Call);
+        let gbm = GeometricBrownianMotion::new(R, V);
+
+        for sampling in [
+            SamplingMethod::Stratified { dimension: 0 },
+            SamplingMethod::LatinHypercube,
+        ] {
+            let config = StochasticProcessConfig::new(S, 0.0, T, 64, 10_000, true)
+                .with_sampling(sampling)
+                .with_path_construction(PathConstruction::BrownianBridge);
+
+            let estimate = monte_carlo_with_config(&gbm, &call(), R, &config);
+
+            // Well inside the standard error of independent sampling (about 0.15).
+            assert!((estimate.price - exact).abs() < 0.05);
+        }
+    }
+}
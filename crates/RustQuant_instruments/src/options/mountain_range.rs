@@ -0,0 +1,355 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Mountain range options on the performances of several assets.
+//!
+//! An asset's performance on a date is its price divided by its initial
+//! price. The option pays `max(P - K, 0)` at the last observation date,
+//! where `P` averages performances chosen by the style of the option:
+//!
+//! - Himalaya: on each observation date, the best performer of the assets
+//!   still in the basket is recorded and removed, and `P` is the average
+//!   of the recorded performances.
+//! - Atlas: at the last observation date, the best and worst performers
+//!   are removed, and `P` is the average performance of the rest.
+//!
+//! The payoffs depend on the joint paths of all the assets, so they are
+//! priced by Monte Carlo.
+
+use crate::Payoff;
+use time::Date;
+use RustQuant_error::RustQuantError;
+use RustQuant_stochastics::{CorrelatedGeometricBrownianMotion, StochasticProcessConfig};
+use RustQuant_time::{today, DayCountConvention};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS & ENUMS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Which performances a mountain range option averages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MountainRangeStyle {
+    /// Record and remove the best remaining performer on each date.
+    Himalaya,
+
+    /// Remove the `best` best and `worst` worst performers at the last date.
+    Atlas {
+        /// Number of best performers removed.
+        best: usize,
+
+        /// Number of worst performers removed.
+        worst: usize,
+    },
+}
+
+/// Mountain range option, paying `notional * max(P - K, 0)` on the last
+/// observation date, where `P` is an average of asset performances.
+#[derive(Debug, Clone)]
+pub struct MountainRangeOption {
+    /// Himalaya or Atlas.
+    pub style: MountainRangeStyle,
+
+    /// Observation dates, in increasing order. The last is the expiry.
+    pub observation_dates: Vec<Date>,
+
+    /// Strike, as a performance (e.g. 1.0 for at-the-money).
+    pub strike: f64,
+
+    /// Notional amount.
+    pub notional: f64,
+
+    /// Day count convention for the times to the observation dates
+    /// (Actual/365.25 by default).
+    pub day_count: DayCountConvention,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl Payoff for MountainRangeOption {
+    /// Performance of each asset, on each observation date.
+    type Underlying = Vec<Vec<f64>>;
+
+    fn payoff(&self, underlying: Self::Underlying) -> f64 {
+        let performance = match self.style {
+            MountainRangeStyle::Himalaya => {
+                let mut remaining = vec![true; underlying[0].len()];
+
+                underlying
+                    .iter()
+                    .map(|performances| {
+                        let (best, &performance) = performances
+                            .iter()
+                            .enumerate()
+                            .filter(|&(i, _)| remaining[i])
+                            .max_by(|a, b| a.1.total_cmp(b.1))
+                            .expect("The basket is empty.");
+
+                        remaining[best] = false;
+                        performance
+                    })
+                    .sum::<f64>()
+                    / underlying.len() as f64
+            }
+            MountainRangeStyle::Atlas { best, worst } => {
+                let mut performances = underlying[underlying.len() - 1].clone();
+                performances.sort_by(f64::total_cmp);
+
+                let kept = &performances[worst..performances.len() - best];
+                kept.iter().sum::<f64>() / kept.len() as f64
+            }
+        };
+
+        self.notional * (performance - self.strike).max(0.0)
+    }
+}
+
+impl MountainRangeOption {
+    /// Create a new mountain range option.
+    ///
+    /// # Errors
+    /// - `RustQuantError::InvalidArgument` if there are no observation
+    ///   dates, or they are not in increasing order.
+    pub fn new(
+        style: MountainRangeStyle,
+        observation_dates: Vec<Date>,
+        strike: f64,
+        notional: f64,
+    ) -> Result<Self, RustQuantError> {
+        if observation_dates.is_empty() || observation_dates.windows(2).any(|w| w[0] >= w[1]) {
+            return Err(RustQuantError::InvalidArgument(
+                "Observation dates must be non-empty and increasing.".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            style,
+            observation_dates,
+            strike,
+            notional,
+            day_count: DayCountConvention::Actual_365_25,
+        })
+    }
+
+    /// Set the day count convention for the times to the observation dates.
+    pub fn with_day_count(mut self, day_count: DayCountConvention) -> Self {
+        self.day_count = day_count;
+        self
+    }
+
+    /// Monte Carlo price of the option.
+    ///
+    /// The prices on each observation date are sampled exactly from
+    /// `process`, whose drifts should be the risk-neutral drifts `r - q_i`,
+    /// so only the number of paths, the seed and antithetic sampling are
+    /// taken from `config`.
+    ///
+    /// # Arguments:
+    /// * `process` - Correlated GBMs driving the assets.
+    /// * `spots` - Initial price of each asset.
+    /// * `config` - The simulation configuration.
+    /// * `r` - Risk-free rate.
+    ///
+    /// # Errors
+    /// - `RustQuantError::UnequalLength` if `spots` does not match the
+    ///   dimension of `process`.
+    /// - `RustQuantError::InvalidArgument` if an observation date is not
+    ///   after today, a Himalaya has more observation dates than assets,
+    ///   or an Atlas removes every asset.
+    /// - `RustQuantError::InvalidArgument` if `config` asks for sampling
+    ///   other than pseudo-random, or a Brownian bridge construction.
+    pub fn price_monte_carlo(
+        &self,
+        process: &CorrelatedGeometricBrownianMotion,
+        spots: &[f64],
+        config: &StochasticProcessConfig,
+        r: f64,
+    ) -> Result<f64, RustQuantError> {
+        if spots.len() != process.dimension() {
+            return Err(RustQuantError::UnequalLength);
+        }
+
+        let n_assets = spots.len();
+        let valid = match self.style {
+            MountainRangeStyle::Himalaya => self.observation_dates.len() <= n_assets,
+            MountainRangeStyle::Atlas { best, worst } => best + worst < n_assets,
+        };
+
+        if !valid {
+            return Err(RustQuantError::InvalidArgument(
+                "Too few assets for the mountain range.".to_string(),
+            ));
+        }
+
+        let times: Vec<f64> = self
+            .observation_dates
+            .iter()
+            .map(|&date| self.day_count.day_count_factor(today(), date))
+            .collect();
+
+        if times[0] <= 0.0 {
+            return Err(RustQuantError::InvalidArgument(
+                "Observation dates must be after today.".to_string(),
+            ));
+        }
+
+        let payoffs: f64 = process
+            .sample_at_times(spots, &times, config)?
+            .into_iter()
+            .map(|path| {
+                let performances = path
+                    .iter()
+                    .map(|prices| prices.iter().zip(spots).map(|(s, s_0)| s / s_0).collect())
+                    .collect();
+
+                self.payoff(performances)
+            })
+            .sum();
+
+        Ok((-r * times[times.len() - 1]).exp() * payoffs / config.m_paths as f64)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_mountain_range {
+    use super::*;
+    use crate::{GeneralisedBlackScholesMerton, Merton73, TypeFlag};
+    use time::Duration;
+    use RustQuant_time::year_fraction;
+    use RustQuant_utils::assert_approx_equal;
+
+    const R: f64 = 0.05;
+
+    /// Seeded configuration; only the number of paths and seed are used.
+    fn config(n_paths: usize, seed: u64) -> StochasticProcessConfig {
+        StochasticProcessConfig::new(0.0, 0.0, 1.0, 1, n_paths, false).with_seed(seed)
+    }
+
+    fn dates(n: i64) -> Vec<Date> {
+        (1..=n).map(|i| today() + Duration::days(365 * i)).collect()
+    }
+
+    fn process(n: usize, rho: f64) -> CorrelatedGeometricBrownianMotion {
+        let correlation: Vec<Vec<f64>> = (0..n)
+            .map(|i| (0..n).map(|j| if i == j { 1.0 } else { rho }).collect())
+            .collect();
+
+        CorrelatedGeometricBrownianMotion::new(vec![R; n], vec![0.25; n], &correlation).unwrap()
+    }
+
+    #[test]
+    fn test_payoffs() {
+        let performances = vec![
+            vec![1.1, 0.9, 1.3, 1.0],
+            vec![1.4, 0.8, 1.5, 1.2],
+            vec![1.2, 0.7, 1.6, 0.9],
+        ];
+
+        // Records 1.3 (asset 2), then 1.4 (asset 0), then 0.9 (asset 3).
+        let himalaya =
+            MountainRangeOption::new(MountainRangeStyle::Himalaya, dates(3), 1.0, 100.0).unwrap();
+        let payoff = himalaya.payoff(performances.clone());
+        assert_approx_equal!(payoff, 20.0, 1e-10);
+
+        // Drops 1.6 and 0.7 at the last date.
+        let atlas = MountainRangeOption::new(
+            MountainRangeStyle::Atlas { best: 1, worst: 1 },
+            dates(3),
+            1.0,
+            100.0,
+        )
+        .unwrap();
+        let payoff = atlas.payoff(performances);
+        assert_approx_equal!(payoff, 5.0, 1e-10);
+    }
+
+    #[test]
+    fn test_single_asset_is_vanilla() {
+        let expiry = dates(1);
+        let t = year_fraction(today(), expiry[0]);
+        let vanilla = 100.0 * Merton73::new(1.0, R, 0.0, 0.25).price(1.0, t, TypeFlag::Call);
+
+        for style in [
+            MountainRangeStyle::Himalaya,
+            MountainRangeStyle::Atlas { best: 0, worst: 0 },
+        ] {
+            let option = MountainRangeOption::new(style, expiry.clone(), 1.0, 100.0).unwrap();
+            let mc = option
+                .price_monte_carlo(&process(1, 0.0), &[50.0], &config(100_000, 3), R)
+                .unwrap();
+
+            // About three standard errors.
+            assert_approx_equal!(mc, vanilla, 0.15);
+        }
+    }
+
+    #[test]
+    fn test_removing_performers() {
+        let process = process(4, 0.4);
+        let spots = [100.0, 50.0, 80.0, 120.0];
+
+        let price = |style| {
+            MountainRangeOption::new(style, dates(3), 1.0, 100.0)
+                .unwrap()
+                .price_monte_carlo(&process, &spots, &config(20_000, 11), R)
+                .unwrap()
+        };
+
+        // On the same paths, removing the best lowers the average and
+        // removing the worst raises it.
+        let atlas = price(MountainRangeStyle::Atlas { best: 0, worst: 0 });
+        let without_best = price(MountainRangeStyle::Atlas { best: 1, worst: 0 });
+        let without_worst = price(MountainRangeStyle::Atlas { best: 0, worst: 1 });
+
+        assert!(without_best < atlas && atlas < without_worst);
+
+        // Recording the best performers beats the average of all assets
+        // at the last date, on average, but not the best of them.
+        let himalaya = price(MountainRangeStyle::Himalaya);
+        let best_only = price(MountainRangeStyle::Atlas { best: 0, worst: 3 });
+
+        assert!(himalaya > atlas && himalaya < best_only);
+    }
+
+    #[test]
+    fn test_invalid_mountain_range() {
+        assert!(MountainRangeOption::new(
+            MountainRangeStyle::Himalaya,
+            vec![today() + Duration::days(2), today() + Duration::days(1)],
+            1.0,
+            1.0
+        )
+        .is_err());
+
+        let himalaya =
+            MountainRangeOption::new(MountainRangeStyle::Himalaya, dates(3), 1.0, 1.0).unwrap();
+        let atlas = MountainRangeOption::new(
+            MountainRangeStyle::Atlas { best: 1, worst: 1 },
+            dates(1),
+            1.0,
+            1.0,
+        )
+        .unwrap();
+
+        assert!(himalaya
+            .price_monte_carlo(&process(2, 0.0), &[1.0, 1.0], &config(10, 0), R)
+            .is_err());
+        assert!(atlas
+            .price_monte_carlo(&process(2, 0.0), &[1.0, 1.0], &config(10, 0), R)
+            .is_err());
+        assert!(atlas
+            .price_monte_carlo(&process(3, 0.0), &[1.0, 1.0], &config(10, 0), R)
+            .is_err());
+    }
+}
@@ -9,6 +9,7 @@
 
 use super::option_flags::*;
 use derive_builder::Builder;
+use RustQuant_time::{today, DayCountConvention};
 
 /// Option contract data.
 #[derive(Debug, Clone, Builder)]
@@ -26,4 +27,21 @@ pub struct OptionContract {
     /// Optional: Settlement type (cash or physical).
     #[builder(default)]
     pub settlement_flag: Option<SettlementFlag>,
+
+    /// Optional: Day count convention for the time to expiry
+    /// (Actual/365.25 by default).
+    #[builder(default = "DayCountConvention::Actual_365_25")]
+    pub day_count: DayCountConvention,
+}
+
+impl OptionContract {
+    /// Time from today to `date`, in years, under the contract's day count.
+    pub fn time_to(&self, date: time::Date) -> f64 {
+        self.day_count.day_count_factor(today(), date)
+    }
+
+    /// Time from today to expiry, in years, under the contract's day count.
+    pub fn time_to_expiry(&self) -> f64 {
+        self.time_to(self.exercise_flag.expiry())
+    }
 }
@@ -114,6 +114,27 @@ pub enum BarrierType {
     DownAndIn,
 }
 
+/// Double barrier type flag.
+#[derive(Clone, Copy, Debug)]
+pub enum DoubleBarrierType {
+    /// Knocked out if the underlying touches either barrier.
+    KnockOut,
+
+    /// Knocked in if the underlying touches either barrier.
+    KnockIn,
+}
+
+/// How the time a Parisian option's underlying spends beyond the
+/// barrier is measured.
+#[derive(Clone, Copy, Debug)]
+pub enum ParisianStyle {
+    /// The longest single excursion beyond the barrier.
+    Consecutive,
+
+    /// The total time beyond the barrier, over all excursions.
+    Cumulative,
+}
+
 /// Binary type enum.
 #[derive(Debug, Clone, Copy)]
 pub enum BinaryType {
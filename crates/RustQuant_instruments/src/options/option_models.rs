@@ -9,9 +9,11 @@
 
 use std::net::SocketAddrV4;
 
-use crate::TypeFlag;
+use crate::{CharacteristicFunction, TypeFlag};
 use argmin::solver::{neldermead::NelderMead, particleswarm::ParticleSwarm};
+use num::Complex;
 use serde::{Deserialize, Serialize};
+use RustQuant_error::RustQuantError;
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // STRUCTS & TRAITS
@@ -89,11 +91,74 @@ pub struct Sabr02 {
     nu: f64,
 }
 
+/// Schroder (1989) constant elasticity of variance (CEV) option pricing parameters.
+///
+/// The underlying follows $dS = (r - q) S dt + \sigma S^\gamma dW$.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Schroder89 {
+    s: f64,
+    r: f64,
+    q: f64,
+    v: f64,
+    gamma: f64,
+}
+
+/// How the price of a generalised Black-Scholes-Merton model depends on
+/// the risk-free rate, which decides its rho.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RateExposure {
+    /// Through discounting and the cost of carry (options on spot).
+    DiscountAndCarry,
+
+    /// Through discounting only (options on futures and forwards, whose
+    /// price does not drift).
+    Discount,
+
+    /// Not at all (futures-style margined options).
+    None,
+}
+
 /// Generalised Black-Scholes-Merton option pricing model and it's Greeks.
+///
+/// The price and Greeks do not check their inputs. A zero strike gives the
+/// limit as the strike goes to zero, so the call is worth $S e^{(b-r)t}$
+/// and the put nothing, while a negative strike gives `NaN`. Use
+/// `try_price` to price with checked inputs, and `validate_inputs` to
+/// check them before computing Greeks.
 pub trait GeneralisedBlackScholesMerton {
     /// Price a European option.
     fn price(&self, k: f64, t: f64, option_type: TypeFlag) -> f64;
 
+    /// Price a European option, checking the strike and time to expiry first.
+    ///
+    /// # Errors
+    /// See `validate_inputs`.
+    fn try_price(&self, k: f64, t: f64, option_type: TypeFlag) -> Result<f64, RustQuantError> {
+        self.validate_inputs(k, t)?;
+
+        Ok(self.price(k, t, option_type))
+    }
+
+    /// Check the strike and time to expiry passed to the price and Greeks.
+    ///
+    /// # Errors
+    /// - `RustQuantError::InvalidArgument` if the strike is not positive or
+    ///   the time to expiry is negative.
+    fn validate_inputs(&self, k: f64, t: f64) -> Result<(), RustQuantError> {
+        if k.is_nan() || k <= 0.0 {
+            return Err(RustQuantError::InvalidArgument(format!(
+                "The strike must be positive for the Black-Scholes-Merton model, got {k}."
+            )));
+        }
+        if t.is_nan() || t < 0.0 {
+            return Err(RustQuantError::InvalidArgument(format!(
+                "The time to expiry must be non-negative, got {t}."
+            )));
+        }
+
+        Ok(())
+    }
+
     /// Delta of a European option.
     fn delta(&self, k: f64, t: f64, option_type: TypeFlag) -> f64;
 
@@ -164,6 +229,10 @@ impl BlackScholes73 {
     fn b(&self) -> f64 {
         self.r
     }
+    #[inline]
+    fn rate_exposure(&self) -> RateExposure {
+        RateExposure::DiscountAndCarry
+    }
 }
 
 impl Merton73 {
@@ -186,6 +255,10 @@ impl Merton73 {
     fn b(&self) -> f64 {
         self.r - self.q
     }
+    #[inline]
+    fn rate_exposure(&self) -> RateExposure {
+        RateExposure::DiscountAndCarry
+    }
 }
 
 impl Black76 {
@@ -208,6 +281,10 @@ impl Black76 {
     fn b(&self) -> f64 {
         0.0
     }
+    #[inline]
+    fn rate_exposure(&self) -> RateExposure {
+        RateExposure::Discount
+    }
 }
 
 impl Asay82 {
@@ -230,6 +307,10 @@ impl Asay82 {
     fn b(&self) -> f64 {
         0.0
     }
+    #[inline]
+    fn rate_exposure(&self) -> RateExposure {
+        RateExposure::None
+    }
 }
 
 impl GarmanKohlhagen83 {
@@ -252,6 +333,10 @@ impl GarmanKohlhagen83 {
     fn b(&self) -> f64 {
         self.r_d - self.r_f
     }
+    #[inline]
+    fn rate_exposure(&self) -> RateExposure {
+        RateExposure::DiscountAndCarry
+    }
 }
 
 impl Heston93 {
@@ -299,16 +384,49 @@ impl Sabr02 {
     }
 }
 
+impl Schroder89 {
+    /// Create a new Schroder (1989) CEV option pricing parameters.
+    ///
+    /// # Panics
+    /// Panics if the elasticity `gamma` is negative.
+    pub fn new(s: f64, r: f64, q: f64, v: f64, gamma: f64) -> Self {
+        assert!(gamma >= 0.0, "CEV elasticity must be non-negative.");
+
+        Self { s, r, q, v, gamma }
+    }
+}
+
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // FUNCTIONS AND TRAIT IMPLS
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-mod bsm {
+pub(crate) mod bsm {
     use RustQuant_math::{Distribution, N};
 
+    /// `d1` of the generalised Black-Scholes-Merton model.
+    ///
+    /// Negative rates and carry are valid. When there is no variance left
+    /// (`t = 0` or `v = 0`), `d1` is `+/-inf` depending on whether the option
+    /// is in or out of the money (and zero at-the-money), so the prices
+    /// reduce to the discounted intrinsic value of the forward.
+    ///
+    /// The strike is not checked here. A zero strike makes `d1` infinite,
+    /// giving the zero-strike limit, and a negative strike gives `NaN`.
+    /// `GeneralisedBlackScholesMerton::validate_inputs` rejects both.
     #[inline]
     pub(crate) fn d1(s: f64, k: f64, t: f64, b: f64, v: f64) -> f64 {
-        ((s / k).ln() + (b + 0.5 * v.powi(2)) * t) / (v * t.sqrt())
+        let log_moneyness = (s / k).ln() + b * t;
+        let std_dev = v * t.sqrt();
+
+        if std_dev > 0.0 {
+            (log_moneyness + 0.5 * std_dev * std_dev) / std_dev
+        } else if log_moneyness > 0.0 {
+            f64::INFINITY
+        } else if log_moneyness < 0.0 {
+            f64::NEG_INFINITY
+        } else {
+            0.0
+        }
     }
 
     #[inline]
@@ -316,6 +434,18 @@ mod bsm {
         d1(s, k, t, b, v) - v * t.sqrt()
     }
 
+    /// `N'(d1) / (v sqrt(t))`, which vanishes when there is no variance left.
+    #[inline]
+    pub(crate) fn density_over_std_dev(d1: f64, t: f64, v: f64) -> f64 {
+        let std_dev = v * t.sqrt();
+
+        if std_dev > 0.0 {
+            N.pdf(d1) / std_dev
+        } else {
+            0.0
+        }
+    }
+
     #[inline]
     pub(crate) fn call_price(s: f64, k: f64, t: f64, r: f64, b: f64, v: f64) -> f64 {
         let d1 = d1(s, k, t, b, v);
@@ -350,7 +480,7 @@ mod bsm {
     pub(crate) fn call_gamma(s: f64, k: f64, t: f64, r: f64, b: f64, v: f64) -> f64 {
         let d1 = d1(s, k, t, b, v);
 
-        ((b - r) * t).exp() * N.pdf(d1) / (s * v * t.sqrt())
+        ((b - r) * t).exp() * density_over_std_dev(d1, t, v) / s
     }
 
     #[inline]
@@ -363,7 +493,7 @@ mod bsm {
         let d1 = d1(s, k, t, b, v);
         let d2 = d2(s, k, t, b, v);
 
-        -s * ((b - r) * t).exp() * N.pdf(d1) * v / (2.0 * t.sqrt())
+        -0.5 * s * ((b - r) * t).exp() * v * v * density_over_std_dev(d1, t, v)
             - (b - r) * s * ((b - r) * t).exp() * N.cdf(d1)
             - r * k * (-r * t).exp() * N.cdf(d2)
     }
@@ -373,7 +503,7 @@ mod bsm {
         let d1 = d1(s, k, t, b, v);
         let d2 = d2(s, k, t, b, v);
 
-        -s * ((b - r) * t).exp() * N.pdf(d1) * v / (2.0 * t.sqrt())
+        -0.5 * s * ((b - r) * t).exp() * v * v * density_over_std_dev(d1, t, v)
             + (b - r) * s * ((b - r) * t).exp() * N.cdf(-d1)
             + r * k * (-r * t).exp() * N.cdf(-d2)
     }
@@ -574,9 +704,15 @@ macro_rules! impl_gbsm {
             }
 
             fn rho(&self, k: f64, t: f64, option_type: TypeFlag) -> f64 {
-                match option_type {
-                    TypeFlag::Call => bsm::call_rho(self.s(), k, t, self.r(), self.b(), self.v),
-                    TypeFlag::Put => bsm::put_rho(self.s(), k, t, self.r(), self.b(), self.v),
+                match (self.rate_exposure(), option_type) {
+                    (RateExposure::DiscountAndCarry, TypeFlag::Call) => {
+                        bsm::call_rho(self.s(), k, t, self.r(), self.b(), self.v)
+                    }
+                    (RateExposure::DiscountAndCarry, TypeFlag::Put) => {
+                        bsm::put_rho(self.s(), k, t, self.r(), self.b(), self.v)
+                    }
+                    (RateExposure::Discount, _) => -t * self.price(k, t, option_type),
+                    (RateExposure::None, _) => 0.0,
                 }
             }
 
@@ -645,15 +781,69 @@ impl_gbsm!(Black76);
 impl_gbsm!(Asay82);
 impl_gbsm!(GarmanKohlhagen83);
 
+macro_rules! impl_gbsm_characteristic_function {
+    ($gbsm_variant:ident) => {
+        impl CharacteristicFunction for $gbsm_variant {
+            /// The log spot price is normal with variance $\sigma^2 t$.
+            fn characteristic_function(&self, u: Complex<f64>, t: f64) -> Complex<f64> {
+                let i: Complex<f64> = Complex::i();
+                let variance = self.v * self.v * t;
+                let mean = self.s().ln() + self.b() * t - 0.5 * variance;
+
+                (i * u * mean - 0.5 * variance * u * u).exp()
+            }
+
+            fn risk_free_rate(&self) -> f64 {
+                self.r()
+            }
+        }
+    };
+}
+
+impl_gbsm_characteristic_function!(BlackScholes73);
+impl_gbsm_characteristic_function!(Merton73);
+impl_gbsm_characteristic_function!(Black76);
+impl_gbsm_characteristic_function!(Asay82);
+impl_gbsm_characteristic_function!(GarmanKohlhagen83);
+
 mod heston {
     use num::Complex;
-    use std::f64::consts::{FRAC_1_PI, PI};
-    use RustQuant_math::integrate;
+    use std::f64::consts::FRAC_1_PI;
+    use RustQuant_math::gauss_legendre;
 
-    #[derive(Clone, Copy)]
-    enum Switch {
-        One = 1,
-        Two = 2,
+    /// Characteristic function of the log spot price at maturity,
+    /// $\phi(u) = E[e^{i u \ln S_T}]$.
+    ///
+    /// Uses the formulation of Albrecher et al. (2007), "The little Heston
+    /// trap", which stays on the principal branch of the complex logarithm,
+    /// so is stable for long maturities, unlike Heston's original form.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn characteristic_function(
+        u: Complex<f64>,
+        s: f64,
+        v: f64,
+        t: f64,
+        r: f64,
+        q: f64,
+        rho: f64,
+        kappa: f64,
+        theta: f64,
+        sigma: f64,
+    ) -> Complex<f64> {
+        // i = sqrt(-1). Used frequently, so assign here.
+        let i: Complex<f64> = Complex::i();
+
+        let xi = kappa - sigma * rho * i * u;
+        let d = (xi * xi + sigma * sigma * (u * u + i * u)).sqrt();
+        let g = (xi - d) / (xi + d);
+        let e = (-d * t).exp();
+
+        let C = i * u * (s.ln() + (r - q) * t)
+            + (kappa * theta / (sigma * sigma))
+                * ((xi - d) * t - 2.0 * ((1.0 - g * e) / (1.0 - g)).ln());
+        let D = (xi - d) / (sigma * sigma) * (1.0 - e) / (1.0 - g * e);
+
+        (C + D * v).exp()
     }
 
     /// Heston model for option pricing.
@@ -736,63 +926,15 @@ mod heston {
         theta: f64,
         sigma: f64,
     ) -> f64 {
-        // Market price of volatility risk (set to 0 for simplicity).
-        // Should probably include, though, since for equity options it has been shown
-        // to be non-zero (Lamoureux & Lastrapes, 1993).
-        let lambda = 0.0;
-
-        // i = sqrt(-1). Used frequently, so assign here.
         let i: Complex<f64> = Complex::i();
+        let phi =
+            |u: Complex<f64>| characteristic_function(u, s, v, t, r, q, rho, kappa, theta, sigma);
+        let forward = s * ((r - q) * t).exp();
 
-        let u = |j: Switch| -> f64 {
-            match j {
-                Switch::One => 0.5,
-                Switch::Two => -0.5,
-            }
-        };
-
-        let b = |j: Switch| -> f64 {
-            match j {
-                Switch::One => kappa + lambda - rho * sigma,
-                Switch::Two => kappa + lambda,
-            }
-        };
-
-        let d = |j: Switch, phi: f64| -> Complex<f64> {
-            ((rho * sigma * i * phi - b(j)).powi(2)
-                - sigma.powi(2) * (2.0 * u(j) * i * phi - phi.powi(2)))
-            .sqrt()
-        };
-
-        let g = |j: Switch, phi: f64| -> Complex<f64> {
-            (b(j) - rho * sigma * i * phi + d(j, phi)) / (b(j) - rho * sigma * i * phi - d(j, phi))
-        };
-
-        let C = |j: Switch, phi: f64| -> Complex<f64> {
-            (r - q) * i * phi * t
-                + (kappa * theta / sigma.powi(2))
-                    * ((b(j) - rho * sigma * i * phi + d(j, phi)) * t
-                        - 2.0
-                            * ((1.0 - g(j, phi) * (d(j, phi) * t).exp()) / (1.0 - g(j, phi))).ln())
-        };
-
-        let D = |j: Switch, phi: f64| -> Complex<f64> {
-            ((b(j) - rho * sigma * i * phi + d(j, phi)) * (1.0 - (d(j, phi) * t).exp()))
-                / (sigma.powi(2) * (1.0 - g(j, phi) * (d(j, phi) * t).exp()))
-        };
-
-        // The Heston characteristic functions.
-        let f = |j: Switch, phi: f64| -> Complex<f64> {
-            (C(j, phi) + D(j, phi) * v + i * phi * s.ln()).exp()
-        };
-
-        let Re1 = |phi: f64| -> f64 {
-            let j = Switch::One;
-
-            ((-i * phi * k.ln()).exp() * f(j, phi)).re
-        };
-
-        let dP1 = integrate(Re1, 0.00001, 50.0) * (PI * s).recip();
+        // P1 depends on the spot through exp(i u ln s), so differentiating
+        // under the integral cancels the 1 / (i u) factor.
+        let dP1 = FRAC_1_PI / s
+            * inversion_integral(|u| ((-i * u * k.ln()).exp() * phi(u - i) / forward).re);
 
         dP1 * (-q * t).exp()
     }
@@ -846,6 +988,8 @@ mod heston {
         k * t * (-r * t).exp() * (P2 - 1.0)
     }
 
+    /// The in-the-money probabilities under the stock measure (P1)
+    /// and the risk-neutral measure (P2), by Gil-Pelaez inversion.
     pub(crate) fn p1p2(
         s: f64,
         v: f64,
@@ -858,79 +1002,65 @@ mod heston {
         theta: f64,
         sigma: f64,
     ) -> (f64, f64) {
-        // Market price of volatility risk (set to 0 for simplicity).
-        // Should probably include, though, since for equity options it has been shown
-        // to be non-zero (Lamoureux & Lastrapes, 1993).
-        let lambda = 0.0;
-
-        // i = sqrt(-1). Used frequently, so assign here.
         let i: Complex<f64> = Complex::i();
+        let phi =
+            |u: Complex<f64>| characteristic_function(u, s, v, t, r, q, rho, kappa, theta, sigma);
+
+        // phi(-i) = E[S_T] is the forward, which changes to the stock measure.
+        let forward = s * ((r - q) * t).exp();
+
+        let P1 = 0.5
+            + FRAC_1_PI
+                * inversion_integral(|u| {
+                    ((-i * u * k.ln()).exp() * phi(u - i) / (i * u * forward)).re
+                });
+        let P2 = 0.5
+            + FRAC_1_PI
+                * inversion_integral(|u| {
+                    ((-i * u * k.ln()).exp() * phi(Complex::from(u)) / (i * u)).re
+                });
 
-        let u = |j: Switch| -> f64 {
-            match j {
-                Switch::One => 0.5,
-                Switch::Two => -0.5,
-            }
-        };
+        (P1, P2)
+    }
 
-        let b = |j: Switch| -> f64 {
-            match j {
-                Switch::One => kappa + lambda - rho * sigma,
-                Switch::Two => kappa + lambda,
+    /// Integrates an inversion integrand over $(0, \infty)$ with 32-point
+    /// Gauss-Legendre panels, stopping once a panel is negligible.
+    ///
+    /// The integrand is concentrated near zero for long maturities and
+    /// decays slowly for short ones, so the panels start narrow and widen,
+    /// rather than using a fixed upper bound.
+    fn inversion_integral<F>(f: F) -> f64
+    where
+        F: Fn(f64) -> f64,
+    {
+        const MAX_PANEL_WIDTH: f64 = 8.0;
+        const MAX_PANELS: usize = 250;
+        const TOLERANCE: f64 = 1e-14;
+
+        let (mut a, mut width) = (0.0, 0.25);
+        let mut integral = 0.0;
+
+        for _ in 0..MAX_PANELS {
+            let contribution = gauss_legendre(&f, a, a + width, 32);
+            let magnitude = gauss_legendre(|u| f(u).abs(), a, a + width, 32);
+
+            integral += contribution;
+
+            if magnitude < TOLERANCE {
+                break;
             }
-        };
-
-        let d = |j: Switch, phi: f64| -> Complex<f64> {
-            ((rho * sigma * i * phi - b(j)).powi(2)
-                - sigma.powi(2) * (2.0 * u(j) * i * phi - phi.powi(2)))
-            .sqrt()
-        };
-
-        let g = |j: Switch, phi: f64| -> Complex<f64> {
-            (b(j) - rho * sigma * i * phi + d(j, phi)) / (b(j) - rho * sigma * i * phi - d(j, phi))
-        };
 
-        let C = |j: Switch, phi: f64| -> Complex<f64> {
-            (r - q) * i * phi * t
-                + (kappa * theta / sigma.powi(2))
-                    * ((b(j) - rho * sigma * i * phi + d(j, phi)) * t
-                        - 2.0
-                            * ((1.0 - g(j, phi) * (d(j, phi) * t).exp()) / (1.0 - g(j, phi))).ln())
-        };
-
-        let D = |j: Switch, phi: f64| -> Complex<f64> {
-            ((b(j) - rho * sigma * i * phi + d(j, phi)) * (1.0 - (d(j, phi) * t).exp()))
-                / (sigma.powi(2) * (1.0 - g(j, phi) * (d(j, phi) * t).exp()))
-        };
-
-        // The Heston characteristic functions.
-        let f = |j: Switch, phi: f64| -> Complex<f64> {
-            (C(j, phi) + D(j, phi) * v + i * phi * s.ln()).exp()
-        };
-
-        // These functions return the integrand for P1 and P2.
-        let Re1 = |phi: f64| -> f64 {
-            let j = Switch::One;
-
-            (f(j, phi) * (-i * phi * k.ln()).exp() / (i * phi)).re
-        };
-        let Re2 = |phi: f64| -> f64 {
-            let j = Switch::Two;
-
-            (f(j, phi) * (-i * phi * k.ln()).exp() / (i * phi)).re
-        };
-
-        // Integration bounds given in Fabrice D. Rouah's book (see tests).
-        // The integral decays rapidly so 50 is probably enough.
-        let P1 = 0.5 + FRAC_1_PI * integrate(Re1, 0.00001, 50.0);
-        let P2 = 0.5 + FRAC_1_PI * integrate(Re2, 0.00001, 50.0);
+            a += width;
+            width = (2.0 * width).min(MAX_PANEL_WIDTH);
+        }
 
-        (P1, P2)
+        integral
     }
 }
 
 impl Heston93 {
-    /// Price a European option using the Heston model.
+    /// Price a European option using the Heston model, by Fourier inversion
+    /// of its characteristic function.
     pub fn price(&self, k: f64, t: f64, option_type: TypeFlag) -> f64 {
         let (s, v, r, q, rho, kappa, theta, sigma) = self.unpack();
 
@@ -985,6 +1115,18 @@ impl Heston93 {
     }
 }
 
+impl CharacteristicFunction for Heston93 {
+    fn characteristic_function(&self, u: Complex<f64>, t: f64) -> Complex<f64> {
+        let (s, v, r, q, rho, kappa, theta, sigma) = self.unpack();
+
+        heston::characteristic_function(u, s, v, t, r, q, rho, kappa, theta, sigma)
+    }
+
+    fn risk_free_rate(&self) -> f64 {
+        self.r
+    }
+}
+
 mod bachelier {
     use std::f64::consts::{FRAC_PI_2, PI};
     use RustQuant_math::{gaussian::N, Distribution};
@@ -1052,50 +1194,49 @@ mod bachelier {
         1.174_240_599_306_01e+1,
     ];
 
+    /// Implied normal volatility from the undiscounted price of a call.
     #[inline]
     pub(crate) fn call_iv(price: f64, f: f64, k: f64, t: f64) -> f64 {
-        let v = (f - k).abs() / (2. * price - (f - k));
-        let eta = v / v.atanh();
-
-        let mut sum1 = 0.0;
-        let mut sum2 = 0.0;
-
-        // for k in 0..A.len() {
-        for (k, v) in A.iter().enumerate() {
-            sum1 += v * eta.powi(k as i32);
-        }
-
-        // for k in 0..B.len() {
-        for (k, v) in B.iter().enumerate() {
-            sum2 += v * eta.powi(k as i32);
-        }
-
-        let hn = eta.sqrt() * sum1 / (1. + sum2);
-
-        (FRAC_PI_2 / t).sqrt() * (2. * price - (f - k)) * hn
+        // The straddle, by put-call parity.
+        straddle_iv(2. * price - (f - k), f, k, t)
     }
 
+    /// Implied normal volatility from the undiscounted price of a put.
     #[inline]
     pub(crate) fn put_iv(price: f64, f: f64, k: f64, t: f64) -> f64 {
-        let v = (f - k).abs() / (2. * price + (f - k));
-        let eta = v / v.atanh();
+        straddle_iv(2. * price + (f - k), f, k, t)
+    }
 
-        let mut sum1 = 0.0;
-        let mut sum2 = 0.0;
+    /// Rational approximation of Choi, Kim and Kwak (2009) for the implied
+    /// normal volatility, from the undiscounted price of a straddle.
+    ///
+    /// The approximation is accurate to near machine precision for all
+    /// moneyness, including negative forwards and strikes. It returns NaN
+    /// if the straddle is at or below its intrinsic value $|F - K|$.
+    #[inline]
+    fn straddle_iv(straddle: f64, f: f64, k: f64, t: f64) -> f64 {
+        let v = (f - k).abs() / straddle;
 
-        // for k in 0..A.len() {
-        for (k, v) in A.iter().enumerate() {
-            sum1 += v * eta.powi(k as i32);
-        }
+        // v / atanh(v) -> 1 at the money.
+        let eta = match v {
+            v if v < 1e-8 => 1.0 - v * v / 3.0,
+            v => v / v.atanh(),
+        };
 
-        // for k in 1..=B.len() {
-        for (k, v) in B.iter().enumerate().skip(1) {
-            sum2 += v * eta.powi(k as i32);
-        }
+        let numerator: f64 = A
+            .iter()
+            .enumerate()
+            .map(|(k, a)| a * eta.powi(k as i32))
+            .sum();
+        let denominator: f64 = B
+            .iter()
+            .enumerate()
+            .map(|(k, b)| b * eta.powi(k as i32 + 1))
+            .sum();
 
-        let hn = eta.sqrt() * sum1 / (1. + sum2);
+        let hn = eta.sqrt() * numerator / (1. + denominator);
 
-        (FRAC_PI_2 / t).sqrt() * (2. * price + (f - k)) * hn
+        (FRAC_PI_2 / t).sqrt() * straddle * hn
     }
 
     #[inline]
@@ -1158,16 +1299,23 @@ impl Bachelier {
 
     /// ATM price of a European option using the Bachelier model.
     pub fn atm_price(&self, t: f64) -> f64 {
-        bachelier::atm_price(t, self.v)
+        (-self.r * t).exp() * bachelier::atm_price(t, self.v)
     }
 
     /// ATM volatility of a European option using the Bachelier model.
     pub fn atm_vol(&self, price: f64, t: f64) -> f64 {
-        bachelier::atm_vol(price, t)
+        bachelier::atm_vol(price * (self.r * t).exp(), t)
     }
 
-    /// Implied volatility of a European option using the Bachelier model.
+    /// Implied normal volatility of a European option using the Bachelier
+    /// model, from its (discounted) market price.
+    ///
+    /// The model's own volatility is ignored. Forwards and strikes may be
+    /// negative. Returns NaN if the price is at or below the discounted
+    /// intrinsic value, where no volatility reproduces it.
     pub fn iv(&self, price: f64, k: f64, t: f64, option_type: TypeFlag) -> f64 {
+        let price = price * (self.r * t).exp();
+
         match option_type {
             TypeFlag::Call => bachelier::call_iv(price, self.f, k, t),
             TypeFlag::Put => bachelier::put_iv(price, self.f, k, t),
@@ -1354,6 +1502,247 @@ impl Sabr02 {
     }
 }
 
+mod cev {
+    use super::bsm;
+    use statrs::function::gamma::gamma_lr;
+
+    /// Non-central chi-squared CDF with `k` degrees of freedom and
+    /// non-centrality `lambda`, as a Poisson mixture of central chi-squared CDFs.
+    /// The series is summed outwards from the mode of the Poisson weights.
+    pub(crate) fn non_central_chi_squared_cdf(x: f64, k: f64, lambda: f64) -> f64 {
+        const TOLERANCE: f64 = 1e-16;
+
+        if x <= 0.0 {
+            return 0.0;
+        }
+
+        let half_x = 0.5 * x;
+        let half_lambda = 0.5 * lambda;
+
+        if half_lambda <= 0.0 {
+            return gamma_lr(0.5 * k, half_x);
+        }
+
+        let mode = half_lambda.floor();
+        let mode_weight = (-half_lambda + mode * half_lambda.ln()
+            - statrs::function::gamma::ln_gamma(mode + 1.0))
+        .exp();
+
+        let mut sum = mode_weight * gamma_lr(0.5 * k + mode, half_x);
+
+        // Forwards from the mode.
+        let mut j = mode;
+        let mut weight = mode_weight;
+        loop {
+            j += 1.0;
+            weight *= half_lambda / j;
+            if weight < TOLERANCE {
+                break;
+            }
+            sum += weight * gamma_lr(0.5 * k + j, half_x);
+        }
+
+        // Backwards from the mode.
+        let mut j = mode;
+        let mut weight = mode_weight;
+        while j > 0.0 {
+            weight *= j / half_lambda;
+            j -= 1.0;
+            if weight < TOLERANCE {
+                break;
+            }
+            sum += weight * gamma_lr(0.5 * k + j, half_x);
+        }
+
+        sum.clamp(0.0, 1.0)
+    }
+
+    /// Returns the `(call, put)` prices under the CEV model.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn prices(s: f64, k: f64, t: f64, r: f64, q: f64, v: f64, gamma: f64) -> (f64, f64) {
+        let b = r - q;
+
+        // Geometric Brownian Motion.
+        if (gamma - 1.0).abs() < f64::EPSILON {
+            return (
+                bsm::call_price(s, k, t, r, b, v),
+                bsm::put_price(s, k, t, r, b, v),
+            );
+        }
+
+        let one_minus_gamma = 1.0 - gamma;
+
+        let variance = if b.abs() < f64::EPSILON {
+            v * v * t
+        } else {
+            v * v / (2.0 * b * (gamma - 1.0)) * ((2.0 * b * (gamma - 1.0) * t).exp() - 1.0)
+        };
+
+        let scale = one_minus_gamma.powi(2) * variance;
+        let x_k = (k * (-b * t).exp()).powf(2.0 * one_minus_gamma) / scale;
+        let x_s = s.powf(2.0 * one_minus_gamma) / scale;
+        let nu = 1.0 / one_minus_gamma;
+
+        let s_q = s * (-q * t).exp();
+        let k_r = k * (-r * t).exp();
+
+        let (p_s, p_k) = if gamma < 1.0 {
+            (
+                non_central_chi_squared_cdf(x_k, nu + 2.0, x_s),
+                non_central_chi_squared_cdf(x_s, nu, x_k),
+            )
+        } else {
+            (
+                non_central_chi_squared_cdf(x_s, -nu, x_k),
+                non_central_chi_squared_cdf(x_k, 2.0 - nu, x_s),
+            )
+        };
+
+        let call = s_q * (1.0 - p_s) - k_r * p_k;
+        let put = k_r * (1.0 - p_k) - s_q * p_s;
+
+        (call, put)
+    }
+}
+
+impl Schroder89 {
+    /// Price a European option using the CEV model.
+    pub fn price(&self, k: f64, t: f64, option_type: TypeFlag) -> f64 {
+        let (call, put) = cev::prices(self.s, k, t, self.r, self.q, self.v, self.gamma);
+
+        match option_type {
+            TypeFlag::Call => call,
+            TypeFlag::Put => put,
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLIED VOLATILITY SURFACE
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Grid of total implied variance `w(K, T) = sigma(K, T)^2 T`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TotalVarianceSurface {
+    /// Strikes (columns of the grid).
+    pub strikes: Vec<f64>,
+
+    /// Strictly increasing maturities (rows of the grid).
+    pub maturities: Vec<f64>,
+
+    /// Total variance, indexed as `total_variance[maturity][strike]`.
+    pub total_variance: Vec<Vec<f64>>,
+}
+
+impl TotalVarianceSurface {
+    /// Create a new total variance surface.
+    ///
+    /// # Errors
+    /// - `RustQuantError::UnequalLength` if the grid does not match the
+    ///   number of maturities and strikes.
+    /// - `RustQuantError::InvalidArgument` if the maturities are not
+    ///   positive and strictly increasing.
+    pub fn new(
+        strikes: Vec<f64>,
+        maturities: Vec<f64>,
+        total_variance: Vec<Vec<f64>>,
+    ) -> Result<Self, RustQuantError> {
+        if total_variance.len() != maturities.len()
+            || total_variance.iter().any(|row| row.len() != strikes.len())
+        {
+            return Err(RustQuantError::UnequalLength);
+        }
+        if maturities.first().is_some_and(|t| *t <= 0.0)
+            || maturities.windows(2).any(|w| w[0] >= w[1])
+        {
+            return Err(RustQuantError::InvalidArgument(
+                "Maturities must be positive and strictly increasing.".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            strikes,
+            maturities,
+            total_variance,
+        })
+    }
+
+    /// Create a total variance surface from implied volatilities,
+    /// indexed as `volatilities[maturity][strike]`.
+    ///
+    /// # Errors
+    /// See [`TotalVarianceSurface::new`].
+    pub fn from_implied_volatilities(
+        strikes: Vec<f64>,
+        maturities: Vec<f64>,
+        volatilities: &[Vec<f64>],
+    ) -> Result<Self, RustQuantError> {
+        let total_variance = volatilities
+            .iter()
+            .zip(&maturities)
+            .map(|(row, t)| row.iter().map(|v| v * v * t).collect())
+            .collect();
+
+        Self::new(strikes, maturities, total_variance)
+    }
+
+    /// Implied volatility at the given maturity and strike indices.
+    pub fn implied_volatility(&self, maturity: usize, strike: usize) -> f64 {
+        (self.total_variance[maturity][strike] / self.maturities[maturity]).sqrt()
+    }
+}
+
+/// Remove calendar-spread arbitrage from a total variance surface.
+///
+/// At each strike, total variance is replaced by its least-squares
+/// projection onto the non-decreasing sequences in maturity
+/// (isotonic regression, by pooling adjacent violators).
+/// Points that are already monotone and not part of a violation
+/// are left unchanged.
+pub fn enforce_calendar_arbitrage(surface: &mut TotalVarianceSurface) {
+    for strike in 0..surface.strikes.len() {
+        let column: Vec<f64> = surface
+            .total_variance
+            .iter()
+            .map(|row| row[strike])
+            .collect();
+
+        for (row, w) in surface
+            .total_variance
+            .iter_mut()
+            .zip(isotonic_regression(&column))
+        {
+            row[strike] = w;
+        }
+    }
+}
+
+/// Pool adjacent violators: the non-decreasing sequence closest to `values`.
+fn isotonic_regression(values: &[f64]) -> Vec<f64> {
+    // Blocks of pooled values, as (mean, count).
+    let mut blocks: Vec<(f64, usize)> = Vec::with_capacity(values.len());
+
+    for &value in values {
+        let (mut mean, mut count) = (value, 1);
+
+        while let Some(&(previous, n)) = blocks.last() {
+            if previous <= mean {
+                break;
+            }
+            blocks.pop();
+            mean = (previous * n as f64 + mean * count as f64) / (n + count) as f64;
+            count += n;
+        }
+
+        blocks.push((mean, count));
+    }
+
+    blocks
+        .into_iter()
+        .flat_map(|(mean, count)| std::iter::repeat_n(mean, count))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests_sabr {
     use super::*;
@@ -1393,3 +1782,476 @@ mod tests_sabr {
         assert!((sabr.nu - 0.4).abs() < 1e-10);
     }
 }
+
+#[cfg(test)]
+mod tests_heston {
+    use super::*;
+    use RustQuant_utils::{assert_approx_equal, RUSTQUANT_EPSILON};
+
+    // Reference prices from the same inversion in 30 digit arithmetic
+    // with adaptive quadrature (mpmath).
+    #[test]
+    fn test_heston_price() {
+        let model = Heston93::new(100.0, 0.05, 0.03, 0.0, -0.8, 5.0, 0.05, 0.5);
+
+        assert_approx_equal!(
+            model.price(100.0, 0.5, TypeFlag::Call),
+            6.867_668_879_440_837,
+            1e-9
+        );
+        assert_approx_equal!(
+            model.price(90.0, 0.5, TypeFlag::Call),
+            13.588_518_088_725_404,
+            1e-9
+        );
+
+        // Short maturities have slowly decaying integrands.
+        let model = Heston93::new(100.0, 0.04, 0.02, 0.0, -0.7, 2.0, 0.04, 0.5);
+        assert_approx_equal!(
+            model.price(100.0, 1.0 / 52.0, TypeFlag::Call),
+            1.120_785_882_407_790_5,
+            1e-9
+        );
+    }
+
+    #[test]
+    fn test_heston_long_maturity() {
+        // Heston's original formulation jumps branches of the complex
+        // logarithm here, and misprices the option.
+        let model = Heston93::new(100.0, 0.04, 0.02, 0.01, -0.7, 0.5, 0.04, 1.0);
+        let t = 30.0;
+
+        assert_approx_equal!(
+            model.price(100.0, t, TypeFlag::Call),
+            30.859_853_897_852_33,
+            1e-8
+        );
+        assert_approx_equal!(
+            model.price(150.0, t, TypeFlag::Call),
+            16.345_166_438_482_7,
+            1e-8
+        );
+
+        let call = model.price(150.0, t, TypeFlag::Call);
+        let put = model.price(150.0, t, TypeFlag::Put);
+        assert_approx_equal!(
+            call - put,
+            100.0 * (-0.01 * t).exp() - 150.0 * (-0.02 * t).exp(),
+            RUSTQUANT_EPSILON
+        );
+    }
+
+    #[test]
+    fn test_heston_limits() {
+        // Without vol of vol, it is Black-Scholes with the integrated variance.
+        let (v, kappa, theta, t) = (0.09, 2.0, 0.04, 1.0_f64);
+        let variance = theta + (v - theta) * (1.0 - (-kappa * t).exp()) / (kappa * t);
+
+        let model = Heston93::new(100.0, v, 0.03, 0.01, 0.0, kappa, theta, 1e-4);
+        let bsm = Merton73::new(100.0, 0.03, 0.01, variance.sqrt());
+
+        for k in [80.0, 100.0, 120.0] {
+            assert_approx_equal!(
+                model.price(k, t, TypeFlag::Call),
+                bsm.price(k, t, TypeFlag::Call),
+                1e-4
+            );
+        }
+
+        // The characteristic function is that of a distribution with
+        // E[S_t] = S_0 exp((r - q) t).
+        let model = Heston93::new(100.0, 0.05, 0.03, 0.01, -0.8, 5.0, 0.05, 0.5);
+        assert_approx_equal!(
+            model.characteristic_function(Complex::new(0.0, 0.0), t).re,
+            1.0,
+            RUSTQUANT_EPSILON
+        );
+        assert_approx_equal!(
+            model.characteristic_function(Complex::new(0.0, -1.0), t).re,
+            100.0 * (0.02 * t).exp(),
+            1e-9
+        );
+
+        // Gamma is the derivative of delta.
+        let h = 1e-3;
+        let bumped = |s: f64| {
+            Heston93::new(s, 0.05, 0.03, 0.01, -0.8, 5.0, 0.05, 0.5).delta(100.0, t, TypeFlag::Call)
+        };
+        assert_approx_equal!(
+            model.gamma(100.0, t, TypeFlag::Call),
+            (bumped(100.0 + h) - bumped(100.0 - h)) / (2.0 * h),
+            1e-6
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests_cev {
+    use super::*;
+    use crate::implied_volatility;
+    use RustQuant_utils::{assert_approx_equal, RUSTQUANT_EPSILON};
+
+    #[test]
+    fn test_non_central_chi_squared_cdf() {
+        // Reference values from numerically integrating the density.
+        assert_approx_equal!(
+            cev::non_central_chi_squared_cdf(2.0, 3.0, 1.5),
+            0.261_053_202_105_698,
+            1e-10
+        );
+        assert_approx_equal!(
+            cev::non_central_chi_squared_cdf(10.0, 4.5, 6.0),
+            0.529_658_303_248_596,
+            1e-10
+        );
+    }
+
+    #[test]
+    fn test_cev_unit_elasticity_is_black_scholes() {
+        let cev = Schroder89::new(100.0, 0.05, 0.0, 0.2, 1.0);
+        let bs = BlackScholes73::new(100.0, 0.05, 0.2);
+
+        for k in [80.0, 100.0, 120.0] {
+            for flag in [TypeFlag::Call, TypeFlag::Put] {
+                assert_approx_equal!(
+                    cev.price(k, 1.0, flag),
+                    bs.price(k, 1.0, flag),
+                    RUSTQUANT_EPSILON
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_cev_put_call_parity() {
+        for gamma in [0.0, 0.5, 1.5] {
+            let sigma = 0.2 * 100_f64.powf(1.0 - gamma);
+            let cev = Schroder89::new(100.0, 0.05, 0.02, sigma, gamma);
+
+            let call = cev.price(95.0, 0.75, TypeFlag::Call);
+            let put = cev.price(95.0, 0.75, TypeFlag::Put);
+
+            assert_approx_equal!(
+                call - put,
+                100.0 * (-0.02_f64 * 0.75).exp() - 95.0 * (-0.05_f64 * 0.75).exp(),
+                1e-8
+            );
+        }
+    }
+
+    #[test]
+    fn test_cev_implied_volatility_skew() {
+        // Scale sigma so that the at-the-money volatility is roughly 20%.
+        let gamma = 0.5;
+        let cev = Schroder89::new(100.0, 0.05, 0.0, 0.2 * 100_f64.powf(1.0 - gamma), gamma);
+
+        let vols: Vec<f64> = [80.0, 90.0, 100.0, 110.0, 120.0]
+            .iter()
+            .map(|&k| {
+                let price = cev.price(k, 1.0, TypeFlag::Call);
+                implied_volatility(price, 100.0, k, 1.0, 0.05, TypeFlag::Call)
+            })
+            .collect();
+
+        assert!(vols.windows(2).all(|w| w[0] > w[1]));
+        assert_approx_equal!(vols[2], 0.2, 0.01);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_cev_negative_elasticity() {
+        Schroder89::new(100.0, 0.05, 0.0, 0.2, -0.5);
+    }
+}
+
+#[cfg(test)]
+mod tests_bsm_limits {
+    use super::*;
+    use RustQuant_utils::{assert_approx_equal, RUSTQUANT_EPSILON};
+
+    #[test]
+    fn test_negative_rate_put_call_parity() {
+        let (s, k, t, r, v) = (100.0, 105.0, 0.75, -0.01, 0.25);
+        let model = BlackScholes73::new(s, r, v);
+
+        let call = model.price(k, t, TypeFlag::Call);
+        let put = model.price(k, t, TypeFlag::Put);
+
+        assert!(call.is_finite() && call > 0.0);
+        assert_approx_equal!(call - put, s - k * (-r * t).exp(), RUSTQUANT_EPSILON);
+    }
+
+    #[test]
+    fn test_zero_maturity_is_intrinsic() {
+        let model = Merton73::new(100.0, -0.01, 0.02, 0.2);
+
+        for k in [90.0, 100.0, 110.0] {
+            assert_approx_equal!(
+                model.price(k, 0.0, TypeFlag::Call),
+                (100.0 - k).max(0.0),
+                RUSTQUANT_EPSILON
+            );
+            assert_approx_equal!(
+                model.price(k, 0.0, TypeFlag::Put),
+                (k - 100.0).max(0.0),
+                RUSTQUANT_EPSILON
+            );
+
+            assert_eq!(model.gamma(k, 0.0, TypeFlag::Call), 0.0);
+            assert_eq!(model.vega(k, 0.0, TypeFlag::Call), 0.0);
+            assert!(model.theta(k, 0.0, TypeFlag::Call).is_finite());
+        }
+    }
+
+    #[test]
+    fn test_zero_volatility_is_discounted_forward_intrinsic() {
+        let (s, t, r, q) = (100.0, 1.0, 0.03, 0.01);
+        let model = Merton73::new(s, r, q, 0.0);
+        let forward = s * ((r - q) * t).exp();
+
+        for k in [90.0, 110.0] {
+            assert_approx_equal!(
+                model.price(k, t, TypeFlag::Call),
+                (-r * t).exp() * (forward - k).max(0.0),
+                RUSTQUANT_EPSILON
+            );
+            assert_approx_equal!(
+                model.price(k, t, TypeFlag::Put),
+                (-r * t).exp() * (k - forward).max(0.0),
+                RUSTQUANT_EPSILON
+            );
+        }
+    }
+
+    #[test]
+    fn test_non_positive_strike() {
+        let model = BlackScholes73::new(100.0, 0.05, 0.2);
+
+        for k in [0.0, -1.0, f64::NAN] {
+            assert!(matches!(
+                model.try_price(k, 1.0, TypeFlag::Call),
+                Err(RustQuantError::InvalidArgument(_))
+            ));
+        }
+        assert!(matches!(
+            model.try_price(100.0, -1.0, TypeFlag::Put),
+            Err(RustQuantError::InvalidArgument(_))
+        ));
+        assert_approx_equal!(
+            model.try_price(100.0, 1.0, TypeFlag::Call).unwrap(),
+            model.price(100.0, 1.0, TypeFlag::Call),
+            RUSTQUANT_EPSILON
+        );
+
+        // Unchecked, a zero strike gives the zero-strike limit and a
+        // negative strike NaN.
+        assert!(model.validate_inputs(0.0, 1.0).is_err());
+        assert_approx_equal!(
+            model.price(0.0, 1.0, TypeFlag::Call),
+            100.0,
+            RUSTQUANT_EPSILON
+        );
+        assert_eq!(model.price(0.0, 1.0, TypeFlag::Put), 0.0);
+        assert!(model.price(-1.0, 1.0, TypeFlag::Call).is_nan());
+
+        let merton = Merton73::new(100.0, 0.05, 0.02, 0.2);
+        assert_approx_equal!(
+            merton.price(0.0, 1.0, TypeFlag::Call),
+            100.0 * f64::exp(-0.02),
+            RUSTQUANT_EPSILON
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests_bachelier {
+    use super::*;
+    use RustQuant_utils::{assert_approx_equal, RUSTQUANT_EPSILON};
+
+    // Reference prices from the closed form in 30 digit arithmetic (mpmath).
+    #[test]
+    fn test_bachelier_price() {
+        let model = Bachelier::new(0.02, 0.03, 0.01);
+        assert_approx_equal!(
+            model.price(0.025, 1.0, TypeFlag::Call),
+            0.001_919_507_856_813_685_5,
+            1e-15
+        );
+
+        // Negative forward, strike and rate.
+        let model = Bachelier::new(-0.002, -0.004, 0.006);
+        assert_approx_equal!(
+            model.price(-0.005, 0.5, TypeFlag::Call),
+            0.003_606_128_735_141_318,
+            1e-15
+        );
+    }
+
+    #[test]
+    fn test_bachelier_put_call_parity() {
+        let (f, k, t, r) = (-0.001, 0.004, 2.0, 0.02);
+        let model = Bachelier::new(f, r, 0.008);
+
+        assert_approx_equal!(
+            model.price(k, t, TypeFlag::Call) - model.price(k, t, TypeFlag::Put),
+            (-r * t).exp() * (f - k),
+            RUSTQUANT_EPSILON
+        );
+    }
+
+    #[test]
+    fn test_implied_normal_volatility_round_trip() {
+        for (f, r, v) in [(0.02, 0.03, 0.01), (-0.003, -0.005, 0.004)] {
+            // Up to three standard deviations in or out of the money.
+            for t in [0.1, 1.0, 10.0] {
+                for moneyness in [-3.0, -1.0, 0.0, 0.5, 3.0] {
+                    let k = f + moneyness * v * f64::sqrt(t);
+
+                    for flag in [TypeFlag::Call, TypeFlag::Put] {
+                        let model = Bachelier::new(f, r, v);
+                        let price = model.price(k, t, flag);
+
+                        assert_approx_equal!(model.iv(price, k, t, flag) / v, 1.0, 1e-9);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_atm_price_and_vol() {
+        let model = Bachelier::new(0.01, 0.05, 0.007);
+        let price = model.price(0.01, 2.0, TypeFlag::Call);
+
+        assert_approx_equal!(model.atm_price(2.0), price, RUSTQUANT_EPSILON);
+        assert_approx_equal!(model.atm_vol(price, 2.0), 0.007, RUSTQUANT_EPSILON);
+    }
+}
+
+#[cfg(test)]
+mod tests_black76 {
+    use super::*;
+    use RustQuant_utils::{assert_approx_equal, RUSTQUANT_EPSILON};
+
+    #[test]
+    fn test_black76_price() {
+        // Haug (2007), section 1.1.3: F = 19, K = 19, T = 0.75, r = 0.1,
+        // v = 0.28. At the money, the call and put are both 1.7011.
+        let model = Black76::new(19.0, 0.1, 0.28);
+
+        assert_approx_equal!(model.price(19.0, 0.75, TypeFlag::Call), 1.7011, 1e-4);
+        assert_approx_equal!(model.price(19.0, 0.75, TypeFlag::Put), 1.7011, 1e-4);
+    }
+
+    #[test]
+    fn test_black76_put_call_parity() {
+        let (f, k, t, r) = (100.0, 110.0, 0.5, 0.04);
+        let model = Black76::new(f, r, 0.3);
+
+        assert_approx_equal!(
+            model.price(k, t, TypeFlag::Call) - model.price(k, t, TypeFlag::Put),
+            (-r * t).exp() * (f - k),
+            RUSTQUANT_EPSILON
+        );
+        assert_approx_equal!(
+            model.delta(k, t, TypeFlag::Call) - model.delta(k, t, TypeFlag::Put),
+            (-r * t).exp(),
+            RUSTQUANT_EPSILON
+        );
+    }
+
+    #[test]
+    fn test_rho_by_rate_exposure() {
+        let (k, t, h) = (105.0, 0.75, 1e-6);
+
+        for flag in [TypeFlag::Call, TypeFlag::Put] {
+            // The futures price does not move with the rate.
+            let black = |r: f64| Black76::new(100.0, r, 0.25).price(k, t, flag);
+            assert_approx_equal!(
+                Black76::new(100.0, 0.05, 0.25).rho(k, t, flag),
+                (black(0.05 + h) - black(0.05 - h)) / (2.0 * h),
+                1e-6
+            );
+
+            // The spot drifts at the rate less the dividend yield.
+            let merton = |r: f64| Merton73::new(100.0, r, 0.02, 0.25).price(k, t, flag);
+            assert_approx_equal!(
+                Merton73::new(100.0, 0.05, 0.02, 0.25).rho(k, t, flag),
+                (merton(0.05 + h) - merton(0.05 - h)) / (2.0 * h),
+                1e-6
+            );
+
+            assert_eq!(Asay82::new(100.0, 0.25).rho(k, t, flag), 0.0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests_calendar_arbitrage {
+    use super::*;
+    use RustQuant_utils::{assert_approx_equal, RUSTQUANT_EPSILON};
+
+    #[test]
+    fn test_enforce_calendar_arbitrage() {
+        let original = vec![
+            vec![0.040, 0.030, 0.035],
+            vec![0.080, 0.065, 0.070],
+            vec![0.120, 0.055, 0.105],
+            vec![0.160, 0.130, 0.140],
+        ];
+        let mut surface = TotalVarianceSurface::new(
+            vec![90.0, 100.0, 110.0],
+            vec![0.5, 1.0, 1.5, 2.0],
+            original.clone(),
+        )
+        .unwrap();
+
+        enforce_calendar_arbitrage(&mut surface);
+
+        for strike in 0..3 {
+            assert!(surface
+                .total_variance
+                .windows(2)
+                .all(|w| w[0][strike] <= w[1][strike]));
+        }
+
+        // The violating pair at the middle strike is pooled to its mean.
+        assert_approx_equal!(surface.total_variance[1][1], 0.06, RUSTQUANT_EPSILON);
+        assert_approx_equal!(surface.total_variance[2][1], 0.06, RUSTQUANT_EPSILON);
+
+        // Every other point is already compliant and left untouched.
+        for (maturity, row) in original.iter().enumerate() {
+            for (strike, w) in row.iter().enumerate() {
+                if strike != 1 || maturity == 0 || maturity == 3 {
+                    assert_eq!(surface.total_variance[maturity][strike], *w);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_isotonic_regression_pools_long_violations() {
+        let fitted = isotonic_regression(&[1.0, 3.0, 2.0, 1.0, 5.0]);
+
+        assert_eq!(fitted, vec![1.0, 2.0, 2.0, 2.0, 5.0]);
+    }
+
+    #[test]
+    fn test_total_variance_surface_from_volatilities() {
+        let surface = TotalVarianceSurface::from_implied_volatilities(
+            vec![100.0],
+            vec![0.5, 2.0],
+            &[vec![0.2], vec![0.25]],
+        )
+        .unwrap();
+
+        assert_approx_equal!(surface.total_variance[1][0], 0.125, RUSTQUANT_EPSILON);
+        assert_approx_equal!(surface.implied_volatility(0, 0), 0.2, RUSTQUANT_EPSILON);
+
+        assert!(
+            TotalVarianceSurface::new(vec![100.0], vec![1.0, 0.5], vec![vec![0.1], vec![0.2]])
+                .is_err()
+        );
+        assert!(TotalVarianceSurface::new(vec![100.0, 110.0], vec![1.0], vec![vec![0.1]]).is_err());
+    }
+}
@@ -0,0 +1,276 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Parisian barrier options (Chesney, Jeanblanc-Picqué and Yor, 1997).
+//!
+//! A Parisian option is only knocked in or out once the underlying has
+//! spent a given time beyond the barrier: either in one excursion
+//! (consecutive), or over all of them (cumulative, sometimes called
+//! ParAsian). A brief spike through the barrier does not trigger it,
+//! which makes the option harder to manipulate than a standard barrier.
+//!
+//! The options are priced by Monte Carlo, tracking the occupation time
+//! beyond the barrier along each simulated path. Each simulated value
+//! beyond the barrier counts for the time step that ends at it.
+
+use super::{BarrierType, MonteCarloEstimate, OptionContract, ParisianStyle, TypeFlag};
+use RustQuant_stochastics::{StochasticProcess, StochasticProcessConfig};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Parisian barrier option.
+#[derive(Debug, Clone)]
+pub struct ParisianOption {
+    /// The option contract.
+    pub contract: OptionContract,
+
+    /// Barrier type (up-and-out, down-and-out, up-and-in, down-and-in).
+    pub barrier_type: BarrierType,
+
+    /// Barrier level.
+    pub barrier: f64,
+
+    /// Time (in years) beyond the barrier that triggers the knock.
+    pub window: f64,
+
+    /// Consecutive or cumulative time beyond the barrier.
+    pub style: ParisianStyle,
+
+    /// Strike price of the option.
+    pub strike: f64,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl ParisianOption {
+    /// Create a new Parisian option.
+    ///
+    /// # Panics
+    /// Panics if `window` is negative.
+    pub fn new(
+        contract: OptionContract,
+        barrier_type: BarrierType,
+        barrier: f64,
+        window: f64,
+        style: ParisianStyle,
+        strike: f64,
+    ) -> Self {
+        assert!(window >= 0.0, "The Parisian window must be non-negative.");
+
+        Self {
+            contract,
+            barrier_type,
+            barrier,
+            window,
+            style,
+            strike,
+        }
+    }
+
+    /// Whether the path spends the window beyond the barrier, given the
+    /// simulated values `path` at `times`.
+    pub fn is_triggered(&self, path: &[f64], times: &[f64]) -> bool {
+        let beyond = |x: f64| match self.barrier_type {
+            BarrierType::UpAndOut | BarrierType::UpAndIn => x >= self.barrier,
+            BarrierType::DownAndOut | BarrierType::DownAndIn => x <= self.barrier,
+        };
+
+        // The initial value counts as an instant beyond the barrier, so a
+        // zero window is a standard (discretely monitored) barrier.
+        if self.window == 0.0 && beyond(path[0]) {
+            return true;
+        }
+
+        let mut occupation = 0.0;
+
+        for (s, t) in path[1..].iter().zip(times.windows(2)) {
+            if beyond(*s) {
+                occupation += t[1] - t[0];
+
+                if occupation >= self.window {
+                    return true;
+                }
+            } else if let ParisianStyle::Consecutive = self.style {
+                occupation = 0.0;
+            }
+        }
+
+        false
+    }
+
+    /// Monte Carlo price of the option, tracking the time each simulated
+    /// path spends beyond the barrier.
+    ///
+    /// # Arguments:
+    /// * `process` - The process driving the underlying (with risk-neutral drift).
+    /// * `config` - The simulation configuration.
+    /// * `rate` - Continuously compounded discount rate.
+    ///
+    /// # Panics
+    /// Panics if `config.m_paths < 2`.
+    pub fn price_monte_carlo_occupation<P>(
+        &self,
+        process: &P,
+        config: &StochasticProcessConfig,
+        rate: f64,
+    ) -> MonteCarloEstimate
+    where
+        P: StochasticProcess,
+    {
+        let trajectories = process.euler_maruyama(config);
+        let discount_factor = (-rate * (config.t_n - config.t_0)).exp();
+
+        let knock_in = matches!(
+            self.barrier_type,
+            BarrierType::UpAndIn | BarrierType::DownAndIn
+        );
+
+        let discounted: Vec<f64> = trajectories
+            .paths
+            .iter()
+            .map(|path| {
+                let terminal = path[path.len() - 1];
+                let payoff = match self.contract.type_flag {
+                    TypeFlag::Call => (terminal - self.strike).max(0.0),
+                    TypeFlag::Put => (self.strike - terminal).max(0.0),
+                };
+
+                match self.is_triggered(path, &trajectories.times) == knock_in {
+                    true => discount_factor * payoff,
+                    false => 0.0,
+                }
+            })
+            .collect();
+
+        MonteCarloEstimate::from_samples(&discounted)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_parisian {
+    use super::*;
+    use crate::{
+        BarrierOption, ExerciseFlag, MonteCarloEngine, MonteCarloPricer, OptionContractBuilder,
+        PutPayoff,
+    };
+    use time::macros::date;
+    use RustQuant_stochastics::GeometricBrownianMotion;
+    use RustQuant_utils::assert_approx_equal;
+
+    const S: f64 = 100.0;
+    const K: f64 = 100.0;
+    const B: f64 = 90.0;
+    const R: f64 = 0.05;
+    const V: f64 = 0.25;
+    const T: f64 = 1.0;
+
+    fn contract() -> OptionContract {
+        OptionContractBuilder::default()
+            .type_flag(TypeFlag::Put)
+            .exercise_flag(ExerciseFlag::European {
+                expiry: date!(2025 - 01 - 01),
+            })
+            .build()
+            .unwrap()
+    }
+
+    fn put(barrier_type: BarrierType, window: f64, style: ParisianStyle) -> ParisianOption {
+        ParisianOption::new(contract(), barrier_type, B, window, style, K)
+    }
+
+    fn config() -> StochasticProcessConfig {
+        StochasticProcessConfig::new(S, 0.0, T, 250, 20_000, true).with_seed(17)
+    }
+
+    #[test]
+    fn test_occupation_time() {
+        let times = [0.0, 0.1, 0.2, 0.3, 0.4, 0.5];
+        let path = [100.0, 89.0, 88.0, 95.0, 85.0, 92.0];
+
+        // Two excursions of 0.2 and 0.1 years.
+        let consecutive = put(BarrierType::DownAndOut, 0.25, ParisianStyle::Consecutive);
+        let cumulative = put(BarrierType::DownAndOut, 0.25, ParisianStyle::Cumulative);
+
+        assert!(!consecutive.is_triggered(&path, &times));
+        assert!(cumulative.is_triggered(&path, &times));
+        assert!(
+            put(BarrierType::DownAndOut, 0.2, ParisianStyle::Consecutive)
+                .is_triggered(&path, &times)
+        );
+    }
+
+    #[test]
+    fn test_limits() {
+        let gbm = GeometricBrownianMotion::new(R, V);
+
+        // A zero window is a standard barrier, monitored at the same times.
+        let parisian = put(BarrierType::DownAndOut, 0.0, ParisianStyle::Consecutive)
+            .price_monte_carlo_occupation(&gbm, &config(), R);
+        let barrier = BarrierOption {
+            contract: contract(),
+            barrier_type: BarrierType::DownAndOut,
+            barrier: B,
+            strike: K,
+            rebate: None,
+        }
+        .price_monte_carlo(&gbm, &config(), R);
+
+        assert_approx_equal!(parisian.price, barrier, 1e-10);
+
+        // A window longer than the option's life never knocks.
+        let never = put(BarrierType::DownAndOut, 2.0, ParisianStyle::Cumulative)
+            .price_monte_carlo_occupation(&gbm, &config(), R);
+        let vanilla = MonteCarloEngine::new(gbm, S, R, T, 20_000, 250)
+            .with_seed(17)
+            .price(&PutPayoff::new(K));
+
+        assert_approx_equal!(never.price, vanilla.price, 1e-10);
+    }
+
+    #[test]
+    fn test_in_out_parity_and_ordering() {
+        let gbm = GeometricBrownianMotion::new(R, V);
+        let window = 0.1;
+
+        let price = |barrier_type, style| {
+            put(barrier_type, window, style)
+                .price_monte_carlo_occupation(&gbm, &config(), R)
+                .price
+        };
+
+        let vanilla = price(BarrierType::DownAndOut, ParisianStyle::Consecutive)
+            + price(BarrierType::DownAndIn, ParisianStyle::Consecutive);
+        let standard = put(BarrierType::DownAndOut, 0.0, ParisianStyle::Consecutive)
+            .price_monte_carlo_occupation(&gbm, &config(), R)
+            .price;
+
+        assert_approx_equal!(
+            price(BarrierType::DownAndOut, ParisianStyle::Cumulative)
+                + price(BarrierType::DownAndIn, ParisianStyle::Cumulative),
+            vanilla,
+            1e-10
+        );
+
+        // Cumulative time beyond the barrier is at least the longest
+        // excursion, so it knocks out more often; both knock out less
+        // often than the standard barrier.
+        let consecutive = price(BarrierType::DownAndOut, ParisianStyle::Consecutive);
+        let cumulative = price(BarrierType::DownAndOut, ParisianStyle::Cumulative);
+
+        assert!(standard < cumulative && cumulative < consecutive && consecutive < vanilla);
+    }
+}
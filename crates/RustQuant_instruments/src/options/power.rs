@@ -7,8 +7,9 @@
 //      - LICENSE-MIT.md
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-use super::{OptionContract, TypeFlag};
+use super::{Greeks, OptionContract, TypeFlag};
 use crate::Payoff;
+use RustQuant_math::{gaussian::N, Distribution};
 
 /// Power Option.
 /// Not to be confused with the [PoweredOption].
@@ -124,6 +125,83 @@ impl PowerOption {
             power,
         }
     }
+
+    /// Price the option under the Black-Scholes-Merton model.
+    ///
+    /// $S_T^p$ is lognormal with volatility $|p| \sigma$, so the option is
+    /// priced with the Black (1976) formula on the power forward
+    /// $F_p = S^p e^{(p (b - \sigma^2 / 2) + p^2 \sigma^2 / 2) T}$, where $b = r - q$.
+    ///
+    /// # Arguments:
+    /// * `s` - Underlying price.
+    /// * `t` - Time to expiry (in years).
+    /// * `r` - Risk-free rate.
+    /// * `q` - Dividend yield.
+    /// * `v` - Volatility.
+    ///
+    /// # Panics
+    /// Panics if the power is zero.
+    pub fn price_closed_form(&self, s: f64, t: f64, r: f64, q: f64, v: f64) -> f64 {
+        let (f, d1, d2) = self.forward_d1_d2(s, t, r, q, v);
+        let phi = self.phi();
+
+        (-r * t).exp() * phi * (f * N.cdf(phi * d1) - self.strike * N.cdf(phi * d2))
+    }
+
+    /// Greeks of the option under the Black-Scholes-Merton model.
+    ///
+    /// Theta is the sensitivity to the passage of time, $-\partial V / \partial t$,
+    /// and rho is the sensitivity to the risk-free rate with the dividend
+    /// yield held fixed.
+    ///
+    /// # Panics
+    /// Panics if the power is zero.
+    pub fn greeks_closed_form(&self, s: f64, t: f64, r: f64, q: f64, v: f64) -> Greeks {
+        let (f, d1, _) = self.forward_d1_d2(s, t, r, q, v);
+        let (p, phi) = (self.power, self.phi());
+        let discount = (-r * t).exp();
+        let price = self.price_closed_form(s, t, r, q, v);
+
+        // Black (1976) sensitivities to the power forward and its volatility.
+        let vol = p.abs() * v;
+        let delta_f = discount * phi * N.cdf(phi * d1);
+        let gamma_f = discount * N.pdf(d1) / (f * vol * t.sqrt());
+        let vega_f = discount * f * N.pdf(d1) * t.sqrt();
+
+        // Sensitivities of the power forward.
+        let f_s = p * f / s;
+        let f_ss = p * (p - 1.0) * f / (s * s);
+        let f_v = p * (p - 1.0) * v * t * f;
+        let f_t = (p * (r - q - 0.5 * v * v) + 0.5 * p * p * v * v) * f;
+
+        Greeks {
+            delta: delta_f * f_s,
+            gamma: gamma_f * f_s * f_s + delta_f * f_ss,
+            vega: vega_f * p.abs() + delta_f * f_v,
+            theta: r * price - delta_f * f_t - vega_f * vol / (2.0 * t),
+            rho: -t * price + delta_f * p * t * f,
+        }
+    }
+
+    fn phi(&self) -> f64 {
+        match self.contract.type_flag {
+            TypeFlag::Call => 1.0,
+            TypeFlag::Put => -1.0,
+        }
+    }
+
+    /// The power forward $F_p$, and $d_1$, $d_2$ of the Black (1976) formula.
+    fn forward_d1_d2(&self, s: f64, t: f64, r: f64, q: f64, v: f64) -> (f64, f64, f64) {
+        let p = self.power;
+
+        assert!(p != 0.0, "The power must be non-zero.");
+
+        let f = s.powf(p) * ((p * (r - q - 0.5 * v * v) + 0.5 * p * p * v * v) * t).exp();
+        let vol = p.abs() * v * t.sqrt();
+        let d1 = ((f / self.strike).ln() + 0.5 * vol * vol) / vol;
+
+        (f, d1, d1 - vol)
+    }
 }
 
 impl PoweredOption {
@@ -147,6 +225,30 @@ impl CappedPowerOption {
             cap,
         }
     }
+
+    /// Price the option under the Black-Scholes-Merton model, as a spread
+    /// of power options struck at $K$ and $K \pm$ cap.
+    ///
+    /// # Arguments:
+    /// * `s` - Underlying price.
+    /// * `t` - Time to expiry (in years).
+    /// * `r` - Risk-free rate.
+    /// * `q` - Dividend yield.
+    /// * `v` - Volatility.
+    pub fn price_closed_form(&self, s: f64, t: f64, r: f64, q: f64, v: f64) -> f64 {
+        let option = |strike: f64| {
+            PowerOption::new(self.contract.clone(), strike, self.power)
+                .price_closed_form(s, t, r, q, v)
+        };
+
+        match self.contract.type_flag {
+            TypeFlag::Call => option(self.strike) - option(self.strike + self.cap),
+            TypeFlag::Put => match self.strike > self.cap {
+                true => option(self.strike) - option(self.strike - self.cap),
+                false => option(self.strike),
+            },
+        }
+    }
 }
 
 impl PowerContract {
@@ -154,4 +256,167 @@ impl PowerContract {
     pub fn new(strike: f64, power: f64) -> Self {
         Self { strike, power }
     }
+
+    /// Price the contract under the Black-Scholes-Merton model,
+    /// $e^{-r T} E[(S_T / K)^p]$.
+    ///
+    /// # Arguments:
+    /// * `s` - Underlying price.
+    /// * `t` - Time to expiry (in years).
+    /// * `r` - Risk-free rate.
+    /// * `q` - Dividend yield.
+    /// * `v` - Volatility.
+    pub fn price_closed_form(&self, s: f64, t: f64, r: f64, q: f64, v: f64) -> f64 {
+        let p = self.power;
+
+        (s / self.strike).powf(p)
+            * ((p * (r - q - 0.5 * v * v) + 0.5 * p * p * v * v - r) * t).exp()
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_power {
+    use super::*;
+    use crate::{ExerciseFlag, GeneralisedBlackScholesMerton, Merton73, OptionContractBuilder};
+    use time::macros::date;
+    use RustQuant_math::gauss_legendre;
+    use RustQuant_utils::assert_approx_equal;
+
+    fn contract(type_flag: TypeFlag) -> OptionContract {
+        OptionContractBuilder::default()
+            .type_flag(type_flag)
+            .exercise_flag(ExerciseFlag::European {
+                expiry: date!(2025 - 01 - 01),
+            })
+            .build()
+            .unwrap()
+    }
+
+    /// Discounted expectation of a payoff of the terminal price, by quadrature
+    /// over the standard normal, split at the payoff's kinks (in price).
+    #[allow(clippy::too_many_arguments)]
+    fn expectation<F: Fn(f64) -> f64>(
+        payoff: F,
+        kinks: &[f64],
+        s: f64,
+        t: f64,
+        r: f64,
+        q: f64,
+        v: f64,
+    ) -> f64 {
+        let density = |z: f64| (-0.5 * z * z).exp() / (2.0 * std::f64::consts::PI).sqrt();
+        let terminal = |z: f64| s * ((r - q - 0.5 * v * v) * t + v * t.sqrt() * z).exp();
+
+        let mut points: Vec<f64> = (-10..=10).map(f64::from).collect();
+        points.extend(
+            kinks
+                .iter()
+                .map(|k| ((k / s).ln() - (r - q - 0.5 * v * v) * t) / (v * t.sqrt())),
+        );
+        points.sort_by(f64::total_cmp);
+
+        (-r * t).exp()
+            * points
+                .windows(2)
+                .map(|w| gauss_legendre(|z| payoff(terminal(z)) * density(z), w[0], w[1], 64))
+                .sum::<f64>()
+    }
+
+    #[test]
+    fn test_power_one_is_vanilla() {
+        let (s, k, t, r, q, v) = (100.0, 95.0, 0.75, 0.05, 0.02, 0.25);
+        let model = Merton73::new(s, r, q, v);
+
+        for type_flag in [TypeFlag::Call, TypeFlag::Put] {
+            let option = PowerOption::new(contract(type_flag), k, 1.0);
+            let greeks = option.greeks_closed_form(s, t, r, q, v);
+
+            assert_approx_equal!(
+                option.price_closed_form(s, t, r, q, v),
+                model.price(k, t, type_flag),
+                1e-12
+            );
+            assert_approx_equal!(greeks.delta, model.delta(k, t, type_flag), 1e-12);
+            assert_approx_equal!(greeks.gamma, model.gamma(k, t, type_flag), 1e-12);
+            assert_approx_equal!(greeks.vega, model.vega(k, t, type_flag), 1e-10);
+            assert_approx_equal!(greeks.theta, model.theta(k, t, type_flag), 1e-10);
+            assert_approx_equal!(greeks.rho, model.rho(k, t, type_flag), 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_power_option_against_quadrature() {
+        let (s, t, r, q, v) = (10.0, 0.5, 0.08, 0.02, 0.3);
+
+        for (k, power) in [(100.0, 2.0), (0.1, -1.0), (3.0, 0.5)] {
+            for type_flag in [TypeFlag::Call, TypeFlag::Put] {
+                let option = PowerOption::new(contract(type_flag), k, power);
+                let exact =
+                    expectation(|x| option.payoff(x), &[k.powf(1.0 / power)], s, t, r, q, v);
+
+                assert_approx_equal!(option.price_closed_form(s, t, r, q, v), exact, 1e-8);
+            }
+        }
+    }
+
+    #[test]
+    fn test_power_option_greeks_against_finite_differences() {
+        let (s, t, r, q, v) = (10.0, 0.5, 0.08, 0.02, 0.3);
+        let h = 1e-5;
+
+        for power in [2.0, -1.5] {
+            let k = 10.0_f64.powf(power);
+
+            for type_flag in [TypeFlag::Call, TypeFlag::Put] {
+                let option = PowerOption::new(contract(type_flag), k, power);
+                let price =
+                    |s: f64, t: f64, r: f64, v: f64| option.price_closed_form(s, t, r, q, v);
+                let greeks = option.greeks_closed_form(s, t, r, q, v);
+                let scale = price(s, t, r, v).max(1.0);
+
+                let delta = (price(s + h, t, r, v) - price(s - h, t, r, v)) / (2.0 * h);
+                let gamma = (price(s + 1e-3, t, r, v) - 2.0 * price(s, t, r, v)
+                    + price(s - 1e-3, t, r, v))
+                    / 1e-6;
+                let vega = (price(s, t, r, v + h) - price(s, t, r, v - h)) / (2.0 * h);
+                let theta = -(price(s, t + h, r, v) - price(s, t - h, r, v)) / (2.0 * h);
+                let rho = (price(s, t, r + h, v) - price(s, t, r - h, v)) / (2.0 * h);
+
+                assert_approx_equal!(greeks.delta, delta, 1e-5 * scale);
+                assert_approx_equal!(greeks.gamma, gamma, 1e-4 * scale);
+                assert_approx_equal!(greeks.vega, vega, 1e-5 * scale);
+                assert_approx_equal!(greeks.theta, theta, 1e-5 * scale);
+                assert_approx_equal!(greeks.rho, rho, 1e-5 * scale);
+            }
+        }
+    }
+
+    #[test]
+    fn test_capped_power_option_and_power_contract() {
+        let (s, t, r, q, v) = (10.0, 0.5, 0.08, 0.02, 0.3);
+
+        for type_flag in [TypeFlag::Call, TypeFlag::Put] {
+            let capped = CappedPowerOption::new(contract(type_flag), 100.0, 2.0, 20.0);
+            let exact = expectation(
+                |x| capped.payoff(x),
+                &[10.0, 80_f64.sqrt(), 120_f64.sqrt()],
+                s,
+                t,
+                r,
+                q,
+                v,
+            );
+
+            assert_approx_equal!(capped.price_closed_form(s, t, r, q, v), exact, 1e-8);
+        }
+
+        let power_contract = PowerContract::new(9.0, 3.0);
+        let exact = expectation(|x| power_contract.payoff(x), &[], s, t, r, q, v);
+
+        assert_approx_equal!(power_contract.price_closed_form(s, t, r, q, v), exact, 1e-8);
+    }
 }
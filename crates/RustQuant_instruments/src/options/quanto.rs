@@ -0,0 +1,222 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use super::{Black76, GeneralisedBlackScholesMerton, OptionContract, TypeFlag};
+use crate::Payoff;
+
+/// Quanto option.
+///
+/// The payoff is computed on a foreign underlying, in units of the foreign
+/// currency, and settled in the domestic currency at the fixed exchange
+/// rate `fx_rate` (domestic per unit of foreign currency).
+#[derive(Debug, Clone)]
+pub struct QuantoOption {
+    /// The option contract.
+    pub contract: OptionContract,
+
+    /// Strike price of the option, in the foreign currency.
+    pub strike: f64,
+
+    /// Fixed exchange rate the payoff is converted at.
+    pub fx_rate: f64,
+}
+
+impl Payoff for QuantoOption {
+    type Underlying = f64;
+
+    fn payoff(&self, underlying: Self::Underlying) -> f64 {
+        let payoff = match self.contract.type_flag {
+            TypeFlag::Call => (underlying - self.strike).max(0.0),
+            TypeFlag::Put => (self.strike - underlying).max(0.0),
+        };
+
+        self.fx_rate * payoff
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl QuantoOption {
+    /// Create a new quanto option.
+    pub fn new(contract: OptionContract, strike: f64, fx_rate: f64) -> Self {
+        Self {
+            contract,
+            strike,
+            fx_rate,
+        }
+    }
+
+    /// Forward of the underlying under the domestic risk-neutral measure.
+    ///
+    /// Changing to the domestic measure shifts the drift of the underlying
+    /// by $-\rho \sigma \sigma_X$, so the forward is
+    /// $S e^{(r_f - q - \rho \sigma \sigma_X) T}$.
+    ///
+    /// # Arguments:
+    /// * `s` - Underlying price, in the foreign currency.
+    /// * `t` - Time to expiry (in years).
+    /// * `r_foreign` - Foreign risk-free rate.
+    /// * `q` - Dividend yield of the underlying.
+    /// * `v` - Volatility of the underlying.
+    /// * `v_fx` - Volatility of the exchange rate (domestic per foreign).
+    /// * `rho` - Correlation between the underlying and the exchange rate.
+    #[allow(clippy::too_many_arguments)]
+    pub fn quanto_forward(
+        &self,
+        s: f64,
+        t: f64,
+        r_foreign: f64,
+        q: f64,
+        v: f64,
+        v_fx: f64,
+        rho: f64,
+    ) -> f64 {
+        s * ((r_foreign - q - rho * v * v_fx) * t).exp()
+    }
+
+    /// Price the option in the domestic currency.
+    ///
+    /// The underlying stays lognormal with volatility `v` under the domestic
+    /// measure, so the price is the Black (1976) price on the
+    /// [quanto forward](Self::quanto_forward), discounted at the domestic
+    /// rate and converted at the fixed exchange rate.
+    ///
+    /// # Arguments:
+    /// * `s` - Underlying price, in the foreign currency.
+    /// * `t` - Time to expiry (in years).
+    /// * `r_domestic` - Domestic risk-free rate.
+    /// * `r_foreign` - Foreign risk-free rate.
+    /// * `q` - Dividend yield of the underlying.
+    /// * `v` - Volatility of the underlying.
+    /// * `v_fx` - Volatility of the exchange rate (domestic per foreign).
+    /// * `rho` - Correlation between the underlying and the exchange rate.
+    #[allow(clippy::too_many_arguments)]
+    pub fn price_closed_form(
+        &self,
+        s: f64,
+        t: f64,
+        r_domestic: f64,
+        r_foreign: f64,
+        q: f64,
+        v: f64,
+        v_fx: f64,
+        rho: f64,
+    ) -> f64 {
+        let forward = self.quanto_forward(s, t, r_foreign, q, v, v_fx, rho);
+        let model = Black76::new(forward, r_domestic, v);
+
+        self.fx_rate * model.price(self.strike, t, self.contract.type_flag)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_quanto {
+    use super::*;
+    use crate::{ExerciseFlag, Merton73, OptionContractBuilder};
+    use time::macros::date;
+    use RustQuant_math::{gauss_legendre, Distribution, N};
+    use RustQuant_utils::assert_approx_equal;
+
+    fn quanto(type_flag: TypeFlag, strike: f64, fx_rate: f64) -> QuantoOption {
+        let contract = OptionContractBuilder::default()
+            .type_flag(type_flag)
+            .exercise_flag(ExerciseFlag::European {
+                expiry: date!(2025 - 01 - 01),
+            })
+            .build()
+            .unwrap();
+
+        QuantoOption::new(contract, strike, fx_rate)
+    }
+
+    #[test]
+    fn test_quanto_reference_values() {
+        // Computed with mpmath.
+        let (s, t, r_d, r_f, q, v, v_fx, rho) = (100.0, 0.5, 0.08, 0.05, 0.04, 0.2, 0.12, 0.3);
+
+        let call = quanto(TypeFlag::Call, 105.0, 1.5);
+        let put = quanto(TypeFlag::Put, 105.0, 1.5);
+
+        assert_approx_equal!(
+            call.price_closed_form(s, t, r_d, r_f, q, v, v_fx, rho),
+            5.293_684_794_121_888,
+            1e-10
+        );
+        assert_approx_equal!(
+            put.price_closed_form(s, t, r_d, r_f, q, v, v_fx, rho),
+            12.297_698_503_561_54,
+            1e-10
+        );
+    }
+
+    #[test]
+    fn test_quanto_uncorrelated_is_vanilla() {
+        // Without correlation, only the rates differ from a vanilla option.
+        let (s, t, r_d, r_f, q, v) = (100.0, 1.0, 0.03, 0.03, 0.01, 0.25);
+
+        for type_flag in [TypeFlag::Call, TypeFlag::Put] {
+            let option = quanto(type_flag, 95.0, 2.0);
+            let vanilla = Merton73::new(s, r_d, q, v).price(95.0, t, type_flag);
+
+            assert_approx_equal!(
+                option.price_closed_form(s, t, r_d, r_f, q, v, 0.15, 0.0),
+                2.0 * vanilla,
+                1e-10
+            );
+        }
+    }
+
+    #[test]
+    fn test_quanto_call_in_foreign_measure() {
+        // Price under the foreign measure instead: convert the domestic
+        // payoff at the terminal exchange rate X_T. Integrating out the part
+        // of X_T independent of the underlying leaves the weight
+        // exp(-rho v_fx sqrt(T) z - rho^2 v_fx^2 T / 2).
+        let (s, k, t, r_d, r_f, q, v, v_fx, rho) =
+            (100.0, 105.0, 0.75, 0.02, 0.05, 0.01, 0.3, 0.1, -0.6);
+        let fx_rate = 1.3;
+
+        let sqrt_t = f64::sqrt(t);
+        let integrand = |z: f64| {
+            let s_t = s * ((r_f - q - 0.5 * v * v) * t + v * sqrt_t * z).exp();
+            let weight = (-rho * v_fx * sqrt_t * z - 0.5 * rho * rho * v_fx * v_fx * t).exp();
+
+            (s_t - k).max(0.0) * weight * N.pdf(z)
+        };
+
+        // The payoff is smooth above the strike.
+        let z_star = ((k / s).ln() - (r_f - q - 0.5 * v * v) * t) / (v * sqrt_t);
+        let expectation = gauss_legendre(integrand, z_star, z_star + 15.0, 64);
+        let expected = fx_rate * (-r_d * t).exp() * expectation;
+
+        let call = quanto(TypeFlag::Call, k, fx_rate);
+
+        assert_approx_equal!(
+            call.price_closed_form(s, t, r_d, r_f, q, v, v_fx, rho),
+            expected,
+            1e-8
+        );
+    }
+
+    #[test]
+    fn test_quanto_payoff() {
+        let call = quanto(TypeFlag::Call, 100.0, 1.5);
+        let put = quanto(TypeFlag::Put, 100.0, 1.5);
+
+        assert_eq!(call.payoff(110.0), 15.0);
+        assert_eq!(put.payoff(110.0), 0.0);
+        assert_eq!(put.payoff(90.0), 15.0);
+    }
+}
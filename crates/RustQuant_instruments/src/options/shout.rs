@@ -0,0 +1,165 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Shout options (Thomas, 1993).
+//!
+//! The holder of a shout option may, once before expiry, lock in the
+//! intrinsic value at that time, $S_\tau - K$ for a call, and still keep
+//! any further upside: a shout call pays
+//!
+//! $$
+//! \max(S_T - K, 0) \text{ if never shouted, or } (S_\tau - K) + \max(S_T - S_\tau, 0).
+//! $$
+//!
+//! The extra state variable, the level shouted at, needs no dimension of
+//! its own on the lattice: once the holder shouts, the rest of the option
+//! is the locked-in amount plus an at-the-money European option, which has
+//! a closed form. So at each node, shouting is worth
+//! $e^{-r(T - t)} (S - K) + C_{BS}(S, S, T - t)$, and the option is
+//! priced by backward induction like an American option.
+
+use super::{BinomialTree, GeneralisedBlackScholesMerton, Lattice, Merton73, TypeFlag};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Shout option, with a single shout.
+#[derive(Debug, Clone, Copy)]
+pub struct ShoutOption {
+    /// Strike price.
+    pub strike: f64,
+
+    /// Call or put.
+    pub type_flag: TypeFlag,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl ShoutOption {
+    /// Create a new shout option.
+    pub fn new(strike: f64, type_flag: TypeFlag) -> Self {
+        Self { strike, type_flag }
+    }
+
+    /// Price the option on a binomial tree, with a shout allowed at every node.
+    ///
+    /// # Panics
+    /// Panics if the tree has discrete dividends, since the option left
+    /// after a shout is then no longer priced in closed form.
+    pub fn price_binomial(&self, tree: &BinomialTree) -> f64 {
+        assert!(
+            tree.dividends.is_empty(),
+            "Shout options do not support discrete dividends."
+        );
+
+        let n = tree.steps();
+        let dt = tree.time_step();
+        let bs = |s: f64| Merton73::new(s, tree.r, tree.q, tree.v);
+
+        let intrinsic = |s: f64| match self.type_flag {
+            TypeFlag::Call => s - self.strike,
+            TypeFlag::Put => self.strike - s,
+        };
+
+        let mut values: Vec<f64> = (0..tree.nodes(n))
+            .map(|node| intrinsic(tree.underlying(n, node)).max(0.0))
+            .collect();
+
+        for step in (0..n).rev() {
+            let remaining = tree.t - step as f64 * dt;
+
+            values = tree.rollback(step, &values);
+
+            for (node, value) in values.iter_mut().enumerate() {
+                let s = tree.underlying(step, node);
+                let locked_in = intrinsic(s);
+
+                // Shouting out of the money locks in nothing, and leaves
+                // less upside than not shouting.
+                if locked_in > 0.0 {
+                    let shout = (-tree.r * remaining).exp() * locked_in
+                        + bs(s).price(s, remaining, self.type_flag);
+
+                    *value = value.max(shout);
+                }
+            }
+        }
+
+        values[0]
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_shout {
+    use super::*;
+    use crate::{
+        ExerciseFlag, LatticeOption, LookbackOption, MonteCarloEngine, OptionContractBuilder,
+        StrikeFlag,
+    };
+    use RustQuant_stochastics::GeometricBrownianMotion;
+    use RustQuant_time::today;
+    use RustQuant_utils::assert_approx_equal;
+
+    #[test]
+    fn test_shout_bounds() {
+        let (s, r, q, v, t) = (100.0, 0.05, 0.01, 0.25, 1.0);
+        let tree = BinomialTree::new(s, r, q, v, t, 1000);
+
+        for type_flag in [TypeFlag::Call, TypeFlag::Put] {
+            let shout = ShoutOption::new(100.0, type_flag).price_binomial(&tree);
+
+            let european = Merton73::new(s, r, q, v).price(100.0, t, type_flag);
+            let american = tree
+                .value(&LatticeOption::new(100.0, type_flag, true))
+                .price;
+
+            // Shouting is worth at least exercising early, which gives up
+            // the upside after the shout.
+            assert!(shout > european);
+            assert!(shout > american);
+
+            // Converged in the number of steps.
+            let coarse = ShoutOption::new(100.0, type_flag)
+                .price_binomial(&BinomialTree::new(s, r, q, v, t, 500));
+
+            assert_approx_equal!(shout, coarse, 0.02);
+        }
+    }
+
+    #[test]
+    fn test_shout_below_lookback() {
+        // Shouting at the maximum, with hindsight, pays the fixed strike
+        // lookback call, so the shout call is cheaper.
+        let (s, r, v, t) = (100.0, 0.05, 0.2, 1.0);
+        let shout = ShoutOption::new(100.0, TypeFlag::Call)
+            .price_binomial(&BinomialTree::new(s, r, 0.0, v, t, 1000));
+
+        let contract = OptionContractBuilder::default()
+            .type_flag(TypeFlag::Call)
+            .exercise_flag(ExerciseFlag::European { expiry: today() })
+            .strike_flag(Some(StrikeFlag::Fixed))
+            .build()
+            .unwrap();
+        let lookback = LookbackOption::new(contract, Some(100.0));
+
+        let estimate =
+            MonteCarloEngine::new(GeometricBrownianMotion::new(r, v), s, r, t, 10_000, 252)
+                .with_seed(37)
+                .price_path(&lookback);
+
+        assert!(shout < estimate.price - 3.0 * estimate.standard_error);
+    }
+}
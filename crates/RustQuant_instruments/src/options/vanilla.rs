@@ -15,12 +15,12 @@ use super::{
     Asay82, Black76, BlackScholes73, GarmanKohlhagen83, GeneralisedBlackScholesMerton, Merton73,
     TypeFlag,
 };
-use super::{Bachelier, Heston93};
+use super::{Bachelier, Heston93, Schroder89};
 use crate::AnalyticOptionPricer;
 use crate::Payoff;
 use derive_builder::Builder;
 use time::Date;
-use RustQuant_time::{today, year_fraction};
+use RustQuant_time::{today, DayCountConvention};
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // STRUCTS
@@ -37,6 +37,10 @@ pub struct EuropeanVanillaOption {
 
     /// The type of the option (call or put).
     pub type_flag: TypeFlag,
+
+    /// Day count convention for the time to expiry.
+    #[builder(default = "DayCountConvention::Actual_365_25")]
+    pub day_count: DayCountConvention,
 }
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -55,14 +59,26 @@ impl Payoff for EuropeanVanillaOption {
 }
 
 impl EuropeanVanillaOption {
-    /// Create a new vanilla option.
+    /// Create a new vanilla option, with an Actual/365.25 time to expiry.
     pub fn new(strike: f64, expiry: Date, type_flag: TypeFlag) -> Self {
         Self {
             strike,
             expiry,
             type_flag,
+            day_count: DayCountConvention::Actual_365_25,
         }
     }
+
+    /// Set the day count convention for the time to expiry.
+    pub fn with_day_count(mut self, day_count: DayCountConvention) -> Self {
+        self.day_count = day_count;
+        self
+    }
+
+    /// Time from today to expiry, in years, under the option's day count.
+    pub fn time_to_expiry(&self) -> f64 {
+        self.day_count.day_count_factor(today(), self.expiry)
+    }
 }
 
 macro_rules! european_vanilla_option_gbsm {
@@ -98,7 +114,7 @@ macro_rules! european_vanilla_option_gbsm {
             /// Calculate the price of the option.
             pub fn price(&self) -> f64 {
                 let k = self.option.strike;
-                let t = year_fraction(today(), self.option.expiry);
+                let t = self.option.time_to_expiry();
                 let f = self.option.type_flag;
 
                 self.model.price(k, t, f)
@@ -107,7 +123,7 @@ macro_rules! european_vanilla_option_gbsm {
             /// Calculate the delta of the option.
             pub fn delta(&self) -> f64 {
                 let k = self.option.strike;
-                let t = year_fraction(today(), self.option.expiry);
+                let t = self.option.time_to_expiry();
                 let f = self.option.type_flag;
 
                 self.model.delta(k, t, f)
@@ -116,7 +132,7 @@ macro_rules! european_vanilla_option_gbsm {
             /// Calculate the gamma of the option.
             pub fn gamma(&self) -> f64 {
                 let k = self.option.strike;
-                let t = year_fraction(today(), self.option.expiry);
+                let t = self.option.time_to_expiry();
                 let f = self.option.type_flag;
 
                 self.model.gamma(k, t, f)
@@ -125,7 +141,7 @@ macro_rules! european_vanilla_option_gbsm {
             /// Calculate the theta of the option.
             pub fn theta(&self) -> f64 {
                 let k = self.option.strike;
-                let t = year_fraction(today(), self.option.expiry);
+                let t = self.option.time_to_expiry();
                 let f = self.option.type_flag;
 
                 self.model.theta(k, t, f)
@@ -134,7 +150,7 @@ macro_rules! european_vanilla_option_gbsm {
             /// Calculate the vega of the option.
             pub fn vega(&self) -> f64 {
                 let k = self.option.strike;
-                let t = year_fraction(today(), self.option.expiry);
+                let t = self.option.time_to_expiry();
                 let f = self.option.type_flag;
 
                 self.model.vega(k, t, f)
@@ -143,7 +159,7 @@ macro_rules! european_vanilla_option_gbsm {
             /// Calculate the rho of the option.
             pub fn rho(&self) -> f64 {
                 let k = self.option.strike;
-                let t = year_fraction(today(), self.option.expiry);
+                let t = self.option.time_to_expiry();
                 let f = self.option.type_flag;
 
                 self.model.rho(k, t, f)
@@ -152,7 +168,7 @@ macro_rules! european_vanilla_option_gbsm {
             /// Calculate the vanna of the option.
             pub fn vanna(&self) -> f64 {
                 let k = self.option.strike;
-                let t = year_fraction(today(), self.option.expiry);
+                let t = self.option.time_to_expiry();
                 let f = self.option.type_flag;
 
                 self.model.vanna(k, t, f)
@@ -161,7 +177,7 @@ macro_rules! european_vanilla_option_gbsm {
             /// Calculate the charm of the option.
             pub fn charm(&self) -> f64 {
                 let k = self.option.strike;
-                let t = year_fraction(today(), self.option.expiry);
+                let t = self.option.time_to_expiry();
                 let f = self.option.type_flag;
 
                 self.model.charm(k, t, f)
@@ -170,7 +186,7 @@ macro_rules! european_vanilla_option_gbsm {
             /// Calculate the lambda of the option.
             pub fn lambda(&self) -> f64 {
                 let k = self.option.strike;
-                let t = year_fraction(today(), self.option.expiry);
+                let t = self.option.time_to_expiry();
                 let f = self.option.type_flag;
 
                 self.model.lambda(k, t, f)
@@ -179,7 +195,7 @@ macro_rules! european_vanilla_option_gbsm {
             /// Calculate the zomma of the option.
             pub fn zomma(&self) -> f64 {
                 let k = self.option.strike;
-                let t = year_fraction(today(), self.option.expiry);
+                let t = self.option.time_to_expiry();
                 let f = self.option.type_flag;
 
                 self.model.zomma(k, t, f)
@@ -188,7 +204,7 @@ macro_rules! european_vanilla_option_gbsm {
             /// Calculate the speed of the option.
             pub fn speed(&self) -> f64 {
                 let k = self.option.strike;
-                let t = year_fraction(today(), self.option.expiry);
+                let t = self.option.time_to_expiry();
                 let f = self.option.type_flag;
 
                 self.model.speed(k, t, f)
@@ -197,7 +213,7 @@ macro_rules! european_vanilla_option_gbsm {
             /// Calculate the color of the option.
             pub fn color(&self) -> f64 {
                 let k = self.option.strike;
-                let t = year_fraction(today(), self.option.expiry);
+                let t = self.option.time_to_expiry();
                 let f = self.option.type_flag;
 
                 self.model.color(k, t, f)
@@ -206,7 +222,7 @@ macro_rules! european_vanilla_option_gbsm {
             /// Calculate the vomma of the option.
             pub fn vomma(&self) -> f64 {
                 let k = self.option.strike;
-                let t = year_fraction(today(), self.option.expiry);
+                let t = self.option.time_to_expiry();
                 let f = self.option.type_flag;
 
                 self.model.vomma(k, t, f)
@@ -215,7 +231,7 @@ macro_rules! european_vanilla_option_gbsm {
             /// Calculate the ultima of the option.
             pub fn ultima(&self) -> f64 {
                 let k = self.option.strike;
-                let t = year_fraction(today(), self.option.expiry);
+                let t = self.option.time_to_expiry();
                 let f = self.option.type_flag;
 
                 self.model.ultima(k, t, f)
@@ -234,7 +250,7 @@ impl AnalyticOptionPricer<EuropeanVanillaOption, Heston93> {
     /// Calculate the price of the option.
     pub fn price(&self) -> f64 {
         let k = self.option.strike;
-        let t = year_fraction(today(), self.option.expiry);
+        let t = self.option.time_to_expiry();
         let f = self.option.type_flag;
 
         self.model.price(k, t, f)
@@ -243,7 +259,7 @@ impl AnalyticOptionPricer<EuropeanVanillaOption, Heston93> {
     /// Calculate the delta of the option.
     pub fn delta(&self) -> f64 {
         let k = self.option.strike;
-        let t = year_fraction(today(), self.option.expiry);
+        let t = self.option.time_to_expiry();
         let f = self.option.type_flag;
 
         self.model.delta(k, t, f)
@@ -252,7 +268,7 @@ impl AnalyticOptionPricer<EuropeanVanillaOption, Heston93> {
     /// Calculate the gamma of the option.
     pub fn gamma(&self) -> f64 {
         let k = self.option.strike;
-        let t = year_fraction(today(), self.option.expiry);
+        let t = self.option.time_to_expiry();
         let f = self.option.type_flag;
 
         self.model.gamma(k, t, f)
@@ -261,7 +277,7 @@ impl AnalyticOptionPricer<EuropeanVanillaOption, Heston93> {
     /// Calculate the rho of the option.
     pub fn rho(&self) -> f64 {
         let k = self.option.strike;
-        let t = year_fraction(today(), self.option.expiry);
+        let t = self.option.time_to_expiry();
         let f = self.option.type_flag;
 
         self.model.rho(k, t, f)
@@ -285,11 +301,22 @@ impl AnalyticOptionPricer<EuropeanVanillaOption, Heston93> {
     }
 }
 
+impl AnalyticOptionPricer<EuropeanVanillaOption, Schroder89> {
+    /// Calculate the price of the option.
+    pub fn price(&self) -> f64 {
+        let k = self.option.strike;
+        let t = self.option.time_to_expiry();
+        let f = self.option.type_flag;
+
+        self.model.price(k, t, f)
+    }
+}
+
 impl AnalyticOptionPricer<EuropeanVanillaOption, Bachelier> {
     /// Calculate the price of the option.
     pub fn price(&self) -> f64 {
         let k = self.option.strike;
-        let t = year_fraction(today(), self.option.expiry);
+        let t = self.option.time_to_expiry();
         let f = self.option.type_flag;
 
         self.model.price(k, t, f)
@@ -297,14 +324,14 @@ impl AnalyticOptionPricer<EuropeanVanillaOption, Bachelier> {
 
     /// Calculate the atm price of the option.
     pub fn atm_price(&self) -> f64 {
-        let t = year_fraction(today(), self.option.expiry);
+        let t = self.option.time_to_expiry();
 
         self.model.atm_price(t)
     }
 
     /// Calculate the atm vol of the option.
     pub fn atm_vol(&self, price: f64) -> f64 {
-        let t = year_fraction(today(), self.option.expiry);
+        let t = self.option.time_to_expiry();
 
         self.model.atm_vol(price, t)
     }
@@ -312,7 +339,7 @@ impl AnalyticOptionPricer<EuropeanVanillaOption, Bachelier> {
     /// Calculate the implied volatility of the option.
     pub fn iv(&self, price: f64) -> f64 {
         let k = self.option.strike;
-        let t = year_fraction(today(), self.option.expiry);
+        let t = self.option.time_to_expiry();
         let f = self.option.type_flag;
 
         self.model.iv(price, k, t, f)
@@ -321,7 +348,7 @@ impl AnalyticOptionPricer<EuropeanVanillaOption, Bachelier> {
     /// Calculate the delta of the option.
     pub fn delta(&self) -> f64 {
         let k = self.option.strike;
-        let t = year_fraction(today(), self.option.expiry);
+        let t = self.option.time_to_expiry();
         let f = self.option.type_flag;
 
         self.model.delta(k, t, f)
@@ -330,7 +357,7 @@ impl AnalyticOptionPricer<EuropeanVanillaOption, Bachelier> {
     /// Calculate the gamma of the option.
     pub fn gamma(&self) -> f64 {
         let k = self.option.strike;
-        let t = year_fraction(today(), self.option.expiry);
+        let t = self.option.time_to_expiry();
         let f = self.option.type_flag;
 
         self.model.gamma(k, t, f)
@@ -339,7 +366,7 @@ impl AnalyticOptionPricer<EuropeanVanillaOption, Bachelier> {
     /// Calculate the theta of the option.
     pub fn theta(&self) -> f64 {
         let k = self.option.strike;
-        let t = year_fraction(today(), self.option.expiry);
+        let t = self.option.time_to_expiry();
         let f = self.option.type_flag;
 
         self.model.theta(k, t, f)
@@ -348,7 +375,7 @@ impl AnalyticOptionPricer<EuropeanVanillaOption, Bachelier> {
     /// Calculate the vega of the option.
     pub fn vega(&self) -> f64 {
         let k = self.option.strike;
-        let t = year_fraction(today(), self.option.expiry);
+        let t = self.option.time_to_expiry();
         let f = self.option.type_flag;
 
         self.model.vega(k, t, f)
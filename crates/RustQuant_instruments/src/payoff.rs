@@ -7,6 +7,32 @@
 //      - LICENSE-MIT.md
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
+//! Payoffs, and combinators to build new ones from them.
+//!
+//! A payoff is either terminal (`Underlying = f64`, the underlying at
+//! expiry) or path-dependent (`Underlying = Vec<f64>`, the simulated path
+//! including its initial value). Terminal payoffs are priced with
+//! `monte_carlo`, and path-dependent ones with `monte_carlo_path`, under
+//! any stochastic process.
+//!
+//! For example, a strangle capped at 10 and paid twice:
+//!
+//! ```
+//! use RustQuant_instruments::{CallPayoff, Payoff, PayoffExt, PutPayoff};
+//!
+//! let payoff = CallPayoff::new(100.0)
+//!     .plus(PutPayoff::new(90.0))
+//!     .capped(10.0)
+//!     .scaled(2.0);
+//!
+//! assert_eq!(payoff.payoff(120.0), 20.0);
+//! assert_eq!(payoff.payoff(95.0), 0.0);
+//! ```
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
 /// Generic payoff trait for derivatives.
 pub trait Payoff {
     /// Underlying input type for the payoff function.
@@ -15,3 +41,303 @@ pub trait Payoff {
     /// Payoff function for the derivative.
     fn payoff(&self, underlying: Self::Underlying) -> f64;
 }
+
+/// Combinators for building payoffs out of other payoffs.
+pub trait PayoffExt: Payoff + Sized {
+    /// The payoff, but no more than `cap`.
+    fn capped(self, cap: f64) -> Capped<Self> {
+        Capped { payoff: self, cap }
+    }
+
+    /// The payoff, but no less than `floor`.
+    fn floored(self, floor: f64) -> Floored<Self> {
+        Floored {
+            payoff: self,
+            floor,
+        }
+    }
+
+    /// The payoff multiplied by `factor` (negative for a short position).
+    fn scaled(self, factor: f64) -> Scaled<Self> {
+        Scaled {
+            payoff: self,
+            factor,
+        }
+    }
+
+    /// The sum of this payoff and `other`, on the same underlying.
+    fn plus<Q>(self, other: Q) -> Sum<Self, Q>
+    where
+        Q: Payoff<Underlying = Self::Underlying>,
+    {
+        Sum {
+            first: self,
+            second: other,
+        }
+    }
+}
+
+impl<P: Payoff> PayoffExt for P {}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Call payoff at expiry, $\max(S_T - K, 0)$.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CallPayoff {
+    /// Strike price.
+    pub strike: f64,
+}
+
+/// Put payoff at expiry, $\max(K - S_T, 0)$.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PutPayoff {
+    /// Strike price.
+    pub strike: f64,
+}
+
+/// Cash-or-nothing payoff at expiry: one unit if $L \le S_T \le U$.
+///
+/// Use an infinite bound for a one-sided digital.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DigitalPayoff {
+    /// Lower bound of the paying range.
+    pub lower: f64,
+
+    /// Upper bound of the paying range.
+    pub upper: f64,
+}
+
+/// Corridor (range accrual) payoff: the fraction of the path's fixings,
+/// after the initial value, with $L \le S_t \le U$.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CorridorPayoff {
+    /// Lower bound of the corridor.
+    pub lower: f64,
+
+    /// Upper bound of the corridor.
+    pub upper: f64,
+}
+
+/// A terminal payoff applied to the last value of a path, so it can be
+/// combined with path-dependent payoffs.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Terminal<P> {
+    /// The terminal payoff.
+    pub payoff: P,
+}
+
+/// A payoff capped from above.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Capped<P> {
+    /// The payoff.
+    pub payoff: P,
+
+    /// Maximum payout.
+    pub cap: f64,
+}
+
+/// A payoff floored from below.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Floored<P> {
+    /// The payoff.
+    pub payoff: P,
+
+    /// Minimum payout.
+    pub floor: f64,
+}
+
+/// A payoff multiplied by a constant.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Scaled<P> {
+    /// The payoff.
+    pub payoff: P,
+
+    /// Multiplier of the payout.
+    pub factor: f64,
+}
+
+/// The sum of two payoffs on the same underlying.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sum<P, Q> {
+    /// The first payoff.
+    pub first: P,
+
+    /// The second payoff.
+    pub second: Q,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl CallPayoff {
+    /// Create a new call payoff.
+    pub fn new(strike: f64) -> Self {
+        Self { strike }
+    }
+}
+
+impl PutPayoff {
+    /// Create a new put payoff.
+    pub fn new(strike: f64) -> Self {
+        Self { strike }
+    }
+}
+
+impl DigitalPayoff {
+    /// Create a new digital payoff, paying between `lower` and `upper`.
+    pub fn new(lower: f64, upper: f64) -> Self {
+        Self { lower, upper }
+    }
+}
+
+impl CorridorPayoff {
+    /// Create a new corridor payoff, accruing between `lower` and `upper`.
+    pub fn new(lower: f64, upper: f64) -> Self {
+        Self { lower, upper }
+    }
+}
+
+impl<P> Terminal<P>
+where
+    P: Payoff<Underlying = f64>,
+{
+    /// Apply a terminal payoff to the end of a path.
+    pub fn new(payoff: P) -> Self {
+        Self { payoff }
+    }
+}
+
+impl Payoff for CallPayoff {
+    type Underlying = f64;
+
+    fn payoff(&self, underlying: Self::Underlying) -> f64 {
+        (underlying - self.strike).max(0.0)
+    }
+}
+
+impl Payoff for PutPayoff {
+    type Underlying = f64;
+
+    fn payoff(&self, underlying: Self::Underlying) -> f64 {
+        (self.strike - underlying).max(0.0)
+    }
+}
+
+impl Payoff for DigitalPayoff {
+    type Underlying = f64;
+
+    fn payoff(&self, underlying: Self::Underlying) -> f64 {
+        f64::from(u8::from((self.lower..=self.upper).contains(&underlying)))
+    }
+}
+
+impl Payoff for CorridorPayoff {
+    type Underlying = Vec<f64>;
+
+    fn payoff(&self, underlying: Self::Underlying) -> f64 {
+        let fixings = &underlying[1..];
+        let inside = fixings
+            .iter()
+            .filter(|s| (self.lower..=self.upper).contains(*s))
+            .count();
+
+        inside as f64 / fixings.len() as f64
+    }
+}
+
+impl<P> Payoff for Terminal<P>
+where
+    P: Payoff<Underlying = f64>,
+{
+    type Underlying = Vec<f64>;
+
+    fn payoff(&self, underlying: Self::Underlying) -> f64 {
+        self.payoff.payoff(underlying[underlying.len() - 1])
+    }
+}
+
+impl<P: Payoff> Payoff for Capped<P> {
+    type Underlying = P::Underlying;
+
+    fn payoff(&self, underlying: Self::Underlying) -> f64 {
+        self.payoff.payoff(underlying).min(self.cap)
+    }
+}
+
+impl<P: Payoff> Payoff for Floored<P> {
+    type Underlying = P::Underlying;
+
+    fn payoff(&self, underlying: Self::Underlying) -> f64 {
+        self.payoff.payoff(underlying).max(self.floor)
+    }
+}
+
+impl<P: Payoff> Payoff for Scaled<P> {
+    type Underlying = P::Underlying;
+
+    fn payoff(&self, underlying: Self::Underlying) -> f64 {
+        self.factor * self.payoff.payoff(underlying)
+    }
+}
+
+impl<P, Q> Payoff for Sum<P, Q>
+where
+    P: Payoff,
+    P::Underlying: Clone,
+    Q: Payoff<Underlying = P::Underlying>,
+{
+    type Underlying = P::Underlying;
+
+    fn payoff(&self, underlying: Self::Underlying) -> f64 {
+        self.first.payoff(underlying.clone()) + self.second.payoff(underlying)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_payoff {
+    use super::*;
+
+    #[test]
+    fn test_terminal_payoffs() {
+        let capped_call = CallPayoff::new(100.0).capped(15.0);
+
+        assert_eq!(capped_call.payoff(90.0), 0.0);
+        assert_eq!(capped_call.payoff(110.0), 10.0);
+        assert_eq!(capped_call.payoff(130.0), 15.0);
+
+        // Long the 90 put, short the 80 put.
+        let put_spread = PutPayoff::new(90.0).plus(PutPayoff::new(80.0).scaled(-1.0));
+
+        assert_eq!(put_spread.payoff(100.0), 0.0);
+        assert_eq!(put_spread.payoff(85.0), 5.0);
+        assert_eq!(put_spread.payoff(50.0), 10.0);
+
+        let digital = DigitalPayoff::new(95.0, f64::INFINITY).floored(0.5);
+
+        assert_eq!(digital.payoff(90.0), 0.5);
+        assert_eq!(digital.payoff(95.0), 1.0);
+    }
+
+    #[test]
+    fn test_path_payoffs() {
+        let path = vec![100.0, 104.0, 112.0, 98.0, 106.0];
+        let corridor = CorridorPayoff::new(100.0, 110.0);
+
+        // The initial value is not a fixing.
+        assert_eq!(corridor.payoff(path.clone()), 0.5);
+
+        // A corridor with a call on the terminal value.
+        let structure = corridor
+            .scaled(10.0)
+            .plus(Terminal::new(CallPayoff::new(100.0)));
+
+        assert_eq!(structure.payoff(path), 11.0);
+    }
+}
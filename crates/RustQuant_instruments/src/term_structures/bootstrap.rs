@@ -0,0 +1,497 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023-2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Yield curve bootstrapping.
+//!
+//! The bootstrapped curve is an [`InterpolatedDiscountCurve`] that is
+//! log-linear in discount factors. The instruments are sorted by maturity
+//! and each adds one node to the curve, at its maturity. The node's discount factor is solved so the
+//! instrument reprices to its quote on the curve built so far, which
+//! includes the new node itself (an FRA or swap may have cash flows
+//! between the previous node and its maturity).
+//!
+//! This is a single-curve bootstrap: the floating leg of a swap starting
+//! on the reference date is worth par, so a par swap rate $S$ with fixed
+//! payments at $t_1, \dots, t_n$ satisfies
+//! $$
+//! 1 = S \sum_{i=1}^n \tau_i P(0, t_i) + P(0, t_n).
+//! $$
+
+use super::{InterpolatedDiscountCurve, YieldTermStructure};
+use time::Date;
+use RustQuant_error::RustQuantError;
+use RustQuant_math::interpolation::LinearInterpolator;
+use RustQuant_time::{add_months, DayCountConvention, Frequency};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// CONSTANTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+const BOOTSTRAP_MAX_ITER: usize = 50;
+const BOOTSTRAP_TOLERANCE: f64 = 1e-14;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS & ENUMS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Market instrument quoted for yield curve bootstrapping.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CurveInstrument {
+    /// Deposit from the reference date to `maturity`, at a simple `rate`.
+    Deposit {
+        /// Maturity date.
+        maturity: Date,
+        /// Simple interest rate.
+        rate: f64,
+    },
+
+    /// Forward rate agreement from `start` to `end`, at a simple `rate`.
+    Fra {
+        /// Start of the forward period.
+        start: Date,
+        /// End of the forward period.
+        end: Date,
+        /// Simple forward rate.
+        rate: f64,
+    },
+
+    /// Interest rate future on the period from `start` to `end`, quoted
+    /// as `100 * (1 - rate)`. The forward rate is the futures rate less
+    /// the `convexity_adjustment`.
+    Future {
+        /// Start of the underlying period.
+        start: Date,
+        /// End of the underlying period.
+        end: Date,
+        /// Futures price.
+        price: f64,
+        /// Futures rate less the forward rate.
+        convexity_adjustment: f64,
+    },
+
+    /// Par swap from the reference date to `maturity`, paying a fixed
+    /// `rate` at the given `frequency` against a floating leg.
+    Swap {
+        /// Maturity date.
+        maturity: Date,
+        /// Par swap rate.
+        rate: f64,
+        /// Fixed leg payment frequency.
+        frequency: Frequency,
+    },
+}
+
+/// Yield curve bootstrapper, holding the conventions of the instruments.
+#[derive(Debug, Clone, Copy)]
+pub struct YieldCurveBootstrapper {
+    /// Date on which the bootstrapped curve's discount factor is 1.
+    pub reference_date: Date,
+
+    /// Day count convention for the curve's time axis.
+    pub day_count: DayCountConvention,
+
+    /// Accrual day count of deposits, FRAs and futures.
+    pub money_market_day_count: DayCountConvention,
+
+    /// Accrual day count of swap fixed legs.
+    pub swap_day_count: DayCountConvention,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl CurveInstrument {
+    /// Date of the instrument's last cash flow, where it adds a node.
+    pub fn maturity(&self) -> Date {
+        match *self {
+            Self::Deposit { maturity, .. } | Self::Swap { maturity, .. } => maturity,
+            Self::Fra { end, .. } | Self::Future { end, .. } => end,
+        }
+    }
+}
+
+impl YieldCurveBootstrapper {
+    /// Create a new bootstrapper, with an Actual/365 Fixed curve,
+    /// Actual/360 money market instruments and 30/360 swap fixed legs.
+    pub fn new(reference_date: Date) -> Self {
+        Self {
+            reference_date,
+            day_count: DayCountConvention::Actual_365_Fixed,
+            money_market_day_count: DayCountConvention::Actual_360,
+            swap_day_count: DayCountConvention::Thirty_360_ISDA,
+        }
+    }
+
+    /// Set the day count convention for the curve's time axis.
+    pub fn with_day_count(mut self, day_count: DayCountConvention) -> Self {
+        self.day_count = day_count;
+        self
+    }
+
+    /// Set the accrual day count of deposits, FRAs and futures.
+    pub fn with_money_market_day_count(mut self, day_count: DayCountConvention) -> Self {
+        self.money_market_day_count = day_count;
+        self
+    }
+
+    /// Set the accrual day count of swap fixed legs.
+    pub fn with_swap_day_count(mut self, day_count: DayCountConvention) -> Self {
+        self.swap_day_count = day_count;
+        self
+    }
+
+    /// Bootstrap a discount curve, log-linear in discount factors, that
+    /// reprices each instrument to its quote.
+    ///
+    /// # Errors
+    /// - `RustQuantError::InvalidArgument` if there are no instruments, two
+    ///   mature on the same date, one matures on or before the reference
+    ///   date, a forward period ends before it starts, or a swap's
+    ///   frequency does not divide the year into whole months.
+    /// - `RustQuantError::ComputationError` if a node's discount factor
+    ///   cannot be solved for.
+    pub fn bootstrap(
+        &self,
+        instruments: &[CurveInstrument],
+    ) -> Result<InterpolatedDiscountCurve<LinearInterpolator<f64, f64>>, RustQuantError> {
+        let mut instruments = instruments.to_vec();
+        instruments.sort_by_key(CurveInstrument::maturity);
+
+        if instruments.is_empty()
+            || instruments[0].maturity() <= self.reference_date
+            || instruments
+                .windows(2)
+                .any(|w| w[0].maturity() == w[1].maturity())
+        {
+            return Err(RustQuantError::InvalidArgument(
+                "Instruments must mature after the reference date, on distinct dates.".to_string(),
+            ));
+        }
+
+        let mut dates = Vec::with_capacity(instruments.len());
+        let mut discount_factors = Vec::with_capacity(instruments.len());
+
+        for instrument in &instruments {
+            let residual = self.residual(instrument)?;
+
+            // Start from the curve so far, extended flat.
+            let guess = match dates.is_empty() {
+                true => 1.0,
+                false => self
+                    .curve(&dates, &discount_factors)?
+                    .discount_factor(instrument.maturity()),
+            };
+
+            dates.push(instrument.maturity());
+            discount_factors.push(guess);
+
+            self.solve_last_node(&dates, &mut discount_factors, &residual)?;
+        }
+
+        self.curve(&dates, &discount_factors)
+    }
+
+    /// Log-linear discount curve through the nodes solved so far.
+    fn curve(
+        &self,
+        dates: &[Date],
+        discount_factors: &[f64],
+    ) -> Result<InterpolatedDiscountCurve<LinearInterpolator<f64, f64>>, RustQuantError> {
+        InterpolatedDiscountCurve::log_linear(
+            self.reference_date,
+            dates,
+            discount_factors,
+            self.day_count,
+        )
+    }
+
+    /// Solve for the last node's discount factor by Newton's method, with a
+    /// finite difference derivative.
+    fn solve_last_node(
+        &self,
+        dates: &[Date],
+        discount_factors: &mut [f64],
+        residual: &dyn Fn(&dyn YieldTermStructure) -> f64,
+    ) -> Result<(), RustQuantError> {
+        let last = discount_factors.len() - 1;
+
+        for _ in 0..BOOTSTRAP_MAX_ITER {
+            let x = discount_factors[last];
+            let f = residual(&self.curve(dates, discount_factors)?);
+
+            let h = 1e-7 * x;
+            discount_factors[last] = x + h;
+            let df = (residual(&self.curve(dates, discount_factors)?) - f) / h;
+
+            let step = f / df;
+            discount_factors[last] = x - step;
+
+            if !discount_factors[last].is_finite() || discount_factors[last] <= 0.0 {
+                break;
+            }
+
+            if step.abs() < BOOTSTRAP_TOLERANCE {
+                return Ok(());
+            }
+        }
+
+        Err(RustQuantError::ComputationError(format!(
+            "Could not bootstrap the discount factor on {}.",
+            dates[last]
+        )))
+    }
+
+    /// Value of the instrument per unit notional on a curve, which is zero
+    /// when the curve reprices it to its quote.
+    #[allow(clippy::type_complexity)]
+    fn residual(
+        &self,
+        instrument: &CurveInstrument,
+    ) -> Result<Box<dyn Fn(&dyn YieldTermStructure) -> f64>, RustQuantError> {
+        let reference_date = self.reference_date;
+        let accrual = self.money_market_day_count;
+
+        let forward = move |start: Date, end: Date, rate: f64| {
+            if end <= start || start < reference_date {
+                return Err(RustQuantError::InvalidArgument(
+                    "Forward periods must start on or after the reference date, and end after they start."
+                        .to_string(),
+                ));
+            }
+
+            let tau = accrual.day_count_factor(start, end);
+
+            Ok(Box::new(move |curve: &dyn YieldTermStructure| {
+                curve.discount_factor(start) - curve.discount_factor(end) * (1.0 + rate * tau)
+            })
+                as Box<dyn Fn(&dyn YieldTermStructure) -> f64>)
+        };
+
+        match *instrument {
+            CurveInstrument::Deposit { maturity, rate } => forward(reference_date, maturity, rate),
+            CurveInstrument::Fra { start, end, rate } => forward(start, end, rate),
+            CurveInstrument::Future {
+                start,
+                end,
+                price,
+                convexity_adjustment,
+            } => forward(start, end, 1.0 - price / 100.0 - convexity_adjustment),
+            CurveInstrument::Swap {
+                maturity,
+                rate,
+                frequency,
+            } => {
+                let payments = fixed_leg_dates(reference_date, maturity, frequency)?;
+                let accruals: Vec<(Date, f64)> = std::iter::once(reference_date)
+                    .chain(payments.iter().copied())
+                    .zip(&payments)
+                    .map(|(start, &end)| (end, self.swap_day_count.day_count_factor(start, end)))
+                    .collect();
+
+                Ok(Box::new(move |curve: &dyn YieldTermStructure| {
+                    let annuity: f64 = accruals
+                        .iter()
+                        .map(|&(date, tau)| tau * curve.discount_factor(date))
+                        .sum();
+
+                    1.0 - curve.discount_factor(maturity) - rate * annuity
+                }))
+            }
+        }
+    }
+}
+
+/// Fixed leg payment dates, stepping back from `maturity` by whole periods
+/// while after `reference_date`, so any short stub is at the front.
+fn fixed_leg_dates(
+    reference_date: Date,
+    maturity: Date,
+    frequency: Frequency,
+) -> Result<Vec<Date>, RustQuantError> {
+    let per_year = frequency.times_in_year();
+
+    if per_year <= 0 || 12 % per_year != 0 {
+        return Err(RustQuantError::InvalidArgument(
+            "Swap frequencies must divide the year into whole months.".to_string(),
+        ));
+    }
+
+    let months = (12 / per_year) as i32;
+
+    let mut dates: Vec<Date> = (0..)
+        .map(|i| add_months(maturity, -months * i))
+        .take_while(|&date| date > reference_date)
+        .collect();
+
+    dates.reverse();
+
+    Ok(dates)
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_bootstrap {
+    use super::*;
+    use time::macros::date;
+    use RustQuant_utils::assert_approx_equal;
+
+    const REFERENCE_DATE: Date = date!(2024 - 01 - 15);
+
+    /// Quotes implied by a discount function, with the default conventions.
+    fn quotes(discount_factor: impl Fn(f64) -> f64) -> Vec<CurveInstrument> {
+        let bootstrapper = YieldCurveBootstrapper::new(REFERENCE_DATE);
+        let t = |date| {
+            bootstrapper
+                .day_count
+                .day_count_factor(REFERENCE_DATE, date)
+        };
+        let df = |date| discount_factor(t(date));
+        let simple = |start, end| {
+            let tau = bootstrapper
+                .money_market_day_count
+                .day_count_factor(start, end);
+            (df(start) / df(end) - 1.0) / tau
+        };
+
+        let mut instruments = vec![
+            CurveInstrument::Deposit {
+                maturity: date!(2024 - 04 - 15),
+                rate: simple(REFERENCE_DATE, date!(2024 - 04 - 15)),
+            },
+            CurveInstrument::Fra {
+                start: date!(2024 - 04 - 15),
+                end: date!(2024 - 10 - 15),
+                rate: simple(date!(2024 - 04 - 15), date!(2024 - 10 - 15)),
+            },
+            CurveInstrument::Future {
+                start: date!(2024 - 10 - 15),
+                end: date!(2025 - 01 - 15),
+                price: 100.0
+                    * (1.0 - simple(date!(2024 - 10 - 15), date!(2025 - 01 - 15)) - 0.0001),
+                convexity_adjustment: 0.0001,
+            },
+        ];
+
+        for years in [2, 3, 5, 7, 10] {
+            let maturity = add_months(REFERENCE_DATE, 12 * years);
+            let payments = fixed_leg_dates(REFERENCE_DATE, maturity, Frequency::Annually).unwrap();
+            let annuity: f64 = std::iter::once(REFERENCE_DATE)
+                .chain(payments.iter().copied())
+                .zip(&payments)
+                .map(|(start, &end)| {
+                    bootstrapper.swap_day_count.day_count_factor(start, end) * df(end)
+                })
+                .sum();
+
+            instruments.push(CurveInstrument::Swap {
+                maturity,
+                rate: (1.0 - df(maturity)) / annuity,
+                frequency: Frequency::Annually,
+            });
+        }
+
+        instruments
+    }
+
+    #[test]
+    fn test_flat_curve_is_recovered() {
+        // Log-linear interpolation is exact on a flat curve.
+        let curve = YieldCurveBootstrapper::new(REFERENCE_DATE)
+            .bootstrap(&quotes(|t| f64::exp(-0.03 * t)))
+            .unwrap();
+
+        for date in [
+            date!(2024 - 02 - 01),
+            date!(2024 - 10 - 15),
+            date!(2026 - 03 - 31),
+            date!(2033 - 07 - 01),
+        ] {
+            assert_approx_equal!(
+                curve.discount_factor(date),
+                f64::exp(-0.03 * curve.time(date)),
+                1e-12
+            );
+            assert_approx_equal!(curve.zero_rate(date), 0.03, 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_instruments_reprice() {
+        let instruments = quotes(|t| f64::exp(-(0.02 * t + 0.002 * t * t)));
+        let bootstrapper = YieldCurveBootstrapper::new(REFERENCE_DATE);
+        let curve = bootstrapper.bootstrap(&instruments).unwrap();
+
+        for instrument in &instruments {
+            let residual = bootstrapper.residual(instrument).unwrap();
+            assert_approx_equal!(residual(&curve), 0.0, 1e-13);
+        }
+
+        // Money market nodes are exact; the swap nodes depend on the
+        // interpolated discount factors between them.
+        let first = date!(2024 - 04 - 15);
+        assert_approx_equal!(
+            curve.discount_factor(first),
+            f64::exp(-(0.02 * curve.time(first) + 0.002 * curve.time(first).powi(2))),
+            1e-14
+        );
+
+        let ten_years = add_months(REFERENCE_DATE, 120);
+        let t = curve.time(ten_years);
+        assert_approx_equal!(curve.zero_rate(ten_years), 0.02 + 0.002 * t, 1e-4);
+    }
+
+    #[test]
+    fn test_invalid_instruments() {
+        let bootstrapper = YieldCurveBootstrapper::new(REFERENCE_DATE);
+        let deposit = |maturity| CurveInstrument::Deposit {
+            maturity,
+            rate: 0.03,
+        };
+
+        assert!(bootstrapper.bootstrap(&[]).is_err());
+        assert!(bootstrapper.bootstrap(&[deposit(REFERENCE_DATE)]).is_err());
+        assert!(bootstrapper
+            .bootstrap(&[
+                deposit(date!(2024 - 06 - 15)),
+                deposit(date!(2024 - 06 - 15))
+            ])
+            .is_err());
+        assert!(bootstrapper
+            .bootstrap(&[CurveInstrument::Swap {
+                maturity: date!(2026 - 01 - 15),
+                rate: 0.03,
+                frequency: Frequency::Weekly,
+            }])
+            .is_err());
+    }
+
+    #[test]
+    fn test_fixed_leg_dates() {
+        let dates = fixed_leg_dates(
+            date!(2024 - 01 - 15),
+            date!(2025 - 08 - 31),
+            Frequency::SemiAnnually,
+        )
+        .unwrap();
+
+        assert_eq!(
+            dates,
+            vec![
+                date!(2024 - 02 - 29),
+                date!(2024 - 08 - 31),
+                date!(2025 - 02 - 28),
+                date!(2025 - 08 - 31)
+            ]
+        );
+    }
+}
@@ -0,0 +1,429 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023-2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Discount curves with a choice of interpolation.
+//!
+//! The curve interpolates either the log discount factor or the zero rate
+//! in time, with any [`Interpolator`]:
+//!
+//! - Log-linear on discount factors: linear interpolation of
+//!   $\ln P(0, t)$, i.e. piecewise flat forward rates.
+//! - Linear on zero rates: linear interpolation of $r(t)$.
+//! - Monotone convex on forwards: the Hagan-West interpolation of
+//!   $\ln P(0, t)$, whose forward rates are continuous.
+//!
+//! Beyond the last node, log discount factors are extended along the last
+//! interval's average forward rate and zero rates are held flat. Zero rates
+//! are also held flat before the first node.
+
+use super::YieldTermStructure;
+use time::Date;
+use RustQuant_error::RustQuantError;
+use RustQuant_math::interpolation::{Interpolator, LinearInterpolator, MonotoneConvexInterpolator};
+use RustQuant_time::DayCountConvention;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS & ENUMS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Quantity a discount curve interpolates in time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CurveQuantity {
+    /// The log discount factor, $\ln P(0, t)$, with a node of zero at
+    /// the reference date.
+    LogDiscountFactor,
+
+    /// The continuously compounded zero rate, $r(t)$.
+    ZeroRate,
+}
+
+/// Discount curve of discount factors at node dates, interpolated with
+/// the interpolator `I`.
+pub struct InterpolatedDiscountCurve<I>
+where
+    I: Interpolator<f64, f64>,
+{
+    /// Date on which the discount factor is 1.
+    pub reference_date: Date,
+
+    /// Day count convention for the curve's time axis.
+    pub day_count: DayCountConvention,
+
+    /// Quantity the interpolator is fitted to.
+    pub quantity: CurveQuantity,
+
+    /// Node times and interpolated values, used to extrapolate.
+    nodes: Vec<(f64, f64)>,
+
+    /// Interpolator fitted to the nodes.
+    interpolator: I,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl<I> InterpolatedDiscountCurve<I>
+where
+    I: Interpolator<f64, f64>,
+{
+    /// Create a new discount curve from discount factors at node dates,
+    /// interpolating `quantity` with the interpolator that `build` makes
+    /// from the node times and values.
+    ///
+    /// # Errors
+    /// - `RustQuantError::UnequalLength` if `dates` and `discount_factors`
+    ///   have different lengths.
+    /// - `RustQuantError::InvalidArgument` if there are no nodes, the dates
+    ///   are not increasing and after `reference_date`, or a discount
+    ///   factor is not positive.
+    /// - Any error from building or fitting the interpolator.
+    pub fn new<F>(
+        reference_date: Date,
+        dates: &[Date],
+        discount_factors: &[f64],
+        day_count: DayCountConvention,
+        quantity: CurveQuantity,
+        build: F,
+    ) -> Result<Self, RustQuantError>
+    where
+        F: FnOnce(Vec<f64>, Vec<f64>) -> Result<I, RustQuantError>,
+    {
+        if dates.len() != discount_factors.len() {
+            return Err(RustQuantError::UnequalLength);
+        }
+
+        if dates.is_empty() || dates[0] <= reference_date || dates.windows(2).any(|w| w[0] >= w[1])
+        {
+            return Err(RustQuantError::InvalidArgument(
+                "Curve dates must be non-empty, increasing and after the reference date."
+                    .to_string(),
+            ));
+        }
+
+        if discount_factors.iter().any(|df| df.is_nan() || *df <= 0.0) {
+            return Err(RustQuantError::InvalidArgument(
+                "Discount factors must be positive.".to_string(),
+            ));
+        }
+
+        let points = dates
+            .iter()
+            .map(|&date| day_count.day_count_factor(reference_date, date))
+            .zip(discount_factors);
+
+        let nodes: Vec<(f64, f64)> = match quantity {
+            CurveQuantity::LogDiscountFactor => std::iter::once((0.0, 0.0))
+                .chain(points.map(|(t, df)| (t, df.ln())))
+                .collect(),
+            CurveQuantity::ZeroRate => points.map(|(t, df)| (t, -df.ln() / t)).collect(),
+        };
+
+        let (xs, ys) = nodes.iter().copied().unzip();
+        let mut interpolator = build(xs, ys)?;
+        interpolator.fit()?;
+
+        Ok(Self {
+            reference_date,
+            day_count,
+            quantity,
+            nodes,
+            interpolator,
+        })
+    }
+
+    /// Interpolated quantity at time `t`, extrapolated outside the nodes.
+    fn value(&self, t: f64) -> f64 {
+        let (first, last) = (self.nodes[0], self.nodes[self.nodes.len() - 1]);
+
+        if (first.0..=last.0).contains(&t) {
+            if let Ok(value) = self.interpolator.interpolate(t) {
+                return value;
+            }
+        }
+
+        match self.quantity {
+            CurveQuantity::ZeroRate => match t < first.0 {
+                true => first.1,
+                false => last.1,
+            },
+            CurveQuantity::LogDiscountFactor => {
+                // Extend the first or last interval's average slope.
+                let (a, b) = match t < first.0 {
+                    true => (first, self.nodes[1]),
+                    false => (self.nodes[self.nodes.len() - 2], last),
+                };
+                let base = match t < first.0 {
+                    true => first,
+                    false => last,
+                };
+
+                base.1 + (b.1 - a.1) / (b.0 - a.0) * (t - base.0)
+            }
+        }
+    }
+}
+
+impl InterpolatedDiscountCurve<LinearInterpolator<f64, f64>> {
+    /// Discount curve interpolating log discount factors linearly, so
+    /// forward rates are flat between nodes.
+    ///
+    /// # Errors
+    /// See [`InterpolatedDiscountCurve::new`].
+    pub fn log_linear(
+        reference_date: Date,
+        dates: &[Date],
+        discount_factors: &[f64],
+        day_count: DayCountConvention,
+    ) -> Result<Self, RustQuantError> {
+        Self::new(
+            reference_date,
+            dates,
+            discount_factors,
+            day_count,
+            CurveQuantity::LogDiscountFactor,
+            LinearInterpolator::new,
+        )
+    }
+
+    /// Discount curve interpolating zero rates linearly.
+    ///
+    /// # Errors
+    /// See [`InterpolatedDiscountCurve::new`].
+    pub fn linear_zero(
+        reference_date: Date,
+        dates: &[Date],
+        discount_factors: &[f64],
+        day_count: DayCountConvention,
+    ) -> Result<Self, RustQuantError> {
+        Self::new(
+            reference_date,
+            dates,
+            discount_factors,
+            day_count,
+            CurveQuantity::ZeroRate,
+            LinearInterpolator::new,
+        )
+    }
+}
+
+impl InterpolatedDiscountCurve<MonotoneConvexInterpolator> {
+    /// Discount curve interpolating log discount factors with the monotone
+    /// convex method, so forward rates are continuous.
+    ///
+    /// # Errors
+    /// See [`InterpolatedDiscountCurve::new`].
+    pub fn monotone_convex(
+        reference_date: Date,
+        dates: &[Date],
+        discount_factors: &[f64],
+        day_count: DayCountConvention,
+    ) -> Result<Self, RustQuantError> {
+        Self::new(
+            reference_date,
+            dates,
+            discount_factors,
+            day_count,
+            CurveQuantity::LogDiscountFactor,
+            MonotoneConvexInterpolator::new,
+        )
+    }
+
+    /// Instantaneous forward rate at `date`, within the curve's nodes.
+    ///
+    /// # Errors
+    /// - `RustQuantError::OutsideOfRange` if `date` is after the last node
+    ///   or before the reference date.
+    pub fn instantaneous_forward(&self, date: Date) -> Result<f64, RustQuantError> {
+        Ok(-self.interpolator.slope(self.time(date))?)
+    }
+}
+
+impl<I> YieldTermStructure for InterpolatedDiscountCurve<I>
+where
+    I: Interpolator<f64, f64>,
+{
+    fn reference_date(&self) -> Date {
+        self.reference_date
+    }
+
+    fn day_count(&self) -> DayCountConvention {
+        self.day_count
+    }
+
+    fn discount_factor(&self, date: Date) -> f64 {
+        let t = self.time(date);
+
+        match self.quantity {
+            CurveQuantity::LogDiscountFactor => self.value(t).exp(),
+            CurveQuantity::ZeroRate => (-self.value(t) * t).exp(),
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_interpolated_discount_curve {
+    use super::*;
+    use time::macros::date;
+    use RustQuant_utils::assert_approx_equal;
+
+    const REFERENCE_DATE: Date = date!(2024 - 01 - 02);
+    const DAY_COUNT: DayCountConvention = DayCountConvention::Actual_365_Fixed;
+
+    fn nodes() -> (Vec<Date>, Vec<f64>) {
+        let dates = vec![
+            date!(2024 - 07 - 02),
+            date!(2025 - 01 - 02),
+            date!(2027 - 01 - 02),
+            date!(2029 - 01 - 02),
+            date!(2034 - 01 - 02),
+        ];
+        let zeros = [0.030, 0.034, 0.039, 0.041, 0.040];
+        let dfs = dates
+            .iter()
+            .zip(zeros)
+            .map(|(&d, z)| f64::exp(-z * DAY_COUNT.day_count_factor(REFERENCE_DATE, d)))
+            .collect();
+
+        (dates, dfs)
+    }
+
+    #[test]
+    fn test_nodes_are_reproduced() {
+        let (dates, dfs) = nodes();
+
+        let log_linear =
+            InterpolatedDiscountCurve::log_linear(REFERENCE_DATE, &dates, &dfs, DAY_COUNT).unwrap();
+        let linear_zero =
+            InterpolatedDiscountCurve::linear_zero(REFERENCE_DATE, &dates, &dfs, DAY_COUNT)
+                .unwrap();
+        let monotone_convex =
+            InterpolatedDiscountCurve::monotone_convex(REFERENCE_DATE, &dates, &dfs, DAY_COUNT)
+                .unwrap();
+
+        for (&date, &df) in dates.iter().zip(&dfs) {
+            assert_approx_equal!(log_linear.discount_factor(date), df, 1e-14);
+            assert_approx_equal!(linear_zero.discount_factor(date), df, 1e-14);
+            assert_approx_equal!(monotone_convex.discount_factor(date), df, 1e-14);
+        }
+
+        assert_approx_equal!(log_linear.discount_factor(REFERENCE_DATE), 1.0, 1e-14);
+        assert_approx_equal!(monotone_convex.discount_factor(REFERENCE_DATE), 1.0, 1e-14);
+    }
+
+    #[test]
+    fn test_flat_log_linear_curve() {
+        let dates = [date!(2025 - 01 - 02), date!(2026 - 01 - 02)];
+        let dfs = dates.map(|d| f64::exp(-0.04 * DAY_COUNT.day_count_factor(REFERENCE_DATE, d)));
+        let curve =
+            InterpolatedDiscountCurve::log_linear(REFERENCE_DATE, &dates, &dfs, DAY_COUNT).unwrap();
+
+        for date in [
+            date!(2024 - 01 - 02),
+            date!(2024 - 07 - 15),
+            date!(2025 - 01 - 02),
+            date!(2025 - 09 - 30),
+            date!(2030 - 06 - 01),
+        ] {
+            let t = curve.time(date);
+
+            assert_approx_equal!(curve.discount_factor(date), f64::exp(-0.04 * t), 1e-14);
+            assert_approx_equal!(curve.zero_rate(date), 0.04, 1e-12);
+        }
+
+        let (start, end) = (date!(2025 - 03 - 01), date!(2025 - 06 - 01));
+        let tau = curve.time(end) - curve.time(start);
+
+        assert_approx_equal!(
+            curve.forward_rate(start, end),
+            (f64::exp(0.04 * tau) - 1.0) / tau,
+            1e-12
+        );
+    }
+
+    #[test]
+    fn test_interpolation_schemes() {
+        let (dates, dfs) = nodes();
+        let date = date!(2026 - 01 - 02);
+        let (t_l, t_r) = (
+            DAY_COUNT.day_count_factor(REFERENCE_DATE, dates[1]),
+            DAY_COUNT.day_count_factor(REFERENCE_DATE, dates[2]),
+        );
+        let t = DAY_COUNT.day_count_factor(REFERENCE_DATE, date);
+        let w = (t - t_l) / (t_r - t_l);
+
+        // Linear in zero rates between the nodes.
+        let linear_zero =
+            InterpolatedDiscountCurve::linear_zero(REFERENCE_DATE, &dates, &dfs, DAY_COUNT)
+                .unwrap();
+        assert_approx_equal!(
+            linear_zero.zero_rate(date),
+            0.034 + w * (0.039 - 0.034),
+            1e-12
+        );
+
+        // Flat zero rates outside the nodes.
+        assert_approx_equal!(linear_zero.zero_rate(date!(2024 - 02 - 01)), 0.030, 1e-12);
+        assert_approx_equal!(linear_zero.zero_rate(date!(2040 - 01 - 02)), 0.040, 1e-12);
+
+        // Continuous forwards, which average to the discrete forwards.
+        let monotone_convex =
+            InterpolatedDiscountCurve::monotone_convex(REFERENCE_DATE, &dates, &dfs, DAY_COUNT)
+                .unwrap();
+        let node = dates[2];
+
+        let left = monotone_convex
+            .instantaneous_forward(node - time::Duration::days(1))
+            .unwrap();
+        let right = monotone_convex
+            .instantaneous_forward(node + time::Duration::days(1))
+            .unwrap();
+
+        assert!((left - right).abs() < 1e-3);
+        assert!(monotone_convex
+            .instantaneous_forward(date!(2040 - 01 - 02))
+            .is_err());
+    }
+
+    #[test]
+    fn test_invalid_discount_curve() {
+        let (dates, dfs) = nodes();
+
+        assert!(InterpolatedDiscountCurve::log_linear(
+            REFERENCE_DATE,
+            &dates,
+            &dfs[1..],
+            DAY_COUNT
+        )
+        .is_err());
+        assert!(InterpolatedDiscountCurve::log_linear(dates[0], &dates, &dfs, DAY_COUNT).is_err());
+        assert!(
+            InterpolatedDiscountCurve::monotone_convex(REFERENCE_DATE, &[], &[], DAY_COUNT)
+                .is_err()
+        );
+        assert!(InterpolatedDiscountCurve::log_linear(
+            REFERENCE_DATE,
+            &[date!(2025 - 01 - 02), date!(2024 - 06 - 02)],
+            &[0.9, 0.95],
+            DAY_COUNT
+        )
+        .is_err());
+        assert!(InterpolatedDiscountCurve::log_linear(
+            REFERENCE_DATE,
+            &[date!(2025 - 01 - 02)],
+            &[-0.9],
+            DAY_COUNT
+        )
+        .is_err());
+    }
+}
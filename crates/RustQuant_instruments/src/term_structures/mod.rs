@@ -0,0 +1,23 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023-2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// The term structure trait that pricers take instead of a flat rate.
+pub mod term_structure;
+pub use term_structure::*;
+
+/// Discount curves with pluggable interpolation.
+pub mod interpolated_discount_curve;
+pub use interpolated_discount_curve::*;
+
+/// Nelson-Siegel and Svensson curves as term structures.
+mod nelson_siegel;
+
+/// Bootstrapping discount curves from deposits, FRAs, futures and swaps.
+pub mod bootstrap;
+pub use bootstrap::*;
@@ -0,0 +1,117 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023-2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Nelson-Siegel and Svensson curves as term structures.
+//!
+//! The models, and their least squares fits, live in `RustQuant_stochastics`.
+//! Here they discount from their reference date, measuring time in their
+//! day count, so a fitted curve can price bonds and options.
+//!
+//! Rates are decimal here, as in the models' `zero_rate_at`. The
+//! `CurveModel::discount_factor` of `NelsonSiegelSvensson` instead takes
+//! the spot rate in percent.
+
+use super::YieldTermStructure;
+use time::Date;
+use RustQuant_stochastics::{NelsonSiegel, NelsonSiegelSvensson};
+use RustQuant_time::DayCountConvention;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl YieldTermStructure for NelsonSiegel {
+    fn reference_date(&self) -> Date {
+        self.reference_date
+    }
+
+    fn day_count(&self) -> DayCountConvention {
+        self.day_count
+    }
+
+    fn discount_factor(&self, date: Date) -> f64 {
+        self.discount_factor_at(self.time(date))
+    }
+}
+
+impl YieldTermStructure for NelsonSiegelSvensson {
+    fn reference_date(&self) -> Date {
+        self.reference_date
+    }
+
+    fn day_count(&self) -> DayCountConvention {
+        self.day_count
+    }
+
+    fn discount_factor(&self, date: Date) -> f64 {
+        self.discount_factor_at(self.time(date))
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_nelson_siegel {
+    use super::*;
+    use crate::{EuropeanVanillaOption, GeneralisedBlackScholesMerton, Merton73, TypeFlag};
+    use time::macros::date;
+    use RustQuant_utils::{assert_approx_equal, RUSTQUANT_EPSILON};
+
+    const REFERENCE_DATE: Date = date!(2024 - 01 - 02);
+    const DAY_COUNT: DayCountConvention = DayCountConvention::Actual_365_Fixed;
+
+    const MATURITIES: [f64; 8] = [0.25, 0.5, 1.0, 2.0, 5.0, 10.0, 20.0, 30.0];
+
+    #[test]
+    fn test_term_structure_rates() {
+        let curve = NelsonSiegel::new(0.045, -0.02, 0.01, 1.5)
+            .with_reference_date(REFERENCE_DATE)
+            .with_day_count(DAY_COUNT);
+
+        assert_approx_equal!(
+            curve.discount_factor(REFERENCE_DATE),
+            1.0,
+            RUSTQUANT_EPSILON
+        );
+
+        for date in [date!(2024 - 07 - 02), date!(2029 - 01 - 02)] {
+            let t = DAY_COUNT.day_count_factor(REFERENCE_DATE, date);
+
+            assert_approx_equal!(curve.zero_rate(date), curve.zero_rate_at(t), 1e-12);
+            assert_approx_equal!(
+                curve.discount_factor(date),
+                curve.discount_factor_at(t),
+                RUSTQUANT_EPSILON
+            );
+        }
+    }
+
+    #[test]
+    fn test_fitted_curve_prices_option() {
+        let model = NelsonSiegelSvensson::new(0.05, -0.03, -0.02, 0.04, 1.0, 6.0);
+        let yields = MATURITIES.map(|t| model.zero_rate_at(t));
+
+        let curve = NelsonSiegelSvensson::fit(&MATURITIES, &yields)
+            .unwrap()
+            .with_reference_date(REFERENCE_DATE)
+            .with_day_count(DAY_COUNT);
+
+        let expiry = date!(2025 - 01 - 02);
+        let t = DAY_COUNT.day_count_factor(REFERENCE_DATE, expiry);
+        let option = EuropeanVanillaOption::new(100.0, expiry, TypeFlag::Call);
+
+        assert_approx_equal!(
+            option.price_with_curve(100.0, 0.0, 0.2, &curve),
+            Merton73::new(100.0, model.zero_rate_at(t), 0.0, 0.2).price(100.0, t, TypeFlag::Call),
+            1e-6
+        );
+    }
+}
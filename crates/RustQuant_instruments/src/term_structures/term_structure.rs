@@ -0,0 +1,141 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023-2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Interest rate term structures.
+
+use crate::{EuropeanVanillaOption, GeneralisedBlackScholesMerton, Merton73};
+use time::{Date, Duration};
+use RustQuant_time::DayCountConvention;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Term structure of interest rates, queried by date.
+///
+/// Pricers take a term structure instead of a single flat rate. Only the
+/// discount factor is required; zero and forward rates follow from it.
+pub trait YieldTermStructure {
+    /// Date on which the discount factor is 1.
+    fn reference_date(&self) -> Date;
+
+    /// Day count convention for the time axis.
+    fn day_count(&self) -> DayCountConvention;
+
+    /// Discount factor from `date` back to the reference date.
+    fn discount_factor(&self, date: Date) -> f64;
+
+    /// Time from the reference date to `date`, in years.
+    fn time(&self, date: Date) -> f64 {
+        self.day_count()
+            .day_count_factor(self.reference_date(), date)
+    }
+
+    /// Continuously compounded zero rate to `date`.
+    ///
+    /// At the reference date this is the limit, approximated by the zero
+    /// rate to the next day.
+    fn zero_rate(&self, date: Date) -> f64 {
+        let date = date.max(self.reference_date() + Duration::days(1));
+
+        -self.discount_factor(date).ln() / self.time(date)
+    }
+
+    /// Simply compounded forward rate from `start` to `end`, accruing
+    /// over the term structure's day count.
+    ///
+    /// # Panics
+    /// Panics if `end` is not after `start`.
+    fn forward_rate(&self, start: Date, end: Date) -> f64 {
+        assert!(end > start, "The forward period must end after it starts.");
+
+        let tau = self.day_count().day_count_factor(start, end);
+
+        (self.discount_factor(start) / self.discount_factor(end) - 1.0) / tau
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl EuropeanVanillaOption {
+    /// Price of the option under Black-Scholes, discounting on `curve`
+    /// and valued on the curve's reference date.
+    ///
+    /// The risk-free rate is the curve's zero rate to expiry, and time to
+    /// expiry is measured in the curve's day count.
+    ///
+    /// # Arguments:
+    /// * `s` - Underlying price.
+    /// * `q` - Continuous dividend yield.
+    /// * `v` - Volatility.
+    /// * `curve` - The discount curve.
+    pub fn price_with_curve<C>(&self, s: f64, q: f64, v: f64, curve: &C) -> f64
+    where
+        C: YieldTermStructure,
+    {
+        let t = curve.time(self.expiry).max(0.0);
+        let r = curve.zero_rate(self.expiry);
+
+        Merton73::new(s, r, q, v).price(self.strike, t, self.type_flag)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_term_structure {
+    use super::*;
+    use crate::{InterpolatedDiscountCurve, TypeFlag};
+    use time::macros::date;
+    use RustQuant_utils::{assert_approx_equal, RUSTQUANT_EPSILON};
+
+    const REFERENCE_DATE: Date = date!(2024 - 01 - 02);
+    const DAY_COUNT: DayCountConvention = DayCountConvention::Actual_365_Fixed;
+
+    #[test]
+    fn test_price_with_flat_curve() {
+        let dates = [date!(2025 - 01 - 02), date!(2026 - 01 - 02)];
+        let dfs = dates.map(|d| f64::exp(-0.05 * DAY_COUNT.day_count_factor(REFERENCE_DATE, d)));
+        let curve =
+            InterpolatedDiscountCurve::monotone_convex(REFERENCE_DATE, &dates, &dfs, DAY_COUNT)
+                .unwrap();
+
+        let expiry = date!(2024 - 10 - 15);
+        let t = DAY_COUNT.day_count_factor(REFERENCE_DATE, expiry);
+
+        for type_flag in [TypeFlag::Call, TypeFlag::Put] {
+            let option = EuropeanVanillaOption::new(105.0, expiry, type_flag);
+
+            assert_approx_equal!(
+                option.price_with_curve(100.0, 0.01, 0.2, &curve),
+                Merton73::new(100.0, 0.05, 0.01, 0.2).price(105.0, t, type_flag),
+                RUSTQUANT_EPSILON
+            );
+        }
+    }
+
+    #[test]
+    fn test_forward_rate() {
+        let dates = [date!(2025 - 01 - 02), date!(2026 - 01 - 02)];
+        let dfs = [0.96, 0.91];
+        let curve =
+            InterpolatedDiscountCurve::log_linear(REFERENCE_DATE, &dates, &dfs, DAY_COUNT).unwrap();
+        let tau = DAY_COUNT.day_count_factor(dates[0], dates[1]);
+
+        assert_approx_equal!(
+            curve.forward_rate(dates[0], dates[1]),
+            (0.96 / 0.91 - 1.0) / tau,
+            RUSTQUANT_EPSILON
+        );
+    }
+}
@@ -0,0 +1,401 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Dupire (1994) local volatility, from an implied volatility surface.
+//!
+//! In terms of the total implied variance $w(y, T)$ at log-forward-moneyness
+//! $y = \ln(K / F_T)$, the local variance is (Gatheral, 2006)
+//!
+//! $$
+//! \sigma_{loc}^2(y, T) = \frac{\partial_T w}
+//!     {1 - \frac{y}{w} \partial_y w
+//!      + \frac{1}{4} \left( -\frac{1}{4} - \frac{1}{w} + \frac{y^2}{w^2} \right)
+//!        (\partial_y w)^2
+//!      + \frac{1}{2} \partial_{yy} w}
+//! $$
+//!
+//! The derivatives are taken by finite differences on the implied surface,
+//! at the nodes of a grid. Between nodes, the local variance is
+//! interpolated bilinearly.
+
+use super::SVISurface;
+use RustQuant_error::RustQuantError;
+use RustQuant_stochastics::StochasticProcess;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// CONSTANTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Log-moneyness step for the finite differences of the implied surface.
+const DUPIRE_MONEYNESS_STEP: f64 = 1e-4;
+
+/// Time step (in years) for the finite differences of the implied surface.
+const DUPIRE_TIME_STEP: f64 = 1e-4;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS & TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// An implied volatility surface, in total implied variance.
+pub trait ImpliedVolatilitySurface {
+    /// Total implied variance $\sigma_{BS}^2 T$ at log-forward-moneyness
+    /// $y = \ln(K / F_T)$ and time to expiry `t` (in years).
+    fn total_variance(&self, y: f64, t: f64) -> f64;
+}
+
+/// Dupire local volatility surface, on a grid of times and
+/// log-forward-moneyness.
+///
+/// Outside the grid, the local volatility at the nearest edge is used.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocalVolatilitySurface {
+    /// Underlying price at time zero.
+    pub spot: f64,
+
+    /// Risk-free rate.
+    pub r: f64,
+
+    /// Dividend yield.
+    pub q: f64,
+
+    /// Grid times (in years), increasing.
+    pub times: Vec<f64>,
+
+    /// Grid log-forward-moneyness, increasing.
+    pub log_moneyness: Vec<f64>,
+
+    /// Local variance at each time (rows) and log-moneyness (columns).
+    pub variances: Vec<Vec<f64>>,
+}
+
+/// Local volatility model of the underlying,
+/// $dS_t = (r - q) S_t dt + \sigma_{loc}(S_t, t) S_t dW_t$,
+/// for simulating paths with the `StochasticProcess` schemes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocalVolatilityProcess {
+    /// The local volatility surface.
+    pub surface: LocalVolatilitySurface,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl ImpliedVolatilitySurface for SVISurface {
+    fn total_variance(&self, y: f64, t: f64) -> f64 {
+        SVISurface::total_variance(self, y, t)
+    }
+}
+
+impl LocalVolatilitySurface {
+    /// Build the local volatility surface of an implied volatility surface
+    /// by Dupire's formula, at each node of the grid.
+    ///
+    /// # Arguments:
+    /// * `implied` - The implied volatility surface.
+    /// * `spot` - Underlying price at time zero.
+    /// * `r` - Risk-free rate.
+    /// * `q` - Dividend yield.
+    /// * `times` - Grid times (in years), positive and increasing.
+    /// * `log_moneyness` - Grid log-forward-moneyness, increasing.
+    ///
+    /// # Errors
+    /// - `RustQuantError::InvalidArgument` if a grid is empty or not
+    ///   increasing, a time is not positive, or the implied surface has
+    ///   calendar or butterfly arbitrage at a node (so the local variance
+    ///   is not positive).
+    pub fn from_implied<S: ImpliedVolatilitySurface>(
+        implied: &S,
+        spot: f64,
+        r: f64,
+        q: f64,
+        times: Vec<f64>,
+        log_moneyness: Vec<f64>,
+    ) -> Result<Self, RustQuantError> {
+        let increasing = |grid: &[f64]| grid.windows(2).all(|pair| pair[0] < pair[1]);
+
+        if times.is_empty()
+            || log_moneyness.is_empty()
+            || !increasing(&times)
+            || !increasing(&log_moneyness)
+            || times[0] <= 0.0
+        {
+            return Err(RustQuantError::InvalidArgument(
+                "The grids must be non-empty and increasing, with positive times.".to_string(),
+            ));
+        }
+
+        let variances = times
+            .iter()
+            .map(|&t| {
+                log_moneyness
+                    .iter()
+                    .map(|&y| {
+                        let variance = dupire_variance(implied, y, t);
+
+                        if variance > 0.0 && variance.is_finite() {
+                            Ok(variance)
+                        } else {
+                            Err(RustQuantError::InvalidArgument(format!(
+                                "The implied surface has arbitrage at y = {y}, t = {t}."
+                            )))
+                        }
+                    })
+                    .collect::<Result<Vec<f64>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            spot,
+            r,
+            q,
+            times,
+            log_moneyness,
+            variances,
+        })
+    }
+
+    /// Local volatility at underlying price `s` and time `t` (in years).
+    pub fn local_volatility(&self, s: f64, t: f64) -> f64 {
+        let forward = self.spot * ((self.r - self.q) * t).exp();
+
+        self.interpolated_variance((s / forward).ln(), t).sqrt()
+    }
+
+    /// Bilinear interpolation of the local variance, flat outside the grid.
+    fn interpolated_variance(&self, y: f64, t: f64) -> f64 {
+        let (i, u) = bracket(&self.times, t);
+        let (j, v) = bracket(&self.log_moneyness, y);
+
+        let row = |i: usize| {
+            let variances = &self.variances[i];
+            let upper = variances[(j + 1).min(variances.len() - 1)];
+
+            (1.0 - v) * variances[j] + v * upper
+        };
+        let upper = row((i + 1).min(self.times.len() - 1));
+
+        (1.0 - u) * row(i) + u * upper
+    }
+}
+
+impl LocalVolatilityProcess {
+    /// Create a new local volatility process.
+    pub fn new(surface: LocalVolatilitySurface) -> Self {
+        Self { surface }
+    }
+}
+
+impl StochasticProcess for LocalVolatilityProcess {
+    fn drift(&self, x: f64, _t: f64) -> f64 {
+        (self.surface.r - self.surface.q) * x
+    }
+
+    fn diffusion(&self, x: f64, t: f64) -> f64 {
+        self.surface.local_volatility(x, t) * x
+    }
+
+    fn jump(&self, _x: f64, _t: f64) -> Option<f64> {
+        None
+    }
+
+    fn parameters(&self) -> Vec<f64> {
+        vec![self.surface.spot, self.surface.r, self.surface.q]
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Dupire local variance of `implied` at log-moneyness `y` and time `t`,
+/// with central differences (one-sided in time when `t` is near zero).
+fn dupire_variance<S: ImpliedVolatilitySurface>(implied: &S, y: f64, t: f64) -> f64 {
+    let (h_y, h_t) = (DUPIRE_MONEYNESS_STEP, DUPIRE_TIME_STEP.min(0.5 * t));
+
+    let w = implied.total_variance(y, t);
+    let w_up = implied.total_variance(y + h_y, t);
+    let w_down = implied.total_variance(y - h_y, t);
+
+    let dw_dt =
+        (implied.total_variance(y, t + h_t) - implied.total_variance(y, t - h_t)) / (2.0 * h_t);
+    let dw_dy = (w_up - w_down) / (2.0 * h_y);
+    let d2w_dy2 = (w_up - 2.0 * w + w_down) / (h_y * h_y);
+
+    let denominator = 1.0 - y / w * dw_dy
+        + 0.25 * (-0.25 - 1.0 / w + y * y / (w * w)) * dw_dy * dw_dy
+        + 0.5 * d2w_dy2;
+
+    dw_dt / denominator
+}
+
+/// Index of the grid point at or below `x`, and the weight of the next one.
+/// Outside the grid, the nearest end point gets all the weight.
+fn bracket(grid: &[f64], x: f64) -> (usize, f64) {
+    let n = grid.len();
+
+    if n == 1 || x <= grid[0] {
+        return (0, 0.0);
+    }
+    if x >= grid[n - 1] {
+        return (n - 1, 0.0);
+    }
+
+    let i = grid.partition_point(|&g| g <= x) - 1;
+
+    (i, (x - grid[i]) / (grid[i + 1] - grid[i]))
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_local_volatility {
+    use super::*;
+    use crate::{BlackScholes73, GeneralisedBlackScholesMerton, MonteCarloEstimate, TypeFlag, SVI};
+    use RustQuant_stochastics::StochasticProcessConfig;
+    use RustQuant_utils::assert_approx_equal;
+
+    /// Implied variance with no smile, $w(T) = a T + b T^2$.
+    struct TermStructure {
+        a: f64,
+        b: f64,
+    }
+
+    impl ImpliedVolatilitySurface for TermStructure {
+        fn total_variance(&self, _y: f64, t: f64) -> f64 {
+            self.a * t + self.b * t * t
+        }
+    }
+
+    fn grid(start: f64, end: f64, n: usize) -> Vec<f64> {
+        (0..n)
+            .map(|i| start + (end - start) * i as f64 / (n - 1) as f64)
+            .collect()
+    }
+
+    #[test]
+    fn test_flat_and_term_structure() {
+        // Flat implied volatility is flat local volatility.
+        let flat = TermStructure { a: 0.04, b: 0.0 };
+        let surface = LocalVolatilitySurface::from_implied(
+            &flat,
+            100.0,
+            0.03,
+            0.01,
+            grid(0.1, 2.0, 20),
+            grid(-1.0, 1.0, 21),
+        )
+        .unwrap();
+
+        for (s, t) in [(50.0, 0.05), (100.0, 0.7), (180.0, 3.0)] {
+            assert_approx_equal!(surface.local_volatility(s, t), 0.2, 1e-8);
+        }
+
+        // Without a smile, the local variance is the forward variance.
+        let term = TermStructure { a: 0.04, b: 0.01 };
+        let surface = LocalVolatilitySurface::from_implied(
+            &term,
+            100.0,
+            0.03,
+            0.01,
+            vec![0.5, 1.0],
+            vec![0.0],
+        )
+        .unwrap();
+
+        assert_approx_equal!(surface.local_volatility(100.0, 0.5), f64::sqrt(0.05), 1e-8);
+        assert_approx_equal!(
+            surface.local_volatility(120.0, 0.75),
+            f64::sqrt(0.055),
+            1e-8
+        );
+    }
+
+    #[test]
+    fn test_arbitrage_is_rejected() {
+        // Total variance falling with time is calendar arbitrage.
+        let falling = TermStructure { a: 0.04, b: -0.05 };
+
+        assert!(LocalVolatilitySurface::from_implied(
+            &falling,
+            100.0,
+            0.0,
+            0.0,
+            vec![0.5, 1.0],
+            vec![0.0]
+        )
+        .is_err());
+        assert!(LocalVolatilitySurface::from_implied(
+            &falling,
+            100.0,
+            0.0,
+            0.0,
+            vec![1.0, 0.5],
+            vec![0.0]
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_monte_carlo_reprices_smile() {
+        // The same smile in implied volatility at every expiry.
+        let (s, r, q, t) = (100.0, 0.03, 0.01, 1.0);
+        let slice = SVI::new(0.02, 0.1, -0.5, 0.05, 0.2).unwrap();
+        let implied = SVISurface {
+            slices: vec![
+                (0.5, slice),
+                (
+                    1.0,
+                    SVI {
+                        a: 2.0 * slice.a,
+                        b: 2.0 * slice.b,
+                        ..slice
+                    },
+                ),
+            ],
+        };
+
+        let surface = LocalVolatilitySurface::from_implied(
+            &implied,
+            s,
+            r,
+            q,
+            grid(0.02, 1.0, 50),
+            grid(-2.0, 2.0, 81),
+        )
+        .unwrap();
+        let process = LocalVolatilityProcess::new(surface);
+
+        let config = StochasticProcessConfig::new(s, 0.0, t, 100, 20_000, false).with_seed(7);
+        let paths = process.euler_maruyama(&config);
+        let discount = f64::exp(-r * t);
+        let forward = s * f64::exp((r - q) * t);
+
+        for k in [85.0, 100.0, 115.0] {
+            let payoffs: Vec<f64> = paths
+                .paths
+                .iter()
+                .map(|path| discount * (path[path.len() - 1] - k).max(0.0))
+                .collect();
+            let estimate = MonteCarloEstimate::from_samples(&payoffs);
+
+            // Black-Scholes on the forward, at the implied volatility.
+            let v = implied.volatility((k / forward).ln(), t);
+            let exact = BlackScholes73::new(forward * discount, r, v).price(k, t, TypeFlag::Call);
+
+            assert!(
+                (estimate.price - exact).abs() < 3.0 * estimate.standard_error + 0.05,
+                "K = {k}: {} vs {exact}",
+                estimate.price
+            );
+        }
+    }
+}
@@ -0,0 +1,20 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023-2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// SVI implied volatility smiles and surfaces.
+pub mod svi;
+pub use svi::*;
+
+/// Dupire local volatility surfaces and processes.
+pub mod local_volatility;
+pub use local_volatility::*;
+
+/// Variance and volatility swaps.
+pub mod variance_swap;
+pub use variance_swap::*;
@@ -0,0 +1,532 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Gatheral's stochastic volatility inspired (SVI) parameterisation of the
+//! implied volatility smile, and surfaces built from SVI slices.
+//!
+//! The raw SVI total implied variance at log-moneyness $k = \ln(K / F)$ is
+//!
+//! $$
+//! w(k) = a + b \left( \rho (k - m) + \sqrt{(k - m)^2 + \sigma^2} \right)
+//! $$
+//!
+//! For fixed $m$ and $\sigma$, $w$ is linear in $a$, $b \rho$ and $b$, so a
+//! slice is fitted by least squares in those three, nested in a Nelder-Mead
+//! search over $m$ and $\sigma$ (the quasi-explicit method of Zeliade, 2009).
+
+use argmin::core::{CostFunction, Executor, State};
+use argmin::solver::neldermead::NelderMead;
+use nalgebra::{DMatrix, DVector};
+use RustQuant_error::RustQuantError;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// CONSTANTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Maximum Nelder-Mead iterations when fitting a slice.
+const SVI_MAX_ITERATIONS: u64 = 2_000;
+
+/// Weight of the penalty on parameters that violate the SVI constraints.
+const SVI_PENALTY: f64 = 1e6;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Raw SVI parameters of one implied volatility smile (a single expiry).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SVI {
+    /// Overall level of total variance ($a$).
+    pub a: f64,
+
+    /// Slope of the wings ($b \geq 0$).
+    pub b: f64,
+
+    /// Skew, as the asymmetry of the wings ($|\rho| < 1$).
+    pub rho: f64,
+
+    /// Log-moneyness of the smile's vertex ($m$).
+    pub m: f64,
+
+    /// Curvature at the vertex ($\sigma > 0$).
+    pub sigma: f64,
+}
+
+/// Implied volatility surface of SVI slices, one per expiry.
+///
+/// Between expiries, total variance is interpolated linearly in time at
+/// constant log-moneyness. Before the first expiry and after the last,
+/// implied volatility is held constant.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SVISurface {
+    /// Expiries (in years) and their slices, in increasing order of expiry.
+    pub slices: Vec<(f64, SVI)>,
+}
+
+/// Least squares fit of one slice, for a trial vertex and curvature.
+struct SVICalibrator {
+    log_moneyness: Vec<f64>,
+    total_variance: Vec<f64>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl SVI {
+    /// Create a new raw SVI slice.
+    ///
+    /// # Errors
+    /// - `RustQuantError::InvalidArgument` if $b < 0$, $|\rho| \geq 1$,
+    ///   $\sigma \leq 0$, or the minimum total variance
+    ///   $a + b \sigma \sqrt{1 - \rho^2}$ is negative.
+    pub fn new(a: f64, b: f64, rho: f64, m: f64, sigma: f64) -> Result<Self, RustQuantError> {
+        if b < 0.0 || rho.abs() >= 1.0 || sigma <= 0.0 {
+            return Err(RustQuantError::InvalidArgument(
+                "SVI needs b >= 0, |rho| < 1 and sigma > 0.".to_string(),
+            ));
+        }
+
+        let svi = Self {
+            a,
+            b,
+            rho,
+            m,
+            sigma,
+        };
+
+        if svi.minimum_total_variance() < 0.0 {
+            return Err(RustQuantError::InvalidArgument(
+                "SVI total variance must be non-negative.".to_string(),
+            ));
+        }
+
+        Ok(svi)
+    }
+
+    /// Fit a slice to implied volatilities at one expiry, by least squares
+    /// in total variance.
+    ///
+    /// # Arguments:
+    /// * `forward` - Forward price to the expiry.
+    /// * `strikes` - Strikes of the quotes.
+    /// * `volatilities` - Implied volatilities of the quotes.
+    /// * `t` - Time to expiry (in years).
+    ///
+    /// # Errors
+    /// - `RustQuantError::UnequalLength` if there are not as many strikes as
+    ///   volatilities.
+    /// - `RustQuantError::InvalidArgument` if there are fewer than five
+    ///   quotes, or the forward, a strike or `t` is not positive.
+    /// - `RustQuantError::ComputationError` if the optimiser fails, or no
+    ///   valid slice is found.
+    pub fn fit(
+        forward: f64,
+        strikes: &[f64],
+        volatilities: &[f64],
+        t: f64,
+    ) -> Result<Self, RustQuantError> {
+        if strikes.len() != volatilities.len() {
+            return Err(RustQuantError::UnequalLength);
+        }
+        if strikes.len() < 5 {
+            return Err(RustQuantError::InvalidArgument(
+                "At least five quotes are needed to fit SVI.".to_string(),
+            ));
+        }
+        if forward <= 0.0 || t <= 0.0 || strikes.iter().any(|&k| k <= 0.0) {
+            return Err(RustQuantError::InvalidArgument(
+                "The forward, strikes and time to expiry must be positive.".to_string(),
+            ));
+        }
+
+        let calibrator = SVICalibrator {
+            log_moneyness: strikes.iter().map(|k| (k / forward).ln()).collect(),
+            total_variance: volatilities.iter().map(|v| v * v * t).collect(),
+        };
+
+        // Start with the vertex at the lowest quote.
+        let (vertex, _) = calibrator
+            .log_moneyness
+            .iter()
+            .zip(&calibrator.total_variance)
+            .min_by(|x, y| x.1.total_cmp(y.1))
+            .expect("There are at least five quotes.");
+        let start = [*vertex, 0.1_f64.ln()];
+
+        let simplex = vec![
+            start.to_vec(),
+            vec![start[0] + 0.1, start[1]],
+            vec![start[0], start[1] + 1.0],
+        ];
+
+        let solver = NelderMead::new(simplex)
+            .with_sd_tolerance(1e-14)
+            .map_err(|error| RustQuantError::ComputationError(error.to_string()))?;
+
+        let result = Executor::new(&calibrator, solver)
+            .configure(|state| state.max_iters(SVI_MAX_ITERATIONS))
+            .run()
+            .map_err(|error| RustQuantError::ComputationError(error.to_string()))?;
+
+        let best = result
+            .state()
+            .get_best_param()
+            .ok_or_else(|| RustQuantError::ComputationError("SVI fit failed.".to_string()))?;
+
+        let (a, c, b) = calibrator
+            .linear_parameters(best[0], best[1].exp())
+            .ok_or_else(|| RustQuantError::ComputationError("SVI fit failed.".to_string()))?;
+
+        Self::new(a, b, c / b, best[0], best[1].exp())
+            .map_err(|_| RustQuantError::ComputationError("No valid SVI slice found.".to_string()))
+    }
+
+    /// Total implied variance $w(k) = \sigma_{BS}^2 T$ at log-moneyness
+    /// $k = \ln(K / F)$.
+    pub fn total_variance(&self, k: f64) -> f64 {
+        let x = k - self.m;
+
+        self.a + self.b * (self.rho * x + (x * x + self.sigma * self.sigma).sqrt())
+    }
+
+    /// Implied volatility at `strike`, for the given forward and time to
+    /// expiry (in years).
+    pub fn volatility(&self, forward: f64, strike: f64, t: f64) -> f64 {
+        (self.total_variance((strike / forward).ln()) / t).sqrt()
+    }
+
+    /// Minimum total variance over all strikes, $a + b \sigma \sqrt{1 - \rho^2}$.
+    pub fn minimum_total_variance(&self) -> f64 {
+        self.a + self.b * self.sigma * (1.0 - self.rho * self.rho).sqrt()
+    }
+
+    /// Durrleman's function $g(k)$, proportional to the risk-neutral
+    /// density at log-moneyness `k`. The slice is free of butterfly
+    /// arbitrage where $g(k) \geq 0$.
+    ///
+    /// $$
+    /// g(k) = \left(1 - \frac{k w'}{2 w}\right)^2
+    ///      - \frac{w'^2}{4} \left(\frac{1}{w} + \frac{1}{4}\right)
+    ///      + \frac{w''}{2}
+    /// $$
+    pub fn durrleman(&self, k: f64) -> f64 {
+        let x = k - self.m;
+        let root = (x * x + self.sigma * self.sigma).sqrt();
+
+        let w = self.total_variance(k);
+        let w1 = self.b * (self.rho + x / root);
+        let w2 = self.b * self.sigma * self.sigma / (root * root * root);
+
+        (1.0 - k * w1 / (2.0 * w)).powi(2) - 0.25 * w1 * w1 * (1.0 / w + 0.25) + 0.5 * w2
+    }
+
+    /// Whether the slice is free of butterfly arbitrage.
+    ///
+    /// The wings are checked with Lee's moment bound $b (1 + |\rho|) \leq 2$,
+    /// and Durrleman's condition on a fine grid of log-moneyness around the
+    /// vertex (beyond which $g$ tends to its wing limit, which is
+    /// non-negative under the moment bound).
+    pub fn is_butterfly_arbitrage_free(&self) -> bool {
+        const POINTS: usize = 2_001;
+
+        if self.b * (1.0 + self.rho.abs()) > 2.0 || self.minimum_total_variance() <= 0.0 {
+            return false;
+        }
+
+        let width = 10.0 * self.sigma.max(0.1) + self.m.abs();
+
+        (0..POINTS)
+            .map(|i| self.m - width + 2.0 * width * i as f64 / (POINTS - 1) as f64)
+            .all(|k| self.durrleman(k) >= 0.0)
+    }
+}
+
+impl SVICalibrator {
+    /// Least squares $(a, b \rho, b)$ for vertex `m` and curvature `sigma`.
+    fn linear_parameters(&self, m: f64, sigma: f64) -> Option<(f64, f64, f64)> {
+        let n = self.log_moneyness.len();
+
+        let regressors: Vec<f64> = self
+            .log_moneyness
+            .iter()
+            .flat_map(|k| {
+                let x = k - m;
+                [1.0, x, (x * x + sigma * sigma).sqrt()]
+            })
+            .collect();
+        let X = DMatrix::from_row_slice(n, 3, &regressors);
+        let y = DVector::from_column_slice(&self.total_variance);
+
+        let beta = X
+            .tr_mul(&X)
+            .svd(true, true)
+            .solve(&X.tr_mul(&y), f64::EPSILON)
+            .ok()?;
+
+        Some((beta[0], beta[1], beta[2]))
+    }
+}
+
+impl CostFunction for &SVICalibrator {
+    type Param = Vec<f64>;
+    type Output = f64;
+
+    /// Sum of squared total variance errors, for vertex `params[0]` and log
+    /// curvature `params[1]`, plus a penalty if the best linear parameters
+    /// break the SVI constraints.
+    fn cost(&self, params: &Self::Param) -> Result<Self::Output, argmin::core::Error> {
+        let (m, sigma) = (params[0], params[1].exp());
+
+        let Some((a, c, b)) = self.linear_parameters(m, sigma) else {
+            return Ok(f64::INFINITY);
+        };
+
+        let errors: f64 = self
+            .log_moneyness
+            .iter()
+            .zip(&self.total_variance)
+            .map(|(k, w)| {
+                let x = k - m;
+                (a + c * x + b * (x * x + sigma * sigma).sqrt() - w).powi(2)
+            })
+            .sum();
+
+        // b >= 0, |b rho| <= b, and non-negative minimum total variance.
+        let violation = (-b).max(0.0)
+            + (c.abs() - b).max(0.0)
+            + (-(a + sigma * (b * b - c * c).max(0.0).sqrt())).max(0.0);
+
+        Ok(errors + SVI_PENALTY * violation * violation)
+    }
+}
+
+impl SVISurface {
+    /// Fit an SVI slice to the quotes at each expiry.
+    ///
+    /// # Arguments:
+    /// * `expiries` - Times to expiry (in years), in increasing order.
+    /// * `forwards` - Forward price to each expiry.
+    /// * `strikes` - Strikes of the quotes at each expiry.
+    /// * `volatilities` - Implied volatilities of the quotes at each expiry.
+    ///
+    /// # Errors
+    /// - `RustQuantError::UnequalLength` if the inputs do not have one entry
+    ///   per expiry.
+    /// - `RustQuantError::InvalidArgument` if there are no expiries, or they
+    ///   are not increasing.
+    /// - Any error from fitting a slice (see `SVI::fit`).
+    pub fn fit(
+        expiries: &[f64],
+        forwards: &[f64],
+        strikes: &[Vec<f64>],
+        volatilities: &[Vec<f64>],
+    ) -> Result<Self, RustQuantError> {
+        let n = expiries.len();
+
+        if forwards.len() != n || strikes.len() != n || volatilities.len() != n {
+            return Err(RustQuantError::UnequalLength);
+        }
+        if n == 0 || expiries.windows(2).any(|pair| pair[1] <= pair[0]) {
+            return Err(RustQuantError::InvalidArgument(
+                "The expiries must be non-empty and increasing.".to_string(),
+            ));
+        }
+
+        let slices = (0..n)
+            .map(|i| {
+                SVI::fit(forwards[i], &strikes[i], &volatilities[i], expiries[i])
+                    .map(|svi| (expiries[i], svi))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self { slices })
+    }
+
+    /// Total implied variance at log-moneyness `k` (relative to the forward
+    /// to `t`) and time to expiry `t` (in years).
+    pub fn total_variance(&self, k: f64, t: f64) -> f64 {
+        let index = self.slices.partition_point(|&(expiry, _)| expiry < t);
+
+        match index {
+            // Constant implied volatility outside the quoted expiries.
+            0 => {
+                let (t_0, svi) = self.slices[0];
+                svi.total_variance(k) * t / t_0
+            }
+            i if i == self.slices.len() => {
+                let (t_n, svi) = self.slices[i - 1];
+                svi.total_variance(k) * t / t_n
+            }
+            i => {
+                let (t_0, lower) = self.slices[i - 1];
+                let (t_1, upper) = self.slices[i];
+                let weight = (t - t_0) / (t_1 - t_0);
+
+                (1.0 - weight) * lower.total_variance(k) + weight * upper.total_variance(k)
+            }
+        }
+    }
+
+    /// Implied volatility at log-moneyness `k` (relative to the forward to
+    /// `t`) and time to expiry `t` (in years).
+    pub fn volatility(&self, k: f64, t: f64) -> f64 {
+        (self.total_variance(k, t) / t).sqrt()
+    }
+
+    /// Whether every slice is free of butterfly arbitrage.
+    pub fn is_butterfly_arbitrage_free(&self) -> bool {
+        self.slices
+            .iter()
+            .all(|(_, svi)| svi.is_butterfly_arbitrage_free())
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_svi {
+    use super::*;
+    use RustQuant_utils::{assert_approx_equal, RUSTQUANT_EPSILON};
+
+    // A typical equity index smile.
+    fn equity_slice() -> SVI {
+        SVI::new(0.02, 0.12, -0.6, 0.05, 0.15).unwrap()
+    }
+
+    fn quotes(svi: &SVI, forward: f64, t: f64) -> (Vec<f64>, Vec<f64>) {
+        let strikes: Vec<f64> = (0..11).map(|i| forward * (0.6 + 0.08 * i as f64)).collect();
+        let volatilities = strikes
+            .iter()
+            .map(|&k| svi.volatility(forward, k, t))
+            .collect();
+
+        (strikes, volatilities)
+    }
+
+    #[test]
+    fn test_total_variance() {
+        let svi = equity_slice();
+        let x: f64 = 0.3 - 0.05;
+
+        assert_approx_equal!(
+            svi.total_variance(0.3),
+            0.02 + 0.12 * (-0.6 * x + (x * x + 0.15 * 0.15).sqrt()),
+            RUSTQUANT_EPSILON
+        );
+        assert_approx_equal!(
+            svi.total_variance(0.05 + 0.6 * 0.15 / 0.8),
+            svi.minimum_total_variance(),
+            1e-12
+        );
+    }
+
+    #[test]
+    fn test_fit_recovers_parameters() {
+        let svi = equity_slice();
+        let (strikes, volatilities) = quotes(&svi, 100.0, 0.5);
+
+        let fitted = SVI::fit(100.0, &strikes, &volatilities, 0.5).unwrap();
+
+        assert_approx_equal!(fitted.a, svi.a, 1e-6);
+        assert_approx_equal!(fitted.b, svi.b, 1e-6);
+        assert_approx_equal!(fitted.rho, svi.rho, 1e-5);
+        assert_approx_equal!(fitted.m, svi.m, 1e-5);
+        assert_approx_equal!(fitted.sigma, svi.sigma, 1e-5);
+
+        for &k in &[55.0, 97.0, 150.0] {
+            assert_approx_equal!(
+                fitted.volatility(100.0, k, 0.5),
+                svi.volatility(100.0, k, 0.5),
+                1e-6
+            );
+        }
+    }
+
+    #[test]
+    fn test_butterfly_arbitrage() {
+        assert!(equity_slice().is_butterfly_arbitrage_free());
+
+        // Wings steeper than Lee's moment bound.
+        assert!(!SVI::new(0.02, 1.5, -0.6, 0.0, 0.1)
+            .unwrap()
+            .is_butterfly_arbitrage_free());
+
+        // A sharp vertex with little variance gives a negative density
+        // (Gatheral and Jacquier, 2014), although the wings are fine.
+        let svi = SVI::new(-0.0410, 0.1331, 0.3060, 0.3586, 0.4153).unwrap();
+        assert!(svi.b * (1.0 + svi.rho.abs()) <= 2.0);
+        assert!(!svi.is_butterfly_arbitrage_free());
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        assert!(SVI::new(0.02, -0.1, 0.0, 0.0, 0.1).is_err());
+        assert!(SVI::new(0.02, 0.1, 1.0, 0.0, 0.1).is_err());
+        assert!(SVI::new(0.02, 0.1, 0.0, 0.0, 0.0).is_err());
+        assert!(SVI::new(-0.1, 0.1, 0.0, 0.0, 0.1).is_err());
+
+        assert!(SVI::fit(100.0, &[90.0, 100.0], &[0.2, 0.2], 1.0).is_err());
+        assert!(SVI::fit(100.0, &[90.0; 5], &[0.2; 4], 1.0).is_err());
+    }
+
+    #[test]
+    fn test_surface() {
+        let short = equity_slice();
+        let long = SVI::new(0.05, 0.1, -0.5, 0.1, 0.3).unwrap();
+
+        let (short_strikes, short_vols) = quotes(&short, 100.0, 0.25);
+        let (long_strikes, long_vols) = quotes(&long, 102.0, 1.0);
+
+        let surface = SVISurface::fit(
+            &[0.25, 1.0],
+            &[100.0, 102.0],
+            &[short_strikes, long_strikes],
+            &[short_vols, long_vols],
+        )
+        .unwrap();
+
+        assert!(surface.is_butterfly_arbitrage_free());
+
+        let k = -0.1;
+        assert_approx_equal!(
+            surface.total_variance(k, 0.25),
+            short.total_variance(k),
+            1e-7
+        );
+        assert_approx_equal!(surface.total_variance(k, 1.0), long.total_variance(k), 1e-7);
+        assert_approx_equal!(
+            surface.total_variance(k, 0.5),
+            (2.0 * short.total_variance(k) + long.total_variance(k)) / 3.0,
+            1e-7
+        );
+
+        // Constant implied volatility outside the quoted expiries.
+        assert_approx_equal!(
+            surface.volatility(k, 0.1),
+            surface.volatility(k, 0.25),
+            1e-12
+        );
+        assert_approx_equal!(
+            surface.volatility(k, 2.0),
+            surface.volatility(k, 1.0),
+            1e-12
+        );
+
+        assert!(SVISurface::fit(
+            &[1.0, 0.5],
+            &[100.0; 2],
+            &[vec![], vec![]],
+            &[vec![], vec![]]
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,355 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Variance and volatility swaps.
+//!
+//! The fair variance strike is replicated statically by a log contract,
+//! i.e. a strip of out-of-the-money options weighted by $1 / K^2$
+//! (Demeterfi, Derman, Kamal and Zou, 1999):
+//!
+//! $$
+//! K_{var} = \frac{2 e^{rT}}{T} \int_0^\infty \frac{Q(K)}{K^2} dK
+//! $$
+//!
+//! where $Q(K)$ is the put price below the forward and the call price
+//! above it. A volatility swap cannot be replicated statically; its fair
+//! strike is approximated from the variance strike with a convexity
+//! adjustment (Brockhaus and Long, 2000).
+
+use super::ImpliedVolatilitySurface;
+use crate::{Black76, GeneralisedBlackScholesMerton, Payoff, TypeFlag};
+use RustQuant_error::RustQuantError;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Variance swap: pays `variance_notional` times the realised variance
+/// less the strike at maturity.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VarianceSwap {
+    /// Notional per unit of variance.
+    pub variance_notional: f64,
+
+    /// Variance strike (annualised, e.g. 0.04 for 20% volatility).
+    pub strike: f64,
+
+    /// Time to maturity (in years).
+    pub maturity: f64,
+}
+
+/// Volatility swap: pays `vega_notional` times the realised volatility
+/// less the strike at maturity.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VolatilitySwap {
+    /// Notional per unit of volatility.
+    pub vega_notional: f64,
+
+    /// Volatility strike (annualised, e.g. 0.2).
+    pub strike: f64,
+
+    /// Time to maturity (in years).
+    pub maturity: f64,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl VarianceSwap {
+    /// Create a new variance swap.
+    pub fn new(variance_notional: f64, strike: f64, maturity: f64) -> Self {
+        Self {
+            variance_notional,
+            strike,
+            maturity,
+        }
+    }
+
+    /// Value of the swap at inception, given the fair variance strike.
+    pub fn value(&self, fair_variance: f64, r: f64) -> f64 {
+        self.variance_notional * (-r * self.maturity).exp() * (fair_variance - self.strike)
+    }
+}
+
+impl VolatilitySwap {
+    /// Create a new volatility swap.
+    pub fn new(vega_notional: f64, strike: f64, maturity: f64) -> Self {
+        Self {
+            vega_notional,
+            strike,
+            maturity,
+        }
+    }
+
+    /// Value of the swap at inception, given the fair volatility strike.
+    pub fn value(&self, fair_volatility: f64, r: f64) -> f64 {
+        self.vega_notional * (-r * self.maturity).exp() * (fair_volatility - self.strike)
+    }
+}
+
+impl Payoff for VarianceSwap {
+    type Underlying = Vec<f64>;
+
+    fn payoff(&self, underlying: Self::Underlying) -> f64 {
+        self.variance_notional * (realised_variance(&underlying, self.maturity) - self.strike)
+    }
+}
+
+impl Payoff for VolatilitySwap {
+    type Underlying = Vec<f64>;
+
+    fn payoff(&self, underlying: Self::Underlying) -> f64 {
+        let volatility = realised_variance(&underlying, self.maturity).sqrt();
+
+        self.vega_notional * (volatility - self.strike)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Annualised realised variance of the fixings `path` over `maturity`
+/// years, from the squared log returns (with zero mean).
+pub fn realised_variance(path: &[f64], maturity: f64) -> f64 {
+    path.windows(2)
+        .map(|pair| (pair[1] / pair[0]).ln().powi(2))
+        .sum::<f64>()
+        / maturity
+}
+
+/// Fair variance strike replicated from a strip of option prices, with
+/// the discretisation of the CBOE VIX methodology.
+///
+/// $K_0$ is the highest strike at or below the forward. Puts are used
+/// below it, calls above it, and their average at it, and the strip is
+/// corrected by $-(F / K_0 - 1)^2 / T$.
+///
+/// # Arguments:
+/// * `forward` - Forward price of the underlying to maturity.
+/// * `r` - Risk-free rate.
+/// * `t` - Time to maturity (in years).
+/// * `strikes` - Strikes of the strip, increasing.
+/// * `calls` - Call prices at the strikes.
+/// * `puts` - Put prices at the strikes.
+///
+/// # Errors
+/// - `RustQuantError::UnequalLength` if the prices do not match the strikes.
+/// - `RustQuantError::InvalidArgument` if there are fewer than two strikes,
+///   they are not positive and increasing, or the lowest is above the forward.
+pub fn fair_variance_from_strip(
+    forward: f64,
+    r: f64,
+    t: f64,
+    strikes: &[f64],
+    calls: &[f64],
+    puts: &[f64],
+) -> Result<f64, RustQuantError> {
+    if strikes.len() != calls.len() || strikes.len() != puts.len() {
+        return Err(RustQuantError::UnequalLength);
+    }
+
+    let n = strikes.len();
+
+    if n < 2
+        || strikes[0] <= 0.0
+        || strikes.windows(2).any(|pair| pair[0] >= pair[1])
+        || strikes[0] > forward
+    {
+        return Err(RustQuantError::InvalidArgument(
+            "The strikes must be positive and increasing, with the lowest at or below the forward."
+                .to_string(),
+        ));
+    }
+
+    let k_0 = strikes.partition_point(|&k| k <= forward) - 1;
+
+    let strip: f64 = (0..n)
+        .map(|i| {
+            let spacing = match i {
+                0 => strikes[1] - strikes[0],
+                i if i == n - 1 => strikes[n - 1] - strikes[n - 2],
+                i => 0.5 * (strikes[i + 1] - strikes[i - 1]),
+            };
+            let price = match i.cmp(&k_0) {
+                std::cmp::Ordering::Less => puts[i],
+                std::cmp::Ordering::Equal => 0.5 * (puts[i] + calls[i]),
+                std::cmp::Ordering::Greater => calls[i],
+            };
+
+            spacing / (strikes[i] * strikes[i]) * price
+        })
+        .sum();
+
+    Ok(2.0 * (r * t).exp() / t * strip - (forward / strikes[k_0] - 1.0).powi(2) / t)
+}
+
+/// Fair variance strike replicated from an implied volatility surface,
+/// with a strip of `n_strikes` options priced by Black (1976) on the
+/// forward, out to `n_std` at-the-money standard deviations either side.
+///
+/// # Arguments:
+/// * `surface` - The implied volatility surface.
+/// * `forward` - Forward price of the underlying to maturity.
+/// * `r` - Risk-free rate.
+/// * `t` - Time to maturity (in years).
+/// * `n_strikes` - Number of strikes in the strip.
+/// * `n_std` - Width of the strip, in at-the-money standard deviations.
+///
+/// # Errors
+/// - `RustQuantError::InvalidArgument` if the strip has fewer than two
+///   strikes, or the at-the-money variance is not positive.
+pub fn fair_variance_from_surface<S: ImpliedVolatilitySurface>(
+    surface: &S,
+    forward: f64,
+    r: f64,
+    t: f64,
+    n_strikes: usize,
+    n_std: f64,
+) -> Result<f64, RustQuantError> {
+    let atm_variance = surface.total_variance(0.0, t);
+
+    if n_strikes < 2 || atm_variance <= 0.0 {
+        return Err(RustQuantError::InvalidArgument(
+            "The strip needs two strikes and a positive at-the-money variance.".to_string(),
+        ));
+    }
+
+    let width = n_std * atm_variance.sqrt();
+    let log_moneyness: Vec<f64> = (0..n_strikes)
+        .map(|i| -width + 2.0 * width * i as f64 / (n_strikes - 1) as f64)
+        .collect();
+
+    let strikes: Vec<f64> = log_moneyness.iter().map(|y| forward * y.exp()).collect();
+    let (calls, puts): (Vec<f64>, Vec<f64>) = log_moneyness
+        .iter()
+        .zip(&strikes)
+        .map(|(&y, &k)| {
+            let black = Black76::new(forward, r, (surface.total_variance(y, t) / t).sqrt());
+
+            (
+                black.price(k, t, TypeFlag::Call),
+                black.price(k, t, TypeFlag::Put),
+            )
+        })
+        .unzip();
+
+    fair_variance_from_strip(forward, r, t, &strikes, &calls, &puts)
+}
+
+/// Fair volatility swap strike from the fair variance strike, with the
+/// convexity adjustment of Brockhaus and Long (2000):
+///
+/// $$
+/// K_{vol} \approx \sqrt{K_{var}} - \frac{Var(\sigma_R^2)}{8 K_{var}^{3/2}}
+/// $$
+///
+/// # Arguments:
+/// * `fair_variance` - Fair variance strike.
+/// * `variance_of_variance` - Variance of the realised variance $\sigma_R^2$.
+pub fn fair_volatility(fair_variance: f64, variance_of_variance: f64) -> f64 {
+    fair_variance.sqrt() - variance_of_variance / (8.0 * fair_variance.powf(1.5))
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_variance_swap {
+    use super::*;
+    use crate::{MonteCarloEngine, SVISurface, SVI};
+    use RustQuant_stochastics::GeometricBrownianMotion;
+    use RustQuant_utils::assert_approx_equal;
+
+    /// Flat implied volatility.
+    struct Flat(f64);
+
+    impl ImpliedVolatilitySurface for Flat {
+        fn total_variance(&self, _y: f64, t: f64) -> f64 {
+            self.0 * self.0 * t
+        }
+    }
+
+    #[test]
+    fn test_flat_surface_replicates_variance() {
+        let fair = fair_variance_from_surface(&Flat(0.2), 105.0, 0.03, 1.0, 401, 8.0).unwrap();
+
+        assert_approx_equal!(fair, 0.04, 1e-4);
+        assert_approx_equal!(
+            VarianceSwap::new(100.0, 0.04, 1.0).value(fair, 0.03),
+            0.0,
+            1e-2
+        );
+    }
+
+    #[test]
+    fn test_skew_raises_fair_variance() {
+        // The put wing is richer than at the money, so the variance strike
+        // is above the at-the-money variance.
+        let slice = SVI::new(0.02, 0.1, -0.6, 0.0, 0.2).unwrap();
+        let surface = SVISurface {
+            slices: vec![(1.0, slice)],
+        };
+
+        let atm = surface.total_variance(0.0, 1.0);
+        let fair = fair_variance_from_surface(&surface, 100.0, 0.0, 1.0, 801, 10.0).unwrap();
+
+        assert!(fair > atm + 1e-3);
+    }
+
+    #[test]
+    fn test_strip_errors() {
+        let strikes = [90.0, 100.0, 110.0];
+
+        assert!(matches!(
+            fair_variance_from_strip(100.0, 0.0, 1.0, &strikes, &[1.0; 2], &[1.0; 3]),
+            Err(RustQuantError::UnequalLength)
+        ));
+        assert!(fair_variance_from_strip(80.0, 0.0, 1.0, &strikes, &[1.0; 3], &[1.0; 3]).is_err());
+    }
+
+    #[test]
+    fn test_monte_carlo_swaps() {
+        // Realised variance of GBM averages its variance; realised
+        // volatility averages a little less, by Jensen's inequality.
+        let engine = MonteCarloEngine::new(
+            GeometricBrownianMotion::new(0.05, 0.2),
+            100.0,
+            0.0,
+            1.0,
+            5_000,
+            252,
+        )
+        .with_seed(23);
+
+        let variance = engine.price_path(&VarianceSwap::new(1.0, 0.04, 1.0));
+        let volatility = engine.price_path(&VolatilitySwap::new(1.0, 0.2, 1.0));
+
+        assert!(variance.price.abs() < 3.0 * variance.standard_error);
+        assert!(volatility.price < 0.0);
+
+        // Realised variance of n returns has variance 2 v^4 / n, which the
+        // convexity adjustment turns into the volatility strike.
+        let adjusted = fair_volatility(0.04, 2.0 * 0.2_f64.powi(4) / 252.0);
+
+        assert!((volatility.price - (adjusted - 0.2)).abs() < 3.0 * volatility.standard_error);
+    }
+
+    #[test]
+    fn test_convexity_adjustment() {
+        assert_approx_equal!(fair_volatility(0.04, 0.0), 0.2, 1e-15);
+        assert_approx_equal!(
+            fair_volatility(0.04, 8e-4),
+            0.2 - 8e-4 / (8.0 * 0.008),
+            1e-15
+        );
+    }
+}
@@ -0,0 +1,189 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Compounding conventions, discount factors, and rate conversions.
+//!
+//! All times are year fractions.
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ENUMS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Interest rate compounding convention.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compounding {
+    /// Simple interest: `1 + r t`.
+    Simple,
+
+    /// Compounded once per year: `(1 + r)^t`.
+    Annual,
+
+    /// Compounded `k` times per year: `(1 + r / k)^(k t)`.
+    Periodic(u32),
+
+    /// Continuously compounded: `exp(r t)`.
+    Continuous,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Growth of one unit invested at `rate` for time `t`.
+///
+/// # Panics
+/// Panics if `compounding` is `Periodic(0)`.
+pub fn compound_factor(rate: f64, t: f64, compounding: Compounding) -> f64 {
+    match compounding {
+        Compounding::Simple => 1.0 + rate * t,
+        Compounding::Annual => (1.0 + rate).powf(t),
+        Compounding::Periodic(k) => {
+            assert!(k > 0, "Compounding frequency must be positive.");
+
+            let k = f64::from(k);
+            (1.0 + rate / k).powf(k * t)
+        }
+        Compounding::Continuous => (rate * t).exp(),
+    }
+}
+
+/// Discount factor for `rate` over time `t`.
+///
+/// # Panics
+/// Panics if `compounding` is `Periodic(0)`.
+pub fn discount_factor(rate: f64, t: f64, compounding: Compounding) -> f64 {
+    1.0 / compound_factor(rate, t, compounding)
+}
+
+/// Rate that grows one unit to `compound` over time `t`.
+///
+/// # Panics
+/// Panics if `t` is not positive, or `compounding` is `Periodic(0)`.
+pub fn implied_rate(compound: f64, t: f64, compounding: Compounding) -> f64 {
+    assert!(t > 0.0, "Time must be positive.");
+
+    match compounding {
+        Compounding::Simple => (compound - 1.0) / t,
+        Compounding::Annual => compound.powf(1.0 / t) - 1.0,
+        Compounding::Periodic(k) => {
+            assert!(k > 0, "Compounding frequency must be positive.");
+
+            let k = f64::from(k);
+            k * (compound.powf(1.0 / (k * t)) - 1.0)
+        }
+        Compounding::Continuous => compound.ln() / t,
+    }
+}
+
+/// Forward rate between `t1` and `t2`, implied by the discount factors
+/// `df1 = P(0, t1)` and `df2 = P(0, t2)`.
+///
+/// # Panics
+/// Panics if `t2 <= t1`, or `compounding` is `Periodic(0)`.
+pub fn forward_rate(df1: f64, df2: f64, t1: f64, t2: f64, compounding: Compounding) -> f64 {
+    assert!(t2 > t1, "The forward period must have positive length.");
+
+    implied_rate(df1 / df2, t2 - t1, compounding)
+}
+
+/// Convert a rate between compounding conventions, so that both
+/// give the same growth over time `t`.
+///
+/// `t` only matters when converting to or from simple interest.
+///
+/// # Panics
+/// Panics if `t` is not positive, or either convention is `Periodic(0)`.
+pub fn convert_rate(rate: f64, from: Compounding, to: Compounding, t: f64) -> f64 {
+    implied_rate(compound_factor(rate, t, from), t, to)
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_compounding {
+    use super::*;
+    use RustQuant_utils::{assert_approx_equal, RUSTQUANT_EPSILON};
+
+    #[test]
+    fn test_annual_to_continuous_round_trip() {
+        let continuous = convert_rate(0.05, Compounding::Annual, Compounding::Continuous, 1.0);
+        let annual = convert_rate(
+            continuous,
+            Compounding::Continuous,
+            Compounding::Annual,
+            1.0,
+        );
+
+        assert_approx_equal!(continuous, 1.05_f64.ln(), RUSTQUANT_EPSILON);
+        assert_approx_equal!(annual, 0.05, RUSTQUANT_EPSILON);
+    }
+
+    #[test]
+    fn test_continuous_discount_factor() {
+        for (r, t) in [(0.05, 1.0), (0.03, 2.5), (-0.01, 0.5)] {
+            assert_approx_equal!(
+                discount_factor(r, t, Compounding::Continuous),
+                (-r * t).exp(),
+                RUSTQUANT_EPSILON
+            );
+        }
+    }
+
+    #[test]
+    fn test_discount_factors() {
+        assert_approx_equal!(
+            discount_factor(0.05, 2.0, Compounding::Simple),
+            1.0 / 1.1,
+            RUSTQUANT_EPSILON
+        );
+        assert_approx_equal!(
+            discount_factor(0.05, 2.0, Compounding::Annual),
+            1.0 / 1.1025,
+            RUSTQUANT_EPSILON
+        );
+        assert_approx_equal!(
+            discount_factor(0.05, 1.0, Compounding::Periodic(2)),
+            1.0 / 1.025_f64.powi(2),
+            RUSTQUANT_EPSILON
+        );
+
+        // Annual is the same as once per year.
+        assert_approx_equal!(
+            discount_factor(0.05, 3.7, Compounding::Periodic(1)),
+            discount_factor(0.05, 3.7, Compounding::Annual),
+            RUSTQUANT_EPSILON
+        );
+    }
+
+    #[test]
+    fn test_forward_rate() {
+        let df1 = discount_factor(0.03, 1.0, Compounding::Continuous);
+        let df2 = discount_factor(0.04, 2.0, Compounding::Continuous);
+
+        let forward = forward_rate(df1, df2, 1.0, 2.0, Compounding::Continuous);
+
+        assert_approx_equal!(forward, 0.05, RUSTQUANT_EPSILON);
+
+        // Investing to t1 and rolling at the forward equals investing to t2.
+        let simple = forward_rate(df1, df2, 1.0, 2.0, Compounding::Simple);
+        assert_approx_equal!(
+            df1 * discount_factor(simple, 1.0, Compounding::Simple),
+            df2,
+            RUSTQUANT_EPSILON
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Compounding frequency must be positive.")]
+    fn test_zero_frequency() {
+        discount_factor(0.05, 1.0, Compounding::Periodic(0));
+    }
+}
@@ -0,0 +1,316 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Elliptical copulas, for sampling dependent uniform variates.
+//!
+//! Samples are vectors of uniforms on `[0, 1]` whose dependence structure
+//! is that of the copula. They can be mapped to any marginal distribution
+//! (default times, returns, etc.) with the marginal's inverse CDF.
+
+use crate::distributions::{gaussian::N, Distribution};
+use nalgebra::{DMatrix, DVector};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use rand_distr::{ChiSquared, StandardNormal};
+use statrs::distribution::{ContinuousCDF, StudentsT};
+use RustQuant_error::RustQuantError;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS & TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Copula trait.
+pub trait Copula {
+    /// Number of variables.
+    fn dimension(&self) -> usize;
+
+    /// Draw one vector of dependent uniforms from the given generator.
+    fn sample_with<R: Rng>(&self, rng: &mut R) -> Vec<f64>;
+
+    /// Draw `n` vectors of dependent uniforms.
+    fn sample(&self, n: usize) -> Vec<Vec<f64>> {
+        let mut rng = rand::thread_rng();
+
+        (0..n).map(|_| self.sample_with(&mut rng)).collect()
+    }
+
+    /// Draw `n` vectors of dependent uniforms, with a seed for reproducibility.
+    fn seedable_sample(&self, n: usize, seed: u64) -> Vec<Vec<f64>> {
+        let mut rng = StdRng::seed_from_u64(seed);
+
+        (0..n).map(|_| self.sample_with(&mut rng)).collect()
+    }
+}
+
+/// Gaussian copula.
+#[derive(Debug, Clone)]
+pub struct GaussianCopula {
+    /// Lower Cholesky factor of the correlation matrix.
+    cholesky: DMatrix<f64>,
+}
+
+/// Student's t copula.
+///
+/// Unlike the Gaussian copula, it has tail dependence: joint extreme
+/// events are more likely, and more so for fewer degrees of freedom.
+#[derive(Debug, Clone)]
+pub struct StudentTCopula {
+    /// Lower Cholesky factor of the correlation matrix.
+    cholesky: DMatrix<f64>,
+
+    /// Degrees of freedom.
+    degrees_of_freedom: f64,
+
+    /// Chi-squared mixing distribution.
+    chi_squared: ChiSquared<f64>,
+
+    /// Univariate t distribution for the marginal transform.
+    students_t: StudentsT,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Validate a correlation matrix and return its lower Cholesky factor.
+///
+/// # Errors
+/// - `RustQuantError::InvalidArgument` if the matrix is not square, does not
+///   have a unit diagonal, or is not symmetric positive definite.
+pub fn correlation_cholesky(correlation: &[Vec<f64>]) -> Result<DMatrix<f64>, RustQuantError> {
+    let n = correlation.len();
+
+    if n == 0 || correlation.iter().any(|row| row.len() != n) {
+        return Err(RustQuantError::InvalidArgument(
+            "The correlation matrix must be square and non-empty.".to_string(),
+        ));
+    }
+
+    for (i, row) in correlation.iter().enumerate() {
+        if (row[i] - 1.0).abs() > 1e-12 {
+            return Err(RustQuantError::InvalidArgument(
+                "The correlation matrix must have a unit diagonal.".to_string(),
+            ));
+        }
+        if row
+            .iter()
+            .zip(correlation.iter())
+            .any(|(x, other)| (x - other[i]).abs() > 1e-12)
+        {
+            return Err(RustQuantError::InvalidArgument(
+                "The correlation matrix must be symmetric.".to_string(),
+            ));
+        }
+    }
+
+    DMatrix::from_fn(n, n, |i, j| correlation[i][j])
+        .cholesky()
+        .map(|cholesky| cholesky.l())
+        .ok_or_else(|| {
+            RustQuantError::InvalidArgument(
+                "The correlation matrix must be positive definite.".to_string(),
+            )
+        })
+}
+
+/// Correlate independent standard normals with the Cholesky factor.
+fn correlate<R: Rng>(cholesky: &DMatrix<f64>, rng: &mut R) -> DVector<f64> {
+    let z = DVector::from_fn(cholesky.nrows(), |_, _| rng.sample(StandardNormal));
+
+    cholesky * z
+}
+
+impl GaussianCopula {
+    /// Create a new Gaussian copula from a correlation matrix.
+    ///
+    /// # Errors
+    /// - `RustQuantError::InvalidArgument` if the matrix is not square,
+    ///   symmetric, positive definite, with a unit diagonal.
+    pub fn new(correlation: Vec<Vec<f64>>) -> Result<Self, RustQuantError> {
+        Ok(Self {
+            cholesky: correlation_cholesky(&correlation)?,
+        })
+    }
+
+    /// Map independent uniforms to dependent uniforms.
+    ///
+    /// The uniforms are mapped to normals with the inverse normal CDF,
+    /// correlated with the Cholesky factor, and mapped back with the normal CDF.
+    ///
+    /// # Panics
+    /// Panics if `uniforms.len() != self.dimension()`.
+    pub fn transform(&self, uniforms: &[f64]) -> Vec<f64> {
+        assert_eq!(uniforms.len(), self.dimension());
+
+        let z = DVector::from_iterator(uniforms.len(), uniforms.iter().map(|&u| N.inv_cdf(u)));
+
+        (&self.cholesky * z).iter().map(|&x| N.cdf(x)).collect()
+    }
+}
+
+impl Copula for GaussianCopula {
+    fn dimension(&self) -> usize {
+        self.cholesky.nrows()
+    }
+
+    fn sample_with<R: Rng>(&self, rng: &mut R) -> Vec<f64> {
+        correlate(&self.cholesky, rng)
+            .iter()
+            .map(|&x| N.cdf(x))
+            .collect()
+    }
+}
+
+impl StudentTCopula {
+    /// Create a new Student's t copula from a correlation matrix
+    /// and the degrees of freedom.
+    ///
+    /// # Errors
+    /// - `RustQuantError::InvalidArgument` if the matrix is not square,
+    ///   symmetric, positive definite, with a unit diagonal,
+    ///   or the degrees of freedom are not positive.
+    pub fn new(
+        correlation: Vec<Vec<f64>>,
+        degrees_of_freedom: f64,
+    ) -> Result<Self, RustQuantError> {
+        if !(degrees_of_freedom > 0.0 && degrees_of_freedom.is_finite()) {
+            return Err(RustQuantError::InvalidArgument(format!(
+                "The degrees of freedom must be positive, got {degrees_of_freedom}."
+            )));
+        }
+
+        Ok(Self {
+            cholesky: correlation_cholesky(&correlation)?,
+            degrees_of_freedom,
+            chi_squared: ChiSquared::new(degrees_of_freedom)?,
+            students_t: StudentsT::new(0.0, 1.0, degrees_of_freedom)
+                .map_err(|e| RustQuantError::InvalidArgument(e.to_string()))?,
+        })
+    }
+
+    /// Degrees of freedom.
+    pub fn degrees_of_freedom(&self) -> f64 {
+        self.degrees_of_freedom
+    }
+}
+
+impl Copula for StudentTCopula {
+    fn dimension(&self) -> usize {
+        self.cholesky.nrows()
+    }
+
+    fn sample_with<R: Rng>(&self, rng: &mut R) -> Vec<f64> {
+        let x = correlate(&self.cholesky, rng);
+        let w: f64 = rng.sample(self.chi_squared);
+        let scale = (self.degrees_of_freedom / w).sqrt();
+
+        x.iter().map(|&x| self.students_t.cdf(x * scale)).collect()
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_copula {
+    use super::*;
+    use std::f64::consts::PI;
+    use RustQuant_utils::assert_approx_equal;
+
+    fn correlation(rho: f64) -> Vec<Vec<f64>> {
+        vec![vec![1.0, rho], vec![rho, 1.0]]
+    }
+
+    /// Kendall's tau of the first two coordinates.
+    fn kendall_tau(samples: &[Vec<f64>]) -> f64 {
+        let n = samples.len();
+        let mut concordance = 0.0;
+
+        for i in 0..n {
+            for j in (i + 1)..n {
+                let sign = (samples[i][0] - samples[j][0]).signum()
+                    * (samples[i][1] - samples[j][1]).signum();
+                concordance += sign;
+            }
+        }
+
+        concordance / (n * (n - 1) / 2) as f64
+    }
+
+    #[test]
+    fn test_rank_correlation() {
+        // For elliptical copulas, tau = 2 / pi * asin(rho).
+        for rho in [-0.4, 0.3, 0.8] {
+            let gaussian = GaussianCopula::new(correlation(rho)).unwrap();
+            let student = StudentTCopula::new(correlation(rho), 4.0).unwrap();
+
+            for samples in [
+                gaussian.seedable_sample(3000, 1),
+                student.seedable_sample(3000, 2),
+            ] {
+                let implied_rho = (0.5 * PI * kendall_tau(&samples)).sin();
+
+                assert_approx_equal!(implied_rho, rho, 0.05);
+            }
+        }
+    }
+
+    #[test]
+    fn test_samples_are_uniform() {
+        let copula = StudentTCopula::new(correlation(0.5), 3.0).unwrap();
+        let samples = copula.seedable_sample(20_000, 3);
+
+        for d in 0..2 {
+            let below_half = samples.iter().filter(|u| u[d] < 0.5).count();
+            let below_tenth = samples.iter().filter(|u| u[d] < 0.1).count();
+
+            assert_approx_equal!(below_half as f64 / 20_000.0, 0.5, 0.02);
+            assert_approx_equal!(below_tenth as f64 / 20_000.0, 0.1, 0.01);
+        }
+    }
+
+    #[test]
+    fn test_student_t_tail_dependence() {
+        let n = 100_000;
+        let q = 0.01;
+
+        let joint_lower_tail =
+            |samples: Vec<Vec<f64>>| samples.iter().filter(|u| u[0] < q && u[1] < q).count();
+
+        let gaussian = GaussianCopula::new(correlation(0.5)).unwrap();
+        let student = StudentTCopula::new(correlation(0.5), 3.0).unwrap();
+
+        let gaussian_tail = joint_lower_tail(gaussian.seedable_sample(n, 4));
+        let student_tail = joint_lower_tail(student.seedable_sample(n, 5));
+
+        assert!(student_tail > 2 * gaussian_tail);
+    }
+
+    #[test]
+    fn test_gaussian_transform() {
+        let copula = GaussianCopula::new(correlation(0.6)).unwrap();
+
+        // The first coordinate is unchanged by the lower Cholesky factor.
+        let u = copula.transform(&[0.3, 0.5]);
+        assert_approx_equal!(u[0], 0.3, 1e-10);
+
+        // The median maps to the median.
+        let u = copula.transform(&[0.5, 0.5]);
+        assert_approx_equal!(u[1], 0.5, 1e-10);
+    }
+
+    #[test]
+    fn test_invalid_correlation() {
+        assert!(GaussianCopula::new(vec![]).is_err());
+        assert!(GaussianCopula::new(vec![vec![1.0, 0.5], vec![0.4, 1.0]]).is_err());
+        assert!(GaussianCopula::new(vec![vec![2.0, 0.5], vec![0.5, 1.0]]).is_err());
+        assert!(GaussianCopula::new(correlation(1.5)).is_err());
+        assert!(StudentTCopula::new(correlation(0.5), 0.0).is_err());
+    }
+}
@@ -8,6 +8,7 @@
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
 use super::Distribution;
+use crate::integration::gauss_legendre_nodes;
 use errorfunctions::RealErrorFunctions;
 use num::Complex;
 use statrs::function::erf;
@@ -309,6 +310,125 @@ impl Distribution for Gaussian {
     }
 }
 
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Bivariate standard normal distribution function,
+/// $P(X \leq x, Y \leq y)$ where $X, Y$ have correlation $\rho$.
+///
+/// Uses the algorithm of Genz (2004), "Numerical computation of rectangular
+/// bivariate and trivariate normal and t probabilities", which is accurate
+/// to about double precision for all correlations.
+/// # Examples
+/// ```
+/// # use RustQuant::utils::assert_approx_equal;
+/// # use RustQuant::math::distributions::*;
+///
+/// // P(X <= 0, Y <= 0) = 1/4 + arcsin(rho) / (2 pi).
+/// let p = bivariate_normal_cdf(0.0, 0.0, 0.5);
+///
+/// assert_approx_equal!(p, 1.0 / 3.0, 1e-15);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `rho` is not in `[-1, 1]`.
+#[must_use]
+pub fn bivariate_normal_cdf(x: f64, y: f64, rho: f64) -> f64 {
+    assert!(
+        (-1.0..=1.0).contains(&rho),
+        "The correlation must be in [-1, 1]."
+    );
+
+    let phi = |x: f64| N.cdf(x);
+
+    // Genz works with upper tail probabilities, P(X > h, Y > k).
+    let (h, mut k) = (-x, -y);
+    let mut hk = h * k;
+
+    // Fewer Gauss-Legendre nodes suffice for small correlations.
+    let n = match rho.abs() {
+        r if r < 0.3 => 6,
+        r if r < 0.75 => 12,
+        _ => 20,
+    };
+    let (nodes, weights) = gauss_legendre_nodes(n);
+
+    if rho.abs() < 0.925 {
+        // Integrate the density over the correlation, from 0 to rho.
+        let hs = 0.5 * (h * h + k * k);
+        let asr = rho.asin();
+
+        let integral: f64 = nodes
+            .iter()
+            .zip(&weights)
+            .map(|(x, w)| {
+                let sn = (0.5 * asr * (x + 1.0)).sin();
+                w * ((sn * hk - hs) / (1.0 - sn * sn)).exp()
+            })
+            .sum();
+
+        return integral * asr / (4.0 * PI) + phi(-h) * phi(-k);
+    }
+
+    // Near perfect correlation, integrate the difference from the
+    // degenerate distribution, with its singularity removed.
+    if rho < 0.0 {
+        k = -k;
+        hk = -hk;
+    }
+
+    let mut bvn = 0.0;
+
+    if rho.abs() < 1.0 {
+        let a_s = (1.0 - rho) * (1.0 + rho);
+        let mut a = a_s.sqrt();
+        let bs = (h - k).powi(2);
+        let c = (4.0 - hk) / 8.0;
+        let d = (12.0 - hk) / 16.0;
+
+        bvn = a
+            * (-0.5 * (bs / a_s + hk)).exp()
+            * (1.0 - c * (bs - a_s) * (1.0 - d * bs / 5.0) / 3.0 + c * d * a_s * a_s / 5.0);
+
+        if hk > -160.0 {
+            let b = bs.sqrt();
+            bvn -= (-0.5 * hk).exp()
+                * (2.0 * PI).sqrt()
+                * phi(-b / a)
+                * b
+                * (1.0 - c * bs * (1.0 - d * bs / 5.0) / 3.0);
+        }
+
+        a *= 0.5;
+
+        for (x, w) in nodes.iter().zip(&weights) {
+            let xs = (a * (x + 1.0)).powi(2);
+            let rs = (1.0 - xs).sqrt();
+
+            bvn += a
+                * w
+                * ((-bs / (2.0 * xs) - hk / (1.0 + rs)).exp() / rs
+                    - (-0.5 * (bs / xs + hk)).exp() * (1.0 + c * xs * (1.0 + d * xs)));
+        }
+
+        bvn /= -2.0 * PI;
+    }
+
+    if rho > 0.0 {
+        bvn + phi(-h.max(k))
+    } else {
+        let bvn = -bvn;
+
+        match (k > h, h < 0.0) {
+            (true, true) => bvn + phi(k) - phi(h),
+            (true, false) => bvn + phi(-h) - phi(-k),
+            (false, _) => bvn,
+        }
+    }
+}
+
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // UNIT TESTS
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -411,4 +531,52 @@ mod tests_gaussian {
 
         assert_approx_equal!(normal.entropy(), 1.418_938_533_204_672_7, EPS);
     }
+
+    #[test]
+    fn test_bivariate_normal_cdf() {
+        let phi = |x: f64| N.cdf(x);
+
+        // Orthant probabilities are known exactly.
+        for rho in [-0.99, -0.95, -0.5, -0.1, 0.0, 0.2, 0.5, 0.8, 0.93, 0.999] {
+            let expected = 0.25 + f64::asin(rho) / (2.0 * PI);
+            assert_approx_equal!(bivariate_normal_cdf(0.0, 0.0, rho), expected, 1e-15);
+        }
+
+        // Independence and perfect (anti-)correlation.
+        for (x, y) in [(-1.5, 0.3), (0.7, 2.1), (-0.4, -2.2), (1.2, -0.6)] {
+            assert_approx_equal!(bivariate_normal_cdf(x, y, 0.0), phi(x) * phi(y), EPS);
+            assert_approx_equal!(bivariate_normal_cdf(x, y, 1.0), phi(x.min(y)), EPS);
+            assert_approx_equal!(
+                bivariate_normal_cdf(x, y, -1.0),
+                (phi(x) + phi(y) - 1.0).max(0.0),
+                EPS
+            );
+
+            // P(X <= x, Y <= y) + P(X <= x, -Y <= -y) = P(X <= x).
+            for rho in [-0.97, -0.6, 0.35, 0.95] {
+                assert_approx_equal!(
+                    bivariate_normal_cdf(x, y, rho) + bivariate_normal_cdf(x, -y, -rho),
+                    phi(x),
+                    1e-15
+                );
+            }
+        }
+
+        // Reference values by adaptive quadrature, in each of the three regimes.
+        assert_approx_equal!(
+            bivariate_normal_cdf(0.5, -0.3, 0.7),
+            0.356_783_634_796_854_7,
+            1e-15
+        );
+        assert_approx_equal!(
+            bivariate_normal_cdf(1.3, 0.9, 0.96),
+            0.813_753_696_191_854_9,
+            1e-15
+        );
+        assert_approx_equal!(
+            bivariate_normal_cdf(-0.8, 1.1, -0.97),
+            0.079_508_712_723_620_99,
+            1e-15
+        );
+    }
 }
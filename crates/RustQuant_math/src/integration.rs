@@ -75,6 +75,70 @@ where
     integral
 }
 
+/// Integrates a function from `a` to `b` with the `n`-point Gauss-Legendre rule.
+/// Exact for polynomials of degree up to $2n - 1$, so well suited to smooth
+/// integrands, e.g. Fourier inversion integrals split into panels.
+pub fn gauss_legendre<F>(f: F, a: f64, b: f64, n: usize) -> f64
+where
+    F: Fn(f64) -> f64,
+{
+    let (nodes, weights) = gauss_legendre_nodes(n);
+
+    // Same change of variables as `integrate`.
+    let c = 0.5 * (b - a);
+    let d = 0.5 * (a + b);
+
+    c * nodes
+        .iter()
+        .zip(&weights)
+        .map(|(x, w)| w * f(c * x + d))
+        .sum::<f64>()
+}
+
+/// Nodes and weights of the `n`-point Gauss-Legendre rule over $[-1, +1]$.
+///
+/// The nodes are the roots of the Legendre polynomial $P_n$, found by
+/// Newton's method, and the weights are $w_k = 2 / ((1 - x_k^2) P_n'(x_k)^2)$.
+pub fn gauss_legendre_nodes(n: usize) -> (Vec<f64>, Vec<f64>) {
+    assert!(n > 0, "The number of nodes must be positive.");
+
+    let mut nodes = vec![0.0; n];
+    let mut weights = vec![0.0; n];
+
+    // The roots are symmetric about zero, so only find the positive half.
+    for i in 0..n.div_ceil(2) {
+        let mut x = (std::f64::consts::PI * (i as f64 + 0.75) / (n as f64 + 0.5)).cos();
+        let mut derivative = 1.0;
+
+        for _ in 0..100 {
+            // P_n(x) from the three-term recurrence.
+            let (mut p, mut p_previous) = (1.0, 0.0);
+            for j in 1..=n {
+                let j = j as f64;
+                (p, p_previous) = (((2.0 * j - 1.0) * x * p - (j - 1.0) * p_previous) / j, p);
+            }
+
+            derivative = n as f64 * (x * p - p_previous) / (x * x - 1.0);
+
+            let step = p / derivative;
+            x -= step;
+
+            if step.abs() < 1e-15 {
+                break;
+            }
+        }
+
+        let weight = 2.0 / ((1.0 - x * x) * derivative * derivative);
+
+        nodes[i] = -x;
+        nodes[n - 1 - i] = x;
+        weights[i] = weight;
+        weights[n - 1 - i] = weight;
+    }
+
+    (nodes, weights)
+}
+
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // ABSCISSAE & WEIGHTS
 // These are for the tanh-sinh quadrature.
@@ -310,4 +374,22 @@ mod tests_integration {
 
         assert_approx_equal!(integral, 7.189_119_252_343_784, EPS);
     }
+
+    #[test]
+    fn test_gauss_legendre() {
+        // Exact for polynomials of degree 2n - 1.
+        let integral = gauss_legendre(|x| x.powi(9) - 3.0 * x.powi(4) + 1.0, -1.0, 2.0, 5);
+
+        assert_approx_equal!(integral, 102.3 - 19.8 + 3.0, 1e-12);
+
+        let (nodes, weights) = gauss_legendre_nodes(4);
+        assert_approx_equal!(weights.iter().sum::<f64>(), 2.0, EPS);
+        assert_approx_equal!(nodes[3], 0.861_136_311_594_052_6, EPS);
+        assert_approx_equal!(weights[3], 0.347_854_845_137_453_9, EPS);
+
+        // Smooth integrands converge quickly.
+        let integral = gauss_legendre(|x| (x.sin()).exp(), 0.0, 5.0, 32);
+
+        assert_approx_equal!(integral, 7.189_119_253_631_28, 1e-12);
+    }
 }
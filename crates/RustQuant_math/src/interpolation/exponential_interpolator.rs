@@ -10,9 +10,10 @@
 //! Module containing functionality for interpolation.
 
 use super::Interpolator;
-use crate::interpolation::{InterpolationIndex, InterpolationValue};
+use crate::interpolation::{
+    locate_point, merge_points, sort_points, InterpolationIndex, InterpolationValue,
+};
 use num::Float;
-use std::cmp::Ordering;
 use RustQuant_error::RustQuantError;
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -48,22 +49,12 @@ where
     ///
     /// # Errors
     /// - `RustQuantError::UnequalLength` if ```xs.length() != ys.length()```.
-    ///
-    /// # Panics
-    /// Panics if NaN is in the index.
+    /// - `RustQuantError::InvalidArgument` if NaN is in the index.
     pub fn new(
         xs: Vec<IndexType>,
         ys: Vec<ValueType>,
     ) -> Result<ExponentialInterpolator<IndexType, ValueType>, RustQuantError> {
-        if xs.len() != ys.len() {
-            return Err(RustQuantError::UnequalLength);
-        }
-
-        let mut tmp: Vec<_> = xs.into_iter().zip(ys).collect();
-
-        tmp.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
-
-        let (xs, ys): (Vec<IndexType>, Vec<ValueType>) = tmp.into_iter().unzip();
+        let (xs, ys) = sort_points(xs, ys)?;
 
         Ok(Self {
             xs,
@@ -95,22 +86,15 @@ where
         self.ys.insert(idx, point.1);
     }
 
-    fn interpolate(&self, point: IndexType) -> Result<ValueType, RustQuantError> {
-        let range = self.range();
-
-        let check1 = point.partial_cmp(&range.0).unwrap() == Ordering::Less;
-        let check2 = point.partial_cmp(&range.1).unwrap() == Ordering::Greater;
-
-        if check1 || check2 {
-            return Err(RustQuantError::OutsideOfRange);
-        }
+    fn add_points(&mut self, points: &[(IndexType, ValueType)]) {
+        merge_points(&mut self.xs, &mut self.ys, points);
+    }
 
-        if let Ok(idx) = self
-            .xs
-            .binary_search_by(|p| p.partial_cmp(&point).expect("Cannot compare values."))
-        {
-            return Ok(self.ys[idx]);
-        }
+    fn interpolate(&self, point: IndexType) -> Result<ValueType, RustQuantError> {
+        let idx_r = match locate_point(&self.xs, point)? {
+            Ok(idx) => return Ok(self.ys[idx]),
+            Err(idx_r) => idx_r,
+        };
 
         //         let idx_r = self.xs.partition_point(|&x| x < point);
         //         let idx_l = idx_r - 1;
@@ -149,8 +133,6 @@ where
 
         // let result = (term1 * term2 + term3).exp();
 
-        let idx_r = self.xs.partition_point(|&x| x < point);
-
         let x_l = self.xs[idx_r - 1];
         let y_l = self.ys[idx_r - 1];
 
@@ -208,4 +190,18 @@ mod tests_exponential_interpolation {
             RUSTQUANT_EPSILON
         );
     }
+
+    #[test]
+    fn test_exponential_interpolation_add_points() {
+        let mut interpolator = ExponentialInterpolator::new(vec![1.0], vec![5.0]).unwrap();
+        interpolator.add_points(&[(5.0, 3125.0), (2.0, 25.0), (3.0, 125.0)]);
+        let _ = interpolator.fit();
+
+        assert_eq!(interpolator.xs, vec![1.0, 2.0, 3.0, 5.0]);
+        assert_approx_equal!(
+            625.0,
+            interpolator.interpolate(4.0).unwrap(),
+            RUSTQUANT_EPSILON
+        );
+    }
 }
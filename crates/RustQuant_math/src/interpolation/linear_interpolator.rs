@@ -9,7 +9,9 @@
 
 //! Module containing functionality for interpolation.
 
-use crate::interpolation::{InterpolationIndex, InterpolationValue, Interpolator};
+use crate::interpolation::{
+    locate_point, merge_points, sort_points, InterpolationIndex, InterpolationValue, Interpolator,
+};
 use RustQuant_error::RustQuantError;
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -45,22 +47,12 @@ where
     ///
     /// # Errors
     /// - `RustQuantError::UnequalLength` if ```xs.length() != ys.length()```.
-    ///
-    /// # Panics
-    /// Panics if NaN is in the index.
+    /// - `RustQuantError::InvalidArgument` if NaN is in the index.
     pub fn new(
         xs: Vec<IndexType>,
         ys: Vec<ValueType>,
     ) -> Result<LinearInterpolator<IndexType, ValueType>, RustQuantError> {
-        if xs.len() != ys.len() {
-            return Err(RustQuantError::UnequalLength);
-        }
-
-        let mut tmp: Vec<_> = xs.into_iter().zip(ys).collect();
-
-        tmp.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
-
-        let (xs, ys): (Vec<IndexType>, Vec<ValueType>) = tmp.into_iter().unzip();
+        let (xs, ys) = sort_points(xs, ys)?;
 
         Ok(Self {
             xs,
@@ -91,20 +83,15 @@ where
         self.ys.insert(idx, point.1);
     }
 
+    fn add_points(&mut self, points: &[(IndexType, ValueType)]) {
+        merge_points(&mut self.xs, &mut self.ys, points);
+    }
+
     fn interpolate(&self, point: IndexType) -> Result<ValueType, RustQuantError> {
-        let range = self.range();
-        if point.partial_cmp(&range.0).unwrap() == std::cmp::Ordering::Less
-            || point.partial_cmp(&range.1).unwrap() == std::cmp::Ordering::Greater
-        {
-            return Err(RustQuantError::OutsideOfRange);
-        }
-        if let Ok(idx) = self
-            .xs
-            .binary_search_by(|p| p.partial_cmp(&point).expect("Cannot compare values."))
-        {
-            return Ok(self.ys[idx]);
-        }
-        let idx_r = self.xs.partition_point(|&x| x < point);
+        let idx_r = match locate_point(&self.xs, point)? {
+            Ok(idx) => return Ok(self.ys[idx]),
+            Err(idx_r) => idx_r,
+        };
         let idx_l = idx_r - 1;
 
         let x_l = self.xs[idx_l];
@@ -209,4 +196,50 @@ mod tests_linear_interpolation {
             RUSTQUANT_EPSILON
         );
     }
+
+    #[test]
+    fn test_linear_interpolation_nan() {
+        let xs = vec![1., f64::NAN, 3.];
+        let ys = vec![1., 2., 3.];
+
+        assert!(LinearInterpolator::new(xs, ys).is_err());
+
+        let xs = vec![1., 2., 3.];
+        let ys = vec![1., 2., 3.];
+
+        let interpolator = LinearInterpolator::new(xs, ys).unwrap();
+
+        assert!(interpolator.interpolate(f64::NAN).is_err());
+    }
+
+    #[test]
+    fn test_linear_interpolation_add_points() {
+        let xs = vec![0.5, 1.0, 2.0, 3.0, 5.0, 7.5];
+        let ys = vec![0.9, 1.5, 2.0, 1.0, 4.0, 3.5];
+
+        let fresh = LinearInterpolator::new(xs.clone(), ys.clone()).unwrap();
+
+        let mut bulk = LinearInterpolator::new(xs[..2].to_vec(), ys[..2].to_vec()).unwrap();
+        let points: Vec<(f64, f64)> = xs.iter().copied().zip(ys.iter().copied()).collect();
+        bulk.add_points(&[points[5], points[2], points[4], points[3]]);
+
+        let mut incremental = LinearInterpolator::new(vec![7.5], vec![3.5]).unwrap();
+        for &point in &points[..5] {
+            incremental.add_point(point);
+        }
+
+        for interpolator in [&mut bulk, &mut incremental] {
+            let _ = interpolator.fit();
+
+            assert_eq!(interpolator.xs, fresh.xs);
+            assert_eq!(interpolator.ys, fresh.ys);
+
+            for x in [0.5, 0.75, 1.9, 2.5, 4.0, 7.5] {
+                assert_eq!(
+                    interpolator.interpolate(x).unwrap(),
+                    fresh.interpolate(x).unwrap()
+                );
+            }
+        }
+    }
 }
@@ -7,6 +7,7 @@
 //      - LICENSE-MIT.md
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
+use std::cmp::Ordering;
 use std::ops::{Div, Mul, Sub};
 use RustQuant_error::RustQuantError;
 
@@ -16,6 +17,12 @@ pub use linear_interpolator::*;
 pub mod exponential_interpolator;
 pub use exponential_interpolator::*;
 
+pub mod step_interpolator;
+pub use step_interpolator::*;
+
+pub mod monotone_convex_interpolator;
+pub use monotone_convex_interpolator::*;
+
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
@@ -58,6 +65,115 @@ where
 
     /// Add a point to the interpolator.
     fn add_point(&mut self, point: (IndexType, ValueType));
+
+    /// Add several points to the interpolator.
+    ///
+    /// Equivalent to calling `add_point` for each point, which
+    /// implementors may override with a cheaper bulk insertion.
+    fn add_points(&mut self, points: &[(IndexType, ValueType)]) {
+        for &point in points {
+            self.add_point(point);
+        }
+    }
+}
+
+/// Sort the points by their index, rejecting indices that cannot be
+/// compared (e.g. `NaN`) instead of panicking.
+pub(crate) fn sort_points<IndexType, ValueType>(
+    xs: Vec<IndexType>,
+    ys: Vec<ValueType>,
+) -> Result<(Vec<IndexType>, Vec<ValueType>), RustQuantError>
+where
+    IndexType: InterpolationIndex,
+{
+    if xs.len() != ys.len() {
+        return Err(RustQuantError::UnequalLength);
+    }
+
+    if xs.iter().any(|x| x.partial_cmp(x).is_none()) {
+        return Err(RustQuantError::InvalidArgument(
+            "Interpolation index contains values that cannot be ordered (NaN).".to_string(),
+        ));
+    }
+
+    let mut tmp: Vec<_> = xs.into_iter().zip(ys).collect();
+
+    tmp.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+
+    Ok(tmp.into_iter().unzip())
+}
+
+/// Merge `points` into the sorted nodes `xs` and `ys` in a single pass.
+///
+/// New points are placed before existing nodes with an equal index,
+/// the same as inserting them one at a time with `add_point`.
+pub(crate) fn merge_points<IndexType, ValueType>(
+    xs: &mut Vec<IndexType>,
+    ys: &mut Vec<ValueType>,
+    points: &[(IndexType, ValueType)],
+) where
+    IndexType: InterpolationIndex,
+    ValueType: Copy,
+{
+    let mut new_points = points.to_vec();
+    new_points.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+
+    let n = xs.len() + new_points.len();
+    let mut merged_xs = Vec::with_capacity(n);
+    let mut merged_ys = Vec::with_capacity(n);
+
+    let mut old_points = xs.iter().copied().zip(ys.iter().copied()).peekable();
+
+    for (x, y) in new_points {
+        while let Some(&(old_x, old_y)) = old_points.peek() {
+            if old_x >= x {
+                break;
+            }
+            merged_xs.push(old_x);
+            merged_ys.push(old_y);
+            old_points.next();
+        }
+        merged_xs.push(x);
+        merged_ys.push(y);
+    }
+
+    for (old_x, old_y) in old_points {
+        merged_xs.push(old_x);
+        merged_ys.push(old_y);
+    }
+
+    *xs = merged_xs;
+    *ys = merged_ys;
+}
+
+/// Locate `point` in the sorted index `xs`.
+///
+/// Returns `Ok(Ok(idx))` on an exact match, or `Ok(Err(idx_r))` with the
+/// index of the first node to the right of `point` otherwise.
+///
+/// # Errors
+/// - `RustQuantError::OutsideOfRange` if `point` lies outside `xs`, or `xs` is empty.
+/// - `RustQuantError::InvalidArgument` if `point` cannot be compared (e.g. `NaN`).
+pub(crate) fn locate_point<IndexType>(
+    xs: &[IndexType],
+    point: IndexType,
+) -> Result<Result<usize, usize>, RustQuantError>
+where
+    IndexType: InterpolationIndex,
+{
+    let (Some(first), Some(last)) = (xs.first(), xs.last()) else {
+        return Err(RustQuantError::OutsideOfRange);
+    };
+
+    match (point.partial_cmp(first), point.partial_cmp(last)) {
+        (None, _) | (_, None) => Err(RustQuantError::InvalidArgument(
+            "Interpolation point cannot be compared with the index (NaN).".to_string(),
+        )),
+        (Some(Ordering::Less), _) | (_, Some(Ordering::Greater)) => {
+            Err(RustQuantError::OutsideOfRange)
+        }
+        _ => Ok(xs.binary_search_by(|p| p.partial_cmp(&point).unwrap_or(Ordering::Less))),
+    }
 }
 
 impl<T> InterpolationValue for T where T: num::Num + std::fmt::Debug + Copy + Clone + Sized {}
@@ -0,0 +1,204 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Step (piecewise constant) interpolation.
+
+use crate::interpolation::{
+    locate_point, merge_points, sort_points, InterpolationIndex, InterpolationValue, Interpolator,
+};
+use RustQuant_error::RustQuantError;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS & ENUMS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Which node's value applies between two nodes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StepConvention {
+    /// Each value applies from its node forward: `ys[i]` on `[xs[i], xs[i+1])`.
+    ForwardFill,
+
+    /// Each value applies from its node backward: `ys[i]` on `(xs[i-1], xs[i]]`.
+    BackwardFill,
+}
+
+/// Piecewise constant (step) interpolator.
+///
+/// Useful for hazard rates and forward curves that are constant
+/// between nodes.
+pub struct StepInterpolator<IndexType, ValueType>
+where
+    IndexType: InterpolationIndex,
+    ValueType: InterpolationValue,
+{
+    /// X-axis values for the interpolator.
+    pub xs: Vec<IndexType>,
+
+    /// Y-axis values for the interpolator.
+    pub ys: Vec<ValueType>,
+
+    /// Whether the interpolator has been fitted.
+    pub fitted: bool,
+
+    /// Which node's value applies between two nodes.
+    pub convention: StepConvention,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl<IndexType, ValueType> StepInterpolator<IndexType, ValueType>
+where
+    IndexType: InterpolationIndex,
+    ValueType: InterpolationValue,
+{
+    /// Create a new StepInterpolator.
+    ///
+    /// # Errors
+    /// - `RustQuantError::UnequalLength` if ```xs.length() != ys.length()```.
+    /// - `RustQuantError::InvalidArgument` if NaN is in the index.
+    pub fn new(
+        xs: Vec<IndexType>,
+        ys: Vec<ValueType>,
+        convention: StepConvention,
+    ) -> Result<StepInterpolator<IndexType, ValueType>, RustQuantError> {
+        let (xs, ys) = sort_points(xs, ys)?;
+
+        Ok(Self {
+            xs,
+            ys,
+            fitted: false,
+            convention,
+        })
+    }
+}
+
+impl<IndexType, ValueType> Interpolator<IndexType, ValueType>
+    for StepInterpolator<IndexType, ValueType>
+where
+    IndexType: InterpolationIndex,
+    ValueType: InterpolationValue,
+{
+    fn fit(&mut self) -> Result<(), RustQuantError> {
+        self.fitted = true;
+        Ok(())
+    }
+
+    fn range(&self) -> (IndexType, IndexType) {
+        (*self.xs.first().unwrap(), *self.xs.last().unwrap())
+    }
+
+    fn add_point(&mut self, point: (IndexType, ValueType)) {
+        let idx = self.xs.partition_point(|&x| x < point.0);
+        self.xs.insert(idx, point.0);
+        self.ys.insert(idx, point.1);
+    }
+
+    fn add_points(&mut self, points: &[(IndexType, ValueType)]) {
+        merge_points(&mut self.xs, &mut self.ys, points);
+    }
+
+    fn interpolate(&self, point: IndexType) -> Result<ValueType, RustQuantError> {
+        let idx_r = match locate_point(&self.xs, point)? {
+            Ok(idx) => return Ok(self.ys[idx]),
+            Err(idx_r) => idx_r,
+        };
+
+        match self.convention {
+            StepConvention::ForwardFill => Ok(self.ys[idx_r - 1]),
+            StepConvention::BackwardFill => Ok(self.ys[idx_r]),
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_step_interpolation {
+    use super::*;
+    use crate::integrate;
+    use time::macros::date;
+    use RustQuant_utils::{assert_approx_equal, RUSTQUANT_EPSILON};
+
+    #[test]
+    fn test_step_interpolation_forward_fill() {
+        let xs = vec![0.0, 1.0, 2.0, 5.0];
+        let ys = vec![0.01, 0.02, 0.03, 0.04];
+
+        let interpolator =
+            StepInterpolator::new(xs.clone(), ys.clone(), StepConvention::ForwardFill).unwrap();
+
+        for i in 0..xs.len() - 1 {
+            for w in [0.0, 0.25, 0.5, 0.999] {
+                let x = xs[i] + w * (xs[i + 1] - xs[i]);
+                assert_eq!(interpolator.interpolate(x).unwrap(), ys[i]);
+            }
+        }
+        assert_eq!(interpolator.interpolate(5.0).unwrap(), 0.04);
+    }
+
+    #[test]
+    fn test_step_interpolation_backward_fill() {
+        let xs = vec![0.0, 1.0, 2.0, 5.0];
+        let ys = vec![0.01, 0.02, 0.03, 0.04];
+
+        let interpolator =
+            StepInterpolator::new(xs.clone(), ys.clone(), StepConvention::BackwardFill).unwrap();
+
+        for i in 1..xs.len() {
+            for w in [0.001, 0.5, 0.75, 1.0] {
+                let x = xs[i - 1] + w * (xs[i] - xs[i - 1]);
+                assert_eq!(interpolator.interpolate(x).unwrap(), ys[i]);
+            }
+        }
+        assert_eq!(interpolator.interpolate(0.0).unwrap(), 0.01);
+    }
+
+    #[test]
+    fn test_step_hazard_survival_probability() {
+        // Hazard rates of 1%, 2%, and 3% on [0, 1), [1, 3), and [3, 5].
+        let hazard = StepInterpolator::new(
+            vec![0.0, 1.0, 3.0, 5.0],
+            vec![0.01, 0.02, 0.03, 0.03],
+            StepConvention::ForwardFill,
+        )
+        .unwrap();
+
+        // Integrate each constant piece separately to avoid the jumps.
+        let nodes = [0.0, 1.0, 3.0, 5.0];
+        let cumulative_hazard: f64 = nodes
+            .windows(2)
+            .map(|w| integrate(|t| hazard.interpolate(t).unwrap(), w[0], w[1] - 1e-12))
+            .sum();
+
+        let survival = (-cumulative_hazard).exp();
+        let expected = (-(0.01 * 1.0 + 0.02 * 2.0 + 0.03 * 2.0_f64)).exp();
+
+        assert_approx_equal!(survival, expected, 1e-8);
+    }
+
+    #[test]
+    fn test_step_interpolation_dates() {
+        let dates = vec![date!(2024 - 01 - 01), date!(2024 - 07 - 01)];
+        let rates = vec![0.05, 0.06];
+
+        let interpolator =
+            StepInterpolator::new(dates, rates, StepConvention::ForwardFill).unwrap();
+
+        assert_approx_equal!(
+            interpolator.interpolate(date!(2024 - 03 - 15)).unwrap(),
+            0.05,
+            RUSTQUANT_EPSILON
+        );
+        assert!(interpolator.interpolate(date!(2025 - 01 - 01)).is_err());
+    }
+}
@@ -62,6 +62,14 @@
 //!
 //! - [x] Risk-Reward Measures (Sharpe, Treynor, Sortino, etc)
 
+/// Compounding conventions and discount factors.
+pub mod compounding;
+pub use compounding::*;
+
+/// Gaussian and Student's t copulas.
+pub mod copula;
+pub use copula::*;
+
 /// Statistical distributions.
 pub mod distributions;
 pub use distributions::*;
@@ -95,6 +103,10 @@ pub use rootfinding::*;
 pub mod sequences;
 pub use sequences::*;
 
+/// Sobol low-discrepancy sequence.
+pub mod sobol;
+pub use sobol::*;
+
 /// Statistic trait.
 pub mod statistic;
 pub use statistic::*;
@@ -0,0 +1,259 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Sobol low-discrepancy sequence, for quasi-Monte Carlo integration.
+//!
+//! Points are generated in Gray code order (Antonov and Saleev, 1979) using
+//! the direction numbers of Joe and Kuo (2008), `new-joe-kuo-6.21201`.
+//! The sequence is unscrambled, so the first point is the origin.
+
+use RustQuant_error::RustQuantError;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// CONSTANTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Number of bits per coordinate (and so the maximum of `2^32` points).
+const SOBOL_BITS: usize = 32;
+
+/// Maximum number of dimensions supported by [`SobolSequence`].
+pub const SOBOL_MAX_DIMENSION: usize = 1 + JOE_KUO.len();
+
+/// Joe-Kuo primitive polynomials and initial direction numbers
+/// for dimensions 2 and up: `(degree, coefficients, m_1, ..., m_degree)`.
+const JOE_KUO: [(u32, u32, &[u32]); 20] = [
+    (1, 0, &[1]),
+    (2, 1, &[1, 3]),
+    (3, 1, &[1, 3, 1]),
+    (3, 2, &[1, 1, 1]),
+    (4, 1, &[1, 1, 3, 3]),
+    (4, 4, &[1, 3, 5, 13]),
+    (5, 2, &[1, 1, 5, 5, 17]),
+    (5, 4, &[1, 1, 5, 5, 5]),
+    (5, 7, &[1, 1, 7, 11, 19]),
+    (5, 11, &[1, 1, 5, 1, 1]),
+    (5, 13, &[1, 1, 1, 3, 11]),
+    (5, 14, &[1, 3, 5, 5, 31]),
+    (6, 1, &[1, 3, 3, 9, 7, 49]),
+    (6, 13, &[1, 1, 1, 15, 21, 21]),
+    (6, 16, &[1, 3, 1, 13, 27, 49]),
+    (6, 19, &[1, 1, 1, 15, 7, 5]),
+    (6, 22, &[1, 3, 1, 15, 13, 25]),
+    (6, 25, &[1, 1, 5, 5, 19, 61]),
+    (7, 1, &[1, 3, 7, 11, 23, 15, 103]),
+    (7, 4, &[1, 3, 7, 13, 13, 15, 69]),
+];
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Sobol sequence generator.
+///
+/// Each call to `next()` returns the next point in `[0, 1)^dimension`.
+#[derive(Debug, Clone)]
+pub struct SobolSequence {
+    /// Number of dimensions of each point.
+    dimension: usize,
+
+    /// Index of the next point.
+    index: u64,
+
+    /// Direction numbers, `SOBOL_BITS` per dimension.
+    directions: Vec<[u32; SOBOL_BITS]>,
+
+    /// Integer coordinates of the next point.
+    state: Vec<u32>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl SobolSequence {
+    /// Create a new Sobol sequence generator.
+    ///
+    /// # Errors
+    /// - `RustQuantError::InvalidArgument` if `dimension` is zero
+    ///   or greater than [`SOBOL_MAX_DIMENSION`].
+    pub fn new(dimension: usize) -> Result<Self, RustQuantError> {
+        if dimension == 0 || dimension > SOBOL_MAX_DIMENSION {
+            return Err(RustQuantError::InvalidArgument(format!(
+                "Sobol dimension must be between 1 and {SOBOL_MAX_DIMENSION}, got {dimension}."
+            )));
+        }
+
+        let mut directions = Vec::with_capacity(dimension);
+
+        // The first dimension is the van der Corput sequence in base 2.
+        let mut first = [0_u32; SOBOL_BITS];
+        for (i, v) in first.iter_mut().enumerate() {
+            *v = 1 << (SOBOL_BITS - 1 - i);
+        }
+        directions.push(first);
+
+        for &(degree, coefficients, initial) in JOE_KUO.iter().take(dimension - 1) {
+            let s = degree as usize;
+            let mut v = [0_u32; SOBOL_BITS];
+
+            for (i, m) in initial.iter().enumerate() {
+                v[i] = m << (SOBOL_BITS - 1 - i);
+            }
+
+            for i in s..SOBOL_BITS {
+                v[i] = v[i - s] ^ (v[i - s] >> s);
+
+                for k in 1..s {
+                    if (coefficients >> (s - 1 - k)) & 1 == 1 {
+                        v[i] ^= v[i - k];
+                    }
+                }
+            }
+
+            directions.push(v);
+        }
+
+        Ok(Self {
+            dimension,
+            index: 0,
+            directions,
+            state: vec![0; dimension],
+        })
+    }
+
+    /// Number of dimensions of each point.
+    pub fn dimension(&self) -> usize {
+        self.dimension
+    }
+
+    /// Skip the next `n` points of the sequence.
+    ///
+    /// Jumps straight to the new point, in `O(dimension)` time.
+    pub fn skip_points(&mut self, n: usize) {
+        let index = (self.index + n as u64).min(1 << SOBOL_BITS);
+
+        // The point at index k is the XOR of the direction numbers
+        // selected by the bits of the Gray code of k.
+        let gray = index ^ (index >> 1);
+
+        for (x, v) in self.state.iter_mut().zip(&self.directions) {
+            *x = (0..SOBOL_BITS)
+                .filter(|bit| (gray >> bit) & 1 == 1)
+                .fold(0, |acc, bit| acc ^ v[bit]);
+        }
+
+        self.index = index;
+    }
+
+    /// Move the integer state to the next point, using the Gray code update.
+    fn advance(&mut self) {
+        let bit = (!self.index).trailing_zeros() as usize;
+
+        if bit < SOBOL_BITS {
+            for (x, v) in self.state.iter_mut().zip(&self.directions) {
+                *x ^= v[bit];
+            }
+        }
+
+        self.index += 1;
+    }
+}
+
+impl Iterator for SobolSequence {
+    type Item = Vec<f64>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= 1 << SOBOL_BITS {
+            return None;
+        }
+
+        let scale = (1_u64 << SOBOL_BITS) as f64;
+        let point = self.state.iter().map(|&x| x as f64 / scale).collect();
+
+        self.advance();
+
+        Some(point)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_sobol {
+    use super::*;
+
+    #[test]
+    fn test_sobol_first_points() {
+        let points: Vec<Vec<f64>> = SobolSequence::new(3).unwrap().take(8).collect();
+
+        let expected = [
+            [0.0, 0.0, 0.0],
+            [0.5, 0.5, 0.5],
+            [0.75, 0.25, 0.25],
+            [0.25, 0.75, 0.75],
+            [0.375, 0.375, 0.625],
+            [0.875, 0.875, 0.125],
+            [0.625, 0.125, 0.875],
+            [0.125, 0.625, 0.375],
+        ];
+
+        for (point, expected) in points.iter().zip(expected.iter()) {
+            assert_eq!(point.as_slice(), expected.as_slice());
+        }
+    }
+
+    #[test]
+    fn test_sobol_stratification() {
+        // The first 2^m points of every coordinate hit each interval
+        // [j / 2^m, (j + 1) / 2^m) exactly once.
+        let n = 1 << 10;
+        let points: Vec<Vec<f64>> = SobolSequence::new(SOBOL_MAX_DIMENSION)
+            .unwrap()
+            .take(n)
+            .collect();
+
+        for d in 0..SOBOL_MAX_DIMENSION {
+            let mut seen = vec![false; n];
+
+            for point in &points {
+                let bin = (point[d] * n as f64) as usize;
+                assert!(!seen[bin]);
+                seen[bin] = true;
+            }
+        }
+    }
+
+    #[test]
+    fn test_sobol_skip_points() {
+        let mut skipped = SobolSequence::new(2).unwrap();
+        skipped.skip_points(5);
+
+        let mut sequence = SobolSequence::new(2).unwrap();
+        let sixth = sequence.nth(5);
+
+        assert_eq!(skipped.next(), sixth);
+
+        // Skipping from part way through the sequence.
+        let mut skipped = SobolSequence::new(5).unwrap();
+        skipped.skip_points(3);
+        skipped.next();
+        skipped.skip_points(996);
+
+        let mut sequence = SobolSequence::new(5).unwrap();
+        assert_eq!(skipped.next(), sequence.nth(1000));
+    }
+
+    #[test]
+    fn test_sobol_invalid_dimension() {
+        assert!(SobolSequence::new(0).is_err());
+        assert!(SobolSequence::new(SOBOL_MAX_DIMENSION + 1).is_err());
+    }
+}
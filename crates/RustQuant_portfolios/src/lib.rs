@@ -70,6 +70,10 @@
 //!     
 //! // Check the profit of the portfolio.
 //! assert_approx_equal!(portfolio.profit(), 550.0 - portfolio.cost(), 1e-10);
+//!
+//! // Net Greeks of the book, and the PnL of a 1.0 move in the spots.
+//! let greeks = portfolio.net_greeks();
+//! let pnl = portfolio.scenario_pnl(1.0, 0.0);
 //! ```
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -77,7 +81,7 @@
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
 use std::collections::HashMap;
-use RustQuant_instruments::{fx::currency::Currency, Instrument};
+use RustQuant_instruments::{fx::currency::Currency, Greeks, Instrument, OptionGreeks};
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // STRUCTS, ENUMS, AND TRAITS
@@ -94,8 +98,8 @@ pub struct Position<I: Instrument> {
     /// Instrument.
     pub instrument: I,
 
-    /// Quantity.
-    pub quantity: u64,
+    /// Quantity (negative for short positions).
+    pub quantity: i64,
 
     /// Purchase price of the instrument (per unit).
     pub purchase_price: f64,
@@ -118,7 +122,7 @@ where
     /// Create a new position.
     pub fn new(
         instrument: I,
-        quantity: u64,
+        quantity: i64,
         purchase_price: f64,
         current_price: f64,
         currency: Option<Currency>,
@@ -148,7 +152,7 @@ where
     }
 
     /// Update the quantity of the position.
-    pub fn update_quantity(&mut self, new_quantity: u64) {
+    pub fn update_quantity(&mut self, new_quantity: i64) {
         self.quantity = new_quantity;
     }
 }
@@ -201,7 +205,7 @@ where
     /// # Panics
     ///
     /// Panics if `instrument_name` not found in the portfolio
-    pub fn update_quantity(&mut self, instrument_name: &str, new_quantity: u64) {
+    pub fn update_quantity(&mut self, instrument_name: &str, new_quantity: i64) {
         self.positions
             .get_mut(instrument_name)
             .unwrap()
@@ -224,6 +228,40 @@ where
     }
 }
 
+impl<I> Portfolio<I>
+where
+    I: Instrument + OptionGreeks,
+{
+    /// Returns the quantity-weighted sum of the Greeks of all positions.
+    ///
+    /// Long and short positions in the same risk offset each other.
+    #[must_use]
+    pub fn net_greeks(&self) -> Greeks {
+        self.positions
+            .values()
+            .map(|position| position.instrument.greeks() * position.quantity as f64)
+            .sum()
+    }
+
+    /// Returns the change in the model value of the portfolio when every
+    /// underlying price is shifted by `spot_shift` and every volatility
+    /// by `vol_shift` (both absolute).
+    ///
+    /// Each instrument is fully repriced, so the result includes the
+    /// higher-order effects missed by a Greeks-based estimate.
+    #[must_use]
+    pub fn scenario_pnl(&self, spot_shift: f64, vol_shift: f64) -> f64 {
+        self.positions
+            .values()
+            .map(|position| {
+                let shifted = position.instrument.shifted(spot_shift, vol_shift);
+
+                position.quantity as f64 * (shifted.price() - position.instrument.price())
+            })
+            .sum()
+    }
+}
+
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // UNIT TESTS
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -307,4 +345,56 @@ mod tests_portfolio {
         assert_eq!(weights.get("Put Options"), Some(&0.36363637));
         assert_eq!(weights.get("Call Options"), Some(&0.6363636));
     }
+
+    #[test]
+    fn test_net_greeks_offsetting_positions() {
+        let call = || {
+            BlackScholesMerton::new(
+                0.08,
+                60.0,
+                65.0,
+                0.3,
+                0.08,
+                None,
+                today() + Duration::days(91),
+                TypeFlag::Call,
+            )
+        };
+
+        let portfolio = Portfolio::new(HashMap::from([
+            (
+                "Long Calls".to_string(),
+                Position::new(call(), 100, 2.1, 2.1, None),
+            ),
+            (
+                "Short Calls".to_string(),
+                Position::new(call(), -100, 2.1, 2.1, None),
+            ),
+        ]));
+
+        let greeks = portfolio.net_greeks();
+
+        assert_approx_equal!(greeks.delta, 0.0, 1e-10);
+        assert_approx_equal!(greeks.gamma, 0.0, 1e-10);
+        assert_approx_equal!(greeks.vega, 0.0, 1e-10);
+        assert_approx_equal!(greeks.theta, 0.0, 1e-10);
+        assert_approx_equal!(greeks.rho, 0.0, 1e-10);
+
+        assert_approx_equal!(portfolio.scenario_pnl(5.0, 0.05), 0.0, 1e-10);
+        assert_approx_equal!(portfolio.scenario_pnl(-5.0, -0.05), 0.0, 1e-10);
+    }
+
+    #[test]
+    fn test_scenario_pnl() {
+        let portfolio = setup_test_portfolio();
+        let greeks = portfolio.net_greeks();
+
+        // No shift, no PnL.
+        assert_approx_equal!(portfolio.scenario_pnl(0.0, 0.0), 0.0, 1e-10);
+
+        // Small moves are explained by the first-order Greeks.
+        let h = 1e-3;
+        assert_approx_equal!(portfolio.scenario_pnl(h, 0.0), greeks.delta * h, 1e-2);
+        assert_approx_equal!(portfolio.scenario_pnl(0.0, h), greeks.vega * h, 1e-2);
+    }
 }
@@ -0,0 +1,212 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::heston::Heston;
+use crate::merton_jump_diffusion::{lognormal_mean_relative_jump, sample_log_jump};
+use crate::model_parameter::ModelParameter;
+use crate::process::{DiscretisationScheme, StochasticProcessConfig, Trajectories};
+use crate::random::simulation_rng;
+use crate::StochasticProcess;
+use rand::Rng;
+use rand_distr::StandardNormal;
+
+/// Struct containing the Bates (1996) stochastic volatility jump (SVJ) model parameters.
+///
+/// Heston stochastic volatility with Merton lognormal jumps in the asset:
+/// $dS_t = (\mu - \lambda k) S_t dt + \sqrt{v_t} S_t dW^1_t + S_{t^-} (e^J - 1) dN_t$,
+/// $dv_t = \kappa (\theta - v_t) dt + \sigma \sqrt{v_t} dW^2_t$,
+/// with $d\langle W^1, W^2 \rangle_t = \rho dt$, $J \sim N(m, \delta^2)$,
+/// and $k = E[e^J - 1]$.
+pub struct Bates {
+    /// The Heston stochastic volatility part of the model.
+    pub heston: Heston,
+
+    /// The jump intensity ($\lambda$), the expected number of jumps per year.
+    pub lambda: ModelParameter,
+
+    /// The mean of the log jump size ($m$).
+    pub jump_mean: ModelParameter,
+
+    /// The volatility of the log jump size ($\delta$).
+    pub jump_volatility: ModelParameter,
+}
+
+impl Bates {
+    /// Create a new Bates process.
+    /// # Arguments
+    /// * `mu` - The drift of the asset ($\mu$).
+    /// * `initial_variance` - The initial variance ($v_0$).
+    /// * `long_run_variance` - The long-run variance ($\theta$).
+    /// * `mean_reversion_rate` - The mean reversion rate ($\kappa$).
+    /// * `correlation` - The correlation between the asset and variance ($\rho$).
+    /// * `volatility_of_volatility` - The volatility of volatility ($\sigma$).
+    /// * `lambda` - The jump intensity ($\lambda$).
+    /// * `jump_mean` - The mean of the log jump size ($m$).
+    /// * `jump_volatility` - The volatility of the log jump size ($\delta$).
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        mu: impl Into<ModelParameter>,
+        initial_variance: impl Into<ModelParameter>,
+        long_run_variance: impl Into<ModelParameter>,
+        mean_reversion_rate: impl Into<ModelParameter>,
+        correlation: impl Into<ModelParameter>,
+        volatility_of_volatility: impl Into<ModelParameter>,
+        lambda: impl Into<ModelParameter>,
+        jump_mean: impl Into<ModelParameter>,
+        jump_volatility: impl Into<ModelParameter>,
+    ) -> Self {
+        Self {
+            heston: Heston::new(
+                mu,
+                initial_variance,
+                long_run_variance,
+                mean_reversion_rate,
+                correlation,
+                volatility_of_volatility,
+            ),
+            lambda: lambda.into(),
+            jump_mean: jump_mean.into(),
+            jump_volatility: jump_volatility.into(),
+        }
+    }
+
+    /// Simulate the asset and variance paths together.
+    ///
+    /// Returns `(asset, variance)` trajectories on the same time grid.
+    pub fn euler_maruyama_with_variance(
+        &self,
+        config: &StochasticProcessConfig,
+    ) -> (Trajectories, Trajectories) {
+        self.simulate_with_variance(config, DiscretisationScheme::EulerMaruyama)
+    }
+
+    /// Simulate the asset and variance paths together with a choice of
+    /// discretisation scheme for the diffusion part.
+    ///
+    /// Returns `(asset, variance)` trajectories on the same time grid.
+    pub fn simulate_with_variance(
+        &self,
+        config: &StochasticProcessConfig,
+        scheme: DiscretisationScheme,
+    ) -> (Trajectories, Trajectories) {
+        self.heston.simulate(
+            config,
+            scheme,
+            |x, t| self.drift(x, t),
+            |rng, t, dt| {
+                sample_log_jump(
+                    rng,
+                    self.lambda.0(t),
+                    self.jump_mean.0(t),
+                    self.jump_volatility.0(t),
+                    dt,
+                )
+            },
+        )
+    }
+}
+
+impl StochasticProcess for Bates {
+    fn drift(&self, x: f64, t: f64) -> f64 {
+        let k = lognormal_mean_relative_jump(self.jump_mean.0(t), self.jump_volatility.0(t));
+
+        (self.heston.mu.0(t) - self.lambda.0(t) * k) * x
+    }
+
+    /// Diffusion at the initial variance.
+    /// Simulated paths use the full variance process instead.
+    fn diffusion(&self, x: f64, t: f64) -> f64 {
+        self.heston.diffusion(x, t)
+    }
+
+    fn jump(&self, x: f64, t: f64) -> Option<f64> {
+        let z: f64 = simulation_rng().sample(StandardNormal);
+
+        Some(x * ((self.jump_mean.0(t) + self.jump_volatility.0(t) * z).exp() - 1.0))
+    }
+
+    fn parameters(&self) -> Vec<f64> {
+        let mut parameters = self.heston.parameters();
+        parameters.extend([
+            self.lambda.0(0.0),
+            self.jump_mean.0(0.0),
+            self.jump_volatility.0(0.0),
+        ]);
+
+        parameters
+    }
+
+    fn euler_maruyama(&self, config: &StochasticProcessConfig) -> Trajectories {
+        self.euler_maruyama_with_variance(config).0
+    }
+
+    fn simulate_with_scheme(
+        &self,
+        config: &StochasticProcessConfig,
+        scheme: DiscretisationScheme,
+    ) -> Trajectories {
+        self.simulate_with_variance(config, scheme).0
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_bates {
+    use super::*;
+    use RustQuant_math::*;
+    use RustQuant_utils::assert_approx_equal;
+
+    #[test]
+    fn test_bates_moments() {
+        let (mu, v, lambda, m, delta, t) = (0.05, 0.04, 1.0, -0.1, 0.2, 1.0);
+        let bates = Bates::new(mu, v, v, 2.0, -0.5, 0.2, lambda, m, delta);
+
+        let config = StochasticProcessConfig::new(100.0, 0.0, t, 250, 20_000, true);
+        let (asset, variance) = bates.euler_maruyama_with_variance(&config);
+
+        let S_T: Vec<f64> = asset.paths.iter().map(|p| p[250]).collect();
+        let v_T: Vec<f64> = variance.paths.iter().map(|p| p[250]).collect();
+        let log_returns: Vec<f64> = S_T.iter().map(|s| (s / 100.0).ln()).collect();
+
+        // The jumps are compensated, so E[S_T] = S_0 exp(mu T).
+        assert_approx_equal!(S_T.mean(), 100.0 * (mu * t).exp(), 1.0);
+
+        // The variance starts at its long-run level.
+        assert_approx_equal!(v_T.mean(), v, 0.002);
+
+        // Diffusive plus jump variance of the log return.
+        assert_approx_equal!(
+            log_returns.variance(),
+            v * t + lambda * t * (m * m + delta * delta),
+            0.01
+        );
+    }
+
+    #[test]
+    fn test_bates_without_jumps_is_heston() {
+        let bates = Bates::new(0.03, 0.04, 0.06, 1.5, -0.7, 0.3, 0.0, -0.1, 0.2);
+        let heston = Heston::new(0.03, 0.04, 0.06, 1.5, -0.7, 0.3);
+
+        assert_eq!(bates.drift(100.0, 0.0), heston.drift(100.0, 0.0));
+        assert_eq!(&bates.parameters()[..6], &heston.parameters()[..]);
+
+        let config = StochasticProcessConfig::new(100.0, 0.0, 1.0, 250, 10_000, true);
+        let S_T: Vec<f64> = bates
+            .euler_maruyama(&config)
+            .paths
+            .iter()
+            .map(|p| p[250])
+            .collect();
+
+        assert_approx_equal!(S_T.mean(), 100.0 * 0.03_f64.exp(), 1.0);
+    }
+}
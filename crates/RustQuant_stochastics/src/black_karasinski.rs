@@ -0,0 +1,153 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::model_parameter::ModelParameter;
+use crate::process::{
+    for_each_path, DiscretisationScheme, StochasticProcess, StochasticProcessConfig, Trajectories,
+};
+use rand::Rng;
+use rand_distr::StandardNormal;
+
+/// Struct containing the Black-Karasinski process parameters.
+///
+/// The log of the short rate mean-reverts:
+/// $d\ln r_t = \left[ \theta(t) - \alpha(t) \ln r_t \right] dt + \sigma(t) dW_t$,
+/// so simulated rates stay positive.
+pub struct BlackKarasinski {
+    /// Mean reversion speed of the log rate ($\alpha$).
+    pub alpha: ModelParameter,
+
+    /// Volatility of the log rate ($\sigma$).
+    pub sigma: ModelParameter,
+
+    /// Time-dependent drift of the log rate ($\theta(t)$).
+    pub theta: ModelParameter,
+}
+
+impl BlackKarasinski {
+    /// Create a new Black-Karasinski process.
+    pub fn new(
+        alpha: impl Into<ModelParameter>,
+        sigma: impl Into<ModelParameter>,
+        theta: impl Into<ModelParameter>,
+    ) -> Self {
+        Self {
+            alpha: alpha.into(),
+            sigma: sigma.into(),
+            theta: theta.into(),
+        }
+    }
+}
+
+impl StochasticProcess for BlackKarasinski {
+    /// Drift of the short rate itself, by Ito's lemma.
+    fn drift(&self, x: f64, t: f64) -> f64 {
+        let sigma = self.sigma.0(t);
+
+        x * (self.theta.0(t) - self.alpha.0(t) * x.ln() + 0.5 * sigma * sigma)
+    }
+
+    fn diffusion(&self, x: f64, t: f64) -> f64 {
+        self.sigma.0(t) * x
+    }
+
+    fn jump(&self, _x: f64, _t: f64) -> Option<f64> {
+        None
+    }
+
+    fn parameters(&self) -> Vec<f64> {
+        vec![self.alpha.0(0.0), self.sigma.0(0.0), self.theta.0(0.0)]
+    }
+
+    /// Euler-Maruyama on the log rate, so every simulated rate is positive.
+    fn euler_maruyama(&self, config: &StochasticProcessConfig) -> Trajectories {
+        let (x_0, t_0, t_n, n_steps, m_paths, parallel) = config.unpack();
+
+        assert!(t_0 < t_n);
+        assert!(x_0 > 0.0, "The initial short rate must be positive.");
+
+        let dt: f64 = (t_n - t_0) / (n_steps as f64);
+
+        // Initialise empty paths and fill in the time points.
+        let mut paths = vec![vec![x_0; n_steps + 1]; m_paths];
+        let times: Vec<f64> = (0..=n_steps).map(|t| t_0 + dt * (t as f64)).collect();
+
+        let path_generator = |(i, path): (usize, &mut Vec<f64>)| {
+            let mut rng = config.path_rng(i);
+            let scale = dt.sqrt();
+            let mut log_rate = x_0.ln();
+
+            for t in 0..n_steps {
+                let z: f64 = rng.sample(StandardNormal);
+
+                log_rate += (self.theta.0(times[t]) - self.alpha.0(times[t]) * log_rate) * dt
+                    + self.sigma.0(times[t]) * z * scale;
+
+                path[t + 1] = log_rate.exp();
+            }
+        };
+
+        for_each_path(&mut paths, parallel, path_generator);
+
+        Trajectories { times, paths }
+    }
+
+    /// The log rate has constant diffusion, where every scheme reduces to Euler-Maruyama.
+    fn simulate_with_scheme(
+        &self,
+        config: &StochasticProcessConfig,
+        _scheme: DiscretisationScheme,
+    ) -> Trajectories {
+        self.euler_maruyama(config)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_black_karasinski {
+    use super::*;
+    use RustQuant_math::*;
+    use RustQuant_utils::assert_approx_equal;
+
+    #[test]
+    fn test_black_karasinski_log_rate_moments() {
+        let (alpha, sigma, theta, r_0, t) = (0.5, 0.3, -1.5, 0.05_f64, 2.0_f64);
+
+        let bk = BlackKarasinski::new(alpha, sigma, theta);
+        let config = StochasticProcessConfig::new(r_0, 0.0, t, 500, 20_000, true);
+        let output = bk.euler_maruyama(&config);
+
+        let r_T: Vec<f64> = output.paths.iter().map(|p| p[500]).collect();
+        let log_r_T: Vec<f64> = r_T.iter().map(|r| r.ln()).collect();
+
+        // The log rate is Ornstein-Uhlenbeck, so ln r_T is Gaussian.
+        let decay = (-alpha * t).exp();
+        let mean = r_0.ln() * decay + theta / alpha * (1.0 - decay);
+        let variance = sigma * sigma / (2.0 * alpha) * (1.0 - decay * decay);
+
+        assert_approx_equal!(log_r_T.mean(), mean, 0.01);
+        assert_approx_equal!(log_r_T.variance(), variance, 0.005);
+
+        // And r_T is lognormal.
+        assert_approx_equal!(r_T.mean(), (mean + 0.5 * variance).exp(), 0.002);
+    }
+
+    #[test]
+    fn test_black_karasinski_positive_rates() {
+        // High volatility and a low starting rate.
+        let bk = BlackKarasinski::new(0.1, 1.0, |t: f64| -4.0 + 0.1 * t);
+        let config = StochasticProcessConfig::new(0.001, 0.0, 5.0, 100, 1000, false);
+        let output = bk.euler_maruyama(&config);
+
+        assert!(output.paths.iter().flatten().all(|&r| r > 0.0));
+    }
+}
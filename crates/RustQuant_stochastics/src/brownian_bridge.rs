@@ -0,0 +1,261 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Brownian bridge construction of Brownian motion paths.
+//!
+//! The first normal variate sets the terminal value, the second the
+//! midpoint, and so on, so most of the path's variance is carried by the
+//! leading variates. Combined with a low-discrepancy sequence, this puts
+//! the most important dimensions of the integrand in the best-distributed
+//! coordinates.
+
+use RustQuant_error::RustQuantError;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Brownian bridge path construction on a fixed time grid.
+#[derive(Debug, Clone)]
+pub struct BrownianBridge {
+    /// Times at which the path is constructed (excluding `t = 0`).
+    times: Vec<f64>,
+
+    /// Index of the time point set by each variate.
+    bridge_index: Vec<usize>,
+
+    /// Index of the left neighbour (plus one, zero meaning `t = 0`).
+    left_index: Vec<usize>,
+
+    /// Index of the right neighbour.
+    right_index: Vec<usize>,
+
+    /// Interpolation weight of the left neighbour.
+    left_weight: Vec<f64>,
+
+    /// Interpolation weight of the right neighbour.
+    right_weight: Vec<f64>,
+
+    /// Conditional standard deviation of each point.
+    std_dev: Vec<f64>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl BrownianBridge {
+    /// Create a new Brownian bridge on the given time grid.
+    ///
+    /// The path starts at zero at `t = 0`, which is not part of `times`.
+    ///
+    /// # Errors
+    /// - `RustQuantError::InvalidArgument` if `times` is empty,
+    ///   not strictly increasing, or does not start after zero.
+    pub fn new(times: &[f64]) -> Result<Self, RustQuantError> {
+        if times.is_empty() {
+            return Err(RustQuantError::InvalidArgument(
+                "The Brownian bridge needs at least one time point.".to_string(),
+            ));
+        }
+        if !(times[0] > 0.0 && times.windows(2).all(|w| w[0] < w[1])) {
+            return Err(RustQuantError::InvalidArgument(
+                "Brownian bridge times must be positive and strictly increasing.".to_string(),
+            ));
+        }
+
+        let n = times.len();
+        let t = times;
+
+        let mut bridge_index = vec![0; n];
+        let mut left_index = vec![0; n];
+        let mut right_index = vec![0; n];
+        let mut left_weight = vec![0.0; n];
+        let mut right_weight = vec![0.0; n];
+        let mut std_dev = vec![0.0; n];
+
+        // The first variate sets the terminal point.
+        let mut populated = vec![false; n];
+        populated[n - 1] = true;
+        bridge_index[0] = n - 1;
+        std_dev[0] = t[n - 1].sqrt();
+
+        // Each subsequent variate fills the midpoint of the next gap.
+        let mut j = 0;
+        for i in 1..n {
+            while populated[j] {
+                j += 1;
+            }
+            let mut k = j;
+            while !populated[k] {
+                k += 1;
+            }
+
+            // Points j..k are empty, k is populated.
+            let l = j + (k - 1 - j) / 2;
+            populated[l] = true;
+
+            bridge_index[i] = l;
+            left_index[i] = j;
+            right_index[i] = k;
+
+            let t_left = if j == 0 { 0.0 } else { t[j - 1] };
+
+            left_weight[i] = (t[k] - t[l]) / (t[k] - t_left);
+            right_weight[i] = (t[l] - t_left) / (t[k] - t_left);
+            std_dev[i] = ((t[l] - t_left) * (t[k] - t[l]) / (t[k] - t_left)).sqrt();
+
+            j = k + 1;
+            if j >= n {
+                j = 0;
+            }
+        }
+
+        Ok(Self {
+            times: times.to_vec(),
+            bridge_index,
+            left_index,
+            right_index,
+            left_weight,
+            right_weight,
+            std_dev,
+        })
+    }
+
+    /// Times at which the path is constructed.
+    pub fn times(&self) -> &[f64] {
+        &self.times
+    }
+
+    /// Number of normal variates (and time points) per path.
+    pub fn size(&self) -> usize {
+        self.times.len()
+    }
+
+    /// Build a Brownian motion path from independent standard normals.
+    ///
+    /// Returns the values of the path at each of `times()`.
+    ///
+    /// # Panics
+    /// Panics if `normals.len() != self.size()`.
+    pub fn build(&self, normals: &[f64]) -> Vec<f64> {
+        assert_eq!(normals.len(), self.size());
+
+        let mut path = vec![0.0; self.size()];
+
+        path[self.bridge_index[0]] = self.std_dev[0] * normals[0];
+
+        for (i, z) in normals.iter().enumerate().skip(1) {
+            let (j, k, l) = (
+                self.left_index[i],
+                self.right_index[i],
+                self.bridge_index[i],
+            );
+
+            let left = if j == 0 { 0.0 } else { path[j - 1] };
+
+            path[l] =
+                self.left_weight[i] * left + self.right_weight[i] * path[k] + self.std_dev[i] * z;
+        }
+
+        path
+    }
+
+    /// Build the increments of a Brownian motion path from independent
+    /// standard normals: `W(t_1) - 0, W(t_2) - W(t_1), ...`.
+    ///
+    /// # Panics
+    /// Panics if `normals.len() != self.size()`.
+    pub fn increments(&self, normals: &[f64]) -> Vec<f64> {
+        let path = self.build(normals);
+
+        std::iter::once(&0.0)
+            .chain(&path)
+            .zip(&path)
+            .map(|(left, right)| right - left)
+            .collect()
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_brownian_bridge {
+    use super::*;
+    use crate::seeded_rng;
+    use rand::Rng;
+    use rand_distr::StandardNormal;
+    use RustQuant_utils::{assert_approx_equal, RUSTQUANT_EPSILON};
+
+    #[test]
+    fn test_first_variate_sets_terminal_value() {
+        let bridge = BrownianBridge::new(&[0.25, 0.5, 0.75, 1.0]).unwrap();
+
+        let path = bridge.build(&[1.5, 0.0, 0.0, 0.0]);
+
+        // With all later variates zero, the path is a straight line.
+        for (w, t) in path.iter().zip(bridge.times()) {
+            assert_approx_equal!(*w, 1.5 * t, RUSTQUANT_EPSILON);
+        }
+    }
+
+    #[test]
+    fn test_bridge_covariance() {
+        // Cov(W(s), W(t)) = min(s, t).
+        let times = [0.1, 0.3, 0.4, 0.7, 0.8, 1.0, 1.5];
+        let bridge = BrownianBridge::new(&times).unwrap();
+        let mut rng = seeded_rng(1234);
+
+        let n_paths = 50_000;
+        let mut covariance = [[0.0; 7]; 7];
+
+        for _ in 0..n_paths {
+            let normals: Vec<f64> = (0..times.len())
+                .map(|_| rng.sample(StandardNormal))
+                .collect();
+            let path = bridge.build(&normals);
+
+            for a in 0..times.len() {
+                for b in 0..times.len() {
+                    covariance[a][b] += path[a] * path[b] / n_paths as f64;
+                }
+            }
+        }
+
+        for a in 0..times.len() {
+            for b in 0..times.len() {
+                assert_approx_equal!(covariance[a][b], times[a].min(times[b]), 0.03);
+            }
+        }
+    }
+
+    #[test]
+    fn test_increments_sum_to_path() {
+        let bridge = BrownianBridge::new(&[0.5, 1.0, 2.0]).unwrap();
+        let normals = [0.3, -1.2, 0.8];
+
+        let path = bridge.build(&normals);
+        let increments = bridge.increments(&normals);
+
+        let mut w = 0.0;
+        for (dw, expected) in increments.iter().zip(&path) {
+            w += dw;
+            assert_approx_equal!(w, *expected, RUSTQUANT_EPSILON);
+        }
+    }
+
+    #[test]
+    fn test_invalid_times() {
+        assert!(BrownianBridge::new(&[]).is_err());
+        assert!(BrownianBridge::new(&[0.0, 1.0]).is_err());
+        assert!(BrownianBridge::new(&[0.5, 0.5]).is_err());
+    }
+}
@@ -0,0 +1,326 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::model_parameter::ModelParameter;
+use crate::process::{
+    for_each_path, DiscretisationScheme, StochasticProcess, StochasticProcessConfig, Trajectories,
+};
+use num::Complex;
+use rand::{Rng, RngCore};
+use rand_distr::{Distribution, Poisson, StandardNormal};
+use statrs::function::gamma::gamma;
+
+/// Struct containing the CGMY process parameters.
+///
+/// A pure jump tempered stable process (Carr, Geman, Madan and Yor, 2002)
+/// with Levy density $C e^{-M x} x^{-1-Y}$ for $x > 0$ and
+/// $C e^{-G |x|} |x|^{-1-Y}$ for $x < 0$.
+///
+/// The process has infinite activity, so it is simulated approximately:
+/// jumps larger than `epsilon` are simulated exactly as compound Poisson,
+/// and the smaller jumps are replaced by a Brownian motion with the same
+/// mean and variance (Asmussen and Rosinski, 2001).
+pub struct CGMY {
+    /// Overall jump activity ($C$).
+    /// Note: $C \in (0, \infty)$.
+    pub c: ModelParameter,
+
+    /// Exponential tempering of the negative jumps ($G$).
+    /// Note: $G \in (0, \infty)$.
+    pub g: ModelParameter,
+
+    /// Exponential tempering of the positive jumps ($M$).
+    /// Note: $M \in (0, \infty)$.
+    pub m: ModelParameter,
+
+    /// Fine structure ($Y$), the activity of the small jumps.
+    /// Note: $Y \in (0, 2)$.
+    pub y: ModelParameter,
+
+    /// Jumps smaller than this are approximated by a Brownian motion.
+    /// Smaller values are more accurate but simulate many more jumps.
+    pub epsilon: f64,
+}
+
+impl CGMY {
+    /// Create a new CGMY process, approximating jumps smaller than 0.01.
+    pub fn new(
+        c: impl Into<ModelParameter>,
+        g: impl Into<ModelParameter>,
+        m: impl Into<ModelParameter>,
+        y: impl Into<ModelParameter>,
+    ) -> Self {
+        Self {
+            c: c.into(),
+            g: g.into(),
+            m: m.into(),
+            y: y.into(),
+            epsilon: 0.01,
+        }
+    }
+
+    /// Characteristic function of the increment $X_t - X_0$:
+    ///
+    /// $$
+    /// \phi(u) = \exp\left( t C \Gamma(-Y) \left[ (M - i u)^Y - M^Y
+    ///     + (G + i u)^Y - G^Y \right] \right)
+    /// $$
+    ///
+    /// Uses the parameters at time zero, so assumes they are constant.
+    /// Not defined for $Y = 1$.
+    pub fn characteristic_function(&self, u: f64, t: f64) -> Complex<f64> {
+        let (c, g, m, y) = (self.c.0(0.0), self.g.0(0.0), self.m.0(0.0), self.y.0(0.0));
+        let i: Complex<f64> = Complex::i();
+
+        let exponent = (m - i * u).powf(y) - m.powf(y) + (g + i * u).powf(y) - g.powf(y);
+
+        (t * c * gamma(-y) * exponent).exp()
+    }
+
+    /// Drift correction $\omega$ that makes $e^{\omega t + X_t}$ a martingale:
+    ///
+    /// $$
+    /// \omega = -C \Gamma(-Y) \left[ (M - 1)^Y - M^Y + (G + 1)^Y - G^Y \right]
+    /// $$
+    ///
+    /// An asset priced under the CGMY model is then
+    /// $S_t = S_0 e^{(r - q + \omega) t + X_t}$.
+    /// Uses the parameters at time zero, so assumes they are constant.
+    ///
+    /// # Panics
+    /// Panics if $M \leq 1$, when $e^{X_t}$ has no finite mean.
+    pub fn martingale_correction(&self) -> f64 {
+        let (c, g, m, y) = (self.c.0(0.0), self.g.0(0.0), self.m.0(0.0), self.y.0(0.0));
+
+        assert!(m > 1.0, "exp(X_t) must have a finite mean.");
+
+        -c * gamma(-y) * ((m - 1.0).powf(y) - m.powf(y) + (g + 1.0).powf(y) - g.powf(y))
+    }
+}
+
+/// Sum of the jumps larger than `epsilon` on one side, over a time step.
+///
+/// Proposes Pareto jumps with density $C x^{-1-Y}$ on $(\epsilon, \infty)$
+/// and keeps each with probability $e^{-\lambda x}$ (thinning).
+fn large_jumps(
+    rng: &mut dyn RngCore,
+    c: f64,
+    tempering: f64,
+    y: f64,
+    epsilon: f64,
+    dt: f64,
+) -> f64 {
+    let intensity = c * epsilon.powf(-y) / y * dt;
+    let n_proposals = Poisson::new(intensity).unwrap().sample(rng) as usize;
+
+    (0..n_proposals)
+        .map(|_| {
+            let u: f64 = rng.gen();
+            let jump = epsilon * (1.0 - u).powf(-1.0 / y);
+
+            if rng.gen::<f64>() < (-tempering * jump).exp() {
+                jump
+            } else {
+                0.0
+            }
+        })
+        .sum()
+}
+
+/// Mean and variance rates of the jumps smaller than `epsilon`,
+/// by integrating the Taylor series of the tempering term by term.
+fn small_jump_moments(c: f64, g: f64, m: f64, y: f64, epsilon: f64) -> (f64, f64) {
+    let (mut mean, mut variance) = (0.0, 0.0);
+    let (mut m_k, mut g_k, mut factorial) = (1.0, 1.0, 1.0);
+
+    for k in 0..50 {
+        if k > 0 {
+            m_k *= -m;
+            g_k *= -g;
+            factorial *= k as f64;
+
+            // The k = 0 terms of the two sides cancel in the mean.
+            let power = k as f64 + 1.0 - y;
+            mean += (m_k - g_k) / factorial * epsilon.powf(power) / power;
+        }
+
+        let power = k as f64 + 2.0 - y;
+        let term = (m_k + g_k) / factorial * epsilon.powf(power) / power;
+        variance += term;
+
+        if term.abs() < f64::EPSILON * variance.abs() {
+            break;
+        }
+    }
+
+    (c * mean, c * variance)
+}
+
+impl StochasticProcess for CGMY {
+    /// Mean rate of the process, $C \Gamma(1 - Y) (M^{Y - 1} - G^{Y - 1})$.
+    fn drift(&self, _x: f64, t: f64) -> f64 {
+        let (c, g, m, y) = (self.c.0(t), self.g.0(t), self.m.0(t), self.y.0(t));
+
+        c * gamma(1.0 - y) * (m.powf(y - 1.0) - g.powf(y - 1.0))
+    }
+
+    /// Standard deviation rate of the process,
+    /// $\sqrt{C \Gamma(2 - Y) (M^{Y - 2} + G^{Y - 2})}$.
+    /// Simulated paths use the jumps instead.
+    fn diffusion(&self, _x: f64, t: f64) -> f64 {
+        let (c, g, m, y) = (self.c.0(t), self.g.0(t), self.m.0(t), self.y.0(t));
+
+        (c * gamma(2.0 - y) * (m.powf(y - 2.0) + g.powf(y - 2.0))).sqrt()
+    }
+
+    fn jump(&self, _x: f64, _t: f64) -> Option<f64> {
+        None
+    }
+
+    fn parameters(&self) -> Vec<f64> {
+        vec![self.c.0(0.0), self.g.0(0.0), self.m.0(0.0), self.y.0(0.0)]
+    }
+
+    /// Simulation with exact large jumps and Brownian small jumps.
+    fn euler_maruyama(&self, config: &StochasticProcessConfig) -> Trajectories {
+        let (x_0, t_0, t_n, n_steps, m_paths, parallel) = config.unpack();
+
+        assert!(t_0 < t_n);
+        assert!(self.epsilon > 0.0, "The truncation level must be positive.");
+
+        let dt: f64 = (t_n - t_0) / (n_steps as f64);
+
+        // Initialise empty paths and fill in the time points.
+        let mut paths = vec![vec![x_0; n_steps + 1]; m_paths];
+        let times: Vec<f64> = (0..=n_steps).map(|t| t_0 + dt * (t as f64)).collect();
+
+        let path_generator = |(i, path): (usize, &mut Vec<f64>)| {
+            let mut rng = config.path_rng(i);
+
+            for t in 0..n_steps {
+                let (c, g, m, y) = (
+                    self.c.0(times[t]),
+                    self.g.0(times[t]),
+                    self.m.0(times[t]),
+                    self.y.0(times[t]),
+                );
+
+                assert!(
+                    c > 0.0 && g > 0.0 && m > 0.0,
+                    "C, G and M must be positive."
+                );
+                assert!(0.0 < y && y < 2.0, "Y must be in (0, 2).");
+
+                let (mean, variance) = small_jump_moments(c, g, m, y, self.epsilon);
+                let z: f64 = rng.sample(StandardNormal);
+
+                path[t + 1] = path[t]
+                    + mean * dt
+                    + (variance * dt).sqrt() * z
+                    + large_jumps(&mut rng, c, m, y, self.epsilon, dt)
+                    - large_jumps(&mut rng, c, g, y, self.epsilon, dt);
+            }
+        };
+
+        for_each_path(&mut paths, parallel, path_generator);
+
+        Trajectories { times, paths }
+    }
+
+    /// Uses the jump simulation of `euler_maruyama`, so the scheme is ignored.
+    fn simulate_with_scheme(
+        &self,
+        config: &StochasticProcessConfig,
+        _scheme: DiscretisationScheme,
+    ) -> Trajectories {
+        self.euler_maruyama(config)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_cgmy {
+    use super::*;
+    use RustQuant_math::Statistic;
+    use RustQuant_utils::assert_approx_equal;
+
+    fn terminal_values(cgmy: &CGMY, t: f64, n_paths: usize) -> Vec<f64> {
+        let config = StochasticProcessConfig::new(0.0, 0.0, t, 10, n_paths, true);
+
+        cgmy.euler_maruyama(&config)
+            .paths
+            .iter()
+            .map(|p| p[10])
+            .collect()
+    }
+
+    #[test]
+    fn test_cgmy_moments() {
+        // Both finite variation (Y < 1) and infinite variation (Y > 1).
+        for (c, g, m, y) in [(1.0, 5.0, 10.0, 0.5), (0.1, 8.0, 12.0, 1.5)] {
+            let cgmy = CGMY::new(c, g, m, y);
+            let X_T = terminal_values(&cgmy, 1.0, 20_000);
+
+            assert_approx_equal!(X_T.mean(), cgmy.drift(0.0, 0.0), 0.01);
+            assert_approx_equal!(X_T.variance(), cgmy.diffusion(0.0, 0.0).powi(2), 0.01);
+        }
+    }
+
+    #[test]
+    fn test_cgmy_empirical_characteristic_function() {
+        let cgmy = CGMY::new(1.0, 5.0, 10.0, 0.5);
+        let X_T = terminal_values(&cgmy, 1.0, 20_000);
+
+        for u in [1.0, 3.0, 6.0] {
+            let empirical = X_T
+                .iter()
+                .map(|x| Complex::new(0.0, u * x).exp())
+                .sum::<Complex<f64>>()
+                / 20_000.0;
+            let exact = cgmy.characteristic_function(u, 1.0);
+
+            assert_approx_equal!(empirical.re, exact.re, 0.02);
+            assert_approx_equal!(empirical.im, exact.im, 0.02);
+        }
+    }
+
+    #[test]
+    fn test_cgmy_martingale_correction() {
+        let cgmy = CGMY::new(0.1, 8.0, 12.0, 1.5);
+        let omega = cgmy.martingale_correction();
+        let X_T = terminal_values(&cgmy, 1.0, 20_000);
+
+        let mean = X_T.iter().map(|x| (omega + x).exp()).sum::<f64>() / 20_000.0;
+
+        assert_approx_equal!(mean, 1.0, 0.01);
+    }
+
+    #[test]
+    fn test_cgmy_small_jump_moments() {
+        // Against direct quadrature of x^2 k(x) and x k(x) near zero.
+        let (c, g, m, y, epsilon) = (1.0, 5.0, 10.0, 0.5, 0.05);
+        let (mean, variance) = small_jump_moments(c, g, m, y, epsilon);
+
+        let n = 200_000;
+        let h = epsilon / n as f64;
+        let (mut mean_quadrature, mut variance_quadrature) = (0.0, 0.0);
+        for i in 0..n {
+            let x = (i as f64 + 0.5) * h;
+            let density = c * x.powf(-1.0 - y);
+            mean_quadrature += x * density * ((-m * x).exp() - (-g * x).exp()) * h;
+            variance_quadrature += x * x * density * ((-m * x).exp() + (-g * x).exp()) * h;
+        }
+
+        assert_approx_equal!(mean, mean_quadrature, 1e-6);
+        assert_approx_equal!(variance, variance_quadrature, 1e-6);
+    }
+}
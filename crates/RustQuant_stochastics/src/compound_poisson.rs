@@ -0,0 +1,179 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::poisson_process::{jump_trajectories, JumpPath, PoissonProcess};
+use crate::process::{
+    map_paths, DiscretisationScheme, StochasticProcess, StochasticProcessConfig, Trajectories,
+};
+use rand::Rng;
+use rand_distr::Distribution;
+
+/// Struct containing the compound Poisson process parameters.
+///
+/// Jumps arrive at the times of a Poisson process with rate $\lambda$,
+/// and their sizes are drawn independently from `jump_distribution`,
+/// which can be any `rand_distr` distribution
+/// (e.g. `rand_distr::Exp` for insurance claim sizes).
+pub struct CompoundPoisson<D>
+where
+    D: Distribution<f64>,
+{
+    /// Arrival rate of the jumps ($\lambda$).
+    /// Note: $\lambda \in [0, \infty)$.
+    pub lambda: f64,
+
+    /// Distribution of the jump sizes.
+    pub jump_distribution: D,
+}
+
+impl<D> CompoundPoisson<D>
+where
+    D: Distribution<f64>,
+{
+    /// Create a new compound Poisson process.
+    ///
+    /// # Panics
+    /// Panics if `lambda` is negative.
+    pub fn new(lambda: f64, jump_distribution: D) -> Self {
+        assert!(lambda >= 0.0, "The arrival rate must be non-negative.");
+
+        Self {
+            lambda,
+            jump_distribution,
+        }
+    }
+
+    /// Jump times and sizes on `(t_0, t_n]`.
+    pub fn sample_jumps<R: Rng + ?Sized>(&self, t_0: f64, t_n: f64, rng: &mut R) -> JumpPath {
+        let times = PoissonProcess::new(self.lambda).sample_arrival_times(t_0, t_n, rng);
+        let sizes = times
+            .iter()
+            .map(|_| self.jump_distribution.sample(rng))
+            .collect();
+
+        JumpPath { times, sizes }
+    }
+}
+
+impl<D> CompoundPoisson<D>
+where
+    D: Distribution<f64> + Sync,
+{
+    /// Simulate the jumps on `[t_0, t_n]` of each path in `config`.
+    /// The number of steps in `config` is ignored.
+    pub fn jump_paths(&self, config: &StochasticProcessConfig) -> Vec<JumpPath> {
+        let (_, t_0, t_n, _, m_paths, parallel) = config.unpack();
+
+        assert!(t_0 < t_n);
+
+        let generator = |i| self.sample_jumps(t_0, t_n, &mut config.path_rng(i));
+
+        map_paths(m_paths, parallel, generator)
+    }
+}
+
+impl<D> StochasticProcess for CompoundPoisson<D>
+where
+    D: Distribution<f64> + Sync,
+{
+    /// No drift between jumps.
+    fn drift(&self, _x: f64, _t: f64) -> f64 {
+        0.0
+    }
+
+    fn diffusion(&self, _x: f64, _t: f64) -> f64 {
+        0.0
+    }
+
+    /// Jump sizes are random, see `jump_distribution`.
+    fn jump(&self, _x: f64, _t: f64) -> Option<f64> {
+        None
+    }
+
+    fn parameters(&self) -> Vec<f64> {
+        vec![self.lambda]
+    }
+
+    /// Exact simulation: the initial value plus the jumps so far,
+    /// at each time on the grid.
+    fn euler_maruyama(&self, config: &StochasticProcessConfig) -> Trajectories {
+        let (_, t_0, t_n, _, _, _) = config.unpack();
+
+        jump_trajectories(config, |rng| self.sample_jumps(t_0, t_n, rng))
+    }
+
+    /// Simulation is exact, so the scheme is ignored.
+    fn simulate_with_scheme(
+        &self,
+        config: &StochasticProcessConfig,
+        _scheme: DiscretisationScheme,
+    ) -> Trajectories {
+        self.euler_maruyama(config)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_compound_poisson {
+    use super::*;
+    use rand_distr::{Exp, Normal};
+    use RustQuant_math::Statistic;
+    use RustQuant_utils::assert_approx_equal;
+
+    #[test]
+    fn test_compound_poisson_claims() {
+        // Insurance claims: 4 a year, exponential sizes with mean 2.5.
+        let (lambda, mean_claim, t) = (4.0, 2.5, 1.5);
+
+        let claims = CompoundPoisson::new(lambda, Exp::new(1.0 / mean_claim).unwrap());
+        let config = StochasticProcessConfig::new(0.0, 0.0, t, 3, 20_000, true);
+        let output = claims.euler_maruyama(&config);
+
+        let S_T: Vec<f64> = output.paths.iter().map(|p| p[3]).collect();
+
+        // E[S_T] = lambda t E[J] and Var[S_T] = lambda t E[J^2].
+        assert_approx_equal!(S_T.mean(), lambda * t * mean_claim, 0.2);
+        assert_approx_equal!(
+            S_T.variance(),
+            lambda * t * 2.0 * mean_claim * mean_claim,
+            3.0
+        );
+
+        // Claims only ever add to the total.
+        assert!(output
+            .paths
+            .iter()
+            .all(|p| p.windows(2).all(|w| w[0] <= w[1])));
+    }
+
+    #[test]
+    fn test_compound_poisson_jump_paths() {
+        let process = CompoundPoisson::new(10.0, Normal::new(-0.1, 0.2).unwrap());
+        let config = StochasticProcessConfig::new(0.0, 0.0, 1.0, 1, 1000, false);
+
+        for path in process.jump_paths(&config) {
+            assert_eq!(path.times.len(), path.sizes.len());
+            assert!(path.times.windows(2).all(|w| w[0] < w[1]));
+            assert!(path.times.iter().all(|&t| 0.0 < t && t <= 1.0));
+        }
+    }
+
+    #[test]
+    fn test_compound_poisson_zero_rate() {
+        let process = CompoundPoisson::new(0.0, Exp::new(1.0).unwrap());
+        let config = StochasticProcessConfig::new(5.0, 0.0, 1.0, 10, 10, false);
+
+        let output = process.euler_maruyama(&config);
+
+        assert!(output.paths.iter().flatten().all(|&x| x == 5.0));
+    }
+}
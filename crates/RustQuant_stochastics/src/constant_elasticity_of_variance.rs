@@ -8,9 +8,21 @@
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
 use crate::model_parameter::ModelParameter;
-use crate::process::StochasticProcess;
+use crate::process::{
+    for_each_path, DiscretisationScheme, StochasticProcess, StochasticProcessConfig, Trajectories,
+};
+use rand::Rng;
+use rand_distr::StandardNormal;
 
 /// Struct containing the CEV process parameters.
+///
+/// $$
+/// dS(t) = \mu S(t) dt + \sigma S(t)^\gamma dW(t)
+/// $$
+///
+/// For $\gamma < 1$ the process can reach zero, which is treated as
+/// an absorbing boundary: once a path hits zero it stays there.
+/// For $\gamma = 1$ the process is a Geometric Brownian Motion.
 pub struct ConstantElasticityOfVariance {
     /// The long-run mean ($\mu$).
     pub mu: ModelParameter,
@@ -20,12 +32,12 @@ pub struct ConstantElasticityOfVariance {
 
     /// Elasticity parameter.
     /// Often denoted as $\beta$, $\rho$, or $\gamma$.
-    /// Must be in the unit interval $[0, 1]$.
+    /// Must be non-negative.
     pub elasticity: ModelParameter,
 }
 
 impl ConstantElasticityOfVariance {
-    /// Create a new Cox-Ingersoll-Ross process.
+    /// Create a new Constant Elasticity of Variance process.
     pub fn new(
         mu: impl Into<ModelParameter>,
         sigma: impl Into<ModelParameter>,
@@ -46,9 +58,9 @@ impl StochasticProcess for ConstantElasticityOfVariance {
 
     fn diffusion(&self, x: f64, t: f64) -> f64 {
         assert!(self.sigma.0(t) >= 0.0);
-        assert!(self.elasticity.0(t) >= 0.0 && self.elasticity.0(t) <= 1.0);
+        assert!(self.elasticity.0(t) >= 0.0);
 
-        self.sigma.0(t) * x.powf(self.elasticity.0(t))
+        self.sigma.0(t) * x.max(0.0).powf(self.elasticity.0(t))
     }
 
     fn jump(&self, _x: f64, _t: f64) -> Option<f64> {
@@ -58,6 +70,73 @@ impl StochasticProcess for ConstantElasticityOfVariance {
     fn parameters(&self) -> Vec<f64> {
         vec![self.mu.0(0.0), self.sigma.0(0.0), self.elasticity.0(0.0)]
     }
+
+    fn euler_maruyama(&self, config: &StochasticProcessConfig) -> Trajectories {
+        let (x_0, t_0, t_n, n_steps, m_paths, parallel) = config.unpack();
+
+        assert!(t_0 < t_n);
+
+        let dt: f64 = (t_n - t_0) / (n_steps as f64);
+
+        // Initialise empty paths and fill in the time points.
+        let mut paths = vec![vec![x_0; n_steps + 1]; m_paths];
+        let times: Vec<f64> = (0..=n_steps).map(|t| t_0 + dt * (t as f64)).collect();
+
+        let path_generator = |(i, path): (usize, &mut Vec<f64>)| {
+            let (dW, uniforms) = shocks(&mut config.path_rng(i), n_steps, dt);
+
+            for t in 0..n_steps {
+                if path[t] <= 0.0 {
+                    path[t + 1] = 0.0;
+                    continue;
+                }
+
+                let diffusion = self.diffusion(path[t], times[t]);
+
+                path[t + 1] =
+                    (path[t] + self.drift(path[t], times[t]) * dt + diffusion * dW[t]).max(0.0);
+
+                // For gamma < 1 the path can touch zero between two positive
+                // grid points. Absorb it with the Brownian bridge probability
+                // of crossing zero, using the diffusion frozen over the step.
+                if self.elasticity.0(times[t]) < 1.0 && path[t + 1] > 0.0 && diffusion > 0.0 {
+                    let crossing =
+                        (-2.0 * path[t] * path[t + 1] / (diffusion * diffusion * dt)).exp();
+
+                    if uniforms[t] < crossing {
+                        path[t + 1] = 0.0;
+                    }
+                }
+            }
+        };
+
+        for_each_path(&mut paths, parallel, path_generator);
+
+        Trajectories { times, paths }
+    }
+
+    /// Uses the boundary-aware Euler scheme of `euler_maruyama`, so the scheme is ignored.
+    fn simulate_with_scheme(
+        &self,
+        config: &StochasticProcessConfig,
+        _scheme: DiscretisationScheme,
+    ) -> Trajectories {
+        self.euler_maruyama(config)
+    }
+}
+
+/// Brownian increments and uniforms (for the boundary crossing test) for one path.
+fn shocks<R: Rng>(rng: &mut R, n_steps: usize, dt: f64) -> (Vec<f64>, Vec<f64>) {
+    let scale = dt.sqrt();
+
+    (0..n_steps)
+        .map(|_| {
+            let z: f64 = rng.sample(StandardNormal);
+            let u: f64 = rng.gen();
+
+            (z * scale, u)
+        })
+        .unzip()
 }
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -69,6 +148,7 @@ mod tests_cev {
     use super::*;
     use crate::StochasticProcessConfig;
     use RustQuant_math::*;
+    use RustQuant_utils::assert_approx_equal;
 
     #[test]
     fn test_cev_process() {
@@ -83,22 +163,59 @@ mod tests_cev {
             .filter_map(|v| v.last().copied())
             .collect();
 
-        let _E_XT = X_T.mean();
+        let E_XT = X_T.mean();
         let _V_XT = X_T.variance();
 
-        // Make a PR if you know the mean and variance of the CEV process.
+        // For gamma <= 1 the discounted process is a true martingale,
+        // even with absorption at zero, so E[X_T] = X_0 exp(mu T).
+        assert_approx_equal!(E_XT, 10. * (0.05 * 0.5_f64).exp(), 0.5);
 
-        // assert_approx_equal!(
-        //     E_XT,
-        //     10. * (-0.01 * 0.5_f64).exp() + 0.15 * (1. - (-0.01 * 0.5_f64).exp()),
-        //     0.5
-        // );
+        // Make a PR if you know the variance of the CEV process.
+    }
+
+    #[test]
+    fn test_cev_absorbing_boundary() {
+        let cev = ConstantElasticityOfVariance::new(0.0, 2.0, 0.5);
+        let config = StochasticProcessConfig::new(1.0, 0.0, 5.0, 500, 200, false);
+        let output = cev.euler_maruyama(&config);
+
+        for path in &output.paths {
+            assert!(path.iter().all(|&x| x >= 0.0));
+
+            if let Some(hit) = path.iter().position(|&x| x == 0.0) {
+                assert!(path[hit..].iter().all(|&x| x == 0.0));
+            }
+        }
+
+        // With such a large volatility, some paths should be absorbed.
+        assert!(output.paths.iter().any(|path| path[500] == 0.0));
+    }
+
+    #[test]
+    fn test_cev_absorption_probability() {
+        // With gamma = 1/2 and no drift, P(S_T = 0) = exp(-2 S_0 / (sigma^2 T)).
+        let (s_0, sigma, t) = (1.0, 2.0, 5.0);
+        let cev = ConstantElasticityOfVariance::new(0.0, sigma, 0.5);
+
+        // A coarse grid, where the crossing correction matters most.
+        let config = StochasticProcessConfig::new(s_0, 0.0, t, 50, 20_000, true);
+        let output = cev.euler_maruyama(&config);
+
+        let absorbed = output.paths.iter().filter(|path| path[50] == 0.0).count();
+
+        assert_approx_equal!(
+            absorbed as f64 / 20_000.0,
+            (-2.0 * s_0 / (sigma * sigma * t)).exp(),
+            0.01
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_cev_negative_elasticity() {
+        let cev = ConstantElasticityOfVariance::new(0.05, 0.2, -0.5);
+        let config = StochasticProcessConfig::new(10.0, 0.0, 0.5, 10, 1, false);
 
-        // assert_approx_equal!(
-        //     V_XT,
-        //     10. * (0.45 * 0.45 / 0.01) * ((-0.01 * 0.5_f64).exp() - (-2. * 0.01 * 0.5_f64).exp())
-        //         + (0.15 * 0.45 * 0.45 / (2. * 0.01)) * (1. - (-0.01 * 0.5_f64).exp()).powi(2),
-        //     0.5
-        // );
+        cev.euler_maruyama(&config);
     }
 }
@@ -0,0 +1,316 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Correlated multi-asset Geometric Brownian Motion.
+//!
+//! Each asset follows
+//! $dS_i = \mu_i S_i dt + \sigma_i S_i dW_i$, with
+//! $d\langle W_i, W_j \rangle = \rho_{ij} dt$.
+
+use crate::{PathConstruction, SamplingMethod, StochasticProcessConfig};
+use nalgebra::{DMatrix, DVector};
+use rand::Rng;
+use rand_distr::StandardNormal;
+use RustQuant_error::RustQuantError;
+use RustQuant_math::correlation_cholesky;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Correlated Geometric Brownian Motions.
+#[derive(Debug, Clone)]
+pub struct CorrelatedGeometricBrownianMotion {
+    /// Drift ($\mu_i$) of each asset.
+    drifts: Vec<f64>,
+
+    /// Volatility ($\sigma_i$) of each asset.
+    volatilities: Vec<f64>,
+
+    /// Lower Cholesky factor of the correlation matrix.
+    cholesky: DMatrix<f64>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl CorrelatedGeometricBrownianMotion {
+    /// Create a new set of correlated Geometric Brownian Motions.
+    ///
+    /// # Errors
+    /// - `RustQuantError::UnequalLength` if `drifts`, `volatilities` and
+    ///   `correlation` have different dimensions.
+    /// - `RustQuantError::InvalidArgument` if a volatility is negative, or
+    ///   `correlation` is not a valid correlation matrix.
+    pub fn new(
+        drifts: Vec<f64>,
+        volatilities: Vec<f64>,
+        correlation: &[Vec<f64>],
+    ) -> Result<Self, RustQuantError> {
+        if drifts.len() != volatilities.len() || drifts.len() != correlation.len() {
+            return Err(RustQuantError::UnequalLength);
+        }
+        if volatilities.iter().any(|v| v.is_nan() || *v < 0.0) {
+            return Err(RustQuantError::InvalidArgument(
+                "Volatilities must be non-negative.".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            drifts,
+            volatilities,
+            cholesky: correlation_cholesky(correlation)?,
+        })
+    }
+
+    /// Number of assets.
+    pub fn dimension(&self) -> usize {
+        self.drifts.len()
+    }
+
+    /// Drift ($\mu_i$) of each asset.
+    pub fn drifts(&self) -> &[f64] {
+        &self.drifts
+    }
+
+    /// Volatility ($\sigma_i$) of each asset.
+    pub fn volatilities(&self) -> &[f64] {
+        &self.volatilities
+    }
+
+    /// Correlation matrix of the driving Brownian motions.
+    pub fn correlation(&self) -> DMatrix<f64> {
+        &self.cholesky * self.cholesky.transpose()
+    }
+
+    /// Sample the asset prices at time `t`, starting from `initial`.
+    ///
+    /// The terminal distribution is lognormal, so this is exact and needs
+    /// no time stepping.
+    ///
+    /// # Panics
+    /// Panics if `initial.len() != self.dimension()`.
+    pub fn sample_terminal<R: Rng>(&self, initial: &[f64], t: f64, rng: &mut R) -> Vec<f64> {
+        assert_eq!(initial.len(), self.dimension());
+
+        let z = DVector::from_fn(self.dimension(), |_, _| rng.sample(StandardNormal));
+
+        self.terminal_from_draws(initial, t, &z)
+    }
+
+    /// Sample `config.m_paths` paths of the asset prices at each of the
+    /// increasing `times`, starting from `initial` at time zero, drawing
+    /// from the streams of `config`.
+    ///
+    /// Returns, for each path, the asset prices at each time.
+    /// The sampling is exact, so only the number of paths, the seed and
+    /// antithetic sampling are taken from `config`; its time grid, `x_0`
+    /// and `parallel` flag are ignored.
+    ///
+    /// # Errors
+    /// - `RustQuantError::InvalidArgument` if `config` asks for sampling
+    ///   other than pseudo-random, or a Brownian bridge construction, which
+    ///   the exact sampler cannot honour.
+    ///
+    /// # Panics
+    /// Panics if `initial.len() != self.dimension()`.
+    pub fn sample_at_times(
+        &self,
+        initial: &[f64],
+        times: &[f64],
+        config: &StochasticProcessConfig,
+    ) -> Result<Vec<Vec<Vec<f64>>>, RustQuantError> {
+        assert_eq!(initial.len(), self.dimension());
+
+        if config.sampling != SamplingMethod::PseudoRandom
+            || config.construction != PathConstruction::Incremental
+        {
+            return Err(RustQuantError::InvalidArgument(
+                "Exact sampling only supports pseudo-random, incremental paths.".to_string(),
+            ));
+        }
+
+        let group_size = config.paths_per_draw();
+
+        Ok((0..config.m_paths.div_ceil(group_size))
+            .flat_map(|group| {
+                let mut rng = config.path_rng(group);
+                let draws: Vec<DVector<f64>> = times
+                    .iter()
+                    .map(|_| DVector::from_fn(self.dimension(), |_, _| rng.sample(StandardNormal)))
+                    .collect();
+                let members = group_size.min(config.m_paths - group * group_size);
+
+                // An antithetic partner reuses the draws with the opposite sign.
+                [1.0, -1.0]
+                    .into_iter()
+                    .take(members)
+                    .map(|sign| {
+                        let mut prices = initial.to_vec();
+                        let mut previous = 0.0;
+
+                        times
+                            .iter()
+                            .zip(&draws)
+                            .map(|(&time, z)| {
+                                prices =
+                                    self.terminal_from_draws(&prices, time - previous, &(z * sign));
+                                previous = time;
+                                prices.clone()
+                            })
+                            .collect()
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect())
+    }
+
+    /// Asset prices at time `t` for independent standard normal draws `z`.
+    fn terminal_from_draws(&self, initial: &[f64], t: f64, z: &DVector<f64>) -> Vec<f64> {
+        let w = &self.cholesky * z * t.sqrt();
+
+        initial
+            .iter()
+            .zip(&self.drifts)
+            .zip(&self.volatilities)
+            .zip(w.iter())
+            .map(|(((s, mu), sigma), w)| s * ((mu - 0.5 * sigma * sigma) * t + sigma * w).exp())
+            .collect()
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_correlated_gbm {
+    use super::*;
+    use crate::seeded_rng;
+    use RustQuant_utils::assert_approx_equal;
+
+    #[test]
+    fn test_terminal_moments_and_correlation() {
+        let (mu, sigma, rho, t) = ([0.05, 0.02], [0.2, 0.3], 0.6, 2.0);
+        let process = CorrelatedGeometricBrownianMotion::new(
+            mu.to_vec(),
+            sigma.to_vec(),
+            &[vec![1.0, rho], vec![rho, 1.0]],
+        )
+        .unwrap();
+
+        let mut rng = seeded_rng(7);
+        let n = 100_000;
+        let samples: Vec<Vec<f64>> = (0..n)
+            .map(|_| process.sample_terminal(&[100.0, 50.0], t, &mut rng))
+            .collect();
+
+        // E[S_i(t)] = S_i(0) exp(mu_i t).
+        for (i, s0) in [100.0, 50.0].iter().enumerate() {
+            let mean = samples.iter().map(|s| s[i]).sum::<f64>() / n as f64;
+            assert_approx_equal!(mean / (s0 * (mu[i] * t).exp()), 1.0, 0.01);
+        }
+
+        // The log-returns have correlation rho.
+        let logs: Vec<(f64, f64)> = samples
+            .iter()
+            .map(|s| ((s[0] / 100.0).ln(), (s[1] / 50.0).ln()))
+            .collect();
+        let mean_x = logs.iter().map(|l| l.0).sum::<f64>() / n as f64;
+        let mean_y = logs.iter().map(|l| l.1).sum::<f64>() / n as f64;
+        let cov = logs
+            .iter()
+            .map(|l| (l.0 - mean_x) * (l.1 - mean_y))
+            .sum::<f64>()
+            / n as f64;
+
+        assert_approx_equal!(cov / (sigma[0] * sigma[1] * t), rho, 0.01);
+    }
+
+    #[test]
+    fn test_sample_at_times_from_config() {
+        let process = CorrelatedGeometricBrownianMotion::new(
+            vec![0.05, 0.02],
+            vec![0.2, 0.3],
+            &[vec![1.0, 0.6], vec![0.6, 1.0]],
+        )
+        .unwrap();
+        let (initial, t) = ([100.0, 50.0], 2.0);
+
+        let config = StochasticProcessConfig::new(0.0, 0.0, t, 1, 5, false).with_seed(3);
+        let samples = process
+            .sample_at_times(&initial, &[1.0, t], &config)
+            .unwrap();
+
+        assert_eq!(samples.len(), 5);
+        assert_eq!(samples[0].len(), 2);
+        assert_eq!(
+            samples,
+            process
+                .sample_at_times(&initial, &[1.0, t], &config)
+                .unwrap()
+        );
+
+        // Antithetic partners have log-returns mirrored about their mean.
+        let antithetic: Vec<Vec<f64>> = process
+            .sample_at_times(&initial, &[t], &config.clone().with_antithetic(true))
+            .unwrap()
+            .into_iter()
+            .map(|path| path[0].clone())
+            .collect();
+
+        assert_eq!(antithetic.len(), 5);
+        for (i, (s0, mu, sigma)) in [(100.0, 0.05, 0.2), (50.0, 0.02, 0.3)].iter().enumerate() {
+            let drift = (mu - 0.5 * sigma * sigma) * t;
+            let log_return = |s: &[f64]| (s[i] / s0).ln() - drift;
+
+            assert_approx_equal!(
+                log_return(&antithetic[0]),
+                -log_return(&antithetic[1]),
+                1e-12
+            );
+        }
+
+        // Quasi-random draws and Brownian bridges are not supported.
+        assert!(process
+            .sample_at_times(
+                &initial,
+                &[t],
+                &config.clone().with_sampling(SamplingMethod::Sobol)
+            )
+            .is_err());
+        assert!(process
+            .sample_at_times(
+                &initial,
+                &[t],
+                &config.with_path_construction(PathConstruction::BrownianBridge)
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        let identity = [vec![1.0, 0.0], vec![0.0, 1.0]];
+
+        assert!(
+            CorrelatedGeometricBrownianMotion::new(vec![0.0], vec![0.2, 0.2], &identity).is_err()
+        );
+        assert!(
+            CorrelatedGeometricBrownianMotion::new(vec![0.0, 0.0], vec![0.2, -0.2], &identity)
+                .is_err()
+        );
+        assert!(CorrelatedGeometricBrownianMotion::new(
+            vec![0.0, 0.0],
+            vec![0.2, 0.2],
+            &[vec![1.0, 1.5], vec![1.5, 1.0]]
+        )
+        .is_err());
+    }
+}
@@ -7,8 +7,12 @@
 //      - LICENSE-MIT.md
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
+use crate::estimation::{validate_series, LeastSquares, ProcessFit};
 use crate::model_parameter::ModelParameter;
-use crate::process::StochasticProcess;
+use crate::process::{for_each_path, StochasticProcess, StochasticProcessConfig, Trajectories};
+use rand::Rng;
+use rand_distr::{Distribution, Gamma, Poisson};
+use RustQuant_error::RustQuantError;
 
 /// Struct containing the Ornstein-Uhlenbeck process parameters.
 #[derive(Debug)]
@@ -37,6 +41,117 @@ impl CoxIngersollRoss {
             theta: theta.into(),
         }
     }
+
+    /// Simulate by sampling the exact transition density:
+    /// given $X_t$, $X_{t + \Delta} / c$ is noncentral chi-squared with
+    /// $d = 4 \theta \mu / \sigma^2$ degrees of freedom and noncentrality
+    /// $X_t e^{-\theta \Delta} / c$, where
+    /// $c = \sigma^2 (1 - e^{-\theta \Delta}) / (4 \theta)$.
+    ///
+    /// Unlike the Euler scheme, the paths never go negative and there is no
+    /// discretisation bias. Time-dependent parameters are frozen at the start
+    /// of each step, so the scheme is only exact for constant parameters.
+    pub fn simulate_exact(&self, config: &StochasticProcessConfig) -> Trajectories {
+        let (x_0, t_0, t_n, n_steps, m_paths, parallel) = config.unpack();
+
+        assert!(t_0 < t_n);
+
+        let dt: f64 = (t_n - t_0) / (n_steps as f64);
+
+        // Initialise empty paths and fill in the time points.
+        let mut paths = vec![vec![x_0; n_steps + 1]; m_paths];
+        let times: Vec<f64> = (0..=n_steps).map(|t| t_0 + dt * (t as f64)).collect();
+
+        let path_generator = |(i, path): (usize, &mut Vec<f64>)| {
+            let mut rng = config.path_rng(i);
+
+            for t in 0..n_steps {
+                let (mu, sigma, theta) = (
+                    self.mu.0(times[t]),
+                    self.sigma.0(times[t]),
+                    self.theta.0(times[t]),
+                );
+                assert!(sigma > 0.0 && theta > 0.0);
+
+                let decay = (-theta * dt).exp();
+                let c = sigma * sigma * (1.0 - decay) / (4.0 * theta);
+                let degrees_of_freedom = 4.0 * theta * mu / (sigma * sigma);
+                let noncentrality = path[t].max(0.0) * decay / c;
+
+                path[t + 1] =
+                    c * noncentral_chi_squared(&mut rng, degrees_of_freedom, noncentrality);
+            }
+        };
+
+        for_each_path(&mut paths, parallel, path_generator);
+
+        Trajectories { times, paths }
+    }
+
+    /// Fit the process to a time series observed every `dt` years, by
+    /// least squares on the Euler discretisation scaled by $\sqrt{X_t}$:
+    /// $\frac{X_{t + dt} - X_t}{\sqrt{X_t}} = \theta \mu \frac{dt}{\sqrt{X_t}}
+    /// - \theta dt \sqrt{X_t} + \sigma \sqrt{dt} \epsilon$.
+    ///
+    /// The estimates are biased for coarsely sampled series.
+    ///
+    /// # Errors
+    /// - `RustQuantError::InvalidArgument` if there are fewer than three
+    ///   observations, `dt` is not positive, or the series is not positive.
+    /// - `RustQuantError::ComputationError` if the series is not mean reverting.
+    pub fn fit(series: &[f64], dt: f64) -> Result<ProcessFit<Self>, RustQuantError> {
+        validate_series(series, dt)?;
+
+        if series.iter().any(|&x| x <= 0.0) {
+            return Err(RustQuantError::InvalidArgument(
+                "The Cox-Ingersoll-Ross process requires a positive time series.".to_string(),
+            ));
+        }
+
+        let (z, y): (Vec<[f64; 2]>, Vec<f64>) = series
+            .windows(2)
+            .map(|w| {
+                let root = w[0].sqrt();
+                ([dt / root, -dt * root], (w[1] - w[0]) / root)
+            })
+            .unzip();
+        let ls = LeastSquares::fit(&z, &y)?;
+        let [theta_mu, theta] = ls.beta;
+
+        if theta <= 0.0 {
+            return Err(RustQuantError::ComputationError(
+                "The time series is not mean reverting.".to_string(),
+            ));
+        }
+
+        let mu = theta_mu / theta;
+        let sigma = (ls.residual_variance / dt).sqrt();
+
+        let mu_se = ls.delta_standard_error([1.0 / theta, -theta_mu / (theta * theta)]);
+        let theta_se = ls.delta_standard_error([0.0, 1.0]);
+        let sigma_se = ls.residual_variance_standard_error() / (2.0 * sigma * dt);
+
+        Ok(ProcessFit {
+            process: Self::new(mu, sigma, theta),
+            estimates: vec![mu, sigma, theta],
+            standard_errors: vec![mu_se, sigma_se, theta_se],
+        })
+    }
+}
+
+/// Noncentral chi-squared draw, as a Poisson mixture of central
+/// chi-squared draws: $\chi^2_{d + 2N}$ with $N \sim Poisson(\lambda / 2)$.
+fn noncentral_chi_squared<R: Rng>(rng: &mut R, degrees_of_freedom: f64, noncentrality: f64) -> f64 {
+    let n = if noncentrality > 0.0 {
+        Poisson::new(0.5 * noncentrality).unwrap().sample(rng)
+    } else {
+        0.0
+    };
+
+    // A chi-squared with k degrees of freedom is Gamma(k / 2, 2).
+    Gamma::new(0.5 * degrees_of_freedom + n, 2.0)
+        .unwrap()
+        .sample(rng)
 }
 
 impl StochasticProcess for CoxIngersollRoss {
@@ -46,7 +161,17 @@ impl StochasticProcess for CoxIngersollRoss {
 
     fn diffusion(&self, x: f64, t: f64) -> f64 {
         assert!(self.sigma.0(t) >= 0.0);
-        self.sigma.0(t) * x.sqrt()
+        self.sigma.0(t) * x.max(0.0).sqrt()
+    }
+
+    fn diffusion_derivative(&self, x: f64, t: f64) -> f64 {
+        // The Milstein term b b' = sigma^2 / 2 does not vanish at zero,
+        // but b does, so keep the product finite there.
+        if x <= 0.0 {
+            return 0.0;
+        }
+
+        0.5 * self.sigma.0(t) / x.sqrt()
     }
 
     fn jump(&self, _x: f64, _t: f64) -> Option<f64> {
@@ -105,4 +230,84 @@ mod tests_cir {
         // let file2 = "./images/CIR2.png";
         // plot_vector((&output.trajectories[1]).clone(), file2)
     }
+
+    // Long-run mean 4%, volatility 30%, mean reversion 0.5,
+    // which violates the Feller condition (2 theta mu < sigma^2).
+    fn feller_violating() -> CoxIngersollRoss {
+        CoxIngersollRoss::new(0.04, 0.3, 0.5)
+    }
+
+    fn exact_moments(x_0: f64, t: f64) -> (f64, f64) {
+        let (mu, sigma, theta) = (0.04, 0.3, 0.5);
+        let decay = (-theta * t).exp();
+
+        let mean = x_0 * decay + mu * (1.0 - decay);
+        let variance = x_0 * sigma * sigma / theta * (decay - decay * decay)
+            + mu * sigma * sigma / (2.0 * theta) * (1.0 - decay).powi(2);
+
+        (mean, variance)
+    }
+
+    #[test]
+    fn test_exact_simulation_moments() {
+        let config = StochasticProcessConfig::new(0.03, 0.0, 2.0, 4, 200_000, true).with_seed(1);
+        let output = feller_violating().simulate_exact(&config);
+
+        for (step, t) in [(1, 0.5), (4, 2.0)] {
+            let X_t: Vec<f64> = output.paths.iter().map(|p| p[step]).collect();
+            let (mean, variance) = exact_moments(0.03, t);
+
+            assert_approx_equal!(X_t.mean(), mean, 5e-4);
+            assert_approx_equal!(X_t.variance(), variance, 5e-5);
+        }
+
+        assert!(output.paths.iter().flatten().all(|&x| x >= 0.0));
+    }
+
+    #[test]
+    fn test_exact_simulation_validates_euler() {
+        let cir = feller_violating();
+
+        // The Euler scheme converges weakly to the exact distribution.
+        let config = StochasticProcessConfig::new(0.03, 0.0, 1.0, 250, 50_000, true).with_seed(2);
+        let euler: Vec<f64> = cir
+            .euler_maruyama(&config)
+            .paths
+            .iter()
+            .map(|p| p[250].max(0.0))
+            .collect();
+
+        let config = StochasticProcessConfig::new(0.03, 0.0, 1.0, 1, 50_000, true).with_seed(3);
+        let exact: Vec<f64> = cir
+            .simulate_exact(&config)
+            .paths
+            .iter()
+            .map(|p| p[1])
+            .collect();
+
+        assert_approx_equal!(euler.mean(), exact.mean(), 5e-4);
+        assert_approx_equal!(euler.variance(), exact.variance(), 5e-5);
+    }
+
+    #[test]
+    fn test_fit() {
+        let cir = CoxIngersollRoss::new(0.05, 0.1, 1.5);
+
+        // Twenty years of weekly observations, sampled exactly.
+        let config = StochasticProcessConfig::new(0.04, 0.0, 20.0, 1040, 1, false).with_seed(9);
+        let series = &cir.simulate_exact(&config).paths[0];
+
+        let fit = CoxIngersollRoss::fit(series, 20.0 / 1040.0).unwrap();
+
+        assert_eq!(fit.estimates, fit.process.parameters());
+        for (estimate, (truth, se)) in fit
+            .estimates
+            .iter()
+            .zip([0.05, 0.1, 1.5].iter().zip(&fit.standard_errors))
+        {
+            assert!((estimate - truth).abs() < 3.0 * se);
+        }
+
+        assert!(CoxIngersollRoss::fit(&[0.01, 0.0, 0.02], 0.1).is_err());
+    }
 }
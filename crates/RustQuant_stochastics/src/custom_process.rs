@@ -0,0 +1,128 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::process::StochasticProcess;
+
+/// Coefficient of a `CustomProcess`, as a function of `(x, t)`.
+pub type Coefficient = Box<dyn Fn(f64, f64) -> f64 + Send + Sync>;
+
+/// One-factor SDE $dX_t = a(X_t, t) dt + b(X_t, t) dW_t$ with user-supplied
+/// drift and diffusion, simulated with the usual schemes.
+///
+/// ```
+/// use RustQuant_stochastics::*;
+///
+/// // A square-root diffusion with a time-dependent long-run mean.
+/// let process = CustomProcess::new(
+///     |x, t| 2.0 * (0.04 + 0.01 * t - x),
+///     |x, _| 0.1 * x.max(0.0).sqrt(),
+/// );
+///
+/// let config = StochasticProcessConfig::new(0.03, 0.0, 1.0, 100, 10, false);
+/// let output = process.euler_maruyama(&config);
+/// ```
+pub struct CustomProcess {
+    /// The drift, $a(x, t)$.
+    pub drift: Coefficient,
+
+    /// The diffusion, $b(x, t)$.
+    pub diffusion: Coefficient,
+
+    /// Derivative of the diffusion with respect to $x$, if known.
+    /// Otherwise a finite difference is used by the Milstein and
+    /// predictor-corrector schemes.
+    pub diffusion_derivative: Option<Coefficient>,
+}
+
+impl CustomProcess {
+    /// Create a new process from its drift and diffusion.
+    pub fn new<A, B>(drift: A, diffusion: B) -> Self
+    where
+        A: Fn(f64, f64) -> f64 + Send + Sync + 'static,
+        B: Fn(f64, f64) -> f64 + Send + Sync + 'static,
+    {
+        Self {
+            drift: Box::new(drift),
+            diffusion: Box::new(diffusion),
+            diffusion_derivative: None,
+        }
+    }
+
+    /// Supply the derivative of the diffusion with respect to `x`.
+    pub fn with_diffusion_derivative<C>(mut self, diffusion_derivative: C) -> Self
+    where
+        C: Fn(f64, f64) -> f64 + Send + Sync + 'static,
+    {
+        self.diffusion_derivative = Some(Box::new(diffusion_derivative));
+        self
+    }
+}
+
+impl StochasticProcess for CustomProcess {
+    fn drift(&self, x: f64, t: f64) -> f64 {
+        (self.drift)(x, t)
+    }
+
+    fn diffusion(&self, x: f64, t: f64) -> f64 {
+        (self.diffusion)(x, t)
+    }
+
+    fn diffusion_derivative(&self, x: f64, t: f64) -> f64 {
+        match &self.diffusion_derivative {
+            Some(derivative) => derivative(x, t),
+            None => {
+                let h = 1e-6 * (1.0 + x.abs());
+
+                (self.diffusion(x + h, t) - self.diffusion(x - h, t)) / (2.0 * h)
+            }
+        }
+    }
+
+    fn jump(&self, _x: f64, _t: f64) -> Option<f64> {
+        None
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_custom_process {
+    use super::*;
+    use crate::{DiscretisationScheme, GeometricBrownianMotion, StochasticProcessConfig};
+    use RustQuant_utils::assert_approx_equal;
+
+    #[test]
+    fn test_custom_process_matches_gbm() {
+        let gbm = GeometricBrownianMotion::new(0.05, 0.2);
+        let custom = CustomProcess::new(|x, _| 0.05 * x, |x, _| 0.2 * x);
+
+        let config = StochasticProcessConfig::new(100.0, 0.0, 1.0, 50, 10, false).with_seed(1);
+
+        assert_eq!(
+            custom.euler_maruyama(&config).paths,
+            gbm.euler_maruyama(&config).paths
+        );
+
+        // Milstein, with the finite difference and the exact derivative.
+        let expected = gbm.simulate_with_scheme(&config, DiscretisationScheme::Milstein);
+        let approximate = custom.simulate_with_scheme(&config, DiscretisationScheme::Milstein);
+        let exact = custom
+            .with_diffusion_derivative(|_, _| 0.2)
+            .simulate_with_scheme(&config, DiscretisationScheme::Milstein);
+
+        assert_eq!(exact.paths, expected.paths);
+        for (path, expected) in approximate.paths.iter().zip(&expected.paths) {
+            for (x, y) in path.iter().zip(expected) {
+                assert_approx_equal!(x, y, 1e-6);
+            }
+        }
+    }
+}
@@ -0,0 +1,177 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use RustQuant_error::RustQuantError;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// A process fitted to a time series, with its parameter estimates.
+///
+/// `estimates` and `standard_errors` are in the same order as the
+/// process's `parameters()`. The standard errors are asymptotic.
+pub struct ProcessFit<P> {
+    /// The fitted process, ready to simulate.
+    pub process: P,
+
+    /// The parameter estimates.
+    pub estimates: Vec<f64>,
+
+    /// The standard errors of the parameter estimates.
+    pub standard_errors: Vec<f64>,
+}
+
+/// Least squares fit of $y_i = \beta_1 z_{i1} + \beta_2 z_{i2} + \epsilon_i$.
+pub(crate) struct LeastSquares {
+    /// The coefficients $\beta$.
+    pub(crate) beta: [f64; 2],
+
+    /// The covariance matrix of $\beta$.
+    pub(crate) covariance: [[f64; 2]; 2],
+
+    /// The maximum likelihood residual variance, $\sum \epsilon_i^2 / n$.
+    pub(crate) residual_variance: f64,
+
+    /// The number of observations.
+    pub(crate) n: usize,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Check that a time series can be used for estimation.
+pub(crate) fn validate_series(series: &[f64], dt: f64) -> Result<(), RustQuantError> {
+    if series.len() < 3 {
+        return Err(RustQuantError::InvalidArgument(
+            "At least three observations are needed to fit a process.".to_string(),
+        ));
+    }
+    if !(dt > 0.0 && dt.is_finite()) {
+        return Err(RustQuantError::InvalidArgument(
+            "The time step must be positive and finite.".to_string(),
+        ));
+    }
+    if series.iter().any(|x| !x.is_finite()) {
+        return Err(RustQuantError::InvalidArgument(
+            "The time series contains non-finite values.".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+impl LeastSquares {
+    /// Regress `y` on the two regressors in `z`, without an intercept.
+    pub(crate) fn fit(z: &[[f64; 2]], y: &[f64]) -> Result<Self, RustQuantError> {
+        if z.len() != y.len() {
+            return Err(RustQuantError::UnequalLength);
+        }
+
+        let n = y.len();
+
+        // Normal equations: (Z'Z) beta = Z'y.
+        let (mut s11, mut s12, mut s22, mut r1, mut r2) = (0.0, 0.0, 0.0, 0.0, 0.0);
+        for (z_i, y_i) in z.iter().zip(y) {
+            s11 += z_i[0] * z_i[0];
+            s12 += z_i[0] * z_i[1];
+            s22 += z_i[1] * z_i[1];
+            r1 += z_i[0] * y_i;
+            r2 += z_i[1] * y_i;
+        }
+
+        let determinant = s11 * s22 - s12 * s12;
+        if determinant.abs() <= f64::EPSILON * s11 * s22 {
+            return Err(RustQuantError::MatrixInversionFailed);
+        }
+
+        let inverse = [
+            [s22 / determinant, -s12 / determinant],
+            [-s12 / determinant, s11 / determinant],
+        ];
+        let beta = [
+            inverse[0][0] * r1 + inverse[0][1] * r2,
+            inverse[1][0] * r1 + inverse[1][1] * r2,
+        ];
+
+        let residual_variance = z
+            .iter()
+            .zip(y)
+            .map(|(z_i, y_i)| (y_i - beta[0] * z_i[0] - beta[1] * z_i[1]).powi(2))
+            .sum::<f64>()
+            / n as f64;
+
+        let covariance = inverse.map(|row| row.map(|v| v * residual_variance));
+
+        Ok(Self {
+            beta,
+            covariance,
+            residual_variance,
+            n,
+        })
+    }
+
+    /// Standard error of a function of $\beta$, from its gradient (delta method).
+    pub(crate) fn delta_standard_error(&self, gradient: [f64; 2]) -> f64 {
+        let c = &self.covariance;
+
+        (gradient[0] * gradient[0] * c[0][0]
+            + 2.0 * gradient[0] * gradient[1] * c[0][1]
+            + gradient[1] * gradient[1] * c[1][1])
+            .sqrt()
+    }
+
+    /// Standard error of the residual variance, $\sqrt{2 / n} \hat{v}$.
+    pub(crate) fn residual_variance_standard_error(&self) -> f64 {
+        self.residual_variance * (2.0 / self.n as f64).sqrt()
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_estimation {
+    use super::*;
+    use RustQuant_utils::assert_approx_equal;
+
+    #[test]
+    fn test_least_squares() {
+        // y = 2 + 3 x, with residuals of +-0.1.
+        let z: Vec<[f64; 2]> = (0..10).map(|i| [1.0, i as f64]).collect();
+        let y: Vec<f64> = (0..10)
+            .map(|i| 2.0 + 3.0 * i as f64 + if i % 2 == 0 { 0.1 } else { -0.1 })
+            .collect();
+
+        let fit = LeastSquares::fit(&z, &y).unwrap();
+
+        // Sxx = 82.5 and sum (x - mean(x)) e = -0.5.
+        assert_approx_equal!(fit.beta[1], 3.0 - 0.5 / 82.5, 1e-12);
+        assert_approx_equal!(fit.beta[0], 2.0 + 4.5 * 0.5 / 82.5, 1e-12);
+        assert!(fit.residual_variance <= 0.01);
+
+        // Var(b) = v / Sxx.
+        assert_approx_equal!(fit.covariance[1][1], fit.residual_variance / 82.5, 1e-12);
+    }
+
+    #[test]
+    fn test_least_squares_errors() {
+        let collinear = [[1.0, 2.0], [2.0, 4.0], [3.0, 6.0]];
+
+        assert!(LeastSquares::fit(&collinear, &[1.0, 2.0, 3.0]).is_err());
+        assert!(LeastSquares::fit(&collinear, &[1.0, 2.0]).is_err());
+
+        assert!(validate_series(&[1.0, 2.0], 0.1).is_err());
+        assert!(validate_series(&[1.0, 2.0, 3.0], 0.0).is_err());
+        assert!(validate_series(&[1.0, f64::NAN, 3.0], 0.1).is_err());
+        assert!(validate_series(&[1.0, 2.0, 3.0], 0.1).is_ok());
+    }
+}
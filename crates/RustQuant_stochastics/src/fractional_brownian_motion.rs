@@ -8,7 +8,8 @@
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
 use super::StochasticProcessConfig;
-use crate::process::{StochasticProcess, Trajectories};
+use crate::process::{for_each_path, StochasticProcess, Trajectories};
+use crate::random::{seeded_rng, simulation_rng};
 use nalgebra::{DMatrix, DVector, Dim, Dyn, RowDVector};
 use ndarray::{concatenate, prelude::*};
 // use ndarray_rand::{rand::random, RandomExt};
@@ -16,9 +17,7 @@ use ndarray_rand::RandomExt;
 use ndrustfft::{ndfft_par, FftHandler};
 use num::{complex::ComplexDistribution, Complex};
 use rand::Rng;
-use rand::{rngs::StdRng, SeedableRng};
 use rand_distr::StandardNormal;
-use rayon::prelude::*;
 
 /// Struct containing the Fractional Brownian Motion parameters.
 #[derive(Debug)]
@@ -98,21 +97,18 @@ impl FractionalBrownianMotion {
 
     /// Fractional Gaussian noise.
     pub fn fgn_cholesky(&self, n: usize, t_n: f64) -> Vec<f64> {
-        let acf_sqrt = self.acf_matrix_sqrt(n);
-        let noise = rand::thread_rng()
-            .sample_iter::<f64, StandardNormal>(StandardNormal)
-            .take(n)
-            .collect();
-        let noise = DVector::<f64>::from_vec(noise);
-        let noise = (acf_sqrt * noise).transpose() * (1.0 * t_n / n as f64).powf(self.hurst);
-
-        noise.data.as_vec().clone()
+        self.fgn_cholesky_with_rng(n, t_n, &mut simulation_rng())
     }
 
     /// Seedable Fractional Gaussian noise.
     pub fn seedable_fgn_cholesky(&self, n: usize, t_n: f64, seed: u64) -> Vec<f64> {
+        self.fgn_cholesky_with_rng(n, t_n, &mut seeded_rng(seed))
+    }
+
+    /// Fractional Gaussian noise, drawing from `rng`.
+    pub fn fgn_cholesky_with_rng<R: Rng>(&self, n: usize, t_n: f64, rng: &mut R) -> Vec<f64> {
         let acf_sqrt = self.acf_matrix_sqrt(n);
-        let noise = StdRng::seed_from_u64(seed)
+        let noise = rng
             .sample_iter::<f64, StandardNormal>(StandardNormal)
             .take(n)
             .collect();
@@ -124,6 +120,11 @@ impl FractionalBrownianMotion {
 
     /// Fractional Gaussian noise via FFT.
     pub fn fgn_fft(&self, n: usize, t_n: f64) -> Vec<f64> {
+        self.fgn_fft_with_rng(n, t_n, &mut simulation_rng())
+    }
+
+    /// Fractional Gaussian noise via FFT, drawing from `rng`.
+    pub fn fgn_fft_with_rng<R: Rng>(&self, n: usize, t_n: f64, rng: &mut R) -> Vec<f64> {
         if !(0.0..=1.0).contains(&self.hurst) {
             panic!("Hurst parameter must be between 0 and 1");
         }
@@ -153,9 +154,10 @@ impl FractionalBrownianMotion {
 
         sqrt_eigenvalues.par_mapv_inplace(|x| Complex::new((x.re / (2.0 * n as f64)).sqrt(), x.im));
 
-        let rnd = Array1::<Complex<f64>>::random(
+        let rnd = Array1::<Complex<f64>>::random_using(
             2 * n,
             ComplexDistribution::new(StandardNormal, StandardNormal),
+            rng,
         );
         let fgn = &sqrt_eigenvalues * &rnd;
         let fft_handler = FftHandler::new(2 * n);
@@ -198,10 +200,15 @@ impl StochasticProcess for FractionalBrownianMotion {
         let mut paths = vec![vec![x_0; n_steps + 1]; m_paths];
         let times: Vec<f64> = (0..=n_steps).map(|t| t_0 + dt * (t as f64)).collect();
 
-        let path_generator = |path: &mut Vec<f64>| {
+        let path_generator = |(i, path): (usize, &mut Vec<f64>)| {
+            let mut rng = config.path_rng(i);
             let fgn = match self.method {
-                FractionalProcessGeneratorMethod::FFT => self.fgn_fft(n_steps, t_n),
-                FractionalProcessGeneratorMethod::CHOLESKY => self.fgn_cholesky(n_steps, t_n),
+                FractionalProcessGeneratorMethod::FFT => {
+                    self.fgn_fft_with_rng(n_steps, t_n, &mut rng)
+                }
+                FractionalProcessGeneratorMethod::CHOLESKY => {
+                    self.fgn_cholesky_with_rng(n_steps, t_n, &mut rng)
+                }
             };
 
             for t in 0..n_steps {
@@ -211,48 +218,7 @@ impl StochasticProcess for FractionalBrownianMotion {
             }
         };
 
-        if parallel {
-            paths.par_iter_mut().for_each(path_generator);
-        } else {
-            paths.iter_mut().for_each(path_generator);
-        }
-
-        Trajectories { times, paths }
-    }
-
-    fn seedable_euler_maruyama(
-        &self,
-        x_0: f64,
-        t_0: f64,
-        t_n: f64,
-        n_steps: usize,
-        m_paths: usize,
-        parallel: bool,
-        seed: u64,
-    ) -> Trajectories {
-        assert!(t_0 < t_n);
-
-        let dt: f64 = (t_n - t_0) / (n_steps as f64);
-
-        // Initialise empty paths and fill in the time points.
-        let mut paths = vec![vec![x_0; n_steps + 1]; m_paths];
-        let times: Vec<f64> = (0..=n_steps).map(|t| t_0 + dt * (t as f64)).collect();
-
-        let path_generator = |path: &mut Vec<f64>| {
-            let fgn = self.seedable_fgn_cholesky(n_steps, t_n, seed);
-
-            for t in 0..n_steps {
-                path[t + 1] = path[t]
-                    + self.drift(path[t], times[t]) * dt
-                    + self.diffusion(path[t], times[t]) * fgn[t];
-            }
-        };
-
-        if parallel {
-            paths.par_iter_mut().for_each(path_generator);
-        } else {
-            paths.iter_mut().for_each(path_generator);
-        }
+        for_each_path(&mut paths, parallel, path_generator);
 
         Trajectories { times, paths }
     }
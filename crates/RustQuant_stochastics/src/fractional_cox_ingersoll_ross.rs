@@ -9,12 +9,11 @@
 
 use super::{
     fractional_brownian_motion::FractionalProcessGeneratorMethod,
-    process::{StochasticProcess, Trajectories},
+    process::{for_each_path, StochasticProcess, Trajectories},
     StochasticProcessConfig,
 };
 use crate::fractional_brownian_motion::FractionalBrownianMotion;
 use crate::model_parameter::ModelParameter;
-use rayon::prelude::*;
 
 /// Struct containing the Ornstein-Uhlenbeck process parameters.
 pub struct FractionalCoxIngersollRoss {
@@ -87,14 +86,14 @@ impl StochasticProcess for FractionalCoxIngersollRoss {
                     self.hurst,
                     FractionalProcessGeneratorMethod::CHOLESKY,
                 );
-                FractionalBrownianMotion::fgn_cholesky(&fbm, n_steps, t_n)
+                fbm.fgn_cholesky_with_rng(n_steps, t_n, &mut config.path_rng(0))
             }
             FractionalProcessGeneratorMethod::FFT => {
                 let fbm = FractionalBrownianMotion::new(
                     self.hurst,
                     FractionalProcessGeneratorMethod::FFT,
                 );
-                FractionalBrownianMotion::fgn_fft(&fbm, n_steps, t_n)
+                fbm.fgn_fft_with_rng(n_steps, t_n, &mut config.path_rng(0))
             }
         };
 
@@ -104,7 +103,7 @@ impl StochasticProcess for FractionalCoxIngersollRoss {
         let mut paths = vec![vec![x_0; n_steps + 1]; m_paths];
         let times: Vec<f64> = (0..=n_steps).map(|t| t_0 + dt * (t as f64)).collect();
 
-        let path_generator = |path: &mut Vec<f64>| {
+        let path_generator = |(_, path): (usize, &mut Vec<f64>)| {
             for t in 0..n_steps {
                 path[t + 1] = path[t]
                     + self.drift(path[t], times[t]) * dt
@@ -112,11 +111,7 @@ impl StochasticProcess for FractionalCoxIngersollRoss {
             }
         };
 
-        if parallel {
-            paths.par_iter_mut().for_each(path_generator);
-        } else {
-            paths.iter_mut().for_each(path_generator);
-        }
+        for_each_path(&mut paths, parallel, path_generator);
 
         Trajectories { times, paths }
     }
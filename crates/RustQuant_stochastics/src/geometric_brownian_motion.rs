@@ -7,8 +7,10 @@
 //      - LICENSE-MIT.md
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
+use crate::estimation::{validate_series, ProcessFit};
 use crate::model_parameter::ModelParameter;
 use crate::process::StochasticProcess;
+use RustQuant_error::RustQuantError;
 
 /// Struct containing the Geometric Brownian Motion parameters.
 pub struct GeometricBrownianMotion {
@@ -39,6 +41,42 @@ impl GeometricBrownianMotion {
 
         (p[0], p[1])
     }
+
+    /// Fit the process to a time series observed every `dt` years, by
+    /// maximum likelihood on the log returns, which are i.i.d.
+    /// $N((\mu - \sigma^2 / 2) dt, \sigma^2 dt)$.
+    ///
+    /// # Errors
+    /// - `RustQuantError::InvalidArgument` if there are fewer than three
+    ///   observations, `dt` is not positive, or the series is not positive.
+    pub fn fit(series: &[f64], dt: f64) -> Result<ProcessFit<Self>, RustQuantError> {
+        validate_series(series, dt)?;
+
+        if series.iter().any(|&x| x <= 0.0) {
+            return Err(RustQuantError::InvalidArgument(
+                "Geometric Brownian Motion requires a positive time series.".to_string(),
+            ));
+        }
+
+        let log_returns: Vec<f64> = series.windows(2).map(|w| (w[1] / w[0]).ln()).collect();
+        let n = log_returns.len() as f64;
+
+        let mean = log_returns.iter().sum::<f64>() / n;
+        let variance = log_returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / n;
+
+        let sigma = (variance / dt).sqrt();
+        let mu = mean / dt + 0.5 * sigma * sigma;
+
+        // The sample mean and variance of normal draws are independent.
+        let sigma_se = sigma / (2.0 * n).sqrt();
+        let mu_se = (sigma * sigma / (n * dt) + (sigma * sigma_se).powi(2)).sqrt();
+
+        Ok(ProcessFit {
+            process: Self::new(mu, sigma),
+            estimates: vec![mu, sigma],
+            standard_errors: vec![mu_se, sigma_se],
+        })
+    }
 }
 
 impl StochasticProcess for GeometricBrownianMotion {
@@ -53,6 +91,10 @@ impl StochasticProcess for GeometricBrownianMotion {
         self.sigma.0(t) * x
     }
 
+    fn diffusion_derivative(&self, _x: f64, t: f64) -> f64 {
+        self.sigma.0(t)
+    }
+
     fn jump(&self, _x: f64, _t: f64) -> Option<f64> {
         None
     }
@@ -103,4 +145,53 @@ mod tests_gbm {
         // let file2 = "./images/GBM2.png";
         // plot_vector((&output.trajectories[1]).clone(), file2)
     }
+
+    #[test]
+    fn test_time_dependent_parameters() {
+        // Volatility of 10% for the first half year and 30% after it,
+        // with a drift rising linearly in time.
+        let gbm = GeometricBrownianMotion::new(
+            |t: f64| 0.02 + 0.04 * t,
+            ModelParameter::piecewise_constant(&[0.5], &[0.1, 0.3]),
+        );
+
+        let config = StochasticProcessConfig::new(1.0, 0.0, 1.0, 200, 20_000, true).with_seed(7);
+        let output = gbm.euler_maruyama(&config);
+
+        let log_X_T: Vec<f64> = output.paths.iter().map(|p| p[200].ln()).collect();
+
+        // ln X_T has variance int sigma^2 dt and mean int (mu - sigma^2 / 2) dt.
+        let variance = 0.5 * 0.1 * 0.1 + 0.5 * 0.3 * 0.3;
+        let mean = 0.02 + 0.04 * 0.5 - 0.5 * variance;
+
+        assert_approx_equal!(log_X_T.mean(), mean, 0.005);
+        assert_approx_equal!(log_X_T.variance(), variance, 0.003);
+    }
+
+    #[test]
+    fn test_fit() {
+        let gbm = GeometricBrownianMotion::new(0.08, 0.25);
+
+        // Ten years of daily observations.
+        let config = StochasticProcessConfig::new(100.0, 0.0, 10.0, 2520, 1, false).with_seed(11);
+        let series = &gbm.euler_maruyama(&config).paths[0];
+
+        let fit = GeometricBrownianMotion::fit(series, 10.0 / 2520.0).unwrap();
+
+        assert_eq!(fit.estimates, fit.process.parameters());
+        for (estimate, (truth, se)) in fit
+            .estimates
+            .iter()
+            .zip([0.08, 0.25].iter().zip(&fit.standard_errors))
+        {
+            assert!((estimate - truth).abs() < 3.0 * se);
+        }
+
+        // Asymptotically sigma / sqrt(2 n) and roughly sigma / sqrt(T).
+        let sigma = fit.estimates[1];
+        assert_approx_equal!(fit.standard_errors[1], sigma / 5040_f64.sqrt(), 1e-12);
+        assert_approx_equal!(fit.standard_errors[0], sigma / 10_f64.sqrt(), 1e-3);
+
+        assert!(GeometricBrownianMotion::fit(&[1.0, -1.0, 2.0], 0.1).is_err());
+    }
 }
@@ -0,0 +1,270 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::poisson_process::{jump_trajectories, JumpPath};
+use crate::process::{
+    map_paths, DiscretisationScheme, StochasticProcess, StochasticProcessConfig, Trajectories,
+};
+use rand::Rng;
+use rand_distr::{Distribution, Exp};
+
+/// Struct containing the Hawkes process parameters.
+///
+/// A self-exciting point process with an exponential kernel:
+/// each event raises the intensity by $\alpha$, and the excess decays
+/// back towards the baseline $\mu$ at rate $\beta$:
+///
+/// $$
+/// \lambda(t) = \mu + \sum_{t_i \leq t} \alpha e^{-\beta (t - t_i)}
+/// $$
+///
+/// The process is stationary when the branching ratio $\alpha / \beta < 1$.
+/// Simulations start with no event history, at intensity $\mu$.
+pub struct HawkesProcess {
+    /// Baseline intensity ($\mu$).
+    /// Note: $\mu \in (0, \infty)$.
+    pub mu: f64,
+
+    /// Jump in the intensity after each event ($\alpha$).
+    /// Note: $\alpha \in [0, \infty)$.
+    pub alpha: f64,
+
+    /// Decay rate of the excitation ($\beta$).
+    /// Note: $\beta \in (0, \infty)$.
+    pub beta: f64,
+}
+
+impl HawkesProcess {
+    /// Create a new Hawkes process.
+    ///
+    /// # Panics
+    /// Panics if `mu` or `beta` is not positive, or `alpha` is negative.
+    pub fn new(mu: f64, alpha: f64, beta: f64) -> Self {
+        assert!(mu > 0.0, "The baseline intensity must be positive.");
+        assert!(alpha >= 0.0, "The excitation must be non-negative.");
+        assert!(beta > 0.0, "The decay rate must be positive.");
+
+        Self { mu, alpha, beta }
+    }
+
+    /// Branching ratio $\alpha / \beta$: the expected number of events
+    /// directly triggered by each event.
+    pub fn branching_ratio(&self) -> f64 {
+        self.alpha / self.beta
+    }
+
+    /// Intensity at time `t`, given the event times so far.
+    pub fn intensity(&self, event_times: &[f64], t: f64) -> f64 {
+        self.mu
+            + event_times
+                .iter()
+                .take_while(|&&s| s <= t)
+                .map(|&s| self.alpha * (-self.beta * (t - s)).exp())
+                .sum::<f64>()
+    }
+
+    /// Event times on `(t_0, t_n]`, by Ogata's thinning algorithm.
+    ///
+    /// The intensity only decays between events, so its current value
+    /// bounds it until the next event.
+    pub fn sample_event_times<R: Rng + ?Sized>(&self, t_0: f64, t_n: f64, rng: &mut R) -> Vec<f64> {
+        let mut times = Vec::new();
+        let mut t = t_0;
+
+        // Excess of the intensity over the baseline.
+        let mut excitation = 0.0;
+
+        loop {
+            let bound = self.mu + excitation;
+            let wait = Exp::new(bound).unwrap().sample(rng);
+
+            t += wait;
+            if t > t_n {
+                break;
+            }
+
+            excitation *= (-self.beta * wait).exp();
+
+            if rng.gen::<f64>() * bound <= self.mu + excitation {
+                times.push(t);
+                excitation += self.alpha;
+            }
+        }
+
+        times
+    }
+
+    /// Simulate the events on `[t_0, t_n]` of each path in `config`,
+    /// as jumps of size one. The number of steps in `config` is ignored.
+    pub fn jump_paths(&self, config: &StochasticProcessConfig) -> Vec<JumpPath> {
+        let (_, t_0, t_n, _, m_paths, parallel) = config.unpack();
+
+        assert!(t_0 < t_n);
+
+        let generator = |i| {
+            let times = self.sample_event_times(t_0, t_n, &mut config.path_rng(i));
+            let sizes = vec![1.0; times.len()];
+
+            JumpPath { times, sizes }
+        };
+
+        map_paths(m_paths, parallel, generator)
+    }
+
+    /// Simulate the event counts and the intensity together.
+    ///
+    /// The count starts from `x_0` in `config`.
+    /// Returns `(counts, intensity)` trajectories on the same time grid,
+    /// and the event times of each path.
+    pub fn simulate_with_intensity(
+        &self,
+        config: &StochasticProcessConfig,
+    ) -> (Trajectories, Trajectories, Vec<JumpPath>) {
+        let (x_0, t_0, t_n, n_steps, _, _) = config.unpack();
+
+        let events = self.jump_paths(config);
+
+        let dt: f64 = (t_n - t_0) / (n_steps as f64);
+        let times: Vec<f64> = (0..=n_steps).map(|t| t_0 + dt * (t as f64)).collect();
+
+        let counts = events
+            .iter()
+            .map(|path| path.on_grid(x_0, &times))
+            .collect();
+        let intensity = events
+            .iter()
+            .map(|path| {
+                times
+                    .iter()
+                    .map(|&t| self.intensity(&path.times, t))
+                    .collect()
+            })
+            .collect();
+
+        (
+            Trajectories {
+                times: times.clone(),
+                paths: counts,
+            },
+            Trajectories {
+                times,
+                paths: intensity,
+            },
+            events,
+        )
+    }
+}
+
+impl StochasticProcess for HawkesProcess {
+    /// No drift between events.
+    fn drift(&self, _x: f64, _t: f64) -> f64 {
+        0.0
+    }
+
+    fn diffusion(&self, _x: f64, _t: f64) -> f64 {
+        0.0
+    }
+
+    /// Every event increases the count by one.
+    fn jump(&self, _x: f64, _t: f64) -> Option<f64> {
+        Some(1.0)
+    }
+
+    fn parameters(&self) -> Vec<f64> {
+        vec![self.mu, self.alpha, self.beta]
+    }
+
+    /// Exact simulation: the count of events at each time on the grid.
+    fn euler_maruyama(&self, config: &StochasticProcessConfig) -> Trajectories {
+        let (_, t_0, t_n, _, _, _) = config.unpack();
+
+        jump_trajectories(config, |rng| {
+            let times = self.sample_event_times(t_0, t_n, rng);
+            let sizes = vec![1.0; times.len()];
+
+            JumpPath { times, sizes }
+        })
+    }
+
+    /// Simulation is exact, so the scheme is ignored.
+    fn simulate_with_scheme(
+        &self,
+        config: &StochasticProcessConfig,
+        _scheme: DiscretisationScheme,
+    ) -> Trajectories {
+        self.euler_maruyama(config)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_hawkes {
+    use super::*;
+    use RustQuant_math::Statistic;
+    use RustQuant_utils::{assert_approx_equal, RUSTQUANT_EPSILON};
+
+    #[test]
+    fn test_hawkes_expected_count() {
+        let (mu, alpha, beta, t) = (1.0, 0.8, 2.0, 5.0_f64);
+
+        let hawkes = HawkesProcess::new(mu, alpha, beta);
+        let config = StochasticProcessConfig::new(0.0, 0.0, t, 5, 20_000, true);
+        let output = hawkes.euler_maruyama(&config);
+
+        let N_T: Vec<f64> = output.paths.iter().map(|p| p[5]).collect();
+
+        // E[lambda(t)] solves d/dt E[lambda] = beta mu - (beta - alpha) E[lambda],
+        // with E[lambda(0)] = mu. Integrate it for E[N(t)].
+        let kappa = beta - alpha;
+        let stationary = beta * mu / kappa;
+        let expected = stationary * t + (mu - stationary) * (1.0 - (-kappa * t).exp()) / kappa;
+
+        assert_approx_equal!(N_T.mean(), expected, 0.1);
+    }
+
+    #[test]
+    fn test_hawkes_without_excitation_is_poisson() {
+        let hawkes = HawkesProcess::new(3.0, 0.0, 1.0);
+        let config = StochasticProcessConfig::new(0.0, 0.0, 2.0, 1, 20_000, true);
+        let output = hawkes.euler_maruyama(&config);
+
+        let N_T: Vec<f64> = output.paths.iter().map(|p| p[1]).collect();
+
+        assert_approx_equal!(N_T.mean(), 6.0, 0.1);
+        assert_approx_equal!(N_T.variance(), 6.0, 0.25);
+    }
+
+    #[test]
+    fn test_hawkes_intensity_path() {
+        let hawkes = HawkesProcess::new(0.5, 1.2, 3.0);
+        let config = StochasticProcessConfig::new(0.0, 0.0, 4.0, 400, 50, false);
+
+        let (counts, intensity, events) = hawkes.simulate_with_intensity(&config);
+
+        for ((count, lambda), path) in counts.paths.iter().zip(&intensity.paths).zip(&events) {
+            assert_eq!(count[400], path.len() as f64);
+            assert!(lambda.iter().all(|&l| l >= 0.5));
+
+            // Each event lifts the intensity by alpha.
+            for &s in &path.times {
+                let before: Vec<f64> = path.times.iter().copied().filter(|&u| u < s).collect();
+                assert_approx_equal!(
+                    hawkes.intensity(&path.times, s) - hawkes.intensity(&before, s),
+                    1.2,
+                    RUSTQUANT_EPSILON
+                );
+            }
+        }
+
+        assert_approx_equal!(hawkes.branching_ratio(), 0.4, RUSTQUANT_EPSILON);
+    }
+}
@@ -8,10 +8,23 @@
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
 use crate::model_parameter::ModelParameter;
+use crate::process::{
+    for_each_path, scheme_step, DiscretisationScheme, StochasticProcessConfig, Trajectories,
+};
 use crate::StochasticProcess;
+use rand::{Rng, RngCore};
+use rand_distr::StandardNormal;
 
 /// Struct containing the Heston model parameters.
+///
+/// The asset and its variance follow
+/// $dS_t = \mu S_t dt + \sqrt{v_t} S_t dW^1_t$ and
+/// $dv_t = \kappa (\theta - v_t) dt + \sigma \sqrt{v_t} dW^2_t$,
+/// with $d\langle W^1, W^2 \rangle_t = \rho dt$.
 pub struct Heston {
+    /// The drift of the asset ($\mu$).
+    pub mu: ModelParameter,
+
     /// The initial variance ($v_0$).
     pub initial_variance: ModelParameter,
 
@@ -29,8 +42,9 @@ pub struct Heston {
 }
 
 impl Heston {
-    /// Create a new Arithmetic Brownian Motion process.
+    /// Create a new Heston process.
     pub fn new(
+        mu: impl Into<ModelParameter>,
         initial_variance: impl Into<ModelParameter>,
         long_run_variance: impl Into<ModelParameter>,
         mean_reversion_rate: impl Into<ModelParameter>,
@@ -38,6 +52,7 @@ impl Heston {
         volatility_of_volatility: impl Into<ModelParameter>,
     ) -> Self {
         Self {
+            mu: mu.into(),
             initial_variance: initial_variance.into(),
             long_run_variance: long_run_variance.into(),
             mean_reversion_rate: mean_reversion_rate.into(),
@@ -45,23 +60,326 @@ impl Heston {
             volatility_of_volatility: volatility_of_volatility.into(),
         }
     }
+
+    /// Drift of the variance process.
+    fn variance_drift(&self, v: f64, t: f64) -> f64 {
+        self.mean_reversion_rate.0(t) * (self.long_run_variance.0(t) - v)
+    }
+
+    /// Diffusion of the variance process.
+    fn variance_diffusion(&self, v: f64, t: f64) -> f64 {
+        self.volatility_of_volatility.0(t) * v.max(0.0).sqrt()
+    }
+
+    /// Derivative of the variance diffusion, zero once the variance is truncated.
+    fn variance_diffusion_derivative(&self, v: f64, t: f64) -> f64 {
+        if v <= 0.0 {
+            return 0.0;
+        }
+
+        0.5 * self.volatility_of_volatility.0(t) / v.sqrt()
+    }
+
+    /// Simulate the asset and variance paths together.
+    ///
+    /// Uses full truncation: the variance may go negative between steps,
+    /// but only its positive part enters the drift and diffusion terms.
+    ///
+    /// Returns `(asset, variance)` trajectories on the same time grid.
+    pub fn euler_maruyama_with_variance(
+        &self,
+        config: &StochasticProcessConfig,
+    ) -> (Trajectories, Trajectories) {
+        self.simulate_with_variance(config, DiscretisationScheme::EulerMaruyama)
+    }
+
+    /// Simulate the asset and variance paths together with Andersen's (2008)
+    /// quadratic-exponential (QE) scheme, the default for `euler_maruyama`.
+    ///
+    /// The variance is drawn from a moment-matched approximation of its
+    /// exact noncentral chi-squared transition, so it never goes negative
+    /// and has little bias even on coarse grids. The log asset is then
+    /// integrated with a trapezoidal rule for the variance, with Andersen's
+    /// martingale correction so that $E[S_{t + dt} | S_t] = S_t e^{\mu dt}$.
+    /// Parameters are frozen at the start of each step.
+    ///
+    /// Returns `(asset, variance)` trajectories on the same time grid.
+    pub fn quadratic_exponential(
+        &self,
+        config: &StochasticProcessConfig,
+    ) -> (Trajectories, Trajectories) {
+        let (x_0, t_0, t_n, n_steps, m_paths, parallel) = config.unpack();
+
+        assert!(t_0 < t_n);
+
+        let dt: f64 = (t_n - t_0) / (n_steps as f64);
+
+        // Initialise empty paths and fill in the time points.
+        let v_0 = self.initial_variance.0(t_0);
+        let mut paths = vec![(vec![x_0; n_steps + 1], vec![v_0; n_steps + 1]); m_paths];
+        let times: Vec<f64> = (0..=n_steps).map(|t| t_0 + dt * (t as f64)).collect();
+
+        let path_generator = |(i, (x, v)): (usize, &mut (Vec<f64>, Vec<f64>))| {
+            let mut rng = config.path_rng(i);
+
+            for t in 0..n_steps {
+                let step = QuadraticExponentialStep::new(self, times[t], dt);
+                let (v_next, k_0) = step.variance(v[t], &mut rng);
+                let z: f64 = rng.sample(StandardNormal);
+
+                let log_return = self.mu.0(times[t]) * dt
+                    + k_0
+                    + step.k_1 * v[t]
+                    + step.k_2 * v_next
+                    + (step.k_3 * v[t] + step.k_4 * v_next).sqrt() * z;
+
+                x[t + 1] = x[t] * log_return.exp();
+                v[t + 1] = v_next;
+            }
+        };
+
+        for_each_path(&mut paths, parallel, path_generator);
+
+        let (asset, variance) = paths.into_iter().unzip();
+
+        (
+            Trajectories {
+                times: times.clone(),
+                paths: asset,
+            },
+            Trajectories {
+                times,
+                paths: variance,
+            },
+        )
+    }
+
+    /// Simulate the asset and variance paths together with a choice of
+    /// discretisation scheme, applied to both factors.
+    ///
+    /// Milstein markedly reduces the bias of the variance process,
+    /// whose diffusion $\sigma \sqrt{v}$ is far from constant.
+    ///
+    /// Returns `(asset, variance)` trajectories on the same time grid.
+    pub fn simulate_with_variance(
+        &self,
+        config: &StochasticProcessConfig,
+        scheme: DiscretisationScheme,
+    ) -> (Trajectories, Trajectories) {
+        self.simulate(config, scheme, |x, t| self.drift(x, t), |_, _, _| 0.0)
+    }
+
+    /// Correlated two-factor scheme shared with models built on Heston.
+    ///
+    /// `drift(x, t)` is the asset drift, and `log_jump(rng, t, dt)` the total
+    /// log jump of the asset over `[t, t + dt]`.
+    pub(crate) fn simulate<D, J>(
+        &self,
+        config: &StochasticProcessConfig,
+        scheme: DiscretisationScheme,
+        drift: D,
+        log_jump: J,
+    ) -> (Trajectories, Trajectories)
+    where
+        D: Fn(f64, f64) -> f64 + Sync,
+        J: Fn(&mut dyn RngCore, f64, f64) -> f64 + Sync,
+    {
+        let (x_0, t_0, t_n, n_steps, m_paths, parallel) = config.unpack();
+
+        assert!(t_0 < t_n);
+        assert!(
+            (-1.0..=1.0).contains(&self.correlation.0(t_0)),
+            "The correlation must be in [-1, 1]."
+        );
+
+        let dt: f64 = (t_n - t_0) / (n_steps as f64);
+
+        // Initialise empty paths and fill in the time points.
+        let v_0 = self.initial_variance.0(t_0);
+        let mut paths = vec![(vec![x_0; n_steps + 1], vec![v_0; n_steps + 1]); m_paths];
+        let times: Vec<f64> = (0..=n_steps).map(|t| t_0 + dt * (t as f64)).collect();
+
+        let path_generator = |(i, (x, v)): (usize, &mut (Vec<f64>, Vec<f64>))| {
+            let mut rng = config.path_rng(i);
+            let scale = dt.sqrt();
+
+            for t in 0..n_steps {
+                let rho = self.correlation.0(times[t]);
+
+                // Correlate the variance shock with the asset shock.
+                let z_1: f64 = rng.sample(StandardNormal);
+                let z_2: f64 = rng.sample(StandardNormal);
+                let dW_1 = z_1 * scale;
+                let dW_2 = (rho * z_1 + (1.0 - rho * rho).sqrt() * z_2) * scale;
+
+                let volatility = v[t].max(0.0).sqrt();
+
+                x[t + 1] = scheme_step(
+                    scheme,
+                    x[t],
+                    times[t],
+                    dt,
+                    dW_1,
+                    &drift,
+                    |y, _| volatility * y,
+                    |_, _| volatility,
+                ) * log_jump(&mut rng, times[t], dt).exp();
+                v[t + 1] = scheme_step(
+                    scheme,
+                    v[t],
+                    times[t],
+                    dt,
+                    dW_2,
+                    |y, s| self.variance_drift(y.max(0.0), s),
+                    |y, s| self.variance_diffusion(y, s),
+                    |y, s| self.variance_diffusion_derivative(y, s),
+                );
+            }
+        };
+
+        for_each_path(&mut paths, parallel, path_generator);
+
+        let (asset, variance) = paths.into_iter().unzip();
+
+        (
+            Trajectories {
+                times: times.clone(),
+                paths: asset,
+            },
+            Trajectories {
+                times,
+                paths: variance,
+            },
+        )
+    }
+}
+
+/// Coefficients of one step of the quadratic-exponential scheme.
+struct QuadraticExponentialStep {
+    /// Mean reversion rate, long-run variance and volatility of volatility.
+    kappa: f64,
+    theta: f64,
+    sigma: f64,
+
+    /// $e^{-\kappa dt}$.
+    decay: f64,
+
+    /// Coefficients of the log asset step,
+    /// $K_0 + K_1 v_t + K_2 v_{t + dt} + \sqrt{K_3 v_t + K_4 v_{t + dt}} Z$.
+    k_0: f64,
+    k_1: f64,
+    k_2: f64,
+    k_3: f64,
+    k_4: f64,
+}
+
+impl QuadraticExponentialStep {
+    /// Switch from the quadratic to the exponential branch above this
+    /// ratio of the variance to the squared mean.
+    const PSI_CRITICAL: f64 = 1.5;
+
+    fn new(heston: &Heston, t: f64, dt: f64) -> Self {
+        let kappa = heston.mean_reversion_rate.0(t);
+        let theta = heston.long_run_variance.0(t);
+        let sigma = heston.volatility_of_volatility.0(t);
+        let rho = heston.correlation.0(t);
+
+        assert!(
+            kappa > 0.0 && sigma > 0.0,
+            "kappa and sigma must be positive."
+        );
+        assert!(
+            (-1.0..=1.0).contains(&rho),
+            "The correlation must be in [-1, 1]."
+        );
+
+        // Trapezoidal weights of the integrated variance.
+        let (gamma_1, gamma_2) = (0.5, 0.5);
+
+        Self {
+            kappa,
+            theta,
+            sigma,
+            decay: (-kappa * dt).exp(),
+            k_0: -rho * kappa * theta * dt / sigma,
+            k_1: gamma_1 * dt * (kappa * rho / sigma - 0.5) - rho / sigma,
+            k_2: gamma_2 * dt * (kappa * rho / sigma - 0.5) + rho / sigma,
+            k_3: gamma_1 * dt * (1.0 - rho * rho),
+            k_4: gamma_2 * dt * (1.0 - rho * rho),
+        }
+    }
+
+    /// Draw the next variance, and the martingale-corrected $K_0$.
+    fn variance<R: Rng>(&self, v: f64, rng: &mut R) -> (f64, f64) {
+        let (kappa, theta, sigma, decay) = (self.kappa, self.theta, self.sigma, self.decay);
+
+        // Conditional mean and variance of the exact transition.
+        let m = theta + (v - theta) * decay;
+        let s2 = v * sigma * sigma * decay * (1.0 - decay) / kappa
+            + theta * sigma * sigma * (1.0 - decay).powi(2) / (2.0 * kappa);
+        let psi = s2 / (m * m);
+
+        // Exponent of the martingale correction, E[exp(A v_{t + dt})].
+        let a = self.k_2 + 0.5 * self.k_4;
+        let correction = -(self.k_1 + 0.5 * self.k_3) * v;
+
+        if psi <= Self::PSI_CRITICAL {
+            // Quadratic branch: a (b + Z)^2.
+            let b2 = 2.0 / psi - 1.0 + (2.0 / psi).sqrt() * (2.0 / psi - 1.0).sqrt();
+            let scale = m / (1.0 + b2);
+            let z: f64 = rng.sample(StandardNormal);
+
+            let k_0 = if 2.0 * a * scale < 1.0 {
+                -a * b2 * scale / (1.0 - 2.0 * a * scale)
+                    + 0.5 * (1.0 - 2.0 * a * scale).ln()
+                    + correction
+            } else {
+                self.k_0
+            };
+
+            (scale * (b2.sqrt() + z).powi(2), k_0)
+        } else {
+            // Exponential branch: a point mass at zero plus an exponential tail.
+            let p = (psi - 1.0) / (psi + 1.0);
+            let beta = (1.0 - p) / m;
+            let u: f64 = rng.gen();
+
+            let k_0 = if a < beta {
+                -(p + beta * (1.0 - p) / (beta - a)).ln() + correction
+            } else {
+                self.k_0
+            };
+
+            let v_next = if u <= p {
+                0.0
+            } else {
+                ((1.0 - p) / (1.0 - u)).ln() / beta
+            };
+
+            (v_next, k_0)
+        }
+    }
 }
 
 impl StochasticProcess for Heston {
-    fn drift(&self, _x: f64, _t: f64) -> f64 {
-        todo!()
+    fn drift(&self, x: f64, t: f64) -> f64 {
+        // mu S_t dt
+        self.mu.0(t) * x
     }
 
-    fn diffusion(&self, _x: f64, _t: f64) -> f64 {
-        todo!()
+    /// Diffusion at the initial variance.
+    /// Simulated paths use the full variance process instead.
+    fn diffusion(&self, x: f64, t: f64) -> f64 {
+        self.initial_variance.0(t).max(0.0).sqrt() * x
     }
 
     fn jump(&self, _x: f64, _t: f64) -> Option<f64> {
-        todo!()
+        None
     }
 
     fn parameters(&self) -> Vec<f64> {
         vec![
+            self.mu.0(0.0),
             self.initial_variance.0(0.0),
             self.long_run_variance.0(0.0),
             self.mean_reversion_rate.0(0.0),
@@ -69,4 +387,176 @@ impl StochasticProcess for Heston {
             self.volatility_of_volatility.0(0.0),
         ]
     }
+
+    /// Simulates with the quadratic-exponential scheme, see
+    /// `quadratic_exponential`. Use `euler_maruyama_with_variance` or
+    /// `simulate_with_scheme` for the Euler-Maruyama scheme.
+    fn euler_maruyama(&self, config: &StochasticProcessConfig) -> Trajectories {
+        self.quadratic_exponential(config).0
+    }
+
+    fn simulate_with_scheme(
+        &self,
+        config: &StochasticProcessConfig,
+        scheme: DiscretisationScheme,
+    ) -> Trajectories {
+        self.simulate_with_variance(config, scheme).0
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_heston {
+    use super::*;
+    use RustQuant_math::*;
+    use RustQuant_utils::assert_approx_equal;
+
+    #[test]
+    fn test_heston_moments() {
+        let (mu, v_0, theta, kappa, t) = (0.05, 0.09, 0.04, 2.0, 1.0);
+        let heston = Heston::new(mu, v_0, theta, kappa, -0.7, 0.3);
+
+        let config = StochasticProcessConfig::new(100.0, 0.0, t, 250, 10_000, true);
+        let (asset, variance) = heston.euler_maruyama_with_variance(&config);
+
+        let S_T: Vec<f64> = asset.paths.iter().map(|p| p[250]).collect();
+        let v_T: Vec<f64> = variance.paths.iter().map(|p| p[250]).collect();
+
+        // E[S_T] = S_0 exp(mu T).
+        assert_approx_equal!(S_T.mean(), 100.0 * (mu * t).exp(), 1.0);
+
+        // E[v_T] = theta + (v_0 - theta) exp(-kappa T).
+        assert_approx_equal!(
+            v_T.mean(),
+            theta + (v_0 - theta) * (-kappa * t).exp(),
+            0.002
+        );
+    }
+
+    #[test]
+    fn test_heston_correlation() {
+        let heston = Heston::new(0.0, 0.04, 0.04, 1.0, -0.7, 0.3);
+
+        let config = StochasticProcessConfig::new(100.0, 0.0, 1.0, 1, 20_000, false);
+        let (asset, variance) = heston.euler_maruyama_with_variance(&config);
+
+        // Over one step, the returns and variance changes have correlation rho.
+        let dS: Vec<f64> = asset.paths.iter().map(|p| p[1] - p[0]).collect();
+        let dv: Vec<f64> = variance.paths.iter().map(|p| p[1] - p[0]).collect();
+
+        let (mean_S, mean_v) = (dS.mean(), dv.mean());
+        let covariance = dS
+            .iter()
+            .zip(&dv)
+            .map(|(a, b)| (a - mean_S) * (b - mean_v))
+            .sum::<f64>()
+            / dS.len() as f64;
+
+        assert_approx_equal!(
+            covariance / (dS.variance() * dv.variance()).sqrt(),
+            -0.7,
+            0.02
+        );
+    }
+
+    #[test]
+    fn test_heston_euler_maruyama_returns_asset_paths() {
+        let heston = Heston::new(0.03, 0.04, 0.04, 1.5, 0.0, 0.2);
+
+        let config = StochasticProcessConfig::new(50.0, 0.0, 0.5, 10, 5, false);
+        let output = heston.euler_maruyama(&config);
+
+        assert_eq!(output.paths.len(), 5);
+        assert!(output.paths.iter().all(|p| p.len() == 11 && p[0] == 50.0));
+    }
+
+    #[test]
+    fn test_heston_milstein_variance() {
+        let (v_0, theta, kappa, t) = (0.04, 0.04, 2.0, 1.0);
+        let heston = Heston::new(0.0, v_0, theta, kappa, -0.7, 0.3);
+
+        // A coarse grid, where Euler often pushes the variance below zero.
+        let config = StochasticProcessConfig::new(100.0, 0.0, t, 10, 20_000, true);
+
+        let negative_steps = |scheme| {
+            let (_, variance) = heston.simulate_with_variance(&config, scheme);
+            variance
+                .paths
+                .iter()
+                .flatten()
+                .filter(|&&v| v < 0.0)
+                .count()
+        };
+
+        let euler = negative_steps(DiscretisationScheme::EulerMaruyama);
+        let milstein = negative_steps(DiscretisationScheme::Milstein);
+
+        assert!(milstein * 10 < euler);
+
+        let (asset, variance) =
+            heston.simulate_with_variance(&config, DiscretisationScheme::Milstein);
+        let S_T: Vec<f64> = asset.paths.iter().map(|p| p[10]).collect();
+        let v_T: Vec<f64> = variance.paths.iter().map(|p| p[10]).collect();
+
+        assert_approx_equal!(S_T.mean(), 100.0, 0.5);
+        assert_approx_equal!(v_T.mean(), theta, 0.002);
+    }
+
+    #[test]
+    fn test_heston_quadratic_exponential_variance() {
+        let (v_0, theta, kappa, t) = (0.09, 0.04, 2.0, 1.0);
+
+        // Violates the Feller condition (2 kappa theta < sigma^2).
+        let heston = Heston::new(0.0, v_0, theta, kappa, -0.7, 0.6);
+
+        // A coarse grid, with four steps per year.
+        let config = StochasticProcessConfig::new(100.0, 0.0, t, 4, 50_000, true).with_seed(1);
+        let (asset, variance) = heston.quadratic_exponential(&config);
+
+        assert!(variance.paths.iter().flatten().all(|&v| v >= 0.0));
+
+        // E[v_T] and V[v_T] of the exact CIR transition.
+        let decay = (-kappa * t).exp();
+        let mean = theta + (v_0 - theta) * decay;
+        let var = v_0 * 0.36 * decay * (1.0 - decay) / kappa
+            + theta * 0.36 * (1.0 - decay).powi(2) / (2.0 * kappa);
+
+        let v_T: Vec<f64> = variance.paths.iter().map(|p| p[4]).collect();
+        assert_approx_equal!(v_T.mean(), mean, 0.001);
+        assert_approx_equal!(v_T.variance(), var, 0.1 * var);
+
+        // The martingale correction keeps E[S_T] = S_0 exactly.
+        let S_T: Vec<f64> = asset.paths.iter().map(|p| p[4]).collect();
+        assert_approx_equal!(S_T.mean(), 100.0, 0.2);
+    }
+
+    #[test]
+    fn test_heston_quadratic_exponential_price() {
+        use RustQuant::instruments::{Heston93, TypeFlag};
+
+        // Rouah's example: S = K = 100, r = 3%, T = 6 months.
+        let (s, k, r, t) = (100.0, 100.0, 0.03, 0.5);
+        let (v_0, theta, kappa, rho, sigma) = (0.05, 0.05, 5.0, -0.8, 0.5);
+
+        let heston = Heston::new(r, v_0, theta, kappa, rho, sigma);
+        let config = StochasticProcessConfig::new(s, 0.0, t, 10, 200_000, true).with_seed(2);
+
+        let payoffs: Vec<f64> = heston
+            .euler_maruyama(&config)
+            .paths
+            .iter()
+            .map(|p| (p[10] - k).max(0.0))
+            .collect();
+
+        let price = (-r * t).exp() * payoffs.mean();
+        let standard_error = (-r * t).exp() * (payoffs.variance() / payoffs.len() as f64).sqrt();
+
+        let analytic =
+            Heston93::new(s, v_0, r, 0.0, rho, kappa, theta, sigma).price(k, t, TypeFlag::Call);
+
+        assert!((price - analytic).abs() < 3.0 * standard_error);
+    }
 }
@@ -35,6 +35,44 @@ impl HullWhite {
             theta: theta.into(),
         }
     }
+
+    /// Create a Hull-White process fitted to today's term structure.
+    ///
+    /// Given the instantaneous forward curve `f(0, t)`, the drift is chosen
+    /// so that simulated short rates reprice the curve:
+    ///
+    /// $$
+    /// \theta(t) = \frac{\partial f(0, t)}{\partial t} + \alpha f(0, t)
+    ///     + \frac{\sigma^2}{2 \alpha} \left(1 - e^{-2 \alpha t}\right)
+    /// $$
+    ///
+    /// The slope of the forward curve is taken by finite differences.
+    /// Simulations should start from the short rate `f(0, 0)`.
+    pub fn from_term_structure<F>(alpha: f64, sigma: f64, instantaneous_forward: F) -> Self
+    where
+        F: Fn(f64) -> f64 + 'static + Send + Sync,
+    {
+        const H: f64 = 1e-5;
+
+        let theta = move |t: f64| {
+            let slope = if t > H {
+                (instantaneous_forward(t + H) - instantaneous_forward(t - H)) / (2.0 * H)
+            } else {
+                (instantaneous_forward(t + H) - instantaneous_forward(t)) / H
+            };
+
+            // Tends to sigma^2 t as alpha goes to zero (Ho-Lee).
+            let convexity = if alpha.abs() > f64::EPSILON {
+                sigma * sigma * (1.0 - (-2.0 * alpha * t).exp()) / (2.0 * alpha)
+            } else {
+                sigma * sigma * t
+            };
+
+            slope + alpha * instantaneous_forward(t) + convexity
+        };
+
+        Self::new(alpha, sigma, theta)
+    }
 }
 
 impl StochasticProcess for HullWhite {
@@ -99,4 +137,50 @@ mod tests_hull_white {
         // No closed form solution for variance that I know of...
         // Have to take it on faith that it works
     }
+
+    #[test]
+    fn test_hull_white_flat_curve_theta() {
+        let (alpha, sigma, r) = (0.1, 0.01, 0.03);
+
+        let hw = HullWhite::from_term_structure(alpha, sigma, move |_| r);
+
+        for t in [0.0, 0.5, 2.0, 10.0] {
+            assert_approx_equal!(
+                hw.theta.0(t),
+                alpha * r + sigma * sigma * (1.0 - (-2.0 * alpha * t).exp()) / (2.0 * alpha),
+                1e-8
+            );
+        }
+    }
+
+    #[test]
+    fn test_hull_white_reprices_initial_curve() {
+        let (alpha, sigma, maturity) = (0.1, 0.01, 5.0_f64);
+
+        // Upward sloping forward curve, and its discount factor at maturity.
+        let forward = |t: f64| 0.02 + 0.01 * (1.0 - (-0.5 * t).exp());
+        let discount = (-(0.03 * maturity - 0.02 * (1.0 - (-0.5 * maturity).exp()))).exp();
+
+        let hw = HullWhite::from_term_structure(alpha, sigma, forward);
+
+        let n_steps = 250;
+        let n_paths = 5000;
+        let config =
+            StochasticProcessConfig::new(forward(0.0), 0.0, maturity, n_steps, n_paths, true);
+        let output = hw.euler_maruyama(&config);
+
+        // Monte Carlo zero-coupon bond: E[exp(-integral of r)].
+        let dt = maturity / n_steps as f64;
+        let bond = output
+            .paths
+            .iter()
+            .map(|path| {
+                let integral: f64 = path.windows(2).map(|w| 0.5 * (w[0] + w[1]) * dt).sum();
+                (-integral).exp()
+            })
+            .sum::<f64>()
+            / n_paths as f64;
+
+        assert_approx_equal!(bond, discount, 2e-3);
+    }
 }
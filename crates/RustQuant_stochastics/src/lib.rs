@@ -14,10 +14,23 @@
 //!   - Geometric Brownian Motion
 //!     - $dX(t) = \mu X(t) dt + \sigma X(t) dW(t)$
 //!   - Fractional Brownian Motion
+//! - Constant Elasticity of Variance
+//!   - $dX(t) = \mu X(t) dt + \sigma X(t)^\gamma dW(t)$
 //! - Cox-Ingersoll-Ross (1985)
 //!   - $dX(t) = \left[ \theta - \alpha X(t) \right] dt + \sigma \sqrt{r_t} dW(t)$
 //! - Ornstein-Uhlenbeck process
 //!   - $dX(t) = \theta \left[ \mu - X(t) \right] dt + \sigma dW(t)$
+//! - Variance Gamma (1998)
+//!   - $X(t) = \theta G(t) + \sigma W(G(t))$, with $G$ a gamma process
+//! - Normal Inverse Gaussian (1997)
+//!   - $X(t) = \beta I(t) + W(I(t))$, with $I$ an inverse Gaussian process
+//! - CGMY (2002)
+//!   - Tempered stable pure jump process, with Levy density
+//!     $C e^{-M x} x^{-1-Y}$ for $x > 0$ and $C e^{-G |x|} |x|^{-1-Y}$ for $x < 0$
+//! - Poisson and compound Poisson processes
+//!   - $X(t) = \sum_{i=1}^{N(t)} J_i$, with $N$ a Poisson process
+//! - Hawkes process (exponential kernel)
+//!   - $\lambda(t) = \mu + \sum_{t_i < t} \alpha e^{-\beta (t - t_i)}$
 //! - Ho-Lee (1986)
 //!   - $dX(t) = \theta(t) dt + \sigma dW(t)$
 //! - Hull-White (1990)
@@ -26,6 +39,8 @@
 //!   - $dX(t) = \left[ \theta(t) - \alpha(t) X(t) \right] dt + \sigma dW(t)$
 //! - Black-Derman-Toy (1990)
 //!   - $d\ln[X(t)] = \left[ \theta(t) + \frac{\sigma'(t)}{\sigma(t)}\ln[X(t)] \right]dt + \sigma_t dW(t)$
+//! - Black-Karasinski (1991)
+//!   - $d\ln[X(t)] = \left[ \theta(t) - \alpha \ln[X(t)] \right]dt + \sigma dW(t)$
 //!
 //! ```rust
 //! use RustQuant::stochastics::*;
@@ -45,22 +60,50 @@
 pub mod arithmetic_brownian_motion;
 pub use arithmetic_brownian_motion::*;
 
+/// Bates stochastic volatility jump diffusion.
+pub mod bates;
+pub use bates::*;
+
 /// Black-Derman-Toy short rate model.
 pub mod black_derman_toy;
 pub use black_derman_toy::*;
 
+/// Black-Karasinski short rate model.
+pub mod black_karasinski;
+pub use black_karasinski::*;
+
+/// Brownian bridge path construction.
+pub mod brownian_bridge;
+pub use brownian_bridge::*;
+
 /// Standard Brownian Motion.
 pub mod brownian_motion;
 pub use brownian_motion::*;
 
+/// CGMY tempered stable process.
+pub mod cgmy;
+pub use cgmy::*;
+
+/// Compound Poisson process.
+pub mod compound_poisson;
+pub use compound_poisson::*;
+
 /// Constant Elasticity of Variance process.
 pub mod constant_elasticity_of_variance;
 pub use constant_elasticity_of_variance::*;
 
+/// Correlated multi-asset Geometric Brownian Motion.
+pub mod correlated_geometric_brownian_motion;
+pub use correlated_geometric_brownian_motion::*;
+
 /// Cox-Ingersoll-Ross process.
 pub mod cox_ingersoll_ross;
 pub use cox_ingersoll_ross::*;
 
+/// User-defined process from drift and diffusion closures.
+pub mod custom_process;
+pub use custom_process::*;
+
 /// Extended Vasicek process.
 pub mod extended_vasicek;
 pub use extended_vasicek::*;
@@ -85,6 +128,10 @@ pub use geometric_brownian_bridge::*;
 pub mod geometric_brownian_motion;
 pub use geometric_brownian_motion::*;
 
+/// Hawkes self-exciting point process.
+pub mod hawkes;
+pub use hawkes::*;
+
 /// Heston model process.
 pub mod heston;
 pub use heston::*;
@@ -97,26 +144,67 @@ pub use ho_lee::*;
 pub mod hull_white;
 pub use hull_white::*;
 
+/// Mean-reverting jump diffusion process, for energy prices.
+pub mod mean_reverting_jump_diffusion;
+pub use mean_reverting_jump_diffusion::*;
+
 /// Merton jump diffusion process.
 pub mod merton_jump_diffusion;
 pub use merton_jump_diffusion::*;
 
+/// Normal Inverse Gaussian process.
+pub mod normal_inverse_gaussian;
+pub use normal_inverse_gaussian::*;
+
 /// Ornstein-Uhlenbeck process.
 pub mod ornstein_uhlenbeck;
 pub use ornstein_uhlenbeck::*;
 
+/// Poisson process.
+pub mod poisson_process;
+pub use poisson_process::*;
+
+/// Regime switching process.
+pub mod regime_switching;
+pub use regime_switching::*;
+
+/// Rough Bergomi model process.
+pub mod rough_bergomi;
+pub use rough_bergomi::*;
+
 /// SABR model process.
 pub mod sabr;
 pub use sabr::*;
 
-/// Defines `Trajectories` and `StochasticProcess`.
+/// Schwartz (1997) two-factor commodity model.
+pub mod schwartz_two_factor;
+pub use schwartz_two_factor::*;
+
+/// Variance Gamma process.
+pub mod variance_gamma;
+pub use variance_gamma::*;
+
+/// Defines `Trajectories`, `StochasticProcess` and `MultiAssetProcess`.
 pub mod process;
 pub use process::*;
 
+/// Random number generators used for path simulation.
+pub mod random;
+pub use random::*;
+
 /// Model parameter struct.
 pub mod model_parameter;
 pub use model_parameter::*;
 
+/// Parameter estimation from observed time series.
+pub mod estimation;
+pub use estimation::*;
+
+/// Nelson-Siegel model, and the least squares fit it shares with
+/// Nelson-Siegel-Svensson.
+pub mod nelson_siegel;
+pub use nelson_siegel::*;
+
 /// Nelson-Siegel-Svensson model process.
 pub mod nelson_siegel_svensson;
 pub use nelson_siegel_svensson::*;
@@ -0,0 +1,225 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::model_parameter::ModelParameter;
+use crate::process::{
+    for_each_path, DiscretisationScheme, StochasticProcess, StochasticProcessConfig, Trajectories,
+};
+use crate::random::simulation_rng;
+use rand::Rng;
+use rand_distr::{Distribution, Exp, Poisson, StandardNormal};
+
+/// Struct containing the parameters of a mean-reverting jump diffusion,
+/// a model of power and gas spot prices.
+///
+/// The price is a deterministic seasonal level $f(t)$ plus a deviation
+/// $Y_t$ that reverts to zero and spikes upwards:
+/// $X_t = f(t) + Y_t$, with $dY_t = -\theta Y_t dt + \sigma dW_t + J dN_t$,
+/// where $N_t$ is a Poisson process with intensity $\lambda$ and the spike
+/// sizes $J$ are exponential with mean $\eta$.
+/// After a spike, the price decays back towards $f(t)$ at rate $\theta$.
+pub struct MeanRevertingJumpDiffusion {
+    /// The seasonal level ($f(t)$) that the price reverts to.
+    pub seasonality: ModelParameter,
+
+    /// Mean reversion parameter ($\theta$).
+    /// Defines the speed at which the price reverts to its seasonal level.
+    pub theta: ModelParameter,
+
+    /// The diffusion, or instantaneous volatility ($\sigma$).
+    pub sigma: ModelParameter,
+
+    /// The jump intensity ($\lambda$), the expected number of spikes per year.
+    pub lambda: ModelParameter,
+
+    /// The mean spike size ($\eta$).
+    pub jump_mean: ModelParameter,
+}
+
+impl MeanRevertingJumpDiffusion {
+    /// Create a new mean-reverting jump diffusion.
+    /// # Arguments
+    /// * `seasonality` - The seasonal level ($f(t)$), a constant or a function of time.
+    /// * `theta` - The mean reversion speed ($\theta$).
+    /// * `sigma` - The volatility ($\sigma$).
+    /// * `lambda` - The jump intensity ($\lambda$).
+    /// * `jump_mean` - The mean spike size ($\eta$).
+    pub fn new(
+        seasonality: impl Into<ModelParameter>,
+        theta: impl Into<ModelParameter>,
+        sigma: impl Into<ModelParameter>,
+        lambda: impl Into<ModelParameter>,
+        jump_mean: impl Into<ModelParameter>,
+    ) -> Self {
+        Self {
+            seasonality: seasonality.into(),
+            theta: theta.into(),
+            sigma: sigma.into(),
+            lambda: lambda.into(),
+            jump_mean: jump_mean.into(),
+        }
+    }
+
+    /// Rate of change of the seasonal level, by central finite difference.
+    fn seasonality_derivative(&self, t: f64) -> f64 {
+        let h = 1e-6 * (1.0 + t.abs());
+
+        (self.seasonality.0(t + h) - self.seasonality.0(t - h)) / (2.0 * h)
+    }
+}
+
+impl StochasticProcess for MeanRevertingJumpDiffusion {
+    fn drift(&self, x: f64, t: f64) -> f64 {
+        self.seasonality_derivative(t) + self.theta.0(t) * (self.seasonality.0(t) - x)
+    }
+
+    fn diffusion(&self, _x: f64, t: f64) -> f64 {
+        assert!(self.sigma.0(t) >= 0.0);
+        self.sigma.0(t)
+    }
+
+    fn jump(&self, _x: f64, t: f64) -> Option<f64> {
+        Some(
+            Exp::new(1.0 / self.jump_mean.0(t))
+                .unwrap()
+                .sample(&mut simulation_rng()),
+        )
+    }
+
+    fn parameters(&self) -> Vec<f64> {
+        vec![
+            self.seasonality.0(0.0),
+            self.theta.0(0.0),
+            self.sigma.0(0.0),
+            self.lambda.0(0.0),
+            self.jump_mean.0(0.0),
+        ]
+    }
+
+    /// Exact simulation of the deviation from the seasonal level, with the
+    /// parameters frozen at the start of each step: its Gaussian part is
+    /// an Ornstein-Uhlenbeck transition, and each spike decays from its
+    /// arrival time to the end of the step.
+    fn euler_maruyama(&self, config: &StochasticProcessConfig) -> Trajectories {
+        let (x_0, t_0, t_n, n_steps, m_paths, parallel) = config.unpack();
+
+        assert!(t_0 < t_n);
+
+        let dt: f64 = (t_n - t_0) / (n_steps as f64);
+
+        // Initialise empty paths and fill in the time points.
+        let mut paths = vec![vec![x_0; n_steps + 1]; m_paths];
+        let times: Vec<f64> = (0..=n_steps).map(|t| t_0 + dt * (t as f64)).collect();
+
+        let path_generator = |(i, path): (usize, &mut Vec<f64>)| {
+            let mut rng = config.path_rng(i);
+            let mut y = x_0 - self.seasonality.0(t_0);
+
+            for t in 0..n_steps {
+                let (theta, sigma) = (self.theta.0(times[t]), self.sigma.0(times[t]));
+                let (lambda, eta) = (self.lambda.0(times[t]), self.jump_mean.0(times[t]));
+                assert!(theta > 0.0 && sigma >= 0.0);
+
+                let decay = (-theta * dt).exp();
+                let z: f64 = rng.sample(StandardNormal);
+                y = y * decay + sigma * ((1.0 - decay * decay) / (2.0 * theta)).sqrt() * z;
+
+                let n_jumps = match Poisson::new(lambda * dt) {
+                    Ok(poisson) => poisson.sample(&mut rng) as usize,
+                    Err(_) => 0,
+                };
+                if n_jumps > 0 {
+                    let size = Exp::new(1.0 / eta).unwrap();
+
+                    // Arrival times are uniform within the step.
+                    for _ in 0..n_jumps {
+                        let elapsed = dt * rng.gen::<f64>();
+                        y += size.sample(&mut rng) * (-theta * elapsed).exp();
+                    }
+                }
+
+                path[t + 1] = self.seasonality.0(times[t + 1]) + y;
+            }
+        };
+
+        for_each_path(&mut paths, parallel, path_generator);
+
+        Trajectories { times, paths }
+    }
+
+    /// Simulation is exact, so the scheme is ignored.
+    fn simulate_with_scheme(
+        &self,
+        config: &StochasticProcessConfig,
+        _scheme: DiscretisationScheme,
+    ) -> Trajectories {
+        self.euler_maruyama(config)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_mean_reverting_jump_diffusion {
+    use super::*;
+    use RustQuant_math::*;
+    use RustQuant_utils::assert_approx_equal;
+
+    #[test]
+    fn test_mean_reverting_jump_diffusion_moments() {
+        let (mu, theta, sigma, lambda, eta, t) = (50.0, 5.0, 20.0, 8.0, 30.0, 0.5_f64);
+        let process = MeanRevertingJumpDiffusion::new(mu, theta, sigma, lambda, eta);
+
+        let config = StochasticProcessConfig::new(40.0, 0.0, t, 50, 50_000, true).with_seed(1);
+        let output = process.euler_maruyama(&config);
+
+        let X_T: Vec<f64> = output.paths.iter().map(|p| p[50]).collect();
+
+        // E[X_T] = mu + (x_0 - mu) e^{-theta T} + lambda eta (1 - e^{-theta T}) / theta.
+        let decay = (-theta * t).exp();
+        let mean = mu + (40.0 - mu) * decay + lambda * eta * (1.0 - decay) / theta;
+
+        // V[X_T] = (sigma^2 + lambda E[J^2]) (1 - e^{-2 theta T}) / (2 theta).
+        let variance =
+            (sigma * sigma + lambda * 2.0 * eta * eta) * (1.0 - decay * decay) / (2.0 * theta);
+
+        assert_approx_equal!(X_T.mean(), mean, 0.5);
+        assert_approx_equal!(X_T.variance(), variance, 0.03 * variance);
+
+        // Spikes make the distribution right-skewed.
+        assert!(X_T.skewness() > 0.5);
+    }
+
+    #[test]
+    fn test_mean_reverting_jump_diffusion_seasonality() {
+        // Winter peaks and summer troughs.
+        let seasonality = |t: f64| 60.0 + 15.0 * (2.0 * std::f64::consts::PI * t).cos();
+        let process = MeanRevertingJumpDiffusion::new(seasonality, 3.0, 0.0, 0.0, 10.0);
+
+        // With no noise or spikes, the price follows its seasonal level.
+        let config = StochasticProcessConfig::new(75.0, 0.0, 1.0, 12, 2, false);
+        let output = process.euler_maruyama(&config);
+
+        for path in &output.paths {
+            for (x, &t) in path.iter().zip(&output.times) {
+                assert_approx_equal!(*x, seasonality(t), 1e-10);
+            }
+        }
+
+        // The drift includes the rate of change of the seasonal level.
+        assert_approx_equal!(process.drift(75.0, 0.0), 0.0, 1e-6);
+        assert_approx_equal!(
+            process.drift(60.0, 0.25),
+            -30.0 * std::f64::consts::PI,
+            1e-4
+        );
+    }
+}
@@ -9,15 +9,18 @@
 
 use super::StochasticProcessConfig;
 use crate::model_parameter::ModelParameter;
-use crate::process::{StochasticProcess, Trajectories};
-use rand_distr::Distribution;
-use rayon::prelude::*;
-use RustQuant_math::Gaussian;
-use RustQuant_math::{Distribution as LocalDistribution, Poisson};
+use crate::process::{for_each_path, DiscretisationScheme, StochasticProcess, Trajectories};
+use crate::random::simulation_rng;
+use rand::{Rng, RngCore};
+use rand_distr::{Distribution, StandardNormal};
 
 /// Struct containing the Merton Jump Diffusion parameters.
-/// The Merton Jump Diffusion is a stochastic process that models a path-dependent option.
-/// It is a modification of the Geometric Brownian Motion where the end value is known.
+///
+/// GBM with Poisson-driven lognormal jumps:
+/// $dS_t = (\mu - \lambda k) S_t dt + \sigma S_t dW_t + S_{t^-} (e^J - 1) dN_t$,
+/// where $N_t$ is a Poisson process with intensity $\lambda$,
+/// $J \sim N(m, \delta^2)$, and $k = E[e^J - 1]$ compensates the jumps
+/// so that $E[S_t] = S_0 e^{\mu t}$.
 pub struct MertonJumpDiffusion {
     /// The drift ($\mu$) in percentage.
     pub mu: ModelParameter,
@@ -25,11 +28,14 @@ pub struct MertonJumpDiffusion {
     /// The volatility ($\sigma$) in percentage.
     pub sigma: ModelParameter,
 
-    /// The jump intensity ($\lambda$) in percentage.
+    /// The jump intensity ($\lambda$), the expected number of jumps per year.
     pub lambda: ModelParameter,
 
-    /// The Gaussian distribution for the jump size.
-    pub gaussian: Gaussian,
+    /// The mean of the log jump size ($m$).
+    pub jump_mean: ModelParameter,
+
+    /// The volatility of the log jump size ($\delta$).
+    pub jump_volatility: ModelParameter,
 }
 
 impl MertonJumpDiffusion {
@@ -37,28 +43,62 @@ impl MertonJumpDiffusion {
     /// # Arguments
     /// * `mu` - The drift ($\mu$) in percentage.
     /// * `sigma` - The volatility ($\sigma$) in percentage.
-    /// * `lambda` - The jump intensity ($\lambda$) in percentage.
-    /// * `m` - The mean of the Gaussian distribution for the jump size.
-    /// * `v` - The variance of the Gaussian distribution for the jump size.
+    /// * `lambda` - The jump intensity ($\lambda$).
+    /// * `jump_mean` - The mean of the log jump size ($m$).
+    /// * `jump_volatility` - The volatility of the log jump size ($\delta$).
     pub fn new(
         mu: impl Into<ModelParameter>,
         sigma: impl Into<ModelParameter>,
         lambda: impl Into<ModelParameter>,
-        m: f64,
-        v: f64,
+        jump_mean: impl Into<ModelParameter>,
+        jump_volatility: impl Into<ModelParameter>,
     ) -> Self {
         Self {
             mu: mu.into(),
             sigma: sigma.into(),
             lambda: lambda.into(),
-            gaussian: Gaussian::new(m, v),
+            jump_mean: jump_mean.into(),
+            jump_volatility: jump_volatility.into(),
         }
     }
+
+    /// Expected relative jump size, $k = E[e^J - 1] = e^{m + \delta^2 / 2} - 1$.
+    pub fn mean_relative_jump(&self, t: f64) -> f64 {
+        lognormal_mean_relative_jump(self.jump_mean.0(t), self.jump_volatility.0(t))
+    }
+}
+
+/// $E[e^J - 1]$ for $J \sim N(m, \delta^2)$.
+pub(crate) fn lognormal_mean_relative_jump(m: f64, delta: f64) -> f64 {
+    (m + 0.5 * delta * delta).exp() - 1.0
+}
+
+/// Total log jump over a step of length `dt`, with Poisson arrivals at rate
+/// `lambda` and log jump sizes $N(m, \delta^2)$.
+pub(crate) fn sample_log_jump(
+    rng: &mut dyn RngCore,
+    lambda: f64,
+    m: f64,
+    delta: f64,
+    dt: f64,
+) -> f64 {
+    let n_jumps = match rand_distr::Poisson::new(lambda * dt) {
+        Ok(poisson) => poisson.sample(rng),
+        Err(_) => 0.0,
+    };
+
+    if n_jumps > 0.0 {
+        // The sum of n log jumps is N(n m, n delta^2).
+        let z: f64 = rng.sample(StandardNormal);
+        n_jumps * m + n_jumps.sqrt() * delta * z
+    } else {
+        0.0
+    }
 }
 
 impl StochasticProcess for MertonJumpDiffusion {
     fn drift(&self, x: f64, t: f64) -> f64 {
-        self.mu.0(t) * x
+        (self.mu.0(t) - self.lambda.0(t) * self.mean_relative_jump(t)) * x
     }
 
     fn diffusion(&self, x: f64, t: f64) -> f64 {
@@ -66,12 +106,20 @@ impl StochasticProcess for MertonJumpDiffusion {
         self.sigma.0(t) * x
     }
 
-    fn jump(&self, _x: f64, _t: f64) -> Option<f64> {
-        self.gaussian.sample(1).unwrap().first().copied()
+    fn jump(&self, x: f64, t: f64) -> Option<f64> {
+        let z: f64 = simulation_rng().sample(StandardNormal);
+
+        Some(x * ((self.jump_mean.0(t) + self.jump_volatility.0(t) * z).exp() - 1.0))
     }
 
     fn parameters(&self) -> Vec<f64> {
-        vec![self.mu.0(0.0), self.sigma.0(0.0), self.lambda.0(0.0)]
+        vec![
+            self.mu.0(0.0),
+            self.sigma.0(0.0),
+            self.lambda.0(0.0),
+            self.jump_mean.0(0.0),
+            self.jump_volatility.0(0.0),
+        ]
     }
 
     fn euler_maruyama(&self, config: &StochasticProcessConfig) -> Trajectories {
@@ -85,43 +133,41 @@ impl StochasticProcess for MertonJumpDiffusion {
         let mut paths = vec![vec![x_0; n_steps + 1]; m_paths];
         let times: Vec<f64> = (0..=n_steps).map(|t| t_0 + dt * (t as f64)).collect();
 
-        let path_generator = |path: &mut Vec<f64>| {
-            let mut rng = rand::thread_rng();
+        let path_generator = |(i, path): (usize, &mut Vec<f64>)| {
+            let mut rng = config.path_rng(i);
             let scale = dt.sqrt();
 
-            let dW: Vec<f64> = rand_distr::Normal::new(0.0, 1.0)
-                .unwrap()
-                .sample_iter(&mut rng)
-                .take(n_steps)
-                .map(|z| z * scale)
-                .collect();
-
-            let jumps = Poisson::new(self.lambda.0(0.0) * dt)
-                .sample(n_steps)
-                .unwrap();
-
             for t in 0..n_steps {
-                if jumps[t] > 0.0 {
-                    path[t + 1] = path[t]
-                        + self.drift(path[t], times[t]) * dt
-                        + self.diffusion(path[t], times[t]) * dW[t]
-                        + self.jump(path[t], times[t]).unwrap_or(0.0);
-                } else {
-                    path[t + 1] = path[t]
-                        + self.drift(path[t], times[t]) * dt
-                        + self.diffusion(path[t], times[t]) * dW[t];
-                }
+                let dW = rng.sample::<f64, _>(StandardNormal) * scale;
+
+                let log_jump = sample_log_jump(
+                    &mut rng,
+                    self.lambda.0(times[t]),
+                    self.jump_mean.0(times[t]),
+                    self.jump_volatility.0(times[t]),
+                    dt,
+                );
+
+                path[t + 1] = (path[t]
+                    + self.drift(path[t], times[t]) * dt
+                    + self.diffusion(path[t], times[t]) * dW)
+                    * log_jump.exp();
             }
         };
 
-        if parallel {
-            paths.par_iter_mut().for_each(path_generator);
-        } else {
-            paths.iter_mut().for_each(path_generator);
-        }
+        for_each_path(&mut paths, parallel, path_generator);
 
         Trajectories { times, paths }
     }
+
+    /// Uses the jump-aware scheme of `euler_maruyama`, so the scheme is ignored.
+    fn simulate_with_scheme(
+        &self,
+        config: &StochasticProcessConfig,
+        _scheme: DiscretisationScheme,
+    ) -> Trajectories {
+        self.euler_maruyama(config)
+    }
 }
 
 #[cfg(test)]
@@ -148,13 +194,28 @@ mod tests_gbm_bridge {
 
         let E_XT = X_T.mean();
         let V_XT = X_T.variance();
-        // E[X_T] = https://en.wikipedia.org/wiki/Geometric_Brownian_motion
+        // E[X_T] = X_0 exp(mu T), since the jumps are compensated.
         assert_approx_equal!(E_XT, 10. * (0.05 * 0.5_f64).exp(), 0.5);
-        // V[X_T] = https://en.wikipedia.org/wiki/Geometric_Brownian_motion
+        // V[X_T] = X_0^2 exp(2 mu T) (exp(sigma^2 T + lambda T E[(e^J - 1)^2]) - 1)
+        let jump_second_moment = (2. * 0.3 * 0.3_f64).exp() - 2. * (0.5 * 0.3 * 0.3_f64).exp() + 1.;
         assert_approx_equal!(
             V_XT,
-            10. * 10. * (2. * 0.05 * 0.5_f64).exp() * ((0.9 * 0.9 * 0.5_f64).exp() - 1.),
+            10. * 10.
+                * (2. * 0.05 * 0.5_f64).exp()
+                * ((0.9 * 0.9 * 0.5 + 1.0 * 0.5 * jump_second_moment).exp() - 1.),
             5.0
         );
     }
+
+    #[test]
+    fn test_merton_jump_compensation() {
+        let mjd = MertonJumpDiffusion::new(0.05, 0.2, 0.0, -0.1, 0.3);
+
+        assert_eq!(mjd.drift(10.0, 0.0), 0.05 * 10.0);
+        assert_approx_equal!(
+            mjd.mean_relative_jump(0.0),
+            (-0.1 + 0.5 * 0.09_f64).exp() - 1.0,
+            1e-15
+        );
+    }
 }
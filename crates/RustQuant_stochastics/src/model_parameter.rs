@@ -21,6 +21,33 @@ pub struct ModelParameter(pub Box<dyn Fn(f64) -> f64 + Send + Sync>);
 // IMPLEMENTATIONS, AND FUNCTIONS
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
+impl ModelParameter {
+    /// Piecewise constant term structure: `values[i]` applies on
+    /// `[times[i - 1], times[i])` (with `times[-1] = -inf`), and the last
+    /// value applies from the last time onwards.
+    ///
+    /// # Panics
+    /// Panics if `values.len() != times.len() + 1`,
+    /// or `times` is not strictly increasing.
+    pub fn piecewise_constant(times: &[f64], values: &[f64]) -> Self {
+        assert_eq!(
+            values.len(),
+            times.len() + 1,
+            "A piecewise constant parameter needs one more value than times."
+        );
+        assert!(
+            times.windows(2).all(|w| w[0] < w[1]),
+            "The times must be strictly increasing."
+        );
+
+        let (times, values) = (times.to_vec(), values.to_vec());
+
+        Self(Box::new(move |t| {
+            values[times.partition_point(|&s| s <= t)]
+        }))
+    }
+}
+
 impl fmt::Debug for ModelParameter {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         write!(f, "TimeDependent")
@@ -55,3 +82,40 @@ where
 //         x.partial_cmp(&y)
 //     }
 // }
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_model_parameter {
+    use super::*;
+
+    #[test]
+    fn test_constant_and_function_parameters() {
+        let constant = ModelParameter::from(0.2);
+        let function = ModelParameter::from(|t: f64| 0.1 + 0.05 * t);
+
+        assert_eq!(constant.0(0.0), 0.2);
+        assert_eq!(constant.0(7.0), 0.2);
+        assert_eq!(function.0(2.0), 0.2);
+    }
+
+    #[test]
+    fn test_piecewise_constant_parameter() {
+        let sigma = ModelParameter::piecewise_constant(&[1.0, 2.0], &[0.1, 0.2, 0.3]);
+
+        assert_eq!(sigma.0(0.0), 0.1);
+        assert_eq!(sigma.0(0.999), 0.1);
+        assert_eq!(sigma.0(1.0), 0.2);
+        assert_eq!(sigma.0(1.5), 0.2);
+        assert_eq!(sigma.0(2.0), 0.3);
+        assert_eq!(sigma.0(10.0), 0.3);
+    }
+
+    #[test]
+    #[should_panic(expected = "one more value than times")]
+    fn test_piecewise_constant_lengths() {
+        ModelParameter::piecewise_constant(&[1.0, 2.0], &[0.1, 0.2]);
+    }
+}
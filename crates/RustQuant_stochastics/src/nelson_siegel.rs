@@ -7,7 +7,50 @@
 //      - LICENSE-MIT.md
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
+//! Nelson-Siegel (1987) yield curve model.
+//!
+//! With $x = t / \lambda$, the instantaneous forward and continuously
+//! compounded zero rates are
+//!
+//! $$
+//! f(t) = \beta_0 + \beta_1 e^{-x} + \beta_2 x e^{-x}
+//! $$
+//!
+//! $$
+//! r(t) = \beta_0 + \beta_1 \frac{1 - e^{-x}}{x}
+//!      + \beta_2 \left( \frac{1 - e^{-x}}{x} - e^{-x} \right)
+//! $$
+//!
+//! For fixed decays the rates are linear in the $\beta$s, so a curve is
+//! fitted by least squares in the $\beta$s, nested in a Nelder-Mead search
+//! over the log decays. The Svensson (1994) fit shares the search.
+
+use argmin::core::{CostFunction, Executor, State};
+use argmin::solver::neldermead::NelderMead;
+use nalgebra::{DMatrix, DVector};
+use time::Date;
+use RustQuant_error::RustQuantError;
+use RustQuant_time::{today, DayCountConvention};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// CONSTANTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Maximum Nelder-Mead iterations when fitting a curve.
+const NELSON_SIEGEL_MAX_ITERATIONS: u64 = 2_000;
+
+/// Decays (in years) tried to start the Nelder-Mead search.
+const NELSON_SIEGEL_DECAY_GRID: [f64; 8] = [0.25, 0.5, 1.0, 2.0, 3.0, 5.0, 7.5, 10.0];
+
+/// Number of grid points the Nelder-Mead search is started from.
+const NELSON_SIEGEL_STARTS: usize = 4;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
 /// Nelson-Siegel (1987) model parameters.
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct NelsonSiegel {
     /// $\beta_0$
     pub beta0: f64,
@@ -20,17 +63,338 @@ pub struct NelsonSiegel {
 
     /// $\lambda$
     pub lambda: f64,
+
+    /// Date from which times to maturity are measured.
+    pub reference_date: Date,
+
+    /// Day count convention for times to maturity.
+    pub day_count: DayCountConvention,
 }
 
+/// Least squares fit of the betas, for trial log decays.
+pub(crate) struct NelsonSiegelCalibrator {
+    maturities: Vec<f64>,
+    yields: Vec<f64>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
 impl NelsonSiegel {
-    /// Create a new Nelson-Siegel model.
+    /// Create a new Nelson-Siegel model, measuring time from today with the
+    /// default day count convention.
     #[must_use]
-    pub const fn new(beta0: f64, beta1: f64, beta2: f64, lambda: f64) -> Self {
+    pub fn new(beta0: f64, beta1: f64, beta2: f64, lambda: f64) -> Self {
         Self {
             beta0,
             beta1,
             beta2,
             lambda,
+            reference_date: today(),
+            day_count: DayCountConvention::default(),
         }
     }
+
+    /// Set the date from which times to maturity are measured.
+    #[must_use]
+    pub fn with_reference_date(mut self, reference_date: Date) -> Self {
+        self.reference_date = reference_date;
+        self
+    }
+
+    /// Set the day count convention for times to maturity.
+    #[must_use]
+    pub fn with_day_count(mut self, day_count: DayCountConvention) -> Self {
+        self.day_count = day_count;
+        self
+    }
+
+    /// Fit a model to continuously compounded zero rates, by least squares.
+    ///
+    /// # Arguments:
+    /// * `maturities` - Times to maturity (in years) of the observed rates.
+    /// * `yields` - Observed zero rates.
+    ///
+    /// # Errors
+    /// - `RustQuantError::UnequalLength` if there are not as many
+    ///   maturities as yields.
+    /// - `RustQuantError::InvalidArgument` if there are fewer than four
+    ///   observations, or a maturity is not positive.
+    /// - `RustQuantError::ComputationError` if the optimiser fails.
+    pub fn fit(maturities: &[f64], yields: &[f64]) -> Result<Self, RustQuantError> {
+        let calibrator = NelsonSiegelCalibrator::new(maturities, yields, 4)?;
+        let (lambdas, betas) = calibrator.calibrate(1)?;
+
+        Ok(Self::new(betas[0], betas[1], betas[2], lambdas[0]))
+    }
+
+    /// Continuously compounded zero rate to time `t` (in years).
+    #[must_use]
+    pub fn zero_rate_at(&self, t: f64) -> f64 {
+        let (slope, hump) = zero_loadings(t, self.lambda);
+
+        self.beta0 + self.beta1 * slope + self.beta2 * hump
+    }
+
+    /// Instantaneous forward rate at time `t` (in years).
+    #[must_use]
+    pub fn forward_rate_at(&self, t: f64) -> f64 {
+        let (slope, hump) = forward_loadings(t, self.lambda);
+
+        self.beta0 + self.beta1 * slope + self.beta2 * hump
+    }
+
+    /// Discount factor to time `t` (in years).
+    #[must_use]
+    pub fn discount_factor_at(&self, t: f64) -> f64 {
+        (-self.zero_rate_at(t) * t).exp()
+    }
+}
+
+impl NelsonSiegelCalibrator {
+    /// Check the observations, of which at least `minimum` are needed.
+    pub(crate) fn new(
+        maturities: &[f64],
+        yields: &[f64],
+        minimum: usize,
+    ) -> Result<Self, RustQuantError> {
+        if maturities.len() != yields.len() {
+            return Err(RustQuantError::UnequalLength);
+        }
+        if maturities.len() < minimum {
+            return Err(RustQuantError::InvalidArgument(format!(
+                "At least {minimum} observations are needed to fit the curve."
+            )));
+        }
+        if maturities.iter().any(|&t| t.is_nan() || t <= 0.0) {
+            return Err(RustQuantError::InvalidArgument(
+                "Maturities must be positive.".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            maturities: maturities.to_vec(),
+            yields: yields.to_vec(),
+        })
+    }
+
+    /// Best decays and betas, for `n_decays` decays.
+    pub(crate) fn calibrate(
+        &self,
+        n_decays: usize,
+    ) -> Result<(Vec<f64>, Vec<f64>), RustQuantError> {
+        // Start from the best few points on a grid of log decays, with the
+        // second decay longer than the first, since the Svensson fit has
+        // local minima.
+        let grid = NELSON_SIEGEL_DECAY_GRID.map(f64::ln);
+        let mut starts: Vec<(f64, Vec<f64>)> = match n_decays {
+            1 => grid.iter().map(|&x| vec![x]).collect::<Vec<_>>(),
+            _ => grid
+                .iter()
+                .enumerate()
+                .flat_map(|(i, &x)| grid[i + 1..].iter().map(move |&y| vec![x, y]))
+                .collect(),
+        }
+        .into_iter()
+        .map(|x| (self.errors(&x), x))
+        .collect();
+        starts.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let mut best: Option<(f64, Vec<f64>)> = None;
+
+        for (_, start) in starts.into_iter().take(NELSON_SIEGEL_STARTS) {
+            let params = self.minimise(start)?;
+            let errors = self.errors(&params);
+
+            if best.as_ref().is_none_or(|(e, _)| errors < *e) {
+                best = Some((errors, params));
+            }
+        }
+
+        let lambdas: Vec<f64> = best
+            .expect("The grid is not empty.")
+            .1
+            .iter()
+            .map(|x| x.exp())
+            .collect();
+
+        let betas = self
+            .linear_parameters(&lambdas)
+            .ok_or_else(|| RustQuantError::ComputationError("Curve fit failed.".to_string()))?;
+
+        Ok((lambdas, betas))
+    }
+
+    /// Nelder-Mead search for the best log decays from `start`.
+    fn minimise(&self, start: Vec<f64>) -> Result<Vec<f64>, RustQuantError> {
+        let simplex = std::iter::once(start.clone())
+            .chain((0..start.len()).map(|i| {
+                let mut vertex = start.clone();
+                vertex[i] += 0.25;
+                vertex
+            }))
+            .collect();
+
+        let solver = NelderMead::new(simplex)
+            .with_sd_tolerance(1e-14)
+            .map_err(|error| RustQuantError::ComputationError(error.to_string()))?;
+
+        let result = Executor::new(self, solver)
+            .configure(|state| state.max_iters(NELSON_SIEGEL_MAX_ITERATIONS))
+            .run()
+            .map_err(|error| RustQuantError::ComputationError(error.to_string()))?;
+
+        result
+            .state()
+            .get_best_param()
+            .cloned()
+            .ok_or_else(|| RustQuantError::ComputationError("Curve fit failed.".to_string()))
+    }
+
+    /// Least squares betas for the decays `lambdas`.
+    fn linear_parameters(&self, lambdas: &[f64]) -> Option<Vec<f64>> {
+        let n = self.maturities.len();
+        let k = 2 + lambdas.len();
+
+        let regressors: Vec<f64> = self
+            .maturities
+            .iter()
+            .flat_map(|&t| {
+                let (slope, _) = zero_loadings(t, lambdas[0]);
+                let humps = lambdas
+                    .iter()
+                    .map(move |&lambda| zero_loadings(t, lambda).1);
+
+                [1.0, slope].into_iter().chain(humps)
+            })
+            .collect();
+        let X = DMatrix::from_row_slice(n, k, &regressors);
+        let y = DVector::from_column_slice(&self.yields);
+
+        let beta = X
+            .tr_mul(&X)
+            .svd(true, true)
+            .solve(&X.tr_mul(&y), f64::EPSILON)
+            .ok()?;
+
+        Some(beta.iter().copied().collect())
+    }
+
+    /// Sum of squared yield errors, for log decays `params`.
+    fn errors(&self, params: &[f64]) -> f64 {
+        let lambdas: Vec<f64> = params.iter().map(|x| x.exp()).collect();
+
+        let Some(betas) = self.linear_parameters(&lambdas) else {
+            return f64::INFINITY;
+        };
+
+        self.maturities
+            .iter()
+            .zip(&self.yields)
+            .map(|(&t, y)| {
+                let (slope, _) = zero_loadings(t, lambdas[0]);
+                let humps = lambdas.iter().map(|&lambda| zero_loadings(t, lambda).1);
+                let fitted = betas[0]
+                    + betas[1] * slope
+                    + humps.zip(&betas[2..]).map(|(h, b)| b * h).sum::<f64>();
+
+                (fitted - y).powi(2)
+            })
+            .sum()
+    }
+}
+
+impl CostFunction for &NelsonSiegelCalibrator {
+    type Param = Vec<f64>;
+    type Output = f64;
+
+    /// Sum of squared yield errors, for log decays `params`.
+    fn cost(&self, params: &Self::Param) -> Result<Self::Output, argmin::core::Error> {
+        Ok(self.errors(params))
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Zero rate loadings of the slope and hump, $(1 - e^{-x}) / x$ and
+/// $(1 - e^{-x}) / x - e^{-x}$, with their limits at $t = 0$.
+pub(crate) fn zero_loadings(t: f64, lambda: f64) -> (f64, f64) {
+    let x = t / lambda;
+
+    if x < 1e-8 {
+        return (1.0 - 0.5 * x, 0.5 * x);
+    }
+
+    let slope = -f64::exp_m1(-x) / x;
+
+    (slope, slope - (-x).exp())
+}
+
+/// Forward rate loadings of the slope and hump, $e^{-x}$ and $x e^{-x}$.
+pub(crate) fn forward_loadings(t: f64, lambda: f64) -> (f64, f64) {
+    let x = t / lambda;
+
+    ((-x).exp(), x * (-x).exp())
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_nelson_siegel {
+    use super::*;
+    use RustQuant_utils::assert_approx_equal;
+
+    const MATURITIES: [f64; 12] = [
+        0.25, 0.5, 1.0, 2.0, 3.0, 5.0, 7.0, 10.0, 15.0, 20.0, 25.0, 30.0,
+    ];
+
+    #[test]
+    fn test_nelson_siegel_rates() {
+        let curve = NelsonSiegel::new(0.045, -0.02, 0.01, 1.5);
+
+        // The short rate is beta0 + beta1, and the long rate beta0.
+        assert_approx_equal!(curve.zero_rate_at(0.0), 0.025, 1e-12);
+        assert_approx_equal!(curve.forward_rate_at(0.0), 0.025, 1e-12);
+        assert_approx_equal!(curve.zero_rate_at(1e4), 0.045, 1e-5);
+
+        // The forward rate is the derivative of t r(t).
+        for t in [0.5, 2.0, 10.0] {
+            let h = 1e-5;
+            let derivative = ((t + h) * curve.zero_rate_at(t + h)
+                - (t - h) * curve.zero_rate_at(t - h))
+                / (2.0 * h);
+
+            assert_approx_equal!(curve.forward_rate_at(t), derivative, 1e-9);
+            assert_approx_equal!(
+                curve.discount_factor_at(t),
+                f64::exp(-t * curve.zero_rate_at(t)),
+                1e-15
+            );
+        }
+    }
+
+    #[test]
+    fn test_nelson_siegel_fit() {
+        let curve = NelsonSiegel::new(0.045, -0.02, 0.03, 2.0);
+        let yields = MATURITIES.map(|t| curve.zero_rate_at(t));
+
+        let fitted = NelsonSiegel::fit(&MATURITIES, &yields).unwrap();
+
+        assert_approx_equal!(fitted.beta0, 0.045, 1e-6);
+        assert_approx_equal!(fitted.beta1, -0.02, 1e-6);
+        assert_approx_equal!(fitted.beta2, 0.03, 1e-6);
+        assert_approx_equal!(fitted.lambda, 2.0, 1e-4);
+    }
+
+    #[test]
+    fn test_invalid_fit() {
+        assert!(NelsonSiegel::fit(&[1.0, 2.0], &[0.03]).is_err());
+        assert!(NelsonSiegel::fit(&[1.0, 2.0, 3.0], &[0.03; 3]).is_err());
+        assert!(NelsonSiegel::fit(&[0.0, 1.0, 2.0, 3.0], &[0.03; 4]).is_err());
+    }
 }
@@ -7,8 +7,10 @@
 //      - LICENSE-MIT.md
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
+use crate::nelson_siegel::{forward_loadings, zero_loadings, NelsonSiegelCalibrator};
 use crate::CurveModel;
 use time::Date;
+use RustQuant_error::RustQuantError;
 use RustQuant_time::{today, DayCountConvention};
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -16,7 +18,10 @@ use RustQuant_time::{today, DayCountConvention};
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
 /// Nelson-Siegel-Svensson (1994) model parameters.
-#[derive(Clone, Copy, Debug, PartialEq, Default)]
+///
+/// The model adds a second hump, $\beta_3$, with its own decay
+/// $\lambda_2$, to the Nelson-Siegel curve.
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct NelsonSiegelSvensson {
     /// $\beta_0$
     pub beta0: f64,
@@ -35,23 +40,29 @@ pub struct NelsonSiegelSvensson {
 
     /// $\lambda_2$
     pub lambda2: f64,
+
+    /// Date from which times to maturity are measured.
+    pub reference_date: Date,
+
+    /// Day count convention for times to maturity.
+    pub day_count: DayCountConvention,
 }
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // IMPLEMENTATIONS, TRAITS, AND FUNCTIONS
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
+impl Default for NelsonSiegelSvensson {
+    fn default() -> Self {
+        Self::new(0.0, 0.0, 0.0, 0.0, 0.0, 0.0)
+    }
+}
+
 impl NelsonSiegelSvensson {
-    /// Create a new Nelson-Siegel model.
+    /// Create a new Nelson-Siegel-Svensson model, measuring time from today
+    /// with the default day count convention.
     #[must_use]
-    pub const fn new(
-        beta0: f64,
-        beta1: f64,
-        beta2: f64,
-        beta3: f64,
-        lambda1: f64,
-        lambda2: f64,
-    ) -> Self {
+    pub fn new(beta0: f64, beta1: f64, beta2: f64, beta3: f64, lambda1: f64, lambda2: f64) -> Self {
         Self {
             beta0,
             beta1,
@@ -59,40 +70,92 @@ impl NelsonSiegelSvensson {
             beta3,
             lambda1,
             lambda2,
+            reference_date: today(),
+            day_count: DayCountConvention::default(),
         }
     }
+
+    /// Set the date from which times to maturity are measured.
+    #[must_use]
+    pub fn with_reference_date(mut self, reference_date: Date) -> Self {
+        self.reference_date = reference_date;
+        self
+    }
+
+    /// Set the day count convention for times to maturity.
+    #[must_use]
+    pub fn with_day_count(mut self, day_count: DayCountConvention) -> Self {
+        self.day_count = day_count;
+        self
+    }
+
+    /// Fit a model to continuously compounded zero rates, by least squares.
+    ///
+    /// # Arguments:
+    /// * `maturities` - Times to maturity (in years) of the observed rates.
+    /// * `yields` - Observed zero rates.
+    ///
+    /// # Errors
+    /// - `RustQuantError::UnequalLength` if there are not as many
+    ///   maturities as yields.
+    /// - `RustQuantError::InvalidArgument` if there are fewer than six
+    ///   observations, or a maturity is not positive.
+    /// - `RustQuantError::ComputationError` if the optimiser fails.
+    pub fn fit(maturities: &[f64], yields: &[f64]) -> Result<Self, RustQuantError> {
+        let calibrator = NelsonSiegelCalibrator::new(maturities, yields, 6)?;
+        let (lambdas, betas) = calibrator.calibrate(2)?;
+
+        Ok(Self::new(
+            betas[0], betas[1], betas[2], betas[3], lambdas[0], lambdas[1],
+        ))
+    }
+
+    /// Continuously compounded zero rate to time `t` (in years).
+    #[must_use]
+    pub fn zero_rate_at(&self, t: f64) -> f64 {
+        let (slope, hump1) = zero_loadings(t, self.lambda1);
+        let (_, hump2) = zero_loadings(t, self.lambda2);
+
+        self.beta0 + self.beta1 * slope + self.beta2 * hump1 + self.beta3 * hump2
+    }
+
+    /// Instantaneous forward rate at time `t` (in years).
+    #[must_use]
+    pub fn forward_rate_at(&self, t: f64) -> f64 {
+        let (slope, hump1) = forward_loadings(t, self.lambda1);
+        let (_, hump2) = forward_loadings(t, self.lambda2);
+
+        self.beta0 + self.beta1 * slope + self.beta2 * hump1 + self.beta3 * hump2
+    }
+
+    /// Discount factor to time `t` (in years).
+    #[must_use]
+    pub fn discount_factor_at(&self, t: f64) -> f64 {
+        (-self.zero_rate_at(t) * t).exp()
+    }
 }
 
 impl CurveModel for NelsonSiegelSvensson {
     /// Returns the forward rate for a given date.
     fn forward_rate(&self, date: Date) -> f64 {
-        assert!(date > today(), "Date must be in the future.");
-
-        let tau = DayCountConvention::default().day_count_factor(today(), date);
+        assert!(date > self.reference_date, "Date must be in the future.");
 
-        let term1 = f64::exp(-tau / self.lambda1);
-        let term2 = (tau / self.lambda1) * term1;
-        let term3 = (tau / self.lambda2) * f64::exp(-tau / self.lambda2);
+        let tau = self.day_count.day_count_factor(self.reference_date, date);
 
-        self.beta0 + self.beta1 * term1 + self.beta2 * term2 + self.beta3 * term3
+        self.forward_rate_at(tau)
     }
 
     /// Returns the spot rate for a given date.
     fn spot_rate(&self, date: Date) -> f64 {
-        assert!(date > today(), "Date must be in the future.");
+        assert!(date > self.reference_date, "Date must be in the future.");
 
-        let tau = DayCountConvention::default().day_count_factor(today(), date);
+        let tau = self.day_count.day_count_factor(self.reference_date, date);
 
-        let term1 = self.lambda1 * (1. - f64::exp(-tau / self.lambda1)) / tau;
-        let term2 = term1 - f64::exp(-tau / self.lambda1);
-        let term3 = self.lambda2 * (1. - f64::exp(-tau / self.lambda2)) / tau
-            - f64::exp(-tau / self.lambda2);
-
-        self.beta0 + self.beta1 * term1 + self.beta2 * term2 + self.beta3 * term3
+        self.zero_rate_at(tau)
     }
 
     fn discount_factor(&self, date: Date) -> f64 {
-        let tau = DayCountConvention::default().day_count_factor(today(), date);
+        let tau = self.day_count.day_count_factor(self.reference_date, date);
 
         f64::exp(-self.spot_rate(date) * tau / 100.)
     }
@@ -105,18 +168,13 @@ impl CurveModel for NelsonSiegelSvensson {
 #[cfg(test)]
 mod tests_nelson_siegel_svensson {
     use super::*;
+    use crate::NelsonSiegel;
     use time::Duration;
+    use RustQuant_utils::assert_approx_equal;
 
     #[test]
     fn test_nelson_siegel_svensson() {
-        let nss = NelsonSiegelSvensson {
-            beta0: 0.0806,
-            beta1: -0.0031,
-            beta2: -0.0625,
-            beta3: -0.0198,
-            lambda1: 1.58,
-            lambda2: 0.15,
-        };
+        let nss = NelsonSiegelSvensson::new(0.0806, -0.0031, -0.0625, -0.0198, 1.58, 0.15);
 
         let dates = (2..365 * 30)
             .map(|i| today() + Duration::days(i))
@@ -138,4 +196,49 @@ mod tests_nelson_siegel_svensson {
         //     "./images/nelson_siegel_svensson_discount.png"
         // );
     }
+
+    const MATURITIES: [f64; 12] = [
+        0.25, 0.5, 1.0, 2.0, 3.0, 5.0, 7.0, 10.0, 15.0, 20.0, 25.0, 30.0,
+    ];
+
+    #[test]
+    fn test_svensson_fit() {
+        let curve = NelsonSiegelSvensson::new(0.05, -0.03, -0.02, 0.04, 1.0, 6.0);
+        let yields = MATURITIES.map(|t| curve.zero_rate_at(t));
+
+        let fitted = NelsonSiegelSvensson::fit(&MATURITIES, &yields).unwrap();
+
+        for t in MATURITIES {
+            assert_approx_equal!(fitted.zero_rate_at(t), curve.zero_rate_at(t), 1e-7);
+            assert_approx_equal!(fitted.forward_rate_at(t), curve.forward_rate_at(t), 1e-5);
+        }
+
+        // Svensson nests Nelson-Siegel, so fits it at least as well.
+        let ns = NelsonSiegel::fit(&MATURITIES, &yields).unwrap();
+        let sse = |f: &dyn Fn(f64) -> f64| {
+            MATURITIES
+                .iter()
+                .zip(&yields)
+                .map(|(&t, y)| (f(t) - y).powi(2))
+                .sum::<f64>()
+        };
+
+        assert!(sse(&|t| fitted.zero_rate_at(t)) <= sse(&|t| ns.zero_rate_at(t)));
+        assert!(NelsonSiegelSvensson::fit(&[1.0, 2.0, 3.0, 4.0, 5.0], &[0.03; 5]).is_err());
+    }
+
+    #[test]
+    fn test_curve_model_uses_reference_date_and_day_count() {
+        let reference_date = time::macros::date!(2024 - 01 - 02);
+        let day_count = DayCountConvention::Actual_365_Fixed;
+        let nss = NelsonSiegelSvensson::new(0.05, -0.03, -0.02, 0.04, 1.0, 6.0)
+            .with_reference_date(reference_date)
+            .with_day_count(day_count);
+
+        let date = time::macros::date!(2029 - 01 - 02);
+        let t = day_count.day_count_factor(reference_date, date);
+
+        assert_approx_equal!(nss.spot_rate(date), nss.zero_rate_at(t), 1e-15);
+        assert_approx_equal!(nss.forward_rate(date), nss.forward_rate_at(t), 1e-15);
+    }
 }
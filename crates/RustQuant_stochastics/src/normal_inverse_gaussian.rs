@@ -0,0 +1,248 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::model_parameter::ModelParameter;
+use crate::process::{
+    for_each_path, DiscretisationScheme, StochasticProcess, StochasticProcessConfig, Trajectories,
+};
+use num::Complex;
+use rand::Rng;
+use rand_distr::{Distribution, StandardNormal};
+
+/// Struct containing the Normal Inverse Gaussian process parameters.
+///
+/// Brownian motion with drift $\beta$, run on an inverse Gaussian clock:
+/// $X_t = \beta I_t + W(I_t)$, where $I_t$ has mean $\delta t / \gamma$
+/// and shape $\delta^2 t^2$, with $\gamma = \sqrt{\alpha^2 - \beta^2}$
+/// (Barndorff-Nielsen, 1997).
+pub struct NormalInverseGaussian {
+    /// Tail heaviness ($\alpha$).
+    /// Note: $\alpha > |\beta|$.
+    pub alpha: ModelParameter,
+
+    /// Asymmetry ($\beta$).
+    pub beta: ModelParameter,
+
+    /// Scale ($\delta$).
+    /// Note: $\delta \in (0, \infty)$.
+    pub delta: ModelParameter,
+}
+
+impl NormalInverseGaussian {
+    /// Create a new Normal Inverse Gaussian process.
+    pub fn new(
+        alpha: impl Into<ModelParameter>,
+        beta: impl Into<ModelParameter>,
+        delta: impl Into<ModelParameter>,
+    ) -> Self {
+        Self {
+            alpha: alpha.into(),
+            beta: beta.into(),
+            delta: delta.into(),
+        }
+    }
+
+    /// Characteristic function of the increment $X_t - X_0$:
+    ///
+    /// $$
+    /// \phi(u) = \exp\left( \delta t \left( \sqrt{\alpha^2 - \beta^2}
+    ///     - \sqrt{\alpha^2 - (\beta + i u)^2} \right) \right)
+    /// $$
+    ///
+    /// Uses the parameters at time zero, so assumes they are constant.
+    pub fn characteristic_function(&self, u: f64, t: f64) -> Complex<f64> {
+        let (alpha, beta, delta) = (self.alpha.0(0.0), self.beta.0(0.0), self.delta.0(0.0));
+        let i: Complex<f64> = Complex::i();
+
+        let gamma = (alpha * alpha - beta * beta).sqrt();
+        let shifted = beta + i * u;
+
+        (delta * t * (gamma - (alpha * alpha - shifted * shifted).sqrt())).exp()
+    }
+
+    /// Drift correction $\omega$ that makes $e^{\omega t + X_t}$ a martingale:
+    ///
+    /// $$
+    /// \omega = -\delta \left( \sqrt{\alpha^2 - \beta^2} - \sqrt{\alpha^2 - (\beta + 1)^2} \right)
+    /// $$
+    ///
+    /// An asset priced under the NIG model is then
+    /// $S_t = S_0 e^{(r - q + \omega) t + X_t}$.
+    /// Uses the parameters at time zero, so assumes they are constant.
+    ///
+    /// # Panics
+    /// Panics if $\alpha \leq |\beta + 1|$, when $e^{X_t}$ has no finite mean.
+    pub fn martingale_correction(&self) -> f64 {
+        let (alpha, beta, delta) = (self.alpha.0(0.0), self.beta.0(0.0), self.delta.0(0.0));
+
+        assert!(
+            alpha > (beta + 1.0).abs(),
+            "exp(X_t) must have a finite mean."
+        );
+
+        let gamma = (alpha * alpha - beta * beta).sqrt();
+
+        -delta * (gamma - (alpha * alpha - (beta + 1.0).powi(2)).sqrt())
+    }
+}
+
+impl StochasticProcess for NormalInverseGaussian {
+    /// Mean rate of the process, $\delta \beta / \gamma$.
+    fn drift(&self, _x: f64, t: f64) -> f64 {
+        let (alpha, beta) = (self.alpha.0(t), self.beta.0(t));
+
+        self.delta.0(t) * beta / (alpha * alpha - beta * beta).sqrt()
+    }
+
+    /// Standard deviation rate of the process, $\sqrt{\delta \alpha^2 / \gamma^3}$.
+    /// Simulated paths use the inverse Gaussian time change instead.
+    fn diffusion(&self, _x: f64, t: f64) -> f64 {
+        let (alpha, beta) = (self.alpha.0(t), self.beta.0(t));
+        let gamma = (alpha * alpha - beta * beta).sqrt();
+
+        (self.delta.0(t) * alpha * alpha / gamma.powi(3)).sqrt()
+    }
+
+    fn jump(&self, _x: f64, _t: f64) -> Option<f64> {
+        None
+    }
+
+    fn parameters(&self) -> Vec<f64> {
+        vec![self.alpha.0(0.0), self.beta.0(0.0), self.delta.0(0.0)]
+    }
+
+    /// Exact simulation by inverse Gaussian time-changed Brownian motion.
+    fn euler_maruyama(&self, config: &StochasticProcessConfig) -> Trajectories {
+        let (x_0, t_0, t_n, n_steps, m_paths, parallel) = config.unpack();
+
+        assert!(t_0 < t_n);
+
+        let dt: f64 = (t_n - t_0) / (n_steps as f64);
+
+        // Initialise empty paths and fill in the time points.
+        let mut paths = vec![vec![x_0; n_steps + 1]; m_paths];
+        let times: Vec<f64> = (0..=n_steps).map(|t| t_0 + dt * (t as f64)).collect();
+
+        let path_generator = |(i, path): (usize, &mut Vec<f64>)| {
+            let mut rng = config.path_rng(i);
+
+            for t in 0..n_steps {
+                let (alpha, beta, delta) = (
+                    self.alpha.0(times[t]),
+                    self.beta.0(times[t]),
+                    self.delta.0(times[t]),
+                );
+
+                assert!(alpha > beta.abs(), "alpha must exceed |beta|.");
+                assert!(delta > 0.0, "delta must be positive.");
+
+                // Inverse Gaussian clock increment.
+                let gamma = (alpha * alpha - beta * beta).sqrt();
+                let clock =
+                    rand_distr::InverseGaussian::new(delta * dt / gamma, (delta * dt).powi(2))
+                        .unwrap();
+                let dI = clock.sample(&mut rng);
+                let z: f64 = rng.sample(StandardNormal);
+
+                path[t + 1] = path[t] + beta * dI + dI.sqrt() * z;
+            }
+        };
+
+        for_each_path(&mut paths, parallel, path_generator);
+
+        Trajectories { times, paths }
+    }
+
+    /// Simulation is exact, so the scheme is ignored.
+    fn simulate_with_scheme(
+        &self,
+        config: &StochasticProcessConfig,
+        _scheme: DiscretisationScheme,
+    ) -> Trajectories {
+        self.euler_maruyama(config)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_normal_inverse_gaussian {
+    use super::*;
+    use RustQuant_math::Statistic;
+    use RustQuant_utils::assert_approx_equal;
+
+    #[test]
+    fn test_nig_moments() {
+        let (alpha, beta, delta, t) = (15.0, -3.0, 0.5, 2.0_f64);
+
+        let nig = NormalInverseGaussian::new(alpha, beta, delta);
+        let config = StochasticProcessConfig::new(0.0, 0.0, t, 20, 50_000, true);
+        let output = nig.euler_maruyama(&config);
+
+        let X_T: Vec<f64> = output.paths.iter().map(|p| p[20]).collect();
+        let gamma = (alpha * alpha - beta * beta).sqrt();
+
+        assert_approx_equal!(X_T.mean(), delta * beta * t / gamma, 0.005);
+        assert_approx_equal!(
+            X_T.variance(),
+            delta * alpha * alpha * t / gamma.powi(3),
+            0.005
+        );
+    }
+
+    #[test]
+    fn test_nig_empirical_characteristic_function() {
+        let (alpha, beta, delta, t) = (10.0, 2.0, 0.3, 1.0);
+
+        let nig = NormalInverseGaussian::new(alpha, beta, delta);
+        let config = StochasticProcessConfig::new(0.0, 0.0, t, 10, 50_000, true);
+        let output = nig.euler_maruyama(&config);
+
+        for u in [0.5, 2.0, 5.0] {
+            let empirical = output
+                .paths
+                .iter()
+                .map(|p| Complex::new(0.0, u * p[10]).exp())
+                .sum::<Complex<f64>>()
+                / 50_000.0;
+            let exact = nig.characteristic_function(u, t);
+
+            assert_approx_equal!(empirical.re, exact.re, 0.01);
+            assert_approx_equal!(empirical.im, exact.im, 0.01);
+        }
+    }
+
+    #[test]
+    fn test_nig_martingale_correction() {
+        let (alpha, beta, delta, t) = (12.0, -2.0, 0.4, 1.0_f64);
+
+        let nig = NormalInverseGaussian::new(alpha, beta, delta);
+        let omega = nig.martingale_correction();
+
+        let config = StochasticProcessConfig::new(0.0, 0.0, t, 10, 50_000, true);
+        let output = nig.euler_maruyama(&config);
+
+        let mean: f64 = output
+            .paths
+            .iter()
+            .map(|p| (omega * t + p[10]).exp())
+            .sum::<f64>()
+            / 50_000.0;
+
+        assert_approx_equal!(mean, 1.0, 0.005);
+    }
+
+    #[test]
+    #[should_panic(expected = "exp(X_t) must have a finite mean.")]
+    fn test_nig_martingale_correction_infinite_mean() {
+        NormalInverseGaussian::new(2.0, 1.5, 0.5).martingale_correction();
+    }
+}
@@ -7,8 +7,10 @@
 //      - LICENSE-MIT.md
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
+use crate::estimation::{validate_series, LeastSquares, ProcessFit};
 use crate::model_parameter::ModelParameter;
 use crate::process::StochasticProcess;
+use RustQuant_error::RustQuantError;
 
 /// Struct containing the Ornstein-Uhlenbeck process parameters.
 pub struct OrnsteinUhlenbeck {
@@ -36,6 +38,53 @@ impl OrnsteinUhlenbeck {
             theta: theta.into(),
         }
     }
+
+    /// Fit the process to a time series observed every `dt` years, by
+    /// maximum likelihood on the exact AR(1) transition
+    /// $X_{t + dt} = a + b X_t + \epsilon$, where $b = e^{-\theta dt}$,
+    /// $a = \mu (1 - b)$ and $Var(\epsilon) = \sigma^2 (1 - b^2) / (2 \theta)$.
+    ///
+    /// # Errors
+    /// - `RustQuantError::InvalidArgument` if there are fewer than three
+    ///   observations or `dt` is not positive.
+    /// - `RustQuantError::ComputationError` if the series is not mean reverting.
+    pub fn fit(series: &[f64], dt: f64) -> Result<ProcessFit<Self>, RustQuantError> {
+        validate_series(series, dt)?;
+
+        let z: Vec<[f64; 2]> = series[..series.len() - 1]
+            .iter()
+            .map(|&x| [1.0, x])
+            .collect();
+        let ls = LeastSquares::fit(&z, &series[1..])?;
+        let ([a, b], v) = (ls.beta, ls.residual_variance);
+
+        if !(0.0 < b && b < 1.0) {
+            return Err(RustQuantError::ComputationError(
+                "The time series is not mean reverting.".to_string(),
+            ));
+        }
+
+        let theta = -b.ln() / dt;
+        let mu = a / (1.0 - b);
+        let sigma_squared = 2.0 * theta * v / (1.0 - b * b);
+        let sigma = sigma_squared.sqrt();
+
+        // Delta method, using the independence of v from (a, b).
+        let theta_se = ls.delta_standard_error([0.0, -1.0 / (b * dt)]);
+        let mu_se = ls.delta_standard_error([1.0 / (1.0 - b), a / (1.0 - b).powi(2)]);
+        let d_sigma_squared_db =
+            -2.0 * v / dt * ((1.0 - b * b) / b + 2.0 * b * b.ln()) / (1.0 - b * b).powi(2);
+        let sigma_squared_se = (ls.delta_standard_error([0.0, d_sigma_squared_db]).powi(2)
+            + (2.0 * theta / (1.0 - b * b) * ls.residual_variance_standard_error()).powi(2))
+        .sqrt();
+        let sigma_se = sigma_squared_se / (2.0 * sigma);
+
+        Ok(ProcessFit {
+            process: Self::new(mu, sigma, theta),
+            estimates: vec![mu, sigma, theta],
+            standard_errors: vec![mu_se, sigma_se, theta_se],
+        })
+    }
 }
 
 impl StochasticProcess for OrnsteinUhlenbeck {
@@ -102,4 +151,31 @@ mod tests_ornstein_uhlenbeck {
         // let file2 = "./images/OU2.png";
         // plot_vector((&output.trajectories[1]).clone(), file2)
     }
+
+    #[test]
+    fn test_fit() {
+        let ou = OrnsteinUhlenbeck::new(0.05, 0.02, 2.0);
+
+        // Twenty years of weekly observations.
+        let config = StochasticProcessConfig::new(0.03, 0.0, 20.0, 10400, 1, false).with_seed(5);
+        let series: Vec<f64> = ou.euler_maruyama(&config).paths[0]
+            .iter()
+            .step_by(10)
+            .copied()
+            .collect();
+
+        let fit = OrnsteinUhlenbeck::fit(&series, 20.0 / 1040.0).unwrap();
+
+        assert_eq!(fit.estimates, fit.process.parameters());
+        for (estimate, (truth, se)) in fit
+            .estimates
+            .iter()
+            .zip([0.05, 0.02, 2.0].iter().zip(&fit.standard_errors))
+        {
+            assert!((estimate - truth).abs() < 3.0 * se);
+        }
+
+        // A random walk is not mean reverting.
+        assert!(OrnsteinUhlenbeck::fit(&[0.0, 1.0, 2.0, 3.0, 4.5], 0.1).is_err());
+    }
 }
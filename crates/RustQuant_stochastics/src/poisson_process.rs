@@ -0,0 +1,260 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::process::{
+    map_paths, DiscretisationScheme, StochasticProcess, StochasticProcessConfig, Trajectories,
+};
+use crate::random::SimulationRng;
+use rand::Rng;
+use rand_distr::{Distribution, Exp};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// The jumps of one simulated path of a jump process.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JumpPath {
+    /// Jump times, in increasing order.
+    pub times: Vec<f64>,
+
+    /// Jump sizes, one for each jump time.
+    pub sizes: Vec<f64>,
+}
+
+/// Struct containing the Poisson process parameters.
+///
+/// Counts events arriving at a constant rate $\lambda$, so the number
+/// of events in a time interval of length $t$ is Poisson($\lambda t$).
+pub struct PoissonProcess {
+    /// Arrival rate of the events ($\lambda$).
+    /// Note: $\lambda \in [0, \infty)$.
+    pub lambda: f64,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl JumpPath {
+    /// Number of jumps in the path.
+    pub fn len(&self) -> usize {
+        self.times.len()
+    }
+
+    /// Whether the path has no jumps.
+    pub fn is_empty(&self) -> bool {
+        self.times.is_empty()
+    }
+
+    /// Sum of the jump sizes up to and including time `t`.
+    pub fn value_at(&self, t: f64) -> f64 {
+        let n = self.times.partition_point(|&s| s <= t);
+
+        self.sizes[..n].iter().sum()
+    }
+
+    /// Values of `x_0` plus the jumps on a sorted time grid.
+    pub fn on_grid(&self, x_0: f64, grid: &[f64]) -> Vec<f64> {
+        let mut value = x_0;
+        let mut next = 0;
+
+        grid.iter()
+            .map(|&t| {
+                while next < self.times.len() && self.times[next] <= t {
+                    value += self.sizes[next];
+                    next += 1;
+                }
+                value
+            })
+            .collect()
+    }
+}
+
+impl PoissonProcess {
+    /// Create a new Poisson process.
+    ///
+    /// # Panics
+    /// Panics if `lambda` is negative.
+    pub fn new(lambda: f64) -> Self {
+        assert!(lambda >= 0.0, "The arrival rate must be non-negative.");
+
+        Self { lambda }
+    }
+
+    /// Arrival times on `(t_0, t_n]`, from exponential inter-arrival times.
+    pub fn sample_arrival_times<R: Rng + ?Sized>(
+        &self,
+        t_0: f64,
+        t_n: f64,
+        rng: &mut R,
+    ) -> Vec<f64> {
+        let mut times = Vec::new();
+
+        if self.lambda == 0.0 {
+            return times;
+        }
+
+        let inter_arrival = Exp::new(self.lambda).unwrap();
+        let mut t = t_0 + inter_arrival.sample(rng);
+
+        while t <= t_n {
+            times.push(t);
+            t += inter_arrival.sample(rng);
+        }
+
+        times
+    }
+
+    /// Simulate the arrivals on `[t_0, t_n]` of each path in `config`,
+    /// as jumps of size one. The number of steps in `config` is ignored.
+    pub fn jump_paths(&self, config: &StochasticProcessConfig) -> Vec<JumpPath> {
+        let (_, t_0, t_n, _, m_paths, parallel) = config.unpack();
+
+        assert!(t_0 < t_n);
+
+        let generator = |i| {
+            let times = self.sample_arrival_times(t_0, t_n, &mut config.path_rng(i));
+            let sizes = vec![1.0; times.len()];
+
+            JumpPath { times, sizes }
+        };
+
+        map_paths(m_paths, parallel, generator)
+    }
+}
+
+/// Simulate jump paths and record them on the time grid of `config`.
+pub(crate) fn jump_trajectories<F>(config: &StochasticProcessConfig, jump_path: F) -> Trajectories
+where
+    F: Fn(&mut SimulationRng) -> JumpPath + Sync,
+{
+    let (x_0, t_0, t_n, n_steps, m_paths, parallel) = config.unpack();
+
+    assert!(t_0 < t_n);
+
+    let dt: f64 = (t_n - t_0) / (n_steps as f64);
+    let times: Vec<f64> = (0..=n_steps).map(|t| t_0 + dt * (t as f64)).collect();
+
+    let generator = |i| jump_path(&mut config.path_rng(i)).on_grid(x_0, &times);
+
+    let paths = map_paths(m_paths, parallel, generator);
+
+    Trajectories { times, paths }
+}
+
+impl StochasticProcess for PoissonProcess {
+    /// No drift between arrivals.
+    fn drift(&self, _x: f64, _t: f64) -> f64 {
+        0.0
+    }
+
+    fn diffusion(&self, _x: f64, _t: f64) -> f64 {
+        0.0
+    }
+
+    /// Every arrival increases the count by one.
+    fn jump(&self, _x: f64, _t: f64) -> Option<f64> {
+        Some(1.0)
+    }
+
+    fn parameters(&self) -> Vec<f64> {
+        vec![self.lambda]
+    }
+
+    /// Exact simulation: the count of arrivals at each time on the grid.
+    fn euler_maruyama(&self, config: &StochasticProcessConfig) -> Trajectories {
+        let (_, t_0, t_n, _, _, _) = config.unpack();
+
+        jump_trajectories(config, |rng| {
+            let times = self.sample_arrival_times(t_0, t_n, rng);
+            let sizes = vec![1.0; times.len()];
+
+            JumpPath { times, sizes }
+        })
+    }
+
+    /// Simulation is exact, so the scheme is ignored.
+    fn simulate_with_scheme(
+        &self,
+        config: &StochasticProcessConfig,
+        _scheme: DiscretisationScheme,
+    ) -> Trajectories {
+        self.euler_maruyama(config)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_poisson_process {
+    use super::*;
+    use RustQuant_math::Statistic;
+    use RustQuant_utils::assert_approx_equal;
+
+    #[test]
+    fn test_poisson_process_counts() {
+        let (lambda, t) = (3.0, 2.0);
+
+        let poisson = PoissonProcess::new(lambda);
+        let config = StochasticProcessConfig::new(0.0, 0.0, t, 4, 20_000, true);
+        let output = poisson.euler_maruyama(&config);
+
+        let N_T: Vec<f64> = output.paths.iter().map(|p| p[4]).collect();
+
+        assert_approx_equal!(N_T.mean(), lambda * t, 0.1);
+        assert_approx_equal!(N_T.variance(), lambda * t, 0.2);
+
+        // Counts are integers and never decrease.
+        for path in &output.paths {
+            assert!(path.iter().all(|n| n.fract() == 0.0));
+            assert!(path.windows(2).all(|w| w[0] <= w[1]));
+        }
+    }
+
+    #[test]
+    fn test_poisson_process_arrival_times() {
+        let lambda = 5.0;
+
+        let poisson = PoissonProcess::new(lambda);
+        let config = StochasticProcessConfig::new(0.0, 1.0, 3.0, 1, 5_000, false);
+        let paths = poisson.jump_paths(&config);
+
+        let mut first_arrivals = Vec::new();
+        for path in &paths {
+            assert!(path.times.windows(2).all(|w| w[0] < w[1]));
+            assert!(path.times.iter().all(|&t| 1.0 < t && t <= 3.0));
+            assert_eq!(path.value_at(3.0), path.len() as f64);
+
+            if let Some(&t) = path.times.first() {
+                first_arrivals.push(t - 1.0);
+            }
+        }
+
+        // Inter-arrival times are exponential with mean 1 / lambda.
+        assert_approx_equal!(first_arrivals.mean(), 1.0 / lambda, 0.01);
+    }
+
+    #[test]
+    fn test_jump_path_on_grid() {
+        let path = JumpPath {
+            times: vec![0.5, 1.0, 2.5],
+            sizes: vec![2.0, -1.0, 4.0],
+        };
+
+        assert_eq!(
+            path.on_grid(10.0, &[0.0, 0.5, 1.0, 2.0, 3.0]),
+            vec![10.0, 12.0, 11.0, 11.0, 15.0]
+        );
+        assert_eq!(path.value_at(0.9), 2.0);
+        assert_eq!(path.value_at(2.5), 5.0);
+    }
+}
@@ -9,14 +9,27 @@
 
 //! `Trajectories` is the return type of all the stochastic processes.
 //! `StochasticProcess` is the base trait for all the stochastic processes.
+//! `MultiAssetProcess` simulates several processes with correlated shocks.
 //!
 //! Currently only autonomous stochastic processes are implemented.
 //! Autonomous refers to processes where the drift and diffusion
 //! do not explicitly depend on the time `t`.
 
+use crate::brownian_bridge::BrownianBridge;
+use crate::random::{simulation_rng, stream_rng, SimulationRng};
+use nalgebra::{DMatrix, DVector};
 use rand::prelude::Distribution;
-use rand::{rngs::StdRng, SeedableRng};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use rand_distr::{Open01, StandardNormal};
+#[cfg(feature = "parallel")]
 use rayon::prelude::*;
+use RustQuant_error::RustQuantError;
+use RustQuant_math::correlation_cholesky;
+use RustQuant_math::interpolation::{Interpolator, LinearInterpolator};
+use RustQuant_math::{
+    gaussian::N, Distribution as _, SobolSequence, Statistic, SOBOL_MAX_DIMENSION,
+};
 // use statrs::distribution::Normal;
 
 /// Struct to contain the time points and path values of the process.
@@ -28,6 +41,170 @@ pub struct Trajectories {
     pub paths: Vec<Vec<f64>>,
 }
 
+/// Histogram of a set of values, with equal-width bins.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Histogram {
+    /// Bin edges, one more than the number of bins.
+    pub edges: Vec<f64>,
+
+    /// Number of values in each bin.
+    /// Bins are half-open, except the last one, which includes its right edge.
+    pub counts: Vec<usize>,
+}
+
+impl Trajectories {
+    /// Resample every path onto `new_times` by linear interpolation.
+    ///
+    /// # Errors
+    /// - `RustQuantError::OutsideOfRange` if any of `new_times` lies outside
+    ///   the simulated time grid.
+    pub fn resample(&self, new_times: &[f64]) -> Result<Trajectories, RustQuantError> {
+        let paths = self
+            .paths
+            .iter()
+            .map(|path| {
+                let mut interpolator = LinearInterpolator::new(self.times.clone(), path.clone())?;
+                interpolator.fit()?;
+
+                new_times
+                    .iter()
+                    .map(|&t| interpolator.interpolate(t))
+                    .collect::<Result<Vec<f64>, RustQuantError>>()
+            })
+            .collect::<Result<Vec<Vec<f64>>, RustQuantError>>()?;
+
+        Ok(Trajectories {
+            times: new_times.to_vec(),
+            paths,
+        })
+    }
+
+    /// Values of every path at the `step`-th time point.
+    ///
+    /// # Panics
+    /// Panics if `step` is past the end of the time grid.
+    pub fn values_at(&self, step: usize) -> Vec<f64> {
+        assert!(
+            step < self.times.len(),
+            "Step is past the end of the time grid."
+        );
+
+        self.paths.iter().map(|path| path[step]).collect()
+    }
+
+    /// Values of every path at the last time point.
+    pub fn terminal_values(&self) -> Vec<f64> {
+        self.values_at(self.times.len() - 1)
+    }
+
+    /// Mean across paths at each time point.
+    pub fn mean_path(&self) -> Vec<f64> {
+        self.map_time_points(|values| values.mean())
+    }
+
+    /// Sample variance across paths at each time point.
+    pub fn variance_path(&self) -> Vec<f64> {
+        self.map_time_points(|values| values.variance())
+    }
+
+    /// The `p`-quantile across paths at each time point,
+    /// linearly interpolated between order statistics.
+    ///
+    /// # Panics
+    /// Panics if `p` is not in `[0, 1]`.
+    pub fn quantile_path(&self, p: f64) -> Vec<f64> {
+        self.map_time_points(|values| values.quantile(p))
+    }
+
+    /// Histogram of the terminal values, with `n_bins` equal-width bins
+    /// spanning their range.
+    ///
+    /// # Panics
+    /// Panics if `n_bins` is zero or there are no paths.
+    pub fn terminal_histogram(&self, n_bins: usize) -> Histogram {
+        assert!(n_bins > 0, "The number of bins must be positive.");
+        assert!(!self.paths.is_empty(), "There are no paths.");
+
+        let values = self.terminal_values();
+        let (min, max) = (values.min(), values.max());
+        let width = (max - min) / n_bins as f64;
+
+        let edges = (0..=n_bins).map(|i| min + width * i as f64).collect();
+        let mut counts = vec![0; n_bins];
+
+        for value in values {
+            let bin = if width > 0.0 {
+                (((value - min) / width) as usize).min(n_bins - 1)
+            } else {
+                0
+            };
+            counts[bin] += 1;
+        }
+
+        Histogram { edges, counts }
+    }
+
+    fn map_time_points<F>(&self, statistic: F) -> Vec<f64>
+    where
+        F: Fn(Vec<f64>) -> f64,
+    {
+        (0..self.times.len())
+            .map(|step| statistic(self.values_at(step)))
+            .collect()
+    }
+
+    /// Keep every `step`-th time point (starting from the first).
+    ///
+    /// # Panics
+    /// Panics if `step` is zero.
+    pub fn thin(&self, step: usize) -> Trajectories {
+        assert!(step > 0, "The thinning step must be positive.");
+
+        Trajectories {
+            times: self.times.iter().step_by(step).copied().collect(),
+            paths: self
+                .paths
+                .iter()
+                .map(|path| path.iter().step_by(step).copied().collect())
+                .collect(),
+        }
+    }
+}
+
+/// Iterator over the paths of a simulation in batches,
+/// returned by `StochasticProcess::path_batches`.
+pub struct PathBatches<'a, P> {
+    /// The process being simulated.
+    process: &'a P,
+
+    /// Configuration of the whole simulation.
+    config: &'a StochasticProcessConfig,
+
+    /// Maximum number of paths in each batch.
+    batch_size: usize,
+
+    /// Index of the first path of the next batch.
+    next_path: usize,
+}
+
+impl<P: StochasticProcess> Iterator for PathBatches<'_, P> {
+    type Item = Trajectories;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next_path >= self.config.m_paths {
+            return None;
+        }
+
+        let mut config = self.config.clone();
+        config.m_paths = self.batch_size.min(self.config.m_paths - self.next_path);
+        config.path_offset = self.config.path_offset + self.next_path;
+
+        self.next_path += config.m_paths;
+
+        Some(self.process.euler_maruyama(&config))
+    }
+}
+
 /// Trait to implement stochastic volatility processes.
 pub trait StochasticVolatilityProcess: Sync {
     /// Base method for the asset's drift.
@@ -63,7 +240,7 @@ pub trait StochasticVolatilityProcess: Sync {
         let times: Vec<f64> = (0..=n_steps).map(|t| t_0 + dt * (t as f64)).collect();
 
         let path_generator = |(x_path, y_path): (&mut Vec<f64>, &mut Vec<f64>)| {
-            let mut rng = rand::thread_rng();
+            let mut rng = simulation_rng();
             let scale = dt.sqrt();
             let dW: Vec<f64> = rand_distr::Normal::new(0.0, 1.0)
                 .unwrap()
@@ -82,17 +259,12 @@ pub trait StochasticVolatilityProcess: Sync {
             }
         };
 
-        if parallel {
-            x_paths
-                .par_iter_mut()
-                .zip(y_paths.par_iter_mut())
-                .for_each(path_generator);
-        } else {
-            x_paths
-                .iter_mut()
-                .zip(y_paths.iter_mut())
-                .for_each(path_generator);
-        }
+        let mut pairs: Vec<(&mut Vec<f64>, &mut Vec<f64>)> =
+            x_paths.iter_mut().zip(y_paths.iter_mut()).collect();
+
+        for_each_path(&mut pairs, parallel, |(_, (x_path, y_path))| {
+            path_generator((x_path, y_path))
+        });
 
         Trajectories {
             times: times.clone(),
@@ -101,6 +273,160 @@ pub trait StochasticVolatilityProcess: Sync {
     }
 }
 
+/// Time discretisation scheme for simulating a stochastic process
+/// $dX_t = a(X_t, t) dt + b(X_t, t) dW_t$.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DiscretisationScheme {
+    /// $X_{n+1} = X_n + a dt + b \Delta W$ (strong order 1/2).
+    #[default]
+    EulerMaruyama,
+
+    /// Euler-Maruyama plus the Ito correction
+    /// $\frac{1}{2} b b' (\Delta W^2 - dt)$ (strong order 1).
+    Milstein,
+
+    /// Euler predictor, followed by a trapezoidal corrector on the drift
+    /// and diffusion, with the drift adjusted by $-\frac{1}{2} b b'$.
+    PredictorCorrector,
+}
+
+/// Source of the Gaussian draws driving the simulated paths.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SamplingMethod {
+    /// Independent pseudo-random normals for every path.
+    #[default]
+    PseudoRandom,
+
+    /// Quasi-random normals: points of a Sobol sequence mapped through the
+    /// inverse normal CDF.
+    ///
+    /// Only the first [`SOBOL_MAX_DIMENSION`] variates of each path come
+    /// from the Sobol sequence; the rest are pseudo-random. Pair it with
+    /// `PathConstruction::BrownianBridge`, so those leading variates set the
+    /// terminal value and the coarse shape of the path.
+    Sobol,
+
+    /// Pseudo-random normals, except that the `dimension`-th variate of the
+    /// `k`-th draw (of `n`) lies in the `k`-th of `n` equiprobable strata.
+    ///
+    /// With `PathConstruction::BrownianBridge`, dimension zero stratifies
+    /// the terminal value, which suits terminal-value payoffs.
+    Stratified {
+        /// Index of the stratified variate, less than the number of steps.
+        dimension: usize,
+    },
+
+    /// Latin hypercube sampling: every variate is stratified as in
+    /// `Stratified`, with the strata of each variate randomly permuted
+    /// across the draws.
+    LatinHypercube,
+}
+
+/// How the Gaussian draws of a path are turned into Brownian increments.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PathConstruction {
+    /// The `i`-th draw sets the `i`-th increment.
+    #[default]
+    Incremental,
+
+    /// The first draw sets the terminal value, the second the midpoint,
+    /// and so on (see [`BrownianBridge`]).
+    ///
+    /// The paths have the same distribution, but most of their variance is
+    /// carried by the leading draws, which suits quasi-random sampling.
+    BrownianBridge,
+}
+
+/// Apply `f` to every path, along with its index, spread over the rayon
+/// thread pool when `parallel` is set and the `parallel` feature is enabled.
+///
+/// Each path should draw from its own generator
+/// (see `StochasticProcessConfig::path_rng`).
+pub(crate) fn for_each_path<T, F>(paths: &mut [T], parallel: bool, f: F)
+where
+    T: Send,
+    F: Fn((usize, &mut T)) + Sync + Send,
+{
+    #[cfg(feature = "parallel")]
+    if parallel {
+        paths.par_iter_mut().enumerate().for_each(f);
+        return;
+    }
+
+    #[cfg(not(feature = "parallel"))]
+    let _ = parallel;
+
+    paths.iter_mut().enumerate().for_each(f);
+}
+
+/// Apply `f` to consecutive groups of `group_size` paths, along with the
+/// index of each group, in parallel as for [`for_each_path`].
+pub(crate) fn for_each_path_group<T, F>(paths: &mut [T], group_size: usize, parallel: bool, f: F)
+where
+    T: Send,
+    F: Fn((usize, &mut [T])) + Sync + Send,
+{
+    #[cfg(feature = "parallel")]
+    if parallel {
+        paths.par_chunks_mut(group_size).enumerate().for_each(f);
+        return;
+    }
+
+    #[cfg(not(feature = "parallel"))]
+    let _ = parallel;
+
+    paths.chunks_mut(group_size).enumerate().for_each(f);
+}
+
+/// Collect `f(i)` for `i` in `0..n`, in parallel as for [`for_each_path`].
+pub(crate) fn map_paths<T, F>(n: usize, parallel: bool, f: F) -> Vec<T>
+where
+    T: Send,
+    F: Fn(usize) -> T + Sync + Send,
+{
+    #[cfg(feature = "parallel")]
+    if parallel {
+        return (0..n).into_par_iter().map(f).collect();
+    }
+
+    #[cfg(not(feature = "parallel"))]
+    let _ = parallel;
+
+    (0..n).map(f).collect()
+}
+
+/// One step of `scheme` for the SDE with drift `a`, diffusion `b`,
+/// and diffusion derivative `b_prime` (all functions of `(x, t)`).
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn scheme_step<A, B, D>(
+    scheme: DiscretisationScheme,
+    x: f64,
+    t: f64,
+    dt: f64,
+    dW: f64,
+    a: A,
+    b: B,
+    b_prime: D,
+) -> f64
+where
+    A: Fn(f64, f64) -> f64,
+    B: Fn(f64, f64) -> f64,
+    D: Fn(f64, f64) -> f64,
+{
+    let euler = x + a(x, t) * dt + b(x, t) * dW;
+
+    match scheme {
+        DiscretisationScheme::EulerMaruyama => euler,
+        DiscretisationScheme::Milstein => euler + 0.5 * b(x, t) * b_prime(x, t) * (dW * dW - dt),
+        DiscretisationScheme::PredictorCorrector => {
+            let adjusted_drift = |y: f64, s: f64| a(y, s) - 0.5 * b(y, s) * b_prime(y, s);
+
+            x + 0.5 * (adjusted_drift(euler, t + dt) + adjusted_drift(x, t)) * dt
+                + 0.5 * (b(euler, t + dt) + b(x, t)) * dW
+        }
+    }
+}
+
 /// Configuration parameters for simulating a stochastic process.
 ///
 /// # Arguments:
@@ -110,6 +436,12 @@ pub trait StochasticVolatilityProcess: Sync {
 /// * `n_steps` - The number of time steps between `t_0` and `t_n`.
 /// * `m_paths` - How many process trajectories to simulate.
 /// * `parallel` - Run in parallel or not (recommended for > 1000 paths).
+///
+/// Antithetic sampling is off by default, see `with_antithetic`,
+/// the draws are pseudo-random, see `with_sampling`, the paths are
+/// built step by step, see `with_path_construction`, and the simulation
+/// is not reproducible, see `with_seed`.
+#[derive(Debug, Clone)]
 pub struct StochasticProcessConfig {
     /// Initial value of the process.
     pub x_0: f64,
@@ -128,6 +460,22 @@ pub struct StochasticProcessConfig {
 
     /// Run in parallel or not (recommended for > 1000 paths).
     pub parallel: bool,
+
+    /// Simulate paths in antithetic pairs or not.
+    pub antithetic: bool,
+
+    /// Source of the Gaussian draws.
+    pub sampling: SamplingMethod,
+
+    /// How the Gaussian draws are turned into paths.
+    pub construction: PathConstruction,
+
+    /// Seed for reproducible simulations.
+    pub seed: Option<u64>,
+
+    /// Index of the first simulated path, when the paths are generated in
+    /// batches by `PathBatches`.
+    pub(crate) path_offset: usize,
 }
 
 impl StochasticProcessConfig {
@@ -147,6 +495,177 @@ impl StochasticProcessConfig {
             n_steps,
             m_paths,
             parallel,
+            antithetic: false,
+            sampling: SamplingMethod::PseudoRandom,
+            construction: PathConstruction::Incremental,
+            seed: None,
+            path_offset: 0,
+        }
+    }
+
+    /// Simulate paths in antithetic pairs: every second path reuses the
+    /// Gaussian draws of the path before it, negated.
+    ///
+    /// This reduces the variance of Monte Carlo estimates of smooth
+    /// (monotone) payoffs. Supported by the Gaussian path generators in
+    /// this module; other simulation schemes ignore it.
+    pub fn with_antithetic(mut self, antithetic: bool) -> Self {
+        self.antithetic = antithetic;
+        self
+    }
+
+    /// Choose the source of the Gaussian draws.
+    ///
+    /// `SamplingMethod::Sobol` makes Monte Carlo estimates converge at close
+    /// to `O(1/N)` rather than `O(1/sqrt(N))`, and the stratified methods
+    /// reduce the variance of the estimates. Supported by the Gaussian path
+    /// generators in this module; other simulation schemes ignore it.
+    pub fn with_sampling(mut self, sampling: SamplingMethod) -> Self {
+        self.sampling = sampling;
+        self
+    }
+
+    /// Choose how the Gaussian draws are turned into paths.
+    ///
+    /// Supported by the Gaussian path generators in this module;
+    /// other simulation schemes ignore it.
+    pub fn with_path_construction(mut self, construction: PathConstruction) -> Self {
+        self.construction = construction;
+        self
+    }
+
+    /// Seed the simulation, so it gives the same paths every time.
+    ///
+    /// Every path draws from its own stream, derived from the seed and the
+    /// path's index, so the paths do not depend on `parallel` either.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Generator for the `index`-th path (or group of paths sharing draws).
+    pub(crate) fn path_rng(&self, index: usize) -> SimulationRng {
+        stream_rng(self.seed, self.path_offset + index)
+    }
+
+    /// Number of consecutive paths that share their Gaussian draws.
+    pub(crate) fn paths_per_draw(&self) -> usize {
+        if self.antithetic {
+            2
+        } else {
+            1
+        }
+    }
+
+    /// Brownian increments over steps of length `dt`, as a function of
+    /// the index of the draw (a group of `paths_per_draw` paths).
+    ///
+    /// Quasi-random draws are generated up front, since the Sobol sequence
+    /// is sequential; pseudo-random draws are generated on demand.
+    pub(crate) fn brownian_increments(&self, dt: f64) -> Box<dyn Fn(usize) -> Vec<f64> + Sync> {
+        let (n_steps, seed) = (self.n_steps, self.seed);
+        let draw_offset = self.path_offset / self.paths_per_draw();
+
+        let bridge = match self.construction {
+            PathConstruction::Incremental => None,
+            PathConstruction::BrownianBridge => {
+                let times: Vec<f64> = (1..=n_steps).map(|i| dt * i as f64).collect();
+
+                Some(BrownianBridge::new(&times).expect("Time steps must be positive."))
+            }
+        };
+
+        let to_increments = move |normals: Vec<f64>| match &bridge {
+            Some(bridge) => bridge.increments(&normals),
+            None => normals.into_iter().map(|z| z * dt.sqrt()).collect(),
+        };
+
+        match self.sampling {
+            SamplingMethod::PseudoRandom => Box::new(move |draw| {
+                to_increments(
+                    stream_rng(seed, draw_offset + draw)
+                        .sample_iter(StandardNormal)
+                        .take(n_steps)
+                        .collect(),
+                )
+            }),
+            _ => {
+                let n_draws = self.m_paths.div_ceil(self.paths_per_draw());
+                let increments: Vec<Vec<f64>> = self
+                    .joint_normals(n_draws)
+                    .into_iter()
+                    .map(to_increments)
+                    .collect();
+
+                Box::new(move |draw| increments[draw].clone())
+            }
+        }
+    }
+
+    /// Standard normals for all `n_draws` draws at once, for the sampling
+    /// methods that spread the draws over the unit hypercube jointly.
+    fn joint_normals(&self, n_draws: usize) -> Vec<Vec<f64>> {
+        let n_steps = self.n_steps;
+
+        // Drawn from a stream that no single draw uses.
+        let mut rng = self.path_rng(n_draws);
+
+        match self.sampling {
+            SamplingMethod::PseudoRandom => unreachable!("Pseudo-random draws are made per path."),
+            SamplingMethod::Sobol => {
+                let dimension = n_steps.min(SOBOL_MAX_DIMENSION);
+
+                let mut sobol = SobolSequence::new(dimension).expect("Dimension is in range.");
+
+                // The origin maps to -infinity under the inverse CDF.
+                // Later batches continue the sequence where the last one ended.
+                sobol.skip_points(1 + self.path_offset / self.paths_per_draw());
+
+                sobol
+                    .take(n_draws)
+                    .map(|point| {
+                        let mut normals: Vec<f64> = point.iter().map(|&u| N.inv_cdf(u)).collect();
+                        normals.extend(
+                            (&mut rng)
+                                .sample_iter::<f64, _>(StandardNormal)
+                                .take(n_steps - dimension),
+                        );
+                        normals
+                    })
+                    .collect()
+            }
+            SamplingMethod::Stratified { dimension } => {
+                assert!(
+                    dimension < n_steps,
+                    "The stratified dimension must be less than the number of steps."
+                );
+
+                (0..n_draws)
+                    .map(|k| {
+                        let mut normals: Vec<f64> = (&mut rng)
+                            .sample_iter(StandardNormal)
+                            .take(n_steps)
+                            .collect();
+                        normals[dimension] = stratified_normal(&mut rng, k, n_draws);
+                        normals
+                    })
+                    .collect()
+            }
+            SamplingMethod::LatinHypercube => {
+                let mut normals = vec![vec![0.0; n_steps]; n_draws];
+                let mut strata: Vec<usize> = (0..n_draws).collect();
+
+                // Each dimension visits every stratum once, in its own random order.
+                for d in 0..n_steps {
+                    strata.shuffle(&mut rng);
+
+                    for (z, &k) in normals.iter_mut().zip(&strata) {
+                        z[d] = stratified_normal(&mut rng, k, n_draws);
+                    }
+                }
+
+                normals
+            }
         }
     }
 
@@ -162,6 +681,13 @@ impl StochasticProcessConfig {
     }
 }
 
+/// Standard normal drawn uniformly from the `k`-th of `n` equiprobable strata.
+fn stratified_normal<R: Rng>(rng: &mut R, k: usize, n: usize) -> f64 {
+    let u: f64 = rng.sample(Open01);
+
+    N.inv_cdf((k as f64 + u) / n as f64)
+}
+
 /// Trait to implement stochastic processes.
 #[allow(clippy::module_name_repetitions)]
 pub trait StochasticProcess: Sync {
@@ -179,6 +705,69 @@ pub trait StochasticProcess: Sync {
         vec![]
     }
 
+    /// Derivative of the diffusion with respect to `x`, used by the
+    /// Milstein and predictor-corrector schemes.
+    ///
+    /// Defaults to a central finite difference.
+    /// Override it when the derivative is known in closed form.
+    fn diffusion_derivative(&self, x: f64, t: f64) -> f64 {
+        let h = 1e-6 * (1.0 + x.abs());
+
+        (self.diffusion(x + h, t) - self.diffusion(x - h, t)) / (2.0 * h)
+    }
+
+    /// Simulate the process with a choice of discretisation scheme.
+    ///
+    /// `DiscretisationScheme::EulerMaruyama` is the same as `euler_maruyama`.
+    /// Processes with their own exact or boundary-aware simulation
+    /// (e.g. jump and subordinated processes) ignore the scheme.
+    fn simulate_with_scheme(
+        &self,
+        config: &StochasticProcessConfig,
+        scheme: DiscretisationScheme,
+    ) -> Trajectories {
+        if scheme == DiscretisationScheme::EulerMaruyama {
+            return self.euler_maruyama(config);
+        }
+
+        let (x_0, t_0, t_n, n_steps, m_paths, parallel) = config.unpack();
+        assert!(t_0 < t_n);
+
+        let dt: f64 = (t_n - t_0) / (n_steps as f64);
+
+        // Initialise empty paths and fill in the time points.
+        let mut paths = vec![vec![x_0; n_steps + 1]; m_paths];
+        let times: Vec<f64> = (0..=n_steps).map(|t| t_0 + dt * (t as f64)).collect();
+
+        let increments = config.brownian_increments(dt);
+
+        let path_generator = |(draw, group): (usize, &mut [Vec<f64>])| {
+            let dW = increments(draw);
+
+            // An antithetic partner reuses the draws with the opposite sign.
+            for (path, sign) in group.iter_mut().zip([1.0, -1.0]) {
+                for t in 0..n_steps {
+                    path[t + 1] = scheme_step(
+                        scheme,
+                        path[t],
+                        times[t],
+                        dt,
+                        sign * dW[t],
+                        |x, s| self.drift(x, s),
+                        |x, s| self.diffusion(x, s),
+                        |x, s| self.diffusion_derivative(x, s),
+                    );
+                }
+            }
+        };
+
+        let group_size = config.paths_per_draw();
+
+        for_each_path_group(&mut paths, group_size, parallel, path_generator);
+
+        Trajectories { times, paths }
+    }
+
     /// Euler-Maruyama discretisation scheme.
     ///
     /// # Arguments:
@@ -198,32 +787,58 @@ pub trait StochasticProcess: Sync {
         let mut paths = vec![vec![x_0; n_steps + 1]; m_paths];
         let times: Vec<f64> = (0..=n_steps).map(|t| t_0 + dt * (t as f64)).collect();
 
-        let path_generator = |path: &mut Vec<f64>| {
-            let mut rng = rand::thread_rng();
-            let scale = dt.sqrt();
-            let dW: Vec<f64> = rand_distr::Normal::new(0.0, 1.0)
-                .unwrap()
-                .sample_iter(&mut rng)
-                .take(n_steps)
-                .map(|z| z * scale)
-                .collect();
+        let increments = config.brownian_increments(dt);
 
-            for t in 0..n_steps {
-                path[t + 1] = path[t]
-                    + self.drift(path[t], times[t]) * dt
-                    + self.diffusion(path[t], times[t]) * dW[t];
+        let path_generator = |(draw, group): (usize, &mut [Vec<f64>])| {
+            let dW = increments(draw);
+
+            // An antithetic partner reuses the draws with the opposite sign.
+            for (path, sign) in group.iter_mut().zip([1.0, -1.0]) {
+                for t in 0..n_steps {
+                    path[t + 1] = path[t]
+                        + self.drift(path[t], times[t]) * dt
+                        + self.diffusion(path[t], times[t]) * sign * dW[t];
+                }
             }
         };
 
-        if parallel {
-            paths.par_iter_mut().for_each(path_generator);
-        } else {
-            paths.iter_mut().for_each(path_generator);
-        }
+        let group_size = config.paths_per_draw();
+
+        for_each_path_group(&mut paths, group_size, parallel, path_generator);
 
         Trajectories { times, paths }
     }
 
+    /// Simulate the paths of `config` lazily, `batch_size` paths at a time,
+    /// so only one batch is held in memory at once.
+    ///
+    /// With pseudo-random or Sobol draws, the batches put together are the
+    /// same paths as `euler_maruyama` gives for a seeded `config`.
+    /// The stratified sampling methods stratify each batch on its own.
+    ///
+    /// # Panics
+    /// Panics if `batch_size` is zero, or odd when `config` is antithetic.
+    fn path_batches<'a>(
+        &'a self,
+        config: &'a StochasticProcessConfig,
+        batch_size: usize,
+    ) -> PathBatches<'a, Self>
+    where
+        Self: Sized,
+    {
+        assert!(
+            batch_size > 0 && batch_size.is_multiple_of(config.paths_per_draw()),
+            "The batch size must be a positive multiple of the paths per draw."
+        );
+
+        PathBatches {
+            process: self,
+            config,
+            batch_size,
+            next_path: 0,
+        }
+    }
+
     /// Euler-Maruyama discretisation scheme with a choice of random seed.
     ///
     /// # Arguments:
@@ -234,6 +849,8 @@ pub trait StochasticProcess: Sync {
     /// * `m_paths` - How many process trajectories to simulate.
     /// * `parallel` - Run in parallel or not (recommended for > 1000 paths).
     /// * `seed` - The seed for the random number generator.
+    ///
+    /// Same as `euler_maruyama` with `StochasticProcessConfig::with_seed`.
     fn seedable_euler_maruyama(
         &self,
         x_0: f64,
@@ -244,38 +861,134 @@ pub trait StochasticProcess: Sync {
         parallel: bool,
         seed: u64,
     ) -> Trajectories {
+        let config =
+            StochasticProcessConfig::new(x_0, t_0, t_n, n_steps, m_paths, parallel).with_seed(seed);
+
+        self.euler_maruyama(&config)
+    }
+}
+
+/// Several stochastic processes driven by correlated Brownian motions,
+/// with $d\langle W_i, W_j \rangle = \rho_{ij} dt$.
+///
+/// Useful for basket and rainbow option Monte Carlo.
+pub struct MultiAssetProcess {
+    /// The process followed by each asset.
+    processes: Vec<Box<dyn StochasticProcess>>,
+
+    /// Lower Cholesky factor of the correlation matrix.
+    cholesky: DMatrix<f64>,
+}
+
+impl MultiAssetProcess {
+    /// Create a new set of correlated processes.
+    ///
+    /// # Errors
+    /// - `RustQuantError::UnequalLength` if `processes` and `correlation`
+    ///   have different dimensions.
+    /// - `RustQuantError::InvalidArgument` if `correlation` is not a valid
+    ///   correlation matrix.
+    pub fn new(
+        processes: Vec<Box<dyn StochasticProcess>>,
+        correlation: &[Vec<f64>],
+    ) -> Result<Self, RustQuantError> {
+        if processes.len() != correlation.len() {
+            return Err(RustQuantError::UnequalLength);
+        }
+
+        Ok(Self {
+            processes,
+            cholesky: correlation_cholesky(correlation)?,
+        })
+    }
+
+    /// Number of assets.
+    pub fn dimension(&self) -> usize {
+        self.processes.len()
+    }
+
+    /// Euler-Maruyama discretisation of all the assets at once, using each
+    /// process' drift and diffusion with correlated Brownian increments.
+    ///
+    /// The `x_0` in `config` is ignored in favour of `initial`, which holds
+    /// the starting value of each asset.
+    /// Returns one `Trajectories` per asset, in the order of the processes.
+    ///
+    /// # Panics
+    /// Panics if `initial.len() != self.dimension()`.
+    pub fn euler_maruyama(
+        &self,
+        initial: &[f64],
+        config: &StochasticProcessConfig,
+    ) -> Vec<Trajectories> {
+        let (_, t_0, t_n, n_steps, m_paths, parallel) = config.unpack();
+
         assert!(t_0 < t_n);
+        assert_eq!(initial.len(), self.dimension());
 
+        let dimension = self.dimension();
         let dt: f64 = (t_n - t_0) / (n_steps as f64);
-
-        // Initialise empty paths and fill in the time points.
-        let mut paths = vec![vec![x_0; n_steps + 1]; m_paths];
         let times: Vec<f64> = (0..=n_steps).map(|t| t_0 + dt * (t as f64)).collect();
 
-        let path_generator = |path: &mut Vec<f64>| {
-            let mut rng = StdRng::seed_from_u64(seed);
+        // Each simulation holds one path per asset, for each path sharing the draws.
+        let group_size = config.paths_per_draw();
+        let simulation = |group: usize| {
+            let mut rng = config.path_rng(group);
             let scale = dt.sqrt();
-            let dW: Vec<f64> = rand_distr::Normal::new(0.0, 1.0)
-                .unwrap()
-                .sample_iter(&mut rng)
-                .take(n_steps)
-                .map(|z| z * scale)
+
+            let dW: Vec<DVector<f64>> = (0..n_steps)
+                .map(|_| {
+                    let z = DVector::from_fn(dimension, |_, _| rng.sample(StandardNormal));
+                    &self.cholesky * z * scale
+                })
                 .collect();
 
-            for t in 0..n_steps {
-                path[t + 1] = path[t]
-                    + self.drift(path[t], times[t]) * dt
-                    + self.diffusion(path[t], times[t]) * dW[t];
-            }
+            let members = group_size.min(m_paths - group * group_size);
+
+            // An antithetic partner reuses the draws with the opposite sign.
+            [1.0, -1.0]
+                .into_iter()
+                .take(members)
+                .map(|sign| {
+                    let mut paths: Vec<Vec<f64>> =
+                        initial.iter().map(|&x| vec![x; n_steps + 1]).collect();
+
+                    for t in 0..n_steps {
+                        for (i, (process, path)) in
+                            self.processes.iter().zip(&mut paths).enumerate()
+                        {
+                            path[t + 1] = path[t]
+                                + process.drift(path[t], times[t]) * dt
+                                + process.diffusion(path[t], times[t]) * sign * dW[t][i];
+                        }
+                    }
+
+                    paths
+                })
+                .collect::<Vec<_>>()
         };
 
-        if parallel {
-            paths.par_iter_mut().for_each(path_generator);
-        } else {
-            paths.iter_mut().for_each(path_generator);
+        let n_groups = m_paths.div_ceil(group_size);
+        let simulations: Vec<Vec<Vec<f64>>> = map_paths(n_groups, parallel, simulation)
+            .into_iter()
+            .flatten()
+            .collect();
+
+        // Regroup the paths by asset.
+        let mut assets: Vec<Vec<Vec<f64>>> = vec![Vec::with_capacity(m_paths); dimension];
+        for paths in simulations {
+            for (asset, path) in assets.iter_mut().zip(paths) {
+                asset.push(path);
+            }
         }
 
-        Trajectories { times, paths }
+        assets
+            .into_iter()
+            .map(|paths| Trajectories {
+                times: times.clone(),
+                paths,
+            })
+            .collect()
     }
 }
 
@@ -283,8 +996,21 @@ pub trait StochasticProcess: Sync {
 mod test_process {
     use crate::geometric_brownian_motion::GeometricBrownianMotion;
     use crate::process::StochasticProcess;
+    use crate::process::{
+        scheme_step, DiscretisationScheme, MultiAssetProcess, PathConstruction, SamplingMethod,
+    };
+    use crate::BrownianMotion;
+    use crate::ConstantElasticityOfVariance;
+    use crate::CoxIngersollRoss;
+    use crate::Heston;
+    use crate::MertonJumpDiffusion;
+    use crate::OrnsteinUhlenbeck;
+    use crate::PoissonProcess;
     use crate::StochasticProcessConfig;
+    use crate::Trajectories;
     use std::time::Instant;
+    use RustQuant_math::{gaussian::N, Distribution};
+    use RustQuant_utils::{assert_approx_equal, RUSTQUANT_EPSILON};
 
     #[test]
     fn test_euler_maruyama() {
@@ -333,4 +1059,537 @@ mod test_process {
         // To see the output of this "test", run:
         // cargo test test_process -- --nocapture
     }
+
+    #[test]
+    fn test_resample() {
+        let bm = BrownianMotion::new();
+        let config = StochasticProcessConfig::new(0.0, 0.0, 1.0, 100, 5, false);
+        let output = bm.euler_maruyama(&config);
+
+        let new_times = [0.0, 0.25, 0.5, 0.75, 1.0];
+        let resampled = output.resample(&new_times).unwrap();
+
+        assert_eq!(resampled.times, new_times);
+        assert_eq!(resampled.paths.len(), 5);
+
+        for (path, original) in resampled.paths.iter().zip(&output.paths) {
+            assert_eq!(path.len(), 5);
+
+            for (value, i) in path.iter().zip([0, 25, 50, 75, 100]) {
+                assert_approx_equal!(*value, original[i], RUSTQUANT_EPSILON);
+            }
+        }
+
+        assert!(output.resample(&[0.5, 1.5]).is_err());
+    }
+
+    #[test]
+    fn test_thin() {
+        let bm = BrownianMotion::new();
+        let config = StochasticProcessConfig::new(0.0, 0.0, 1.0, 100, 3, false);
+        let output = bm.euler_maruyama(&config);
+
+        let thinned = output.thin(10);
+
+        assert_eq!(thinned.times.len(), 11);
+        assert_eq!(thinned.paths[2][5], output.paths[2][50]);
+        assert_eq!(thinned.times[10], output.times[100]);
+    }
+
+    #[test]
+    fn test_path_statistics() {
+        let output = Trajectories {
+            times: vec![0.0, 0.5, 1.0],
+            paths: vec![
+                vec![1.0, 2.0, 4.0],
+                vec![1.0, 0.0, 1.0],
+                vec![1.0, 1.0, 2.5],
+                vec![1.0, 3.0, 0.5],
+            ],
+        };
+
+        assert_eq!(output.values_at(1), vec![2.0, 0.0, 1.0, 3.0]);
+        assert_eq!(output.terminal_values(), vec![4.0, 1.0, 2.5, 0.5]);
+        assert_eq!(output.mean_path(), vec![1.0, 1.5, 2.0]);
+        assert_eq!(output.variance_path(), vec![0.0, 5.0 / 3.0, 2.5]);
+        assert_eq!(output.quantile_path(0.5), vec![1.0, 1.5, 1.75]);
+        assert_eq!(output.quantile_path(1.0), vec![1.0, 3.0, 4.0]);
+
+        let histogram = output.terminal_histogram(7);
+        assert_eq!(histogram.edges.len(), 8);
+        assert_eq!(histogram.edges[0], 0.5);
+        assert_eq!(histogram.edges[7], 4.0);
+        assert_eq!(histogram.counts, vec![1, 1, 0, 0, 1, 0, 1]);
+    }
+
+    #[test]
+    fn test_path_batches() {
+        let gbm = GeometricBrownianMotion::new(0.05, 0.2);
+
+        for config in [
+            StochasticProcessConfig::new(100.0, 0.0, 1.0, 12, 25, true).with_seed(3),
+            StochasticProcessConfig::new(100.0, 0.0, 1.0, 12, 26, false)
+                .with_seed(3)
+                .with_antithetic(true),
+            StochasticProcessConfig::new(100.0, 0.0, 1.0, 12, 25, false)
+                .with_sampling(SamplingMethod::Sobol),
+        ] {
+            let full = gbm.euler_maruyama(&config);
+            let batches: Vec<Trajectories> = gbm.path_batches(&config, 8).collect();
+
+            assert_eq!(batches.len(), 4);
+            assert_eq!(batches[3].paths.len(), config.m_paths - 24);
+            assert!(batches.iter().all(|batch| batch.times == full.times));
+
+            let streamed: Vec<Vec<f64>> = batches.into_iter().flat_map(|b| b.paths).collect();
+            assert_eq!(streamed, full.paths);
+        }
+
+        // Custom simulators draw their paths from the same streams.
+        let heston = Heston::new(0.05, 0.04, 0.04, 1.5, -0.7, 0.3);
+        let config = StochasticProcessConfig::new(100.0, 0.0, 1.0, 12, 10, false).with_seed(4);
+        let streamed: Vec<Vec<f64>> = heston
+            .path_batches(&config, 3)
+            .flat_map(|b| b.paths)
+            .collect();
+        assert_eq!(streamed, heston.euler_maruyama(&config).paths);
+    }
+
+    #[test]
+    fn test_multi_asset_correlated_gbm() {
+        let (rho, t) = (-0.5, 1.0_f64);
+        let assets = MultiAssetProcess::new(
+            vec![
+                Box::new(GeometricBrownianMotion::new(0.05, 0.2)),
+                Box::new(GeometricBrownianMotion::new(0.02, 0.3)),
+            ],
+            &[vec![1.0, rho], vec![rho, 1.0]],
+        )
+        .unwrap();
+
+        let config = StochasticProcessConfig::new(0.0, 0.0, t, 100, 20_000, true);
+        let output = assets.euler_maruyama(&[100.0, 50.0], &config);
+
+        assert_eq!(output.len(), 2);
+
+        let returns: Vec<Vec<f64>> = output
+            .iter()
+            .map(|asset| asset.paths.iter().map(|p| (p[100] / p[0]).ln()).collect())
+            .collect();
+
+        // Each asset keeps its own drift.
+        let means: Vec<f64> = output
+            .iter()
+            .map(|asset| asset.paths.iter().map(|p| p[100]).sum::<f64>() / 20_000.0)
+            .collect();
+        assert_approx_equal!(means[0] / (100.0 * (0.05 * t).exp()), 1.0, 0.01);
+        assert_approx_equal!(means[1] / (50.0 * (0.02 * t).exp()), 1.0, 0.01);
+
+        // And the log-returns have correlation rho.
+        let mean = |x: &[f64]| x.iter().sum::<f64>() / x.len() as f64;
+        let (m0, m1) = (mean(&returns[0]), mean(&returns[1]));
+        let covariance = returns[0]
+            .iter()
+            .zip(&returns[1])
+            .map(|(x, y)| (x - m0) * (y - m1))
+            .sum::<f64>()
+            / 20_000.0;
+
+        assert_approx_equal!(covariance / (0.2 * 0.3 * t), rho, 0.03);
+    }
+
+    #[test]
+    fn test_multi_asset_mixed_processes() {
+        // Any processes can be combined, each with its own initial value.
+        let assets = MultiAssetProcess::new(
+            vec![
+                Box::new(BrownianMotion::new()),
+                Box::new(OrnsteinUhlenbeck::new(0.05, 0.1, 0.5)),
+            ],
+            &[vec![1.0, 0.3], vec![0.3, 1.0]],
+        )
+        .unwrap();
+
+        let config = StochasticProcessConfig::new(0.0, 0.0, 1.0, 10, 5, false);
+        let output = assets.euler_maruyama(&[0.0, 0.03], &config);
+
+        assert_eq!(assets.dimension(), 2);
+        for asset in &output {
+            assert_eq!(asset.times.len(), 11);
+            assert_eq!(asset.paths.len(), 5);
+            assert!(asset.paths.iter().all(|p| p.len() == 11));
+        }
+        assert!(output[1].paths.iter().all(|p| p[0] == 0.03));
+    }
+
+    #[test]
+    fn test_multi_asset_dimension_mismatch() {
+        let assets = MultiAssetProcess::new(
+            vec![Box::new(BrownianMotion::new())],
+            &[vec![1.0, 0.0], vec![0.0, 1.0]],
+        );
+
+        assert!(assets.is_err());
+    }
+
+    #[test]
+    fn test_milstein_strong_convergence() {
+        // GBM has the exact solution S_0 exp((mu - sigma^2 / 2) T + sigma W_T),
+        // so drive each scheme with the same Brownian increments and compare.
+        let (mu, sigma, s_0, t) = (0.05, 0.5, 1.0, 1.0_f64);
+        let gbm = GeometricBrownianMotion::new(mu, sigma);
+
+        let n_steps = 50;
+        let dt = t / n_steps as f64;
+        let mut rng = crate::seeded_rng(42);
+
+        let mut errors = [0.0; 3];
+        let schemes = [
+            DiscretisationScheme::EulerMaruyama,
+            DiscretisationScheme::Milstein,
+            DiscretisationScheme::PredictorCorrector,
+        ];
+
+        for _ in 0..2000 {
+            let dW: Vec<f64> = (0..n_steps)
+                .map(|_| {
+                    rand::Rng::sample::<f64, _>(&mut rng, rand_distr::StandardNormal) * dt.sqrt()
+                })
+                .collect();
+            let exact =
+                s_0 * ((mu - 0.5 * sigma * sigma) * t + sigma * dW.iter().sum::<f64>()).exp();
+
+            for (scheme, error) in schemes.iter().zip(&mut errors) {
+                let mut x = s_0;
+                for (n, dw) in dW.iter().enumerate() {
+                    x = scheme_step(
+                        *scheme,
+                        x,
+                        n as f64 * dt,
+                        dt,
+                        *dw,
+                        |x, s| gbm.drift(x, s),
+                        |x, s| gbm.diffusion(x, s),
+                        |x, s| gbm.diffusion_derivative(x, s),
+                    );
+                }
+                *error += (x - exact).abs() / 2000.0;
+            }
+        }
+
+        // Milstein has strong order 1, against 1/2 for Euler.
+        assert!(errors[1] < 0.2 * errors[0]);
+        assert!(errors[2] < errors[0]);
+    }
+
+    #[test]
+    fn test_finite_difference_diffusion_derivative() {
+        let gbm = GeometricBrownianMotion::new(0.05, 0.3);
+        let bm = BrownianMotion::new();
+
+        assert_approx_equal!(gbm.diffusion_derivative(100.0, 0.0), 0.3, 1e-6);
+        assert_approx_equal!(bm.diffusion_derivative(1.0, 0.0), 0.0, RUSTQUANT_EPSILON);
+    }
+
+    #[test]
+    fn test_cir_schemes() {
+        let (mu, sigma, theta, x_0, t) = (0.04, 0.2, 1.5, 0.03, 2.0_f64);
+        let cir = CoxIngersollRoss::new(mu, sigma, theta);
+
+        // A coarse grid, close to the boundary.
+        let config = StochasticProcessConfig::new(x_0, 0.0, t, 20, 20_000, true);
+
+        for scheme in [
+            DiscretisationScheme::Milstein,
+            DiscretisationScheme::PredictorCorrector,
+        ] {
+            let output = cir.simulate_with_scheme(&config, scheme);
+            let X_T: Vec<f64> = output.paths.iter().map(|p| p[20]).collect();
+
+            assert!(X_T.iter().all(|x| x.is_finite()));
+
+            let mean = X_T.iter().sum::<f64>() / X_T.len() as f64;
+            assert_approx_equal!(mean, mu + (x_0 - mu) * (-theta * t).exp(), 0.002);
+        }
+    }
+
+    #[test]
+    fn test_antithetic_pairs() {
+        // Brownian motion makes the pairing visible: partners mirror each other.
+        let bm = BrownianMotion::new();
+        let config =
+            StochasticProcessConfig::new(0.0, 0.0, 1.0, 50, 5, false).with_antithetic(true);
+
+        let output = bm.euler_maruyama(&config);
+        assert_eq!(output.paths.len(), 5);
+
+        for pair in output.paths.chunks(2).filter(|pair| pair.len() == 2) {
+            for (x, y) in pair[0].iter().zip(&pair[1]) {
+                assert_approx_equal!(*x, -*y, RUSTQUANT_EPSILON);
+            }
+        }
+    }
+
+    #[test]
+    fn test_antithetic_variance_reduction() {
+        let gbm = GeometricBrownianMotion::new(0.05, 0.2);
+        let n_paths = 10_000;
+
+        // Variance of the mean of S_T, estimated from 20 independent runs.
+        let estimator_variance = |antithetic: bool| {
+            let means: Vec<f64> = (0..20)
+                .map(|_| {
+                    let config = StochasticProcessConfig::new(100.0, 0.0, 1.0, 10, n_paths, true)
+                        .with_antithetic(antithetic);
+                    let output = gbm.simulate_with_scheme(&config, DiscretisationScheme::Milstein);
+                    output.paths.iter().map(|p| p[10]).sum::<f64>() / n_paths as f64
+                })
+                .collect();
+
+            let mean = means.iter().sum::<f64>() / 20.0;
+            means.iter().map(|m| (m - mean).powi(2)).sum::<f64>() / 19.0
+        };
+
+        assert!(estimator_variance(true) < 0.2 * estimator_variance(false));
+    }
+
+    #[test]
+    fn test_multi_asset_antithetic() {
+        let assets = MultiAssetProcess::new(
+            vec![
+                Box::new(BrownianMotion::new()),
+                Box::new(BrownianMotion::new()),
+            ],
+            &[vec![1.0, 0.5], vec![0.5, 1.0]],
+        )
+        .unwrap();
+
+        let config =
+            StochasticProcessConfig::new(0.0, 0.0, 1.0, 20, 7, false).with_antithetic(true);
+        let output = assets.euler_maruyama(&[0.0, 0.0], &config);
+
+        for asset in &output {
+            assert_eq!(asset.paths.len(), 7);
+            for pair in asset.paths.chunks(2).filter(|pair| pair.len() == 2) {
+                assert_approx_equal!(pair[0][20], -pair[1][20], RUSTQUANT_EPSILON);
+            }
+        }
+    }
+
+    #[test]
+    fn test_sobol_sampling_terminal_distribution() {
+        let bm = BrownianMotion::new();
+        let config = StochasticProcessConfig::new(0.0, 0.0, 1.0, 10, 1023, false)
+            .with_sampling(SamplingMethod::Sobol)
+            .with_path_construction(PathConstruction::BrownianBridge);
+
+        let output = bm.euler_maruyama(&config);
+        let terminal: Vec<f64> = output.paths.iter().map(|p| p[10]).collect();
+
+        let mean = terminal.iter().sum::<f64>() / 1023.0;
+        let variance = terminal.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / 1022.0;
+
+        // Pseudo-random draws would give a standard error of about 0.03 on the mean.
+        assert!(mean.abs() < 1e-3);
+        assert_approx_equal!(variance, 1.0, 0.02);
+    }
+
+    #[test]
+    fn test_sobol_sampling_is_deterministic() {
+        let gbm = GeometricBrownianMotion::new(0.05, 0.2);
+        let config = StochasticProcessConfig::new(100.0, 0.0, 1.0, 12, 64, true)
+            .with_sampling(SamplingMethod::Sobol)
+            .with_antithetic(true);
+
+        let first = gbm.simulate_with_scheme(&config, DiscretisationScheme::Milstein);
+        let second = gbm.simulate_with_scheme(&config, DiscretisationScheme::Milstein);
+
+        assert_eq!(first.paths, second.paths);
+    }
+
+    #[test]
+    fn test_sobol_sampling_beyond_max_dimension() {
+        // Steps past the Sobol dimension are padded with pseudo-random draws.
+        let gbm = GeometricBrownianMotion::new(0.05, 0.2);
+        let config = StochasticProcessConfig::new(100.0, 0.0, 1.0, 100, 4095, true)
+            .with_sampling(SamplingMethod::Sobol)
+            .with_path_construction(PathConstruction::BrownianBridge);
+
+        let output = gbm.euler_maruyama(&config);
+        let mean = output.paths.iter().map(|p| p[100]).sum::<f64>() / 4095.0;
+
+        assert_eq!(output.paths.len(), 4095);
+        assert_approx_equal!(mean, 100.0 * 0.05_f64.exp(), 0.1);
+    }
+
+    #[test]
+    fn test_brownian_bridge_construction_distribution() {
+        // Cov(W(s), W(t)) = min(s, t) whichever way the path is built.
+        let bm = BrownianMotion::new();
+        let n_paths = 20_000;
+
+        for sampling in [SamplingMethod::PseudoRandom, SamplingMethod::Sobol] {
+            let config = StochasticProcessConfig::new(0.0, 0.0, 1.0, 8, n_paths, true)
+                .with_sampling(sampling)
+                .with_path_construction(PathConstruction::BrownianBridge);
+
+            let output = bm.euler_maruyama(&config);
+
+            for (a, b) in [(2, 8), (4, 4), (8, 8)] {
+                let covariance =
+                    output.paths.iter().map(|p| p[a] * p[b]).sum::<f64>() / n_paths as f64;
+
+                assert_approx_equal!(covariance, output.times[a].min(output.times[b]), 0.05);
+            }
+        }
+    }
+
+    #[test]
+    fn test_brownian_bridge_construction_with_sobol() {
+        // With a bridge, the first Sobol coordinate alone sets W(T),
+        // so the terminal values are the normal quantiles j / 1024.
+        let bm = BrownianMotion::new();
+        let config = StochasticProcessConfig::new(0.0, 0.0, 1.0, 16, 1023, false)
+            .with_sampling(SamplingMethod::Sobol)
+            .with_path_construction(PathConstruction::BrownianBridge);
+
+        let output = bm.euler_maruyama(&config);
+        let mut terminal: Vec<f64> = output.paths.iter().map(|p| p[16]).collect();
+        terminal.sort_by(f64::total_cmp);
+
+        for (j, w) in terminal.iter().enumerate() {
+            assert_approx_equal!(*w, N.inv_cdf((j + 1) as f64 / 1024.0), 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_parallel_and_serial_paths_agree() {
+        // Quasi-random draws are fixed up front, so threading cannot change them.
+        let gbm = GeometricBrownianMotion::new(0.05, 0.2);
+        let simulate = |parallel| {
+            let config = StochasticProcessConfig::new(100.0, 0.0, 1.0, 16, 1000, parallel)
+                .with_sampling(SamplingMethod::Sobol)
+                .with_antithetic(true);
+
+            gbm.euler_maruyama(&config).paths
+        };
+
+        assert_eq!(simulate(true), simulate(false));
+    }
+
+    #[test]
+    fn test_seeded_simulations_are_reproducible() {
+        let processes: Vec<Box<dyn StochasticProcess>> = vec![
+            Box::new(GeometricBrownianMotion::new(0.05, 0.2)),
+            Box::new(ConstantElasticityOfVariance::new(0.05, 0.4, 0.5)),
+            Box::new(MertonJumpDiffusion::new(0.05, 0.2, 1.0, 0.0, 0.1)),
+            Box::new(Heston::new(0.05, 0.04, 0.04, 2.0, -0.7, 0.3)),
+            Box::new(PoissonProcess::new(3.0)),
+        ];
+
+        for process in &processes {
+            let simulate = |seed, parallel| {
+                let config =
+                    StochasticProcessConfig::new(1.0, 0.0, 1.0, 20, 50, parallel).with_seed(seed);
+
+                process.euler_maruyama(&config).paths
+            };
+
+            let paths = simulate(42, true);
+
+            // Same seed, same paths, however they are spread over threads.
+            assert_eq!(paths, simulate(42, true));
+            assert_eq!(paths, simulate(42, false));
+            assert_ne!(paths, simulate(43, true));
+
+            // Every path draws from its own stream.
+            assert_ne!(paths[0], paths[1]);
+        }
+    }
+
+    #[test]
+    fn test_stratified_sampling_strata() {
+        let bm = BrownianMotion::new();
+        let n_paths = 200;
+
+        // The k-th path's stratified variate lies in the k-th stratum.
+        for construction in [
+            PathConstruction::Incremental,
+            PathConstruction::BrownianBridge,
+        ] {
+            let config = StochasticProcessConfig::new(0.0, 0.0, 1.0, 4, n_paths, false)
+                .with_sampling(SamplingMethod::Stratified { dimension: 0 })
+                .with_path_construction(construction);
+
+            let output = bm.euler_maruyama(&config);
+
+            for (k, path) in output.paths.iter().enumerate() {
+                // W(0.25) / 0.5 or W(1) is the first variate.
+                let z = match construction {
+                    PathConstruction::Incremental => path[1] / 0.5,
+                    PathConstruction::BrownianBridge => path[4],
+                };
+                let u = N.cdf(z) * n_paths as f64;
+
+                assert!(u >= k as f64 - 1e-9 && u <= (k + 1) as f64 + 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn test_latin_hypercube_strata() {
+        let bm = BrownianMotion::new();
+        let n_paths = 100;
+        let config = StochasticProcessConfig::new(0.0, 0.0, 4.0, 4, n_paths, false)
+            .with_sampling(SamplingMethod::LatinHypercube)
+            .with_seed(1);
+
+        let output = bm.euler_maruyama(&config);
+
+        // Every increment visits each of the strata exactly once.
+        for t in 0..4 {
+            let mut seen = vec![false; n_paths];
+
+            for path in &output.paths {
+                let k = (N.cdf(path[t + 1] - path[t]) * n_paths as f64) as usize;
+                assert!(!seen[k]);
+                seen[k] = true;
+            }
+        }
+    }
+
+    #[test]
+    fn test_stratified_sampling_variance_reduction() {
+        let gbm = GeometricBrownianMotion::new(0.05, 0.2);
+        let n_paths = 1000;
+
+        // Variance of the mean of max(S_T - 100, 0), estimated from 30 independent runs.
+        let estimator_variance = |sampling| {
+            let means: Vec<f64> = (0..30)
+                .map(|_| {
+                    let config = StochasticProcessConfig::new(100.0, 0.0, 1.0, 8, n_paths, true)
+                        .with_sampling(sampling)
+                        .with_path_construction(PathConstruction::BrownianBridge);
+                    let output = gbm.euler_maruyama(&config);
+
+                    output
+                        .paths
+                        .iter()
+                        .map(|p| (p[8] - 100.0).max(0.0))
+                        .sum::<f64>()
+                        / n_paths as f64
+                })
+                .collect();
+
+            let mean = means.iter().sum::<f64>() / 30.0;
+            means.iter().map(|m| (m - mean).powi(2)).sum::<f64>() / 29.0
+        };
+
+        let pseudo_random = estimator_variance(SamplingMethod::PseudoRandom);
+
+        assert!(
+            estimator_variance(SamplingMethod::Stratified { dimension: 0 }) < 0.2 * pseudo_random
+        );
+        assert!(estimator_variance(SamplingMethod::LatinHypercube) < 0.2 * pseudo_random);
+    }
 }
@@ -0,0 +1,118 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Random number generators used to simulate the stochastic processes.
+//!
+//! By default, unseeded simulations draw from `rand::thread_rng()` and
+//! seeded simulations use `StdRng`.
+//!
+//! With the `wasm` feature enabled, both are replaced by `SmallRng`,
+//! since thread-local generators are not available on `wasm32-unknown-unknown`.
+//! Unseeded generators are then seeded from the platform's entropy source
+//! (`crypto.getRandomValues` in the browser), so every call, and every page
+//! load, produces a different stream.
+//!
+//! Seeded path simulations (see `StochasticProcessConfig::with_seed`)
+//! give each path its own stream, derived from the seed and the path's index.
+
+use rand::{Rng, SeedableRng};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TYPES
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Seedable generator used by the `seedable_*` simulation methods.
+#[cfg(not(feature = "wasm"))]
+pub type SimulationRng = rand::rngs::StdRng;
+
+/// Seedable generator used by the `seedable_*` simulation methods.
+#[cfg(feature = "wasm")]
+pub type SimulationRng = rand::rngs::SmallRng;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Seeded generator for reproducible simulations.
+pub fn seeded_rng(seed: u64) -> SimulationRng {
+    SimulationRng::seed_from_u64(seed)
+}
+
+/// Unseeded generator for the default simulation methods.
+#[cfg(not(feature = "wasm"))]
+pub fn simulation_rng() -> impl Rng {
+    rand::thread_rng()
+}
+
+/// Unseeded generator for the default simulation methods.
+#[cfg(feature = "wasm")]
+pub fn simulation_rng() -> impl Rng {
+    SimulationRng::from_entropy()
+}
+
+/// Generator for the `index`-th stream of a simulation.
+///
+/// With a seed, every stream is reproducible and independent of the others
+/// (and of how the streams are spread over threads). Without one, the
+/// stream is seeded from [`simulation_rng`].
+pub(crate) fn stream_rng(seed: Option<u64>, index: usize) -> SimulationRng {
+    match seed {
+        Some(seed) => seeded_rng(split_mix(seed ^ split_mix(index as u64))),
+        None => seeded_rng(simulation_rng().gen()),
+    }
+}
+
+/// SplitMix64 finaliser, to decorrelate nearby seeds.
+fn split_mix(x: u64) -> u64 {
+    let mut z = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_random {
+    use super::*;
+
+    #[test]
+    fn test_seeded_rng_is_reproducible() {
+        let a: Vec<f64> = seeded_rng(42)
+            .sample_iter(rand_distr::StandardNormal)
+            .take(10)
+            .collect();
+        let b: Vec<f64> = seeded_rng(42)
+            .sample_iter(rand_distr::StandardNormal)
+            .take(10)
+            .collect();
+
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_simulation_rng_streams_differ() {
+        let a: f64 = simulation_rng().gen();
+        let b: f64 = simulation_rng().gen();
+
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn test_stream_rng() {
+        let draw = |seed, index| -> f64 { stream_rng(seed, index).gen() };
+
+        assert_eq!(draw(Some(7), 3), draw(Some(7), 3));
+        assert_ne!(draw(Some(7), 3), draw(Some(7), 4));
+        assert_ne!(draw(Some(7), 3), draw(Some(8), 3));
+        assert_ne!(draw(None, 3), draw(None, 3));
+    }
+}
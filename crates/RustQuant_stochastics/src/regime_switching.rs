@@ -0,0 +1,306 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::process::{for_each_path, StochasticProcess, StochasticProcessConfig, Trajectories};
+use rand::Rng;
+use rand_distr::StandardNormal;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS AND ENUMS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// The regimes of a `RegimeSwitching` process.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Regime {
+    /// The regime of the first process.
+    #[default]
+    First,
+
+    /// The regime of the second process.
+    Second,
+}
+
+/// Process that follows one of two processes at a time, with the regime
+/// driven by a two-state continuous-time Markov chain.
+///
+/// E.g. a GBM that alternates between low and high volatility regimes.
+pub struct RegimeSwitching<P1, P2> {
+    /// The process followed in the first regime.
+    pub first: P1,
+
+    /// The process followed in the second regime.
+    pub second: P2,
+
+    /// Rate of switching from the first regime to the second.
+    pub first_to_second_rate: f64,
+
+    /// Rate of switching from the second regime to the first.
+    pub second_to_first_rate: f64,
+
+    /// The regime at the initial time point.
+    pub initial_regime: Regime,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl Regime {
+    /// The other regime.
+    pub fn other(self) -> Self {
+        match self {
+            Regime::First => Regime::Second,
+            Regime::Second => Regime::First,
+        }
+    }
+}
+
+impl<P1: StochasticProcess, P2: StochasticProcess> RegimeSwitching<P1, P2> {
+    /// Create a new regime switching process, starting in the first regime.
+    ///
+    /// # Panics
+    /// Panics if either switching rate is negative.
+    pub fn new(
+        first: P1,
+        second: P2,
+        first_to_second_rate: f64,
+        second_to_first_rate: f64,
+    ) -> Self {
+        assert!(
+            first_to_second_rate >= 0.0 && second_to_first_rate >= 0.0,
+            "The switching rates must be non-negative."
+        );
+
+        Self {
+            first,
+            second,
+            first_to_second_rate,
+            second_to_first_rate,
+            initial_regime: Regime::First,
+        }
+    }
+
+    /// Start in `regime` instead of the first regime.
+    pub fn with_initial_regime(mut self, regime: Regime) -> Self {
+        self.initial_regime = regime;
+        self
+    }
+
+    /// Long-run probability of being in the second regime.
+    pub fn stationary_probability(&self) -> f64 {
+        let total = self.first_to_second_rate + self.second_to_first_rate;
+
+        if total == 0.0 {
+            return 0.0;
+        }
+
+        self.first_to_second_rate / total
+    }
+
+    /// Probability of leaving `regime` over a time step of length `dt`,
+    /// from the exact transition matrix of the Markov chain.
+    fn switching_probability(&self, regime: Regime, dt: f64) -> f64 {
+        let total = self.first_to_second_rate + self.second_to_first_rate;
+
+        if total == 0.0 {
+            return 0.0;
+        }
+
+        let rate = match regime {
+            Regime::First => self.first_to_second_rate,
+            Regime::Second => self.second_to_first_rate,
+        };
+
+        rate / total * (1.0 - (-total * dt).exp())
+    }
+
+    fn drift_in(&self, regime: Regime, x: f64, t: f64) -> f64 {
+        match regime {
+            Regime::First => self.first.drift(x, t),
+            Regime::Second => self.second.drift(x, t),
+        }
+    }
+
+    fn diffusion_in(&self, regime: Regime, x: f64, t: f64) -> f64 {
+        match regime {
+            Regime::First => self.first.diffusion(x, t),
+            Regime::Second => self.second.diffusion(x, t),
+        }
+    }
+
+    /// Simulate the process and its regimes together.
+    ///
+    /// The regime chain is sampled exactly on the time grid, and each
+    /// Euler-Maruyama step follows the regime at the start of the step.
+    ///
+    /// Returns `(process, regime)` trajectories on the same time grid,
+    /// with the regime recorded as 0 (first) or 1 (second).
+    pub fn euler_maruyama_with_regimes(
+        &self,
+        config: &StochasticProcessConfig,
+    ) -> (Trajectories, Trajectories) {
+        let (x_0, t_0, t_n, n_steps, m_paths, parallel) = config.unpack();
+
+        assert!(t_0 < t_n);
+
+        let dt: f64 = (t_n - t_0) / (n_steps as f64);
+
+        // Initialise empty paths and fill in the time points.
+        let r_0 = self.initial_regime as u8 as f64;
+        let mut paths = vec![(vec![x_0; n_steps + 1], vec![r_0; n_steps + 1]); m_paths];
+        let times: Vec<f64> = (0..=n_steps).map(|t| t_0 + dt * (t as f64)).collect();
+
+        let switch = [
+            self.switching_probability(Regime::First, dt),
+            self.switching_probability(Regime::Second, dt),
+        ];
+
+        let path_generator = |(i, (x, r)): (usize, &mut (Vec<f64>, Vec<f64>))| {
+            let mut rng = config.path_rng(i);
+            let mut regime = self.initial_regime;
+
+            for t in 0..n_steps {
+                let dW = rng.sample::<f64, _>(StandardNormal) * dt.sqrt();
+
+                x[t + 1] = x[t]
+                    + self.drift_in(regime, x[t], times[t]) * dt
+                    + self.diffusion_in(regime, x[t], times[t]) * dW;
+
+                if rng.gen::<f64>() < switch[regime as usize] {
+                    regime = regime.other();
+                }
+                r[t + 1] = regime as u8 as f64;
+            }
+        };
+
+        for_each_path(&mut paths, parallel, path_generator);
+
+        let (process, regimes) = paths.into_iter().unzip();
+
+        (
+            Trajectories {
+                times: times.clone(),
+                paths: process,
+            },
+            Trajectories {
+                times,
+                paths: regimes,
+            },
+        )
+    }
+}
+
+impl<P1: StochasticProcess, P2: StochasticProcess> StochasticProcess for RegimeSwitching<P1, P2> {
+    /// Drift in the initial regime.
+    /// Simulated paths switch between the regimes instead.
+    fn drift(&self, x: f64, t: f64) -> f64 {
+        self.drift_in(self.initial_regime, x, t)
+    }
+
+    /// Diffusion in the initial regime.
+    /// Simulated paths switch between the regimes instead.
+    fn diffusion(&self, x: f64, t: f64) -> f64 {
+        self.diffusion_in(self.initial_regime, x, t)
+    }
+
+    fn jump(&self, _x: f64, _t: f64) -> Option<f64> {
+        None
+    }
+
+    /// The parameters of the first process, then those of the second,
+    /// then the two switching rates.
+    fn parameters(&self) -> Vec<f64> {
+        let mut parameters = self.first.parameters();
+        parameters.extend(self.second.parameters());
+        parameters.extend([self.first_to_second_rate, self.second_to_first_rate]);
+        parameters
+    }
+
+    fn euler_maruyama(&self, config: &StochasticProcessConfig) -> Trajectories {
+        self.euler_maruyama_with_regimes(config).0
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_regime_switching {
+    use super::*;
+    use crate::GeometricBrownianMotion;
+    use RustQuant_math::*;
+    use RustQuant_utils::assert_approx_equal;
+
+    // Calm (10% volatility) and stressed (40% volatility) markets.
+    fn calm_and_stressed(
+        a: f64,
+        b: f64,
+    ) -> RegimeSwitching<GeometricBrownianMotion, GeometricBrownianMotion> {
+        RegimeSwitching::new(
+            GeometricBrownianMotion::new(0.05, 0.1),
+            GeometricBrownianMotion::new(0.05, 0.4),
+            a,
+            b,
+        )
+    }
+
+    #[test]
+    fn test_regime_occupancy() {
+        let (a, b, t) = (2.0, 3.0, 1.0_f64);
+        let process = calm_and_stressed(a, b);
+
+        let config = StochasticProcessConfig::new(1.0, 0.0, t, 100, 20_000, true).with_seed(1);
+        let (_, regimes) = process.euler_maruyama_with_regimes(&config);
+
+        // P(second regime at t) = a / (a + b) (1 - exp(-(a + b) t)).
+        let r_T: Vec<f64> = regimes.paths.iter().map(|p| p[100]).collect();
+        let expected = process.stationary_probability() * (1.0 - (-(a + b) * t).exp());
+
+        assert_approx_equal!(process.stationary_probability(), 0.4, 1e-15);
+        assert_approx_equal!(r_T.mean(), expected, 0.01);
+        assert!(regimes
+            .paths
+            .iter()
+            .flatten()
+            .all(|&r| r == 0.0 || r == 1.0));
+    }
+
+    #[test]
+    fn test_regime_switching_gbm() {
+        let (a, b, t) = (2.0, 3.0, 1.0_f64);
+        let process = calm_and_stressed(a, b);
+
+        let config = StochasticProcessConfig::new(1.0, 0.0, t, 250, 20_000, true).with_seed(2);
+        let log_X_T: Vec<f64> = process
+            .euler_maruyama(&config)
+            .paths
+            .iter()
+            .map(|p| p[250].ln())
+            .collect();
+
+        // E[ln X_T] = mu T - E[int sigma^2 dt] / 2, integrating the
+        // probability of the second regime over [0, T].
+        let occupancy = a / (a + b) * (t - (1.0 - (-(a + b) * t).exp()) / (a + b));
+        let integrated_variance = 0.1 * 0.1 * t + (0.4 * 0.4 - 0.1 * 0.1) * occupancy;
+
+        assert_approx_equal!(log_X_T.mean(), 0.05 * t - 0.5 * integrated_variance, 0.005);
+
+        // Without switching, it stays a GBM in the initial regime.
+        let stressed = calm_and_stressed(0.0, 0.0).with_initial_regime(Regime::Second);
+
+        let config = StochasticProcessConfig::new(1.0, 0.0, t, 250, 20_000, true).with_seed(3);
+        let (paths, regimes) = stressed.euler_maruyama_with_regimes(&config);
+        let log_X_T: Vec<f64> = paths.paths.iter().map(|p| p[250].ln()).collect();
+
+        assert!(regimes.paths.iter().flatten().all(|&r| r == 1.0));
+        assert_approx_equal!(log_X_T.mean(), (0.05 - 0.5 * 0.4 * 0.4) * t, 0.01);
+        assert_approx_equal!(log_X_T.variance(), 0.4 * 0.4 * t, 0.005);
+    }
+}
@@ -0,0 +1,268 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::model_parameter::ModelParameter;
+use crate::process::{
+    for_each_path, DiscretisationScheme, StochasticProcess, StochasticProcessConfig, Trajectories,
+};
+use rand::Rng;
+use rand_distr::StandardNormal;
+
+/// Struct containing the rough Bergomi (rBergomi) model parameters.
+///
+/// Bayer, Friz and Gatheral (2016). The asset and its variance follow
+/// $dS_t = \mu S_t dt + \sqrt{V_t} S_t dZ_t$ and
+/// $V_t = \xi_0(t) \exp(\eta Y_t - \frac{1}{2} \eta^2 t^{2H})$, where
+/// $Y_t = \sqrt{2H} \int_0^t (t - s)^{H - 1/2} dW_s$ is a Riemann-Liouville
+/// fractional Brownian motion and $d\langle W, Z \rangle_t = \rho dt$.
+pub struct RoughBergomi {
+    /// The drift of the asset ($\mu$).
+    pub mu: ModelParameter,
+
+    /// The forward variance curve ($\xi_0(t)$).
+    pub forward_variance: ModelParameter,
+
+    /// Hurst parameter of the variance ($H$), in $(0, 1/2)$ for rough volatility.
+    pub hurst: f64,
+
+    /// The volatility of volatility ($\eta$).
+    pub eta: f64,
+
+    /// The correlation between the asset and the variance Brownian motions ($\rho$).
+    pub rho: f64,
+}
+
+impl RoughBergomi {
+    /// Create a new rough Bergomi process.
+    ///
+    /// # Panics
+    /// Panics if `hurst` is not in `(0, 1)` or `rho` is not in `[-1, 1]`.
+    pub fn new(
+        mu: impl Into<ModelParameter>,
+        forward_variance: impl Into<ModelParameter>,
+        hurst: f64,
+        eta: f64,
+        rho: f64,
+    ) -> Self {
+        assert!(
+            0.0 < hurst && hurst < 1.0,
+            "The Hurst parameter must be in (0, 1)."
+        );
+        assert!(
+            (-1.0..=1.0).contains(&rho),
+            "The correlation must be in [-1, 1]."
+        );
+
+        Self {
+            mu: mu.into(),
+            forward_variance: forward_variance.into(),
+            hurst,
+            eta,
+            rho,
+        }
+    }
+
+    /// Simulate the asset and variance paths together with the hybrid scheme
+    /// of Bennedsen, Lunde and Pakkanen (2017).
+    ///
+    /// The Volterra kernel is integrated exactly over the most recent step,
+    /// and approximated at optimally chosen points over the earlier ones.
+    /// Each path costs $O(n^2)$ in the number of steps $n$.
+    ///
+    /// Returns `(asset, variance)` trajectories on the same time grid.
+    pub fn simulate_with_variance(
+        &self,
+        config: &StochasticProcessConfig,
+    ) -> (Trajectories, Trajectories) {
+        let (x_0, t_0, t_n, n_steps, m_paths, parallel) = config.unpack();
+
+        assert!(t_0 < t_n);
+
+        let dt: f64 = (t_n - t_0) / (n_steps as f64);
+
+        // Initialise empty paths and fill in the time points.
+        let v_0 = self.forward_variance.0(t_0);
+        let mut paths = vec![(vec![x_0; n_steps + 1], vec![v_0; n_steps + 1]); m_paths];
+        let times: Vec<f64> = (0..=n_steps).map(|t| t_0 + dt * (t as f64)).collect();
+
+        let alpha = self.hurst - 0.5;
+
+        // Joint law of the Brownian increment over a step and the kernel
+        // integral over the same step, via its Cholesky factor.
+        let var_increment = dt;
+        let covariance = dt.powf(alpha + 1.0) / (alpha + 1.0);
+        let var_integral = dt.powf(2.0 * alpha + 1.0) / (2.0 * alpha + 1.0);
+        let l_21 = covariance / var_increment.sqrt();
+        let l_22 = (var_integral - l_21 * l_21).max(0.0).sqrt();
+
+        // Kernel weights (b_k dt)^alpha for the steps k >= 2 back.
+        let weights: Vec<f64> = (0..=n_steps)
+            .map(|k| match k {
+                0 | 1 => 0.0,
+                _ => {
+                    let k = k as f64;
+                    let b = ((k.powf(alpha + 1.0) - (k - 1.0).powf(alpha + 1.0)) / (alpha + 1.0))
+                        .powf(1.0 / alpha);
+
+                    (b * dt).powf(alpha)
+                }
+            })
+            .collect();
+
+        let scale = (2.0 * self.hurst).sqrt();
+
+        let path_generator = |(i, (x, v)): (usize, &mut (Vec<f64>, Vec<f64>))| {
+            let mut rng = config.path_rng(i);
+
+            let mut increments = Vec::with_capacity(n_steps);
+
+            for t in 0..n_steps {
+                let z_1: f64 = rng.sample(StandardNormal);
+                let z_2: f64 = rng.sample(StandardNormal);
+                let z_3: f64 = rng.sample(StandardNormal);
+
+                let dW = var_increment.sqrt() * z_1;
+                let kernel_integral = l_21 * z_1 + l_22 * z_2;
+                increments.push(dW);
+
+                // The Volterra process at the end of the step.
+                let y = scale
+                    * (kernel_integral
+                        + (2..=t + 1)
+                            .map(|k| weights[k] * increments[t + 1 - k])
+                            .sum::<f64>());
+
+                // The asset shock, correlated with the variance shock.
+                let dZ = self.rho * dW + (1.0 - self.rho * self.rho).sqrt() * dt.sqrt() * z_3;
+
+                x[t + 1] =
+                    x[t] * ((self.mu.0(times[t]) - 0.5 * v[t]) * dt + v[t].sqrt() * dZ).exp();
+
+                let elapsed = times[t + 1] - t_0;
+                v[t + 1] = self.forward_variance.0(times[t + 1])
+                    * (self.eta * y - 0.5 * self.eta * self.eta * elapsed.powf(2.0 * self.hurst))
+                        .exp();
+            }
+        };
+
+        for_each_path(&mut paths, parallel, path_generator);
+
+        let (asset, variance) = paths.into_iter().unzip();
+
+        (
+            Trajectories {
+                times: times.clone(),
+                paths: asset,
+            },
+            Trajectories {
+                times,
+                paths: variance,
+            },
+        )
+    }
+}
+
+impl StochasticProcess for RoughBergomi {
+    fn drift(&self, x: f64, t: f64) -> f64 {
+        self.mu.0(t) * x
+    }
+
+    /// Diffusion at the forward variance.
+    /// Simulated paths use the full variance process instead.
+    fn diffusion(&self, x: f64, t: f64) -> f64 {
+        self.forward_variance.0(t).max(0.0).sqrt() * x
+    }
+
+    fn jump(&self, _x: f64, _t: f64) -> Option<f64> {
+        None
+    }
+
+    fn parameters(&self) -> Vec<f64> {
+        vec![
+            self.mu.0(0.0),
+            self.forward_variance.0(0.0),
+            self.hurst,
+            self.eta,
+            self.rho,
+        ]
+    }
+
+    fn euler_maruyama(&self, config: &StochasticProcessConfig) -> Trajectories {
+        self.simulate_with_variance(config).0
+    }
+
+    /// Uses the hybrid scheme of `simulate_with_variance`, so the scheme is ignored.
+    fn simulate_with_scheme(
+        &self,
+        config: &StochasticProcessConfig,
+        _scheme: DiscretisationScheme,
+    ) -> Trajectories {
+        self.euler_maruyama(config)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_rough_bergomi {
+    use super::*;
+    use RustQuant::instruments::{implied_volatility, TypeFlag};
+    use RustQuant_math::*;
+    use RustQuant_utils::assert_approx_equal;
+
+    // The parameters of Bayer, Friz and Gatheral (2016), fitted to SPX.
+    fn spx() -> RoughBergomi {
+        RoughBergomi::new(0.0, 0.235 * 0.235, 0.07, 1.9, -0.9)
+    }
+
+    #[test]
+    fn test_rough_bergomi_moments() {
+        let config = StochasticProcessConfig::new(100.0, 0.0, 1.0, 100, 50_000, true).with_seed(1);
+        let (asset, variance) = spx().simulate_with_variance(&config);
+
+        assert!(variance.paths.iter().flatten().all(|&v| v > 0.0));
+
+        // The variance is a martingale with E[V_t] = xi_0(t),
+        // and the asset a martingale with E[S_t] = S_0.
+        for step in [10, 100] {
+            let V_t: Vec<f64> = variance.paths.iter().map(|p| p[step]).collect();
+            let S_t: Vec<f64> = asset.paths.iter().map(|p| p[step]).collect();
+
+            assert_approx_equal!(V_t.mean(), 0.235 * 0.235, 0.003);
+            assert_approx_equal!(S_t.mean(), 100.0, 0.2);
+        }
+    }
+
+    #[test]
+    fn test_rough_bergomi_smile() {
+        let t = 1.0;
+        let config = StochasticProcessConfig::new(100.0, 0.0, t, 100, 50_000, true).with_seed(2);
+        let S_T: Vec<f64> = spx()
+            .euler_maruyama(&config)
+            .paths
+            .iter()
+            .map(|p| p[100])
+            .collect();
+
+        let implied_vol = |k: f64| {
+            let price = S_T.iter().map(|s| (s - k).max(0.0)).sum::<f64>() / S_T.len() as f64;
+
+            implied_volatility(price, 100.0, k, t, 0.0, TypeFlag::Call)
+        };
+
+        let (low, atm, high) = (implied_vol(80.0), implied_vol(100.0), implied_vol(120.0));
+
+        // Bayer, Friz and Gatheral report a one year ATM volatility of
+        // about 20%, below sqrt(xi_0) = 23.5%, with a steep negative skew.
+        assert!(0.17 < atm && atm < 0.225, "ATM volatility {atm}");
+        assert!(low > atm + 0.03 && atm > high + 0.02);
+    }
+}
@@ -7,13 +7,21 @@
 //      - LICENSE-MIT.md
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-// use crate::{models::SABR, stochastics::StochasticProcess};
-
 use crate::model_parameter::ModelParameter;
+use crate::process::{
+    for_each_path, DiscretisationScheme, StochasticProcess, StochasticProcessConfig, Trajectories,
+};
+use rand::Rng;
+use rand_distr::StandardNormal;
 
-/// Struct containing the Heston model parameters.
+/// Struct containing the SABR model parameters.
+///
+/// The forward and its volatility follow
+/// $dF_t = \sigma_t F_t^\beta dW^1_t$ and $d\sigma_t = \nu \sigma_t dW^2_t$,
+/// with $\sigma_0 = \alpha$ and $d\langle W^1, W^2 \rangle_t = \rho dt$.
+/// The notation matches the Hagan et al. (2002) implied volatility formula.
 pub struct SABR {
-    /// The volatility of the volatility ($\alpha$).
+    /// The initial volatility ($\alpha$).
     /// Note: $\alpha \in [0, \infty)$.
     pub alpha: ModelParameter,
 
@@ -21,9 +29,13 @@ pub struct SABR {
     /// Note: $\beta \in [0, 1]$.
     pub beta: ModelParameter,
 
-    /// The correlation between the asset and the variance Brownian motions ($\rho$).
+    /// The correlation between the forward and the volatility Brownian motions ($\rho$).
     /// Note: $\rho \in [-1, 1]$.
     pub rho: ModelParameter,
+
+    /// The volatility of the volatility ($\nu$).
+    /// Note: $\nu \in [0, \infty)$.
+    pub nu: ModelParameter,
 }
 
 impl SABR {
@@ -32,38 +44,191 @@ impl SABR {
         alpha: impl Into<ModelParameter>,
         beta: impl Into<ModelParameter>,
         rho: impl Into<ModelParameter>,
+        nu: impl Into<ModelParameter>,
     ) -> Self {
         Self {
             alpha: alpha.into(),
             beta: beta.into(),
             rho: rho.into(),
+            nu: nu.into(),
         }
     }
+
+    /// Simulate the forward and volatility paths together.
+    ///
+    /// The volatility is stepped exactly (it is lognormal), and the forward
+    /// by Euler-Maruyama, absorbed at zero.
+    ///
+    /// Returns `(forward, volatility)` trajectories on the same time grid.
+    pub fn euler_maruyama_with_volatility(
+        &self,
+        config: &StochasticProcessConfig,
+    ) -> (Trajectories, Trajectories) {
+        let (x_0, t_0, t_n, n_steps, m_paths, parallel) = config.unpack();
+
+        assert!(t_0 < t_n);
+        assert!(
+            (-1.0..=1.0).contains(&self.rho.0(t_0)),
+            "The correlation must be in [-1, 1]."
+        );
+
+        let dt: f64 = (t_n - t_0) / (n_steps as f64);
+
+        // Initialise empty paths and fill in the time points.
+        let alpha = self.alpha.0(t_0);
+        let mut paths = vec![(vec![x_0; n_steps + 1], vec![alpha; n_steps + 1]); m_paths];
+        let times: Vec<f64> = (0..=n_steps).map(|t| t_0 + dt * (t as f64)).collect();
+
+        let path_generator = |(i, (f, sigma)): (usize, &mut (Vec<f64>, Vec<f64>))| {
+            let mut rng = config.path_rng(i);
+            let scale = dt.sqrt();
+
+            for t in 0..n_steps {
+                let (beta, rho, nu) = (
+                    self.beta.0(times[t]),
+                    self.rho.0(times[t]),
+                    self.nu.0(times[t]),
+                );
+
+                // Correlate the volatility shock with the forward shock.
+                let z_1: f64 = rng.sample(StandardNormal);
+                let z_2: f64 = rng.sample(StandardNormal);
+                let dW_1 = z_1 * scale;
+                let dW_2 = (rho * z_1 + (1.0 - rho * rho).sqrt() * z_2) * scale;
+
+                f[t + 1] = (f[t] + sigma[t] * f[t].powf(beta) * dW_1).max(0.0);
+                sigma[t + 1] = sigma[t] * (nu * dW_2 - 0.5 * nu * nu * dt).exp();
+            }
+        };
+
+        for_each_path(&mut paths, parallel, path_generator);
+
+        let (forward, volatility) = paths.into_iter().unzip();
+
+        (
+            Trajectories {
+                times: times.clone(),
+                paths: forward,
+            },
+            Trajectories {
+                times,
+                paths: volatility,
+            },
+        )
+    }
 }
 
-// impl SABR {
-//     /// The volatility drift term.
-//     fn drift_2(&self, x: f64, t: f64) -> f64 {
-//         0.0
-//     }
-
-//     /// The volatility diffusion term.
-//     fn diffusion_2(&self, x: f64, t: f64) -> f64 {
-//         self.alpha.0(t) * x
-//     }
-// }
-
-// impl StochasticProcess for SABR {
-//     fn drift(&self, x: f64, t: f64) -> f64 {
-//         0.0
-//     }
-
-//     fn diffusion(&self, x: f64, t: f64) -> f64 {
-//         // self.mu.0(t) * x
-//         self.
-//     }
-
-//     fn jump(&self, x: f64, t: f64) -> Option<f64> {
-//         todo!()
-//     }
-// }
+impl StochasticProcess for SABR {
+    fn drift(&self, _x: f64, _t: f64) -> f64 {
+        // The forward is a martingale.
+        0.0
+    }
+
+    /// Diffusion at the initial volatility.
+    /// Simulated paths use the full volatility process instead.
+    fn diffusion(&self, x: f64, t: f64) -> f64 {
+        self.alpha.0(t) * x.max(0.0).powf(self.beta.0(t))
+    }
+
+    fn jump(&self, _x: f64, _t: f64) -> Option<f64> {
+        None
+    }
+
+    fn parameters(&self) -> Vec<f64> {
+        vec![
+            self.alpha.0(0.0),
+            self.beta.0(0.0),
+            self.rho.0(0.0),
+            self.nu.0(0.0),
+        ]
+    }
+
+    fn euler_maruyama(&self, config: &StochasticProcessConfig) -> Trajectories {
+        self.euler_maruyama_with_volatility(config).0
+    }
+
+    /// Uses the scheme of `euler_maruyama_with_volatility`, so the scheme is ignored.
+    fn simulate_with_scheme(
+        &self,
+        config: &StochasticProcessConfig,
+        _scheme: DiscretisationScheme,
+    ) -> Trajectories {
+        self.euler_maruyama(config)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_sabr {
+    use super::*;
+    use RustQuant_math::*;
+    use RustQuant_utils::assert_approx_equal;
+
+    #[test]
+    fn test_sabr_martingales() {
+        let sabr = SABR::new(0.3, 0.5, -0.4, 0.6);
+
+        let config = StochasticProcessConfig::new(1.0, 0.0, 1.0, 250, 20_000, true);
+        let (forward, volatility) = sabr.euler_maruyama_with_volatility(&config);
+
+        let F_T: Vec<f64> = forward.paths.iter().map(|p| p[250]).collect();
+        let sigma_T: Vec<f64> = volatility.paths.iter().map(|p| p[250]).collect();
+
+        // Both the forward and the volatility are martingales.
+        assert_approx_equal!(F_T.mean(), 1.0, 0.01);
+        assert_approx_equal!(sigma_T.mean(), 0.3, 0.01);
+
+        // The volatility is lognormal: Var[ln sigma_T] = nu^2 T.
+        let log_sigma: Vec<f64> = sigma_T.iter().map(|s| s.ln()).collect();
+        assert_approx_equal!(log_sigma.variance(), 0.36, 0.02);
+    }
+
+    #[test]
+    fn test_sabr_lognormal_limit() {
+        // With beta = 1 and no vol of vol, the forward is GBM with volatility alpha.
+        let sabr = SABR::new(0.2, 1.0, 0.0, 0.0);
+
+        let config = StochasticProcessConfig::new(100.0, 0.0, 2.0, 250, 20_000, true);
+        let F_T: Vec<f64> = sabr
+            .euler_maruyama(&config)
+            .paths
+            .iter()
+            .map(|p| (p[250] / 100.0).ln())
+            .collect();
+
+        assert_approx_equal!(F_T.variance(), 0.2 * 0.2 * 2.0, 0.003);
+    }
+
+    #[test]
+    fn test_sabr_correlation() {
+        let sabr = SABR::new(0.2, 1.0, 0.5, 0.4);
+
+        let config = StochasticProcessConfig::new(100.0, 0.0, 1.0, 1, 20_000, false);
+        let (forward, volatility) = sabr.euler_maruyama_with_volatility(&config);
+
+        // Over one step, the forward and log volatility shocks have correlation rho.
+        let dF: Vec<f64> = forward.paths.iter().map(|p| p[1] - p[0]).collect();
+        let dsigma: Vec<f64> = volatility
+            .paths
+            .iter()
+            .map(|p| (p[1] / p[0]).ln())
+            .collect();
+
+        let (mean_F, mean_sigma) = (dF.mean(), dsigma.mean());
+        let covariance = dF
+            .iter()
+            .zip(&dsigma)
+            .map(|(a, b)| (a - mean_F) * (b - mean_sigma))
+            .sum::<f64>()
+            / dF.len() as f64;
+
+        assert_approx_equal!(
+            covariance / (dF.variance() * dsigma.variance()).sqrt(),
+            0.5,
+            0.02
+        );
+    }
+}
@@ -0,0 +1,289 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::process::{
+    for_each_path, DiscretisationScheme, StochasticProcess, StochasticProcessConfig, Trajectories,
+};
+use rand::Rng;
+use rand_distr::StandardNormal;
+
+/// Struct containing the Schwartz (1997) two-factor commodity model parameters.
+///
+/// The spot price and the convenience yield follow
+/// $dS_t = (\mu - \delta_t) S_t dt + \sigma_1 S_t dW^1_t$ and
+/// $d\delta_t = \kappa (\alpha - \delta_t) dt + \sigma_2 dW^2_t$,
+/// with $d\langle W^1, W^2 \rangle_t = \rho dt$.
+///
+/// Under the risk-neutral measure, $\mu$ becomes the risk-free rate $r$
+/// and $\alpha$ becomes $\hat{\alpha} = \alpha - \lambda / \kappa$, where
+/// $\lambda$ is the market price of convenience yield risk.
+pub struct SchwartzTwoFactor {
+    /// The drift of the spot price ($\mu$).
+    pub mu: f64,
+
+    /// The mean reversion rate of the convenience yield ($\kappa$).
+    pub kappa: f64,
+
+    /// The long-run convenience yield ($\alpha$).
+    pub alpha: f64,
+
+    /// The volatility of the spot price ($\sigma_1$).
+    pub sigma_1: f64,
+
+    /// The volatility of the convenience yield ($\sigma_2$).
+    pub sigma_2: f64,
+
+    /// The correlation between the spot and convenience yield Brownian motions ($\rho$).
+    pub rho: f64,
+
+    /// The market price of convenience yield risk ($\lambda$).
+    pub lambda: f64,
+
+    /// The convenience yield at the initial time point ($\delta_0$).
+    pub initial_convenience_yield: f64,
+}
+
+impl SchwartzTwoFactor {
+    /// Create a new Schwartz two-factor model, with no market price of
+    /// convenience yield risk, starting at the long-run convenience yield.
+    ///
+    /// # Panics
+    /// Panics if `kappa` is not positive or `rho` is not in `[-1, 1]`.
+    pub fn new(mu: f64, kappa: f64, alpha: f64, sigma_1: f64, sigma_2: f64, rho: f64) -> Self {
+        assert!(kappa > 0.0, "The mean reversion rate must be positive.");
+        assert!(
+            (-1.0..=1.0).contains(&rho),
+            "The correlation must be in [-1, 1]."
+        );
+
+        Self {
+            mu,
+            kappa,
+            alpha,
+            sigma_1,
+            sigma_2,
+            rho,
+            lambda: 0.0,
+            initial_convenience_yield: alpha,
+        }
+    }
+
+    /// Set the market price of convenience yield risk ($\lambda$).
+    pub fn with_market_price_of_risk(mut self, lambda: f64) -> Self {
+        self.lambda = lambda;
+        self
+    }
+
+    /// Set the convenience yield at the initial time point ($\delta_0$).
+    pub fn with_initial_convenience_yield(mut self, delta_0: f64) -> Self {
+        self.initial_convenience_yield = delta_0;
+        self
+    }
+
+    /// Risk-neutral long-run convenience yield, $\hat{\alpha} = \alpha - \lambda / \kappa$.
+    pub fn risk_neutral_alpha(&self) -> f64 {
+        self.alpha - self.lambda / self.kappa
+    }
+
+    /// The same model under the risk-neutral measure with risk-free rate `r`.
+    pub fn risk_neutral(&self, r: f64) -> Self {
+        Self::new(
+            r,
+            self.kappa,
+            self.risk_neutral_alpha(),
+            self.sigma_1,
+            self.sigma_2,
+            self.rho,
+        )
+        .with_initial_convenience_yield(self.initial_convenience_yield)
+    }
+
+    /// Futures price for delivery in `maturity` years, given the current
+    /// spot price and convenience yield, and a constant risk-free rate `r`:
+    /// $F = S \exp(-\delta \frac{1 - e^{-\kappa T}}{\kappa} + A(T))$.
+    pub fn futures_price(&self, spot: f64, convenience_yield: f64, r: f64, maturity: f64) -> f64 {
+        let (kappa, s1, s2, rho) = (self.kappa, self.sigma_1, self.sigma_2, self.rho);
+        let alpha = self.risk_neutral_alpha();
+        let t = maturity;
+
+        let decay = 1.0 - (-kappa * t).exp();
+
+        let a = (r - alpha + 0.5 * s2 * s2 / (kappa * kappa) - s1 * s2 * rho / kappa) * t
+            + 0.25 * s2 * s2 * (1.0 - (-2.0 * kappa * t).exp()) / kappa.powi(3)
+            + (alpha * kappa + s1 * s2 * rho - s2 * s2 / kappa) * decay / (kappa * kappa);
+
+        spot * (-convenience_yield * decay / kappa + a).exp()
+    }
+
+    /// Simulate the spot price and convenience yield paths together.
+    ///
+    /// The spot price is stepped in logs, so it stays positive.
+    ///
+    /// Returns `(spot, convenience_yield)` trajectories on the same time grid.
+    pub fn euler_maruyama_with_convenience_yield(
+        &self,
+        config: &StochasticProcessConfig,
+    ) -> (Trajectories, Trajectories) {
+        let (x_0, t_0, t_n, n_steps, m_paths, parallel) = config.unpack();
+
+        assert!(t_0 < t_n);
+
+        let dt: f64 = (t_n - t_0) / (n_steps as f64);
+
+        // Initialise empty paths and fill in the time points.
+        let delta_0 = self.initial_convenience_yield;
+        let mut paths = vec![(vec![x_0; n_steps + 1], vec![delta_0; n_steps + 1]); m_paths];
+        let times: Vec<f64> = (0..=n_steps).map(|t| t_0 + dt * (t as f64)).collect();
+
+        let path_generator = |(i, (s, delta)): (usize, &mut (Vec<f64>, Vec<f64>))| {
+            let mut rng = config.path_rng(i);
+            let scale = dt.sqrt();
+
+            for t in 0..n_steps {
+                // Correlate the convenience yield shock with the spot shock.
+                let z_1: f64 = rng.sample(StandardNormal);
+                let z_2: f64 = rng.sample(StandardNormal);
+                let dW_1 = z_1 * scale;
+                let dW_2 = (self.rho * z_1 + (1.0 - self.rho * self.rho).sqrt() * z_2) * scale;
+
+                s[t + 1] = s[t]
+                    * ((self.mu - delta[t] - 0.5 * self.sigma_1 * self.sigma_1) * dt
+                        + self.sigma_1 * dW_1)
+                        .exp();
+                delta[t + 1] =
+                    delta[t] + self.kappa * (self.alpha - delta[t]) * dt + self.sigma_2 * dW_2;
+            }
+        };
+
+        for_each_path(&mut paths, parallel, path_generator);
+
+        let (spot, convenience_yield) = paths.into_iter().unzip();
+
+        (
+            Trajectories {
+                times: times.clone(),
+                paths: spot,
+            },
+            Trajectories {
+                times,
+                paths: convenience_yield,
+            },
+        )
+    }
+}
+
+impl StochasticProcess for SchwartzTwoFactor {
+    /// Drift at the initial convenience yield.
+    /// Simulated paths use the full convenience yield process instead.
+    fn drift(&self, x: f64, _t: f64) -> f64 {
+        (self.mu - self.initial_convenience_yield) * x
+    }
+
+    fn diffusion(&self, x: f64, _t: f64) -> f64 {
+        assert!(self.sigma_1 >= 0.0);
+        self.sigma_1 * x
+    }
+
+    fn jump(&self, _x: f64, _t: f64) -> Option<f64> {
+        None
+    }
+
+    fn parameters(&self) -> Vec<f64> {
+        vec![
+            self.mu,
+            self.kappa,
+            self.alpha,
+            self.sigma_1,
+            self.sigma_2,
+            self.rho,
+            self.lambda,
+        ]
+    }
+
+    fn euler_maruyama(&self, config: &StochasticProcessConfig) -> Trajectories {
+        self.euler_maruyama_with_convenience_yield(config).0
+    }
+
+    /// Uses the two-factor scheme of `euler_maruyama`, so the scheme is ignored.
+    fn simulate_with_scheme(
+        &self,
+        config: &StochasticProcessConfig,
+        _scheme: DiscretisationScheme,
+    ) -> Trajectories {
+        self.euler_maruyama(config)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_schwartz_two_factor {
+    use super::*;
+    use RustQuant_math::*;
+    use RustQuant_utils::{assert_approx_equal, RUSTQUANT_EPSILON};
+
+    // Crude oil parameters, roughly those estimated by Schwartz (1997).
+    fn crude_oil() -> SchwartzTwoFactor {
+        SchwartzTwoFactor::new(0.15, 1.5, 0.1, 0.35, 0.4, 0.8)
+            .with_market_price_of_risk(0.1)
+            .with_initial_convenience_yield(0.05)
+    }
+
+    #[test]
+    fn test_futures_price() {
+        let model = crude_oil();
+        let r = 0.04;
+
+        // Converges to the spot at delivery.
+        assert_approx_equal!(
+            model.futures_price(80.0, 0.05, r, 0.0),
+            80.0,
+            RUSTQUANT_EPSILON
+        );
+
+        // Without convenience yield risk, it is the cost-of-carry price.
+        let constant = SchwartzTwoFactor::new(0.15, 1.5, 0.05, 0.35, 0.0, 0.0);
+        assert_approx_equal!(
+            constant.futures_price(80.0, 0.05, r, 2.0),
+            80.0 * ((r - 0.05) * 2.0_f64).exp(),
+            1e-10
+        );
+
+        // A higher convenience yield lowers the futures price.
+        assert!(model.futures_price(80.0, 0.1, r, 1.0) < model.futures_price(80.0, 0.0, r, 1.0));
+    }
+
+    #[test]
+    fn test_futures_price_is_risk_neutral_expectation() {
+        let (r, t) = (0.04, 1.0);
+        let model = crude_oil().risk_neutral(r);
+
+        assert_approx_equal!(model.alpha, 0.1 - 0.1 / 1.5, RUSTQUANT_EPSILON);
+
+        let config = StochasticProcessConfig::new(80.0, 0.0, t, 250, 50_000, true).with_seed(1);
+        let (spot, convenience_yield) = model.euler_maruyama_with_convenience_yield(&config);
+
+        let S_T: Vec<f64> = spot.paths.iter().map(|p| p[250]).collect();
+        let delta_T: Vec<f64> = convenience_yield.paths.iter().map(|p| p[250]).collect();
+
+        // F(0, T) = E^Q[S_T].
+        let futures = crude_oil().futures_price(80.0, 0.05, r, t);
+        assert_approx_equal!(S_T.mean(), futures, 0.5);
+
+        // E[delta_T] = alpha + (delta_0 - alpha) exp(-kappa T).
+        let alpha = model.alpha;
+        assert_approx_equal!(
+            delta_T.mean(),
+            alpha + (0.05 - alpha) * (-1.5 * t).exp(),
+            0.005
+        );
+    }
+}
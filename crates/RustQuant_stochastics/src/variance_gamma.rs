@@ -0,0 +1,241 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::model_parameter::ModelParameter;
+use crate::process::{
+    for_each_path, DiscretisationScheme, StochasticProcess, StochasticProcessConfig, Trajectories,
+};
+use num::Complex;
+use rand::Rng;
+use rand_distr::{Distribution, StandardNormal};
+
+/// Struct containing the Variance Gamma process parameters.
+///
+/// Brownian motion with drift, run on a gamma clock:
+/// $X_t = \theta G_t + \sigma W(G_t)$, where $G_t$ is a gamma process
+/// with mean $t$ and variance $\nu t$ (Madan, Carr and Chang, 1998).
+pub struct VarianceGamma {
+    /// Volatility of the Brownian motion ($\sigma$).
+    pub sigma: ModelParameter,
+
+    /// Variance rate of the gamma time change ($\nu$).
+    /// Note: $\nu \in (0, \infty)$.
+    pub nu: ModelParameter,
+
+    /// Drift of the Brownian motion ($\theta$), which controls the skew.
+    pub theta: ModelParameter,
+}
+
+impl VarianceGamma {
+    /// Create a new Variance Gamma process.
+    pub fn new(
+        sigma: impl Into<ModelParameter>,
+        nu: impl Into<ModelParameter>,
+        theta: impl Into<ModelParameter>,
+    ) -> Self {
+        Self {
+            sigma: sigma.into(),
+            nu: nu.into(),
+            theta: theta.into(),
+        }
+    }
+
+    /// Characteristic function of the increment $X_t - X_0$:
+    ///
+    /// $$
+    /// \phi(u) = \left(1 - i \theta \nu u + \frac{1}{2} \sigma^2 \nu u^2 \right)^{-t / \nu}
+    /// $$
+    ///
+    /// Uses the parameters at time zero, so assumes they are constant.
+    pub fn characteristic_function(&self, u: f64, t: f64) -> Complex<f64> {
+        let (sigma, nu, theta) = (self.sigma.0(0.0), self.nu.0(0.0), self.theta.0(0.0));
+        let i: Complex<f64> = Complex::i();
+
+        (1.0 - i * theta * nu * u + 0.5 * sigma * sigma * nu * u * u).powf(-t / nu)
+    }
+
+    /// Drift correction $\omega$ that makes $e^{\omega t + X_t}$ a martingale:
+    ///
+    /// $$
+    /// \omega = \frac{1}{\nu} \ln\left(1 - \theta \nu - \frac{1}{2} \sigma^2 \nu \right)
+    /// $$
+    ///
+    /// An asset priced under the Variance Gamma model is then
+    /// $S_t = S_0 e^{(r - q + \omega) t + X_t}$.
+    /// Uses the parameters at time zero, so assumes they are constant.
+    ///
+    /// # Panics
+    /// Panics if $\theta \nu + \sigma^2 \nu / 2 \geq 1$, when $e^{X_t}$
+    /// has no finite mean.
+    pub fn martingale_correction(&self) -> f64 {
+        let (sigma, nu, theta) = (self.sigma.0(0.0), self.nu.0(0.0), self.theta.0(0.0));
+        let argument = 1.0 - theta * nu - 0.5 * sigma * sigma * nu;
+
+        assert!(argument > 0.0, "exp(X_t) must have a finite mean.");
+
+        argument.ln() / nu
+    }
+}
+
+impl StochasticProcess for VarianceGamma {
+    /// Mean rate of the process.
+    fn drift(&self, _x: f64, t: f64) -> f64 {
+        self.theta.0(t)
+    }
+
+    /// Volatility of the subordinated Brownian motion.
+    /// Simulated paths use the gamma time change instead.
+    fn diffusion(&self, _x: f64, t: f64) -> f64 {
+        self.sigma.0(t)
+    }
+
+    fn jump(&self, _x: f64, _t: f64) -> Option<f64> {
+        None
+    }
+
+    fn parameters(&self) -> Vec<f64> {
+        vec![self.sigma.0(0.0), self.nu.0(0.0), self.theta.0(0.0)]
+    }
+
+    /// Exact simulation by gamma time-changed Brownian motion.
+    fn euler_maruyama(&self, config: &StochasticProcessConfig) -> Trajectories {
+        let (x_0, t_0, t_n, n_steps, m_paths, parallel) = config.unpack();
+
+        assert!(t_0 < t_n);
+
+        let dt: f64 = (t_n - t_0) / (n_steps as f64);
+
+        // Initialise empty paths and fill in the time points.
+        let mut paths = vec![vec![x_0; n_steps + 1]; m_paths];
+        let times: Vec<f64> = (0..=n_steps).map(|t| t_0 + dt * (t as f64)).collect();
+
+        let path_generator = |(i, path): (usize, &mut Vec<f64>)| {
+            let mut rng = config.path_rng(i);
+
+            for t in 0..n_steps {
+                let (sigma, nu, theta) = (
+                    self.sigma.0(times[t]),
+                    self.nu.0(times[t]),
+                    self.theta.0(times[t]),
+                );
+
+                assert!(nu > 0.0, "The variance rate must be positive.");
+
+                // Gamma clock increment with mean dt and variance nu dt.
+                let clock = rand_distr::Gamma::new(dt / nu, nu).unwrap();
+                let dG = clock.sample(&mut rng);
+                let z: f64 = rng.sample(StandardNormal);
+
+                path[t + 1] = path[t] + theta * dG + sigma * dG.sqrt() * z;
+            }
+        };
+
+        for_each_path(&mut paths, parallel, path_generator);
+
+        Trajectories { times, paths }
+    }
+
+    /// Simulation is exact, so the scheme is ignored.
+    fn simulate_with_scheme(
+        &self,
+        config: &StochasticProcessConfig,
+        _scheme: DiscretisationScheme,
+    ) -> Trajectories {
+        self.euler_maruyama(config)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_variance_gamma {
+    use super::*;
+    use RustQuant_math::{Distribution as _, Gamma, Statistic};
+    use RustQuant_utils::{assert_approx_equal, RUSTQUANT_EPSILON};
+
+    #[test]
+    fn test_variance_gamma_moments() {
+        let (sigma, nu, theta, t) = (0.2, 0.3, -0.15, 2.0);
+
+        let vg = VarianceGamma::new(sigma, nu, theta);
+        let config = StochasticProcessConfig::new(0.0, 0.0, t, 20, 50_000, true);
+        let output = vg.euler_maruyama(&config);
+
+        let X_T: Vec<f64> = output.paths.iter().map(|p| p[20]).collect();
+
+        assert_approx_equal!(X_T.mean(), theta * t, 0.005);
+        assert_approx_equal!(
+            X_T.variance(),
+            (sigma * sigma + nu * theta * theta) * t,
+            0.005
+        );
+    }
+
+    #[test]
+    fn test_variance_gamma_empirical_characteristic_function() {
+        let (sigma, nu, theta, t) = (0.25, 0.2, 0.1, 1.0);
+
+        let vg = VarianceGamma::new(sigma, nu, theta);
+        let config = StochasticProcessConfig::new(0.0, 0.0, t, 10, 50_000, true);
+        let output = vg.euler_maruyama(&config);
+
+        for u in [0.5, 2.0, 5.0] {
+            let empirical = output
+                .paths
+                .iter()
+                .map(|p| Complex::new(0.0, u * p[10]).exp())
+                .sum::<Complex<f64>>()
+                / 50_000.0;
+            let exact = vg.characteristic_function(u, t);
+
+            assert_approx_equal!(empirical.re, exact.re, 0.01);
+            assert_approx_equal!(empirical.im, exact.im, 0.01);
+        }
+    }
+
+    #[test]
+    fn test_variance_gamma_pure_gamma_characteristic_function() {
+        // Without diffusion and with theta = 1, X_t is the gamma clock itself.
+        let (nu, t) = (0.4, 1.5);
+
+        let vg = VarianceGamma::new(0.0, nu, 1.0);
+        let gamma = Gamma::new(t / nu, 1.0 / nu);
+
+        for u in [0.1, 1.0, 3.0] {
+            let vg_cf = vg.characteristic_function(u, t);
+            let gamma_cf = gamma.cf(u);
+
+            assert_approx_equal!(vg_cf.re, gamma_cf.re, RUSTQUANT_EPSILON);
+            assert_approx_equal!(vg_cf.im, gamma_cf.im, RUSTQUANT_EPSILON);
+        }
+    }
+
+    #[test]
+    fn test_variance_gamma_martingale_correction() {
+        let (sigma, nu, theta, t) = (0.2, 0.25, -0.1, 1.0_f64);
+
+        let vg = VarianceGamma::new(sigma, nu, theta);
+        let omega = vg.martingale_correction();
+
+        // E[exp(X_t)] = exp(-omega t), from the characteristic function at u = -i.
+        let config = StochasticProcessConfig::new(0.0, 0.0, t, 10, 50_000, true);
+        let output = vg.euler_maruyama(&config);
+
+        let mean: f64 = output
+            .paths
+            .iter()
+            .map(|p| (omega * t + p[10]).exp())
+            .sum::<f64>()
+            / 50_000.0;
+
+        assert_approx_equal!(mean, 1.0, 0.005);
+    }
+}
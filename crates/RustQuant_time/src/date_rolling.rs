@@ -7,10 +7,11 @@
 //      - LICENSE-MIT.md
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-use super::{next_business_day, previous_business_day};
+use super::{add_months, next_business_day, previous_business_day};
 use crate::calendar::Calendar;
+use crate::tenor::{Tenor, TenorUnit};
 use std::fmt;
-use time::Date;
+use time::{Date, Duration};
 
 /// Date rolling business day conventions.
 ///
@@ -60,6 +61,22 @@ pub trait DateRoller {
 
     /// Roll a list of dates according to the given convention.
     fn roll_dates(&self, dates: &[Date], convention: &DateRollingConvention) -> Vec<Date>;
+
+    /// Advance the date by a tenor, and roll the result according to the
+    /// given convention.
+    ///
+    /// Business day tenors count business days and ignore the convention.
+    /// Month and year tenors keep the day of the month where possible, or
+    /// use the last day of the month. With `end_of_month`, a date on the
+    /// last business day of its month is advanced to the last business day
+    /// of the new month.
+    fn advance(
+        &self,
+        date: Date,
+        tenor: Tenor,
+        convention: &DateRollingConvention,
+        end_of_month: bool,
+    ) -> Date;
 }
 
 impl<C> DateRoller for C
@@ -84,6 +101,55 @@ where
             .map(|&date| self.roll_date(date, convention))
             .collect()
     }
+
+    fn advance(
+        &self,
+        date: Date,
+        tenor: Tenor,
+        convention: &DateRollingConvention,
+        end_of_month: bool,
+    ) -> Date {
+        let months = match tenor.unit {
+            TenorUnit::BusinessDays => {
+                let step = Duration::days(i64::from(tenor.length.signum()));
+                let mut new_date = date;
+
+                for _ in 0..tenor.length.unsigned_abs() {
+                    new_date += step;
+
+                    while !self.is_business_day(new_date) {
+                        new_date += step;
+                    }
+                }
+
+                return new_date;
+            }
+            TenorUnit::Days => {
+                return self.roll_date(date + Duration::days(i64::from(tenor.length)), convention)
+            }
+            TenorUnit::Weeks => {
+                return self.roll_date(date + Duration::weeks(i64::from(tenor.length)), convention)
+            }
+            TenorUnit::Months => tenor.length,
+            TenorUnit::Years => 12 * tenor.length,
+        };
+
+        let new_date = add_months(date, months);
+
+        match end_of_month && date == last_business_day_of_month(date, self) {
+            true => last_business_day_of_month(new_date, self),
+            false => self.roll_date(new_date, convention),
+        }
+    }
+}
+
+/// Last business day in the month of `date`.
+fn last_business_day_of_month<C: Calendar>(date: Date, calendar: &C) -> Date {
+    let last_day = date
+        .replace_day(date.month().length(date.year()))
+        .expect("The month has this many days.");
+
+    previous_business_day(last_day, calendar)
 }
 
 impl Default for DateRollingConvention {
@@ -156,3 +222,144 @@ impl DateRollingConvention {
         previous_business_day(date, calendar)
     }
 }
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod test_date_rolling {
+    use super::*;
+    use crate::{TargetCalendar, WeekendCalendar};
+    use time::macros::date;
+
+    #[test]
+    fn test_roll_date() {
+        let calendar = TargetCalendar;
+
+        // Good Friday, with Easter Monday after it.
+        let good_friday = date!(2024 - 03 - 29);
+
+        assert_eq!(
+            calendar.roll_date(good_friday, &DateRollingConvention::Following),
+            date!(2024 - 04 - 02)
+        );
+        assert_eq!(
+            calendar.roll_date(good_friday, &DateRollingConvention::ModifiedFollowing),
+            date!(2024 - 03 - 28)
+        );
+        assert_eq!(
+            calendar.roll_date(good_friday, &DateRollingConvention::Preceding),
+            date!(2024 - 03 - 28)
+        );
+
+        // A Saturday at the start of a month.
+        let saturday = date!(2024 - 06 - 01);
+
+        assert_eq!(
+            calendar.roll_date(saturday, &DateRollingConvention::Preceding),
+            date!(2024 - 05 - 31)
+        );
+        assert_eq!(
+            calendar.roll_date(saturday, &DateRollingConvention::ModifiedPreceding),
+            date!(2024 - 06 - 03)
+        );
+    }
+
+    #[test]
+    fn test_advance_business_days() {
+        let calendar = TargetCalendar;
+        let date = date!(2024 - 03 - 28);
+
+        assert_eq!(
+            calendar.advance(
+                date,
+                Tenor::business_days(1),
+                &DateRollingConvention::Actual,
+                false
+            ),
+            date!(2024 - 04 - 02)
+        );
+        assert_eq!(
+            calendar.advance(
+                date,
+                Tenor::business_days(2),
+                &DateRollingConvention::Actual,
+                false
+            ),
+            date!(2024 - 04 - 03)
+        );
+        assert_eq!(
+            calendar.advance(
+                date!(2024 - 04 - 02),
+                Tenor::business_days(-1),
+                &DateRollingConvention::Actual,
+                false
+            ),
+            date
+        );
+    }
+
+    #[test]
+    fn test_advance_months() {
+        let calendar = TargetCalendar;
+        let convention = DateRollingConvention::ModifiedFollowing;
+
+        // The day is capped at the end of the month.
+        assert_eq!(
+            calendar.advance(date!(2024 - 01 - 31), Tenor::months(1), &convention, false),
+            date!(2024 - 02 - 29)
+        );
+
+        // Without end of month, the 29th is kept and rolled back from
+        // Good Friday.
+        assert_eq!(
+            calendar.advance(date!(2024 - 02 - 29), Tenor::months(1), &convention, false),
+            date!(2024 - 03 - 28)
+        );
+
+        // With end of month, month ends stay at month ends.
+        assert_eq!(
+            calendar.advance(date!(2024 - 02 - 29), Tenor::months(2), &convention, true),
+            date!(2024 - 04 - 30)
+        );
+        assert_eq!(
+            calendar.advance(date!(2024 - 02 - 29), Tenor::months(2), &convention, false),
+            date!(2024 - 04 - 29)
+        );
+
+        // Years, and a Saturday rolled forward.
+        assert_eq!(
+            calendar.advance(date!(2023 - 09 - 15), Tenor::years(1), &convention, false),
+            date!(2024 - 09 - 16)
+        );
+        assert_eq!(
+            calendar.advance(date!(2024 - 09 - 16), -Tenor::years(1), &convention, false),
+            date!(2023 - 09 - 18)
+        );
+    }
+
+    #[test]
+    fn test_advance_days_and_weeks() {
+        let calendar = WeekendCalendar;
+
+        assert_eq!(
+            calendar.advance(
+                date!(2024 - 06 - 27),
+                Tenor::days(2),
+                &DateRollingConvention::Following,
+                false
+            ),
+            date!(2024 - 07 - 01)
+        );
+        assert_eq!(
+            calendar.advance(
+                date!(2024 - 06 - 27),
+                Tenor::weeks(1),
+                &DateRollingConvention::Following,
+                false
+            ),
+            date!(2024 - 07 - 04)
+        );
+    }
+}
@@ -34,7 +34,7 @@ use time::{util::is_leap_year, Date, Duration, Month};
 /// payment dates, the seller is eligible to some fraction of the coupon amount.
 /// """
 #[allow(non_camel_case_types)]
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum DayCountConvention {
     /// The '1/1' day count, which always returns a day count of 1.
     One_One,
@@ -413,6 +413,11 @@ impl DayCountConvention {
             return 0.0;
         }
 
+        // The year split only works forwards.
+        if end_date < start_date {
+            return -Self::day_count_factor_actual_actual_isda(end_date, start_date);
+        }
+
         let (y1, y2) = (start_date.year(), end_date.year());
 
         let (dib1, dib2) = (
@@ -622,6 +627,47 @@ mod TESTS_thirty_360 {
     use RustQuant_utils::assert_approx_equal;
     use RustQuant_utils::RUSTQUANT_EPSILON;
 
+    #[test]
+    fn thirty_360_isda() {
+        // The end date is only moved from the 31st if the start date is
+        // (or was moved to) the 30th.
+        let cases = [
+            (date!(2007 - 01 - 31), date!(2007 - 02 - 28), 28.0),
+            (date!(2007 - 01 - 31), date!(2007 - 03 - 31), 60.0),
+            (date!(2007 - 02 - 28), date!(2007 - 03 - 31), 33.0),
+            (date!(2006 - 08 - 31), date!(2007 - 02 - 28), 178.0),
+            (date!(2007 - 01 - 15), date!(2007 - 07 - 15), 180.0),
+        ];
+
+        for (start_date, end_date, days) in cases {
+            assert_approx_equal!(
+                DayCountConvention::Thirty_360_ISDA.day_count_factor(start_date, end_date),
+                days / 360.0,
+                RUSTQUANT_EPSILON
+            );
+        }
+    }
+
+    #[test]
+    fn thirty_e_360() {
+        // The end date is always moved from the 31st.
+        let cases = [
+            (date!(2007 - 01 - 31), date!(2007 - 02 - 28), 28.0),
+            (date!(2007 - 01 - 31), date!(2007 - 03 - 31), 60.0),
+            (date!(2007 - 02 - 28), date!(2007 - 03 - 31), 32.0),
+            (date!(2006 - 08 - 31), date!(2007 - 02 - 28), 178.0),
+            (date!(2007 - 01 - 15), date!(2007 - 07 - 15), 180.0),
+        ];
+
+        for (start_date, end_date, days) in cases {
+            assert_approx_equal!(
+                DayCountConvention::Thirty_E_360.day_count_factor(start_date, end_date),
+                days / 360.0,
+                RUSTQUANT_EPSILON
+            );
+        }
+    }
+
     #[test]
     fn thirty_e_365() {
         let start_date = date!(2011 - 06 - 17);
@@ -688,6 +734,27 @@ mod TESTS_actual_constant {
         }
     }
 
+    #[test]
+    fn actual_360_and_actual_365_fixed() {
+        let (start_date, end_date) = (date!(2024 - 01 - 01), date!(2024 - 07 - 01));
+
+        assert_approx_equal!(
+            DayCountConvention::Actual_360.day_count_factor(start_date, end_date),
+            182.0 / 360.0,
+            RUSTQUANT_EPSILON
+        );
+        assert_approx_equal!(
+            DayCountConvention::Actual_365_Fixed.day_count_factor(start_date, end_date),
+            182.0 / 365.0,
+            RUSTQUANT_EPSILON
+        );
+        assert_approx_equal!(
+            DayCountConvention::Actual_365_Fixed.day_count_factor(end_date, start_date),
+            -182.0 / 365.0,
+            RUSTQUANT_EPSILON
+        );
+    }
+
     #[test]
     fn actual_366() {
         // Test cases from QuantLib.
@@ -755,6 +822,35 @@ mod TESTS_actual_actual {
         );
     }
 
+    #[test]
+    fn actual_actual_isda_across_years() {
+        // Test cases from QuantLib.
+        let cases = [
+            (date!(1999 - 02 - 01), date!(1999 - 07 - 01), 0.410958904110),
+            (date!(2002 - 08 - 15), date!(2003 - 07 - 15), 0.915068493151),
+            (date!(1999 - 07 - 30), date!(2000 - 01 - 30), 0.503892506924),
+            (date!(2000 - 01 - 30), date!(2000 - 06 - 30), 0.415300546448),
+            (date!(1999 - 11 - 30), date!(2000 - 04 - 30), 0.415540085336),
+        ];
+
+        for (start_date, end_date, expected) in cases {
+            assert_approx_equal!(
+                DayCountConvention::Actual_Actual_ISDA.day_count_factor(start_date, end_date),
+                expected,
+                RUSTQUANT_EPSILON
+            );
+        }
+    }
+
+    #[test]
+    fn actual_actual_isda_reversed() {
+        assert_approx_equal!(
+            DayCountConvention::day_count_factor_actual_actual_isda(DATE_2, DATE_1),
+            -0.497724380567,
+            RUSTQUANT_EPSILON
+        );
+    }
+
     #[test]
     fn actual_actual_icma() {
         // Test cases from QuantLib.
@@ -0,0 +1,166 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023-2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Calendars that are not tied to a single country: a weekend-only
+//! calendar, and the TARGET calendar of euro settlement days.
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::calendar::Calendar;
+use crate::utilities::unpack_date;
+use time::{Date, Month};
+use RustQuant_iso::*;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// CONSTANTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// User-assigned ISO 3166 code, for calendars with no single country.
+const NO_COUNTRY: ISO_3166 = ISO_3166 {
+    alpha_2: "ZZ",
+    alpha_3: "ZZZ",
+    numeric: "999",
+};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Calendar whose only non-business days are weekends.
+pub struct WeekendCalendar;
+
+/// TARGET (Trans-European Automated Real-time Gross settlement Express
+/// Transfer) calendar, of settlement days for the euro.
+pub struct TargetCalendar;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS, METHODS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl Calendar for WeekendCalendar {
+    fn new() -> Self {
+        Self
+    }
+
+    fn name(&self) -> &'static str {
+        "Weekends only"
+    }
+
+    fn country_code(&self) -> ISO_3166 {
+        NO_COUNTRY
+    }
+
+    fn market_identifier_code(&self) -> ISO_10383 {
+        XXXX
+    }
+
+    fn is_holiday(&self, _date: Date) -> bool {
+        false
+    }
+}
+
+impl Calendar for TargetCalendar {
+    fn new() -> Self {
+        Self
+    }
+
+    fn name(&self) -> &'static str {
+        "TARGET"
+    }
+
+    fn country_code(&self) -> ISO_3166 {
+        NO_COUNTRY
+    }
+
+    fn market_identifier_code(&self) -> ISO_10383 {
+        XXXX
+    }
+
+    fn is_holiday(&self, date: Date) -> bool {
+        let (y, m, d, _, yd, em) = unpack_date(date, false);
+
+        if (
+            // New Year's Day
+            d == 1 && m == Month::January
+
+            // Good Friday (since 2000)
+            || (yd == em - 3 && y >= 2000)
+
+            // Easter Monday (since 2000)
+            || (yd == em && y >= 2000)
+
+            // Labour Day (since 2000)
+            || (d == 1 && m == Month::May && y >= 2000)
+
+            // Christmas
+            || (d == 25 && m == Month::December)
+
+            // Day of Goodwill (since 2000)
+            || (d == 26 && m == Month::December && y >= 2000)
+
+            // December 31st, 1998, 1999 and 2001 only
+            || (d == 31 && m == Month::December && (y == 1998 || y == 1999 || y == 2001))
+        ) {
+            return true;
+        }
+
+        false
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod test_financial_calendars {
+    use super::*;
+    use time::macros::date;
+
+    #[test]
+    fn test_weekend_calendar() {
+        let calendar = WeekendCalendar;
+
+        assert!(calendar.is_business_day(date!(2024 - 12 - 25)));
+        assert!(!calendar.is_business_day(date!(2024 - 12 - 28)));
+        assert!(!calendar.is_business_day(date!(2024 - 12 - 29)));
+        assert_eq!(calendar.market_identifier_code(), XXXX);
+    }
+
+    #[test]
+    fn test_target_holidays() {
+        let calendar = TargetCalendar;
+
+        let holidays = calendar.all_holidays_between(date!(2024 - 01 - 01), date!(2024 - 12 - 31));
+
+        assert_eq!(
+            holidays,
+            vec![
+                date!(2024 - 01 - 01),
+                date!(2024 - 03 - 29),
+                date!(2024 - 04 - 01),
+                date!(2024 - 05 - 01),
+                date!(2024 - 12 - 25),
+                date!(2024 - 12 - 26),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_target_before_2000() {
+        let calendar = TargetCalendar;
+
+        // Good Friday 1999 was a TARGET day, but not New Year's Eve.
+        assert!(calendar.is_business_day(date!(1999 - 04 - 02)));
+        assert!(!calendar.is_business_day(date!(1999 - 12 - 31)));
+        assert!(calendar.is_business_day(date!(2002 - 12 - 31)));
+    }
+}
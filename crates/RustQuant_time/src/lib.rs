@@ -23,6 +23,11 @@ pub use constants::*;
 pub mod countries;
 pub use countries::*;
 
+/// Weekend-only and TARGET calendars.
+#[allow(unused_parens)]
+pub mod financial_calendars;
+pub use financial_calendars::*;
+
 /// Date rolling conventions and methods.
 pub mod date_rolling;
 pub use date_rolling::*;
@@ -54,3 +59,7 @@ pub use date_generation::*;
 /// Stub generation rules.
 pub mod stub_generation;
 pub use stub_generation::*;
+
+/// Tenors, for advancing dates by days, weeks, months or years.
+pub mod tenor;
+pub use tenor::*;
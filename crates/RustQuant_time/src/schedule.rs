@@ -9,9 +9,13 @@
 
 use crate::date_rolling::{DateRoller, DateRollingConvention};
 use crate::day_counting::{DayCountConvention, DayCounter};
-use crate::Calendar;
+use crate::stub_generation::StubGeneration;
+use crate::tenor::{Tenor, TenorUnit};
+use crate::utilities::is_last_day_of_month;
+use crate::{Calendar, Frequency};
 use std::fmt;
 use time::Date;
+use RustQuant_error::RustQuantError;
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // STRUCTS, ENUMS, AND TRAITS
@@ -28,10 +32,13 @@ use time::Date;
 /// and pricing methods should be implemented using date/time functionality.
 #[derive(Clone, Debug)]
 pub struct Schedule {
-    /// The dates of the schedule.
+    /// The start of the first period.
+    pub effective_date: Date,
+
+    /// The dates of the schedule, which end each period.
     pub dates: Vec<Date>,
 
-    /// The day count factors of the schedule.
+    /// The day count factors of each period.
     pub day_count_factors: Vec<f64>,
 
     /// The convention of the schedule.
@@ -62,6 +69,29 @@ pub trait Scheduler {
     ) -> Schedule;
 }
 
+/// Builder for a schedule of periods between an effective date and a
+/// maturity date.
+///
+/// Unadjusted dates are generated a regular tenor apart, backward from the
+/// maturity date for front stubs and forward from the effective date for
+/// back stubs. All dates, including the effective and maturity dates, are
+/// then rolled to business days on the calendar.
+pub struct ScheduleBuilder<C>
+where
+    C: Calendar,
+{
+    effective_date: Date,
+    maturity_date: Date,
+    frequency: Frequency,
+    calendar: C,
+    stub_generation: StubGeneration,
+    date_rolling_convention: DateRollingConvention,
+    day_counting_convention: DayCountConvention,
+    end_of_month: bool,
+    first_date: Option<Date>,
+    next_to_last_date: Option<Date>,
+}
+
 impl<C> Scheduler for C
 where
     C: Calendar,
@@ -85,6 +115,7 @@ where
         );
 
         Schedule {
+            effective_date: today,
             dates: rolled_dates,
             day_count_factors,
             day_counting_convention,
@@ -97,10 +128,12 @@ impl fmt::Display for Schedule {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
-            "Dates:                     {:?}\n\
+            "Effective Date:            {}\n\
+            Dates:                      {:?}\n\
             Day Count Factors:          {:?}\n\
             Day Counting Convention:    {}\n\
             Date Rolling Convention:    {}",
+            self.effective_date,
             self.dates,
             self.day_count_factors,
             self.day_counting_convention,
@@ -113,182 +146,436 @@ impl fmt::Display for Schedule {
 // IMPLEMENTATIONS
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-// impl Schedule {
-//     /// Create a new schedule from a vector of dates.
-//     ///
-//     /// The list can be in any order, and the schedule will be
-//     /// sorted in chronological order.
-//     pub fn new(mut dates: &[Date]) -> Schedule {
-//         dates.sort();
-
-//         Schedule {
-//             dates: dates.to_vec(),
-//             start: Some(dates[0]),
-//             end: Some(dates[dates.len() - 1]),
-//             frequency: None,
-//             day_count_convention: DayCountConvention::default(),
-//             date_rolling_convention: DateRollingConvention::default(),
-//         }
-//     }
-
-//     /// Creates a new schedule from a given start date, period length, and number
-//     /// of periods in the schedule.
-//     pub fn new_from_start(start: Date, period: Duration, num_periods: i64) -> Schedule {
-//         let mut payments = Vec::with_capacity(num_periods as usize + 1);
-//         let mut current_time = start;
-
-//         for _ in 0..=num_periods {
-//             payments.push(current_time);
-//             current_time += period;
-//         }
-
-//         Schedule {
-//             dates: payments,
-//             start: Some(start),
-//             end: Some(current_time),
-//             frequency: None,
-//             day_count_convention: DayCountConvention::Actual365,
-//             date_rolling_convention: DateRollingConvention::Following,
-//         }
-//     }
-
-//     /// Creates a new schedule from a given end date, period length, and number
-//     /// of periods in the schedule.
-//     pub fn new_from_end(end: Date, period: Duration, num_periods: i64) -> Schedule {
-//         let mut payments = Vec::with_capacity(num_periods as usize + 1);
-
-//         let mut current_time = end;
-
-//         for _ in 0..=num_periods {
-//             payments.push(current_time);
-//             current_time -= period;
-//         }
-
-//         payments.reverse();
-
-//         Schedule {
-//             dates: payments,
-//             start: Some(current_time),
-//             end: Some(end),
-//             frequency: None,
-//             day_count_convention: DayCountConvention::Actual365,
-//             date_rolling_convention: DateRollingConvention::Following,
-//         }
-//     }
-
-//     /// Creates a new schedule from a vector of dates.
-//     /// Dates must be in ascending order.
-//     pub fn new_from_dates(dates: &&[Date]) -> Schedule {
-//         assert!(&dates.windows(2).all(|window| window[0] < window[1]));
-
-//         Schedule {
-//             dates: dates.clone(),
-//             start: Some(dates[0]),
-//             end: Some(dates[dates.len() - 1]),
-//             frequency: None,
-//             day_count_convention: DayCountConvention::Actual365,
-//             date_rolling_convention: DateRollingConvention::Following,
-//         }
-//     }
-
-//     /// Drops a given date from the schedule.
-//     pub fn drop(&mut self, date: Date) {
-//         // let date = date.midnight_at(UtcOffset::UTC); // Convert to Date for comparison
-//         self.dates.retain(|&payment| payment.date() != date.date());
-//     }
-// }
-
-// // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
-// // UNIT TESTS
-// // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
-
-// #[cfg(test)]
-// mod test_schedule {
-//     use super::*;
-//     use time::macros::datetime;
-
-//     #[test]
-//     fn test_new_from_start() {
-//         let start = datetime!(2023-06-01 0:0:0 UTC);
-//         let period = Duration::days(30);
-//         let num_periods = 3;
-//         let schedule = Schedule::new_from_start(start, period, num_periods);
-//         assert_eq!(schedule.dates.len(), num_periods as usize + 1);
-//         assert_eq!(
-//             schedule.dates,
-//             vec![
-//                 datetime!(2023-06-01 0:0:0 UTC),
-//                 datetime!(2023-07-01 0:0:0 UTC),
-//                 datetime!(2023-07-31 0:0:0 UTC),
-//                 datetime!(2023-08-30 0:0:0 UTC),
-//             ],
-//         );
-//         for i in 0..num_periods {
-//             assert_eq!(schedule.dates[i as usize], start + period * i as i32);
-//         }
-//     }
-
-//     #[test]
-//     fn test_new_from_end() {
-//         let end = datetime!(2023-08-01 0:0:0 UTC);
-//         let period = Duration::days(30);
-//         let num_periods = 3;
-//         let schedule = Schedule::new_from_end(end, period, num_periods);
-//         // Length is num_periods + 1 because the end date is included.
-//         // Fencepost problem.
-//         assert_eq!(schedule.dates.len(), num_periods as usize + 1);
-//         assert_eq!(
-//             schedule.dates,
-//             vec![
-//                 datetime!(2023-05-03 0:0:0 UTC),
-//                 datetime!(2023-06-02 0:0:0 UTC),
-//                 datetime!(2023-07-02 0:0:0 UTC),
-//                 datetime!(2023-08-01 0:0:0 UTC),
-//             ],
-//         );
-//         // for i in 0..num_periods {
-//         //     assert_eq!(
-//         //         schedule.dates[(num_periods - 1 - i) as usize],
-//         //         end - period * i as i32
-//         //     );
-//         // }
-//     }
-
-//     #[test]
-//     fn test_new_from_dates() {
-//         let dates = vec![
-//             datetime!(2023-06-01 0:0:0 UTC),
-//             datetime!(2023-07-01 0:0:0 UTC),
-//             datetime!(2023-08-01 0:0:0 UTC),
-//         ];
-//         let schedule = Schedule::new_from_dates(&dates);
-//         assert_eq!(schedule.dates, dates);
-//     }
-
-//     #[test]
-//     #[should_panic(expected = "assertion failed")]
-//     fn test_new_from_dates_out_of_order() {
-//         let dates = vec![
-//             datetime!(2023-07-01 0:0:0 UTC),
-//             datetime!(2023-06-01 0:0:0 UTC),
-//         ];
-//         let _ = Schedule::new_from_dates(&dates);
-//     }
-
-//     #[test]
-//     fn test_drop() {
-//         let mut schedule =
-//             Schedule::new_from_start(datetime!(2023-06-01 0:0:0 UTC), Duration::days(30), 3);
-//         schedule.drop(datetime!(2023-07-01 0:0:0 UTC));
-//         assert_eq!(schedule.dates.len(), 3);
-//         assert_eq!(
-//             schedule.dates,
-//             vec![
-//                 datetime!(2023-06-01 0:0:0 UTC),
-//                 // datetime!(2023-07-01 0:0:0 UTC), // Dropped
-//                 datetime!(2023-07-31 0:0:0 UTC),
-//                 datetime!(2023-08-30 0:0:0 UTC),
-//             ]
-//         );
-//     }
-// }
+impl Schedule {
+    /// Start building a schedule of periods from `effective_date` to
+    /// `maturity_date`, paid at `frequency`, on `calendar`.
+    pub fn builder<C: Calendar>(
+        effective_date: Date,
+        maturity_date: Date,
+        frequency: Frequency,
+        calendar: C,
+    ) -> ScheduleBuilder<C> {
+        ScheduleBuilder::new(effective_date, maturity_date, frequency, calendar)
+    }
+
+    /// The periods of the schedule, as start and end dates.
+    pub fn periods(&self) -> Vec<(Date, Date)> {
+        std::iter::once(self.effective_date)
+            .chain(self.dates.iter().copied())
+            .zip(&self.dates)
+            .map(|(start, &end)| (start, end))
+            .collect()
+    }
+}
+
+impl<C> ScheduleBuilder<C>
+where
+    C: Calendar,
+{
+    /// Create a new schedule builder.
+    ///
+    /// By default the schedule has a short front stub, dates are not
+    /// rolled, and the day count is the default convention.
+    pub fn new(
+        effective_date: Date,
+        maturity_date: Date,
+        frequency: Frequency,
+        calendar: C,
+    ) -> Self {
+        Self {
+            effective_date,
+            maturity_date,
+            frequency,
+            calendar,
+            stub_generation: StubGeneration::ShortFront,
+            date_rolling_convention: DateRollingConvention::default(),
+            day_counting_convention: DayCountConvention::default(),
+            end_of_month: false,
+            first_date: None,
+            next_to_last_date: None,
+        }
+    }
+
+    /// Set the stub generation rule.
+    pub fn with_stub_generation(mut self, stub_generation: StubGeneration) -> Self {
+        self.stub_generation = stub_generation;
+        self
+    }
+
+    /// Set the convention that rolls dates to business days.
+    pub fn with_date_rolling_convention(
+        mut self,
+        date_rolling_convention: DateRollingConvention,
+    ) -> Self {
+        self.date_rolling_convention = date_rolling_convention;
+        self
+    }
+
+    /// Set the day count convention of the periods.
+    pub fn with_day_counting_convention(
+        mut self,
+        day_counting_convention: DayCountConvention,
+    ) -> Self {
+        self.day_counting_convention = day_counting_convention;
+        self
+    }
+
+    /// Keep dates at month ends, if the date they are generated from
+    /// (the maturity date for front stubs, otherwise the effective date)
+    /// is the last day of its month.
+    pub fn with_end_of_month(mut self, end_of_month: bool) -> Self {
+        self.end_of_month = end_of_month;
+        self
+    }
+
+    /// Set the first and next-to-last dates, between which the periods are
+    /// regular, for `StubGeneration::Both`.
+    pub fn with_regular_dates(mut self, first_date: Date, next_to_last_date: Date) -> Self {
+        self.first_date = Some(first_date);
+        self.next_to_last_date = Some(next_to_last_date);
+        self
+    }
+
+    /// Generate the schedule.
+    ///
+    /// # Errors
+    /// - `RustQuantError::InvalidArgument` if the maturity date is not
+    ///   after the effective date, the frequency is not a whole number of
+    ///   business days, weeks or months, the dates do not divide into whole
+    ///   periods with `StubGeneration::None`, or the regular dates for
+    ///   `StubGeneration::Both` are missing or are not whole periods apart.
+    pub fn build(&self) -> Result<Schedule, RustQuantError> {
+        if self.maturity_date <= self.effective_date {
+            return Err(RustQuantError::InvalidArgument(
+                "The maturity date must be after the effective date.".to_string(),
+            ));
+        }
+
+        let unadjusted = match (self.frequency, self.frequency.tenor()) {
+            (Frequency::Zero, _) => vec![self.effective_date, self.maturity_date],
+            (_, Some(tenor)) => self.unadjusted_dates(tenor)?,
+            (_, None) => {
+                return Err(RustQuantError::InvalidArgument(
+                    "The frequency must be a whole number of business days, weeks or months."
+                        .to_string(),
+                ))
+            }
+        };
+
+        let mut dates = self
+            .calendar
+            .roll_dates(&unadjusted, &self.date_rolling_convention);
+        dates.dedup();
+
+        let effective_date = dates.remove(0);
+        let day_count_factors = std::iter::once(effective_date)
+            .chain(dates.iter().copied())
+            .zip(&dates)
+            .map(|(start, &end)| self.day_counting_convention.day_count_factor(start, end))
+            .collect();
+
+        Ok(Schedule {
+            effective_date,
+            dates,
+            day_count_factors,
+            day_counting_convention: self.day_counting_convention,
+            date_rolling_convention: self.date_rolling_convention,
+        })
+    }
+
+    /// Unadjusted dates from the effective date to the maturity date.
+    fn unadjusted_dates(&self, tenor: Tenor) -> Result<Vec<Date>, RustQuantError> {
+        let (effective, maturity) = (self.effective_date, self.maturity_date);
+
+        let dates = match self.stub_generation {
+            StubGeneration::None | StubGeneration::ShortFront | StubGeneration::LongFront => {
+                let (mut inner, exact) = self.regular_dates(maturity, -tenor, effective);
+                inner.reverse();
+
+                match self.stub_generation {
+                    StubGeneration::None if !exact => {
+                        return Err(RustQuantError::InvalidArgument(
+                            "The dates do not divide into whole periods.".to_string(),
+                        ))
+                    }
+                    StubGeneration::LongFront if !exact && !inner.is_empty() => {
+                        inner.remove(0);
+                    }
+                    _ => {}
+                }
+
+                [vec![effective], inner, vec![maturity]].concat()
+            }
+            StubGeneration::ShortBack | StubGeneration::LongBack => {
+                let (mut inner, exact) = self.regular_dates(effective, tenor, maturity);
+
+                if self.stub_generation == StubGeneration::LongBack && !exact {
+                    inner.pop();
+                }
+
+                [vec![effective], inner, vec![maturity]].concat()
+            }
+            StubGeneration::Both => {
+                let (Some(first), Some(next_to_last)) = (self.first_date, self.next_to_last_date)
+                else {
+                    return Err(RustQuantError::InvalidArgument(
+                        "Front and back stubs need the first and next-to-last dates.".to_string(),
+                    ));
+                };
+
+                if !(effective <= first && first <= next_to_last && next_to_last <= maturity) {
+                    return Err(RustQuantError::InvalidArgument(
+                        "The first and next-to-last dates must be in order, within the schedule."
+                            .to_string(),
+                    ));
+                }
+
+                let (inner, exact) = self.regular_dates(first, tenor, next_to_last);
+
+                if !exact && first != next_to_last {
+                    return Err(RustQuantError::InvalidArgument(
+                        "The first and next-to-last dates must be whole periods apart.".to_string(),
+                    ));
+                }
+
+                let mut dates =
+                    [vec![effective, first], inner, vec![next_to_last, maturity]].concat();
+                dates.dedup();
+
+                dates
+            }
+        };
+
+        Ok(dates)
+    }
+
+    /// Dates a whole number of tenors from `anchor`, strictly before
+    /// `bound` (in the direction of the tenor), and whether the next one
+    /// lands exactly on `bound`.
+    fn regular_dates(&self, anchor: Date, tenor: Tenor, bound: Date) -> (Vec<Date>, bool) {
+        let forward = tenor.length > 0;
+        let mut dates = Vec::new();
+
+        for k in 1.. {
+            let date = self.shift(anchor, tenor * k);
+
+            if (forward && date >= bound) || (!forward && date <= bound) {
+                return (dates, date == bound);
+            }
+
+            dates.push(date);
+        }
+
+        unreachable!("The dates pass the bound.")
+    }
+
+    /// Unadjusted date a tenor from `anchor`, kept at the month end if
+    /// required.
+    fn shift(&self, anchor: Date, tenor: Tenor) -> Date {
+        let date = self
+            .calendar
+            .advance(anchor, tenor, &DateRollingConvention::Actual, false);
+
+        let monthly = matches!(tenor.unit, TenorUnit::Months | TenorUnit::Years);
+
+        match self.end_of_month && monthly && is_last_day_of_month(anchor) {
+            true => date
+                .replace_day(date.month().length(date.year()))
+                .expect("The month has this many days."),
+            false => date,
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod test_schedule {
+    use super::*;
+    use crate::{TargetCalendar, WeekendCalendar};
+    use time::macros::date;
+    use RustQuant_utils::{assert_approx_equal, RUSTQUANT_EPSILON};
+
+    fn stub_schedule(stub_generation: StubGeneration) -> Result<Schedule, RustQuantError> {
+        Schedule::builder(
+            date!(2024 - 03 - 01),
+            date!(2026 - 01 - 15),
+            Frequency::SemiAnnually,
+            WeekendCalendar,
+        )
+        .with_stub_generation(stub_generation)
+        .build()
+    }
+
+    #[test]
+    fn test_regular_schedule() {
+        let schedule = Schedule::builder(
+            date!(2024 - 01 - 15),
+            date!(2026 - 01 - 15),
+            Frequency::SemiAnnually,
+            WeekendCalendar,
+        )
+        .with_stub_generation(StubGeneration::None)
+        .with_day_counting_convention(DayCountConvention::Thirty_360_ISDA)
+        .build()
+        .unwrap();
+
+        assert_eq!(schedule.effective_date, date!(2024 - 01 - 15));
+        assert_eq!(
+            schedule.dates,
+            vec![
+                date!(2024 - 07 - 15),
+                date!(2025 - 01 - 15),
+                date!(2025 - 07 - 15),
+                date!(2026 - 01 - 15),
+            ]
+        );
+
+        for factor in &schedule.day_count_factors {
+            assert_approx_equal!(*factor, 0.5, RUSTQUANT_EPSILON);
+        }
+
+        assert_eq!(
+            schedule.periods()[1],
+            (date!(2024 - 07 - 15), date!(2025 - 01 - 15))
+        );
+    }
+
+    #[test]
+    fn test_stubs() {
+        assert_eq!(
+            stub_schedule(StubGeneration::ShortFront).unwrap().dates,
+            vec![
+                date!(2024 - 07 - 15),
+                date!(2025 - 01 - 15),
+                date!(2025 - 07 - 15),
+                date!(2026 - 01 - 15),
+            ]
+        );
+        assert_eq!(
+            stub_schedule(StubGeneration::LongFront).unwrap().dates,
+            vec![
+                date!(2025 - 01 - 15),
+                date!(2025 - 07 - 15),
+                date!(2026 - 01 - 15),
+            ]
+        );
+        assert_eq!(
+            stub_schedule(StubGeneration::ShortBack).unwrap().dates,
+            vec![
+                date!(2024 - 09 - 01),
+                date!(2025 - 03 - 01),
+                date!(2025 - 09 - 01),
+                date!(2026 - 01 - 15),
+            ]
+        );
+        assert_eq!(
+            stub_schedule(StubGeneration::LongBack).unwrap().dates,
+            vec![
+                date!(2024 - 09 - 01),
+                date!(2025 - 03 - 01),
+                date!(2026 - 01 - 15),
+            ]
+        );
+        assert!(stub_schedule(StubGeneration::None).is_err());
+    }
+
+    #[test]
+    fn test_front_and_back_stubs() {
+        let schedule = Schedule::builder(
+            date!(2024 - 01 - 10),
+            date!(2025 - 05 - 01),
+            Frequency::Quarterly,
+            WeekendCalendar,
+        )
+        .with_stub_generation(StubGeneration::Both)
+        .with_regular_dates(date!(2024 - 03 - 15), date!(2025 - 03 - 15))
+        .build()
+        .unwrap();
+
+        assert_eq!(schedule.effective_date, date!(2024 - 01 - 10));
+        assert_eq!(
+            schedule.dates,
+            vec![
+                date!(2024 - 03 - 15),
+                date!(2024 - 06 - 15),
+                date!(2024 - 09 - 15),
+                date!(2024 - 12 - 15),
+                date!(2025 - 03 - 15),
+                date!(2025 - 05 - 01),
+            ]
+        );
+
+        let missing = Schedule::builder(
+            date!(2024 - 01 - 10),
+            date!(2025 - 05 - 01),
+            Frequency::Quarterly,
+            WeekendCalendar,
+        )
+        .with_stub_generation(StubGeneration::Both);
+
+        assert!(missing.build().is_err());
+    }
+
+    #[test]
+    fn test_rolled_end_of_month_schedule() {
+        let builder = Schedule::builder(
+            date!(2024 - 02 - 29),
+            date!(2025 - 02 - 28),
+            Frequency::Quarterly,
+            TargetCalendar,
+        )
+        .with_date_rolling_convention(DateRollingConvention::ModifiedFollowing);
+
+        assert_eq!(
+            builder.build().unwrap().dates,
+            vec![
+                date!(2024 - 05 - 28),
+                date!(2024 - 08 - 28),
+                date!(2024 - 11 - 28),
+                date!(2025 - 02 - 28),
+            ]
+        );
+
+        // Month ends, rolled back within the month from weekends.
+        assert_eq!(
+            builder.with_end_of_month(true).build().unwrap().dates,
+            vec![
+                date!(2024 - 05 - 31),
+                date!(2024 - 08 - 30),
+                date!(2024 - 11 - 29),
+                date!(2025 - 02 - 28),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invalid_schedule() {
+        let build = |frequency| {
+            Schedule::builder(
+                date!(2024 - 01 - 15),
+                date!(2025 - 01 - 15),
+                frequency,
+                WeekendCalendar,
+            )
+            .build()
+        };
+
+        assert!(build(Frequency::SemiMonthly).is_err());
+        assert_eq!(
+            build(Frequency::Zero).unwrap().dates,
+            vec![date!(2025 - 01 - 15)]
+        );
+
+        assert!(Schedule::builder(
+            date!(2025 - 01 - 15),
+            date!(2024 - 01 - 15),
+            Frequency::Annually,
+            WeekendCalendar,
+        )
+        .build()
+        .is_err());
+    }
+}
@@ -8,6 +8,7 @@
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
 /// Stub generation rules.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum StubGeneration {
     /// No stubs.
     None,
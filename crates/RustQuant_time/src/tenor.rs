@@ -0,0 +1,160 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023-2024 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Tenors: lengths of time such as 2D, 3M or 10Y.
+
+use crate::Frequency;
+use std::fmt;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Unit of a tenor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TenorUnit {
+    /// Calendar days.
+    Days,
+
+    /// Business days, counted on a calendar.
+    BusinessDays,
+
+    /// Weeks.
+    Weeks,
+
+    /// Calendar months.
+    Months,
+
+    /// Years.
+    Years,
+}
+
+/// A length of time, as a (possibly negative) number of units.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Tenor {
+    /// Number of units.
+    pub length: i32,
+
+    /// Unit of the tenor.
+    pub unit: TenorUnit,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS, METHODS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl Tenor {
+    /// Create a new tenor.
+    #[must_use]
+    pub const fn new(length: i32, unit: TenorUnit) -> Self {
+        Self { length, unit }
+    }
+
+    /// Tenor of `length` calendar days.
+    #[must_use]
+    pub const fn days(length: i32) -> Self {
+        Self::new(length, TenorUnit::Days)
+    }
+
+    /// Tenor of `length` business days.
+    #[must_use]
+    pub const fn business_days(length: i32) -> Self {
+        Self::new(length, TenorUnit::BusinessDays)
+    }
+
+    /// Tenor of `length` weeks.
+    #[must_use]
+    pub const fn weeks(length: i32) -> Self {
+        Self::new(length, TenorUnit::Weeks)
+    }
+
+    /// Tenor of `length` months.
+    #[must_use]
+    pub const fn months(length: i32) -> Self {
+        Self::new(length, TenorUnit::Months)
+    }
+
+    /// Tenor of `length` years.
+    #[must_use]
+    pub const fn years(length: i32) -> Self {
+        Self::new(length, TenorUnit::Years)
+    }
+}
+
+impl std::ops::Neg for Tenor {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.length, self.unit)
+    }
+}
+
+impl std::ops::Mul<i32> for Tenor {
+    type Output = Self;
+
+    fn mul(self, n: i32) -> Self {
+        Self::new(self.length * n, self.unit)
+    }
+}
+
+impl fmt::Display for Tenor {
+    #[rustfmt::skip]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.unit {
+            TenorUnit::Days             => write!(f, "{}D", self.length),
+            TenorUnit::BusinessDays     => write!(f, "{}BD", self.length),
+            TenorUnit::Weeks            => write!(f, "{}W", self.length),
+            TenorUnit::Months           => write!(f, "{}M", self.length),
+            TenorUnit::Years            => write!(f, "{}Y", self.length),
+        }
+    }
+}
+
+impl Frequency {
+    /// The tenor between payments at this frequency, if it is a whole
+    /// number of business days, weeks or months.
+    #[rustfmt::skip]
+    pub fn tenor(&self) -> Option<Tenor> {
+        match self {
+            Self::Daily         => Some(Tenor::business_days(1)),
+            Self::Weekly        => Some(Tenor::weeks(1)),
+            Self::BiWeekly      => Some(Tenor::weeks(2)),
+            Self::Monthly       => Some(Tenor::months(1)),
+            Self::Quarterly     => Some(Tenor::months(3)),
+            Self::TriAnnually   => Some(Tenor::months(4)),
+            Self::SemiAnnually  => Some(Tenor::months(6)),
+            Self::Annually      => Some(Tenor::years(1)),
+            Self::SemiMonthly | Self::SemiQuarterly | Self::Zero => None,
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod test_tenor {
+    use super::*;
+
+    #[test]
+    fn test_tenor_display() {
+        assert_eq!(Tenor::business_days(2).to_string(), "2BD");
+        assert_eq!(Tenor::months(3).to_string(), "3M");
+        assert_eq!((-Tenor::years(10)).to_string(), "-10Y");
+        assert_eq!((Tenor::weeks(1) * 2).to_string(), "2W");
+    }
+
+    #[test]
+    fn test_frequency_tenor() {
+        assert_eq!(Frequency::Quarterly.tenor(), Some(Tenor::months(3)));
+        assert_eq!(Frequency::Annually.tenor(), Some(Tenor::years(1)));
+        assert_eq!(Frequency::SemiQuarterly.tenor(), None);
+    }
+}
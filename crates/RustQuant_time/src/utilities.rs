@@ -9,7 +9,7 @@
 
 //! This module defines general calendar and holiday related functions.
 
-use crate::{calendar::Calendar, constants::EASTER_MONDAYS};
+use crate::{calendar::Calendar, constants::EASTER_MONDAYS, DayCountConvention};
 use time::{
     util::{days_in_year, days_in_year_month, is_leap_year},
     Date, Duration, Error, Month, Weekday,
@@ -47,11 +47,13 @@ fn easter_monday(year: usize, is_orthodox: bool) -> u16 {
 
 /// Compute a default year fraction between two dates.
 ///
-/// The default year fraction is computed as the
+/// The default year fraction uses the Act/365.25 day count: the
 /// actual number of days between two dates divided by 365.25.
-/// This attempts to account for leap years.
+/// This attempts to account for leap years, and is the time to expiry
+/// used by the option pricers. Use [`DayCountConvention::day_count_factor`]
+/// for accruals and discounting under a market convention.
 pub fn year_fraction(start: Date, end: Date) -> f64 {
-    days_between(start, end) as f64 / 365.25
+    DayCountConvention::Actual_365_25.day_count_factor(start, end)
 }
 
 /// Checks if date is a weekend.
@@ -154,6 +156,17 @@ pub fn is_last_day_of_february(date: Date) -> bool {
     last_day_of_feb_non_leap || last_day_of_feb_leap
 }
 
+/// Add a number of months to a date, keeping the day of the month where
+/// possible and otherwise using the last day of the month.
+pub fn add_months(date: Date, months: i32) -> Date {
+    let total = date.year() * 12 + date.month() as i32 - 1 + months;
+    let year = total.div_euclid(12);
+    let month = Month::try_from(total.rem_euclid(12) as u8 + 1).expect("Month is in 1..=12.");
+    let day = date.day().min(month.length(year));
+
+    Date::from_calendar_date(year, month, day).expect("Day is within the month.")
+}
+
 /// Function to get the next business day for a given date and calendar.
 pub fn next_business_day<C: Calendar>(date: Date, calendar: &C) -> Date {
     let mut new_date = date;